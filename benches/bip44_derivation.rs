@@ -0,0 +1,50 @@
+//! BIP44 address derivation benchmarks
+//!
+//! Compares full-path derivation from the master key against
+//! `Bip44Wallet`, which caches the account and chain keys.
+
+use blvm_sdk::governance::bip32::derive_master_key;
+use blvm_sdk::governance::bip44::{Bip44Path, Bip44Wallet, ChangeChain, CoinType};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+
+const SEED: [u8; 32] = [0x42; 32];
+const ADDRESSES: u32 = 100;
+
+fn bench_bip44_derivation(c: &mut Criterion) {
+    let (master, _) = derive_master_key(&SEED).unwrap();
+    let mut group = c.benchmark_group("bip44_100_addresses");
+
+    group.bench_function("full_path", |b| {
+        b.iter(|| {
+            for index in 0..ADDRESSES {
+                let path = Bip44Path::new(CoinType::Bitcoin, 0, ChangeChain::External, index);
+                black_box(path.derive(&master).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("cached_wallet", |b| {
+        b.iter(|| {
+            let wallet = Bip44Wallet::from_master_key(master.clone(), CoinType::Bitcoin);
+            for index in 0..ADDRESSES {
+                black_box(wallet.receiving_address(0, index).unwrap());
+            }
+        })
+    });
+
+    group.bench_function("derive_addresses", |b| {
+        b.iter(|| {
+            let wallet = Bip44Wallet::from_master_key(master.clone(), CoinType::Bitcoin);
+            black_box(
+                wallet
+                    .derive_addresses(0, ChangeChain::External, 0..ADDRESSES)
+                    .unwrap(),
+            );
+        })
+    });
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_bip44_derivation);
+criterion_main!(benches);
@@ -0,0 +1,49 @@
+//! Build script
+//!
+//! With the `ffi` feature, generates the C header `bllvm.h` from
+//! `src/ffi.rs` with cbindgen.
+
+fn main() {
+    println!("cargo:rerun-if-changed=build.rs");
+
+    #[cfg(feature = "ffi")]
+    generate_ffi_header();
+}
+
+/// Write `bllvm.h` to `$OUT_DIR` and next to the built libraries
+#[cfg(feature = "ffi")]
+fn generate_ffi_header() {
+    use std::env;
+    use std::fs;
+    use std::path::PathBuf;
+
+    println!("cargo:rerun-if-changed=src/ffi.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml"))
+        .expect("Failed to read cbindgen.toml");
+
+    let header = out_dir.join("bllvm.h");
+    cbindgen::Builder::new()
+        .with_config(config)
+        .with_src(crate_dir.join("src/ffi.rs"))
+        .generate()
+        .expect("Failed to generate bllvm.h")
+        .write_to_file(&header);
+
+    // $OUT_DIR is target/<profile>/build/<pkg>-<hash>/out
+    if let Some(profile_dir) = out_dir.ancestors().nth(3) {
+        let include_dir = profile_dir.join("include");
+        if fs::create_dir_all(&include_dir).is_ok() {
+            let _ = fs::copy(&header, include_dir.join("bllvm.h"));
+        }
+    }
+
+    // Lets the FFI tests compile their C driver for the same target
+    println!(
+        "cargo:rustc-env=BLLVM_FFI_TARGET={}",
+        env::var("TARGET").unwrap()
+    );
+}
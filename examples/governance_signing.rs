@@ -4,6 +4,7 @@
 
 use bllvm_sdk::governance::verify_signature;
 use bllvm_sdk::{sign_message, GovernanceKeypair, GovernanceMessage};
+use std::collections::HashMap;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Governance Signing Example ===\n");
@@ -23,6 +24,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     println!("   Message: {}", message.description());
     println!(
@@ -52,6 +54,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let different_message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "def456ghi789".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     let verified_different = verify_signature(
         &signature,
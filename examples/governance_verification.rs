@@ -4,6 +4,7 @@
 
 use bllvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Multisig};
 use bllvm_sdk::{sign_message, verify_signature};
+use std::collections::HashMap;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Governance Verification Example ===\n");
@@ -35,6 +36,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     println!("   Message: {}", message.description());
     println!();
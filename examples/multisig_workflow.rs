@@ -4,6 +4,7 @@
 
 use bllvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Multisig};
 use bllvm_sdk::{sign_message, verify_signature};
+use std::collections::HashMap;
 
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("=== Multisig Workflow Example ===\n");
@@ -36,15 +37,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
         GovernanceMessage::Release {
             version: "v1.0.0".to_string(),
             commit_hash: "abc123def456".to_string(),
+            artifact_hashes: HashMap::new(),
         },
-        GovernanceMessage::ModuleApproval {
-            module_name: "lightning-network".to_string(),
-            version: "v2.0.0".to_string(),
-        },
-        GovernanceMessage::BudgetDecision {
-            amount: 1000000,
-            purpose: "development and maintenance".to_string(),
-        },
+        GovernanceMessage::module_approval("lightning-network", "v2.0.0"),
+        GovernanceMessage::single_budget("bc1qdevfund", 1000000, "development and maintenance"),
     ];
 
     for (i, message) in messages.iter().enumerate() {
@@ -87,6 +83,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let wrong_message = GovernanceMessage::Release {
         version: "v2.0.0".to_string(),
         commit_hash: "def456ghi789".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     for kp in &keypairs[4..6] {
         let sig = sign_message(&kp.secret_key, &wrong_message.to_signing_bytes()).unwrap();
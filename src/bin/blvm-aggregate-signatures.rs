@@ -5,7 +5,9 @@
 //! This tool collects signatures from multiple maintainers and creates a
 //! single signature file that can be verified against a multisig threshold.
 
-use blvm_sdk::cli::input::parse_comma_separated;
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::input::{parse_comma_separated, parse_threshold, InputError};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
 use clap::Parser;
 use serde_json::Value;
@@ -16,12 +18,13 @@ use std::path::Path;
 #[derive(Parser, Debug)]
 #[command(name = "blvm-aggregate-signatures")]
 #[command(about = "Aggregate multiple signatures into a single multisig signature file")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
     /// Output file for aggregated signatures
     #[arg(short, long, default_value = "signatures.json")]
     output: String,
 
-    /// Output format (text, json)
+    /// Output format (text, json, ndjson)
     #[arg(short, long, default_value = "text")]
     format: OutputFormat,
 
@@ -29,7 +32,7 @@ struct Args {
     #[arg(short, long, required = true)]
     signatures: String,
 
-    /// Threshold (e.g., "6-of-7")
+    /// Threshold as M-of-N, M/N, M:N or M,N (e.g., "6-of-7")
     #[arg(short, long)]
     threshold: Option<String>,
 
@@ -39,17 +42,17 @@ struct Args {
 }
 
 fn main() {
-    let args = Args::parse();
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
     let formatter = OutputFormatter::new(args.format.clone());
 
-    match aggregate_signatures(&args) {
+    match aggregate_signatures(&args, &formatter) {
         Ok(result) => {
             let output = format_aggregation_output(&result, &args, &formatter);
             println!("{}", output);
         }
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
@@ -62,7 +65,16 @@ struct AggregationResult {
     signatures: Vec<Value>,
 }
 
-fn aggregate_signatures(args: &Args) -> Result<AggregationResult, Box<dyn std::error::Error>> {
+fn aggregate_signatures(
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> Result<AggregationResult, Box<dyn std::error::Error>> {
+    // Validate threshold before writing anything
+    let required = match &args.threshold {
+        Some(threshold_str) => Some(parse_threshold(threshold_str)?.0),
+        None => None,
+    };
+
     // Parse signature files
     let signature_files = parse_comma_separated(&args.signatures);
     let mut signatures = Vec::new();
@@ -70,7 +82,7 @@ fn aggregate_signatures(args: &Args) -> Result<AggregationResult, Box<dyn std::e
 
     for file_path in &signature_files {
         if !Path::new(file_path).exists() {
-            return Err(format!("Signature file not found: {}", file_path).into());
+            return Err(InputError::FileNotFound(file_path.to_string()).into());
         }
 
         let sig_data = fs::read_to_string(file_path)?;
@@ -84,6 +96,11 @@ fn aggregate_signatures(args: &Args) -> Result<AggregationResult, Box<dyn std::e
             "public_key": sig_json.get("public_key"),
         });
 
+        formatter.emit(&serde_json::json!({
+            "record": "signature",
+            "file": file_path,
+            "signer": signature_entry["signer"],
+        }))?;
         signatures.push(signature_entry);
 
         // Use first signature's metadata as base
@@ -107,21 +124,9 @@ fn aggregate_signatures(args: &Args) -> Result<AggregationResult, Box<dyn std::e
     fs::write(&args.output, json_str)?;
 
     // Check threshold if provided
-    let threshold_met = if let Some(threshold_str) = &args.threshold {
-        let parts: Vec<&str> = threshold_str.split("-of-").collect();
-        if parts.len() == 2 {
-            if let (Ok(required), Ok(_total)) =
-                (parts[0].parse::<usize>(), parts[1].parse::<usize>())
-            {
-                signatures.len() >= required
-            } else {
-                false
-            }
-        } else {
-            false
-        }
-    } else {
-        true // No threshold specified, assume met if we have signatures
+    let threshold_met = match required {
+        Some(required) => signatures.len() >= required,
+        None => true, // No threshold specified, assume met if we have signatures
     };
 
     Ok(AggregationResult {
@@ -137,7 +142,18 @@ fn format_aggregation_output(
     args: &Args,
     formatter: &OutputFormatter,
 ) -> String {
-    if args.format == OutputFormat::Json {
+    if args.format == OutputFormat::Ndjson {
+        let summary = serde_json::json!({
+            "record": "summary",
+            "success": true,
+            "signature_count": result.signature_count,
+            "threshold_met": result.threshold_met,
+            "output_file": result.output_file,
+        });
+        formatter
+            .format(&summary)
+            .unwrap_or_else(|_| "{}".to_string())
+    } else if args.format == OutputFormat::Json {
         let output_data = serde_json::json!({
             "success": true,
             "signature_count": result.signature_count,
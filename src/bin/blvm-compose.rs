@@ -2,13 +2,20 @@
 //!
 //! Command-line interface for composing Bitcoin nodes from modules.
 
+use blvm_sdk::cli::config::{env_vars, CliConfig};
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
+use blvm_sdk::composition::logs;
 use blvm_sdk::composition::*;
+use chrono::{DateTime, Utc};
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::io::Write;
+use std::path::{Path, PathBuf};
 
 #[derive(Parser)]
 #[command(name = "blvm-compose")]
 #[command(about = "Compose Bitcoin nodes from modules", long_about = None)]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Cli {
     #[command(subcommand)]
     command: Option<Commands>,
@@ -16,15 +23,74 @@ struct Cli {
     /// Modules directory path
     #[arg(long, default_value = "./modules")]
     modules_dir: PathBuf,
+
+    /// Output format (text, json, table, ndjson, csv)
+    #[arg(short, long, default_value = "text")]
+    format: OutputFormat,
+
+    /// Do not truncate long values in table output
+    #[arg(long)]
+    wide: bool,
+
+    /// Network override for the node configuration (mainnet, testnet, regtest)
+    #[arg(long)]
+    network: Option<String>,
+
+    /// Module log directory (default: ~/.bllvm/logs)
+    #[arg(long)]
+    log_dir: Option<PathBuf>,
+
+    /// Module IPC socket directory; modules answering there count as running
+    #[arg(long)]
+    socket_dir: Option<PathBuf>,
 }
 
 #[derive(Subcommand)]
 enum Commands {
-    /// Compose a node from configuration file
+    /// Compose a node from configuration file or descriptor
     Compose {
         /// Configuration file path
+        #[arg(short, long, required_unless_present = "descriptor")]
+        config: Option<PathBuf>,
+
+        /// Node descriptor, e.g. "node(mainnet,[lightning@1.0])"
+        #[arg(short, long, conflicts_with = "config")]
+        descriptor: Option<String>,
+
+        /// Save a checkpoint of the composed node to this file
+        #[arg(long)]
+        checkpoint: Option<PathBuf>,
+
+        /// Start modules even if their binaries changed since installation
+        #[arg(long)]
+        skip_integrity_check: bool,
+
+        /// Fail if resolved modules differ from the lockfile (written if missing)
+        #[arg(long)]
+        locked: bool,
+
+        /// Rewrite the lockfile from the modules resolved now
+        #[arg(long)]
+        update_lock: bool,
+
+        /// Lockfile path (default: node.lock next to the configuration)
+        #[arg(long)]
+        lockfile: Option<PathBuf>,
+    },
+
+    /// Write a lockfile pinning the module versions and hashes a node resolves to
+    Lock {
+        /// Configuration file path
+        #[arg(short, long, required_unless_present = "descriptor")]
+        config: Option<PathBuf>,
+
+        /// Node descriptor, e.g. "node(mainnet,[lightning@1.0])"
+        #[arg(short, long, conflicts_with = "config")]
+        descriptor: Option<String>,
+
+        /// Lockfile path (default: node.lock next to the configuration)
         #[arg(short, long)]
-        config: PathBuf,
+        output: Option<PathBuf>,
     },
 
     /// Validate a composition configuration
@@ -34,6 +100,13 @@ enum Commands {
         config: PathBuf,
     },
 
+    /// Show the state saved in a composition checkpoint
+    Status {
+        /// Checkpoint file path
+        #[arg(long)]
+        checkpoint: PathBuf,
+    },
+
     /// Generate a configuration template
     GenerateTemplate {
         /// Output file path (default: stdout)
@@ -41,9 +114,50 @@ enum Commands {
         output: Option<PathBuf>,
     },
 
+    /// Show module stdout/stderr logs
+    Logs {
+        /// Module to show (repeat for interleaved output; default: all logged modules)
+        #[arg(short, long = "module", value_name = "NAME")]
+        modules: Vec<String>,
+
+        /// Keep streaming new lines as modules write them
+        #[arg(short, long)]
+        follow: bool,
+
+        /// Number of trailing lines to show
+        #[arg(short = 'n', long, default_value_t = 50)]
+        lines: usize,
+
+        /// Only show lines since an RFC 3339 timestamp or an age (e.g. 15m, 2h)
+        #[arg(long, value_parser = parse_since_arg)]
+        since: Option<DateTime<Utc>>,
+    },
+
     /// Module registry operations
     #[command(subcommand)]
     Modules(ModuleCommands),
+
+    /// CLI configuration file operations
+    #[command(subcommand)]
+    Config(ConfigCommands),
+}
+
+#[derive(Subcommand)]
+enum ConfigCommands {
+    /// Show the effective configuration for bllvm-compose
+    Show,
+
+    /// Set a value in the CLI configuration file
+    Set {
+        /// Config key (e.g. default_modules_dir, default_output_format)
+        key: String,
+
+        /// New value
+        value: String,
+    },
+
+    /// Show recognized environment variables and their current values
+    Env,
 }
 
 #[derive(Subcommand)]
@@ -56,9 +170,35 @@ enum ModuleCommands {
         /// Module source (path, registry URL, or git URL)
         source: String,
 
-        /// Module version (optional)
+        /// Module version (optional; registry sources default to the latest)
         #[arg(short, long)]
         version: Option<String>,
+
+        /// Module to install from a registry source
+        #[arg(long)]
+        module: Option<String>,
+
+        /// Install a git source at this tag
+        #[arg(long, conflicts_with_all = ["branch", "commit"])]
+        tag: Option<String>,
+
+        /// Install a git source at the tip of this branch
+        #[arg(long, conflicts_with = "commit")]
+        branch: Option<String>,
+
+        /// Install a git source at this commit SHA
+        #[arg(long)]
+        commit: Option<String>,
+    },
+
+    /// Search a module registry
+    Search {
+        /// Registry URL
+        registry: String,
+
+        /// Text to match against module names and descriptions
+        #[arg(default_value = "")]
+        query: String,
     },
 
     /// Update a module
@@ -74,50 +214,147 @@ enum ModuleCommands {
     Remove {
         /// Module name
         name: String,
+
+        /// Remove even if other modules depend on it
+        #[arg(long)]
+        force: bool,
     },
 }
 
 #[tokio::main]
-async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
-    let cli = Cli::parse();
+async fn main() {
+    let cli_config = CliConfig::load_or_exit();
+    let cli: Cli = exit::parse_args(&cli_config);
+    let formatter = OutputFormatter::new(cli.format.clone()).with_wide(cli.wide);
+
+    if let Err(e) = run(cli, &cli_config, &formatter).await {
+        eprintln!("{}", formatter.format_error(&*e));
+        ExitCode::from_error(&*e).exit();
+    }
+}
 
+async fn run(
+    cli: Cli,
+    cli_config: &CliConfig,
+    formatter: &OutputFormatter,
+) -> std::result::Result<(), Box<dyn std::error::Error>> {
+    let log_dir = cli.log_dir.clone().or_else(logs::default_log_dir);
     let mut composer = NodeComposer::new(&cli.modules_dir);
+    if let Some(log_dir) = &log_dir {
+        composer = composer.with_log_dir(log_dir);
+    }
+    if let Some(socket_dir) = &cli.socket_dir {
+        composer = composer.with_socket_dir(socket_dir);
+    }
 
     match cli.command {
-        Some(Commands::Compose { config }) => {
-            println!("Composing node from configuration: {:?}", config);
-            let composed = composer.compose_from_config(&config).await?;
+        Some(Commands::Compose {
+            config,
+            descriptor,
+            checkpoint,
+            skip_integrity_check,
+            locked,
+            update_lock,
+            lockfile,
+        }) => {
+            if skip_integrity_check {
+                composer = composer.with_integrity_check(false);
+            }
+            if locked || update_lock {
+                let lockfile = lockfile.unwrap_or_else(|| default_lockfile(config.as_deref()));
+                composer = composer
+                    .with_lockfile(lockfile)
+                    .with_lock_update(update_lock);
+            }
+            let composed = match (config, descriptor) {
+                (_, Some(descriptor)) => {
+                    println!("Composing node from descriptor: {}", descriptor);
+                    let spec = NodeSpec::from_descriptor(&descriptor)?;
+                    composer.compose_node(spec).await?
+                }
+                (Some(config), None) => {
+                    println!("Composing node from configuration: {:?}", config);
+                    let node_config = load_node_config(&config, cli.network.as_deref())?;
+                    composer.compose_from_node_config(node_config).await?
+                }
+                (None, None) => unreachable!("clap requires --config or --descriptor"),
+            };
             println!("Successfully composed node: {}", composed.spec.name);
-            println!("Modules: {}", composed.modules.len());
-            for module in &composed.modules {
-                println!(
-                    "  - {} ({}): {:?}",
-                    module.info.name, module.info.version, module.status
-                );
+            print!("{}", composed.to_status_report());
+            if let Some(path) = checkpoint {
+                composed.save_checkpoint(&path)?;
+                println!("Checkpoint written to: {:?}", path);
+            }
+            Ok(())
+        }
+
+        Some(Commands::Lock {
+            config,
+            descriptor,
+            output,
+        }) => {
+            let output = output.unwrap_or_else(|| default_lockfile(config.as_deref()));
+            let spec = match (config, descriptor) {
+                (_, Some(descriptor)) => NodeSpec::from_descriptor(&descriptor)?,
+                (Some(config), None) => {
+                    load_node_config(&config, cli.network.as_deref())?.to_spec()?
+                }
+                (None, None) => unreachable!("clap requires --config or --descriptor"),
+            };
+            composer.registry_mut().discover_modules()?;
+            let lock = composer.write_lockfile(&spec, &output)?;
+            for module in &lock.modules {
+                println!("  - {} {}", module.name, module.version);
+            }
+            println!("Lockfile written to: {:?}", output);
+            Ok(())
+        }
+
+        Some(Commands::Status { checkpoint }) => {
+            let saved = NodeCheckpoint::load(&checkpoint)?;
+            let report = saved.to_composed_node().to_report();
+            let output = formatter.format_node_report(&report);
+            if output.ends_with('\n') {
+                print!("{}", output);
+            } else {
+                println!("{}", output);
             }
             Ok(())
         }
 
         Some(Commands::Validate { config }) => {
             println!("Validating configuration: {:?}", config);
-            let node_config = NodeConfig::from_file(&config)?;
-            let validation = composer.validate_composition(&node_config.to_spec()?)?;
+            let node_config = load_node_config(&config, cli.network.as_deref())?;
+            composer.registry_mut().discover_modules()?;
+            let mut validation = node_config.validate_with_registry(composer.registry())?;
+            validation
+                .warnings
+                .extend_from_slice(composer.registry().discovery_warnings());
 
             if validation.valid {
                 println!("✓ Configuration is valid");
-                if !validation.warnings.is_empty() {
-                    println!("Warnings:");
-                    for warning in &validation.warnings {
-                        println!("  - {}", warning);
-                    }
-                }
-                Ok(())
             } else {
                 println!("✗ Configuration is invalid:");
                 for error in &validation.errors {
                     println!("  - {}", error);
                 }
-                std::process::exit(1)
+            }
+            if !validation.warnings.is_empty() {
+                println!("Warnings:");
+                for warning in &validation.warnings {
+                    println!("  - {}", warning);
+                }
+            }
+            if !validation.dependencies.is_empty() {
+                println!("Modules:");
+                for module in &validation.dependencies {
+                    println!("  - {} {}", module.name, module.version);
+                }
+            }
+            if validation.valid {
+                Ok(())
+            } else {
+                ExitCode::ValidationFailed.exit()
             }
         }
 
@@ -133,31 +370,170 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
 
+        Some(Commands::Logs {
+            modules,
+            follow,
+            lines,
+            since,
+        }) => {
+            let log_dir = log_dir.ok_or("Cannot determine log directory; use --log-dir")?;
+            let modules = if modules.is_empty() {
+                logs::logged_modules(&log_dir)?
+            } else {
+                modules
+            };
+            let prefixed = modules.len() > 1;
+            let json = matches!(cli.format, OutputFormat::Json | OutputFormat::Ndjson);
+
+            for line in logs::read_logs(&log_dir, &modules, lines, since)? {
+                print_log_line(&line, json, prefixed)?;
+            }
+            if follow {
+                let mut follower = LogFollower::new(&log_dir, &modules)?;
+                loop {
+                    for line in follower.poll(std::time::Duration::from_secs(1))? {
+                        print_log_line(&line, json, prefixed)?;
+                    }
+                }
+            }
+            Ok(())
+        }
+
+        Some(Commands::Config(ConfigCommands::Show)) => {
+            let path = cli_config
+                .path
+                .as_ref()
+                .map(|path| path.display().to_string());
+            if !matches!(
+                cli.format,
+                OutputFormat::Json | OutputFormat::Ndjson | OutputFormat::Csv
+            ) {
+                println!("Config file: {}", path.as_deref().unwrap_or("(none)"));
+            }
+
+            let rows: Vec<Vec<String>> = cli_config
+                .effective("blvm-compose")
+                .into_iter()
+                .map(|(setting, value, source)| {
+                    vec![
+                        setting.key.to_string(),
+                        value.unwrap_or_default(),
+                        source.to_string(),
+                    ]
+                })
+                .collect();
+            let output = formatter.format_table(&["KEY", "VALUE", "SOURCE"], &rows);
+            println!("{}", output.trim_end());
+            Ok(())
+        }
+
+        Some(Commands::Config(ConfigCommands::Set { key, value })) => {
+            let path = CliConfig::config_path().ok_or("Cannot determine config file location")?;
+            CliConfig::set_value(&path, &key, &value)?;
+            println!("Set {} = {} in {}", key, value, path.display());
+            Ok(())
+        }
+
+        Some(Commands::Config(ConfigCommands::Env)) => {
+            let rows: Vec<Vec<String>> = env_vars()
+                .into_iter()
+                .map(|(name, value)| {
+                    vec![
+                        name.to_string(),
+                        value.unwrap_or_else(|| "(unset)".to_string()),
+                    ]
+                })
+                .collect();
+            let output = formatter.format_table(&["VARIABLE", "VALUE"], &rows);
+            println!("{}", output.trim_end());
+            Ok(())
+        }
+
         Some(Commands::Modules(ModuleCommands::List)) => {
             composer.registry_mut().discover_modules()?;
+            for warning in composer.registry().discovery_warnings() {
+                eprintln!("Warning: {}", warning);
+            }
             let modules = composer.registry().list_modules();
 
-            if modules.is_empty() {
+            if formatter.is_streaming() {
+                for module in &modules {
+                    formatter.emit(&serde_json::json!({
+                        "record": "module",
+                        "name": module.name,
+                        "version": module.version,
+                        "description": module.description,
+                    }))?;
+                }
+                formatter.emit(&serde_json::json!({
+                    "record": "summary",
+                    "success": true,
+                    "module_count": modules.len(),
+                }))?;
+            } else if modules.is_empty() && cli.format != OutputFormat::Csv {
                 println!("No modules found in {:?}", cli.modules_dir);
-            } else {
-                println!("Available modules:");
-                for module in modules {
-                    println!("  - {} ({})", module.name, module.version);
-                    if let Some(desc) = &module.description {
-                        println!("    {}", desc);
-                    }
+            } else if matches!(cli.format, OutputFormat::Table | OutputFormat::Csv) {
+                let mut rows = Vec::new();
+                for module in &modules {
+                    let status = composer.lifecycle().get_module_status(&module.name).await?;
+                    rows.push(vec![
+                        module.name.clone(),
+                        module.version.clone(),
+                        module_status_label(&status),
+                    ]);
                 }
+                print!(
+                    "{}",
+                    formatter.format_table(&["NAME", "VERSION", "STATUS"], &rows)
+                );
+            } else {
+                let items: Vec<String> = modules
+                    .iter()
+                    .map(|module| match &module.description {
+                        Some(desc) => format!("{} ({}) - {}", module.name, module.version, desc),
+                        None => format!("{} ({})", module.name, module.version),
+                    })
+                    .collect();
+                let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                let output = formatter.format_list("Available modules", &items);
+                println!("{}", output.trim_end());
             }
             Ok(())
         }
 
-        Some(Commands::Modules(ModuleCommands::Install { source, version: _ })) => {
-            let module_source = if source.starts_with("http://") || source.starts_with("https://") {
-                ModuleSource::Registry(source)
-            } else if source.starts_with("git+") || source.contains("github.com") {
+        Some(Commands::Modules(ModuleCommands::Install {
+            source,
+            version,
+            module,
+            tag,
+            branch,
+            commit,
+        })) => {
+            // A git reference flag always means a git source
+            let reference = match (tag, branch, commit) {
+                (Some(tag), _, _) => Some(GitReference::Tag(tag)),
+                (_, Some(branch), _) => Some(GitReference::Branch(branch)),
+                (_, _, Some(sha)) => Some(GitReference::Commit(sha)),
+                _ => None,
+            };
+            let module_source = if reference.is_some()
+                || source.starts_with("git+")
+                || source.contains("github.com")
+            {
                 ModuleSource::Git {
                     url: source,
-                    tag: None,
+                    reference: reference.unwrap_or_default(),
+                }
+            } else if source.starts_with("http://") || source.starts_with("https://") {
+                let name = module.ok_or_else(|| {
+                    CompositionError::InvalidConfiguration(
+                        "Installing from a registry requires --module <NAME>".to_string(),
+                    )
+                })?;
+                ModuleSource::Registry {
+                    url: source,
+                    name,
+                    version,
                 }
             } else {
                 ModuleSource::Path(PathBuf::from(source))
@@ -172,6 +548,55 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
 
+        Some(Commands::Modules(ModuleCommands::Search { registry, query })) => {
+            let entries = composer.registry().search_remote(&registry, &query)?;
+
+            if formatter.is_streaming() {
+                for entry in &entries {
+                    formatter.emit(&serde_json::json!({
+                        "record": "module",
+                        "name": entry.name,
+                        "version": entry.version,
+                        "description": entry.manifest.description,
+                    }))?;
+                }
+                formatter.emit(&serde_json::json!({
+                    "record": "summary",
+                    "success": true,
+                    "module_count": entries.len(),
+                }))?;
+            } else if entries.is_empty() && cli.format != OutputFormat::Csv {
+                println!("No modules matching {:?} in {}", query, registry);
+            } else if matches!(cli.format, OutputFormat::Table | OutputFormat::Csv) {
+                let rows: Vec<Vec<String>> = entries
+                    .iter()
+                    .map(|entry| {
+                        vec![
+                            entry.name.clone(),
+                            entry.version.clone(),
+                            entry.manifest.description.clone().unwrap_or_default(),
+                        ]
+                    })
+                    .collect();
+                print!(
+                    "{}",
+                    formatter.format_table(&["NAME", "VERSION", "DESCRIPTION"], &rows)
+                );
+            } else {
+                let items: Vec<String> = entries
+                    .iter()
+                    .map(|entry| match &entry.manifest.description {
+                        Some(desc) => format!("{} ({}) - {}", entry.name, entry.version, desc),
+                        None => format!("{} ({})", entry.name, entry.version),
+                    })
+                    .collect();
+                let items: Vec<&str> = items.iter().map(String::as_str).collect();
+                let output = formatter.format_list("Registry modules", &items);
+                println!("{}", output.trim_end());
+            }
+            Ok(())
+        }
+
         Some(Commands::Modules(ModuleCommands::Update { name, version })) => {
             println!("Updating module {} to version {}", name, version);
             let module = composer.registry_mut().update_module(&name, &version)?;
@@ -179,9 +604,20 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
             Ok(())
         }
 
-        Some(Commands::Modules(ModuleCommands::Remove { name })) => {
+        Some(Commands::Modules(ModuleCommands::Remove { name, force })) => {
             println!("Removing module: {}", name);
-            composer.registry_mut().remove_module(&name)?;
+            composer.registry_mut().discover_modules()?;
+            if force {
+                let dependents = composer.registry().dependents_of(&name);
+                if !dependents.is_empty() {
+                    eprintln!(
+                        "Warning: removing {} anyway; required by {}",
+                        name,
+                        dependents.join(", ")
+                    );
+                }
+            }
+            composer.lifecycle_mut().remove_module(&name, force)?;
             println!("Successfully removed: {}", name);
             Ok(())
         }
@@ -192,3 +628,50 @@ async fn main() -> std::result::Result<(), Box<dyn std::error::Error>> {
         }
     }
 }
+
+/// Lockfile used when none is given: `node.lock` next to the configuration
+fn default_lockfile(config: Option<&Path>) -> PathBuf {
+    config
+        .and_then(Path::parent)
+        .unwrap_or(Path::new(""))
+        .join(LOCKFILE_NAME)
+}
+
+/// Load a node configuration, applying the network override if given
+fn load_node_config(path: &Path, network: Option<&str>) -> Result<NodeConfig> {
+    let mut node_config = NodeConfig::from_file(path)?;
+    if let Some(network) = network {
+        node_config.node.network = network.to_string();
+    }
+    Ok(node_config)
+}
+
+/// Parse `--since` for clap
+fn parse_since_arg(value: &str) -> std::result::Result<DateTime<Utc>, String> {
+    logs::parse_since(value).map_err(|e| e.to_string())
+}
+
+/// Print one log line as text or as a JSON object
+fn print_log_line(line: &LogLine, json: bool, prefixed: bool) -> std::io::Result<()> {
+    let mut stdout = std::io::stdout().lock();
+    if json {
+        writeln!(stdout, "{}", serde_json::to_string(line)?)?;
+    } else if prefixed {
+        writeln!(stdout, "{}", line.to_prefixed())?;
+    } else {
+        writeln!(stdout, "{}", line.line)?;
+    }
+    stdout.flush()
+}
+
+/// Short module status label for table output
+fn module_status_label(status: &ModuleStatus) -> String {
+    match status {
+        // Listed modules were discovered on disk, so an uncached status means not started
+        ModuleStatus::NotInstalled | ModuleStatus::Stopped => "stopped".to_string(),
+        ModuleStatus::Initializing => "initializing".to_string(),
+        ModuleStatus::Running => "running".to_string(),
+        ModuleStatus::Stopping => "stopping".to_string(),
+        ModuleStatus::Error(msg) => format!("error: {}", msg),
+    }
+}
@@ -2,21 +2,33 @@
 //!
 //! Generate governance keypairs for Bitcoin governance operations.
 
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::KeyFile;
+use blvm_sdk::cli::input::{parse_derivation_path, parse_hex, InputError};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
+use blvm_sdk::governance::bip32::derive_master_key;
+use blvm_sdk::governance::ceremony::KeyCeremony;
 use blvm_sdk::governance::GovernanceKeypair;
-use clap::Parser;
+use clap::{Parser, Subcommand};
 use std::fs;
+use std::path::{Path, PathBuf};
 // No need for Path import
 
 /// Generate governance keypairs
 #[derive(Parser, Debug)]
 #[command(name = "blvm-keygen")]
 #[command(about = "Generate governance keypairs for Bitcoin Commons governance operations")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
     /// Output file for the keypair
-    #[arg(short, long, default_value = "governance.key")]
+    #[arg(short = 'o', long = "output", default_value = "governance.key")]
     output: String,
 
+    /// Overwrite existing key files
+    #[arg(long)]
+    force: bool,
+
     /// Output format (text, json)
     #[arg(short, long, default_value = "text")]
     format: OutputFormat,
@@ -28,25 +40,95 @@ struct Args {
     /// Show private key in output
     #[arg(long)]
     show_private: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Derive a keypair from a hex seed along a BIP32 path
+    Derive {
+        /// Derivation path (e.g. m/44'/0'/0'/0/0)
+        #[arg(short, long)]
+        path: String,
+
+        /// Seed in hex (16-64 bytes)
+        #[arg(long)]
+        seed_hex: String,
+    },
+    /// Run a key ceremony: one keypair per participant plus a cosigned transcript
+    Ceremony {
+        /// Participant names (repeat or comma-separate)
+        #[arg(short, long = "participant", required = true, value_delimiter = ',')]
+        participants: Vec<String>,
+
+        /// Directory for participant key files and the transcript
+        #[arg(short = 'd', long, default_value = ".")]
+        output_dir: PathBuf,
+    },
+    /// Inspect the CLI configuration file
+    #[command(subcommand)]
+    Config(ConfigCommand),
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print the effective configuration (environment merged over file)
+    Show {
+        /// Tool whose per-tool section to merge (e.g. blvm-verify)
+        #[arg(long, default_value = "blvm-keygen")]
+        tool: String,
+    },
 }
 
 fn main() {
-    let args = Args::parse();
+    let config = CliConfig::load_or_exit();
+    let args: Args = exit::parse_args(&config);
     let formatter = OutputFormatter::new(args.format.clone());
 
-    match generate_keypair(&args) {
-        Ok(keypair) => {
-            let output = format_keypair_output(&keypair, &args, &formatter);
+    if let Some(Command::Config(ConfigCommand::Show { tool })) = &args.command {
+        println!("{}", format_config_output(&config, tool, &args, &formatter));
+        return;
+    }
+
+    if let Some(Command::Ceremony {
+        participants,
+        output_dir,
+    }) = &args.command
+    {
+        match run_ceremony(participants, output_dir, args.force) {
+            Ok(summary) => println!("{}", format_ceremony_output(&summary, &args, &formatter)),
+            Err(e) => {
+                eprintln!("{}", formatter.format_error(&*e));
+                ExitCode::from_error(&*e).exit();
+            }
+        }
+        return;
+    }
+
+    let result = match &args.command {
+        Some(Command::Derive { path, seed_hex }) => {
+            derive_keypair(path, seed_hex, &args.output, args.force)
+        }
+        _ => generate_keypair(&args),
+    };
+
+    match result {
+        Ok((keypair, created_at)) => {
+            let output = format_keypair_output(&keypair, &created_at, &args, &formatter);
             println!("{}", output);
         }
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
 
-fn generate_keypair(args: &Args) -> Result<GovernanceKeypair, Box<dyn std::error::Error>> {
+fn generate_keypair(
+    args: &Args,
+) -> Result<(GovernanceKeypair, String), Box<dyn std::error::Error>> {
     let keypair = if let Some(seed) = &args.seed {
         // Generate deterministic keypair from seed
         let seed_bytes = seed.as_bytes();
@@ -63,56 +145,266 @@ fn generate_keypair(args: &Args) -> Result<GovernanceKeypair, Box<dyn std::error
     };
 
     // Save keypair to file
-    save_keypair(&keypair, &args.output)?;
+    let created_at = save_keypair(&keypair, &args.output, args.force)?;
 
-    Ok(keypair)
+    Ok((keypair, created_at))
 }
 
+fn derive_keypair(
+    path: &str,
+    seed_hex: &str,
+    output_path: &str,
+    force: bool,
+) -> Result<(GovernanceKeypair, String), Box<dyn std::error::Error>> {
+    let indices = parse_derivation_path(path)?;
+    let seed = parse_hex(seed_hex)?;
+
+    let (mut private_key, _) = derive_master_key(&seed)?;
+    for index in &indices {
+        private_key = private_key.derive_child(*index)?.0;
+    }
+
+    let keypair = GovernanceKeypair::from_secret_key(&private_key.private_key_bytes())?;
+    let created_at = save_keypair(&keypair, output_path, force)?;
+
+    Ok((keypair, created_at))
+}
+
+/// Write a key file, refusing to replace an existing one unless `force` is set
 fn save_keypair(
     keypair: &GovernanceKeypair,
     output_path: &str,
-) -> Result<(), Box<dyn std::error::Error>> {
-    let keypair_data = serde_json::json!({
-        "public_key": hex::encode(keypair.public_key().to_bytes()),
-        "secret_key": hex::encode(keypair.secret_key_bytes()),
-        "created_at": chrono::Utc::now().to_rfc3339(),
-    });
-
-    let json_str = serde_json::to_string_pretty(&keypair_data)?;
+    force: bool,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if !force && Path::new(output_path).exists() {
+        return Err(InputError::InvalidValue(format!(
+            "{} already exists; pass --force to overwrite it",
+            output_path
+        ))
+        .into());
+    }
+    let created_at = chrono::Utc::now().to_rfc3339();
+    let key_file = KeyFile::from_keypair(keypair, created_at.clone());
+
+    let json_str = serde_json::to_string_pretty(&key_file)?;
     fs::write(output_path, json_str)?;
 
-    Ok(())
+    Ok(created_at)
+}
+
+/// Files and keys produced by a ceremony
+struct CeremonySummary {
+    /// (participant, public key hex, key file)
+    participants: Vec<(String, String, PathBuf)>,
+    transcript_file: PathBuf,
+    signatures_file: PathBuf,
+}
+
+fn run_ceremony(
+    participants: &[String],
+    output_dir: &Path,
+    force: bool,
+) -> Result<CeremonySummary, Box<dyn std::error::Error>> {
+    for participant in participants {
+        if participant.is_empty()
+            || !participant
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Err(format!(
+                "Invalid participant name '{}': use letters, digits, '-' or '_'",
+                participant
+            )
+            .into());
+        }
+    }
+    // Refuse before generating anything, so no participant's key is replaced
+    if !force {
+        if let Some(existing) = participants
+            .iter()
+            .map(|p| output_dir.join(format!("{}.key", p)))
+            .find(|key_file| key_file.exists())
+        {
+            return Err(InputError::InvalidValue(format!(
+                "{} already exists; pass --force to overwrite it",
+                existing.display()
+            ))
+            .into());
+        }
+    }
+    fs::create_dir_all(output_dir)?;
+
+    let mut ceremony = KeyCeremony::start(participants.to_vec());
+    let mut generated = Vec::new();
+    for participant in ceremony.pending_participants() {
+        generated.push((participant.to_string(), GovernanceKeypair::generate()?));
+    }
+    for (participant, keypair) in &generated {
+        ceremony.add_participant_key(participant, keypair)?;
+    }
+    let transcript = ceremony.finalize()?;
+
+    // Every participant cosigns the exact transcript bytes written to disk
+    let mut signatures = Vec::new();
+    let mut summary_participants = Vec::new();
+    for (participant, keypair) in &generated {
+        let key_file = output_dir.join(format!("{}.key", participant));
+        save_keypair(keypair, &key_file.to_string_lossy(), force)?;
+
+        let public_key = keypair.public_key().to_string();
+        signatures.push(serde_json::json!({
+            "participant": participant,
+            "public_key": public_key,
+            "signature": transcript.sign_transcript(keypair)?.to_string(),
+        }));
+        summary_participants.push((participant.clone(), public_key, key_file));
+    }
+
+    let transcript_file = output_dir.join("ceremony-transcript.json");
+    fs::write(&transcript_file, transcript.to_json())?;
+    let signatures_file = output_dir.join("ceremony-signatures.json");
+    fs::write(
+        &signatures_file,
+        serde_json::to_string_pretty(&serde_json::Value::Array(signatures))?,
+    )?;
+
+    Ok(CeremonySummary {
+        participants: summary_participants,
+        transcript_file,
+        signatures_file,
+    })
+}
+
+fn format_ceremony_output(
+    summary: &CeremonySummary,
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> String {
+    if args.format == OutputFormat::Json {
+        let participants: Vec<serde_json::Value> = summary
+            .participants
+            .iter()
+            .map(|(participant, public_key, key_file)| {
+                serde_json::json!({
+                    "participant": participant,
+                    "public_key": public_key,
+                    "key_file": key_file.display().to_string(),
+                })
+            })
+            .collect();
+        let output_data = serde_json::json!({
+            "success": true,
+            "participants": participants,
+            "transcript_file": summary.transcript_file.display().to_string(),
+            "signatures_file": summary.signatures_file.display().to_string(),
+        });
+        formatter
+            .format(&output_data)
+            .unwrap_or_else(|_| "{}".to_string())
+    } else {
+        let rows: Vec<Vec<String>> = summary
+            .participants
+            .iter()
+            .map(|(participant, public_key, key_file)| {
+                vec![
+                    participant.clone(),
+                    public_key.clone(),
+                    key_file.display().to_string(),
+                ]
+            })
+            .collect();
+        format!(
+            "Key ceremony complete\n{}Transcript: {}\nSignatures: {}",
+            formatter.format_table(&["PARTICIPANT", "PUBLIC KEY", "KEY FILE"], &rows),
+            summary.transcript_file.display(),
+            summary.signatures_file.display()
+        )
+    }
 }
 
 fn format_keypair_output(
     keypair: &GovernanceKeypair,
+    created_at: &str,
     args: &Args,
     formatter: &OutputFormatter,
 ) -> String {
     if args.format == OutputFormat::Json {
         let output_data = serde_json::json!({
             "success": true,
-            "public_key": hex::encode(keypair.public_key().to_bytes()),
+            "public_key": keypair.public_key(),
             "secret_key": if args.show_private {
                 Some(hex::encode(keypair.secret_key_bytes()))
             } else {
                 None
             },
             "output_file": args.output,
+            "created_at": created_at,
         });
         formatter
             .format(&output_data)
             .unwrap_or_else(|_| "{}".to_string())
     } else {
-        let mut output = "Generated governance keypair\n".to_string();
-        output.push_str(&format!("Public key: {}\n", keypair.public_key()));
+        let public_key = keypair.public_key().to_string();
+        let secret_key = hex::encode(keypair.secret_key_bytes());
+        let mut pairs = vec![("Public key", public_key.as_str())];
         if args.show_private {
+            pairs.push(("Secret key", secret_key.as_str()));
+        }
+        pairs.push(("Saved to", args.output.as_str()));
+        pairs.push(("Created at", created_at));
+
+        format!(
+            "Generated governance keypair\n{}",
+            formatter.format_key_value(&pairs)
+        )
+    }
+}
+
+fn format_config_output(
+    config: &CliConfig,
+    tool: &str,
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> String {
+    let settings = config.effective(tool);
+    let config_file = config.path.as_ref().map(|path| path.display().to_string());
+
+    if args.format == OutputFormat::Json {
+        let values: serde_json::Map<String, serde_json::Value> = settings
+            .iter()
+            .map(|(setting, value, source)| {
+                (
+                    setting.key.to_string(),
+                    serde_json::json!({
+                        "value": value,
+                        "source": source.to_string(),
+                        "env": setting.env,
+                    }),
+                )
+            })
+            .collect();
+        let output_data = serde_json::json!({
+            "config_file": config_file,
+            "tool": tool,
+            "settings": values,
+        });
+        formatter
+            .format(&output_data)
+            .unwrap_or_else(|_| "{}".to_string())
+    } else {
+        let mut output = format!(
+            "Config file: {}\nTool: {}\n",
+            config_file.as_deref().unwrap_or("(none)"),
+            tool
+        );
+        for (setting, value, source) in &settings {
             output.push_str(&format!(
-                "Secret key: {}\n",
-                hex::encode(keypair.secret_key_bytes())
+                "{} = {} ({})\n",
+                setting.key,
+                value.as_deref().unwrap_or("(unset)"),
+                source
             ));
         }
-        output.push_str(&format!("Saved to: {}\n", args.output));
         output
     }
 }
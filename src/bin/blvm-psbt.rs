@@ -0,0 +1,381 @@
+//! # Bitcoin Commons BLLVM PSBT Tool
+//!
+//! Inspect, combine, sign, finalize, and extract BIP174 PSBTs.
+//!
+//! PSBT files may be binary, base64, or hex. PSBTs are written as base64.
+
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, NothingToDo, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::KeyFile;
+use blvm_sdk::cli::input::InputError;
+use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
+use blvm_sdk::governance::psbt::{
+    psbt_key, PartiallySignedTransaction, PsbtGlobalKey, PsbtIssueSeverity, PSBT_MAGIC,
+};
+use blvm_sdk::governance::transaction::Transaction;
+use blvm_sdk::governance::{GovernanceError, GovernanceKeypair};
+use clap::{Parser, Subcommand};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Work with partially signed Bitcoin transactions
+#[derive(Parser, Debug)]
+#[command(name = "blvm-psbt")]
+#[command(about = "Inspect, combine, sign, finalize, and extract PSBTs (BIP174)")]
+#[command(after_long_help = EXIT_CODES_HELP)]
+struct Args {
+    /// Output format (text, json)
+    #[arg(short, long, default_value = "text")]
+    format: OutputFormat,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Show a PSBT summary, validation issues, fee, and signing status
+    Inspect {
+        /// PSBT file
+        file: PathBuf,
+    },
+    /// Merge PSBTs for the same transaction
+    Combine {
+        /// PSBT files (at least two)
+        #[arg(required = true, num_args = 2..)]
+        files: Vec<PathBuf>,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Sign every input the key can spend
+    Sign {
+        /// Private key file
+        #[arg(short, long, required = true)]
+        key: PathBuf,
+
+        /// PSBT file
+        file: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Build final scripts from partial signatures
+    Finalize {
+        /// PSBT file
+        file: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+    /// Extract the signed network transaction (hex)
+    Extract {
+        /// PSBT file
+        file: PathBuf,
+
+        /// Output file (default: stdout)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+fn main() {
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
+    let formatter = OutputFormatter::new(args.format.clone());
+
+    if let Err(e) = run(&args, &formatter) {
+        eprintln!("{}", formatter.format_error(&*e));
+        ExitCode::from_error(&*e).exit();
+    }
+}
+
+fn run(args: &Args, formatter: &OutputFormatter) -> Result<(), Box<dyn std::error::Error>> {
+    match &args.command {
+        Command::Inspect { file } => {
+            let psbt = read_psbt(file)?;
+            let report = inspect(&psbt)?;
+            if args.format == OutputFormat::Json {
+                println!("{}", formatter.format(&report.json)?);
+            } else {
+                println!("{}", report.text);
+            }
+            if report.has_errors {
+                return Err(GovernanceError::InvalidInput(
+                    "PSBT has validation errors".to_string(),
+                )
+                .into());
+            }
+            Ok(())
+        }
+
+        Command::Combine { files, output } => {
+            let mut combined = read_psbt(&files[0])?;
+            let original = combined.serialize()?;
+            for file in &files[1..] {
+                combined.combine(&read_psbt(file)?)?;
+            }
+            if combined.serialize()? == original {
+                return Err(NothingToDo("Combined PSBT is unchanged".to_string()).into());
+            }
+            write_psbt(&combined, output.as_deref())?;
+            report_success(formatter, output.as_deref(), "Combined PSBTs");
+            Ok(())
+        }
+
+        Command::Sign { key, file, output } => {
+            let keypair = load_keypair(key)?;
+            let mut psbt = read_psbt(file)?;
+            let summary = psbt.sign_with_key(&keypair.secret_key)?;
+            for (index, reason) in &summary.skipped {
+                eprintln!("Skipped input {}: {}", index, reason);
+            }
+            if summary.signed.is_empty() {
+                return Err(
+                    NothingToDo("No inputs could be signed with this key".to_string()).into(),
+                );
+            }
+            write_psbt(&psbt, output.as_deref())?;
+            report_success(
+                formatter,
+                output.as_deref(),
+                &format!("Signed {} input(s)", summary.signed.len()),
+            );
+            Ok(())
+        }
+
+        Command::Finalize { file, output } => {
+            let mut psbt = read_psbt(file)?;
+            let pending: Vec<usize> = (0..psbt.inputs.len())
+                .filter(|&index| !psbt.inputs[index].is_finalized())
+                .collect();
+            if pending.is_empty() {
+                return Err(NothingToDo("All inputs are already finalized".to_string()).into());
+            }
+            let failures: Vec<String> = pending
+                .iter()
+                .filter_map(|&index| {
+                    psbt.finalize_input(index)
+                        .err()
+                        .map(|e| format!("input {}: {}", index, e))
+                })
+                .collect();
+            if !failures.is_empty() {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Cannot finalize {}",
+                    failures.join("; ")
+                ))
+                .into());
+            }
+            write_psbt(&psbt, output.as_deref())?;
+            report_success(
+                formatter,
+                output.as_deref(),
+                &format!("Finalized {} input(s)", pending.len()),
+            );
+            Ok(())
+        }
+
+        Command::Extract { file, output } => {
+            let psbt = read_psbt(file)?;
+            if !psbt.is_finalized() {
+                return Err(
+                    GovernanceError::InvalidInput("PSBT is not finalized".to_string()).into(),
+                );
+            }
+            let tx_hex = hex::encode(psbt.extract_transaction()?);
+            match output {
+                Some(path) => {
+                    fs::write(path, format!("{}\n", tx_hex))?;
+                    println!(
+                        "{}",
+                        formatter
+                            .format_success(&format!("Transaction written to {}", path.display()))
+                    );
+                }
+                None => println!("{}", tx_hex),
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Read a binary, base64, or hex PSBT file
+fn read_psbt(path: &Path) -> Result<PartiallySignedTransaction, Box<dyn std::error::Error>> {
+    let data = fs::read(path)?;
+    let parsed = if data.starts_with(&PSBT_MAGIC) {
+        PartiallySignedTransaction::deserialize(&data)
+    } else {
+        let text = String::from_utf8(data)
+            .map_err(|_| InputError::InvalidFormat(format!("{} is not a PSBT", path.display())))?;
+        PartiallySignedTransaction::auto_decode(&text)
+    };
+    parsed.map_err(|e| {
+        InputError::InvalidFormat(format!("Failed to parse {}: {}", path.display(), e)).into()
+    })
+}
+
+/// Write a PSBT as base64 to `output`, or to stdout
+fn write_psbt(
+    psbt: &PartiallySignedTransaction,
+    output: Option<&Path>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let encoded = psbt.to_base64()?;
+    match output {
+        Some(path) => fs::write(path, format!("{}\n", encoded))?,
+        None => println!("{}", encoded),
+    }
+    Ok(())
+}
+
+/// Report success, keeping stdout clean when the PSBT was written there
+fn report_success(formatter: &OutputFormatter, output: Option<&Path>, message: &str) {
+    match output {
+        Some(path) => println!(
+            "{}",
+            formatter.format_success(&format!("{}; written to {}", message, path.display()))
+        ),
+        None => eprintln!("{}", message),
+    }
+}
+
+fn load_keypair(key_path: &Path) -> Result<GovernanceKeypair, Box<dyn std::error::Error>> {
+    let key_data = fs::read_to_string(key_path)?;
+    let invalid = |reason: String| {
+        InputError::InvalidFormat(format!(
+            "Invalid key file {}: {}",
+            key_path.display(),
+            reason
+        ))
+    };
+    let key_file: KeyFile = serde_json::from_str(&key_data).map_err(|e| invalid(e.to_string()))?;
+    key_file
+        .keypair()
+        .map_err(|e| invalid(e.to_string()).into())
+}
+
+/// Decoded PSBT summary
+struct InspectReport {
+    json: serde_json::Value,
+    text: String,
+    has_errors: bool,
+}
+
+fn inspect(psbt: &PartiallySignedTransaction) -> Result<InspectReport, Box<dyn std::error::Error>> {
+    let issues = psbt.validate();
+    let has_errors = issues
+        .iter()
+        .any(|issue| issue.severity == PsbtIssueSeverity::Error);
+
+    let tx = psbt
+        .global
+        .get(&psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]))
+        .and_then(|bytes| Transaction::parse_without_witness(bytes).ok());
+    let txid = tx.as_ref().map(|tx| {
+        let mut txid = tx.txid();
+        txid.reverse();
+        hex::encode(txid)
+    });
+
+    let fee = psbt.fee().ok();
+    let vsize = psbt.estimated_vsize().ok();
+    let fee_rate = psbt.fee_rate().ok();
+
+    let inputs: Vec<serde_json::Value> = psbt
+        .inputs
+        .iter()
+        .enumerate()
+        .map(|(index, input)| {
+            let status = if input.is_finalized() {
+                "finalized".to_string()
+            } else if input.partial_sigs.is_empty() {
+                "unsigned".to_string()
+            } else {
+                format!("{} signature(s)", input.partial_sigs.len())
+            };
+            let prevout = tx.as_ref().and_then(|tx| tx.inputs.get(index)).map(|txin| {
+                let mut txid = txin.previous_txid;
+                txid.reverse();
+                format!("{}:{}", hex::encode(txid), txin.previous_vout)
+            });
+            serde_json::json!({
+                "index": index,
+                "prevout": prevout,
+                "status": status,
+                "partial_signatures": input.partial_sigs.len(),
+                "finalized": input.is_finalized(),
+            })
+        })
+        .collect();
+
+    let issue_values: Vec<serde_json::Value> = issues
+        .iter()
+        .map(|issue| {
+            serde_json::json!({
+                "severity": match issue.severity {
+                    PsbtIssueSeverity::Error => "error",
+                    PsbtIssueSeverity::Warning => "warning",
+                },
+                "code": issue.code.as_str(),
+                "message": issue.to_string(),
+            })
+        })
+        .collect();
+
+    let json = serde_json::json!({
+        "txid": txid,
+        "version": psbt.version,
+        "inputs": inputs,
+        "outputs": psbt.outputs.len(),
+        "fee": fee,
+        "estimated_vsize": vsize,
+        "fee_rate": fee_rate,
+        "finalized": psbt.is_finalized(),
+        "issues": issue_values,
+    });
+
+    let mut text = format!("PSBT version {}\n", psbt.version);
+    text.push_str(&format!(
+        "Txid: {}\n",
+        txid.as_deref().unwrap_or("(invalid unsigned transaction)")
+    ));
+    text.push_str(&format!(
+        "Inputs: {}  Outputs: {}\n",
+        psbt.inputs.len(),
+        psbt.outputs.len()
+    ));
+    match fee {
+        Some(fee) => text.push_str(&format!("Fee: {} sat\n", fee)),
+        None => text.push_str("Fee: unknown (missing UTXO information)\n"),
+    }
+    if let (Some(vsize), Some(fee_rate)) = (vsize, fee_rate) {
+        text.push_str(&format!(
+            "Estimated size: {} vB ({:.2} sat/vB)\n",
+            vsize, fee_rate
+        ));
+    }
+    for input in &inputs {
+        text.push_str(&format!(
+            "  input {}: {} ({})\n",
+            input["index"],
+            input["prevout"].as_str().unwrap_or("?"),
+            input["status"].as_str().unwrap_or_default()
+        ));
+    }
+    if issues.is_empty() {
+        text.push_str("No validation issues");
+    } else {
+        text.push_str("Validation issues:");
+        for issue in &issues {
+            text.push_str(&format!("\n  - {}", issue));
+        }
+    }
+
+    Ok(InspectReport {
+        json,
+        text,
+        has_errors,
+    })
+}
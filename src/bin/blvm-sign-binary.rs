@@ -5,6 +5,10 @@
 //! This tool signs binaries and verification bundles with maintainer multisig,
 //! creating cryptographic proof that binaries match verified code.
 
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::KeyFile;
+use blvm_sdk::cli::input::InputError;
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
 use blvm_sdk::governance::{GovernanceKeypair, Signature};
 use blvm_sdk::sign_message as crypto_sign_message;
@@ -17,6 +21,7 @@ use std::path::Path;
 #[derive(Parser, Debug)]
 #[command(name = "blvm-sign-binary")]
 #[command(about = "Sign binaries and verification bundles for Bitcoin Commons releases")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
     /// Output file for the signature
     #[arg(short, long, default_value = "signature.json")]
@@ -70,7 +75,7 @@ enum SignTarget {
         build_config_hash: Option<String>,
 
         /// Orange Paper specification hash (SHA256)
-        #[arg(short, long)]
+        #[arg(long)]
         spec_hash: Option<String>,
     },
     /// Sign a SHA256SUMS file
@@ -86,7 +91,7 @@ enum SignTarget {
 }
 
 fn main() {
-    let args = Args::parse();
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
     let formatter = OutputFormatter::new(args.format.clone());
 
     match sign_target(&args) {
@@ -96,7 +101,7 @@ fn main() {
         }
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
@@ -157,7 +162,7 @@ fn sign_binary(
     commit: Option<&str>,
 ) -> Result<SignResult, Box<dyn std::error::Error>> {
     if !Path::new(file_path).exists() {
-        return Err(format!("Binary file not found: {}", file_path).into());
+        return Err(InputError::FileNotFound(file_path.to_string()).into());
     }
 
     // Read binary file
@@ -212,7 +217,7 @@ fn sign_bundle(
     spec_hash: Option<&str>,
 ) -> Result<SignResult, Box<dyn std::error::Error>> {
     if !Path::new(file_path).exists() {
-        return Err(format!("Bundle file not found: {}", file_path).into());
+        return Err(InputError::FileNotFound(file_path.to_string()).into());
     }
 
     // Read bundle file
@@ -264,7 +269,7 @@ fn sign_checksums(
     version: Option<&str>,
 ) -> Result<SignResult, Box<dyn std::error::Error>> {
     if !Path::new(file_path).exists() {
-        return Err(format!("Checksums file not found: {}", file_path).into());
+        return Err(InputError::FileNotFound(file_path.to_string()).into());
     }
 
     // Read checksums file
@@ -304,18 +309,13 @@ fn sign_checksums(
 
 fn load_keypair(key_path: &str) -> Result<GovernanceKeypair, Box<dyn std::error::Error>> {
     if !Path::new(key_path).exists() {
-        return Err(format!("Key file not found: {}", key_path).into());
+        return Err(InputError::FileNotFound(key_path.to_string()).into());
     }
 
     let key_data = fs::read_to_string(key_path)?;
-    let key_json: serde_json::Value = serde_json::from_str(&key_data)?;
-
-    let secret_key_hex = key_json["secret_key"]
-        .as_str()
-        .ok_or("Invalid key file format")?;
-
-    let secret_key_bytes = hex::decode(secret_key_hex)?;
-    GovernanceKeypair::from_secret_key(&secret_key_bytes)
+    let key_file: KeyFile = serde_json::from_str(&key_data)?;
+    key_file
+        .keypair()
         .map_err(|e| format!("Invalid secret key: {}", e).into())
 }
 
@@ -324,7 +324,7 @@ fn save_signature(
     output_path: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let signature_data = serde_json::json!({
-        "signature": hex::encode(result.signature.to_bytes()),
+        "signature": result.signature,
         "signer": hex::encode(result.metadata.get("signer").and_then(|s| s.as_str()).unwrap_or("unknown")),
         "file_path": result.file_path,
         "file_hash": result.file_hash,
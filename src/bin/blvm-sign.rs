@@ -2,17 +2,28 @@
 //!
 //! Sign governance messages for Bitcoin Commons governance operations.
 
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::KeyFile;
+use blvm_sdk::cli::input::{parse_budget_allocations, InputError};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
-use blvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Signature};
-use blvm_sdk::sign_message as crypto_sign_message;
+use blvm_sdk::governance::messages::hash_artifacts_dir;
+use blvm_sdk::governance::ur::{self, REQUEST_UR_TYPE, SIGNATURE_UR_TYPE};
+use blvm_sdk::governance::{
+    BlockingSigner, GovernanceKeypair, GovernanceMessage, GovernanceSigner, SecurityRating,
+    Signature, SignatureEnvelope, SigningRequest,
+};
 use clap::{Parser, Subcommand};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::io::BufRead;
+use std::path::{Path, PathBuf};
 
 /// Sign governance messages
 #[derive(Parser, Debug)]
 #[command(name = "blvm-sign")]
 #[command(about = "Sign governance messages for Bitcoin Commons governance operations")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
     /// Output file for the signature
     #[arg(short, long, default_value = "signature.txt")]
@@ -22,13 +33,59 @@ struct Args {
     #[arg(short, long, default_value = "text")]
     format: OutputFormat,
 
-    /// Private key file
-    #[arg(short, long, required = true)]
-    key: String,
+    /// Private key file (required with `--signer key`)
+    #[arg(short, long)]
+    key: Option<String>,
+
+    /// Signing backend: key (private key file) or kms (cloud KMS)
+    #[arg(long, default_value = "key", value_parser = ["key", "kms"])]
+    signer: String,
+
+    /// KMS key ID or ARN (required with `--signer kms`)
+    #[arg(long)]
+    key_id: Option<String>,
 
     /// Message to sign
     #[command(subcommand)]
-    message: MessageCommand,
+    command: Command,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    #[command(flatten)]
+    Message(MessageCommand),
+    /// Carry signing requests and signatures across an air gap as UR parts
+    Request {
+        #[command(subcommand)]
+        action: RequestCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum RequestCommand {
+    /// Print an unsigned request as `ur:bllvm-request` parts
+    ExportUr {
+        /// Maximum fragment size in bytes
+        #[arg(long, default_value_t = ur::DEFAULT_MAX_FRAGMENT_LEN)]
+        max_fragment_len: usize,
+
+        /// Message to request a signature for
+        #[command(subcommand)]
+        message: MessageCommand,
+    },
+    /// Import UR parts from the other side of the air gap
+    ///
+    /// A `ur:bllvm-request` is shown, signed and answered with
+    /// `ur:bllvm-signature` parts. A `ur:bllvm-signature` is saved as the
+    /// signature file.
+    ImportUr {
+        /// Maximum fragment size in bytes for the signature parts
+        #[arg(long, default_value_t = ur::DEFAULT_MAX_FRAGMENT_LEN)]
+        max_fragment_len: usize,
+
+        /// UR parts in any order; read one per line from stdin if omitted
+        parts: Vec<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -42,6 +99,10 @@ enum MessageCommand {
         /// Commit hash
         #[arg(short, long, required = true)]
         commit: String,
+
+        /// Directory of release artifacts to hash into the message
+        #[arg(long)]
+        artifacts_dir: Option<PathBuf>,
     },
     /// Sign a module approval message
     Module {
@@ -52,12 +113,28 @@ enum MessageCommand {
         /// Module version
         #[arg(short, long, required = true)]
         version: String,
+
+        /// SHA256 of the security audit report
+        #[arg(long, requires_all = ["rating", "auditor"])]
+        audit_hash: Option<String>,
+
+        /// Security rating from the audit (critical, high, medium, low, minimal)
+        #[arg(long, requires = "audit_hash")]
+        rating: Option<SecurityRating>,
+
+        /// Who performed the audit
+        #[arg(long, requires = "audit_hash")]
+        auditor: Option<String>,
     },
     /// Sign a budget decision message
     Budget {
-        /// Amount in satoshis
-        #[arg(short, long, required = true)]
-        amount: u64,
+        /// Recipient address (repeat, each followed by its --amount)
+        #[arg(short, long = "recipient", required = true)]
+        recipients: Vec<String>,
+
+        /// Amount in satoshis for the recipient at the same position
+        #[arg(short, long = "amount", required = true)]
+        amounts: Vec<u64>,
 
         /// Purpose description
         #[arg(short, long, required = true)]
@@ -66,43 +143,35 @@ enum MessageCommand {
 }
 
 fn main() {
-    let args = Args::parse();
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
     let formatter = OutputFormatter::new(args.format.clone());
 
-    match sign_message(&args) {
-        Ok(signature) => {
-            let output = format_signature_output(&signature, &args, &formatter);
-            println!("{}", output);
-        }
+    let result = match &args.command {
+        Command::Message(message) => sign_message(&args, message)
+            .map(|signature| format_signature_output(&signature, &args, &formatter)),
+        Command::Request { action } => run_request(&args, action, &formatter),
+    };
+    match result {
+        Ok(output) => println!("{}", output),
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
 
-fn sign_message(args: &Args) -> Result<Signature, Box<dyn std::error::Error>> {
-    // Load the keypair
-    let keypair = load_keypair(&args.key)?;
+fn sign_message(
+    args: &Args,
+    message: &MessageCommand,
+) -> Result<Signature, Box<dyn std::error::Error>> {
+    // Load the signer
+    let signer = BlockingSigner::new(load_signer(args)?);
 
     // Create the message
-    let message = match &args.message {
-        MessageCommand::Release { version, commit } => GovernanceMessage::Release {
-            version: version.clone(),
-            commit_hash: commit.clone(),
-        },
-        MessageCommand::Module { name, version } => GovernanceMessage::ModuleApproval {
-            module_name: name.clone(),
-            version: version.clone(),
-        },
-        MessageCommand::Budget { amount, purpose } => GovernanceMessage::BudgetDecision {
-            amount: *amount,
-            purpose: purpose.clone(),
-        },
-    };
+    let message = build_message(message)?;
 
     // Sign the message
-    let signature = crypto_sign_message(&keypair.secret_key, &message.to_signing_bytes())?;
+    let signature = signer.sign_governance_message(&message)?;
 
     // Save signature to file
     save_signature(&signature, &args.output)?;
@@ -110,20 +179,158 @@ fn sign_message(args: &Args) -> Result<Signature, Box<dyn std::error::Error>> {
     Ok(signature)
 }
 
+fn build_message(
+    message: &MessageCommand,
+) -> Result<GovernanceMessage, Box<dyn std::error::Error>> {
+    Ok(match message {
+        MessageCommand::Release {
+            version,
+            commit,
+            artifacts_dir,
+        } => GovernanceMessage::Release {
+            version: version.clone(),
+            commit_hash: commit.clone(),
+            artifact_hashes: match artifacts_dir {
+                Some(dir) => hash_artifacts_dir(dir)?,
+                None => HashMap::new(),
+            },
+        },
+        MessageCommand::Module {
+            name,
+            version,
+            audit_hash,
+            rating,
+            auditor,
+        } => {
+            let message = GovernanceMessage::module_approval(name, version);
+            match (audit_hash, rating, auditor) {
+                (Some(hash), Some(rating), Some(auditor)) => {
+                    message.with_audit(hash, *rating, auditor)
+                }
+                _ => message,
+            }
+        }
+        MessageCommand::Budget {
+            recipients,
+            amounts,
+            purpose,
+        } => GovernanceMessage::BudgetDecision {
+            allocations: parse_budget_allocations(recipients, amounts)?,
+            rationale: purpose.clone(),
+        },
+    })
+}
+
+fn run_request(
+    args: &Args,
+    action: &RequestCommand,
+    formatter: &OutputFormatter,
+) -> Result<String, Box<dyn std::error::Error>> {
+    match action {
+        RequestCommand::ExportUr {
+            max_fragment_len,
+            message,
+        } => {
+            let request = SigningRequest::new(build_message(message)?);
+            let parts = request.to_ur_parts(*max_fragment_len)?;
+            format_parts_output(&request.request_id.to_string(), &parts, args, formatter)
+        }
+        RequestCommand::ImportUr {
+            max_fragment_len,
+            parts,
+        } => {
+            let parts = if parts.is_empty() {
+                read_parts_from_stdin()?
+            } else {
+                parts.clone()
+            };
+            let (ur_type, payload) = ur::decode(&parts)?;
+            match ur_type.as_str() {
+                REQUEST_UR_TYPE => {
+                    let request = SigningRequest::from_cbor(&payload)?;
+                    eprintln!(
+                        "Signing request {}:\n{}",
+                        request.request_id,
+                        serde_json::to_string_pretty(&request.message)?
+                    );
+                    let signer = BlockingSigner::new(load_signer(args)?);
+                    let signature = signer.sign_governance_message(&request.message)?;
+                    save_signature(&signature, &args.output)?;
+                    let envelope = SignatureEnvelope {
+                        request_id: request.request_id,
+                        signature,
+                        public_key: signer.public_key(),
+                    };
+                    let parts = envelope.to_ur_parts(*max_fragment_len)?;
+                    format_parts_output(&request.request_id.to_string(), &parts, args, formatter)
+                }
+                SIGNATURE_UR_TYPE => {
+                    let envelope = SignatureEnvelope::from_cbor(&payload)?;
+                    save_signature(&envelope.signature, &args.output)?;
+                    Ok(format_envelope_output(&envelope, args, formatter))
+                }
+                other => Err(format!("Unsupported UR type: {}", other).into()),
+            }
+        }
+    }
+}
+
+fn read_parts_from_stdin() -> Result<Vec<String>, Box<dyn std::error::Error>> {
+    let mut parts = Vec::new();
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            parts.push(line.to_string());
+        }
+    }
+    Ok(parts)
+}
+
+fn load_signer(args: &Args) -> Result<Box<dyn GovernanceSigner>, Box<dyn std::error::Error>> {
+    match args.signer.as_str() {
+        "kms" => {
+            let key_id = args
+                .key_id
+                .as_deref()
+                .ok_or("--key-id is required with --signer kms")?;
+            load_kms_signer(key_id)
+        }
+        _ => {
+            let key = args
+                .key
+                .as_deref()
+                .ok_or("--key is required with --signer key")?;
+            Ok(Box::new(load_keypair(key)?))
+        }
+    }
+}
+
+#[cfg(feature = "kms")]
+fn load_kms_signer(key_id: &str) -> Result<Box<dyn GovernanceSigner>, Box<dyn std::error::Error>> {
+    use blvm_sdk::governance::kms::{AwsCliKmsClient, KmsSigner};
+
+    let signer = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()?
+        .block_on(KmsSigner::connect(AwsCliKmsClient::new(), key_id))?;
+    Ok(Box::new(signer))
+}
+
+#[cfg(not(feature = "kms"))]
+fn load_kms_signer(_key_id: &str) -> Result<Box<dyn GovernanceSigner>, Box<dyn std::error::Error>> {
+    Err("KMS signing requires blvm-sdk built with the `kms` feature".into())
+}
+
 fn load_keypair(key_path: &str) -> Result<GovernanceKeypair, Box<dyn std::error::Error>> {
     if !Path::new(key_path).exists() {
-        return Err(format!("Key file not found: {}", key_path).into());
+        return Err(InputError::FileNotFound(key_path.to_string()).into());
     }
 
     let key_data = fs::read_to_string(key_path)?;
-    let key_json: serde_json::Value = serde_json::from_str(&key_data)?;
-
-    let secret_key_hex = key_json["secret_key"]
-        .as_str()
-        .ok_or("Invalid key file format")?;
-
-    let secret_key_bytes = hex::decode(secret_key_hex)?;
-    GovernanceKeypair::from_secret_key(&secret_key_bytes)
+    let key_file: KeyFile = serde_json::from_str(&key_data)?;
+    key_file
+        .keypair()
         .map_err(|e| format!("Invalid secret key: {}", e).into())
 }
 
@@ -132,7 +339,7 @@ fn save_signature(
     output_path: &str,
 ) -> Result<(), Box<dyn std::error::Error>> {
     let signature_data = serde_json::json!({
-        "signature": hex::encode(signature.to_bytes()),
+        "signature": signature,
         "created_at": chrono::Utc::now().to_rfc3339(),
     });
 
@@ -142,6 +349,47 @@ fn save_signature(
     Ok(())
 }
 
+fn format_parts_output(
+    request_id: &str,
+    parts: &[String],
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> Result<String, Box<dyn std::error::Error>> {
+    if args.format == OutputFormat::Json {
+        let output_data = serde_json::json!({
+            "request_id": request_id,
+            "parts": parts,
+        });
+        Ok(formatter.format(&output_data)?)
+    } else {
+        Ok(parts.join("\n"))
+    }
+}
+
+fn format_envelope_output(
+    envelope: &SignatureEnvelope,
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> String {
+    if args.format == OutputFormat::Json {
+        let output_data = serde_json::json!({
+            "success": true,
+            "request_id": envelope.request_id,
+            "signature": envelope.signature,
+            "public_key": envelope.public_key,
+            "output_file": args.output,
+        });
+        formatter
+            .format(&output_data)
+            .unwrap_or_else(|_| "{}".to_string())
+    } else {
+        format!(
+            "Imported signature for request {}\nSignature: {}\nPublic key: {}\nSaved to: {}\n",
+            envelope.request_id, envelope.signature, envelope.public_key, args.output
+        )
+    }
+}
+
 fn format_signature_output(
     signature: &Signature,
     args: &Args,
@@ -150,7 +398,7 @@ fn format_signature_output(
     if args.format == OutputFormat::Json {
         let output_data = serde_json::json!({
             "success": true,
-            "signature": hex::encode(signature.to_bytes()),
+            "signature": signature,
             "output_file": args.output,
         });
         formatter
@@ -5,38 +5,59 @@
 //! This tool verifies that binaries and verification bundles are signed by
 //! authorized maintainers and match their cryptographic hashes.
 
-use blvm_sdk::cli::input::{parse_comma_separated, parse_threshold};
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::{PublicKeyFile, SignatureCheck, SignatureFile, VerificationReport};
+use blvm_sdk::cli::input::{parse_list_arg, parse_threshold_and_total};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
-use blvm_sdk::governance::{Multisig, PublicKey, Signature};
-use clap::{Parser, Subcommand};
+use blvm_sdk::governance::{GovernanceError, Multisig, PublicKey, Signature};
+use clap::{ArgAction, Parser, Subcommand};
 use sha2::{Digest, Sha256};
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Verify binary and verification bundle signatures
 #[derive(Parser, Debug)]
 #[command(name = "blvm-verify-binary")]
 #[command(about = "Verify binary and verification bundle signatures for Bitcoin Commons releases")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
-    /// Output format (text, json)
+    /// Output format (text, json, table, ndjson)
     #[arg(short, long, default_value = "text")]
     format: OutputFormat,
 
+    /// Do not truncate long values (e.g. hashes) in table output
+    #[arg(long)]
+    wide: bool,
+
+    /// Suppress all output on stdout; rely on the exit status
+    #[arg(short, long)]
+    quiet: bool,
+
     /// What to verify
     #[command(subcommand)]
     target: VerifyTarget,
 
-    /// Signature files (comma-separated)
+    /// Signature files (comma-separated; @file reads one path per line)
     #[arg(short, long, required = true)]
     signatures: String,
 
-    /// Threshold (e.g., "6-of-7")
+    /// Threshold as M-of-N, M/N, M:N or M,N (e.g., "6-of-7"); a bare M uses the
+    /// number of public keys as N
     #[arg(short, long)]
     threshold: Option<String>,
 
-    /// Public key files (comma-separated)
+    /// Public key files (comma-separated; @file reads one path per line)
     #[arg(short, long)]
     pubkeys: Option<String>,
+
+    /// Also write a JSON verification report to this file, whatever the --format
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+
+    /// Exit non-zero when verification fails; `false` only reports the result
+    #[arg(long, value_name = "BOOL", default_value_t = true, action = ArgAction::Set)]
+    fail_on_invalid: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -74,7 +95,7 @@ enum VerifyTarget {
         build_config_hash: Option<String>,
 
         /// Orange Paper specification hash (SHA256)
-        #[arg(short, long)]
+        #[arg(long)]
         spec_hash: Option<String>,
     },
     /// Verify a SHA256SUMS file
@@ -90,20 +111,31 @@ enum VerifyTarget {
 }
 
 fn main() {
-    let args = Args::parse();
-    let formatter = OutputFormatter::new(args.format.clone());
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
+    let formatter = OutputFormatter::new(args.format.clone()).with_wide(args.wide);
 
-    match verify_target(&args) {
+    match verify_target(&args, &formatter) {
         Ok(result) => {
-            let output = format_verification_output(&result, &args, &formatter);
-            println!("{}", output);
-            if !result.valid {
-                std::process::exit(1);
+            if let Some(path) = &args.output_file {
+                if let Err(e) = result.report.write(path) {
+                    eprintln!("{}", formatter.format_error(&e));
+                    ExitCode::from_error(&e).exit();
+                }
+            }
+            if !args.quiet {
+                let output = format_verification_output(&result, &args, &formatter);
+                println!("{}", output);
+            }
+            if !args.fail_on_invalid {
+                return;
+            }
+            if !result.valid || !result.errors.is_empty() {
+                ExitCode::VerificationFailed.exit();
             }
         }
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
@@ -117,16 +149,20 @@ struct VerificationResult {
     invalid_signatures: usize,
     threshold_met: bool,
     errors: Vec<String>,
+    report: VerificationReport,
 }
 
-fn verify_target(args: &Args) -> Result<VerificationResult, Box<dyn std::error::Error>> {
+fn verify_target(
+    args: &Args,
+    formatter: &OutputFormatter,
+) -> Result<VerificationResult, Box<dyn std::error::Error>> {
     // Load signatures
-    let signature_files = parse_comma_separated(&args.signatures);
+    let signature_files = parse_list_arg(&args.signatures)?;
     let signatures = load_signatures(&signature_files)?;
 
     // Load public keys if provided
     let public_keys = if let Some(pubkey_files) = &args.pubkeys {
-        let pubkey_files = parse_comma_separated(pubkey_files);
+        let pubkey_files = parse_list_arg(pubkey_files)?;
         load_public_keys(&pubkey_files)?
     } else {
         Vec::new()
@@ -199,13 +235,16 @@ fn verify_target(args: &Args) -> Result<VerificationResult, Box<dyn std::error::
     let mut valid_signatures = 0;
     let mut invalid_signatures = 0;
     let mut errors = Vec::new();
+    let mut checks = Vec::new();
 
-    for signature in &signatures {
+    for (index, signature) in signatures.iter().enumerate() {
         let mut verified = false;
+        let mut signer = None;
         for public_key in &public_keys {
             match blvm_sdk::governance::verify_signature(signature, &message_bytes, public_key) {
                 Ok(true) => {
                     verified = true;
+                    signer = Some(public_key.clone());
                     break;
                 }
                 Ok(false) => continue,
@@ -220,11 +259,27 @@ fn verify_target(args: &Args) -> Result<VerificationResult, Box<dyn std::error::
         } else {
             invalid_signatures += 1;
         }
+        checks.push(SignatureCheck {
+            file: signature_files[index].clone(),
+            valid: verified,
+            signer,
+        });
+
+        if !args.quiet {
+            formatter.emit(&serde_json::json!({
+                "record": "signature",
+                "file_path": file_path,
+                "signature_file": signature_files.get(index),
+                "valid": verified,
+            }))?;
+        }
     }
 
     // Check multisig threshold if provided
+    let mut threshold = None;
     let threshold_met = if let Some(threshold_str) = &args.threshold {
-        let (threshold, total) = parse_threshold(threshold_str)?;
+        let (required, total) = parse_threshold_and_total(threshold_str, Some(public_keys.len()))?;
+        threshold = Some(format!("{}-of-{}", required, total));
         if public_keys.len() != total {
             errors.push(format!(
                 "Expected {} public keys, got {}",
@@ -233,9 +288,10 @@ fn verify_target(args: &Args) -> Result<VerificationResult, Box<dyn std::error::
             ));
             false
         } else {
-            let multisig = Multisig::new(threshold, total, public_keys)?;
+            let multisig = Multisig::new(required, total, public_keys.clone())?;
             match multisig.verify(&message_bytes, &signatures) {
                 Ok(result) => result,
+                Err(GovernanceError::InsufficientSignatures { .. }) => false,
                 Err(e) => {
                     errors.push(format!("Multisig verification error: {}", e));
                     false
@@ -246,14 +302,27 @@ fn verify_target(args: &Args) -> Result<VerificationResult, Box<dyn std::error::
         valid_signatures > 0
     };
 
+    let valid = threshold_met && invalid_signatures == 0;
+    let report = VerificationReport {
+        verified_at: chrono::Utc::now().to_rfc3339(),
+        subject: file_path.clone(),
+        file_hash: Some(file_hash.clone()),
+        public_keys,
+        signatures: checks,
+        threshold,
+        threshold_met,
+        valid: valid && errors.is_empty(),
+        errors: errors.clone(),
+    };
     Ok(VerificationResult {
-        valid: threshold_met && invalid_signatures == 0,
+        valid,
         file_path,
         file_hash,
         valid_signatures,
         invalid_signatures,
         threshold_met,
         errors,
+        report,
     })
 }
 
@@ -264,19 +333,16 @@ fn load_signatures(
 
     for file_path in signature_files {
         if !Path::new(file_path).exists() {
-            return Err(format!("Signature file not found: {}", file_path).into());
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Signature file not found: {}", file_path),
+            )
+            .into());
         }
 
         let sig_data = fs::read_to_string(file_path)?;
-        let sig_json: serde_json::Value = serde_json::from_str(&sig_data)?;
-
-        let signature_hex = sig_json["signature"]
-            .as_str()
-            .ok_or("Invalid signature file format")?;
-
-        let signature_bytes = hex::decode(signature_hex)?;
-        let signature = Signature::from_bytes(&signature_bytes)?;
-        signatures.push(signature);
+        let sig_file: SignatureFile = serde_json::from_str(&sig_data)?;
+        signatures.push(sig_file.signature);
     }
 
     Ok(signatures)
@@ -287,19 +353,16 @@ fn load_public_keys(pubkey_files: &[String]) -> Result<Vec<PublicKey>, Box<dyn s
 
     for file_path in pubkey_files {
         if !Path::new(file_path).exists() {
-            return Err(format!("Public key file not found: {}", file_path).into());
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Public key file not found: {}", file_path),
+            )
+            .into());
         }
 
         let key_data = fs::read_to_string(file_path)?;
-        let key_json: serde_json::Value = serde_json::from_str(&key_data)?;
-
-        let pubkey_hex = key_json["public_key"]
-            .as_str()
-            .ok_or("Invalid public key file format")?;
-
-        let pubkey_bytes = hex::decode(pubkey_hex)?;
-        let public_key = PublicKey::from_bytes(&pubkey_bytes)?;
-        public_keys.push(public_key);
+        let key_file: PublicKeyFile = serde_json::from_str(&key_data)?;
+        public_keys.push(key_file.public_key);
     }
 
     Ok(public_keys)
@@ -310,7 +373,21 @@ fn format_verification_output(
     args: &Args,
     formatter: &OutputFormatter,
 ) -> String {
-    if args.format == OutputFormat::Json {
+    if args.format == OutputFormat::Ndjson {
+        let summary = serde_json::json!({
+            "record": "summary",
+            "success": result.valid,
+            "file_path": result.file_path,
+            "file_hash": result.file_hash,
+            "valid_signatures": result.valid_signatures,
+            "invalid_signatures": result.invalid_signatures,
+            "threshold_met": result.threshold_met,
+            "errors": result.errors,
+        });
+        formatter
+            .format(&summary)
+            .unwrap_or_else(|_| "{}".to_string())
+    } else if args.format == OutputFormat::Json {
         let output_data = serde_json::json!({
             "success": result.valid,
             "file_path": result.file_path,
@@ -323,6 +400,18 @@ fn format_verification_output(
         formatter
             .format(&output_data)
             .unwrap_or_else(|_| "{}".to_string())
+    } else if args.format == OutputFormat::Table {
+        let rows = vec![vec![
+            result.file_path.clone(),
+            result.file_hash.clone(),
+            format!(
+                "{}/{}",
+                result.valid_signatures,
+                result.valid_signatures + result.invalid_signatures
+            ),
+            if result.valid { "PASSED" } else { "FAILED" }.to_string(),
+        ]];
+        formatter.format_table(&["FILE", "HASH", "SIGNATURES", "STATUS"], &rows)
     } else {
         let mut output = "Verification Results\n".to_string();
         output.push_str(&format!("File: {}\n", result.file_path));
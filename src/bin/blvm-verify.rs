@@ -2,37 +2,60 @@
 //!
 //! Verify governance signatures and multisig thresholds.
 
-use blvm_sdk::cli::input::{parse_comma_separated, parse_threshold};
+use blvm_sdk::cli::config::CliConfig;
+use blvm_sdk::cli::exit::{self, ExitCode, EXIT_CODES_HELP};
+use blvm_sdk::cli::files::{PublicKeyFile, SignatureCheck, SignatureFile, VerificationReport};
+use blvm_sdk::cli::input::{
+    parse_budget_allocations, parse_list_arg, parse_threshold_and_total, InputError,
+};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
-use blvm_sdk::governance::{GovernanceMessage, Multisig, PublicKey, Signature};
-use clap::{Parser, Subcommand};
+use blvm_sdk::governance::messages::hash_artifacts_dir;
+use blvm_sdk::governance::{
+    GovernanceError, GovernanceMessage, Multisig, PublicKey, SecurityRating, Signature,
+};
+use clap::{ArgAction, Parser, Subcommand};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 /// Verify governance signatures
 #[derive(Parser, Debug)]
 #[command(name = "blvm-verify")]
 #[command(about = "Verify governance signatures and multisig thresholds")]
+#[command(after_long_help = EXIT_CODES_HELP)]
 struct Args {
-    /// Output format (text, json)
+    /// Output format (text, json, csv)
     #[arg(short, long, default_value = "text")]
     format: OutputFormat,
 
+    /// Suppress all output on stdout; rely on the exit status
+    #[arg(short, long)]
+    quiet: bool,
+
     /// Message to verify
     #[command(subcommand)]
     message: MessageCommand,
 
-    /// Signature files (comma-separated)
+    /// Signature files (comma-separated; @file reads one path per line)
     #[arg(short, long, required = true)]
     signatures: String,
 
-    /// Threshold (e.g., "3-of-5")
+    /// Threshold as M-of-N, M/N, M:N or M,N (e.g., "3-of-5"); a bare M uses the
+    /// number of public keys as N
     #[arg(short, long)]
     threshold: Option<String>,
 
-    /// Public key files (comma-separated)
+    /// Public key files (comma-separated; @file reads one path per line)
     #[arg(short, long)]
     pubkeys: Option<String>,
+
+    /// Also write a JSON verification report to this file, whatever the --format
+    #[arg(long, value_name = "PATH")]
+    output_file: Option<PathBuf>,
+
+    /// Exit non-zero when verification fails; `false` only reports the result
+    #[arg(long, value_name = "BOOL", default_value_t = true, action = ArgAction::Set)]
+    fail_on_invalid: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -46,6 +69,10 @@ enum MessageCommand {
         /// Commit hash
         #[arg(short, long, required = true)]
         commit: String,
+
+        /// Directory of release artifacts to hash into the message
+        #[arg(long)]
+        artifacts_dir: Option<PathBuf>,
     },
     /// Verify a module approval message
     Module {
@@ -56,12 +83,28 @@ enum MessageCommand {
         /// Module version
         #[arg(short, long, required = true)]
         version: String,
+
+        /// SHA256 of the security audit report
+        #[arg(long, requires_all = ["rating", "auditor"])]
+        audit_hash: Option<String>,
+
+        /// Security rating from the audit (critical, high, medium, low, minimal)
+        #[arg(long, requires = "audit_hash")]
+        rating: Option<SecurityRating>,
+
+        /// Who performed the audit
+        #[arg(long, requires = "audit_hash")]
+        auditor: Option<String>,
     },
     /// Verify a budget decision message
     Budget {
-        /// Amount in satoshis
-        #[arg(short, long, required = true)]
-        amount: u64,
+        /// Recipient address (repeat, each followed by its --amount)
+        #[arg(short, long = "recipient", required = true)]
+        recipients: Vec<String>,
+
+        /// Amount in satoshis for the recipient at the same position
+        #[arg(short, long = "amount", required = true)]
+        amounts: Vec<u64>,
 
         /// Purpose description
         #[arg(short, long, required = true)]
@@ -70,17 +113,28 @@ enum MessageCommand {
 }
 
 fn main() {
-    let args = Args::parse();
+    let args: Args = exit::parse_args(&CliConfig::load_or_exit());
     let formatter = OutputFormatter::new(args.format.clone());
 
     match verify_message(&args) {
         Ok(result) => {
-            let output = format_verification_output(&result, &args, &formatter);
-            println!("{}", output);
+            if let Some(path) = &args.output_file {
+                if let Err(e) = result.report.write(path) {
+                    eprintln!("{}", formatter.format_error(&e));
+                    ExitCode::from_error(&e).exit();
+                }
+            }
+            if !args.quiet {
+                let output = format_verification_output(&result, &args, &formatter);
+                println!("{}", output);
+            }
+            if args.fail_on_invalid && !result.report.valid {
+                ExitCode::VerificationFailed.exit();
+            }
         }
         Err(e) => {
             eprintln!("{}", formatter.format_error(&*e));
-            std::process::exit(1);
+            ExitCode::from_error(&*e).exit();
         }
     }
 }
@@ -88,27 +142,50 @@ fn main() {
 fn verify_message(args: &Args) -> Result<VerificationResult, Box<dyn std::error::Error>> {
     // Create the message
     let message = match &args.message {
-        MessageCommand::Release { version, commit } => GovernanceMessage::Release {
+        MessageCommand::Release {
+            version,
+            commit,
+            artifacts_dir,
+        } => GovernanceMessage::Release {
             version: version.clone(),
             commit_hash: commit.clone(),
+            artifact_hashes: match artifacts_dir {
+                Some(dir) => hash_artifacts_dir(dir)?,
+                None => HashMap::new(),
+            },
         },
-        MessageCommand::Module { name, version } => GovernanceMessage::ModuleApproval {
-            module_name: name.clone(),
-            version: version.clone(),
-        },
-        MessageCommand::Budget { amount, purpose } => GovernanceMessage::BudgetDecision {
-            amount: *amount,
-            purpose: purpose.clone(),
+        MessageCommand::Module {
+            name,
+            version,
+            audit_hash,
+            rating,
+            auditor,
+        } => {
+            let message = GovernanceMessage::module_approval(name, version);
+            match (audit_hash, rating, auditor) {
+                (Some(hash), Some(rating), Some(auditor)) => {
+                    message.with_audit(hash, *rating, auditor)
+                }
+                _ => message,
+            }
+        }
+        MessageCommand::Budget {
+            recipients,
+            amounts,
+            purpose,
+        } => GovernanceMessage::BudgetDecision {
+            allocations: parse_budget_allocations(recipients, amounts)?,
+            rationale: purpose.clone(),
         },
     };
 
     // Load signatures
-    let signature_files = parse_comma_separated(&args.signatures);
+    let signature_files = parse_list_arg(&args.signatures)?;
     let signatures = load_signatures(&signature_files)?;
 
     // Load public keys if provided
     let public_keys = if let Some(pubkey_files) = &args.pubkeys {
-        let pubkey_files = parse_comma_separated(pubkey_files);
+        let pubkey_files = parse_list_arg(pubkey_files)?;
         load_public_keys(&pubkey_files)?
     } else {
         Vec::new()
@@ -116,44 +193,66 @@ fn verify_message(args: &Args) -> Result<VerificationResult, Box<dyn std::error:
 
     // Verify signatures
     let message_bytes = message.to_signing_bytes();
-    let mut valid_signatures = 0;
-    let mut invalid_signatures = 0;
+    let mut checks = Vec::new();
 
-    for signature in &signatures {
-        let mut verified = false;
+    for (signature, file) in signatures.iter().zip(&signature_files) {
+        let mut signer = None;
         for public_key in &public_keys {
             if blvm_sdk::governance::verify_signature(signature, &message_bytes, public_key)? {
-                verified = true;
+                signer = Some(public_key.clone());
                 break;
             }
         }
-        if verified {
-            valid_signatures += 1;
-        } else {
-            invalid_signatures += 1;
-        }
+        checks.push(SignatureCheck {
+            file: file.clone(),
+            valid: signer.is_some(),
+            signer,
+        });
     }
+    let valid_signatures = checks.iter().filter(|check| check.valid).count();
+    let invalid_signatures = checks.len() - valid_signatures;
 
     // Check multisig threshold if provided
+    let mut threshold = None;
     let threshold_met = if let Some(threshold_str) = &args.threshold {
-        let (threshold, total) = parse_threshold(threshold_str)?;
+        let (required, total) = parse_threshold_and_total(threshold_str, Some(public_keys.len()))?;
         if public_keys.len() != total {
-            return Err(
-                format!("Expected {} public keys, got {}", total, public_keys.len()).into(),
-            );
+            return Err(InputError::InvalidValue(format!(
+                "Expected {} public keys, got {}",
+                total,
+                public_keys.len()
+            ))
+            .into());
         }
+        threshold = Some(format!("{}-of-{}", required, total));
 
-        let multisig = Multisig::new(threshold, total, public_keys)?;
-        multisig.verify(&message_bytes, &signatures)?
+        let multisig = Multisig::new(required, total, public_keys.clone())?;
+        match multisig.verify(&message_bytes, &signatures) {
+            Ok(result) => result,
+            Err(GovernanceError::InsufficientSignatures { .. }) => false,
+            Err(e) => return Err(e.into()),
+        }
     } else {
         valid_signatures > 0
     };
 
+    let report = VerificationReport {
+        verified_at: chrono::Utc::now().to_rfc3339(),
+        subject: message.description(),
+        file_hash: None,
+        public_keys,
+        signatures: checks,
+        threshold,
+        threshold_met,
+        valid: threshold_met && invalid_signatures == 0,
+        errors: Vec::new(),
+    };
     Ok(VerificationResult {
         message,
         valid_signatures,
         invalid_signatures,
         threshold_met,
+        report,
     })
 }
 
@@ -163,6 +262,7 @@ struct VerificationResult {
     valid_signatures: usize,
     invalid_signatures: usize,
     threshold_met: bool,
+    report: VerificationReport,
 }
 
 fn load_signatures(
@@ -172,19 +272,16 @@ fn load_signatures(
 
     for file_path in signature_files {
         if !Path::new(file_path).exists() {
-            return Err(format!("Signature file not found: {}", file_path).into());
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Signature file not found: {}", file_path),
+            )
+            .into());
         }
 
         let sig_data = fs::read_to_string(file_path)?;
-        let sig_json: serde_json::Value = serde_json::from_str(&sig_data)?;
-
-        let signature_hex = sig_json["signature"]
-            .as_str()
-            .ok_or("Invalid signature file format")?;
-
-        let signature_bytes = hex::decode(signature_hex)?;
-        let signature = Signature::from_bytes(&signature_bytes)?;
-        signatures.push(signature);
+        let sig_file: SignatureFile = serde_json::from_str(&sig_data)?;
+        signatures.push(sig_file.signature);
     }
 
     Ok(signatures)
@@ -195,19 +292,16 @@ fn load_public_keys(pubkey_files: &[String]) -> Result<Vec<PublicKey>, Box<dyn s
 
     for file_path in pubkey_files {
         if !Path::new(file_path).exists() {
-            return Err(format!("Public key file not found: {}", file_path).into());
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                format!("Public key file not found: {}", file_path),
+            )
+            .into());
         }
 
         let key_data = fs::read_to_string(file_path)?;
-        let key_json: serde_json::Value = serde_json::from_str(&key_data)?;
-
-        let pubkey_hex = key_json["public_key"]
-            .as_str()
-            .ok_or("Invalid public key file format")?;
-
-        let pubkey_bytes = hex::decode(pubkey_hex)?;
-        let public_key = PublicKey::from_bytes(&pubkey_bytes)?;
-        public_keys.push(public_key);
+        let key_file: PublicKeyFile = serde_json::from_str(&key_data)?;
+        public_keys.push(key_file.public_key);
     }
 
     Ok(public_keys)
@@ -229,6 +323,16 @@ fn format_verification_output(
         formatter
             .format(&output_data)
             .unwrap_or_else(|_| "{}".to_string())
+    } else if args.format == OutputFormat::Csv {
+        formatter
+            .format_key_value(&[
+                ("message", &result.message.description()),
+                ("valid_signatures", &result.valid_signatures.to_string()),
+                ("invalid_signatures", &result.invalid_signatures.to_string()),
+                ("threshold_met", &result.threshold_met.to_string()),
+            ])
+            .trim_end()
+            .to_string()
     } else {
         let mut output = "Verification Results\n".to_string();
         output.push_str(&format!("Message: {}\n", result.message.description()));
@@ -0,0 +1,96 @@
+//! # Shell Completions
+//!
+//! Shell completion script generation for CLI tools.
+//!
+//! Each tool passes its clap `Parser` type, so the completion generator and
+//! the argument parser share a single command definition.
+
+use clap::CommandFactory;
+pub use clap_complete::Shell;
+use std::ffi::OsString;
+use std::io::Write;
+
+/// Hidden flag that requests a completion script instead of running the tool
+pub const GENERATE_COMPLETIONS_FLAG: &str = "--generate-completions";
+
+/// Write the completion script for `P` in the given shell
+pub fn generate_completions<P: CommandFactory>(shell: Shell, out: &mut dyn Write) {
+    let mut command = P::command();
+    let bin_name = command.get_name().to_string();
+    clap_complete::generate(shell, &mut command, bin_name, out);
+}
+
+/// Find a `--generate-completions <shell>` request in the argument list
+///
+/// Returns `None` if the flag is absent, or an error message if the shell
+/// is missing or unsupported.
+pub fn requested_shell<I>(args: I) -> Option<Result<Shell, String>>
+where
+    I: IntoIterator<Item = OsString>,
+{
+    let mut args = args.into_iter().skip(1);
+    while let Some(arg) = args.next() {
+        let arg = arg.to_string_lossy();
+        let value = if let Some(value) = arg.strip_prefix("--generate-completions=") {
+            Some(value.to_string())
+        } else if arg == GENERATE_COMPLETIONS_FLAG {
+            args.next().map(|v| v.to_string_lossy().to_string())
+        } else {
+            continue;
+        };
+
+        return Some(match value {
+            Some(value) => value
+                .parse::<Shell>()
+                .map_err(|_| format!("Unsupported shell: {}", value)),
+            None => Err("Missing shell for --generate-completions".to_string()),
+        });
+    }
+    None
+}
+
+/// Print completions and exit if `--generate-completions <shell>` was passed
+///
+/// Call at the top of `main`, before parsing, so required arguments do not
+/// get in the way of completion generation. An unsupported shell is reported
+/// as a clap usage error so each tool can map it to its own exit status.
+pub fn generate_if_requested<P: CommandFactory>() -> Result<(), clap::Error> {
+    match requested_shell(std::env::args_os()) {
+        Some(Ok(shell)) => {
+            generate_completions::<P>(shell, &mut std::io::stdout());
+            std::process::exit(0);
+        }
+        Some(Err(e)) => Err(P::command().error(clap::error::ErrorKind::InvalidValue, e)),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<OsString> {
+        list.iter().map(OsString::from).collect()
+    }
+
+    #[test]
+    fn test_requested_shell() {
+        assert!(requested_shell(args(&["tool", "--format", "json"])).is_none());
+        assert_eq!(
+            requested_shell(args(&["tool", "--generate-completions", "bash"])),
+            Some(Ok(Shell::Bash))
+        );
+        assert_eq!(
+            requested_shell(args(&["tool", "--generate-completions=zsh"])),
+            Some(Ok(Shell::Zsh))
+        );
+        assert!(matches!(
+            requested_shell(args(&["tool", "--generate-completions", "tcsh"])),
+            Some(Err(_))
+        ));
+        assert!(matches!(
+            requested_shell(args(&["tool", "--generate-completions"])),
+            Some(Err(_))
+        ));
+    }
+}
@@ -0,0 +1,601 @@
+//! # CLI Configuration
+//!
+//! Per-tool defaults loaded from `~/.config/bllvm/config.toml` (falling back
+//! to `~/.bllvm/config.toml`).
+//!
+//! Settings are layered onto each tool's clap command so that precedence is
+//! always: command-line flag > environment variable > config file > built-in
+//! default.
+//!
+//! ```toml
+//! format = "json"
+//! pubkeys = "roster/maintainers.txt"
+//! threshold = "6-of-7"
+//!
+//! [tools.blvm-compose]
+//! modules_dir = "/var/lib/bllvm/modules"
+//! ```
+//!
+//! The global keys `default_key_file`, `default_modules_dir`,
+//! `default_output_format` and `default_network` are accepted as aliases for
+//! `key`, `modules_dir`, `format` and `network`.
+
+use crate::cli::input::InputError;
+use crate::cli::output::OutputFormat;
+use crate::governance::NetworkType;
+use clap::{Command, Parser};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Environment variable overriding the config file location
+pub const CONFIG_ENV_VAR: &str = "BLLVM_CONFIG";
+
+/// Exit status when the config file cannot be loaded (sysexits EX_CONFIG)
+pub const EXIT_CONFIG_ERROR: i32 = 78;
+
+/// A setting that can be supplied by config file or environment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Setting {
+    /// Config file key (also the clap argument id)
+    pub key: &'static str,
+    /// Environment variable name
+    pub env: &'static str,
+    /// Short description shown in help text
+    pub description: &'static str,
+    /// Whether the value should be masked when displayed
+    pub sensitive: bool,
+}
+
+/// All recognized settings
+pub const SETTINGS: &[Setting] = &[
+    Setting {
+        key: "format",
+        env: "BLLVM_OUTPUT_FORMAT",
+        description: "Default output format (text, json, table, ndjson)",
+        sensitive: false,
+    },
+    Setting {
+        key: "pubkeys",
+        env: "BLLVM_PUBKEYS",
+        description: "Default public key list",
+        sensitive: false,
+    },
+    Setting {
+        key: "threshold",
+        env: "BLLVM_THRESHOLD",
+        description: "Default signature threshold (e.g. 3-of-5, 3/5)",
+        sensitive: false,
+    },
+    Setting {
+        key: "key",
+        env: "BLLVM_KEY_FILE",
+        description: "Default governance key file",
+        sensitive: true,
+    },
+    Setting {
+        key: "modules_dir",
+        env: "BLLVM_MODULES_DIR",
+        description: "Default modules directory",
+        sensitive: false,
+    },
+    Setting {
+        key: "network",
+        env: "BLLVM_NETWORK",
+        description: "Default network (mainnet, testnet, regtest)",
+        sensitive: false,
+    },
+    Setting {
+        key: "log_dir",
+        env: "BLLVM_LOG_DIR",
+        description: "Module log directory (default ~/.bllvm/logs)",
+        sensitive: false,
+    },
+    Setting {
+        key: "socket_dir",
+        env: "BLLVM_SOCKET_DIR",
+        description: "Module IPC socket directory, used to detect running modules",
+        sensitive: false,
+    },
+];
+
+/// Placeholder shown instead of sensitive values
+const MASKED_VALUE: &str = "********";
+
+/// Help text listing every recognized environment variable
+pub fn env_help() -> String {
+    let width = SETTINGS
+        .iter()
+        .map(|setting| setting.env.len())
+        .chain(std::iter::once(CONFIG_ENV_VAR.len()))
+        .max()
+        .unwrap_or(0);
+    let mut help = String::from("Environment variables:\n");
+    help.push_str(&format!(
+        "  {:<width$}  Config file location\n",
+        CONFIG_ENV_VAR
+    ));
+    for setting in SETTINGS {
+        help.push_str(&format!(
+            "  {:<width$}  {}\n",
+            setting.env, setting.description
+        ));
+    }
+    help.push_str("\nPrecedence: flag > environment variable > config file > built-in default");
+    help
+}
+
+/// Recognized environment variables and their current values
+///
+/// Sensitive values (e.g. key material) are masked.
+pub fn env_vars() -> Vec<(&'static str, Option<String>)> {
+    let current = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+    std::iter::once((CONFIG_ENV_VAR, current(CONFIG_ENV_VAR)))
+        .chain(SETTINGS.iter().map(|setting| {
+            let value = current(setting.env);
+            let value = if setting.sensitive {
+                value.map(|_| MASKED_VALUE.to_string())
+            } else {
+                value
+            };
+            (setting.env, value)
+        }))
+        .collect()
+}
+
+/// Global config key aliases and the setting each refers to
+const ALIASES: &[(&str, &str)] = &[
+    ("default_key_file", "key"),
+    ("default_modules_dir", "modules_dir"),
+    ("default_output_format", "format"),
+    ("default_network", "network"),
+];
+
+/// Resolve a config key (or alias) to its setting key
+pub fn canonical_key(key: &str) -> Option<&'static str> {
+    SETTINGS
+        .iter()
+        .map(|s| s.key)
+        .find(|k| *k == key)
+        .or_else(|| ALIASES.iter().find(|(a, _)| *a == key).map(|(_, k)| *k))
+}
+
+/// Global defaults shared by all CLI tools
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct GlobalConfig {
+    /// Default private key file
+    pub default_key_file: Option<PathBuf>,
+    /// Default modules directory
+    pub default_modules_dir: Option<PathBuf>,
+    /// Default output format
+    pub default_output_format: Option<OutputFormat>,
+    /// Default network (mainnet, testnet, regtest)
+    pub default_network: Option<String>,
+}
+
+impl GlobalConfig {
+    /// Extract the global defaults from a loaded CLI config
+    pub fn from_cli_config(config: &CliConfig) -> Self {
+        Self {
+            default_key_file: config.defaults.get("key").map(PathBuf::from),
+            default_modules_dir: config.defaults.get("modules_dir").map(PathBuf::from),
+            default_output_format: config.defaults.get("format").and_then(|f| f.parse().ok()),
+            default_network: config.defaults.get("network").cloned(),
+        }
+    }
+}
+
+/// Load the global config file, if one exists and parses
+pub fn load_global_config() -> Option<GlobalConfig> {
+    let config = CliConfig::load().ok()?;
+    config.path.as_ref()?;
+    Some(GlobalConfig::from_cli_config(&config))
+}
+
+/// Where an effective setting value came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingSource {
+    /// Environment variable
+    Env,
+    /// Tool-specific section of the config file
+    ToolFile,
+    /// Top-level section of the config file
+    File,
+    /// Not set; the tool's built-in default applies
+    Default,
+}
+
+impl std::fmt::Display for SettingSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SettingSource::Env => write!(f, "env"),
+            SettingSource::ToolFile => write!(f, "config (tool)"),
+            SettingSource::File => write!(f, "config"),
+            SettingSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+/// Loaded CLI configuration file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CliConfig {
+    /// Path the configuration was loaded from (if any)
+    pub path: Option<PathBuf>,
+    /// Defaults shared by all tools
+    pub defaults: BTreeMap<String, String>,
+    /// Per-tool defaults, keyed by binary name
+    pub tools: BTreeMap<String, BTreeMap<String, String>>,
+    /// Warnings produced while loading (e.g. unknown keys)
+    pub warnings: Vec<String>,
+}
+
+impl CliConfig {
+    /// Default config file location
+    ///
+    /// Uses `$XDG_CONFIG_HOME/bllvm/config.toml` (or `~/.config/...`), unless
+    /// only the legacy `~/.bllvm/config.toml` exists.
+    pub fn default_path() -> Option<PathBuf> {
+        let home = std::env::var_os("HOME").map(PathBuf::from);
+        let xdg = std::env::var_os("XDG_CONFIG_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(PathBuf::from)
+            .or_else(|| home.as_ref().map(|home| home.join(".config")))
+            .map(|base| base.join("bllvm").join("config.toml"));
+        let legacy = home.map(|home| home.join(".bllvm").join("config.toml"));
+
+        match (xdg, legacy) {
+            (Some(xdg), Some(legacy)) if !xdg.exists() && legacy.exists() => Some(legacy),
+            (xdg, legacy) => xdg.or(legacy),
+        }
+    }
+
+    /// Config file location, honouring the `BLLVM_CONFIG` override
+    pub fn config_path() -> Option<PathBuf> {
+        std::env::var_os(CONFIG_ENV_VAR)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .or_else(Self::default_path)
+    }
+
+    /// Load the config file, returning an empty config if none exists
+    ///
+    /// An explicit `BLLVM_CONFIG` path must exist.
+    pub fn load() -> Result<Self, InputError> {
+        let explicit = std::env::var_os(CONFIG_ENV_VAR).is_some_and(|p| !p.is_empty());
+        match Self::config_path() {
+            Some(path) if path.exists() => Self::from_file(path),
+            Some(path) if explicit => {
+                Err(InputError::FileNotFound(path.to_string_lossy().to_string()))
+            }
+            _ => Ok(Self::default()),
+        }
+    }
+
+    /// Load the config file for a CLI tool
+    ///
+    /// Warnings are printed to stderr; a malformed file is fatal.
+    pub fn load_or_exit() -> Self {
+        match Self::load() {
+            Ok(config) => {
+                for warning in &config.warnings {
+                    eprintln!("warning: {}", warning);
+                }
+                config
+            }
+            Err(e) => {
+                eprintln!("error: failed to load CLI config: {}", e);
+                std::process::exit(EXIT_CONFIG_ERROR);
+            }
+        }
+    }
+
+    /// Load configuration from a TOML file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self, InputError> {
+        let contents = std::fs::read_to_string(path.as_ref())?;
+        let mut config = Self::from_toml_str(&contents)?;
+        config.path = Some(path.as_ref().to_path_buf());
+        Ok(config)
+    }
+
+    /// Parse configuration from TOML
+    ///
+    /// Unknown keys are recorded in `warnings` rather than rejected.
+    pub fn from_toml_str(contents: &str) -> Result<Self, InputError> {
+        let table: toml::Table = toml::from_str(contents)
+            .map_err(|e| InputError::InvalidFormat(format!("Invalid config TOML: {}", e)))?;
+
+        let mut config = Self::default();
+        for (key, value) in table {
+            if key == "tools" {
+                let toml::Value::Table(tools) = value else {
+                    return Err(InputError::InvalidFormat(
+                        "'tools' must be a table".to_string(),
+                    ));
+                };
+                for (tool, section) in tools {
+                    let toml::Value::Table(section) = section else {
+                        return Err(InputError::InvalidFormat(format!(
+                            "'tools.{}' must be a table",
+                            tool
+                        )));
+                    };
+                    let context = format!("tools.{}", tool);
+                    let values = read_settings(section, &context, &mut config.warnings)?;
+                    config.tools.insert(tool, values);
+                }
+            } else {
+                let mut single = toml::Table::new();
+                single.insert(key, value);
+                config
+                    .defaults
+                    .extend(read_settings(single, "config", &mut config.warnings)?);
+            }
+        }
+
+        Ok(config)
+    }
+
+    /// Set a top-level key in a config file, creating it if needed
+    ///
+    /// The key may be a setting name or a `default_*` alias. Other keys in
+    /// the file are preserved.
+    pub fn set_value<P: AsRef<Path>>(path: P, key: &str, value: &str) -> Result<(), InputError> {
+        let setting_key = canonical_key(key)
+            .ok_or_else(|| InputError::InvalidValue(format!("Unknown config key '{}'", key)))?;
+        validate_value(setting_key, value)?;
+
+        let path = path.as_ref();
+        let mut table: toml::Table = if path.exists() {
+            toml::from_str(&std::fs::read_to_string(path)?)
+                .map_err(|e| InputError::InvalidFormat(format!("Invalid config TOML: {}", e)))?
+        } else {
+            toml::Table::new()
+        };
+        table.insert(key.to_string(), toml::Value::String(value.to_string()));
+
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let contents = toml::to_string_pretty(&table)
+            .map_err(|e| InputError::InvalidFormat(format!("Failed to write config: {}", e)))?;
+        std::fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Value from the config file for a tool, preferring its own section
+    pub fn get(&self, tool: &str, key: &str) -> Option<&str> {
+        self.get_with_source(tool, key).map(|(value, _)| value)
+    }
+
+    fn get_with_source(&self, tool: &str, key: &str) -> Option<(&str, SettingSource)> {
+        if let Some(value) = self.tools.get(tool).and_then(|section| section.get(key)) {
+            return Some((value, SettingSource::ToolFile));
+        }
+        self.defaults
+            .get(key)
+            .map(|value| (value.as_str(), SettingSource::File))
+    }
+
+    /// Layer environment variables and config file values onto a command
+    ///
+    /// Only top-level arguments whose id matches a known setting are affected.
+    /// File values become the argument's default, and environment variables
+    /// are attached with `Arg::env`, so explicit flags always win. The
+    /// recognized environment variables are appended to the help text.
+    pub fn apply(&self, mut command: Command) -> Command {
+        let tool = command.get_name().to_string();
+        for setting in SETTINGS {
+            if !command.get_arguments().any(|a| a.get_id() == setting.key) {
+                continue;
+            }
+            let file_value = self.get(&tool, setting.key).map(str::to_string);
+            command = command.mut_arg(setting.key, |arg| {
+                let arg = arg.env(setting.env);
+                match file_value {
+                    Some(value) => arg.default_value(value).required(false),
+                    None => arg,
+                }
+            });
+        }
+        let help = match command.get_after_help() {
+            Some(existing) => format!("{}\n\n{}", existing, env_help()),
+            None => env_help(),
+        };
+        // `--help` shows only the long text when a tool sets one
+        if let Some(existing) = command.get_after_long_help() {
+            let long_help = format!("{}\n\n{}", existing, env_help());
+            command = command.after_long_help(long_help);
+        }
+        command.after_help(help)
+    }
+
+    /// Parse command-line arguments for `P` with this configuration applied
+    pub fn try_parse<P: Parser>(&self) -> Result<P, clap::Error> {
+        self.try_parse_from::<P, _, _>(std::env::args_os())
+    }
+
+    /// Parse the given arguments for `P` with this configuration applied
+    pub fn try_parse_from<P, I, T>(&self, args: I) -> Result<P, clap::Error>
+    where
+        P: Parser,
+        I: IntoIterator<Item = T>,
+        T: Into<std::ffi::OsString> + Clone,
+    {
+        let mut command = self.apply(P::command());
+        let matches = command.try_get_matches_from_mut(args)?;
+        P::from_arg_matches(&matches).map_err(|e| e.format(&mut command))
+    }
+
+    /// Parse command-line arguments for `P`, exiting on error
+    pub fn parse<P: Parser>(&self) -> P {
+        self.try_parse().unwrap_or_else(|e| e.exit())
+    }
+
+    /// Effective value and source of every setting for a tool
+    ///
+    /// Command-line flags are not considered; this describes what a tool
+    /// would use when the flag is omitted.
+    pub fn effective(&self, tool: &str) -> Vec<(Setting, Option<String>, SettingSource)> {
+        SETTINGS
+            .iter()
+            .map(|setting| {
+                if let Some(value) = std::env::var(setting.env).ok().filter(|v| !v.is_empty()) {
+                    (*setting, Some(value), SettingSource::Env)
+                } else if let Some((value, source)) = self.get_with_source(tool, setting.key) {
+                    (*setting, Some(value.to_string()), source)
+                } else {
+                    (*setting, None, SettingSource::Default)
+                }
+            })
+            .collect()
+    }
+}
+
+/// Check that a value is acceptable for a setting
+fn validate_value(key: &str, value: &str) -> Result<(), InputError> {
+    match key {
+        "format" => value
+            .parse::<OutputFormat>()
+            .map(|_| ())
+            .map_err(InputError::InvalidValue),
+        "network" if value.parse::<NetworkType>().is_err() => {
+            Err(InputError::InvalidValue(format!(
+                "Invalid network '{}': must be one of mainnet, testnet, regtest",
+                value
+            )))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Extract known settings from a table, warning on unknown keys
+fn read_settings(
+    table: toml::Table,
+    context: &str,
+    warnings: &mut Vec<String>,
+) -> Result<BTreeMap<String, String>, InputError> {
+    let mut values = BTreeMap::new();
+    for (key, value) in table {
+        let Some(setting_key) = canonical_key(&key) else {
+            warnings.push(format!("Unknown key '{}' in {}", key, context));
+            continue;
+        };
+        let value = match value {
+            toml::Value::String(s) => s,
+            other => {
+                return Err(InputError::InvalidValue(format!(
+                    "'{}' in {} must be a string, got {}",
+                    key,
+                    context,
+                    other.type_str()
+                )))
+            }
+        };
+        values.insert(setting_key.to_string(), value);
+    }
+    Ok(values)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_with_tool_section() {
+        let config = CliConfig::from_toml_str(
+            r#"
+            format = "json"
+            threshold = "3-of-5"
+
+            [tools.blvm-verify]
+            threshold = "6-of-7"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.get("blvm-verify", "threshold"), Some("6-of-7"));
+        assert_eq!(config.get("blvm-sign", "threshold"), Some("3-of-5"));
+        assert_eq!(config.get("blvm-verify", "format"), Some("json"));
+        assert_eq!(config.get("blvm-verify", "key"), None);
+        assert!(config.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_keys_warn() {
+        let config = CliConfig::from_toml_str(
+            r#"
+            colour = "always"
+
+            [tools.blvm-sign]
+            keyfile = "me.json"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.warnings.len(), 2);
+        assert!(config.warnings.iter().any(|w| w.contains("colour")));
+        assert!(config
+            .warnings
+            .iter()
+            .any(|w| w.contains("tools.blvm-sign")));
+    }
+
+    #[test]
+    fn test_global_aliases() {
+        let config = CliConfig::from_toml_str(
+            r#"
+            default_key_file = "/keys/maintainer.json"
+            default_output_format = "json"
+            default_network = "testnet"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.get("blvm-sign", "key"),
+            Some("/keys/maintainer.json")
+        );
+        let global = GlobalConfig::from_cli_config(&config);
+        assert_eq!(global.default_output_format, Some(OutputFormat::Json));
+        assert_eq!(global.default_network.as_deref(), Some("testnet"));
+        assert_eq!(global.default_modules_dir, None);
+    }
+
+    #[test]
+    fn test_set_value() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("nested").join("config.toml");
+
+        CliConfig::set_value(&path, "default_modules_dir", "/srv/modules").unwrap();
+        CliConfig::set_value(&path, "format", "table").unwrap();
+        assert!(CliConfig::set_value(&path, "format", "xml").is_err());
+        assert!(CliConfig::set_value(&path, "network", "signet").is_err());
+        assert!(CliConfig::set_value(&path, "colour", "always").is_err());
+
+        let config = CliConfig::from_file(&path).unwrap();
+        assert_eq!(
+            config.get("blvm-compose", "modules_dir"),
+            Some("/srv/modules")
+        );
+        assert_eq!(config.get("blvm-compose", "format"), Some("table"));
+    }
+
+    #[test]
+    fn test_invalid_config_rejected() {
+        assert!(CliConfig::from_toml_str("format = 3").is_err());
+        assert!(CliConfig::from_toml_str("tools = 1").is_err());
+        assert!(CliConfig::from_toml_str("not toml").is_err());
+    }
+
+    #[test]
+    fn test_apply_appends_env_help() {
+        let command = Command::new("blvm-test").after_help("Exit codes: 0 ok");
+        let help = CliConfig::default()
+            .apply(command)
+            .get_after_help()
+            .unwrap()
+            .to_string();
+        assert!(help.starts_with("Exit codes: 0 ok"));
+        assert!(help.contains("BLLVM_KEY_FILE"));
+        assert!(help.contains(CONFIG_ENV_VAR));
+    }
+}
@@ -0,0 +1,231 @@
+//! # Process Exit Codes
+//!
+//! Documented exit statuses shared by the CLI tools, so scripts can tell
+//! a bad configuration from a missing module or a failed verification.
+//! Codes follow sysexits(3) where one applies.
+
+use crate::cli::completions;
+use crate::cli::config::CliConfig;
+use crate::cli::input::InputError;
+use crate::composition::CompositionError;
+use crate::governance::error::GovernanceError;
+use clap::Parser;
+
+/// Process exit status of a CLI tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExitCode {
+    /// The command succeeded
+    Success,
+    /// A signature, key, or threshold check failed
+    VerificationFailed,
+    /// The command had no effect, e.g. no PSBT input could be signed
+    NothingToDo,
+    /// Invalid arguments or configuration (EX_USAGE)
+    Usage,
+    /// Input or composition failed validation (EX_DATAERR)
+    ValidationFailed,
+    /// A file, module, or module version does not exist (EX_NOINPUT)
+    NotFound,
+    /// A remote registry could not be reached (EX_UNAVAILABLE)
+    Unavailable,
+    /// Unexpected internal failure (EX_SOFTWARE)
+    Internal,
+    /// Reading or writing a file failed (EX_IOERR)
+    Io,
+}
+
+/// Exit code table for each tool's `--help` output
+pub const EXIT_CODES_HELP: &str = "\
+Exit codes:
+  0   Success
+  1   Signature, key, or threshold verification failed
+  2   Nothing to do
+  64  Invalid usage or configuration
+  65  Validation failed or malformed input
+  66  File, module, or module version not found
+  69  Module registry unavailable
+  70  Unexpected internal error
+  74  I/O error
+  78  CLI config file error";
+
+impl ExitCode {
+    /// Numeric process exit status
+    pub fn code(self) -> i32 {
+        match self {
+            ExitCode::Success => 0,
+            ExitCode::VerificationFailed => 1,
+            ExitCode::NothingToDo => 2,
+            ExitCode::Usage => 64,
+            ExitCode::ValidationFailed => 65,
+            ExitCode::NotFound => 66,
+            ExitCode::Unavailable => 69,
+            ExitCode::Internal => 70,
+            ExitCode::Io => 74,
+        }
+    }
+
+    /// Exit the process with this status
+    pub fn exit(self) -> ! {
+        std::process::exit(self.code())
+    }
+
+    /// Exit status for an error returned by a command
+    ///
+    /// The first error in the `source` chain with a known type decides the
+    /// status; anything unrecognized is [`ExitCode::Internal`].
+    pub fn from_error(error: &(dyn std::error::Error + 'static)) -> Self {
+        std::iter::successors(Some(error), |e| e.source())
+            .find_map(known_error_code)
+            .unwrap_or(ExitCode::Internal)
+    }
+}
+
+/// A command that had no effect, reported with [`ExitCode::NothingToDo`]
+#[derive(Debug, thiserror::Error)]
+#[error("{0}")]
+pub struct NothingToDo(pub String);
+
+fn known_error_code(error: &(dyn std::error::Error + 'static)) -> Option<ExitCode> {
+    if error.is::<NothingToDo>() {
+        Some(ExitCode::NothingToDo)
+    } else if let Some(e) = error.downcast_ref::<CompositionError>() {
+        Some(e.into())
+    } else if let Some(e) = error.downcast_ref::<GovernanceError>() {
+        Some(e.into())
+    } else if let Some(e) = error.downcast_ref::<InputError>() {
+        Some(e.into())
+    } else if let Some(e) = error.downcast_ref::<std::io::Error>() {
+        Some(e.into())
+    } else if error.is::<serde_json::Error>()
+        || error.is::<toml::de::Error>()
+        || error.is::<hex::FromHexError>()
+    {
+        Some(ExitCode::ValidationFailed)
+    } else {
+        None
+    }
+}
+
+impl From<&CompositionError> for ExitCode {
+    fn from(error: &CompositionError) -> Self {
+        match error {
+            CompositionError::ModuleNotFound(_) | CompositionError::ModuleVersionNotFound(..) => {
+                ExitCode::NotFound
+            }
+            CompositionError::InvalidConfiguration(_) => ExitCode::Usage,
+            CompositionError::DependencyResolutionFailed(_)
+            | CompositionError::ValidationFailed(_)
+            | CompositionError::SerializationError(_)
+            | CompositionError::ChecksumMismatch(..)
+            | CompositionError::IntegrityCheckFailed(..)
+            | CompositionError::LockfileMismatch(_) => ExitCode::ValidationFailed,
+            CompositionError::RegistryUnavailable(_) => ExitCode::Unavailable,
+            CompositionError::IoError(e) => e.into(),
+            CompositionError::InstallationFailed(_) => ExitCode::Internal,
+        }
+    }
+}
+
+impl From<&GovernanceError> for ExitCode {
+    fn from(error: &GovernanceError) -> Self {
+        match error {
+            GovernanceError::InvalidKey(_)
+            | GovernanceError::InvalidChildIndex(_)
+            | GovernanceError::SignatureVerification(_)
+            | GovernanceError::Cryptographic(_)
+            | GovernanceError::InvalidSignatureFormat(_)
+            | GovernanceError::InsufficientSignatures { .. }
+            | GovernanceError::SignerUnavailable(_)
+            | GovernanceError::SigningRefused(_) => ExitCode::VerificationFailed,
+            GovernanceError::InvalidMultisig(_) | GovernanceError::InvalidThreshold { .. } => {
+                ExitCode::Usage
+            }
+            GovernanceError::MessageFormat(_)
+            | GovernanceError::Serialization(_)
+            | GovernanceError::InvalidBase58Character { .. }
+            | GovernanceError::ChecksumMismatch(_)
+            | GovernanceError::InvalidInput(_) => ExitCode::ValidationFailed,
+            GovernanceError::NotImplemented(_) => ExitCode::Internal,
+        }
+    }
+}
+
+impl From<&InputError> for ExitCode {
+    fn from(error: &InputError) -> Self {
+        match error {
+            InputError::FileNotFound(_) => ExitCode::NotFound,
+            InputError::InvalidFormat(_) => ExitCode::ValidationFailed,
+            InputError::InvalidValue(_) => ExitCode::Usage,
+            InputError::IoError(e) => e.into(),
+        }
+    }
+}
+
+impl From<&std::io::Error> for ExitCode {
+    fn from(error: &std::io::Error) -> Self {
+        if error.kind() == std::io::ErrorKind::NotFound {
+            ExitCode::NotFound
+        } else {
+            ExitCode::Io
+        }
+    }
+}
+
+/// Parse arguments, handling `--generate-completions` first
+///
+/// Usage errors exit with [`ExitCode::Usage`]; `--help` and `--version`
+/// exit successfully.
+pub fn parse_args<P: Parser>(config: &CliConfig) -> P {
+    completions::generate_if_requested::<P>()
+        .and_then(|_| config.try_parse())
+        .unwrap_or_else(|e| {
+            let _ = e.print();
+            if e.use_stderr() {
+                ExitCode::Usage.exit()
+            } else {
+                ExitCode::Success.exit()
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes() {
+        let cases: Vec<(Box<dyn std::error::Error>, i32)> = vec![
+            (
+                Box::new(CompositionError::InvalidConfiguration("bad".into())),
+                64,
+            ),
+            (
+                Box::new(CompositionError::ValidationFailed("bad".into())),
+                65,
+            ),
+            (Box::new(CompositionError::ModuleNotFound("lnd".into())), 66),
+            (
+                Box::new(CompositionError::RegistryUnavailable("timeout".into())),
+                69,
+            ),
+            (
+                Box::new(GovernanceError::InsufficientSignatures { got: 1, need: 2 }),
+                1,
+            ),
+            (Box::new(InputError::FileNotFound("key.json".into())), 66),
+            (Box::new(NothingToDo("unchanged".into())), 2),
+            (
+                Box::new(std::io::Error::from(std::io::ErrorKind::PermissionDenied)),
+                74,
+            ),
+            ("something else".into(), 70),
+        ];
+        for (error, code) in cases {
+            assert_eq!(ExitCode::from_error(&*error).code(), code, "{}", error);
+        }
+
+        // I/O errors inside composition errors keep their kind
+        let wrapped = CompositionError::IoError(std::io::ErrorKind::NotFound.into());
+        assert_eq!(ExitCode::from(&wrapped), ExitCode::NotFound);
+    }
+}
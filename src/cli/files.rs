@@ -0,0 +1,168 @@
+//! # CLI Key and Signature Files
+//!
+//! JSON formats of the key, public key, and signature files read and
+//! written by the CLI tools. Unknown fields are ignored, so files with
+//! extra metadata (e.g. from blvm-sign-binary) load as well.
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::{GovernanceKeypair, PublicKey, Signature};
+use serde::{Deserialize, Serialize};
+
+/// Private key file written by blvm-keygen
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyFile {
+    /// Public key (optional in hand-written key files)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<PublicKey>,
+    /// Secret key (hex)
+    pub secret_key: String,
+    /// Creation time (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+impl KeyFile {
+    /// Key file contents for a keypair
+    pub fn from_keypair(keypair: &GovernanceKeypair, created_at: impl Into<String>) -> Self {
+        Self {
+            public_key: Some(keypair.public_key()),
+            secret_key: hex::encode(keypair.secret_key_bytes()),
+            created_at: Some(created_at.into()),
+        }
+    }
+
+    /// Load the keypair, checking it against the recorded public key
+    pub fn keypair(&self) -> GovernanceResult<GovernanceKeypair> {
+        let secret_key = self.secret_key.trim();
+        let secret_key = secret_key.strip_prefix("0x").unwrap_or(secret_key);
+        let bytes = hex::decode(secret_key)
+            .map_err(|e| GovernanceError::InvalidKey(format!("Invalid secret key hex: {}", e)))?;
+        let keypair = GovernanceKeypair::from_secret_key(&bytes)?;
+        if let Some(public_key) = &self.public_key {
+            if *public_key != keypair.public_key() {
+                return Err(GovernanceError::InvalidKey(
+                    "Public key does not match secret key".to_string(),
+                ));
+            }
+        }
+        Ok(keypair)
+    }
+}
+
+/// Public key file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PublicKeyFile {
+    /// Public key
+    pub public_key: PublicKey,
+}
+
+/// Signature file written by blvm-sign
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureFile {
+    /// Signature
+    pub signature: Signature,
+    /// Signing time (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<String>,
+}
+
+/// Verification report written by `blvm-verify --output-file` and
+/// `blvm-verify-binary --output-file`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VerificationReport {
+    /// Verification time (RFC 3339)
+    pub verified_at: String,
+    /// What was verified: a message description or a file path
+    pub subject: String,
+    /// SHA256 of the verified file (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_hash: Option<String>,
+    /// Public keys the signatures were checked against
+    pub public_keys: Vec<PublicKey>,
+    /// Result for each signature file, in command-line order
+    pub signatures: Vec<SignatureCheck>,
+    /// Threshold as `M-of-N`, if one was given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<String>,
+    /// Whether the threshold (or, without one, a single valid signature) was met
+    pub threshold_met: bool,
+    /// Whether verification passed as a whole
+    pub valid: bool,
+    /// Errors met while verifying
+    #[serde(default)]
+    pub errors: Vec<String>,
+}
+
+/// Result of checking one signature file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignatureCheck {
+    /// Signature file path
+    pub file: String,
+    /// Whether the signature verified against one of the public keys
+    pub valid: bool,
+    /// The public key it verified against
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signer: Option<PublicKey>,
+}
+
+impl VerificationReport {
+    /// Write the report to `path` as pretty-printed JSON
+    pub fn write(&self, path: &std::path::Path) -> std::io::Result<()> {
+        let json = serde_json::to_string_pretty(self).map_err(std::io::Error::other)?;
+        std::fs::write(path, json + "\n")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_key_file_roundtrip() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let json = serde_json::to_string(&KeyFile::from_keypair(&keypair, "now")).unwrap();
+        let parsed: KeyFile = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.keypair().unwrap().public_key(), keypair.public_key());
+
+        // A mismatched public key is rejected
+        let mut mismatched = parsed;
+        mismatched.public_key = Some(GovernanceKeypair::generate().unwrap().public_key());
+        assert!(mismatched.keypair().is_err());
+    }
+
+    #[test]
+    fn test_signature_file_wrong_length() {
+        let err = serde_json::from_str::<SignatureFile>(r#"{"signature":"abcd"}"#).unwrap_err();
+        assert!(err.to_string().contains("expected 64 bytes"), "{}", err);
+    }
+
+    #[test]
+    fn test_verification_report_roundtrip() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let report = VerificationReport {
+            verified_at: "2024-01-01T00:00:00+00:00".to_string(),
+            subject: "bllvm-node".to_string(),
+            file_hash: None,
+            public_keys: vec![keypair.public_key()],
+            signatures: vec![SignatureCheck {
+                file: "a.sig.json".to_string(),
+                valid: true,
+                signer: Some(keypair.public_key()),
+            }],
+            threshold: Some("1-of-1".to_string()),
+            threshold_met: true,
+            valid: true,
+            errors: Vec::new(),
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("report.json");
+        report.write(&path).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(json.get("file_hash").is_none());
+        assert_eq!(json["signatures"][0]["valid"], true);
+        let parsed: VerificationReport = serde_json::from_value(json).unwrap();
+        assert_eq!(parsed.public_keys, vec![keypair.public_key()]);
+    }
+}
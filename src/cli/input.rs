@@ -2,6 +2,8 @@
 //!
 //! Input parsing and validation utilities for CLI tools.
 
+use crate::governance::bip32::{format_child_index, parse_child_index, HardenedStyle};
+use crate::governance::BudgetAllocation;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -63,33 +65,218 @@ pub fn parse_comma_separated(value: &str) -> Vec<String> {
         .collect()
 }
 
+/// Parse a list-valued argument with `@file` expansion
+///
+/// The value is split on commas like [`parse_comma_separated`]. Entries
+/// beginning with `@` are replaced by the newline-separated entries of the
+/// named file, with `#` comments and blank lines removed. Expansion does not
+/// recurse: an `@` entry inside a file is kept literally.
+pub fn parse_list_arg(value: &str) -> Result<Vec<String>, InputError> {
+    let mut entries = Vec::new();
+    for entry in parse_comma_separated(value) {
+        match entry.strip_prefix('@') {
+            Some(file_path) => entries.extend(read_list_file(file_path)?),
+            None => entries.push(entry),
+        }
+    }
+    Ok(entries)
+}
+
+/// Read newline-separated list entries from a file
+fn read_list_file(file_path: &str) -> Result<Vec<String>, InputError> {
+    if !Path::new(file_path).exists() {
+        return Err(InputError::FileNotFound(format!(
+            "{} (referenced as @{})",
+            file_path, file_path
+        )));
+    }
+
+    let contents = std::fs::read_to_string(file_path)?;
+    Ok(contents
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or("").trim())
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Largest multisig total accepted by `parse_threshold`
+pub const MAX_THRESHOLD_TOTAL: usize = 100;
+
+/// Accepted threshold formats, for error and help messages
+const THRESHOLD_FORMATS: &str = "'M-of-N', 'M/N', or 'M:N'";
+
 /// Validate a threshold string (e.g., "3-of-5")
+///
+/// The alternative spellings "3/5", "3:5", "3,5", "3of5" and "3 of 5" are
+/// also accepted.
 pub fn parse_threshold(threshold: &str) -> Result<(usize, usize), InputError> {
-    let parts: Vec<&str> = threshold.split("-of-").collect();
+    parse_threshold_and_total(threshold, None)
+}
+
+/// Validate a threshold string, taking the total from `total` if the string omits it
+///
+/// A bare number (e.g. "3") is the threshold; the total comes from `total`,
+/// typically the number of public keys supplied. When the string includes a
+/// total, it is used as given.
+pub fn parse_threshold_and_total(
+    threshold: &str,
+    total: Option<usize>,
+) -> Result<(usize, usize), InputError> {
+    let normalized = threshold.trim().to_ascii_lowercase();
+    let format_error = || {
+        InputError::InvalidFormat(format!(
+            "expected format {}, got '{}'",
+            THRESHOLD_FORMATS, threshold
+        ))
+    };
+
+    let separator = ["-of-", "of", "/", ":", ","]
+        .into_iter()
+        .find(|separator| normalized.contains(separator));
+    let bare = normalized.parse::<usize>().is_ok();
+    let parts: Vec<String> = match (separator, total) {
+        (Some(separator), _) => normalized.split(separator).map(str::to_string).collect(),
+        (None, Some(total)) if bare => vec![normalized.clone(), total.to_string()],
+        (None, None) if bare => {
+            return Err(InputError::InvalidFormat(format!(
+                "threshold '{}' has no total; expected format {}",
+                threshold, THRESHOLD_FORMATS
+            )))
+        }
+        (None, _) => return Err(format_error()),
+    };
 
     if parts.len() != 2 {
-        return Err(InputError::InvalidFormat(
-            "Threshold must be in format 'N-of-M'".to_string(),
-        ));
+        return Err(format_error());
     }
 
     let threshold_num = parts[0]
+        .trim()
         .parse::<usize>()
         .map_err(|e| InputError::InvalidValue(format!("Invalid threshold number: {}", e)))?;
 
     let total_num = parts[1]
+        .trim()
         .parse::<usize>()
         .map_err(|e| InputError::InvalidValue(format!("Invalid total number: {}", e)))?;
 
-    if threshold_num > total_num {
+    if total_num == 0 {
         return Err(InputError::InvalidValue(
-            "Threshold cannot be greater than total".to_string(),
+            "total must be at least 1".to_string(),
         ));
     }
 
+    if threshold_num == 0 {
+        return Err(InputError::InvalidValue(
+            "threshold must be at least 1".to_string(),
+        ));
+    }
+
+    if threshold_num > total_num {
+        return Err(InputError::InvalidValue(format!(
+            "threshold {} exceeds total {}",
+            threshold_num, total_num
+        )));
+    }
+
+    if total_num > MAX_THRESHOLD_TOTAL {
+        return Err(InputError::InvalidValue(format!(
+            "total {} exceeds maximum of {}",
+            total_num, MAX_THRESHOLD_TOTAL
+        )));
+    }
+
     Ok((threshold_num, total_num))
 }
 
+/// Parse a BIP32 derivation path (e.g., "m/44'/0'/0'/0/0")
+///
+/// Both `'` and `h`/`H` mark hardened components.
+pub fn parse_derivation_path(path: &str) -> Result<Vec<u32>, InputError> {
+    let path = path.trim();
+    let components = match path.strip_prefix("m/") {
+        Some(rest) => rest,
+        None if path == "m" => return Ok(Vec::new()),
+        None => {
+            return Err(InputError::InvalidFormat(format!(
+                "derivation path must start with 'm/': {}",
+                path
+            )))
+        }
+    };
+
+    components
+        .split('/')
+        .enumerate()
+        .map(|(position, component)| {
+            parse_child_index(component).map_err(|_| {
+                InputError::InvalidValue(format!(
+                    "invalid component '{}' at position {}",
+                    component,
+                    position + 1
+                ))
+            })
+        })
+        .collect()
+}
+
+/// Format BIP32 child indices as a derivation path (hardened as `44'`)
+pub fn format_derivation_path(indices: &[u32]) -> String {
+    let mut path = "m".to_string();
+    for &index in indices {
+        path.push('/');
+        path.push_str(&format_child_index(index, HardenedStyle::Apostrophe));
+    }
+    path
+}
+
+/// Pair repeated `--recipient` and `--amount` arguments into budget allocations
+///
+/// The n-th amount goes to the n-th recipient. Fails if there are no
+/// recipients, the counts differ, a recipient is empty, or the total overflows.
+pub fn parse_budget_allocations(
+    recipients: &[String],
+    amounts: &[u64],
+) -> Result<Vec<BudgetAllocation>, InputError> {
+    if recipients.is_empty() {
+        return Err(InputError::InvalidValue(
+            "budget needs at least one --recipient".to_string(),
+        ));
+    }
+    if recipients.len() != amounts.len() {
+        return Err(InputError::InvalidValue(format!(
+            "{} recipients but {} amounts; give one --amount per --recipient",
+            recipients.len(),
+            amounts.len()
+        )));
+    }
+    if recipients.iter().any(|r| r.trim().is_empty()) {
+        return Err(InputError::InvalidValue(
+            "recipient cannot be empty".to_string(),
+        ));
+    }
+    if amounts
+        .iter()
+        .try_fold(0u64, |total, amount| total.checked_add(*amount))
+        .is_none()
+    {
+        return Err(InputError::InvalidValue(
+            "total amount overflows".to_string(),
+        ));
+    }
+
+    Ok(recipients
+        .iter()
+        .zip(amounts)
+        .map(|(recipient, amount)| BudgetAllocation {
+            recipient: recipient.trim().to_string(),
+            amount_satoshis: *amount,
+            description: String::new(),
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -129,6 +316,41 @@ mod tests {
         assert_eq!(result, vec!["a", "b", "c"]);
     }
 
+    #[test]
+    fn test_parse_list_arg() {
+        let dir = tempdir().unwrap();
+        let list_path = dir.path().join("roster.txt");
+        std::fs::write(
+            &list_path,
+            "# maintainers\nalice.json\n\nbob.json  # lead\n",
+        )
+        .unwrap();
+
+        let value = format!("first.json, @{}, last.json", list_path.display());
+        let result = parse_list_arg(&value).unwrap();
+        assert_eq!(
+            result,
+            vec!["first.json", "alice.json", "bob.json", "last.json"]
+        );
+    }
+
+    #[test]
+    fn test_parse_list_arg_crlf_and_nested() {
+        let dir = tempdir().unwrap();
+        let list_path = dir.path().join("roster.txt");
+        std::fs::write(&list_path, "alice.json\r\n@other.txt\r\nbob.json").unwrap();
+
+        let result = parse_list_arg(&format!("@{}", list_path.display())).unwrap();
+        assert_eq!(result, vec!["alice.json", "@other.txt", "bob.json"]);
+    }
+
+    #[test]
+    fn test_parse_list_arg_missing_file() {
+        let err = parse_list_arg("@/nonexistent/roster.txt").unwrap_err();
+        assert!(matches!(err, InputError::FileNotFound(_)));
+        assert!(err.to_string().contains("/nonexistent/roster.txt"));
+    }
+
     #[test]
     fn test_parse_threshold() {
         let result = parse_threshold("3-of-5");
@@ -141,6 +363,51 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_parse_threshold_and_total() {
+        assert_eq!(parse_threshold_and_total("3", Some(5)).unwrap(), (3, 5));
+        assert_eq!(parse_threshold_and_total("3:5", Some(7)).unwrap(), (3, 5));
+        assert!(parse_threshold_and_total("6", Some(5)).is_err());
+        assert!(parse_threshold_and_total("0", Some(5)).is_err());
+        assert!(parse_threshold_and_total("", Some(5)).is_err());
+
+        let err = parse_threshold_and_total("3", None).unwrap_err();
+        assert!(err.to_string().contains("has no total"), "{}", err);
+    }
+
+    #[test]
+    fn test_parse_derivation_path() {
+        let result = parse_derivation_path("m/44'/0'/0'/0/0").unwrap();
+        assert_eq!(result, vec![0x8000002c, 0x80000000, 0x80000000, 0, 0]);
+
+        let result = parse_derivation_path("m/84h/1h/2H/1/7").unwrap();
+        assert_eq!(result, vec![0x80000054, 0x80000001, 0x80000002, 1, 7]);
+
+        assert_eq!(parse_derivation_path("m").unwrap(), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn test_parse_invalid_derivation_path() {
+        assert!(parse_derivation_path("44'/0'/0'").is_err());
+        assert!(parse_derivation_path("m/").is_err());
+        assert!(parse_derivation_path("m/2147483648").is_err());
+
+        let err = parse_derivation_path("m/44x/0").unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("invalid component '44x' at position 1"));
+    }
+
+    #[test]
+    fn test_format_derivation_path() {
+        let path = "m/86'/0'/0'/0/5";
+        assert_eq!(
+            format_derivation_path(&parse_derivation_path(path).unwrap()),
+            path
+        );
+        assert_eq!(format_derivation_path(&[]), "m");
+    }
+
     #[test]
     fn test_parse_file_path() {
         let dir = tempdir().unwrap();
@@ -157,4 +424,18 @@ mod tests {
         let result = parse_file_path("/nonexistent/file.txt");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_parse_budget_allocations() {
+        let recipients = vec!["bc1qalice".to_string(), " bc1qbob ".to_string()];
+        let allocations = parse_budget_allocations(&recipients, &[100, 200]).unwrap();
+        assert_eq!(allocations.len(), 2);
+        assert_eq!(allocations[1].recipient, "bc1qbob");
+        assert_eq!(allocations[1].amount_satoshis, 200);
+
+        assert!(parse_budget_allocations(&recipients, &[100]).is_err());
+        assert!(parse_budget_allocations(&recipients, &[u64::MAX, 1]).is_err());
+        assert!(parse_budget_allocations(&[String::new()], &[1]).is_err());
+        assert!(parse_budget_allocations(&[], &[]).is_err());
+    }
 }
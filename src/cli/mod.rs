@@ -2,5 +2,9 @@
 //!
 //! Shared utilities for command-line tools.
 
+pub mod completions;
+pub mod config;
+pub mod exit;
+pub mod files;
 pub mod input;
 pub mod output;
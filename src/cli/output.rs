@@ -2,8 +2,11 @@
 //!
 //! Output formatting utilities for CLI tools.
 
+use crate::composition::NodeReport;
+use crate::governance::error::GovernanceError;
 use serde::Serialize;
 use std::fmt;
+use std::io::Write;
 
 /// Output format options
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -12,8 +15,17 @@ pub enum OutputFormat {
     Text,
     /// JSON output
     Json,
+    /// Column-aligned table output for multi-row results
+    Table,
+    /// Newline-delimited JSON, one object per line as results are produced
+    Ndjson,
+    /// RFC 4180 CSV with a header row, for spreadsheet export
+    Csv,
 }
 
+/// Maximum table cell width before truncation (unless wide output is requested)
+pub const TABLE_MAX_CELL_WIDTH: usize = 24;
+
 impl std::str::FromStr for OutputFormat {
     type Err = String;
 
@@ -21,6 +33,9 @@ impl std::str::FromStr for OutputFormat {
         match s.to_lowercase().as_str() {
             "text" | "txt" => Ok(OutputFormat::Text),
             "json" => Ok(OutputFormat::Json),
+            "table" => Ok(OutputFormat::Table),
+            "ndjson" | "jsonl" => Ok(OutputFormat::Ndjson),
+            "csv" => Ok(OutputFormat::Csv),
             _ => Err(format!("Invalid output format: {}", s)),
         }
     }
@@ -29,37 +44,280 @@ impl std::str::FromStr for OutputFormat {
 /// Output formatter for CLI tools
 pub struct OutputFormatter {
     format: OutputFormat,
+    wide: bool,
 }
 
 impl OutputFormatter {
     /// Create a new output formatter
     pub fn new(format: OutputFormat) -> Self {
-        Self { format }
+        Self {
+            format,
+            wide: false,
+        }
+    }
+
+    /// Disable truncation of long table cells
+    pub fn with_wide(mut self, wide: bool) -> Self {
+        self.wide = wide;
+        self
     }
 
     /// Format a value for output
+    ///
+    /// Table output falls back to text for scalar values. CSV output renders
+    /// objects as a single-row CSV and other values as one quoted field.
     pub fn format<T>(&self, value: &T) -> Result<String, String>
     where
         T: Serialize + fmt::Display,
     {
         match self.format {
-            OutputFormat::Text => Ok(value.to_string()),
+            OutputFormat::Text | OutputFormat::Table => Ok(value.to_string()),
             OutputFormat::Json => serde_json::to_string_pretty(value)
                 .map_err(|e| format!("JSON serialization error: {}", e)),
+            OutputFormat::Ndjson => {
+                serde_json::to_string(value).map_err(|e| format!("JSON serialization error: {}", e))
+            }
+            OutputFormat::Csv => match serde_json::to_value(value) {
+                Ok(serde_json::Value::Object(map)) => {
+                    let headers: Vec<&str> = map.keys().map(String::as_str).collect();
+                    let row: Vec<String> = map.values().map(cell_text).collect();
+                    Ok(render_csv(&headers, &[row]))
+                }
+                _ => Ok(csv_field(&value.to_string()) + "\r\n"),
+            },
         }
     }
 
-    /// Format an error for output
-    pub fn format_error(&self, error: &dyn std::error::Error) -> String {
+    /// Whether records should be emitted incrementally as they are produced
+    pub fn is_streaming(&self) -> bool {
+        self.format == OutputFormat::Ndjson
+    }
+
+    /// Emit a single record to stdout in NDJSON mode
+    ///
+    /// Does nothing for other formats, so batch code paths can call it unconditionally.
+    pub fn emit<T>(&self, record: &T) -> Result<(), String>
+    where
+        T: Serialize,
+    {
+        let stdout = std::io::stdout();
+        let mut handle = stdout.lock();
+        self.emit_to(&mut handle, record)
+    }
+
+    /// Emit a single record to a writer in NDJSON mode
+    pub fn emit_to<W, T>(&self, writer: &mut W, record: &T) -> Result<(), String>
+    where
+        W: Write,
+        T: Serialize,
+    {
+        if !self.is_streaming() {
+            return Ok(());
+        }
+
+        let line = serde_json::to_string(record)
+            .map_err(|e| format!("JSON serialization error: {}", e))?;
+        writeln!(writer, "{}", line)
+            .and_then(|_| writer.flush())
+            .map_err(|e| format!("Output error: {}", e))
+    }
+
+    /// Format a list of serializable rows
+    ///
+    /// Each row should serialize to a JSON object; its keys become the table columns.
+    pub fn format_rows<T>(&self, rows: &[T]) -> Result<String, String>
+    where
+        T: Serialize,
+    {
+        let values = rows
+            .iter()
+            .map(serde_json::to_value)
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("JSON serialization error: {}", e))?;
+
         match self.format {
-            OutputFormat::Text => format!("Error: {}", error),
-            OutputFormat::Json => {
-                let error_json = serde_json::json!({
-                    "error": true,
-                    "message": error.to_string()
+            OutputFormat::Json => serde_json::to_string_pretty(&values)
+                .map_err(|e| format!("JSON serialization error: {}", e)),
+            OutputFormat::Ndjson => Ok(values
+                .iter()
+                .map(|value| value.to_string() + "\n")
+                .collect()),
+            OutputFormat::Text | OutputFormat::Table | OutputFormat::Csv => {
+                let mut headers: Vec<String> = Vec::new();
+                for value in &values {
+                    if let serde_json::Value::Object(map) = value {
+                        for key in map.keys() {
+                            if !headers.contains(key) {
+                                headers.push(key.clone());
+                            }
+                        }
+                    }
+                }
+
+                let rows: Vec<Vec<String>> = values
+                    .iter()
+                    .map(|value| match value {
+                        serde_json::Value::Object(map) => headers
+                            .iter()
+                            .map(|header| map.get(header).map(cell_text).unwrap_or_default())
+                            .collect(),
+                        scalar => vec![cell_text(scalar)],
+                    })
+                    .collect();
+
+                let headers: Vec<&str> = headers.iter().map(String::as_str).collect();
+                if self.format == OutputFormat::Csv {
+                    Ok(render_csv(&headers, &rows))
+                } else {
+                    Ok(render_table(&headers, &rows, self.wide))
+                }
+            }
+        }
+    }
+
+    /// Format tabular data with explicit column headers
+    pub fn format_table(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        match self.format {
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                let objects: Vec<serde_json::Value> = rows
+                    .iter()
+                    .map(|row| {
+                        let map: serde_json::Map<String, serde_json::Value> = headers
+                            .iter()
+                            .zip(row.iter())
+                            .map(|(header, cell)| {
+                                (header.to_string(), serde_json::Value::String(cell.clone()))
+                            })
+                            .collect();
+                        serde_json::Value::Object(map)
+                    })
+                    .collect();
+                if self.format == OutputFormat::Ndjson {
+                    objects
+                        .iter()
+                        .map(|object| object.to_string() + "\n")
+                        .collect()
+                } else {
+                    serde_json::to_string_pretty(&objects).unwrap_or_else(|_| "[]".to_string())
+                }
+            }
+            OutputFormat::Text | OutputFormat::Table => render_table(headers, rows, self.wide),
+            OutputFormat::Csv => self.format_csv(headers, rows),
+        }
+    }
+
+    /// Format tabular data as RFC 4180 CSV with a header row
+    ///
+    /// Cells are never truncated, whatever the `wide` setting.
+    pub fn format_csv(&self, headers: &[&str], rows: &[Vec<String>]) -> String {
+        render_csv(headers, rows)
+    }
+
+    /// Format a titled list of items
+    ///
+    /// Text renders a `title:` header followed by `  • item` lines.
+    pub fn format_list(&self, title: &str, items: &[&str]) -> String {
+        match self.format {
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                let value = serde_json::json!({
+                    "title": title,
+                    "items": items,
                 });
-                serde_json::to_string_pretty(&error_json)
-                    .unwrap_or_else(|_| format!("{{\"error\": true, \"message\": \"{}\"}}", error))
+                self.format(&value).unwrap_or_else(|_| "{}".to_string())
+            }
+            OutputFormat::Text | OutputFormat::Table => {
+                let mut output = format!("{}:\n", title);
+                for item in items {
+                    output.push_str(&format!("  • {}\n", item));
+                }
+                output
+            }
+            OutputFormat::Csv => {
+                let rows: Vec<Vec<String>> =
+                    items.iter().map(|item| vec![item.to_string()]).collect();
+                render_csv(&[title], &rows)
+            }
+        }
+    }
+
+    /// Format labeled key-value pairs
+    ///
+    /// Text aligns keys into a two-column layout; JSON emits an object; CSV
+    /// emits the keys as headers over a single row.
+    pub fn format_key_value(&self, pairs: &[(&str, &str)]) -> String {
+        match self.format {
+            OutputFormat::Json | OutputFormat::Ndjson => {
+                let map: serde_json::Map<String, serde_json::Value> = pairs
+                    .iter()
+                    .map(|(key, value)| (key.to_string(), serde_json::Value::from(*value)))
+                    .collect();
+                self.format(&serde_json::Value::Object(map))
+                    .unwrap_or_else(|_| "{}".to_string())
+            }
+            OutputFormat::Text | OutputFormat::Table => {
+                let width = pairs
+                    .iter()
+                    .map(|(key, _)| key.chars().count() + 1)
+                    .max()
+                    .unwrap_or(0);
+                let mut output = String::new();
+                for (key, value) in pairs {
+                    output.push_str(&format!(
+                        "{:width$}  {}\n",
+                        format!("{}:", key),
+                        value,
+                        width = width
+                    ));
+                }
+                output
+            }
+            OutputFormat::Csv => {
+                let headers: Vec<&str> = pairs.iter().map(|(key, _)| *key).collect();
+                let row: Vec<String> = pairs.iter().map(|(_, value)| value.to_string()).collect();
+                render_csv(&headers, &[row])
+            }
+        }
+    }
+
+    /// Format an error for output
+    ///
+    /// JSON output carries the human `message`; when the error, or one of its
+    /// sources, is a [`GovernanceError`], it also carries the stable `code`,
+    /// `category` and whether the operation is `retryable`.
+    pub fn format_error(&self, error: &(dyn std::error::Error + 'static)) -> String {
+        match self.format {
+            OutputFormat::Text | OutputFormat::Table | OutputFormat::Csv => {
+                format!("Error: {}", error)
+            }
+            OutputFormat::Json => serde_json::to_string_pretty(&error_json(error))
+                .unwrap_or_else(|_| format!("{{\"error\": true, \"message\": \"{}\"}}", error)),
+            OutputFormat::Ndjson => error_json(error).to_string(),
+        }
+    }
+
+    /// Format a node health report
+    ///
+    /// Table and CSV output list one row per module.
+    pub fn format_node_report(&self, report: &NodeReport) -> String {
+        match self.format {
+            OutputFormat::Text => report.to_text(),
+            OutputFormat::Json => report.to_json().unwrap_or_else(|e| self.format_error(&e)),
+            OutputFormat::Ndjson => serde_json::to_string(report)
+                .unwrap_or_else(|e| format!("{{\"error\": true, \"message\": \"{}\"}}", e)),
+            OutputFormat::Table | OutputFormat::Csv => {
+                let rows: Vec<Vec<String>> = report
+                    .modules
+                    .iter()
+                    .map(|module| {
+                        vec![
+                            module.name.clone(),
+                            module.version.clone(),
+                            module.status_label(),
+                            module.health_label(),
+                        ]
+                    })
+                    .collect();
+                self.format_table(&["NAME", "VERSION", "STATUS", "HEALTH"], &rows)
             }
         }
     }
@@ -67,7 +325,9 @@ impl OutputFormatter {
     /// Format a success message
     pub fn format_success(&self, message: &str) -> String {
         match self.format {
-            OutputFormat::Text => format!("Success: {}", message),
+            OutputFormat::Text | OutputFormat::Table | OutputFormat::Csv => {
+                format!("Success: {}", message)
+            }
             OutputFormat::Json => {
                 let success_json = serde_json::json!({
                     "success": true,
@@ -77,8 +337,126 @@ impl OutputFormatter {
                     format!("{{\"success\": true, \"message\": \"{}\"}}", message)
                 })
             }
+            OutputFormat::Ndjson => serde_json::json!({
+                "success": true,
+                "message": message
+            })
+            .to_string(),
+        }
+    }
+}
+
+/// Render rows as a column-aligned table
+///
+/// Cells longer than [`TABLE_MAX_CELL_WIDTH`] are truncated with an ellipsis unless `wide` is set.
+pub fn render_table(headers: &[&str], rows: &[Vec<String>], wide: bool) -> String {
+    let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    let body: Vec<Vec<String>> = rows
+        .iter()
+        .map(|row| {
+            row.iter()
+                .map(|cell| {
+                    if wide {
+                        cell.clone()
+                    } else {
+                        truncate_cell(cell, TABLE_MAX_CELL_WIDTH)
+                    }
+                })
+                .collect()
+        })
+        .collect();
+
+    let columns = body
+        .iter()
+        .map(Vec::len)
+        .chain(std::iter::once(header_cells.len()))
+        .max()
+        .unwrap_or(0);
+    let mut widths = vec![0; columns];
+    for row in std::iter::once(&header_cells).chain(body.iter()) {
+        for (i, cell) in row.iter().enumerate() {
+            widths[i] = widths[i].max(cell.chars().count());
         }
     }
+
+    let mut output = String::new();
+    if !header_cells.is_empty() {
+        push_table_row(&mut output, &header_cells, &widths);
+        let rule: Vec<String> = widths.iter().map(|width| "-".repeat(*width)).collect();
+        push_table_row(&mut output, &rule, &widths);
+    }
+    for row in &body {
+        push_table_row(&mut output, row, &widths);
+    }
+    output
+}
+
+/// Render rows as RFC 4180 CSV with a header row and CRLF line endings
+pub fn render_csv(headers: &[&str], rows: &[Vec<String>]) -> String {
+    let mut output = String::new();
+    let header_cells: Vec<String> = headers.iter().map(|h| h.to_string()).collect();
+    for row in std::iter::once(&header_cells).chain(rows.iter()) {
+        let line: Vec<String> = row.iter().map(|cell| csv_field(cell)).collect();
+        output.push_str(&line.join(","));
+        output.push_str("\r\n");
+    }
+    output
+}
+
+/// Quote a CSV field if it contains a comma, quote, or line break
+fn csv_field(cell: &str) -> String {
+    if cell.contains([',', '"', '\r', '\n']) {
+        format!("\"{}\"", cell.replace('"', "\"\""))
+    } else {
+        cell.to_string()
+    }
+}
+
+fn push_table_row(output: &mut String, cells: &[String], widths: &[usize]) {
+    let line = widths
+        .iter()
+        .enumerate()
+        .map(|(i, width)| {
+            let cell = cells.get(i).map(String::as_str).unwrap_or("");
+            format!("{:<width$}", cell, width = *width)
+        })
+        .collect::<Vec<_>>()
+        .join("  ");
+    output.push_str(line.trim_end());
+    output.push('\n');
+}
+
+fn truncate_cell(cell: &str, max_width: usize) -> String {
+    if cell.chars().count() <= max_width {
+        return cell.to_string();
+    }
+    let mut truncated: String = cell.chars().take(max_width.saturating_sub(1)).collect();
+    truncated.push('…');
+    truncated
+}
+
+fn cell_text(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s.clone(),
+        serde_json::Value::Null => String::new(),
+        other => other.to_string(),
+    }
+}
+
+/// JSON object describing an error for `format_error`
+fn error_json(error: &(dyn std::error::Error + 'static)) -> serde_json::Value {
+    let mut json = serde_json::json!({
+        "error": true,
+        "message": error.to_string()
+    });
+    let governance_error = std::iter::successors(Some(error), |e| e.source())
+        .find_map(|e| e.downcast_ref::<GovernanceError>());
+    if let Some(governance_error) = governance_error {
+        json["code"] = governance_error.code().into();
+        json["category"] = governance_error.category().as_str().into();
+        json["retryable"] = governance_error.is_retryable().into();
+    }
+    json
 }
 
 #[cfg(test)]
@@ -90,6 +468,15 @@ mod tests {
         assert_eq!("text".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
         assert_eq!("txt".parse::<OutputFormat>().unwrap(), OutputFormat::Text);
         assert_eq!("json".parse::<OutputFormat>().unwrap(), OutputFormat::Json);
+        assert_eq!(
+            "table".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Table
+        );
+        assert_eq!(
+            "ndjson".parse::<OutputFormat>().unwrap(),
+            OutputFormat::Ndjson
+        );
+        assert_eq!("CSV".parse::<OutputFormat>().unwrap(), OutputFormat::Csv);
         assert!("invalid".parse::<OutputFormat>().is_err());
     }
 
@@ -106,4 +493,131 @@ mod tests {
         let result = formatter.format(&serde_json::json!({"message": "test"}));
         assert!(result.unwrap().contains("test"));
     }
+
+    #[test]
+    fn test_table_alignment() {
+        let rows = vec![
+            vec!["lightning".to_string(), "0.1.0".to_string()],
+            vec!["rpc".to_string(), "12.0.0".to_string()],
+        ];
+        let table = render_table(&["NAME", "VERSION"], &rows, false);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines[0], "NAME       VERSION");
+        assert_eq!(lines[1], "---------  -------");
+        assert_eq!(lines[2], "lightning  0.1.0");
+        assert_eq!(lines[3], "rpc        12.0.0");
+    }
+
+    #[test]
+    fn test_table_truncation() {
+        let hash = "ab".repeat(32);
+        let rows = vec![vec![hash.clone()]];
+
+        let narrow = render_table(&["HASH"], &rows, false);
+        let cell = narrow.lines().nth(2).unwrap();
+        assert_eq!(cell.chars().count(), TABLE_MAX_CELL_WIDTH);
+        assert!(cell.ends_with('…'));
+
+        let wide = render_table(&["HASH"], &rows, true);
+        assert!(wide.contains(&hash));
+    }
+
+    #[test]
+    fn test_format_rows_table() {
+        let formatter = OutputFormatter::new(OutputFormat::Table);
+        let rows = vec![
+            serde_json::json!({"name": "a", "version": "1.0"}),
+            serde_json::json!({"name": "b", "version": "2.0"}),
+        ];
+        let output = formatter.format_rows(&rows).unwrap();
+        assert!(output.starts_with("name"));
+        assert_eq!(output.lines().count(), 4);
+    }
+
+    #[test]
+    fn test_ndjson_emit_stream() {
+        let formatter = OutputFormatter::new(OutputFormat::Ndjson);
+        let mut stream = Vec::new();
+        for index in 0..3 {
+            formatter
+                .emit_to(
+                    &mut stream,
+                    &serde_json::json!({"index": index, "valid": true}),
+                )
+                .unwrap();
+        }
+        formatter
+            .emit_to(
+                &mut stream,
+                &serde_json::json!({"record": "summary", "count": 3}),
+            )
+            .unwrap();
+
+        let stream = String::from_utf8(stream).unwrap();
+        let records: Vec<serde_json::Value> = stream
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(records.len(), 4);
+        assert_eq!(records[1]["index"], 1);
+        assert_eq!(records[3]["record"], "summary");
+    }
+
+    #[test]
+    fn test_emit_ignored_for_buffered_formats() {
+        let formatter = OutputFormatter::new(OutputFormat::Json);
+        let mut stream = Vec::new();
+        formatter
+            .emit_to(&mut stream, &serde_json::json!({"index": 0}))
+            .unwrap();
+        assert!(stream.is_empty());
+    }
+
+    #[test]
+    fn test_format_list() {
+        let formatter = OutputFormatter::new(OutputFormat::Text);
+        let output = formatter.format_list("Modules", &["lightning", "privacy"]);
+        assert_eq!(output, "Modules:\n  • lightning\n  • privacy\n");
+
+        let formatter = OutputFormatter::new(OutputFormat::Json);
+        let output = formatter.format_list("Modules", &["lightning"]);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["title"], "Modules");
+        assert_eq!(parsed["items"][0], "lightning");
+    }
+
+    #[test]
+    fn test_format_key_value() {
+        let formatter = OutputFormatter::new(OutputFormat::Text);
+        let output = formatter.format_key_value(&[("Public key", "02ab"), ("File", "k.json")]);
+        assert_eq!(output, "Public key:  02ab\nFile:        k.json\n");
+
+        let formatter = OutputFormatter::new(OutputFormat::Json);
+        let output = formatter.format_key_value(&[("file", "k.json")]);
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(parsed["file"], "k.json");
+    }
+
+    #[test]
+    fn test_table_scalar_fallback() {
+        let formatter = OutputFormatter::new(OutputFormat::Table);
+        assert_eq!(formatter.format(&"scalar").unwrap(), "scalar");
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let formatter = OutputFormatter::new(OutputFormat::Csv);
+        let rows = vec![
+            vec!["lightning".to_string(), "Payments, channels".to_string()],
+            vec!["rpc".to_string(), "Says \"hi\"\nand more".to_string()],
+        ];
+        assert_eq!(
+            formatter.format_table(&["NAME", "DESCRIPTION"], &rows),
+            "NAME,DESCRIPTION\r\nlightning,\"Payments, channels\"\r\nrpc,\"Says \"\"hi\"\"\nand more\"\r\n"
+        );
+        assert_eq!(
+            formatter.format_key_value(&[("valid", "2"), ("threshold_met", "true")]),
+            "valid,threshold_met\r\n2,true\r\n"
+        );
+    }
 }
@@ -0,0 +1,245 @@
+//! Composition Checkpoints
+//!
+//! Persist the state of a composed node to disk so it can be inspected,
+//! compared, or restored without recomposing from scratch.
+
+use crate::composition::types::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Current checkpoint file format version
+pub const CHECKPOINT_FORMAT_VERSION: u32 = 1;
+
+/// Saved state of a single loaded module
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModuleCheckpoint {
+    /// Module information at the time of the checkpoint
+    pub info: ModuleInfo,
+    /// Module status
+    pub status: ModuleStatus,
+    /// Module health
+    pub health: ModuleHealth,
+}
+
+/// Saved state of a composed node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeCheckpoint {
+    /// Checkpoint file format version
+    pub format_version: u32,
+    /// Node specification
+    pub spec: NodeSpec,
+    /// Overall node status
+    pub status: NodeStatus,
+    /// Loaded modules
+    pub modules: Vec<ModuleCheckpoint>,
+}
+
+impl NodeCheckpoint {
+    /// Capture the state of a composed node
+    pub fn from_node(node: &ComposedNode) -> Self {
+        Self {
+            format_version: CHECKPOINT_FORMAT_VERSION,
+            spec: node.spec.clone(),
+            status: node.status.clone(),
+            modules: node
+                .modules
+                .iter()
+                .map(|module| ModuleCheckpoint {
+                    info: module.info.clone(),
+                    status: module.status.clone(),
+                    health: module.health.clone(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Parse a checkpoint from JSON
+    ///
+    /// Checkpoints written by a newer format version are rejected.
+    pub fn from_json(json: &str) -> Result<Self> {
+        let checkpoint: NodeCheckpoint = serde_json::from_str(json).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to parse checkpoint: {}", e))
+        })?;
+        if checkpoint.format_version > CHECKPOINT_FORMAT_VERSION {
+            return Err(CompositionError::InvalidConfiguration(format!(
+                "Unsupported checkpoint format version {} (supported: {})",
+                checkpoint.format_version, CHECKPOINT_FORMAT_VERSION
+            )));
+        }
+        Ok(checkpoint)
+    }
+
+    /// Serialize the checkpoint to JSON
+    pub fn to_json(&self) -> Result<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to serialize checkpoint: {}", e))
+        })
+    }
+
+    /// Load a checkpoint file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let json = std::fs::read_to_string(path.as_ref()).map_err(CompositionError::IoError)?;
+        Self::from_json(&json)
+    }
+
+    /// Write the checkpoint to a file
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path.as_ref(), self.to_json()?).map_err(CompositionError::IoError)
+    }
+
+    /// Look up a saved module by name
+    pub fn module_by_name(&self, name: &str) -> Option<&ModuleCheckpoint> {
+        self.modules.iter().find(|m| m.info.name == name)
+    }
+
+    /// Rebuild the composed node as it was when the checkpoint was taken
+    pub fn to_composed_node(&self) -> ComposedNode {
+        ComposedNode {
+            spec: self.spec.clone(),
+            modules: self
+                .modules
+                .iter()
+                .map(|module| LoadedModule {
+                    info: module.info.clone(),
+                    status: module.status.clone(),
+                    health: module.health.clone(),
+                })
+                .collect(),
+            status: self.status.clone(),
+        }
+    }
+}
+
+impl ComposedNode {
+    /// Save the node specification, module statuses, and health to a JSON file
+    pub fn save_checkpoint(&self, path: &Path) -> Result<()> {
+        NodeCheckpoint::from_node(self).save(path)
+    }
+
+    /// Describe how this node differs from a saved checkpoint
+    ///
+    /// Each entry is one human-readable change, phrased from the checkpoint
+    /// to the current state. An empty list means nothing changed.
+    pub fn diff_checkpoint(&self, checkpoint: &Path) -> Result<Vec<String>> {
+        let saved = NodeCheckpoint::load(checkpoint)?;
+        let mut changes = Vec::new();
+
+        if saved.spec.name != self.spec.name {
+            changes.push(format!(
+                "node name: {} -> {}",
+                saved.spec.name, self.spec.name
+            ));
+        }
+        if saved.spec.version != self.spec.version {
+            changes.push(format!(
+                "node version: {:?} -> {:?}",
+                saved.spec.version, self.spec.version
+            ));
+        }
+        if saved.spec.network != self.spec.network {
+            changes.push(format!(
+                "network: {:?} -> {:?}",
+                saved.spec.network, self.spec.network
+            ));
+        }
+        if saved.status != self.status {
+            changes.push(format!(
+                "node status: {:?} -> {:?}",
+                saved.status, self.status
+            ));
+        }
+
+        for module in &saved.modules {
+            let name = &module.info.name;
+            let Some(current) = self.module_by_name(name) else {
+                changes.push(format!("module {}: removed", name));
+                continue;
+            };
+            if module.info.version != current.info.version {
+                changes.push(format!(
+                    "module {}: version {} -> {}",
+                    name, module.info.version, current.info.version
+                ));
+            }
+            if module.status != current.status {
+                changes.push(format!(
+                    "module {}: status {:?} -> {:?}",
+                    name, module.status, current.status
+                ));
+            }
+            if module.health != current.health {
+                changes.push(format!(
+                    "module {}: health {:?} -> {:?}",
+                    name, module.health, current.health
+                ));
+            }
+        }
+        for module in &self.modules {
+            if saved.module_by_name(&module.info.name).is_none() {
+                changes.push(format!(
+                    "module {}: added ({})",
+                    module.info.name, module.info.version
+                ));
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn test_node() -> ComposedNode {
+        ComposedNode {
+            spec: NodeSpec {
+                name: "test-node".to_string(),
+                version: None,
+                network: NetworkType::Regtest,
+                modules: Vec::new(),
+            },
+            modules: vec![LoadedModule {
+                info: ModuleInfo {
+                    name: "lightning".to_string(),
+                    version: "1.0.0".to_string(),
+                    description: None,
+                    author: None,
+                    capabilities: Vec::new(),
+                    dependencies: HashMap::new(),
+                    optional_dependencies: HashMap::new(),
+                    provides: Vec::new(),
+                    entry_point: "lightning".to_string(),
+                    directory: None,
+                    binary_path: None,
+                    config_schema: HashMap::new(),
+                    installed_sha256: None,
+                },
+                status: ModuleStatus::Running,
+                health: ModuleHealth::Healthy,
+            }],
+            status: NodeStatus::Running,
+        }
+    }
+
+    #[test]
+    fn test_checkpoint_json_roundtrip() {
+        let checkpoint = NodeCheckpoint::from_node(&test_node());
+        let parsed = NodeCheckpoint::from_json(&checkpoint.to_json().unwrap()).unwrap();
+        assert_eq!(parsed.format_version, CHECKPOINT_FORMAT_VERSION);
+        assert_eq!(parsed.modules, checkpoint.modules);
+        assert_eq!(parsed.status, NodeStatus::Running);
+    }
+
+    #[test]
+    fn test_checkpoint_rejects_newer_format() {
+        let mut checkpoint = NodeCheckpoint::from_node(&test_node());
+        checkpoint.format_version = CHECKPOINT_FORMAT_VERSION + 1;
+        let result = NodeCheckpoint::from_json(&checkpoint.to_json().unwrap());
+        assert!(matches!(
+            result,
+            Err(CompositionError::InvalidConfiguration(_))
+        ));
+    }
+}
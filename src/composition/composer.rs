@@ -2,27 +2,165 @@
 //!
 //! High-level API for composing Bitcoin nodes from modules.
 
+use crate::composition::checkpoint::{ModuleCheckpoint, NodeCheckpoint};
 use crate::composition::config::NodeConfig;
 use crate::composition::lifecycle::ModuleLifecycle;
-use crate::composition::registry::ModuleRegistry;
-use crate::composition::schema::validate_config_schema;
+use crate::composition::lock::NodeLock;
+use crate::composition::registry::{ModuleRegistry, MODULE_PATH_ENV_VAR};
+use crate::composition::schema::{validate_config_schema, validate_spec_schema};
 use crate::composition::types::*;
 use crate::composition::validation::validate_composition;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+/// Hook run on the node specification before composition
+pub type PreComposeHook = Box<dyn Fn(&NodeSpec) -> Result<()> + Send + Sync>;
+
+/// Hook run on the composed node once every module has started
+pub type PostComposeHook = Box<dyn Fn(&ComposedNode) -> Result<()> + Send + Sync>;
+
+/// Hook run after each module has started
+pub type ModuleStartedHook = Box<dyn Fn(&LoadedModule) -> Result<()> + Send + Sync>;
+
+/// Hook notified when a module fails to load or start
+pub type ModuleErrorHook = Box<dyn Fn(&str, &CompositionError) + Send + Sync>;
+
+/// Callbacks invoked during [`NodeComposer::compose_with_hooks`]
+///
+/// A hook returning `Err` aborts the composition with that error. Modules
+/// already started are left running.
+#[derive(Default)]
+pub struct CompositionHooks {
+    /// Runs before validation
+    pub pre_compose: Option<PreComposeHook>,
+    /// Runs after all modules are started
+    pub post_compose: Option<PostComposeHook>,
+    /// Runs after each module is started
+    pub on_module_started: Option<ModuleStartedHook>,
+    /// Notified with the module name when a module fails to load or start
+    pub on_module_error: Option<ModuleErrorHook>,
+}
+
+impl CompositionHooks {
+    /// Create an empty set of hooks
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the hook run before validation
+    pub fn pre_compose(
+        mut self,
+        hook: impl Fn(&NodeSpec) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.pre_compose = Some(Box::new(hook));
+        self
+    }
+
+    /// Set the hook run after all modules are started
+    pub fn post_compose(
+        mut self,
+        hook: impl Fn(&ComposedNode) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.post_compose = Some(Box::new(hook));
+        self
+    }
+
+    /// Set the hook run after each module is started
+    pub fn on_module_started(
+        mut self,
+        hook: impl Fn(&LoadedModule) -> Result<()> + Send + Sync + 'static,
+    ) -> Self {
+        self.on_module_started = Some(Box::new(hook));
+        self
+    }
+
+    /// Set the hook notified when a module fails to load or start
+    pub fn on_module_error(
+        mut self,
+        hook: impl Fn(&str, &CompositionError) + Send + Sync + 'static,
+    ) -> Self {
+        self.on_module_error = Some(Box::new(hook));
+        self
+    }
+}
+
+impl std::fmt::Debug for CompositionHooks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CompositionHooks")
+            .field("pre_compose", &self.pre_compose.is_some())
+            .field("post_compose", &self.post_compose.is_some())
+            .field("on_module_started", &self.on_module_started.is_some())
+            .field("on_module_error", &self.on_module_error.is_some())
+            .finish()
+    }
+}
 
 /// Node composer for building nodes from modules
 pub struct NodeComposer {
     /// Module lifecycle manager (owns the registry)
     lifecycle: ModuleLifecycle,
+    /// Lockfile checked before and written after composing
+    lockfile: Option<PathBuf>,
+    /// Whether the lockfile is rewritten instead of checked
+    update_lock: bool,
 }
 
 impl NodeComposer {
     /// Create a new node composer
+    ///
+    /// Modules are searched in `modules_dir` first, then in each directory
+    /// listed in `BLLVM_MODULE_PATH` (colon-separated on Unix).
     pub fn new<P: AsRef<Path>>(modules_dir: P) -> Self {
-        let registry = ModuleRegistry::new(modules_dir);
+        let mut registry = ModuleRegistry::new(modules_dir);
+        if let Some(module_path) = std::env::var_os(MODULE_PATH_ENV_VAR) {
+            for dir in std::env::split_paths(&module_path) {
+                if !dir.as_os_str().is_empty() {
+                    registry.add_search_dir(&dir);
+                }
+            }
+        }
         let lifecycle = ModuleLifecycle::new(registry);
 
-        Self { lifecycle }
+        Self {
+            lifecycle,
+            lockfile: None,
+            update_lock: false,
+        }
+    }
+
+    /// Set the directory containing module IPC sockets used for liveness checks
+    pub fn with_socket_dir<P: AsRef<Path>>(mut self, socket_dir: P) -> Self {
+        self.lifecycle = self.lifecycle.with_socket_dir(socket_dir);
+        self
+    }
+
+    /// Set the directory that module stdout/stderr is written to
+    pub fn with_log_dir<P: AsRef<Path>>(mut self, log_dir: P) -> Self {
+        self.lifecycle = self.lifecycle.with_log_dir(log_dir);
+        self
+    }
+
+    /// Enable or disable checking module binaries against their install
+    /// records before starting them (enabled by default)
+    pub fn with_integrity_check(mut self, enabled: bool) -> Self {
+        self.lifecycle = self.lifecycle.with_integrity_check(enabled);
+        self
+    }
+
+    /// Compose against a lockfile
+    ///
+    /// If the lockfile exists, composing fails with
+    /// [`CompositionError::LockfileMismatch`] when any resolved module's
+    /// version or binary hash differs from it. If it does not exist, it is
+    /// written once composing succeeds.
+    pub fn with_lockfile<P: AsRef<Path>>(mut self, path: P) -> Self {
+        self.lockfile = Some(path.as_ref().to_path_buf());
+        self
+    }
+
+    /// Rewrite the lockfile after composing instead of checking it
+    pub fn with_lock_update(mut self, update: bool) -> Self {
+        self.update_lock = update;
+        self
     }
 
     /// Compose node from configuration file
@@ -32,7 +170,11 @@ impl NodeComposer {
     ) -> Result<ComposedNode> {
         // Load configuration
         let config = NodeConfig::from_file(config_path)?;
+        self.compose_from_node_config(config).await
+    }
 
+    /// Compose node from an already loaded configuration
+    pub async fn compose_from_node_config(&mut self, config: NodeConfig) -> Result<ComposedNode> {
         // Validate schema
         let schema_validation = validate_config_schema(&config)?;
         if !schema_validation.valid {
@@ -51,6 +193,26 @@ impl NodeComposer {
 
     /// Compose node from specification
     pub async fn compose_node(&mut self, spec: NodeSpec) -> Result<ComposedNode> {
+        self.compose_with_hooks(spec, CompositionHooks::default())
+            .await
+    }
+
+    /// Compose node from specification, invoking `hooks` along the way
+    ///
+    /// `pre_compose` runs before validation, `on_module_started` after each
+    /// module starts, and `post_compose` once all modules are running.
+    pub async fn compose_with_hooks(
+        &mut self,
+        spec: NodeSpec,
+        hooks: CompositionHooks,
+    ) -> Result<ComposedNode> {
+        if let Some(pre_compose) = &hooks.pre_compose {
+            pre_compose(&spec)?;
+        }
+
+        // Reject malformed specs before consulting the registry
+        spec.assert_valid()?;
+
         // Validate composition
         let validation = self.validate_composition(&spec)?;
         if !validation.valid {
@@ -60,6 +222,18 @@ impl NodeComposer {
             )));
         }
 
+        // Resolved modules must match an existing lockfile unless it is being updated
+        let lock = match &self.lockfile {
+            Some(path) => {
+                let lock = self.lock(&spec)?;
+                if path.exists() && !self.update_lock {
+                    NodeLock::load(path)?.check(&lock)?;
+                }
+                Some((path.clone(), lock))
+            }
+            None => None,
+        };
+
         // Load all modules
         let mut loaded_modules = Vec::new();
         for module_spec in &spec.modules {
@@ -67,21 +241,107 @@ impl NodeComposer {
                 continue;
             }
 
-            let info = self
-                .lifecycle
-                .registry
-                .get_module(&module_spec.name, module_spec.version.as_deref())?;
-
-            // Start module via lifecycle (now async)
-            self.lifecycle_mut().start_module(&info.name).await?;
-            let status = self.lifecycle().get_module_status(&info.name).await?;
-            let health = self.lifecycle().health_check(&info.name).await?;
-
-            loaded_modules.push(LoadedModule {
-                info,
-                status,
-                health,
-            });
+            let module = match self.start_module_spec(module_spec).await {
+                Ok(module) => module,
+                Err(e) => {
+                    if let Some(on_module_error) = &hooks.on_module_error {
+                        on_module_error(&module_spec.name, &e);
+                    }
+                    return Err(e);
+                }
+            };
+            if let Some(on_module_started) = &hooks.on_module_started {
+                on_module_started(&module)?;
+            }
+            loaded_modules.push(module);
+        }
+
+        let node = ComposedNode {
+            spec,
+            modules: loaded_modules,
+            status: NodeStatus::Running,
+        };
+        if let Some(post_compose) = &hooks.post_compose {
+            post_compose(&node)?;
+        }
+        if let Some((path, lock)) = lock {
+            if self.update_lock || !path.exists() {
+                lock.save(&path)?;
+            }
+        }
+        Ok(node)
+    }
+
+    /// Resolve and start one module from the specification
+    async fn start_module_spec(&mut self, module_spec: &ModuleSpec) -> Result<LoadedModule> {
+        let info = self
+            .lifecycle
+            .registry
+            .get_module(&module_spec.name, module_spec.version.as_deref())?;
+
+        // Start module via lifecycle (now async)
+        self.lifecycle_mut().start_module(&info.name).await?;
+        let status = self.lifecycle().get_module_status(&info.name).await?;
+        let health = self.lifecycle().health_check(&info.name).await?;
+
+        Ok(LoadedModule {
+            info,
+            status,
+            health,
+        })
+    }
+
+    /// Restore a node from a checkpoint written by `ComposedNode::save_checkpoint`
+    ///
+    /// Modules saved as `Running` whose IPC socket still accepts connections
+    /// are kept as-is. The remaining enabled modules are validated and started
+    /// as in [`NodeComposer::compose_node`].
+    pub async fn restore_from_checkpoint(&mut self, path: &Path) -> Result<ComposedNode> {
+        let checkpoint = NodeCheckpoint::load(path)?;
+        let spec = checkpoint.spec.clone();
+
+        // Modules still answering on their IPC socket are not restarted
+        let live: Vec<&ModuleCheckpoint> = checkpoint
+            .modules
+            .iter()
+            .filter(|saved| {
+                saved.status == ModuleStatus::Running
+                    && self.lifecycle.module_responds(&saved.info.name)
+            })
+            .collect();
+        let find_live = |name: &str| live.iter().find(|saved| saved.info.name == name).copied();
+
+        let needs_start = spec
+            .modules
+            .iter()
+            .any(|module_spec| module_spec.enabled && find_live(&module_spec.name).is_none());
+        if needs_start {
+            let validation = self.validate_composition(&spec)?;
+            if !validation.valid {
+                return Err(CompositionError::ValidationFailed(format!(
+                    "Composition validation failed: {:?}",
+                    validation.errors
+                )));
+            }
+        }
+
+        let mut loaded_modules = Vec::new();
+        for module_spec in &spec.modules {
+            if !module_spec.enabled {
+                continue;
+            }
+
+            if let Some(saved) = find_live(&module_spec.name) {
+                self.lifecycle.mark_running(&saved.info.name);
+                loaded_modules.push(LoadedModule {
+                    info: saved.info.clone(),
+                    status: ModuleStatus::Running,
+                    health: saved.health.clone(),
+                });
+                continue;
+            }
+
+            loaded_modules.push(self.start_module_spec(module_spec).await?);
         }
 
         Ok(ComposedNode {
@@ -91,9 +351,34 @@ impl NodeComposer {
         })
     }
 
-    /// Validate composition
+    /// Pin the modules `spec` resolves to, including their dependencies
+    pub fn lock(&self, spec: &NodeSpec) -> Result<NodeLock> {
+        let registry = &self.lifecycle.registry;
+        let mut modules = Vec::new();
+        for module_spec in spec.modules.iter().filter(|m| m.enabled) {
+            modules.push(registry.get_module(&module_spec.name, module_spec.version.as_deref())?);
+        }
+        let names: Vec<String> = modules.iter().map(|m| m.name.clone()).collect();
+        for dependency in registry.resolve_dependencies(&names)? {
+            if !modules.iter().any(|m| m.name == dependency.name) {
+                modules.push(dependency);
+            }
+        }
+        NodeLock::from_modules(&modules)
+    }
+
+    /// Write a lockfile pinning the modules `spec` resolves to
+    pub fn write_lockfile<P: AsRef<Path>>(&self, spec: &NodeSpec, path: P) -> Result<NodeLock> {
+        let lock = self.lock(spec)?;
+        lock.save(path)?;
+        Ok(lock)
+    }
+
+    /// Validate composition (spec schema merged with module resolution)
     pub fn validate_composition(&self, spec: &NodeSpec) -> Result<ValidationResult> {
-        validate_composition(spec, &self.lifecycle.registry)
+        let schema_validation = validate_spec_schema(spec)?;
+        let composition_validation = validate_composition(spec, &self.lifecycle.registry)?;
+        Ok(schema_validation.merge(composition_validation))
     }
 
     /// Generate configuration template
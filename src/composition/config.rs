@@ -2,10 +2,15 @@
 //!
 //! TOML-based declarative configuration format for node composition.
 
+use crate::composition::registry::ModuleRegistry;
+use crate::composition::schema::validate_config_schema;
 use crate::composition::types::*;
+use crate::composition::validation::{validate_composition, validate_module_requirements};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use std::fmt;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 /// Node configuration from TOML file
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -62,38 +67,38 @@ impl NodeConfig {
     /// Load configuration from TOML file
     pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
         let contents = std::fs::read_to_string(path.as_ref()).map_err(CompositionError::IoError)?;
+        Self::from_toml_str(&contents)
+    }
 
-        let config: NodeConfig = toml::from_str(&contents).map_err(|e| {
-            CompositionError::InvalidConfiguration(format!("Failed to parse TOML: {}", e))
-        })?;
-
-        Ok(config)
+    /// Parse configuration from a TOML string
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        Ok(s.parse()?)
     }
 
     /// Save configuration to TOML file
     pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<()> {
-        let toml_string = toml::to_string_pretty(self).map_err(|e| {
-            CompositionError::SerializationError(format!("Failed to serialize config: {}", e))
-        })?;
+        let toml_string = self.to_toml_string()?;
 
         std::fs::write(path.as_ref(), toml_string).map_err(CompositionError::IoError)?;
 
         Ok(())
     }
 
+    /// Serialize configuration to a TOML string
+    pub fn to_toml_string(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to serialize config: {}", e))
+        })
+    }
+
     /// Convert to NodeSpec
     pub fn to_spec(&self) -> Result<NodeSpec> {
-        let network = match self.node.network.as_str() {
-            "mainnet" => NetworkType::Mainnet,
-            "testnet" => NetworkType::Testnet,
-            "regtest" => NetworkType::Regtest,
-            _ => {
-                return Err(CompositionError::InvalidConfiguration(format!(
-                    "Unknown network type: {}",
-                    self.node.network
-                )))
-            }
-        };
+        let network: NetworkType = self.node.network.parse().map_err(|_| {
+            CompositionError::InvalidConfiguration(format!(
+                "Unknown network type: {}",
+                self.node.network
+            ))
+        })?;
 
         let modules: Result<Vec<ModuleSpec>> = self
             .modules
@@ -127,6 +132,28 @@ impl NodeConfig {
         })
     }
 
+    /// Validate the configuration against the modules in `registry`
+    ///
+    /// Runs the schema checks, module resolution, and per-module checks
+    /// (config schema, dependency availability, network support) and merges
+    /// the results. Registry checks are skipped if the network is invalid.
+    pub fn validate_with_registry(&self, registry: &ModuleRegistry) -> Result<ValidationResult> {
+        let schema_validation = validate_config_schema(self)?;
+        let Ok(spec) = self.to_spec() else {
+            return Ok(schema_validation);
+        };
+        Ok(schema_validation
+            .merge(validate_composition(&spec, registry)?)
+            .merge(validate_module_requirements(&spec, registry)?))
+    }
+
+    /// Discover the modules in `modules_dir` and run [`Self::validate_with_registry`]
+    pub fn preflight_check(&self, modules_dir: &Path) -> Result<ValidationResult> {
+        let mut registry = ModuleRegistry::new(modules_dir);
+        registry.discover_modules()?;
+        self.validate_with_registry(&registry)
+    }
+
     /// Generate template configuration
     pub fn template() -> Self {
         let mut modules = HashMap::new();
@@ -159,6 +186,76 @@ impl NodeConfig {
             modules,
         }
     }
+
+    /// Generate a fuller example configuration
+    ///
+    /// Unlike [`Self::template`], every field has a non-default value and
+    /// both modules are enabled with module-specific settings.
+    pub fn example() -> Self {
+        let mut modules = HashMap::new();
+
+        modules.insert(
+            "lightning".to_string(),
+            ModuleConfig {
+                enabled: true,
+                version: Some("0.1.0".to_string()),
+                config: HashMap::from([
+                    ("listen_port".to_string(), toml::Value::Integer(9735)),
+                    (
+                        "alias".to_string(),
+                        toml::Value::String("example-node".to_string()),
+                    ),
+                ]),
+            },
+        );
+
+        modules.insert(
+            "privacy".to_string(),
+            ModuleConfig {
+                enabled: true,
+                version: Some("0.2.0".to_string()),
+                config: HashMap::from([
+                    ("tor".to_string(), toml::Value::Boolean(true)),
+                    ("max_peers".to_string(), toml::Value::Integer(16)),
+                ]),
+            },
+        );
+
+        Self {
+            node: NodeMetadata {
+                name: "example-node".to_string(),
+                version: Some("1.2.0".to_string()),
+                network: "testnet".to_string(),
+            },
+            modules,
+        }
+    }
+}
+
+/// Error parsing a [`NodeConfig`] from TOML
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+#[error("Failed to parse TOML: {0}")]
+pub struct ParseError(String);
+
+impl From<ParseError> for CompositionError {
+    fn from(e: ParseError) -> Self {
+        CompositionError::InvalidConfiguration(e.to_string())
+    }
+}
+
+impl FromStr for NodeConfig {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        toml::from_str(s).map_err(|e| ParseError(e.to_string()))
+    }
+}
+
+impl fmt::Display for NodeConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let toml_string = toml::to_string_pretty(self).map_err(|_| fmt::Error)?;
+        f.write_str(&toml_string)
+    }
 }
 
 /// Convert toml::Value to serde_json::Value
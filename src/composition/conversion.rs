@@ -17,10 +17,13 @@ impl From<&RefDiscoveredModule> for ModuleInfo {
             author: discovered.manifest.author.clone(),
             capabilities: discovered.manifest.capabilities.clone(),
             dependencies: discovered.manifest.dependencies.clone(),
+            optional_dependencies: HashMap::new(),
+            provides: Vec::new(),
             entry_point: discovered.manifest.entry_point.clone(),
             directory: Some(discovered.directory.clone()),
             binary_path: Some(discovered.binary_path.clone()),
             config_schema: discovered.manifest.config_schema.clone(),
+            installed_sha256: None,
         }
     }
 }
@@ -40,10 +43,13 @@ impl From<&RefModuleMetadata> for ModuleInfo {
             author: Some(metadata.author.clone()),
             capabilities: metadata.capabilities.clone(),
             dependencies: metadata.dependencies.clone(),
+            optional_dependencies: metadata.optional_dependencies.clone(),
+            provides: Vec::new(),
             entry_point: metadata.entry_point.clone(),
             directory: None,
             binary_path: None,
             config_schema: HashMap::new(),
+            installed_sha256: None,
         }
     }
 }
@@ -63,7 +69,7 @@ impl From<ModuleInfo> for RefModuleMetadata {
             author: info.author.unwrap_or_default(),
             capabilities: info.capabilities,
             dependencies: info.dependencies,
-            optional_dependencies: HashMap::new(), // ModuleInfo doesn't track optional deps separately
+            optional_dependencies: info.optional_dependencies,
             entry_point: info.entry_point,
         }
     }
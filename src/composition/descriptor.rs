@@ -0,0 +1,253 @@
+//! Node Composition Descriptors
+//!
+//! Compact, script-descriptor-like strings describing a node composition:
+//!
+//! ```text
+//! node(mainnet,[lightning@1.0{port=9735},privacy@2.0,!rpc])
+//! ```
+//!
+//! Each module is `name`, optionally followed by `@version` and an inline
+//! `{key=value,...}` config; a leading `!` marks the module disabled. Config
+//! values are parsed as JSON where possible and as plain strings otherwise.
+//! Descriptors do not carry the node name or version.
+
+use crate::composition::types::*;
+use std::collections::HashMap;
+
+/// Node name used for specs parsed from a descriptor
+pub const DESCRIPTOR_NODE_NAME: &str = "node";
+
+impl NodeSpec {
+    /// Parse a node descriptor such as `node(mainnet,[lightning@1.0,rpc])`
+    pub fn from_descriptor(s: &str) -> Result<NodeSpec> {
+        let body = s
+            .trim()
+            .strip_prefix("node(")
+            .and_then(|rest| rest.strip_suffix(')'))
+            .ok_or_else(|| invalid("expected node(<network>,[<modules>])"))?;
+
+        let (network, modules) = body
+            .split_once(',')
+            .ok_or_else(|| invalid("expected node(<network>,[<modules>])"))?;
+        let network = parse_network(network.trim())?;
+
+        let modules = modules
+            .trim()
+            .strip_prefix('[')
+            .and_then(|rest| rest.strip_suffix(']'))
+            .ok_or_else(|| invalid("module list must be enclosed in [ ]"))?;
+        let modules = if modules.trim().is_empty() {
+            Vec::new()
+        } else {
+            split_top_level(modules)?
+                .into_iter()
+                .map(parse_module)
+                .collect::<Result<Vec<_>>>()?
+        };
+
+        Ok(NodeSpec {
+            name: DESCRIPTOR_NODE_NAME.to_string(),
+            version: None,
+            network,
+            modules,
+        })
+    }
+
+    /// Render this spec as a node descriptor
+    pub fn to_descriptor(&self) -> String {
+        let modules: Vec<String> = self.modules.iter().map(format_module).collect();
+        format!("node({},[{}])", self.network, modules.join(","))
+    }
+}
+
+/// Build an invalid-descriptor error
+fn invalid(reason: &str) -> CompositionError {
+    CompositionError::InvalidConfiguration(format!("Invalid node descriptor: {}", reason))
+}
+
+/// Parse a network name
+fn parse_network(network: &str) -> Result<NetworkType> {
+    network
+        .parse()
+        .map_err(|_| invalid(&format!("unknown network '{}'", network)))
+}
+
+/// Parse one module entry: `[!]name[@version][{key=value,...}]`
+fn parse_module(entry: &str) -> Result<ModuleSpec> {
+    let entry = entry.trim();
+    let (enabled, entry) = match entry.strip_prefix('!') {
+        Some(rest) => (false, rest),
+        None => (true, entry),
+    };
+
+    let (head, config) = match entry.find('{') {
+        Some(start) => {
+            let inner = entry[start + 1..]
+                .strip_suffix('}')
+                .ok_or_else(|| invalid(&format!("unterminated config in '{}'", entry)))?;
+            (&entry[..start], parse_config(inner)?)
+        }
+        None => (entry, HashMap::new()),
+    };
+
+    let (name, version) = match head.split_once('@') {
+        Some((name, version)) if !version.is_empty() => (name, Some(version.to_string())),
+        Some(_) => return Err(invalid(&format!("empty version in '{}'", entry))),
+        None => (head, None),
+    };
+    if name.is_empty() || !name.chars().all(is_name_char) {
+        return Err(invalid(&format!("invalid module name '{}'", name)));
+    }
+
+    Ok(ModuleSpec {
+        name: name.to_string(),
+        version,
+        enabled,
+        config,
+    })
+}
+
+/// Parse inline module config: `key=value,...`
+fn parse_config(inner: &str) -> Result<HashMap<String, serde_json::Value>> {
+    if inner.trim().is_empty() {
+        return Ok(HashMap::new());
+    }
+    split_top_level(inner)?
+        .into_iter()
+        .map(|pair| {
+            let (key, value) = pair
+                .split_once('=')
+                .ok_or_else(|| invalid(&format!("expected key=value, got '{}'", pair)))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(is_name_char) {
+                return Err(invalid(&format!("invalid config key '{}'", key)));
+            }
+            let value = value.trim();
+            let value = serde_json::from_str(value)
+                .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+            Ok((key.to_string(), value))
+        })
+        .collect()
+}
+
+/// Split on commas that are not nested in brackets, braces, or quotes
+fn split_top_level(s: &str) -> Result<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut start = 0;
+
+    for (i, c) in s.char_indices() {
+        if in_string {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => in_string = true,
+            '{' | '[' => depth += 1,
+            '}' | ']' => {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| invalid("unbalanced brackets"))?
+            }
+            ',' if depth == 0 => {
+                parts.push(&s[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if depth != 0 || in_string {
+        return Err(invalid("unbalanced brackets"));
+    }
+    parts.push(&s[start..]);
+    Ok(parts)
+}
+
+/// Characters allowed in module names and config keys
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.')
+}
+
+/// Render one module entry
+fn format_module(module: &ModuleSpec) -> String {
+    let mut entry = String::new();
+    if !module.enabled {
+        entry.push('!');
+    }
+    entry.push_str(&module.name);
+    if let Some(version) = &module.version {
+        entry.push('@');
+        entry.push_str(version);
+    }
+    if !module.config.is_empty() {
+        let mut keys: Vec<&String> = module.config.keys().collect();
+        keys.sort();
+        let pairs: Vec<String> = keys
+            .into_iter()
+            .map(|key| format!("{}={}", key, format_value(&module.config[key])))
+            .collect();
+        entry.push('{');
+        entry.push_str(&pairs.join(","));
+        entry.push('}');
+    }
+    entry
+}
+
+/// Render a config value, leaving simple strings unquoted
+fn format_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s)
+            if !s.is_empty()
+                && s.chars().all(|c| is_name_char(c) || matches!(c, '/' | ':'))
+                && serde_json::from_str::<serde_json::Value>(s).is_err() =>
+        {
+            s.clone()
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_descriptor() {
+        let spec =
+            NodeSpec::from_descriptor("node(mainnet,[lightning@1.0{port=9735},privacy@2.0,!rpc])")
+                .unwrap();
+        assert_eq!(spec.network, NetworkType::Mainnet);
+        assert_eq!(spec.modules.len(), 3);
+
+        let lightning = &spec.modules[0];
+        assert_eq!(lightning.name, "lightning");
+        assert_eq!(lightning.version.as_deref(), Some("1.0"));
+        assert_eq!(lightning.config["port"], serde_json::json!(9735));
+
+        assert_eq!(spec.modules[2].name, "rpc");
+        assert!(spec.modules[2].version.is_none());
+        assert!(!spec.modules[2].enabled);
+    }
+
+    #[test]
+    fn test_invalid_descriptors() {
+        for desc in [
+            "mainnet,[rpc]",
+            "node(signet,[rpc])",
+            "node(mainnet,rpc)",
+            "node(mainnet,[rpc@])",
+            "node(mainnet,[rpc{port}])",
+            "node(mainnet,[rpc{port=1])",
+            "node(mainnet,[bad name])",
+        ] {
+            assert!(NodeSpec::from_descriptor(desc).is_err(), "{}", desc);
+        }
+    }
+}
@@ -0,0 +1,325 @@
+//! Git Checkouts
+//!
+//! Fetches a single revision of a module repository using the `git` CLI.
+//! Only the requested revision is fetched (shallow), falling back to a full
+//! fetch for commits the remote will not serve directly.
+//!
+//! URLs may carry a `git+` prefix (`git+https://`, `git+ssh://`), as in
+//! package manifests; it is stripped before running git.
+
+use crate::composition::install::check_entry_point;
+use crate::composition::types::*;
+use crate::module::manifest::ModuleManifest;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Check out `reference` of the repository at `url` into `dest`
+///
+/// `dest` is created if needed and must not already be a git repository.
+/// Git's stderr is included in the error when a step fails.
+pub fn checkout(url: &str, reference: &GitReference, dest: &Path) -> Result<()> {
+    let url = normalize_url(url);
+    validate(url, reference)?;
+
+    std::fs::create_dir_all(dest)?;
+    git(dest, &["init", "-q"])?;
+
+    match reference {
+        GitReference::Commit(sha) => {
+            // Not every server allows fetching an unadvertised commit by SHA
+            if git(dest, &["fetch", "-q", "--depth", "1", url, sha]).is_err() {
+                git(
+                    dest,
+                    &["fetch", "-q", url, "+refs/heads/*:refs/remotes/origin/*"],
+                )?;
+            }
+            git(dest, &["checkout", "-q", "--detach", sha])
+        }
+        _ => {
+            git(
+                dest,
+                &["fetch", "-q", "--depth", "1", url, &reference.refspec()],
+            )?;
+            // Annotated tags fetch a tag object, so peel to its commit
+            git(dest, &["checkout", "-q", "--detach", "FETCH_HEAD^{commit}"])
+        }
+    }
+}
+
+/// Strip a `git+` prefix (`git+https://...` becomes `https://...`)
+pub fn normalize_url(url: &str) -> &str {
+    url.strip_prefix("git+").unwrap_or(url)
+}
+
+/// A checkout in a temporary directory, removed when dropped
+pub struct Checkout {
+    dir: PathBuf,
+}
+
+impl Checkout {
+    /// Check out `reference` of `url` into a fresh temporary directory
+    ///
+    /// Nothing is left behind if the checkout fails.
+    pub fn new(url: &str, reference: &GitReference) -> Result<Self> {
+        let temp = Checkout {
+            dir: std::env::temp_dir().join(format!("bllvm-git-{}", uuid::Uuid::new_v4())),
+        };
+        checkout(url, reference, &temp.dir)?;
+        Ok(temp)
+    }
+
+    /// Root of the working tree
+    pub fn path(&self) -> &Path {
+        &self.dir
+    }
+
+    /// Locate the module manifest (see [`ModuleManifest::locate`])
+    pub fn find_manifest(&self) -> Result<PathBuf> {
+        ModuleManifest::locate(&self.dir)
+    }
+
+    /// Path to the module binary, building it with cargo if it is not committed
+    ///
+    /// `entry_point` is relative to the manifest's directory and may not leave
+    /// it. Crates are built in release mode into a target directory inside the
+    /// checkout.
+    pub fn module_binary(&self, manifest_dir: &Path, entry_point: &str) -> Result<PathBuf> {
+        check_entry_point(entry_point)?;
+        let committed = manifest_dir.join(entry_point);
+        if committed.is_file() {
+            return Ok(committed);
+        }
+
+        let cargo_manifest = [manifest_dir, self.dir.as_path()]
+            .iter()
+            .map(|dir| dir.join("Cargo.toml"))
+            .find(|path| path.is_file())
+            .ok_or_else(|| {
+                CompositionError::InstallationFailed(format!(
+                    "Module binary {} is not in the repository and there is no Cargo.toml to build it",
+                    entry_point
+                ))
+            })?;
+        let bin_name = Path::new(entry_point)
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                CompositionError::InstallationFailed(format!("Invalid entry point {}", entry_point))
+            })?;
+
+        let target_dir = self.dir.join("target");
+        let output = Command::new(std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+            .args(["build", "--release", "--bin", bin_name, "--manifest-path"])
+            .arg(&cargo_manifest)
+            .arg("--target-dir")
+            .arg(&target_dir)
+            .output()
+            .map_err(|e| {
+                CompositionError::InstallationFailed(format!("Failed to run cargo: {}", e))
+            })?;
+        if !output.status.success() {
+            return Err(CompositionError::InstallationFailed(format!(
+                "cargo build failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        let built = target_dir.join("release").join(bin_name);
+        if built.is_file() {
+            Ok(built)
+        } else {
+            Err(CompositionError::InstallationFailed(format!(
+                "cargo build did not produce {:?}",
+                built
+            )))
+        }
+    }
+}
+
+impl Drop for Checkout {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Reject arguments git would parse as options, and malformed SHAs
+fn validate(url: &str, reference: &GitReference) -> Result<()> {
+    let invalid = |msg: String| Err(CompositionError::InstallationFailed(msg));
+
+    if url.is_empty() || url.starts_with('-') {
+        return invalid(format!("Invalid git URL: {:?}", url));
+    }
+    match reference {
+        GitReference::Tag(name) | GitReference::Branch(name)
+            if name.is_empty() || name.starts_with('-') =>
+        {
+            invalid(format!("Invalid git {}", reference))
+        }
+        GitReference::Commit(sha)
+            if sha.len() != 40 || !sha.chars().all(|c| c.is_ascii_hexdigit()) =>
+        {
+            invalid(format!(
+                "Invalid git commit {:?}: expected a 40-character SHA",
+                sha
+            ))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Run git in `dir`, never prompting for credentials
+fn git(dir: &Path, args: &[&str]) -> Result<()> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .map_err(|e| CompositionError::InstallationFailed(format!("Failed to run git: {}", e)))?;
+
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(CompositionError::InstallationFailed(format!(
+            "git {} failed: {}",
+            args[0],
+            String::from_utf8_lossy(&output.stderr).trim()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::module::manifest::MANIFEST_FILE;
+    use tempfile::TempDir;
+
+    fn run(dir: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8(output.stdout).unwrap().trim().to_string()
+    }
+
+    fn commit(dir: &Path, version: &str) -> String {
+        std::fs::write(dir.join("VERSION"), version).unwrap();
+        run(dir, &["add", "VERSION"]);
+        run(dir, &["commit", "-q", "-m", version]);
+        run(dir, &["rev-parse", "HEAD"])
+    }
+
+    /// Repository with history: v1 (annotated tag) -> v2 (commit) -> v3 (main),
+    /// plus a `dev` branch at v4
+    fn fixture() -> (TempDir, String) {
+        let dir = TempDir::new().unwrap();
+        let repo = dir.path();
+        run(repo, &["init", "-q", "-b", "main"]);
+        commit(repo, "v1");
+        run(repo, &["tag", "-a", "v1.0.0", "-m", "release"]);
+        let v2 = commit(repo, "v2");
+        commit(repo, "v3");
+        run(repo, &["checkout", "-q", "-b", "dev"]);
+        commit(repo, "v4");
+        run(repo, &["checkout", "-q", "main"]);
+        (dir, v2)
+    }
+
+    fn checked_out(url: &str, reference: GitReference) -> String {
+        let dest = TempDir::new().unwrap();
+        checkout(url, &reference, dest.path()).unwrap();
+        std::fs::read_to_string(dest.path().join("VERSION")).unwrap()
+    }
+
+    #[test]
+    fn test_checkout_each_reference() {
+        let (repo, v2) = fixture();
+        let url = repo.path().to_str().unwrap();
+
+        assert_eq!(checked_out(url, GitReference::Tag("v1.0.0".into())), "v1");
+        assert_eq!(checked_out(url, GitReference::Commit(v2)), "v2");
+        assert_eq!(checked_out(url, GitReference::Latest), "v3");
+        assert_eq!(checked_out(url, GitReference::Branch("dev".into())), "v4");
+    }
+
+    #[test]
+    fn test_checkout_failures() {
+        let (repo, _) = fixture();
+        let url = repo.path().to_str().unwrap();
+        let dest = TempDir::new().unwrap();
+
+        // A branch is not a tag, and git's stderr is surfaced
+        let err = checkout(url, &GitReference::Tag("dev".into()), dest.path()).unwrap_err();
+        assert!(
+            matches!(err, CompositionError::InstallationFailed(ref msg) if msg.contains("git fetch failed"))
+        );
+
+        for (url, reference) in [
+            ("--upload-pack=evil", GitReference::Latest),
+            (url, GitReference::Branch("--force".into())),
+            (url, GitReference::Commit("abc123".into())),
+        ] {
+            assert!(checkout(url, &reference, dest.path()).is_err());
+        }
+    }
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("git+https://github.com/org/module.git"),
+            "https://github.com/org/module.git"
+        );
+        assert_eq!(
+            normalize_url("git+ssh://git@github.com/org/module.git"),
+            "ssh://git@github.com/org/module.git"
+        );
+        assert_eq!(normalize_url("/srv/git/module"), "/srv/git/module");
+    }
+
+    #[test]
+    fn test_temporary_checkout() {
+        let (repo, _) = fixture();
+        std::fs::create_dir(repo.path().join("indexer")).unwrap();
+        std::fs::write(repo.path().join("indexer").join(MANIFEST_FILE), "").unwrap();
+        std::fs::write(repo.path().join("indexer").join("indexer"), "#!/bin/sh\n").unwrap();
+        run(repo.path(), &["add", "."]);
+        run(repo.path(), &["commit", "-q", "-m", "module"]);
+        let url = format!("git+file://{}", repo.path().display());
+
+        let checkout = Checkout::new(&url, &GitReference::Latest).unwrap();
+        let dir = checkout.path().to_path_buf();
+        let manifest = checkout.find_manifest().unwrap();
+        assert_eq!(manifest, dir.join("indexer").join(MANIFEST_FILE));
+        assert_eq!(
+            checkout
+                .module_binary(manifest.parent().unwrap(), "indexer")
+                .unwrap(),
+            dir.join("indexer").join("indexer")
+        );
+
+        // Without a committed binary or a crate to build, nothing can be installed
+        let err = checkout
+            .module_binary(manifest.parent().unwrap(), "missing")
+            .unwrap_err();
+        assert!(err.to_string().contains("no Cargo.toml"), "{}", err);
+
+        // Entry points may not leave the manifest's directory
+        for entry_point in ["../../x", "../indexer/indexer", "/bin/sh", ""] {
+            assert!(matches!(
+                checkout.module_binary(manifest.parent().unwrap(), entry_point),
+                Err(CompositionError::InstallationFailed(ref msg)) if msg.contains("relative path")
+            ));
+        }
+
+        drop(checkout);
+        assert!(!dir.exists());
+
+        // Tags without a manifest have nothing to install
+        let checkout = Checkout::new(&url, &GitReference::Tag("v1.0.0".into())).unwrap();
+        assert!(checkout.find_manifest().is_err());
+    }
+}
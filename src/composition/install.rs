@@ -0,0 +1,81 @@
+//! Install Metadata
+//!
+//! `install_module` records where each module came from and the SHA256 of
+//! its binary in `install.json`, next to the module's manifest. The hash is
+//! checked again before the module starts, so a binary swapped on disk after
+//! installation is refused.
+
+use crate::composition::types::*;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Component, Path};
+
+/// Install record file name, in the module directory
+pub const INSTALL_FILE: &str = "install.json";
+
+/// How and when a module was installed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstallMetadata {
+    /// Where the module was installed from
+    pub source: ModuleSource,
+    /// Installed module version
+    pub version: String,
+    /// SHA256 of the module binary at install time (hex)
+    pub sha256: String,
+    /// When the module was installed
+    pub installed_at: DateTime<Utc>,
+}
+
+impl InstallMetadata {
+    /// Record installing `binary` (version `version`) from `source` now
+    pub fn new(source: ModuleSource, version: &str, binary: &Path) -> Result<Self> {
+        Ok(Self {
+            source,
+            version: version.to_string(),
+            sha256: sha256_file(binary)?,
+            installed_at: Utc::now(),
+        })
+    }
+
+    /// Read the install record of the module in `module_dir`, if it has one
+    pub fn load(module_dir: &Path) -> Result<Option<Self>> {
+        let path = module_dir.join(INSTALL_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        let json = std::fs::read_to_string(&path)?;
+        serde_json::from_str(&json)
+            .map(Some)
+            .map_err(|e| CompositionError::SerializationError(format!("Invalid {:?}: {}", path, e)))
+    }
+
+    /// Write the install record into `module_dir`
+    pub fn save(&self, module_dir: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| CompositionError::SerializationError(e.to_string()))?;
+        std::fs::write(module_dir.join(INSTALL_FILE), json)?;
+        Ok(())
+    }
+}
+
+/// SHA256 of a file (hex)
+pub fn sha256_file(path: &Path) -> Result<String> {
+    Ok(hex::encode(Sha256::digest(std::fs::read(path)?)))
+}
+
+/// Check that a manifest's `entry_point` stays inside the module directory
+///
+/// Manifests from git repositories and registries are untrusted, so the
+/// entry point must be a relative path of plain components before it is
+/// joined onto a checkout or install directory.
+pub fn check_entry_point(entry_point: &str) -> Result<()> {
+    let path = Path::new(entry_point);
+    if entry_point.is_empty() || !path.components().all(|c| matches!(c, Component::Normal(_))) {
+        return Err(CompositionError::InstallationFailed(format!(
+            "Module entry point '{}' must be a relative path inside the module",
+            entry_point
+        )));
+    }
+    Ok(())
+}
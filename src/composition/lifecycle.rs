@@ -3,11 +3,16 @@
 //! Handles starting, stopping, restarting, and health checking of modules.
 
 use crate::composition::conversion::*;
+use crate::composition::logs::module_log_path;
 use crate::composition::registry::ModuleRegistry;
+use crate::composition::resources::{
+    ProcessController, ResourceLimits, ResourceUsage, SystemProcessController,
+};
 use crate::composition::types::*;
 use blvm_node::module::manager::ModuleManager;
 use blvm_node::module::traits::ModuleMetadata as RefModuleMetadata;
 use std::collections::HashMap;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -19,6 +24,20 @@ pub struct ModuleLifecycle {
     module_manager: Option<Arc<Mutex<ModuleManager>>>,
     /// Module status cache
     status_cache: HashMap<String, ModuleStatus>,
+    /// Process IDs of running modules
+    process_ids: HashMap<String, u32>,
+    /// Directory containing module IPC sockets (`<name>.sock`)
+    socket_dir: Option<PathBuf>,
+    /// File used by `save_state` / `load_state`
+    state_file: Option<PathBuf>,
+    /// Directory receiving module stdout/stderr (`<name>.log`)
+    log_dir: Option<PathBuf>,
+    /// Per-module resource limits
+    resource_limits: HashMap<String, ResourceLimits>,
+    /// Process layer that applies limits and reports usage
+    process_controller: Arc<dyn ProcessController>,
+    /// Whether binaries are checked against their install record before starting
+    integrity_check: bool,
 }
 
 impl ModuleLifecycle {
@@ -28,6 +47,13 @@ impl ModuleLifecycle {
             registry,
             module_manager: None,
             status_cache: HashMap::new(),
+            process_ids: HashMap::new(),
+            socket_dir: None,
+            state_file: None,
+            log_dir: None,
+            resource_limits: HashMap::new(),
+            process_controller: Arc::new(SystemProcessController::new()),
+            integrity_check: true,
         }
     }
 
@@ -37,9 +63,95 @@ impl ModuleLifecycle {
         self
     }
 
+    /// Set the directory containing module IPC sockets used for liveness checks
+    pub fn with_socket_dir<P: AsRef<Path>>(mut self, socket_dir: P) -> Self {
+        self.socket_dir = Some(socket_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the file used to persist lifecycle state
+    pub fn with_state_file<P: AsRef<Path>>(mut self, state_file: P) -> Self {
+        self.state_file = Some(state_file.as_ref().to_path_buf());
+        self
+    }
+
+    /// Set the directory that module stdout/stderr is written to
+    pub fn with_log_dir<P: AsRef<Path>>(mut self, log_dir: P) -> Self {
+        self.log_dir = Some(log_dir.as_ref().to_path_buf());
+        self
+    }
+
+    /// Log file a module's stdout/stderr is written to, if a log directory is set
+    pub fn log_path(&self, name: &str) -> Option<PathBuf> {
+        self.log_dir
+            .as_ref()
+            .map(|log_dir| module_log_path(log_dir, name))
+    }
+
+    /// Set resource limits applied to a module's process when it starts
+    pub fn with_resource_limits(mut self, module_name: &str, limits: ResourceLimits) -> Self {
+        self.resource_limits.insert(module_name.to_string(), limits);
+        self
+    }
+
+    /// Set the process layer used to apply limits and read usage
+    pub fn with_process_controller(mut self, controller: Arc<dyn ProcessController>) -> Self {
+        self.process_controller = controller;
+        self
+    }
+
+    /// Enable or disable checking module binaries before they start
+    ///
+    /// Enabled by default; see [`ModuleRegistry::verify_integrity`].
+    pub fn with_integrity_check(mut self, enabled: bool) -> Self {
+        self.integrity_check = enabled;
+        self
+    }
+
+    /// Get the resource limits configured for a module
+    pub fn resource_limits(&self, name: &str) -> Option<&ResourceLimits> {
+        self.resource_limits.get(name)
+    }
+
+    /// Record the process ID of a running module
+    pub fn set_process_id(&mut self, name: &str, pid: u32) {
+        self.process_ids.insert(name.to_string(), pid);
+    }
+
     /// Start a module
+    ///
+    /// With a log directory set, the module's stdout and stderr are appended
+    /// to its log file (see [`ModuleLifecycle::log_path`]); the file is
+    /// created here and handed to the module manager as the `log_file`
+    /// config entry.
+    ///
+    /// Unless disabled with [`ModuleLifecycle::with_integrity_check`], a
+    /// module whose binary no longer matches its install record is not
+    /// started.
     pub async fn start_module(&mut self, name: &str) -> Result<()> {
         let info = self.registry.get_module(name, None)?;
+        if self.integrity_check {
+            if let Err(e) = self.registry.verify_integrity(name) {
+                self.status_cache
+                    .insert(name.to_string(), ModuleStatus::Error(e.to_string()));
+                return Err(e);
+            }
+        }
+
+        let mut config = HashMap::new(); // TODO: Get config from ModuleSpec
+        if let Some(log_path) = self.log_path(name) {
+            if let Some(log_dir) = log_path.parent() {
+                std::fs::create_dir_all(log_dir)?;
+            }
+            std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&log_path)?;
+            config.insert(
+                "log_file".to_string(),
+                log_path.to_string_lossy().into_owned(),
+            );
+        }
 
         if let Some(ref manager) = self.module_manager {
             // Convert ModuleInfo to ModuleMetadata
@@ -51,26 +163,50 @@ impl ModuleLifecycle {
 
             // Load module via ModuleManager
             let mut mgr = manager.lock().await;
-            mgr.load_module(
-                &info.name,
-                binary_path,
-                metadata,
-                HashMap::new(), // TODO: Get config from ModuleSpec
-            )
-            .await
-            .map_err(|e| CompositionError::from(e))?;
-
-            self.status_cache
-                .insert(name.to_string(), ModuleStatus::Running);
-        } else {
-            // Fallback: just cache status
-            self.status_cache
-                .insert(name.to_string(), ModuleStatus::Running);
+            mgr.load_module(&info.name, binary_path, metadata, config)
+                .await
+                .map_err(|e| CompositionError::from(e))?;
+        }
+
+        // Limits can only be applied once the module's process ID is known
+        if self.process_ids.contains_key(name) {
+            if let Err(e) = self.apply_resource_limits(name) {
+                self.status_cache.insert(
+                    name.to_string(),
+                    ModuleStatus::Error(format!("Failed to apply resource limits: {}", e)),
+                );
+                return Err(e);
+            }
         }
 
+        self.status_cache
+            .insert(name.to_string(), ModuleStatus::Running);
         Ok(())
     }
 
+    /// Apply a module's configured resource limits to its recorded process
+    ///
+    /// Does nothing if no limits are configured for the module.
+    pub fn apply_resource_limits(&self, name: &str) -> Result<()> {
+        let Some(limits) = self.resource_limits.get(name) else {
+            return Ok(());
+        };
+        let pid = self.module_pid(name)?;
+        self.process_controller.apply_limits(name, pid, limits)
+    }
+
+    /// Get the current memory and CPU consumption of a module's process
+    pub fn get_resource_usage(&self, name: &str) -> Result<ResourceUsage> {
+        let pid = self.module_pid(name)?;
+        self.process_controller.resource_usage(pid)
+    }
+
+    fn module_pid(&self, name: &str) -> Result<u32> {
+        self.process_ids.get(name).copied().ok_or_else(|| {
+            CompositionError::ModuleNotFound(format!("No process recorded for module {}", name))
+        })
+    }
+
     /// Stop a module
     pub async fn stop_module(&mut self, name: &str) -> Result<()> {
         let _info = self.registry.get_module(name, None)?;
@@ -84,6 +220,38 @@ impl ModuleLifecycle {
 
         self.status_cache
             .insert(name.to_string(), ModuleStatus::Stopped);
+        self.process_ids.remove(name);
+        Ok(())
+    }
+
+    /// Whether a module is running
+    ///
+    /// True if this lifecycle started it or its IPC socket accepts
+    /// connections, so modules run by another process are seen too.
+    pub fn is_running(&self, name: &str) -> bool {
+        self.status_cache.get(name) == Some(&ModuleStatus::Running) || self.module_responds(name)
+    }
+
+    /// Remove an installed module
+    ///
+    /// A running module (see [`ModuleLifecycle::is_running`]) is never
+    /// removed; stop it first. Unless `force` is set, modules other modules
+    /// depend on are refused as by [`ModuleRegistry::remove_module`].
+    pub fn remove_module(&mut self, name: &str, force: bool) -> Result<()> {
+        if self.is_running(name) {
+            return Err(CompositionError::InvalidConfiguration(format!(
+                "module {} is running; stop it before removing it",
+                name
+            )));
+        }
+
+        if force {
+            self.registry.remove_module_unchecked(name)?;
+        } else {
+            self.registry.remove_module(name)?;
+        }
+        self.status_cache.remove(name);
+        self.process_ids.remove(name);
         Ok(())
     }
 
@@ -126,4 +294,129 @@ impl ModuleLifecycle {
     pub fn registry_mut(&mut self) -> &mut ModuleRegistry {
         &mut self.registry
     }
+
+    /// Serialize the status cache and process metadata to JSON
+    pub fn export_state(&self) -> Result<String> {
+        let state = LifecycleState {
+            modules: self
+                .status_cache
+                .iter()
+                .map(|(name, status)| {
+                    (
+                        name.clone(),
+                        ModuleProcessState {
+                            status: status.clone(),
+                            pid: self.process_ids.get(name).copied(),
+                        },
+                    )
+                })
+                .collect(),
+        };
+
+        serde_json::to_string_pretty(&state).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to serialize state: {}", e))
+        })
+    }
+
+    /// Restore state previously produced by `export_state`
+    ///
+    /// Modules cached as `Running` are checked for liveness before their
+    /// status is confirmed; see [`ModuleLifecycle::sync_state`].
+    pub fn import_state(&mut self, json: &str) -> Result<()> {
+        let state: LifecycleState = serde_json::from_str(json).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to parse state: {}", e))
+        })?;
+
+        self.status_cache.clear();
+        self.process_ids.clear();
+        for (name, module) in state.modules {
+            if let Some(pid) = module.pid {
+                self.process_ids.insert(name.clone(), pid);
+            }
+            self.status_cache.insert(name, module.status);
+        }
+
+        self.sync_state()?;
+        Ok(())
+    }
+
+    /// Check all `Running` modules and mark unresponsive ones as errored
+    ///
+    /// Returns the names of modules whose status changed. Liveness is checked
+    /// by connecting to `<socket_dir>/<name>.sock`; without a socket directory
+    /// no check is possible and cached statuses are left unchanged.
+    pub fn sync_state(&mut self) -> Result<Vec<String>> {
+        let Some(socket_dir) = self.socket_dir.as_ref() else {
+            return Ok(Vec::new());
+        };
+
+        let mut lost = Vec::new();
+        for (name, status) in self.status_cache.iter_mut() {
+            if *status != ModuleStatus::Running {
+                continue;
+            }
+
+            let socket_path = socket_dir.join(format!("{}.sock", name));
+            if !socket_responds(&socket_path) {
+                *status = ModuleStatus::Error("process not found".to_string());
+                lost.push(name.clone());
+            }
+        }
+
+        for name in &lost {
+            self.process_ids.remove(name);
+        }
+        lost.sort();
+        Ok(lost)
+    }
+
+    /// Whether a module's IPC socket accepts connections
+    ///
+    /// Always `false` when no socket directory is configured.
+    pub fn module_responds(&self, name: &str) -> bool {
+        self.socket_dir
+            .as_ref()
+            .is_some_and(|dir| socket_responds(&dir.join(format!("{}.sock", name))))
+    }
+
+    /// Record a module as running without starting it
+    pub(crate) fn mark_running(&mut self, name: &str) {
+        self.status_cache
+            .insert(name.to_string(), ModuleStatus::Running);
+    }
+
+    /// Write lifecycle state to the configured state file
+    pub fn save_state(&self) -> Result<()> {
+        let path = self.state_file()?;
+        std::fs::write(path, self.export_state()?).map_err(CompositionError::IoError)
+    }
+
+    /// Load lifecycle state from the configured state file, if it exists
+    pub fn load_state(&mut self) -> Result<()> {
+        let path = self.state_file()?;
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let json = std::fs::read_to_string(path).map_err(CompositionError::IoError)?;
+        self.import_state(&json)
+    }
+
+    fn state_file(&self) -> Result<&Path> {
+        self.state_file.as_deref().ok_or_else(|| {
+            CompositionError::InvalidConfiguration("No state file configured".to_string())
+        })
+    }
+}
+
+/// Check whether a module IPC socket accepts connections
+#[cfg(unix)]
+fn socket_responds(path: &Path) -> bool {
+    std::os::unix::net::UnixStream::connect(path).is_ok()
+}
+
+/// Check whether a module IPC socket exists
+#[cfg(not(unix))]
+fn socket_responds(path: &Path) -> bool {
+    path.exists()
 }
@@ -0,0 +1,209 @@
+//! Composition Lockfiles
+//!
+//! A lockfile (`node.lock`) pins the exact version, install source, and
+//! binary SHA256 of every module a composition resolved to, so the same node
+//! can be composed on another machine. Composing against a lockfile fails if
+//! any module drifted from it.
+
+use crate::composition::install::{sha256_file, InstallMetadata};
+use crate::composition::types::*;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+/// Default lockfile name, next to the node configuration
+pub const LOCKFILE_NAME: &str = "node.lock";
+
+/// Current lockfile format version
+pub const LOCKFILE_FORMAT_VERSION: u32 = 1;
+
+/// A module pinned by a lockfile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LockedModule {
+    /// Module name
+    pub name: String,
+    /// Exact module version
+    pub version: String,
+    /// Where the module was installed from, if it has an install record
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<ModuleSource>,
+    /// SHA256 of the module binary (hex), if it has one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sha256: Option<String>,
+}
+
+impl LockedModule {
+    /// Pin an installed module as it is on disk now
+    pub fn from_module(module: &ModuleInfo) -> Result<Self> {
+        let source = match &module.directory {
+            Some(dir) => InstallMetadata::load(dir)?.map(|metadata| metadata.source),
+            None => None,
+        };
+        let sha256 = match &module.binary_path {
+            Some(binary) if binary.is_file() => Some(sha256_file(binary)?),
+            _ => None,
+        };
+        Ok(Self {
+            name: module.name.clone(),
+            version: module.version.clone(),
+            source,
+            sha256,
+        })
+    }
+}
+
+/// Contents of a lockfile
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NodeLock {
+    /// Lockfile format version
+    pub format_version: u32,
+    /// Pinned modules, by name
+    #[serde(default, rename = "module")]
+    pub modules: Vec<LockedModule>,
+}
+
+impl NodeLock {
+    /// Pin the given installed modules
+    pub fn from_modules(modules: &[ModuleInfo]) -> Result<Self> {
+        let mut locked = modules
+            .iter()
+            .map(LockedModule::from_module)
+            .collect::<Result<Vec<_>>>()?;
+        locked.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(Self {
+            format_version: LOCKFILE_FORMAT_VERSION,
+            modules: locked,
+        })
+    }
+
+    /// Parse a lockfile from TOML
+    ///
+    /// Lockfiles written by a newer format version are rejected.
+    pub fn from_toml(content: &str) -> Result<Self> {
+        let lock: NodeLock = toml::from_str(content).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to parse lockfile: {}", e))
+        })?;
+        if lock.format_version > LOCKFILE_FORMAT_VERSION {
+            return Err(CompositionError::InvalidConfiguration(format!(
+                "Unsupported lockfile format version {} (supported: {})",
+                lock.format_version, LOCKFILE_FORMAT_VERSION
+            )));
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the lockfile to TOML
+    pub fn to_toml(&self) -> Result<String> {
+        toml::to_string_pretty(self).map_err(|e| {
+            CompositionError::SerializationError(format!("Failed to serialize lockfile: {}", e))
+        })
+    }
+
+    /// Load a lockfile
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let content = std::fs::read_to_string(path.as_ref()).map_err(CompositionError::IoError)?;
+        Self::from_toml(&content)
+    }
+
+    /// Write the lockfile
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<()> {
+        std::fs::write(path.as_ref(), self.to_toml()?).map_err(CompositionError::IoError)
+    }
+
+    /// Look up a pinned module by name
+    pub fn module_by_name(&self, name: &str) -> Option<&LockedModule> {
+        self.modules.iter().find(|m| m.name == name)
+    }
+
+    /// Check that `resolved` pins exactly the modules, versions, and binary
+    /// hashes of this lockfile
+    ///
+    /// Fails with [`CompositionError::LockfileMismatch`] listing every
+    /// difference.
+    pub fn check(&self, resolved: &NodeLock) -> Result<()> {
+        let mut drift = Vec::new();
+        for module in &resolved.modules {
+            let Some(locked) = self.module_by_name(&module.name) else {
+                drift.push(format!(
+                    "{} {} is not in the lockfile",
+                    module.name, module.version
+                ));
+                continue;
+            };
+            if locked.version != module.version {
+                drift.push(format!(
+                    "{} is locked at {} but resolved to {}",
+                    module.name, locked.version, module.version
+                ));
+            } else if locked.sha256 != module.sha256 {
+                drift.push(format!(
+                    "{} {} binary hash is {}, locked {}",
+                    module.name,
+                    module.version,
+                    module.sha256.as_deref().unwrap_or("missing"),
+                    locked.sha256.as_deref().unwrap_or("missing")
+                ));
+            }
+        }
+        for locked in &self.modules {
+            if resolved.module_by_name(&locked.name).is_none() {
+                drift.push(format!(
+                    "{} {} is locked but no longer resolved",
+                    locked.name, locked.version
+                ));
+            }
+        }
+
+        if drift.is_empty() {
+            Ok(())
+        } else {
+            Err(CompositionError::LockfileMismatch(drift.join("; ")))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn locked(name: &str, version: &str, sha256: Option<&str>) -> LockedModule {
+        LockedModule {
+            name: name.to_string(),
+            version: version.to_string(),
+            source: None,
+            sha256: sha256.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_lockfile_roundtrip() {
+        let mut indexer = locked("indexer", "1.2.3", Some("ab12"));
+        indexer.source = Some(ModuleSource::Git {
+            url: "https://example.com/indexer.git".to_string(),
+            reference: GitReference::Tag("v1.2.3".to_string()),
+        });
+        let lock = NodeLock {
+            format_version: LOCKFILE_FORMAT_VERSION,
+            modules: vec![indexer, locked("wallet", "0.4.0", None)],
+        };
+
+        let toml = lock.to_toml().unwrap();
+        assert!(toml.contains("[[module]]"));
+        let parsed = NodeLock::from_toml(&toml).unwrap();
+        assert_eq!(parsed.modules.len(), 2);
+        assert_eq!(
+            parsed.module_by_name("indexer").unwrap().sha256.as_deref(),
+            Some("ab12")
+        );
+        assert!(matches!(
+            parsed.module_by_name("indexer").unwrap().source,
+            Some(ModuleSource::Git { .. })
+        ));
+        parsed.check(&lock).unwrap();
+
+        let newer = toml.replace("format_version = 1", "format_version = 2");
+        assert!(matches!(
+            NodeLock::from_toml(&newer),
+            Err(CompositionError::InvalidConfiguration(_))
+        ));
+    }
+}
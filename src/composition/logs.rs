@@ -0,0 +1,410 @@
+//! Module Logs
+//!
+//! Reading the per-module log files that [`ModuleLifecycle`] directs module
+//! stdout/stderr into (`<log_dir>/<module>.log`).
+//!
+//! Log files hold raw process output. A line that starts with an RFC 3339
+//! timestamp (as written by `tracing`'s default formatter) is stamped with
+//! it; other lines, such as the rest of a multi-line message, inherit the
+//! timestamp of the line before them.
+//!
+//! [`ModuleLifecycle`]: crate::composition::lifecycle::ModuleLifecycle
+
+use crate::composition::types::*;
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use notify::{RecursiveMode, Watcher};
+use serde::Serialize;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::time::Duration;
+
+/// Extension of module log files
+pub const LOG_FILE_EXTENSION: &str = "log";
+
+/// Default log directory: `~/.bllvm/logs`
+pub fn default_log_dir() -> Option<PathBuf> {
+    std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".bllvm").join("logs"))
+}
+
+/// Log file of a module
+pub fn module_log_path(log_dir: &Path, module: &str) -> PathBuf {
+    log_dir.join(format!("{}.{}", module, LOG_FILE_EXTENSION))
+}
+
+/// Names of the modules with a log file in `log_dir`, sorted
+pub fn logged_modules(log_dir: &Path) -> Result<Vec<String>> {
+    let mut modules = Vec::new();
+    for entry in std::fs::read_dir(log_dir)? {
+        let path = entry?.path();
+        if path.is_file()
+            && path.extension().and_then(|ext| ext.to_str()) == Some(LOG_FILE_EXTENSION)
+        {
+            if let Some(stem) = path.file_stem().and_then(|stem| stem.to_str()) {
+                modules.push(stem.to_string());
+            }
+        }
+    }
+    modules.sort();
+    Ok(modules)
+}
+
+/// Parse a `--since` value: an RFC 3339 timestamp or a relative age such as
+/// `30s`, `15m`, `2h` or `1d`
+pub fn parse_since(value: &str) -> Result<DateTime<Utc>> {
+    if let Ok(timestamp) = DateTime::parse_from_rfc3339(value) {
+        return Ok(timestamp.with_timezone(&Utc));
+    }
+
+    let invalid = || {
+        CompositionError::InvalidConfiguration(format!(
+            "Invalid time '{}': expected an RFC 3339 timestamp or an age like 15m",
+            value
+        ))
+    };
+    let split = value.len().saturating_sub(1);
+    let (amount, unit) = (value.get(..split).ok_or_else(invalid)?, &value[split..]);
+    let amount: i64 = amount.parse().map_err(|_| invalid())?;
+    let age = match unit {
+        "s" => ChronoDuration::try_seconds(amount),
+        "m" => ChronoDuration::try_minutes(amount),
+        "h" => ChronoDuration::try_hours(amount),
+        "d" => ChronoDuration::try_days(amount),
+        _ => None,
+    }
+    .ok_or_else(invalid)?;
+    Utc::now().checked_sub_signed(age).ok_or_else(invalid)
+}
+
+/// One line of module output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LogLine {
+    /// Module that wrote the line
+    pub module: String,
+    /// When the line (or the entry it continues) was written, if known
+    pub timestamp: Option<DateTime<Utc>>,
+    /// Line text without the trailing newline
+    pub line: String,
+}
+
+impl LogLine {
+    /// Parse a raw line, taking its timestamp from a leading RFC 3339
+    /// timestamp or else from `previous`
+    pub fn parse(module: &str, line: &str, previous: Option<DateTime<Utc>>) -> Self {
+        let timestamp = line
+            .split_whitespace()
+            .next()
+            .and_then(|token| DateTime::parse_from_rfc3339(token).ok())
+            .map(|timestamp| timestamp.with_timezone(&Utc))
+            .or(previous);
+        Self {
+            module: module.to_string(),
+            timestamp,
+            line: line.trim_end_matches(['\n', '\r']).to_string(),
+        }
+    }
+
+    /// Line prefixed with its module name, for interleaved output
+    pub fn to_prefixed(&self) -> String {
+        format!("[{}] {}", self.module, self.line)
+    }
+}
+
+/// Read the last `lines` lines logged by `modules`, interleaved by timestamp
+///
+/// With `since`, only lines stamped at or after it are kept; lines without
+/// any timestamp are then skipped.
+pub fn read_logs(
+    log_dir: &Path,
+    modules: &[String],
+    lines: usize,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<LogLine>> {
+    let mut merged = Vec::new();
+    for module in modules {
+        merged.extend(read_module_log(log_dir, module, lines, since)?);
+    }
+    // Stable, so each module's lines keep their order
+    merged.sort_by_key(|line| line.timestamp);
+    let skip = merged.len().saturating_sub(lines);
+    Ok(merged.split_off(skip))
+}
+
+fn read_module_log(
+    log_dir: &Path,
+    module: &str,
+    lines: usize,
+    since: Option<DateTime<Utc>>,
+) -> Result<Vec<LogLine>> {
+    let path = module_log_path(log_dir, module);
+    let file = File::open(&path).map_err(|e| {
+        CompositionError::ModuleNotFound(format!(
+            "No log for module {} at {}: {}",
+            module,
+            path.display(),
+            e
+        ))
+    })?;
+
+    let mut tail = VecDeque::with_capacity(lines.min(1024));
+    let mut previous = None;
+    for raw in BufReader::new(file).lines() {
+        let line = LogLine::parse(module, &raw?, previous);
+        previous = line.timestamp;
+        if since.is_some_and(|since| line.timestamp.is_none_or(|t| t < since)) {
+            continue;
+        }
+        if tail.len() == lines {
+            tail.pop_front();
+        }
+        if lines > 0 {
+            tail.push_back(line);
+        }
+    }
+    Ok(tail.into())
+}
+
+/// Position in one followed log file
+struct FollowedLog {
+    module: String,
+    path: PathBuf,
+    offset: u64,
+    partial: String,
+    previous: Option<DateTime<Utc>>,
+}
+
+impl FollowedLog {
+    /// Read complete lines appended since the last read
+    fn read_new(&mut self) -> Result<Vec<LogLine>> {
+        let mut file = match File::open(&self.path) {
+            Ok(file) => file,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e.into()),
+        };
+        let len = file.metadata()?.len();
+        if len < self.offset {
+            // Truncated or rotated: start over
+            self.offset = 0;
+            self.partial.clear();
+        }
+        file.seek(SeekFrom::Start(self.offset))?;
+        let mut appended = Vec::new();
+        file.read_to_end(&mut appended)?;
+        self.offset += appended.len() as u64;
+        self.partial.push_str(&String::from_utf8_lossy(&appended));
+
+        let Some(end) = self.partial.rfind('\n') else {
+            return Ok(Vec::new());
+        };
+        let complete: String = self.partial.drain(..=end).collect();
+        let mut lines = Vec::new();
+        for raw in complete.lines() {
+            let line = LogLine::parse(&self.module, raw, self.previous);
+            self.previous = line.timestamp;
+            lines.push(line);
+        }
+        Ok(lines)
+    }
+}
+
+/// Streams lines appended to module log files
+///
+/// Changes are picked up with inotify on Linux and kqueue on macOS and the
+/// BSDs; the files are also re-read whenever [`poll`](Self::poll) times out,
+/// so a missed notification only delays output.
+pub struct LogFollower {
+    logs: Vec<FollowedLog>,
+    events: mpsc::Receiver<notify::Result<notify::Event>>,
+    _watcher: notify::RecommendedWatcher,
+}
+
+impl LogFollower {
+    /// Follow `modules` from the current end of their log files
+    ///
+    /// Logs that do not exist yet are followed from their creation.
+    pub fn new(log_dir: &Path, modules: &[String]) -> Result<Self> {
+        std::fs::create_dir_all(log_dir)?;
+
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx).map_err(watch_error)?;
+        watcher
+            .watch(log_dir, RecursiveMode::NonRecursive)
+            .map_err(watch_error)?;
+
+        let logs = modules
+            .iter()
+            .map(|module| {
+                let path = module_log_path(log_dir, module);
+                let offset = std::fs::metadata(&path).map(|m| m.len()).unwrap_or(0);
+                FollowedLog {
+                    module: module.clone(),
+                    path,
+                    offset,
+                    partial: String::new(),
+                    previous: None,
+                }
+            })
+            .collect();
+
+        Ok(Self {
+            logs,
+            events,
+            _watcher: watcher,
+        })
+    }
+
+    /// Wait up to `timeout` for a change, then return the new lines
+    ///
+    /// Returns an empty list if nothing was appended.
+    pub fn poll(&mut self, timeout: Duration) -> Result<Vec<LogLine>> {
+        match self.events.recv_timeout(timeout) {
+            Ok(Err(e)) => return Err(watch_error(e)),
+            Ok(Ok(_)) | Err(mpsc::RecvTimeoutError::Timeout) => {}
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                return Err(CompositionError::InvalidConfiguration(
+                    "Log watcher stopped".to_string(),
+                ))
+            }
+        }
+        // Coalesce a burst of writes into one read
+        while let Ok(event) = self.events.try_recv() {
+            event.map_err(watch_error)?;
+        }
+
+        let mut lines = Vec::new();
+        for log in &mut self.logs {
+            lines.extend(log.read_new()?);
+        }
+        lines.sort_by_key(|line| line.timestamp);
+        Ok(lines)
+    }
+}
+
+fn watch_error(e: notify::Error) -> CompositionError {
+    CompositionError::IoError(std::io::Error::other(format!(
+        "Failed to watch logs: {}",
+        e
+    )))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    fn write_log(dir: &Path, module: &str, contents: &str) {
+        std::fs::write(module_log_path(dir, module), contents).unwrap();
+    }
+
+    #[test]
+    fn test_parse_line_timestamps() {
+        let line = LogLine::parse(
+            "lightning",
+            "2024-05-01T12:00:00.123456Z  INFO started",
+            None,
+        );
+        assert_eq!(
+            line.timestamp,
+            Some("2024-05-01T12:00:00.123456Z".parse().unwrap())
+        );
+        assert_eq!(
+            line.to_prefixed(),
+            "[lightning] 2024-05-01T12:00:00.123456Z  INFO started"
+        );
+
+        // Continuation lines inherit the previous timestamp
+        let continuation = LogLine::parse("lightning", "  at main.rs:10", line.timestamp);
+        assert_eq!(continuation.timestamp, line.timestamp);
+        assert_eq!(LogLine::parse("lightning", "plain", None).timestamp, None);
+    }
+
+    #[test]
+    fn test_read_logs_tails_and_interleaves() {
+        let dir = tempfile::tempdir().unwrap();
+        write_log(
+            dir.path(),
+            "a",
+            "2024-05-01T12:00:00Z a1\n2024-05-01T12:00:02Z a2\n  a2 detail\n",
+        );
+        write_log(
+            dir.path(),
+            "b",
+            "2024-05-01T12:00:01Z b1\n2024-05-01T12:00:03Z b2\n",
+        );
+        let modules = vec!["a".to_string(), "b".to_string()];
+
+        let lines = read_logs(dir.path(), &modules, 50, None).unwrap();
+        let text: Vec<_> = lines.iter().map(LogLine::to_prefixed).collect();
+        assert_eq!(
+            text,
+            [
+                "[a] 2024-05-01T12:00:00Z a1",
+                "[b] 2024-05-01T12:00:01Z b1",
+                "[a] 2024-05-01T12:00:02Z a2",
+                "[a]   a2 detail",
+                "[b] 2024-05-01T12:00:03Z b2",
+            ]
+        );
+
+        let last = read_logs(dir.path(), &modules, 2, None).unwrap();
+        assert_eq!(last[0].line, "  a2 detail");
+        assert_eq!(last[1].line, "2024-05-01T12:00:03Z b2");
+
+        let since = parse_since("2024-05-01T12:00:02Z").unwrap();
+        let recent = read_logs(dir.path(), &modules, 50, Some(since)).unwrap();
+        assert_eq!(recent.len(), 3);
+
+        assert_eq!(logged_modules(dir.path()).unwrap(), modules);
+        assert!(read_logs(dir.path(), &["missing".to_string()], 50, None).is_err());
+    }
+
+    #[test]
+    fn test_parse_since_relative() {
+        let since = parse_since("15m").unwrap();
+        let age = Utc::now() - since;
+        assert_eq!(age.num_minutes(), 15);
+        assert!(parse_since("15").is_err());
+        assert!(parse_since("m").is_err());
+        assert!(parse_since("yesterday").is_err());
+    }
+
+    #[test]
+    fn test_follower_streams_appended_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        write_log(dir.path(), "a", "old line\n");
+        let mut follower =
+            LogFollower::new(dir.path(), &["a".to_string(), "b".to_string()]).unwrap();
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(module_log_path(dir.path(), "a"))
+            .unwrap();
+        write!(file, "new line\npartial").unwrap();
+        // "b" is created after following started
+        write_log(dir.path(), "b", "b line\n");
+
+        let mut lines = Vec::new();
+        for _ in 0..20 {
+            lines.extend(follower.poll(Duration::from_millis(100)).unwrap());
+            if lines.len() >= 2 {
+                break;
+            }
+        }
+        let mut text: Vec<_> = lines.iter().map(LogLine::to_prefixed).collect();
+        text.sort();
+        assert_eq!(text, ["[a] new line", "[b] b line"]);
+
+        // The partial line is emitted once completed
+        writeln!(file, " done").unwrap();
+        let mut lines = Vec::new();
+        for _ in 0..20 {
+            lines.extend(follower.poll(Duration::from_millis(100)).unwrap());
+            if !lines.is_empty() {
+                break;
+            }
+        }
+        assert_eq!(lines[0].line, "partial done");
+    }
+}
@@ -5,22 +5,47 @@
 //!
 //! This module enables:
 //! - Module discovery and registry management
+//! - Installing modules from git tags, branches, or commits
+//! - Installing and searching modules from remote registries
+//! - Checking module binaries against the hash recorded at install time
 //! - Declarative node composition from TOML configuration
 //! - Module lifecycle management (start/stop/restart)
-//! - Dependency resolution and validation
+//! - Node health reports
+//! - Per-module log files and log following
+//! - Per-module resource limits
+//! - Checkpointing composed node state to disk
+//! - Lockfiles pinning module versions and binary hashes
+//! - Dependency resolution and validation, with semver version requirements
 
+pub mod checkpoint;
 pub mod composer;
 pub mod config;
 pub mod conversion;
+pub mod descriptor;
+pub mod git;
+pub mod install;
 pub mod lifecycle;
+pub mod lock;
+pub mod logs;
 pub mod registry;
+pub mod remote;
+pub mod report;
+pub mod resources;
 pub mod schema;
 pub mod types;
 pub mod validation;
+pub mod version;
 
 // Re-export main types for convenience
-pub use composer::NodeComposer;
+pub use checkpoint::{ModuleCheckpoint, NodeCheckpoint, CHECKPOINT_FORMAT_VERSION};
+pub use composer::{CompositionHooks, NodeComposer};
 pub use config::NodeConfig;
+pub use install::InstallMetadata;
 pub use lifecycle::ModuleLifecycle;
+pub use lock::{LockedModule, NodeLock, LOCKFILE_FORMAT_VERSION, LOCKFILE_NAME};
+pub use logs::{LogFollower, LogLine};
 pub use registry::ModuleRegistry;
+pub use remote::{CurlRegistryClient, Fetched, IndexEntry, RegistryClient};
+pub use report::{ModuleReport, NodeReport};
+pub use resources::{ProcessController, ResourceLimits, ResourceUsage, SystemProcessController};
 pub use types::*;
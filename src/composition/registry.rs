@@ -2,67 +2,191 @@
 //!
 //! High-level module registry API for discovering, installing, updating,
 //! and removing modules. Wraps bllvm-node module registry functionality.
+//!
+//! A registry can scan several directories (e.g. system, user, and
+//! project-local modules). Search directories are listed in priority order.
 
 use crate::composition::conversion::*;
+use crate::composition::git;
+use crate::composition::install::{check_entry_point, sha256_file, InstallMetadata};
+use crate::composition::remote::{self, CurlRegistryClient, IndexEntry, RegistryClient};
 use crate::composition::types::*;
+use crate::composition::version::{self, compare_versions};
+use crate::module::manifest::ModuleManifest;
 use blvm_node::module::registry::{
-    DiscoveredModule as RefDiscoveredModule, ModuleDependencies as RefModuleDependencies,
-    ModuleDiscovery as RefModuleDiscovery, ModuleManifest as RefModuleManifest,
+    DiscoveredModule as RefDiscoveredModule, ModuleDiscovery as RefModuleDiscovery,
+    ModuleManifest as RefModuleManifest,
 };
 use blvm_node::module::traits::ModuleError as RefModuleError;
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
+/// Environment variable with extra module directories (`PATH`-style list)
+pub const MODULE_PATH_ENV_VAR: &str = "BLLVM_MODULE_PATH";
+
 /// Module registry for managing module lifecycle
 pub struct ModuleRegistry {
-    /// Base directory for modules
-    modules_dir: PathBuf,
+    /// Module search directories, highest priority first
+    search_dirs: Vec<PathBuf>,
+    /// How to resolve modules found in more than one directory
+    duplicate_policy: DuplicatePolicy,
     /// Discovered modules cache
     discovered: Vec<ModuleInfo>,
+    /// Problems noticed by the last discovery
+    discovery_warnings: Vec<String>,
+    /// Transport to remote registries
+    client: Box<dyn RegistryClient>,
+    /// Where remote registry indexes are cached, if anywhere
+    registry_cache: Option<PathBuf>,
 }
 
 impl ModuleRegistry {
     /// Create a new module registry
     pub fn new<P: AsRef<Path>>(modules_dir: P) -> Self {
-        Self {
-            modules_dir: modules_dir.as_ref().to_path_buf(),
+        Self::new_multi(&[modules_dir])
+    }
+
+    /// Create a registry that scans several directories, highest priority first
+    pub fn new_multi<P: AsRef<Path>>(dirs: &[P]) -> Self {
+        let mut registry = Self {
+            search_dirs: Vec::new(),
+            duplicate_policy: DuplicatePolicy::default(),
             discovered: Vec::new(),
+            discovery_warnings: Vec::new(),
+            client: Box::new(CurlRegistryClient::new()),
+            registry_cache: Some(std::env::temp_dir().join("bllvm-registry-cache")),
+        };
+        for dir in dirs {
+            registry.add_search_dir(dir.as_ref());
         }
+        registry
     }
 
-    /// Discover available modules in the modules directory
-    pub fn discover_modules(&mut self) -> Result<Vec<ModuleInfo>> {
-        let discovery = RefModuleDiscovery::new(&self.modules_dir);
-        let discovered = discovery
-            .discover_modules()
-            .map_err(|e: RefModuleError| CompositionError::from(e))?;
+    /// Set how modules found in more than one directory are resolved
+    pub fn with_duplicate_policy(mut self, policy: DuplicatePolicy) -> Self {
+        self.duplicate_policy = policy;
+        self
+    }
+
+    /// Use a different transport for remote registries
+    pub fn with_registry_client(mut self, client: impl RegistryClient + 'static) -> Self {
+        self.client = Box::new(client);
+        self
+    }
 
-        self.discovered = discovered.iter().map(|d| ModuleInfo::from(d)).collect();
+    /// Cache remote registry indexes in `dir`, or not at all if `None`
+    pub fn with_registry_cache(mut self, dir: Option<PathBuf>) -> Self {
+        self.registry_cache = dir;
+        self
+    }
 
-        Ok(self.discovered.clone())
+    /// Add a search directory with the lowest priority
+    ///
+    /// Directories already searched are ignored.
+    pub fn add_search_dir(&mut self, dir: &Path) {
+        if !self.search_dirs.iter().any(|existing| existing == dir) {
+            self.search_dirs.push(dir.to_path_buf());
+        }
     }
 
-    /// Get module by name and optional version
-    pub fn get_module(&self, name: &str, version: Option<&str>) -> Result<ModuleInfo> {
-        let module = self
+    /// Module search directories, highest priority first
+    pub fn search_dirs(&self) -> &[PathBuf] {
+        &self.search_dirs
+    }
+
+    /// Discover available modules in all search directories
+    ///
+    /// Modules whose version is not a semantic version are still discovered,
+    /// with a warning (see [`ModuleRegistry::discovery_warnings`]): version
+    /// requirements only match them exactly.
+    pub fn discover_modules(&mut self) -> Result<Vec<ModuleInfo>> {
+        let discovered = self.discover_all()?;
+
+        self.discovered = discovered.iter().map(module_info).collect();
+        self.discovery_warnings = self
             .discovered
             .iter()
-            .find(|m| m.name == name && version.map_or(true, |v| m.version == v))
-            .ok_or_else(|| {
-                let msg = if let Some(v) = version {
-                    format!("Module {} version {} not found", name, v)
-                } else {
-                    format!("Module {} not found", name)
+            .filter(|m| version::parse_version(&m.version).is_none())
+            .map(|m| {
+                format!(
+                    "Module '{}' version '{}' is not a semantic version; only an exact version requirement matches it",
+                    m.name, m.version
+                )
+            })
+            .collect();
+
+        Ok(self.discovered.clone())
+    }
+
+    /// Warnings from the last [`ModuleRegistry::discover_modules`]
+    pub fn discovery_warnings(&self) -> &[String] {
+        &self.discovery_warnings
+    }
+
+    /// Scan every search directory, resolving duplicates by the registry's policy
+    ///
+    /// Every version installed in one directory is kept; the policy decides
+    /// between directories. Directories that do not exist are skipped.
+    fn discover_all(&self) -> Result<Vec<RefDiscoveredModule>> {
+        let mut modules: Vec<RefDiscoveredModule> = Vec::new();
+        for dir in self.search_dirs.iter().filter(|dir| dir.exists()) {
+            let earlier = modules.len();
+            for module in discover_dir(dir)? {
+                let existing = modules[..earlier]
+                    .iter_mut()
+                    .find(|m| m.manifest.name == module.manifest.name);
+                let Some(existing) = existing else {
+                    modules.push(module);
+                    continue;
                 };
-                CompositionError::ModuleNotFound(msg)
-            })?;
+                match self.duplicate_policy {
+                    DuplicatePolicy::FirstWins => {}
+                    DuplicatePolicy::LatestVersion => {
+                        if compare_versions(&module.manifest.version, &existing.manifest.version)
+                            == Ordering::Greater
+                        {
+                            *existing = module;
+                        }
+                    }
+                    DuplicatePolicy::Error => {
+                        return Err(CompositionError::InvalidConfiguration(format!(
+                            "Module {} found in both {:?} and {:?}",
+                            module.manifest.name, existing.directory, module.directory
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(modules)
+    }
+
+    /// Get module by name and optional version requirement
+    ///
+    /// Picks the highest installed version satisfying `version` (see
+    /// [`version::select`]), or the highest installed version if `None`.
+    pub fn get_module(&self, name: &str, version: Option<&str>) -> Result<ModuleInfo> {
+        let installed: Vec<&ModuleInfo> =
+            self.discovered.iter().filter(|m| m.name == name).collect();
+        let module = match version {
+            Some(requirement) if !installed.is_empty() => version::select(&installed, requirement)?,
+            _ => installed
+                .into_iter()
+                .max_by(|a, b| compare_versions(&a.version, &b.version))
+                .ok_or_else(|| {
+                    CompositionError::ModuleNotFound(format!("Module {} not found", name))
+                })?,
+        };
 
         Ok(module.clone())
     }
 
     /// Install module from source
+    ///
+    /// Records the source and the binary's SHA256 in the module's
+    /// `install.json` (see [`ModuleRegistry::verify_integrity`]).
     pub fn install_module(&mut self, source: ModuleSource) -> Result<ModuleInfo> {
-        match source {
+        match &source {
             ModuleSource::Path(path) => {
                 // Validate path exists
                 if !path.exists() {
@@ -74,7 +198,7 @@ impl ModuleRegistry {
 
                 // For now, we'll just discover from the path
                 // In a full implementation, this would copy/install the module
-                let discovery = RefModuleDiscovery::new(&path);
+                let discovery = RefModuleDiscovery::new(path);
                 let discovered = discovery
                     .discover_modules()
                     .map_err(|e| CompositionError::from(e))?;
@@ -85,24 +209,260 @@ impl ModuleRegistry {
                     ));
                 }
 
+                Self::verify_module(&discovered[0])?;
+                InstallMetadata::new(
+                    source.clone(),
+                    &discovered[0].manifest.version,
+                    &discovered[0].binary_path,
+                )?
+                .save(&discovered[0].directory)?;
+
                 // Refresh discovered modules
                 self.discover_modules()?;
 
-                Ok(ModuleInfo::from(&discovered[0]))
+                Ok(module_info(&discovered[0]))
             }
-            ModuleSource::Registry(_url) => {
-                // TODO: Implement registry download
-                Err(CompositionError::InstallationFailed(
-                    "Registry installation not yet implemented".to_string(),
-                ))
+            ModuleSource::Registry { url, name, version } => {
+                self.install_from_registry(&source, url, name, version.as_deref())
+            }
+            ModuleSource::Git { url, reference } => self.install_from_git(&source, url, reference),
+        }
+    }
+
+    /// Install a module from a git repository into `<modules_dir>/<name>/<version>/`
+    ///
+    /// The repository is checked out into a temporary directory, which is
+    /// removed afterwards. The module binary is copied if committed, otherwise
+    /// built with cargo.
+    fn install_from_git(
+        &mut self,
+        source: &ModuleSource,
+        url: &str,
+        reference: &GitReference,
+    ) -> Result<ModuleInfo> {
+        let checkout = git::Checkout::new(url, reference)?;
+        let manifest_path = checkout.find_manifest()?;
+        let manifest = ModuleManifest::from_file(&manifest_path)?;
+        let manifest_dir = manifest_path.parent().unwrap_or(checkout.path());
+        let binary = checkout.module_binary(manifest_dir, &manifest.entry_point)?;
+        self.install_files(source, &manifest_path, &manifest, &binary)
+    }
+
+    /// Install a module release listed by a remote registry
+    ///
+    /// Without a version, the highest listed version is installed. The
+    /// archive must match the index's SHA256 and contain the listed module.
+    fn install_from_registry(
+        &mut self,
+        source: &ModuleSource,
+        url: &str,
+        name: &str,
+        version: Option<&str>,
+    ) -> Result<ModuleInfo> {
+        let index_url = remote::index_url(url);
+        let entries = remote::fetch_index(
+            self.client.as_ref(),
+            &index_url,
+            self.registry_cache.as_deref(),
+        )?;
+        let releases = entries.iter().filter(|e| e.name == name);
+        let entry = match version {
+            Some(version) => releases
+                .into_iter()
+                .find(|e| e.version == version)
+                .ok_or_else(|| {
+                    CompositionError::ModuleVersionNotFound(name.to_string(), version.to_string())
+                })?,
+            None => releases
+                .max_by(|a, b| compare_versions(&a.version, &b.version))
+                .ok_or_else(|| {
+                    CompositionError::ModuleNotFound(format!("{} is not in {}", name, index_url))
+                })?,
+        };
+
+        let archive = remote::UnpackedArchive::download(self.client.as_ref(), &index_url, entry)?;
+        let manifest_path = ModuleManifest::locate(&archive.path())?;
+        let manifest = ModuleManifest::from_file(&manifest_path)?;
+        if manifest.name != entry.name || manifest.version != entry.version {
+            return Err(CompositionError::InstallationFailed(format!(
+                "Archive for {} {} contains {} {}",
+                entry.name, entry.version, manifest.name, manifest.version
+            )));
+        }
+        check_entry_point(&manifest.entry_point)?;
+        let binary = manifest_path
+            .parent()
+            .unwrap_or(&archive.path())
+            .join(&manifest.entry_point);
+        if !binary.is_file() {
+            return Err(CompositionError::InstallationFailed(format!(
+                "Archive for {} {} does not contain {}",
+                entry.name, entry.version, manifest.entry_point
+            )));
+        }
+        self.install_files(source, &manifest_path, &manifest, &binary)
+    }
+
+    /// Search a remote registry's index
+    ///
+    /// Returns releases whose name or description contains `query`, ignoring
+    /// case, sorted by name and version.
+    pub fn search_remote(&self, url: &str, query: &str) -> Result<Vec<IndexEntry>> {
+        let mut entries = remote::fetch_index(
+            self.client.as_ref(),
+            &remote::index_url(url),
+            self.registry_cache.as_deref(),
+        )?;
+        entries.retain(|entry| entry.matches(query));
+        entries.sort_by(|a, b| {
+            a.name
+                .cmp(&b.name)
+                .then_with(|| compare_versions(&a.version, &b.version))
+        });
+        Ok(entries)
+    }
+
+    /// Install a manifest and binary into `<modules_dir>/<name>/<version>/`
+    ///
+    /// Nothing is left in the modules directory on failure.
+    fn install_files(
+        &mut self,
+        source: &ModuleSource,
+        manifest_path: &Path,
+        manifest: &ModuleManifest,
+        binary: &Path,
+    ) -> Result<ModuleInfo> {
+        let modules_dir = self.search_dirs.first().cloned().ok_or_else(|| {
+            CompositionError::InstallationFailed(
+                "Registry has no module directory to install into".to_string(),
+            )
+        })?;
+        for (field, value) in [("name", &manifest.name), ("version", &manifest.version)] {
+            if value.contains(['/', '\\']) || value.starts_with('.') {
+                return Err(CompositionError::InstallationFailed(format!(
+                    "Module {} '{}' is not a valid directory name",
+                    field, value
+                )));
             }
-            ModuleSource::Git { url: _, tag: _ } => {
-                // TODO: Implement git clone
-                Err(CompositionError::InstallationFailed(
-                    "Git installation not yet implemented".to_string(),
+        }
+        check_entry_point(&manifest.entry_point)?;
+
+        let module_root = modules_dir.join(&manifest.name);
+        let dest = module_root.join(&manifest.version);
+        if dest.exists() {
+            return Err(CompositionError::InstallationFailed(format!(
+                "Module {} {} is already installed at {:?}",
+                manifest.name, manifest.version, dest
+            )));
+        }
+
+        let installed = Self::copy_module(manifest_path, binary, &manifest.entry_point, &dest)
+            .and_then(|()| {
+                InstallMetadata::new(
+                    source.clone(),
+                    &manifest.version,
+                    &dest.join(&manifest.entry_point),
+                )?
+                .save(&dest)?;
+                let discovered = RefModuleDiscovery::new(&module_root)
+                    .discover_modules()
+                    .map_err(CompositionError::from)?
+                    .into_iter()
+                    .find(|m| {
+                        m.manifest.name == manifest.name && m.manifest.version == manifest.version
+                    })
+                    .ok_or_else(|| {
+                        CompositionError::InstallationFailed(format!(
+                            "Installed module not discovered at {:?}",
+                            dest
+                        ))
+                    })?;
+                Self::verify_module(&discovered)?;
+                Ok(module_info(&discovered))
+            });
+        if installed.is_err() {
+            let _ = std::fs::remove_dir_all(&dest);
+            // Only removes the name directory if no other version is installed
+            let _ = std::fs::remove_dir(&module_root);
+        }
+        let module = installed?;
+
+        self.discover_modules()?;
+        Ok(module)
+    }
+
+    /// Copy a manifest and its binary into a new module directory
+    fn copy_module(manifest: &Path, binary: &Path, entry_point: &str, dest: &Path) -> Result<()> {
+        std::fs::create_dir_all(dest)?;
+        std::fs::copy(manifest, dest.join("module.toml"))?;
+        let installed_binary = dest.join(entry_point);
+        if let Some(parent) = installed_binary.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::copy(binary, installed_binary)?;
+        Ok(())
+    }
+
+    /// Verify a module's binary and manifest signature before installing
+    ///
+    /// Signed manifests must verify against the module's declared
+    /// `signing_public_key`.
+    fn verify_module(module: &RefDiscoveredModule) -> Result<()> {
+        let manifest = ModuleManifest::from_file(module.directory.join("module.toml"))?;
+        let failed = |e: crate::governance::GovernanceError| {
+            CompositionError::ValidationFailed(format!("Module '{}': {}", manifest.name, e))
+        };
+
+        manifest
+            .validate_binary(&module.binary_path)
+            .map_err(failed)?;
+
+        if manifest.manifest_signature.is_some() {
+            let public_key = manifest.identity_key().map_err(failed)?.ok_or_else(|| {
+                CompositionError::ValidationFailed(format!(
+                    "Module '{}' is signed but declares no signing_public_key",
+                    manifest.name
                 ))
+            })?;
+            if !manifest.verify_signature(&public_key).map_err(failed)? {
+                return Err(CompositionError::ValidationFailed(format!(
+                    "Module '{}' manifest signature is invalid",
+                    manifest.name
+                )));
             }
         }
+
+        Ok(())
+    }
+
+    /// Re-hash a module's binary and compare it with its install record
+    ///
+    /// Modules without an `install.json` (placed in a module directory by
+    /// hand) have no recorded hash and pass.
+    pub fn verify_integrity(&self, name: &str) -> Result<()> {
+        let module = self.get_module(name, None)?;
+        let Some(directory) = &module.directory else {
+            return Ok(());
+        };
+        let Some(record) = InstallMetadata::load(directory)? else {
+            return Ok(());
+        };
+        let failed =
+            |reason: String| CompositionError::IntegrityCheckFailed(name.to_string(), reason);
+
+        let binary = module
+            .binary_path
+            .as_ref()
+            .ok_or_else(|| failed("module has no binary path".to_string()))?;
+        let actual =
+            sha256_file(binary).map_err(|e| failed(format!("cannot read {:?}: {}", binary, e)))?;
+        if !actual.eq_ignore_ascii_case(&record.sha256) {
+            return Err(failed(format!(
+                "{:?} has SHA256 {}, but {} was installed",
+                binary, actual, record.sha256
+            )));
+        }
+        Ok(())
     }
 
     /// Update module to new version
@@ -123,12 +483,34 @@ impl ModuleRegistry {
     }
 
     /// Remove module
+    ///
+    /// Refuses with [`CompositionError::DependencyResolutionFailed`] while any
+    /// other discovered module depends on it, directly or transitively. Nothing
+    /// is deleted in that case.
     pub fn remove_module(&mut self, name: &str) -> Result<()> {
+        self.get_module(name, None)?;
+
+        let dependents = self.transitive_dependents(name);
+        if !dependents.is_empty() {
+            return Err(CompositionError::DependencyResolutionFailed(format!(
+                "module {} is required by {}",
+                name,
+                dependents.join(", ")
+            )));
+        }
+
+        self.remove_module_unchecked(name)
+    }
+
+    /// Remove module without checking whether other modules depend on it
+    ///
+    /// The registry does not know which modules are running; use
+    /// [`ModuleLifecycle::remove_module`](crate::composition::lifecycle::ModuleLifecycle::remove_module)
+    /// to also refuse removing a running module.
+    pub fn remove_module_unchecked(&mut self, name: &str) -> Result<()> {
         let module = self.get_module(name, None)?;
 
         if let Some(dir) = &module.directory {
-            // TODO: Check if module is running and stop it first
-            // For now, this is a placeholder
             std::fs::remove_dir_all(dir).map_err(CompositionError::IoError)?;
         }
 
@@ -138,37 +520,184 @@ impl ModuleRegistry {
         Ok(())
     }
 
+    /// Names of discovered modules that directly depend on `module_name`
+    pub fn dependents_of(&self, module_name: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = self
+            .discovered
+            .iter()
+            .filter(|m| m.name != module_name && m.dependencies.contains_key(module_name))
+            .map(|m| m.name.clone())
+            .collect();
+        dependents.sort();
+        dependents.dedup();
+        dependents
+    }
+
+    /// Names of discovered modules that depend on `module_name` through any chain
+    fn transitive_dependents(&self, module_name: &str) -> Vec<String> {
+        let mut dependents: Vec<String> = Vec::new();
+        let mut pending = vec![module_name.to_string()];
+        while let Some(name) = pending.pop() {
+            for dependent in self.dependents_of(&name) {
+                if dependent != module_name && !dependents.contains(&dependent) {
+                    dependents.push(dependent.clone());
+                    pending.push(dependent);
+                }
+            }
+        }
+        dependents.sort();
+        dependents
+    }
+
     /// List all installed modules
     pub fn list_modules(&self) -> Vec<ModuleInfo> {
         self.discovered.clone()
     }
 
+    /// Discovered modules that list `capability` in `provides`, by name
+    pub fn what_provides(&self, capability: &str) -> Vec<&ModuleInfo> {
+        let mut providers: Vec<&ModuleInfo> = self
+            .discovered
+            .iter()
+            .filter(|m| m.provides.iter().any(|p| p == capability))
+            .collect();
+        providers.sort_by(|a, b| a.name.cmp(&b.name));
+        providers
+    }
+
     /// Resolve dependencies for a set of modules
+    ///
+    /// Returns the modules and everything they need in load order,
+    /// dependencies first. A dependency is satisfied by the module of that
+    /// name or, failing that, by a module that provides it. Optional
+    /// dependencies are loaded first when installed and skipped otherwise.
     pub fn resolve_dependencies(&self, module_names: &[String]) -> Result<Vec<ModuleInfo>> {
-        // First, we need to get the actual RefDiscoveredModule objects
-        // We'll need to re-discover or cache them. For now, let's re-discover.
-        let discovery = RefModuleDiscovery::new(&self.modules_dir);
-        let all_discovered = discovery
-            .discover_modules()
-            .map_err(|e| CompositionError::from(e))?;
+        let mut resolved = Vec::new();
+        let mut visiting = Vec::new();
+        for name in module_names {
+            self.resolve_into(name, None, &mut visiting, &mut resolved)?;
+        }
+        Ok(resolved)
+    }
 
-        // Filter to only requested modules and convert to owned values
-        let requested: Vec<_> = all_discovered
-            .iter()
-            .filter(|d| module_names.contains(&d.manifest.name))
-            .cloned()
-            .collect();
+    /// Depth-first step of [`ModuleRegistry::resolve_dependencies`]
+    ///
+    /// `requirement` is the version requirement on `name`, if it was named
+    /// directly by a dependent; capabilities are versioned by their name.
+    fn resolve_into(
+        &self,
+        name: &str,
+        requirement: Option<&str>,
+        visiting: &mut Vec<String>,
+        resolved: &mut Vec<ModuleInfo>,
+    ) -> Result<()> {
+        if let Some(module) = resolved.iter().find(|m| m.name == name) {
+            return match requirement {
+                Some(requirement) if !version::satisfies(&module.version, requirement)? => {
+                    Err(CompositionError::DependencyResolutionFailed(format!(
+                        "{} {} is selected, but {} {} is required",
+                        name, module.version, name, requirement
+                    )))
+                }
+                _ => Ok(()),
+            };
+        }
+        if let Some(start) = visiting.iter().position(|n| n == name) {
+            let mut cycle = visiting[start..].to_vec();
+            cycle.push(name.to_string());
+            return Err(CompositionError::DependencyResolutionFailed(format!(
+                "circular dependency: {}",
+                cycle.join(" -> ")
+            )));
+        }
+        let module = self.get_module(name, requirement).map_err(|e| match e {
+            CompositionError::ModuleVersionNotFound(name, detail) => {
+                CompositionError::DependencyResolutionFailed(format!(
+                    "no installed version of {} matches {}",
+                    name, detail
+                ))
+            }
+            e => e,
+        })?;
+
+        visiting.push(name.to_string());
+        let mut required: Vec<(&String, &String)> = module.dependencies.iter().collect();
+        required.sort();
+        for (dependency, requirement) in required {
+            let provider = self.provider_of(dependency).ok_or_else(|| {
+                CompositionError::DependencyResolutionFailed(format!(
+                    "module {} requires {}, which no installed module provides",
+                    name, dependency
+                ))
+            })?;
+            let requirement = (provider == *dependency).then_some(requirement.as_str());
+            self.resolve_into(&provider, requirement, visiting, resolved)?;
+        }
+        let mut optional: Vec<(&String, &String)> = module.optional_dependencies.iter().collect();
+        optional.sort();
+        for (dependency, requirement) in optional {
+            if let Some(provider) = self.provider_of(dependency) {
+                let requirement = (provider == *dependency).then_some(requirement.as_str());
+                self.resolve_into(&provider, requirement, visiting, resolved)?;
+            }
+        }
+        visiting.pop();
 
-        let resolution =
-            RefModuleDependencies::resolve(&requested).map_err(|e| CompositionError::from(e))?;
+        resolved.push(module);
+        Ok(())
+    }
 
-        // Build result with resolved modules
-        let mut resolved = Vec::new();
-        for name in &resolution.load_order {
-            let module = self.get_module(name, None)?;
-            resolved.push(module);
+    /// Name of the module satisfying `dependency`, if one is installed
+    ///
+    /// A module named `dependency` wins over modules that provide it.
+    pub fn provider_of(&self, dependency: &str) -> Option<String> {
+        if self.discovered.iter().any(|m| m.name == dependency) {
+            return Some(dependency.to_string());
         }
+        self.what_provides(dependency)
+            .first()
+            .map(|m| m.name.clone())
+    }
+}
 
-        Ok(resolved)
+/// Discover the modules in one search directory
+///
+/// Besides `<dir>/<module>/module.toml`, finds modules installed from git
+/// as `<dir>/<name>/<version>/module.toml`.
+fn discover_dir(dir: &Path) -> Result<Vec<RefDiscoveredModule>> {
+    let discover = |dir: &Path| {
+        RefModuleDiscovery::new(dir)
+            .discover_modules()
+            .map_err(|e: RefModuleError| CompositionError::from(e))
+    };
+    let mut modules = discover(dir)?;
+
+    let mut versioned: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.is_dir() && !path.join("module.toml").exists())
+        .collect();
+    versioned.sort();
+    for name_dir in versioned {
+        for module in discover(&name_dir)? {
+            if !modules.iter().any(|m| m.directory == module.directory) {
+                modules.push(module);
+            }
+        }
+    }
+    Ok(modules)
+}
+
+/// Module info including SDK manifest fields and the binary hash from its
+/// install record
+fn module_info(discovered: &RefDiscoveredModule) -> ModuleInfo {
+    let mut info = ModuleInfo::from(discovered);
+    if let Ok(manifest) = ModuleManifest::from_file(discovered.directory.join("module.toml")) {
+        info.optional_dependencies = manifest.optional_dependencies;
+        info.provides = manifest.provides;
     }
+    info.installed_sha256 = InstallMetadata::load(&discovered.directory)
+        .ok()
+        .flatten()
+        .map(|record| record.sha256);
+    info
 }
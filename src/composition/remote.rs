@@ -0,0 +1,392 @@
+//! Remote Module Registries
+//!
+//! A registry is a static HTTPS site serving `index.json`, a JSON array of
+//! [`IndexEntry`] releases, next to the module archives it lists. Archives
+//! are gzipped tarballs holding `module.toml` and the module binary, either
+//! at the top level or in a single directory.
+//!
+//! All network access goes through a [`RegistryClient`]. Indexes are cached
+//! on disk with their ETag and revalidated with `If-None-Match`, so an
+//! unchanged index is not downloaded again.
+
+use crate::composition::types::*;
+use crate::module::manifest::ModuleManifest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Index file served at a registry's base URL
+pub const INDEX_FILE: &str = "index.json";
+
+/// A module release listed in a registry index
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct IndexEntry {
+    /// Module name
+    pub name: String,
+    /// Module version
+    pub version: String,
+    /// SHA256 of the archive (hex)
+    pub sha256: String,
+    /// Archive URL, absolute or relative to the index
+    pub url: String,
+    /// The release's module manifest
+    pub manifest: ModuleManifest,
+}
+
+impl IndexEntry {
+    /// Whether `query` appears in the name or description, ignoring case
+    ///
+    /// An empty query matches every entry.
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.to_lowercase();
+        self.name.to_lowercase().contains(&query)
+            || self
+                .manifest
+                .description
+                .as_deref()
+                .is_some_and(|d| d.to_lowercase().contains(&query))
+    }
+}
+
+/// Response to a registry GET
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fetched {
+    /// The resource, with its ETag if the server sent one
+    Body { body: Vec<u8>, etag: Option<String> },
+    /// 304 Not Modified: the cached copy is current
+    NotModified,
+}
+
+/// Transport to a remote registry
+///
+/// Implementations map connection failures and HTTP error statuses to
+/// [`CompositionError::RegistryUnavailable`].
+pub trait RegistryClient: Send + Sync {
+    /// GET `url`, sending `If-None-Match` when `etag` is given
+    fn get(&self, url: &str, etag: Option<&str>) -> Result<Fetched>;
+}
+
+/// [`RegistryClient`] that runs `curl`, allowing HTTPS only
+#[derive(Debug, Clone)]
+pub struct CurlRegistryClient {
+    program: String,
+}
+
+impl Default for CurlRegistryClient {
+    fn default() -> Self {
+        Self {
+            program: "curl".to_string(),
+        }
+    }
+}
+
+impl CurlRegistryClient {
+    /// Create a client using `curl` from `PATH`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a different curl executable
+    pub fn with_program(mut self, program: &str) -> Self {
+        self.program = program.to_string();
+        self
+    }
+}
+
+impl RegistryClient for CurlRegistryClient {
+    fn get(&self, url: &str, etag: Option<&str>) -> Result<Fetched> {
+        if !url.starts_with("https://") {
+            return Err(unavailable(format!("{}: registries must use https", url)));
+        }
+
+        let staging = Staging::new()?;
+        let body = staging.path().join("body");
+        let headers = staging.path().join("headers");
+        let mut command = Command::new(&self.program);
+        command
+            .args(["--silent", "--show-error", "--location"])
+            .args(["--proto", "=https", "--proto-redir", "=https"])
+            .arg("--output")
+            .arg(&body)
+            .arg("--dump-header")
+            .arg(&headers)
+            .args(["--write-out", "%{http_code}"]);
+        if let Some(etag) = etag {
+            command
+                .arg("--header")
+                .arg(format!("If-None-Match: {}", etag));
+        }
+        let output = command
+            .arg(url)
+            .output()
+            .map_err(|e| unavailable(format!("Failed to run {}: {}", self.program, e)))?;
+        if !output.status.success() {
+            return Err(unavailable(format!(
+                "{}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+
+        match String::from_utf8_lossy(&output.stdout).trim() {
+            "200" => Ok(Fetched::Body {
+                body: std::fs::read(&body)?,
+                etag: parse_etag(&std::fs::read_to_string(&headers)?),
+            }),
+            "304" => Ok(Fetched::NotModified),
+            status => Err(unavailable(format!("{}: HTTP {}", url, status))),
+        }
+    }
+}
+
+/// URL of the index for a registry base URL (or an index URL itself)
+pub fn index_url(registry: &str) -> String {
+    if registry.ends_with(".json") {
+        registry.to_string()
+    } else {
+        format!("{}/{}", registry.trim_end_matches('/'), INDEX_FILE)
+    }
+}
+
+/// Absolute URL of an entry's archive
+pub fn archive_url(index_url: &str, entry: &IndexEntry) -> String {
+    if entry.url.contains("://") {
+        return entry.url.clone();
+    }
+    let base = index_url
+        .rsplit_once('/')
+        .map_or(index_url, |(base, _)| base);
+    format!("{}/{}", base, entry.url.trim_start_matches('/'))
+}
+
+/// Index as cached on disk
+#[derive(Serialize, Deserialize)]
+struct CachedIndex {
+    url: String,
+    etag: String,
+    entries: Vec<IndexEntry>,
+}
+
+/// Fetch a registry index, revalidating the copy cached in `cache_dir`
+///
+/// Only indexes served with an ETag are cached.
+pub fn fetch_index(
+    client: &dyn RegistryClient,
+    index_url: &str,
+    cache_dir: Option<&Path>,
+) -> Result<Vec<IndexEntry>> {
+    let cache_file = cache_dir.map(|dir| {
+        let key = hex::encode(Sha256::digest(index_url.as_bytes()));
+        dir.join(format!("{}.json", &key[..16]))
+    });
+    let cached = cache_file
+        .as_ref()
+        .and_then(|path| std::fs::read(path).ok())
+        .and_then(|bytes| serde_json::from_slice::<CachedIndex>(&bytes).ok())
+        .filter(|cached| cached.url == index_url);
+
+    match client.get(index_url, cached.as_ref().map(|c| c.etag.as_str()))? {
+        Fetched::NotModified => cached.map(|c| c.entries).ok_or_else(|| {
+            unavailable(format!(
+                "{} returned 304 Not Modified without a cached index",
+                index_url
+            ))
+        }),
+        Fetched::Body { body, etag } => {
+            let entries: Vec<IndexEntry> = serde_json::from_slice(&body).map_err(|e| {
+                CompositionError::SerializationError(format!(
+                    "Invalid registry index {}: {}",
+                    index_url, e
+                ))
+            })?;
+            if let (Some(path), Some(etag)) = (cache_file, etag) {
+                let cached = CachedIndex {
+                    url: index_url.to_string(),
+                    etag,
+                    entries,
+                };
+                // The cache only saves downloads; a read-only cache is not an error
+                let _ = write_cache(&path, &cached);
+                return Ok(cached.entries);
+            }
+            Ok(entries)
+        }
+    }
+}
+
+fn write_cache(path: &Path, cached: &CachedIndex) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_vec(cached)
+        .map_err(|e| CompositionError::SerializationError(e.to_string()))?;
+    std::fs::write(path, json)?;
+    Ok(())
+}
+
+/// An archive unpacked into a temporary directory, removed when dropped
+pub struct UnpackedArchive {
+    staging: Staging,
+}
+
+impl UnpackedArchive {
+    /// Download `entry`'s archive, verify its SHA256, and unpack it
+    ///
+    /// Fails with [`CompositionError::ChecksumMismatch`] before unpacking
+    /// anything if the archive does not match the index.
+    pub fn download(
+        client: &dyn RegistryClient,
+        index_url: &str,
+        entry: &IndexEntry,
+    ) -> Result<Self> {
+        let url = archive_url(index_url, entry);
+        let archive = match client.get(&url, None)? {
+            Fetched::Body { body, .. } => body,
+            Fetched::NotModified => {
+                return Err(unavailable(format!("{}: unexpected 304 Not Modified", url)))
+            }
+        };
+
+        let actual = hex::encode(Sha256::digest(&archive));
+        if !actual.eq_ignore_ascii_case(entry.sha256.trim()) {
+            return Err(CompositionError::ChecksumMismatch(
+                format!("{} {}", entry.name, entry.version),
+                entry.sha256.clone(),
+                actual,
+            ));
+        }
+
+        let staging = Staging::new()?;
+        let archive_path = staging.path().join("module.tar.gz");
+        let dest = staging.path().join("module");
+        std::fs::write(&archive_path, archive)?;
+        std::fs::create_dir(&dest)?;
+        let output = Command::new("tar")
+            .arg("-xzf")
+            .arg(&archive_path)
+            .arg("-C")
+            .arg(&dest)
+            .output()
+            .map_err(|e| {
+                CompositionError::InstallationFailed(format!("Failed to run tar: {}", e))
+            })?;
+        if !output.status.success() {
+            return Err(CompositionError::InstallationFailed(format!(
+                "Failed to unpack {}: {}",
+                url,
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(Self { staging })
+    }
+
+    /// Root of the unpacked archive
+    pub fn path(&self) -> PathBuf {
+        self.staging.path().join("module")
+    }
+}
+
+/// Temporary directory, removed when dropped
+struct Staging {
+    dir: PathBuf,
+}
+
+impl Staging {
+    fn new() -> Result<Self> {
+        let dir = std::env::temp_dir().join(format!("bllvm-registry-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self { dir })
+    }
+
+    fn path(&self) -> &Path {
+        &self.dir
+    }
+}
+
+impl Drop for Staging {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// ETag of the final response in a `--dump-header` file (after redirects)
+fn parse_etag(headers: &str) -> Option<String> {
+    let last = headers
+        .split("\r\n\r\n")
+        .map(str::trim)
+        .filter(|block| !block.is_empty())
+        .last()?;
+    last.lines().find_map(|line| {
+        let (name, value) = line.split_once(':')?;
+        name.trim()
+            .eq_ignore_ascii_case("etag")
+            .then(|| value.trim().to_string())
+    })
+}
+
+fn unavailable(msg: String) -> CompositionError {
+    CompositionError::RegistryUnavailable(msg)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(url: &str) -> IndexEntry {
+        IndexEntry {
+            name: "indexer".to_string(),
+            version: "1.0.0".to_string(),
+            sha256: String::new(),
+            url: url.to_string(),
+            manifest: ModuleManifest::from_toml_str(
+                "name = \"indexer\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\ndescription = \"Address Index\"\n",
+            )
+            .unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_registry_urls() {
+        let index = index_url("https://modules.example/");
+        assert_eq!(index, "https://modules.example/index.json");
+        assert_eq!(
+            index_url("https://modules.example/v2.json"),
+            "https://modules.example/v2.json"
+        );
+
+        assert_eq!(
+            archive_url(&index, &entry("indexer-1.0.0.tar.gz")),
+            "https://modules.example/indexer-1.0.0.tar.gz"
+        );
+        assert_eq!(
+            archive_url(&index, &entry("https://cdn.example/indexer.tar.gz")),
+            "https://cdn.example/indexer.tar.gz"
+        );
+    }
+
+    #[test]
+    fn test_index_entry_matches() {
+        let entry = entry("indexer.tar.gz");
+        assert!(entry.matches(""));
+        assert!(entry.matches("INDEX"));
+        assert!(entry.matches("address"));
+        assert!(!entry.matches("lightning"));
+    }
+
+    #[test]
+    fn test_parse_etag() {
+        let headers = "HTTP/1.1 302 Found\r\nETag: \"redirect\"\r\nLocation: /index.json\r\n\r\n\
+                       HTTP/2 200\r\ncontent-type: application/json\r\netag: W/\"abc\"\r\n\r\n";
+        assert_eq!(parse_etag(headers).as_deref(), Some("W/\"abc\""));
+        assert_eq!(parse_etag("HTTP/2 200\r\n\r\n"), None);
+    }
+
+    #[test]
+    fn test_curl_client_requires_https() {
+        let err = CurlRegistryClient::new()
+            .get("http://modules.example/index.json", None)
+            .unwrap_err();
+        assert!(matches!(err, CompositionError::RegistryUnavailable(_)));
+    }
+}
@@ -0,0 +1,168 @@
+//! Node Status Reports
+//!
+//! Structured health summary of a composed node, rendered as text for
+//! operators or JSON for tooling.
+
+use crate::composition::types::*;
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use serde::{Deserialize, Serialize};
+
+/// Health summary of a composed node
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct NodeReport {
+    /// Node name
+    pub name: String,
+    /// Overall node status
+    pub overall_status: NodeStatus,
+    /// Number of loaded modules
+    pub module_count: usize,
+    /// Modules reporting [`ModuleHealth::Healthy`]
+    pub healthy_count: usize,
+    /// Modules reporting [`ModuleHealth::Degraded`]
+    pub degraded_count: usize,
+    /// Modules reporting [`ModuleHealth::Unhealthy`]
+    pub unhealthy_count: usize,
+    /// Per-module reports, in load order
+    pub modules: Vec<ModuleReport>,
+}
+
+/// Health summary of a single loaded module
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModuleReport {
+    /// Module name
+    pub name: String,
+    /// Module version
+    pub version: String,
+    /// Module lifecycle status
+    pub status: ModuleStatus,
+    /// Module health
+    pub health: ModuleHealth,
+    /// Why the module is unhealthy, if it reported a reason
+    pub health_detail: Option<String>,
+}
+
+impl ComposedNode {
+    /// Summarize node and module health
+    pub fn to_report(&self) -> NodeReport {
+        let modules: Vec<ModuleReport> = self
+            .modules
+            .iter()
+            .map(|module| ModuleReport {
+                name: module.info.name.clone(),
+                version: module.info.version.clone(),
+                status: module.status.clone(),
+                health: module.health.clone(),
+                health_detail: match &module.health {
+                    ModuleHealth::Unhealthy(detail) => Some(detail.clone()),
+                    _ => None,
+                },
+            })
+            .collect();
+        let count =
+            |pred: fn(&ModuleHealth) -> bool| modules.iter().filter(|m| pred(&m.health)).count();
+
+        NodeReport {
+            name: self.spec.name.clone(),
+            overall_status: self.status.clone(),
+            module_count: modules.len(),
+            healthy_count: count(|h| *h == ModuleHealth::Healthy),
+            degraded_count: count(|h| *h == ModuleHealth::Degraded),
+            unhealthy_count: count(|h| matches!(h, ModuleHealth::Unhealthy(_))),
+            modules,
+        }
+    }
+}
+
+impl NodeReport {
+    /// Health indicator for the node as a whole
+    ///
+    /// Any unhealthy module or a node error is ❌; otherwise any module that
+    /// is not healthy is ⚠️.
+    pub fn indicator(&self) -> &'static str {
+        if self.unhealthy_count > 0 || matches!(self.overall_status, NodeStatus::Error(_)) {
+            "❌"
+        } else if self.healthy_count < self.module_count {
+            "⚠️"
+        } else {
+            "✅"
+        }
+    }
+
+    /// Human-readable report with one line per module
+    pub fn to_text(&self) -> String {
+        let mut text = format!(
+            "{} Node: {} ({})\nModules: {} total, {} healthy, {} degraded, {} unhealthy\n",
+            self.indicator(),
+            self.name,
+            node_status_label(&self.overall_status),
+            self.module_count,
+            self.healthy_count,
+            self.degraded_count,
+            self.unhealthy_count
+        );
+        for module in &self.modules {
+            text.push_str(&format!(
+                "  {} {} {}: {}, {}\n",
+                module.indicator(),
+                module.name,
+                module.version,
+                module.status_label(),
+                module.health_label()
+            ));
+        }
+        text
+    }
+
+    /// Pretty-printed JSON report
+    pub fn to_json(&self) -> GovernanceResult<String> {
+        serde_json::to_string_pretty(self).map_err(|e| {
+            GovernanceError::Serialization(format!("Failed to serialize node report: {}", e))
+        })
+    }
+}
+
+impl ModuleReport {
+    /// ✅ healthy, ⚠️ degraded or unknown, ❌ unhealthy
+    pub fn indicator(&self) -> &'static str {
+        match self.health {
+            ModuleHealth::Healthy => "✅",
+            ModuleHealth::Degraded | ModuleHealth::Unknown => "⚠️",
+            ModuleHealth::Unhealthy(_) => "❌",
+        }
+    }
+
+    /// Lifecycle status as a short label
+    pub fn status_label(&self) -> String {
+        match &self.status {
+            ModuleStatus::NotInstalled => "not installed".to_string(),
+            ModuleStatus::Stopped => "stopped".to_string(),
+            ModuleStatus::Initializing => "initializing".to_string(),
+            ModuleStatus::Running => "running".to_string(),
+            ModuleStatus::Stopping => "stopping".to_string(),
+            ModuleStatus::Error(msg) => format!("error: {}", msg),
+        }
+    }
+
+    /// Health as a short label, including the unhealthy reason
+    pub fn health_label(&self) -> String {
+        match &self.health {
+            ModuleHealth::Healthy => "healthy".to_string(),
+            ModuleHealth::Degraded => "degraded".to_string(),
+            ModuleHealth::Unhealthy(_) => match &self.health_detail {
+                Some(detail) => format!("unhealthy ({})", detail),
+                None => "unhealthy".to_string(),
+            },
+            ModuleHealth::Unknown => "unknown".to_string(),
+        }
+    }
+}
+
+fn node_status_label(status: &NodeStatus) -> String {
+    match status {
+        NodeStatus::Stopped => "stopped".to_string(),
+        NodeStatus::Starting => "starting".to_string(),
+        NodeStatus::Running => "running".to_string(),
+        NodeStatus::Stopping => "stopping".to_string(),
+        NodeStatus::Error(msg) => format!("error: {}", msg),
+    }
+}
@@ -0,0 +1,260 @@
+//! Module Resource Limits
+//!
+//! Per-module memory, CPU, and open file limits, and the process layer that
+//! applies them to running module processes.
+//!
+//! On Linux, memory (`RLIMIT_AS`) and open file (`RLIMIT_NOFILE`) limits are
+//! set with `prlimit`, and CPU limits use a cgroup v2 `cpu.max` quota when the
+//! cgroup v2 CPU controller is available.
+
+use crate::composition::types::*;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Resource limits for a module process
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    /// Maximum address space in bytes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_memory_bytes: Option<u64>,
+    /// Maximum CPU usage as a percentage of one core (200.0 = two cores)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_cpu_percent: Option<f32>,
+    /// Maximum number of open file descriptors
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_open_files: Option<u32>,
+}
+
+impl ResourceLimits {
+    /// Whether no limit is set
+    pub fn is_unlimited(&self) -> bool {
+        self.max_memory_bytes.is_none()
+            && self.max_cpu_percent.is_none()
+            && self.max_open_files.is_none()
+    }
+
+    /// Check that the configured limits are usable
+    pub fn validate(&self) -> Result<()> {
+        if let Some(cpu) = self.max_cpu_percent {
+            if !cpu.is_finite() || cpu <= 0.0 {
+                return Err(CompositionError::InvalidConfiguration(format!(
+                    "CPU limit must be a positive percentage, got {}",
+                    cpu
+                )));
+            }
+        }
+        if self.max_memory_bytes == Some(0) || self.max_open_files == Some(0) {
+            return Err(CompositionError::InvalidConfiguration(
+                "Memory and open file limits must be greater than zero".to_string(),
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// Current resource consumption of a module process
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    /// Resident memory in bytes
+    pub memory_bytes: u64,
+    /// Total CPU time (user + system) in milliseconds
+    pub cpu_time_ms: u64,
+    /// Average CPU usage since the process started, as a percentage of one core
+    pub cpu_percent: f32,
+}
+
+/// Process layer used by `ModuleLifecycle` to constrain and inspect modules
+pub trait ProcessController: Send + Sync {
+    /// Apply resource limits to a running module process
+    fn apply_limits(&self, module_name: &str, pid: u32, limits: &ResourceLimits) -> Result<()>;
+
+    /// Read the current resource usage of a module process
+    fn resource_usage(&self, pid: u32) -> Result<ResourceUsage>;
+}
+
+/// Default cgroup v2 mount point
+pub const DEFAULT_CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// cgroup v2 `cpu.max` period in microseconds
+const CPU_PERIOD_US: u64 = 100_000;
+
+/// Operating system process controller
+///
+/// CPU limits place the module in `<cgroup_root>/blvm-<module>`; the root's
+/// `cgroup.subtree_control` must already enable the `cpu` controller.
+#[derive(Debug, Clone)]
+pub struct SystemProcessController {
+    cgroup_root: PathBuf,
+}
+
+impl Default for SystemProcessController {
+    fn default() -> Self {
+        Self {
+            cgroup_root: PathBuf::from(DEFAULT_CGROUP_ROOT),
+        }
+    }
+}
+
+impl SystemProcessController {
+    /// Create a controller using the default cgroup mount point
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the cgroup v2 root used for CPU limits
+    pub fn with_cgroup_root<P: AsRef<Path>>(mut self, cgroup_root: P) -> Self {
+        self.cgroup_root = cgroup_root.as_ref().to_path_buf();
+        self
+    }
+
+    /// Whether the cgroup v2 CPU controller is available
+    pub fn cpu_cgroup_available(&self) -> bool {
+        std::fs::read_to_string(self.cgroup_root.join("cgroup.controllers"))
+            .is_ok_and(|controllers| controllers.split_whitespace().any(|c| c == "cpu"))
+    }
+
+    /// Move a process into its module cgroup with a `cpu.max` quota
+    fn apply_cpu_limit(&self, module_name: &str, pid: u32, cpu_percent: f32) -> Result<()> {
+        let cgroup = self.cgroup_root.join(format!("blvm-{}", module_name));
+        std::fs::create_dir_all(&cgroup)?;
+
+        let quota = ((CPU_PERIOD_US as f64) * (cpu_percent as f64) / 100.0).ceil() as u64;
+        std::fs::write(
+            cgroup.join("cpu.max"),
+            format!("{} {}", quota.max(1), CPU_PERIOD_US),
+        )?;
+        std::fs::write(cgroup.join("cgroup.procs"), pid.to_string())?;
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl ProcessController for SystemProcessController {
+    fn apply_limits(&self, module_name: &str, pid: u32, limits: &ResourceLimits) -> Result<()> {
+        limits.validate()?;
+
+        // SAFETY: the new limit is a valid rlimit and the old-limit pointer may be null
+        if let Some(bytes) = limits.max_memory_bytes {
+            let limit = rlimit(bytes);
+            check_os_result(unsafe {
+                libc::prlimit(
+                    pid as libc::pid_t,
+                    libc::RLIMIT_AS,
+                    &limit,
+                    std::ptr::null_mut(),
+                )
+            })?;
+        }
+        if let Some(files) = limits.max_open_files {
+            let limit = rlimit(files as u64);
+            check_os_result(unsafe {
+                libc::prlimit(
+                    pid as libc::pid_t,
+                    libc::RLIMIT_NOFILE,
+                    &limit,
+                    std::ptr::null_mut(),
+                )
+            })?;
+        }
+        // Without cgroup v2 there is no per-process CPU quota; the limit is skipped
+        if let Some(cpu_percent) = limits.max_cpu_percent {
+            if self.cpu_cgroup_available() {
+                self.apply_cpu_limit(module_name, pid, cpu_percent)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn resource_usage(&self, pid: u32) -> Result<ResourceUsage> {
+        let proc_dir = PathBuf::from(format!("/proc/{}", pid));
+        let statm = std::fs::read_to_string(proc_dir.join("statm"))?;
+        let stat = std::fs::read_to_string(proc_dir.join("stat"))?;
+        let uptime = std::fs::read_to_string("/proc/uptime")?;
+
+        let malformed = |file: &str| {
+            CompositionError::SerializationError(format!("Malformed /proc/{}/{}", pid, file))
+        };
+
+        // statm: size resident shared ... (in pages)
+        let resident_pages: u64 = statm
+            .split_whitespace()
+            .nth(1)
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| malformed("statm"))?;
+
+        // stat: fields after the parenthesised command name, starting at field 3
+        let fields: Vec<&str> = stat
+            .rsplit_once(')')
+            .map(|(_, rest)| rest.split_whitespace().collect())
+            .ok_or_else(|| malformed("stat"))?;
+        let field = |n: usize| -> Result<u64> {
+            fields
+                .get(n - 3)
+                .and_then(|v| v.parse().ok())
+                .ok_or_else(|| malformed("stat"))
+        };
+        let cpu_ticks = field(14)? + field(15)?;
+        let start_ticks = field(22)?;
+
+        let uptime_secs: f64 = uptime
+            .split_whitespace()
+            .next()
+            .and_then(|v| v.parse().ok())
+            .ok_or_else(|| CompositionError::SerializationError("Malformed /proc/uptime".into()))?;
+
+        // SAFETY: sysconf has no preconditions
+        let page_size = unsafe { libc::sysconf(libc::_SC_PAGESIZE) }.max(1) as u64;
+        let ticks_per_sec = unsafe { libc::sysconf(libc::_SC_CLK_TCK) }.max(1) as f64;
+
+        let cpu_secs = cpu_ticks as f64 / ticks_per_sec;
+        let elapsed_secs = uptime_secs - start_ticks as f64 / ticks_per_sec;
+        let cpu_percent = if elapsed_secs > 0.0 {
+            (cpu_secs / elapsed_secs * 100.0) as f32
+        } else {
+            0.0
+        };
+
+        Ok(ResourceUsage {
+            memory_bytes: resident_pages * page_size,
+            cpu_time_ms: (cpu_secs * 1000.0) as u64,
+            cpu_percent,
+        })
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+impl ProcessController for SystemProcessController {
+    fn apply_limits(&self, _module_name: &str, _pid: u32, limits: &ResourceLimits) -> Result<()> {
+        limits.validate()?;
+        if limits.is_unlimited() {
+            return Ok(());
+        }
+        Err(CompositionError::InvalidConfiguration(
+            "Module resource limits are only supported on Linux".to_string(),
+        ))
+    }
+
+    fn resource_usage(&self, _pid: u32) -> Result<ResourceUsage> {
+        Err(CompositionError::InvalidConfiguration(
+            "Module resource usage is only available on Linux".to_string(),
+        ))
+    }
+}
+
+/// Soft and hard limit set to the same value
+#[cfg(target_os = "linux")]
+fn rlimit(value: u64) -> libc::rlimit {
+    libc::rlimit {
+        rlim_cur: value as libc::rlim_t,
+        rlim_max: value as libc::rlim_t,
+    }
+}
+
+/// Map a libc return code to the current OS error
+#[cfg(target_os = "linux")]
+fn check_os_result(ret: libc::c_int) -> Result<()> {
+    if ret != 0 {
+        return Err(CompositionError::IoError(std::io::Error::last_os_error()));
+    }
+    Ok(())
+}
@@ -15,7 +15,7 @@ pub fn validate_config_schema(config: &NodeConfig) -> Result<ValidationResult> {
         errors.push("Node name cannot be empty".to_string());
     }
 
-    if !["mainnet", "testnet", "regtest"].contains(&config.node.network.as_str()) {
+    if config.node.network.parse::<NetworkType>().is_err() {
         errors.push(format!(
             "Invalid network type: {}. Must be one of: mainnet, testnet, regtest",
             config.node.network
@@ -47,3 +47,34 @@ pub fn validate_config_schema(config: &NodeConfig) -> Result<ValidationResult> {
         dependencies: Vec::new(), // Will be populated during dependency resolution
     })
 }
+
+/// Validate node specification schema
+///
+/// Mirrors [`validate_config_schema`] for specs that were built directly
+/// rather than loaded from a configuration file.
+pub fn validate_spec_schema(spec: &NodeSpec) -> Result<ValidationResult> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+
+    // Validate node metadata
+    if spec.name.is_empty() {
+        errors.push("Node name cannot be empty".to_string());
+    }
+
+    // Validate modules
+    for module_spec in spec.modules.iter().filter(|m| m.enabled) {
+        if module_spec.name.is_empty() {
+            errors.push("Module name cannot be empty".to_string());
+        }
+
+        // Warn if version not specified
+        if module_spec.version.is_none() {
+            warnings.push(format!(
+                "Module '{}' does not specify version, will use latest available",
+                module_spec.name
+            ));
+        }
+    }
+
+    Ok(ValidationResult::from_errors(errors).merge(ValidationResult::from_warnings(warnings)))
+}
@@ -7,6 +7,8 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use thiserror::Error;
 
+pub use crate::governance::network::NetworkType;
+
 /// Module information from registry
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub struct ModuleInfo {
@@ -20,8 +22,14 @@ pub struct ModuleInfo {
     pub author: Option<String>,
     /// Capabilities this module declares it can use
     pub capabilities: Vec<String>,
-    /// Required dependencies (module names with versions)
+    /// Required dependencies (module names or provided capabilities, with versions)
     pub dependencies: HashMap<String, String>,
+    /// Dependencies loaded first when installed, but not required
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub optional_dependencies: HashMap<String, String>,
+    /// Abstract capabilities this module provides (e.g. `bitcoin-p2p-v1`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provides: Vec<String>,
     /// Module entry point (binary name or path)
     pub entry_point: String,
     /// Path to module directory
@@ -30,6 +38,9 @@ pub struct ModuleInfo {
     pub binary_path: Option<PathBuf>,
     /// Module configuration schema (optional)
     pub config_schema: HashMap<String, String>,
+    /// SHA256 of the binary recorded when the module was installed (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub installed_sha256: Option<String>,
 }
 
 /// Module source for installation
@@ -37,10 +48,109 @@ pub struct ModuleInfo {
 pub enum ModuleSource {
     /// Install from local path
     Path(PathBuf),
-    /// Install from remote registry (URL)
-    Registry(String),
+    /// Install from a remote registry (see [`crate::composition::remote`])
+    Registry {
+        /// Registry base URL, or the URL of its `index.json`
+        url: String,
+        /// Module name
+        name: String,
+        /// Exact version; the highest listed version if `None`
+        version: Option<String>,
+    },
     /// Install from git repository
-    Git { url: String, tag: Option<String> },
+    Git {
+        /// Repository URL (anything `git fetch` accepts)
+        url: String,
+        /// Revision to check out
+        reference: GitReference,
+    },
+}
+
+impl ModuleSource {
+    /// Install the latest version of `name` listed by the registry at `url`
+    pub fn registry(url: impl Into<String>, name: impl Into<String>) -> Self {
+        Self::Registry {
+            url: url.into(),
+            name: name.into(),
+            version: None,
+        }
+    }
+
+    /// Install from a git tag
+    pub fn git_tag(url: impl Into<String>, tag: impl Into<String>) -> Self {
+        Self::Git {
+            url: url.into(),
+            reference: GitReference::Tag(tag.into()),
+        }
+    }
+
+    /// Install from the tip of a git branch
+    pub fn git_branch(url: impl Into<String>, branch: impl Into<String>) -> Self {
+        Self::Git {
+            url: url.into(),
+            reference: GitReference::Branch(branch.into()),
+        }
+    }
+
+    /// Install from an exact git commit
+    pub fn git_commit(url: impl Into<String>, sha: impl Into<String>) -> Self {
+        Self::Git {
+            url: url.into(),
+            reference: GitReference::Commit(sha.into()),
+        }
+    }
+}
+
+/// Git revision a module is installed from
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum GitReference {
+    /// Tag name (annotated or lightweight)
+    Tag(String),
+    /// Branch name, checked out at its current tip
+    Branch(String),
+    /// Full commit SHA
+    Commit(String),
+    /// The remote's default branch (`HEAD`)
+    #[default]
+    Latest,
+}
+
+impl GitReference {
+    /// Remote ref to fetch for this reference
+    ///
+    /// Tags and branches are fully qualified so a tag never resolves to a
+    /// branch of the same name (or vice versa).
+    pub fn refspec(&self) -> String {
+        match self {
+            GitReference::Tag(tag) => format!("refs/tags/{}", tag),
+            GitReference::Branch(branch) => format!("refs/heads/{}", branch),
+            GitReference::Commit(sha) => sha.clone(),
+            GitReference::Latest => "HEAD".to_string(),
+        }
+    }
+}
+
+impl std::fmt::Display for GitReference {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GitReference::Tag(tag) => write!(f, "tag {}", tag),
+            GitReference::Branch(branch) => write!(f, "branch {}", branch),
+            GitReference::Commit(sha) => write!(f, "commit {}", sha),
+            GitReference::Latest => write!(f, "default branch"),
+        }
+    }
+}
+
+/// How to resolve a module name found in more than one search directory
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// Keep the module from the earliest search directory
+    #[default]
+    FirstWins,
+    /// Keep the module with the highest version
+    LatestVersion,
+    /// Fail discovery
+    Error,
 }
 
 /// Module lifecycle status
@@ -60,6 +170,23 @@ pub enum ModuleStatus {
     Error(String),
 }
 
+/// Persisted process state for a single module
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ModuleProcessState {
+    /// Last known lifecycle status
+    pub status: ModuleStatus,
+    /// Process ID of the running module (if known)
+    #[serde(default)]
+    pub pid: Option<u32>,
+}
+
+/// Lifecycle state persisted across node restarts
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct LifecycleState {
+    /// Per-module process state, keyed by module name
+    pub modules: HashMap<String, ModuleProcessState>,
+}
+
 /// Module health status
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum ModuleHealth {
@@ -73,17 +200,6 @@ pub enum ModuleHealth {
     Unknown,
 }
 
-/// Network type for node composition
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
-pub enum NetworkType {
-    /// Bitcoin mainnet
-    Mainnet,
-    /// Bitcoin testnet
-    Testnet,
-    /// Regression test network
-    Regtest,
-}
-
 /// Node specification for composition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NodeSpec {
@@ -102,7 +218,7 @@ pub struct NodeSpec {
 pub struct ModuleSpec {
     /// Module name
     pub name: String,
-    /// Module version (optional, uses latest if not specified)
+    /// Module version requirement (e.g. `1.2.3`, `^1.2`); latest if not specified
     pub version: Option<String>,
     /// Whether module is enabled
     pub enabled: bool,
@@ -111,6 +227,108 @@ pub struct ModuleSpec {
     pub config: HashMap<String, serde_json::Value>,
 }
 
+/// Longest node name accepted by [`NodeSpec::validate_structure`]
+pub const MAX_NODE_NAME_LEN: usize = 64;
+
+impl NodeSpec {
+    /// Check the spec on its own, without a registry
+    ///
+    /// Catches empty or overlong names, non-semver versions, and duplicate
+    /// modules. Returns one message per problem; empty means valid.
+    pub fn validate_structure(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.name.trim().is_empty() {
+            errors.push("Node name must not be empty".to_string());
+        } else if self.name.chars().count() > MAX_NODE_NAME_LEN {
+            errors.push(format!(
+                "Node name '{}' is longer than {} characters",
+                self.name, MAX_NODE_NAME_LEN
+            ));
+        }
+        if let Some(version) = &self.version {
+            if semver::Version::parse(version).is_err() {
+                errors.push(format!(
+                    "Node version '{}' is not a semantic version",
+                    version
+                ));
+            }
+        }
+
+        let mut seen = std::collections::HashSet::new();
+        for module in &self.modules {
+            errors.extend(module.validate_structure());
+            if !module.name.trim().is_empty() && !seen.insert(module.name.as_str()) {
+                errors.push(format!("Module '{}' is listed more than once", module.name));
+            }
+        }
+        errors
+    }
+
+    /// [`NodeSpec::validate_structure`] as a `Result`
+    pub fn assert_valid(&self) -> Result<()> {
+        let errors = self.validate_structure();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(CompositionError::ValidationFailed(errors.join("; ")))
+        }
+    }
+}
+
+impl ModuleSpec {
+    /// Check the name and version; returns one message per problem
+    pub fn validate_structure(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.name.trim().is_empty() {
+            errors.push("Module name must not be empty".to_string());
+        }
+        if let Some(version) = &self.version {
+            if let Err(e) = crate::composition::version::parse_requirement(version) {
+                errors.push(format!("Module '{}': {}", self.name, e));
+            }
+        }
+        errors
+    }
+
+    /// Set a configuration value, builder-style
+    pub fn with_config_value(mut self, key: &str, value: impl Into<serde_json::Value>) -> Self {
+        self.set_config_value(key, value);
+        self
+    }
+
+    /// Set a configuration value, replacing any existing entry
+    pub fn set_config_value(&mut self, key: &str, value: impl Into<serde_json::Value>) {
+        self.config.insert(key.to_string(), value.into());
+    }
+
+    /// Remove a configuration value, returning it if present
+    pub fn remove_config_value(&mut self, key: &str) -> Option<serde_json::Value> {
+        self.config.remove(key)
+    }
+
+    /// Add or replace configuration entries from `other`
+    pub fn merge_config(&mut self, other: &HashMap<String, serde_json::Value>) {
+        for (key, value) in other {
+            self.config.insert(key.clone(), value.clone());
+        }
+    }
+
+    /// Get a configuration value as a string, if present and a string
+    pub fn config_string(&self, key: &str) -> Option<&str> {
+        self.config.get(key).and_then(|v| v.as_str())
+    }
+
+    /// Get a configuration value as an integer, if present and an `i64`
+    pub fn config_integer(&self, key: &str) -> Option<i64> {
+        self.config.get(key).and_then(|v| v.as_i64())
+    }
+
+    /// Get a configuration value as a boolean, if present and a boolean
+    pub fn config_bool(&self, key: &str) -> Option<bool> {
+        self.config.get(key).and_then(|v| v.as_bool())
+    }
+}
+
 /// Loaded module information
 #[derive(Debug, Clone)]
 pub struct LoadedModule {
@@ -133,8 +351,68 @@ pub struct ComposedNode {
     pub status: NodeStatus,
 }
 
+impl ComposedNode {
+    /// Look up a loaded module by name
+    pub fn module_by_name(&self, name: &str) -> Option<&LoadedModule> {
+        self.modules.iter().find(|m| m.info.name == name)
+    }
+
+    /// Look up a loaded module by name for modification
+    pub fn module_by_name_mut(&mut self, name: &str) -> Option<&mut LoadedModule> {
+        self.modules.iter_mut().find(|m| m.info.name == name)
+    }
+
+    /// Get all loaded modules in the given status
+    pub fn modules_by_status(&self, status: &ModuleStatus) -> Vec<&LoadedModule> {
+        self.modules
+            .iter()
+            .filter(|m| &m.status == status)
+            .collect()
+    }
+
+    /// Whether every loaded module reports healthy
+    pub fn all_healthy(&self) -> bool {
+        self.modules
+            .iter()
+            .all(|m| m.health == ModuleHealth::Healthy)
+    }
+
+    /// Number of modules enabled in the node specification
+    pub fn enabled_module_count(&self) -> usize {
+        self.spec.modules.iter().filter(|m| m.enabled).count()
+    }
+
+    /// Number of loaded modules that report healthy
+    pub fn healthy_module_count(&self) -> usize {
+        self.modules
+            .iter()
+            .filter(|m| m.health == ModuleHealth::Healthy)
+            .count()
+    }
+
+    /// Human-readable summary of node and module status
+    pub fn to_status_report(&self) -> String {
+        let mut report = format!(
+            "Node: {} ({:?})\nStatus: {:?}\nModules: {} loaded, {} enabled, {} healthy\n",
+            self.spec.name,
+            self.spec.network,
+            self.status,
+            self.modules.len(),
+            self.enabled_module_count(),
+            self.healthy_module_count()
+        );
+        for module in &self.modules {
+            report.push_str(&format!(
+                "  - {} ({}): {:?}, {:?}\n",
+                module.info.name, module.info.version, module.status, module.health
+            ));
+        }
+        report
+    }
+}
+
 /// Node status
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 pub enum NodeStatus {
     /// Node is stopped
     Stopped,
@@ -161,6 +439,64 @@ pub struct ValidationResult {
     pub dependencies: Vec<ModuleInfo>,
 }
 
+impl ValidationResult {
+    /// Create a failed result from a list of errors
+    pub fn from_errors(errors: Vec<String>) -> Self {
+        Self {
+            valid: errors.is_empty(),
+            errors,
+            warnings: Vec::new(),
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Create a passing result carrying only warnings
+    pub fn from_warnings(warnings: Vec<String>) -> Self {
+        Self {
+            valid: true,
+            errors: Vec::new(),
+            warnings,
+            dependencies: Vec::new(),
+        }
+    }
+
+    /// Combine two validation results
+    ///
+    /// Errors and warnings are concatenated, dependencies are unioned by module
+    /// name, and the merged result is only valid if both inputs were valid.
+    pub fn merge(mut self, other: ValidationResult) -> ValidationResult {
+        self.valid = self.valid && other.valid;
+        self.errors.extend(other.errors);
+        self.warnings.extend(other.warnings);
+        for dependency in other.dependencies {
+            if !self.dependencies.iter().any(|d| d.name == dependency.name) {
+                self.dependencies.push(dependency);
+            }
+        }
+        self
+    }
+
+    /// Whether any errors were reported
+    pub fn has_errors(&self) -> bool {
+        !self.errors.is_empty()
+    }
+
+    /// Whether any warnings were reported
+    pub fn has_warnings(&self) -> bool {
+        !self.warnings.is_empty()
+    }
+
+    /// Number of errors reported
+    pub fn error_count(&self) -> usize {
+        self.errors.len()
+    }
+
+    /// Number of warnings reported
+    pub fn warning_count(&self) -> usize {
+        self.warnings.len()
+    }
+}
+
 /// Composition errors
 #[derive(Debug, Error)]
 pub enum CompositionError {
@@ -187,6 +523,18 @@ pub enum CompositionError {
 
     #[error("Serialization error: {0}")]
     SerializationError(String),
+
+    #[error("Checksum mismatch for {0}: expected {1}, got {2}")]
+    ChecksumMismatch(String, String, String),
+
+    #[error("Registry unavailable: {0}")]
+    RegistryUnavailable(String),
+
+    #[error("Integrity check failed for module {0}: {1}")]
+    IntegrityCheckFailed(String, String),
+
+    #[error("Lockfile mismatch: {0}")]
+    LockfileMismatch(String),
 }
 
 pub type Result<T> = std::result::Result<T, CompositionError>;
@@ -4,6 +4,7 @@
 
 use crate::composition::registry::ModuleRegistry;
 use crate::composition::types::*;
+use crate::module::manifest::ModuleManifest;
 
 /// Validate a node composition specification
 pub fn validate_composition(
@@ -14,14 +15,6 @@ pub fn validate_composition(
     let mut warnings = Vec::new();
     let mut dependencies = Vec::new();
 
-    // Resolve all module names
-    let module_names: Vec<String> = spec
-        .modules
-        .iter()
-        .filter(|m| m.enabled)
-        .map(|m| m.name.clone())
-        .collect();
-
     // Check all modules exist
     for module_spec in &spec.modules {
         if !module_spec.enabled {
@@ -42,7 +35,8 @@ pub fn validate_composition(
         }
     }
 
-    // Resolve dependencies
+    // Resolve dependencies of the modules that exist; missing ones are reported above
+    let module_names: Vec<String> = dependencies.iter().map(|m| m.name.clone()).collect();
     match registry.resolve_dependencies(&module_names) {
         Ok(resolved) => {
             // Check for missing dependencies
@@ -71,3 +65,80 @@ pub fn validate_composition(
         dependencies,
     })
 }
+
+/// Check enabled modules against their manifests
+///
+/// Each module's config keys must appear in its `config_schema`, its
+/// dependencies must be installed or provided by an installed module, and
+/// the node's network must be one the module supports. Modules missing from
+/// the registry are skipped; [`validate_composition`] reports those.
+pub fn validate_module_requirements(
+    spec: &NodeSpec,
+    registry: &ModuleRegistry,
+) -> Result<ValidationResult> {
+    let mut errors = Vec::new();
+    let mut warnings = Vec::new();
+    let network = spec.network.as_str();
+
+    for module_spec in spec.modules.iter().filter(|m| m.enabled) {
+        let Ok(info) = registry.get_module(&module_spec.name, module_spec.version.as_deref())
+        else {
+            continue;
+        };
+
+        // Module config must match the declared schema
+        if info.config_schema.is_empty() {
+            if !module_spec.config.is_empty() {
+                warnings.push(format!(
+                    "Module '{}' declares no config schema; its config is not checked",
+                    info.name
+                ));
+            }
+        } else {
+            let mut unknown: Vec<&String> = module_spec
+                .config
+                .keys()
+                .filter(|key| !info.config_schema.contains_key(*key))
+                .collect();
+            unknown.sort();
+            for key in unknown {
+                errors.push(format!(
+                    "Module '{}' has unknown config key '{}'",
+                    info.name, key
+                ));
+            }
+        }
+
+        // Dependencies must be installed or provided; optional ones may be absent
+        let mut dependencies: Vec<&String> = info.dependencies.keys().collect();
+        dependencies.sort();
+        for dependency in dependencies {
+            if registry.provider_of(dependency).is_none() {
+                errors.push(format!(
+                    "Module '{}' depends on '{}', which is not available",
+                    info.name, dependency
+                ));
+            }
+        }
+
+        // The node's network must be supported
+        if let Some(directory) = &info.directory {
+            match ModuleManifest::from_file(directory.join("module.toml")) {
+                Ok(manifest) if !manifest.supports_network(network) => {
+                    errors.push(format!(
+                        "Module '{}' does not support {} (supports: {})",
+                        info.name,
+                        network,
+                        manifest.networks.join(", ")
+                    ));
+                }
+                Ok(_) => {}
+                Err(e) => {
+                    errors.push(format!("Module '{}' manifest is invalid: {}", info.name, e));
+                }
+            }
+        }
+    }
+
+    Ok(ValidationResult::from_errors(errors).merge(ValidationResult::from_warnings(warnings)))
+}
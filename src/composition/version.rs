@@ -0,0 +1,171 @@
+//! Module Versions
+//!
+//! Module versions are semantic versions; node specs and module dependencies
+//! name them with Cargo-style requirements (`1`, `^1.2`, `~1.2.3`, `=1.2.3`,
+//! `>=1.0, <2`, `*`). A bare version such as `1.2.3` is a caret requirement,
+//! except that an installed module whose version string is exactly `1.2.3` is
+//! always chosen over newer compatible ones.
+
+use crate::composition::types::*;
+use semver::{Version, VersionReq};
+use std::cmp::Ordering;
+
+/// Parse a module version, allowing a leading `v`
+pub fn parse_version(version: &str) -> Option<Version> {
+    Version::parse(version.trim().trim_start_matches('v')).ok()
+}
+
+/// Parse a version requirement; an empty requirement matches any version
+pub fn parse_requirement(requirement: &str) -> Result<VersionReq> {
+    let requirement = requirement.trim();
+    if requirement.is_empty() {
+        return Ok(VersionReq::STAR);
+    }
+    VersionReq::parse(requirement).map_err(|e| {
+        CompositionError::InvalidConfiguration(format!(
+            "Invalid version requirement '{}': {}",
+            requirement, e
+        ))
+    })
+}
+
+/// Whether `version` satisfies `requirement`
+///
+/// Versions that are not semantic versions only match their exact string.
+pub fn satisfies(version: &str, requirement: &str) -> Result<bool> {
+    if version == requirement.trim() {
+        return Ok(true);
+    }
+    let requirement = parse_requirement(requirement)?;
+    Ok(parse_version(version).is_some_and(|v| requirement.matches(&v)))
+}
+
+/// The installed module to use for `requirement`
+///
+/// An exact version match wins; otherwise the highest version satisfying the
+/// requirement. Fails with [`CompositionError::ModuleVersionNotFound`],
+/// listing the available versions, if none does.
+pub fn select<'a>(installed: &[&'a ModuleInfo], requirement: &str) -> Result<&'a ModuleInfo> {
+    if let Some(exact) = installed.iter().find(|m| m.version == requirement.trim()) {
+        return Ok(exact);
+    }
+    let mut matching = Vec::new();
+    for module in installed {
+        if satisfies(&module.version, requirement)? {
+            matching.push(*module);
+        }
+    }
+    matching
+        .into_iter()
+        .max_by(|a, b| compare_versions(&a.version, &b.version))
+        .ok_or_else(|| {
+            let mut available: Vec<&str> = installed.iter().map(|m| m.version.as_str()).collect();
+            available.sort_by(|a, b| compare_versions(a, b));
+            let name = installed.first().map_or("", |m| m.name.as_str());
+            CompositionError::ModuleVersionNotFound(
+                name.to_string(),
+                format!("{} (available: {})", requirement, available.join(", ")),
+            )
+        })
+}
+
+/// Compare versions, semantically when both parse
+///
+/// Otherwise dotted components compare numerically (`1.10` > `1.9`), missing
+/// components count as zero, and non-numeric components compare as text.
+pub fn compare_versions(a: &str, b: &str) -> Ordering {
+    if let (Some(x), Some(y)) = (parse_version(a), parse_version(b)) {
+        return x.cmp(&y);
+    }
+    let a: Vec<&str> = a.trim_start_matches('v').split('.').collect();
+    let b: Vec<&str> = b.trim_start_matches('v').split('.').collect();
+    for i in 0..a.len().max(b.len()) {
+        let x = a.get(i).copied().unwrap_or("0");
+        let y = b.get(i).copied().unwrap_or("0");
+        let ordering = match (x.parse::<u64>(), y.parse::<u64>()) {
+            (Ok(x), Ok(y)) => x.cmp(&y),
+            _ => x.cmp(y),
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+    Ordering::Equal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn module(version: &str) -> ModuleInfo {
+        ModuleInfo {
+            name: "indexer".to_string(),
+            version: version.to_string(),
+            description: None,
+            author: None,
+            capabilities: Vec::new(),
+            dependencies: HashMap::new(),
+            optional_dependencies: HashMap::new(),
+            provides: Vec::new(),
+            entry_point: "indexer".to_string(),
+            directory: None,
+            binary_path: None,
+            config_schema: HashMap::new(),
+            installed_sha256: None,
+        }
+    }
+
+    fn selected(installed: &[ModuleInfo], requirement: &str) -> Result<String> {
+        let installed: Vec<&ModuleInfo> = installed.iter().collect();
+        select(&installed, requirement).map(|m| m.version.clone())
+    }
+
+    #[test]
+    fn test_select_requirements() {
+        let installed: Vec<ModuleInfo> = ["1.0.0", "1.2.3", "1.3.0", "2.0.0-rc.1", "2.1.0"]
+            .into_iter()
+            .map(module)
+            .collect();
+
+        // Caret, including bare partial versions
+        assert_eq!(selected(&installed, "^1.2").unwrap(), "1.3.0");
+        assert_eq!(selected(&installed, "1").unwrap(), "1.3.0");
+        // Tilde
+        assert_eq!(selected(&installed, "~1.2.0").unwrap(), "1.2.3");
+        // Exact, and installed bare versions pin themselves
+        assert_eq!(selected(&installed, "=1.0.0").unwrap(), "1.0.0");
+        assert_eq!(selected(&installed, "1.0.0").unwrap(), "1.0.0");
+        // Wildcards skip pre-releases
+        assert_eq!(selected(&installed, "*").unwrap(), "2.1.0");
+        assert_eq!(selected(&installed, "").unwrap(), "2.1.0");
+        assert_eq!(selected(&installed, "1.*").unwrap(), "1.3.0");
+        assert_eq!(selected(&installed, ">=1.2, <2").unwrap(), "1.3.0");
+
+        match selected(&installed, "^3") {
+            Err(CompositionError::ModuleVersionNotFound(name, detail)) => {
+                assert_eq!(name, "indexer");
+                assert_eq!(
+                    detail,
+                    "^3 (available: 1.0.0, 1.2.3, 1.3.0, 2.0.0-rc.1, 2.1.0)"
+                );
+            }
+            other => panic!("expected version not found, got {:?}", other),
+        }
+        assert!(matches!(
+            selected(&installed, "^1.x.y"),
+            Err(CompositionError::InvalidConfiguration(_))
+        ));
+    }
+
+    #[test]
+    fn test_non_semver_versions() {
+        assert!(satisfies("nightly", "nightly").unwrap());
+        assert!(!satisfies("nightly", "*").unwrap());
+        assert!(satisfies("v1.4.0", "^1.2").unwrap());
+
+        assert_eq!(compare_versions("1.10.0", "1.9.2"), Ordering::Greater);
+        assert_eq!(compare_versions("1.0.0-rc.1", "1.0.0"), Ordering::Less);
+        assert_eq!(compare_versions("1.2", "1.2.0"), Ordering::Equal);
+    }
+}
@@ -0,0 +1,425 @@
+//! # C FFI
+//!
+//! C ABI over the governance primitives, so Go, C and other languages can
+//! sign and verify governance bundles without shelling out to the CLI.
+//!
+//! Public keys, signatures and multisig rosters cross the boundary as opaque
+//! handles. Every handle returned through an `out` pointer is owned by the
+//! caller and must be released exactly once with its `bllvm_*_free`
+//! function; handles passed *into* a function are only borrowed for the
+//! duration of the call. Handles are immutable, so they may be shared
+//! between threads as long as none of them frees a handle still in use.
+//!
+//! Fallible functions return a [`BllvmStatus`]. On failure nothing is
+//! written to `out` pointers, and [`bllvm_last_error_message`] describes
+//! the error.
+//!
+//! The header `bllvm.h` is generated by cbindgen during the build (see
+//! `build.rs`) into `$OUT_DIR` and `target/<profile>/include/`.
+
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::fmt;
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+use crate::governance::error::{ErrorCategory, GovernanceError};
+use crate::governance::signatures::{sign_message, verify_signature};
+use crate::governance::{GovernanceKeypair, Multisig, PublicKey, Signature};
+
+/// Result of an FFI call
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BllvmStatus {
+    /// The call succeeded
+    Ok = 0,
+    /// A required pointer argument was null
+    NullPointer = 1,
+    /// A string argument was not valid UTF-8
+    InvalidUtf8 = 2,
+    /// Key parsing or validation failed
+    KeyManagement = 3,
+    /// Signing, verification or signature parsing failed
+    SignatureOperations = 4,
+    /// Invalid multisig configuration or too few signatures
+    MultisigLogic = 5,
+    /// Malformed governance message
+    MessageFormat = 6,
+    /// Other malformed input
+    InvalidInput = 7,
+    /// The SDK panicked; this is a bug
+    Panic = 8,
+}
+
+impl From<ErrorCategory> for BllvmStatus {
+    fn from(category: ErrorCategory) -> Self {
+        match category {
+            ErrorCategory::KeyManagement => BllvmStatus::KeyManagement,
+            ErrorCategory::SignatureOperations => BllvmStatus::SignatureOperations,
+            ErrorCategory::MultisigLogic => BllvmStatus::MultisigLogic,
+            ErrorCategory::MessageFormat => BllvmStatus::MessageFormat,
+            ErrorCategory::InvalidInput => BllvmStatus::InvalidInput,
+        }
+    }
+}
+
+/// Opaque handle to a secp256k1 public key
+pub struct BllvmPublicKey(PublicKey);
+
+/// Opaque handle to a governance signature
+pub struct BllvmSignature(Signature);
+
+/// Opaque handle to a multisig roster
+pub struct BllvmMultisig(Multisig);
+
+/// Failure of an FFI call before it is reported as a status
+enum FfiError {
+    NullPointer(&'static str),
+    InvalidUtf8(&'static str),
+    Governance(GovernanceError),
+}
+
+impl FfiError {
+    fn status(&self) -> BllvmStatus {
+        match self {
+            FfiError::NullPointer(_) => BllvmStatus::NullPointer,
+            FfiError::InvalidUtf8(_) => BllvmStatus::InvalidUtf8,
+            FfiError::Governance(e) => e.category().into(),
+        }
+    }
+}
+
+impl fmt::Display for FfiError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FfiError::NullPointer(name) => write!(f, "Null pointer: {}", name),
+            FfiError::InvalidUtf8(name) => write!(f, "Invalid UTF-8: {}", name),
+            FfiError::Governance(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<GovernanceError> for FfiError {
+    fn from(e: GovernanceError) -> Self {
+        FfiError::Governance(e)
+    }
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Run an FFI body, recording its error for [`bllvm_last_error_message`]
+fn ffi_call<F: FnOnce() -> Result<(), FfiError>>(body: F) -> BllvmStatus {
+    let (status, message) = match panic::catch_unwind(AssertUnwindSafe(body)) {
+        Ok(Ok(())) => return BllvmStatus::Ok,
+        Ok(Err(e)) => (e.status(), e.to_string()),
+        Err(_) => (BllvmStatus::Panic, "Internal panic".to_string()),
+    };
+    let message = CString::new(message.replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|last| *last.borrow_mut() = Some(message));
+    status
+}
+
+unsafe fn borrow<'a, T>(ptr: *const T, name: &'static str) -> Result<&'a T, FfiError> {
+    ptr.as_ref().ok_or(FfiError::NullPointer(name))
+}
+
+/// `len` bytes at `ptr`; null is allowed for an empty buffer
+unsafe fn borrow_bytes<'a>(
+    ptr: *const u8,
+    len: usize,
+    name: &'static str,
+) -> Result<&'a [u8], FfiError> {
+    if len == 0 {
+        return Ok(&[]);
+    }
+    if ptr.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    Ok(std::slice::from_raw_parts(ptr, len))
+}
+
+unsafe fn borrow_str<'a>(ptr: *const c_char, name: &'static str) -> Result<&'a str, FfiError> {
+    if ptr.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    CStr::from_ptr(ptr)
+        .to_str()
+        .map_err(|_| FfiError::InvalidUtf8(name))
+}
+
+/// Borrow each handle of a `len`-element array of handle pointers
+unsafe fn borrow_handles<'a, T>(
+    ptr: *const *const T,
+    len: usize,
+    name: &'static str,
+) -> Result<Vec<&'a T>, FfiError> {
+    if len == 0 {
+        return Ok(Vec::new());
+    }
+    if ptr.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    std::slice::from_raw_parts(ptr, len)
+        .iter()
+        .map(|&handle| borrow(handle, name))
+        .collect()
+}
+
+unsafe fn write_out<T>(out: *mut T, value: T, name: &'static str) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    out.write(value);
+    Ok(())
+}
+
+/// Move `value` into a new handle owned by the caller
+unsafe fn write_handle<T>(out: *mut *mut T, value: T, name: &'static str) -> Result<(), FfiError> {
+    if out.is_null() {
+        return Err(FfiError::NullPointer(name));
+    }
+    out.write(Box::into_raw(Box::new(value)));
+    Ok(())
+}
+
+unsafe fn free_handle<T>(handle: *mut T) {
+    if !handle.is_null() {
+        drop(Box::from_raw(handle));
+    }
+}
+
+/// Message of the most recent failed call on this thread
+///
+/// Returns null if no call on this thread has failed. The string is owned
+/// by the SDK: do not free it. It stays valid until the next failing call
+/// on the same thread.
+#[no_mangle]
+pub extern "C" fn bllvm_last_error_message() -> *const c_char {
+    LAST_ERROR.with(|last| {
+        last.borrow()
+            .as_ref()
+            .map_or(ptr::null(), |message| message.as_ptr())
+    })
+}
+
+/// Parse a hex public key (33-byte compressed or 65-byte uncompressed)
+///
+/// # Safety
+///
+/// `hex` must be a NUL-terminated string, borrowed for the call. On success
+/// `*out` receives a new handle owned by the caller, to be released with
+/// [`bllvm_public_key_free`].
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_parse_public_key(
+    hex: *const c_char,
+    out: *mut *mut BllvmPublicKey,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let key: PublicKey = borrow_str(hex, "hex")?.parse()?;
+        write_handle(out, BllvmPublicKey(key), "out")
+    })
+}
+
+/// Release a public key handle; null is ignored
+///
+/// # Safety
+///
+/// `key` must be null or a handle from [`bllvm_parse_public_key`] that has
+/// not been freed yet. The handle must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_public_key_free(key: *mut BllvmPublicKey) {
+    free_handle(key)
+}
+
+/// Parse a hex 64-byte compact signature
+///
+/// # Safety
+///
+/// `hex` must be a NUL-terminated string, borrowed for the call. On success
+/// `*out` receives a new handle owned by the caller, to be released with
+/// [`bllvm_signature_free`].
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_parse_signature(
+    hex: *const c_char,
+    out: *mut *mut BllvmSignature,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let signature: Signature = borrow_str(hex, "hex")?.parse()?;
+        write_handle(out, BllvmSignature(signature), "out")
+    })
+}
+
+/// Hex encoding of a signature, as accepted by [`bllvm_parse_signature`]
+///
+/// # Safety
+///
+/// `signature` is borrowed for the call. On success `*out` receives a new
+/// NUL-terminated string owned by the caller, to be released with
+/// [`bllvm_string_free`] (not `free`).
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_signature_to_hex(
+    signature: *const BllvmSignature,
+    out: *mut *mut c_char,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let signature = borrow(signature, "signature")?;
+        if out.is_null() {
+            return Err(FfiError::NullPointer("out"));
+        }
+        let hex = CString::new(signature.0.to_string()).expect("hex contains no NUL");
+        out.write(hex.into_raw());
+        Ok(())
+    })
+}
+
+/// Release a signature handle; null is ignored
+///
+/// # Safety
+///
+/// `signature` must be null or a handle from [`bllvm_parse_signature`] or
+/// [`bllvm_sign_message`] that has not been freed yet. The handle must not
+/// be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_signature_free(signature: *mut BllvmSignature) {
+    free_handle(signature)
+}
+
+/// Release a string returned by the SDK; null is ignored
+///
+/// # Safety
+///
+/// `string` must be null or a string from [`bllvm_signature_to_hex`] that
+/// has not been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_string_free(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+/// Sign `message` with a 32-byte secret key
+///
+/// The message is signed as given; for governance messages pass their full
+/// signing bytes, including the signing domain.
+///
+/// # Safety
+///
+/// `secret_key` must point to 32 readable bytes and `message` to
+/// `message_len` readable bytes (it may be null if `message_len` is 0);
+/// both are borrowed for the call and the SDK keeps no copy of the key. On
+/// success `*out` receives a new handle owned by the caller, to be released
+/// with [`bllvm_signature_free`].
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_sign_message(
+    secret_key: *const u8,
+    message: *const u8,
+    message_len: usize,
+    out: *mut *mut BllvmSignature,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let secret_key = borrow_bytes(secret_key, 32, "secret_key")?;
+        let message = borrow_bytes(message, message_len, "message")?;
+        let keypair = GovernanceKeypair::from_secret_key(secret_key)?;
+        let signature = sign_message(&keypair.secret_key, message)?;
+        write_handle(out, BllvmSignature(signature), "out")
+    })
+}
+
+/// Check `signature` over `message` against `public_key`
+///
+/// A signature that does not match is not an error: the call returns
+/// `BLLVM_STATUS_OK` and writes `false` to `*out_valid`.
+///
+/// # Safety
+///
+/// `signature` and `public_key` are borrowed handles. `message` must point
+/// to `message_len` readable bytes (it may be null if `message_len` is 0).
+/// `out_valid` must point to a writable `bool`.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_verify_signature(
+    signature: *const BllvmSignature,
+    message: *const u8,
+    message_len: usize,
+    public_key: *const BllvmPublicKey,
+    out_valid: *mut bool,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let signature = borrow(signature, "signature")?;
+        let message = borrow_bytes(message, message_len, "message")?;
+        let public_key = borrow(public_key, "public_key")?;
+        let valid = verify_signature(&signature.0, message, &public_key.0)?;
+        write_out(out_valid, valid, "out_valid")
+    })
+}
+
+/// Create a `threshold`-of-`keys_len` multisig roster
+///
+/// # Safety
+///
+/// `keys` must point to `keys_len` public key handles. The array and the
+/// handles are borrowed for the call; the roster keeps its own copies, so
+/// the key handles may be freed afterwards. On success `*out` receives a
+/// new handle owned by the caller, to be released with
+/// [`bllvm_multisig_free`].
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_multisig_new(
+    threshold: usize,
+    keys: *const *const BllvmPublicKey,
+    keys_len: usize,
+    out: *mut *mut BllvmMultisig,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let keys = borrow_handles(keys, keys_len, "keys")?
+            .into_iter()
+            .map(|key| key.0.clone())
+            .collect();
+        let multisig = Multisig::new(threshold, keys_len, keys)?;
+        write_handle(out, BllvmMultisig(multisig), "out")
+    })
+}
+
+/// Release a multisig handle; null is ignored
+///
+/// # Safety
+///
+/// `multisig` must be null or a handle from [`bllvm_multisig_new`] that has
+/// not been freed yet. The handle must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_multisig_free(multisig: *mut BllvmMultisig) {
+    free_handle(multisig)
+}
+
+/// Check whether `signatures` over `message` meet the roster's threshold
+///
+/// Writes `false` to `*out_valid` if too few of the signatures are valid
+/// roster signatures. Passing fewer signatures than the threshold fails
+/// with `BLLVM_STATUS_MULTISIG_LOGIC`.
+///
+/// # Safety
+///
+/// `multisig` is a borrowed handle. `message` must point to `message_len`
+/// readable bytes (it may be null if `message_len` is 0). `signatures` must
+/// point to `signatures_len` signature handles, borrowed for the call.
+/// `out_valid` must point to a writable `bool`.
+#[no_mangle]
+pub unsafe extern "C" fn bllvm_multisig_verify(
+    multisig: *const BllvmMultisig,
+    message: *const u8,
+    message_len: usize,
+    signatures: *const *const BllvmSignature,
+    signatures_len: usize,
+    out_valid: *mut bool,
+) -> BllvmStatus {
+    ffi_call(|| {
+        let multisig = borrow(multisig, "multisig")?;
+        let message = borrow_bytes(message, message_len, "message")?;
+        let signatures: Vec<Signature> = borrow_handles(signatures, signatures_len, "signatures")?
+            .into_iter()
+            .map(|signature| signature.0.clone())
+            .collect();
+        let valid = multisig.0.verify(message, &signatures)?;
+        write_out(out_valid, valid, "out_valid")
+    })
+}
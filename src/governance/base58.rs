@@ -0,0 +1,115 @@
+//! Base58 and Base58Check Encoding
+//!
+//! Bitcoin's Base58 alphabet, used by WIF keys, extended key strings,
+//! legacy addresses, and BIP38. Base58Check appends the first four bytes
+//! of SHA256(SHA256(payload)) before encoding.
+//!
+//! Leading zero bytes are encoded as leading '1' characters.
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::sha256d;
+
+/// Bitcoin Base58 alphabet (no 0, O, I, or l)
+const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Number of checksum bytes appended by Base58Check
+const CHECKSUM_LENGTH: usize = 4;
+
+/// Encode bytes as Base58 without a checksum
+pub fn encode(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+    // Base58 digits, least significant first
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut encoded = String::with_capacity(zeros + digits.len());
+    encoded.extend(std::iter::repeat_n('1', zeros));
+    encoded.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    encoded
+}
+
+/// Decode a Base58 string without a checksum
+///
+/// Fails with `InvalidBase58Character` at the first character outside
+/// the alphabet.
+pub fn decode(s: &str) -> GovernanceResult<Vec<u8>> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+
+    // Decoded bytes, least significant first
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+    for (position, character) in s.chars().enumerate().skip(zeros) {
+        let mut carry = u8::try_from(character)
+            .ok()
+            .and_then(|c| ALPHABET.iter().position(|&a| a == c))
+            .ok_or(GovernanceError::InvalidBase58Character {
+                character,
+                position,
+            })? as u32;
+        for byte in bytes.iter_mut() {
+            carry += (*byte as u32) * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut decoded = vec![0u8; zeros];
+    decoded.extend(bytes.iter().rev());
+    Ok(decoded)
+}
+
+/// Encode a payload as Base58Check
+pub fn encode_check(payload: &[u8]) -> String {
+    let mut data = Vec::with_capacity(payload.len() + CHECKSUM_LENGTH);
+    data.extend_from_slice(payload);
+    data.extend_from_slice(&sha256d(payload)[..CHECKSUM_LENGTH]);
+    encode(&data)
+}
+
+/// Decode a Base58Check string, returning the payload
+///
+/// Fails with `ChecksumMismatch` if the checksum does not match the payload.
+pub fn decode_check(s: &str) -> GovernanceResult<Vec<u8>> {
+    let mut data = decode(s)?;
+    if data.len() < CHECKSUM_LENGTH {
+        return Err(GovernanceError::InvalidInput(format!(
+            "Base58Check data too short ({} bytes)",
+            data.len()
+        )));
+    }
+    let checksum = data.split_off(data.len() - CHECKSUM_LENGTH);
+    if checksum != sha256d(&data)[..CHECKSUM_LENGTH] {
+        return Err(GovernanceError::ChecksumMismatch(
+            "Base58Check checksum does not match payload".to_string(),
+        ));
+    }
+    Ok(data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_check_roundtrip() {
+        let payload = [0x00, 0x00, 0x01, 0x02, 0xff];
+        let encoded = encode_check(&payload);
+        assert!(encoded.starts_with("11"));
+        assert_eq!(decode_check(&encoded).unwrap(), payload);
+    }
+}
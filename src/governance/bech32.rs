@@ -0,0 +1,310 @@
+//! Bech32 and Bech32m Encoding
+//!
+//! Specifications:
+//! - https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! - https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+//!
+//! Data is handled as 5-bit groups; use [`convert_bits`] to move between
+//! bytes and groups. Segwit addresses use bech32 for witness version 0 and
+//! bech32m for versions 1 through 16.
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+
+/// Data character set, indexed by 5-bit value
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Generator coefficients of the BCH checksum
+const GENERATOR: [u32; 5] = [0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3];
+
+/// Checksum constant for bech32
+const BECH32_CONST: u32 = 1;
+
+/// Checksum constant for bech32m
+const BECH32M_CONST: u32 = 0x2bc830a3;
+
+/// Number of checksum characters
+const CHECKSUM_LENGTH: usize = 6;
+
+/// Maximum length of an encoded string
+pub const MAX_LENGTH: usize = 90;
+
+/// Checksum variant
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Variant {
+    /// BIP173 bech32 (segwit version 0)
+    Bech32,
+    /// BIP350 bech32m (segwit version 1 and later)
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => BECH32_CONST,
+            Variant::Bech32m => BECH32M_CONST,
+        }
+    }
+}
+
+/// Encode a human-readable part and 5-bit data with a checksum
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> GovernanceResult<String> {
+    check_hrp(hrp)?;
+    if hrp.bytes().any(|c| c.is_ascii_uppercase()) && hrp.bytes().any(|c| c.is_ascii_lowercase()) {
+        return Err(invalid("Mixed-case human-readable part"));
+    }
+    if let Some(&value) = data.iter().find(|&&value| value >= 32) {
+        return Err(invalid(&format!("Data value {} is not 5 bits", value)));
+    }
+    if hrp.len() + 1 + data.len() + CHECKSUM_LENGTH > MAX_LENGTH {
+        return Err(invalid("Encoded string exceeds 90 characters"));
+    }
+
+    let hrp = hrp.to_ascii_lowercase();
+    let checksum = create_checksum(hrp.as_bytes(), data, variant);
+    let mut encoded = String::with_capacity(hrp.len() + 1 + data.len() + CHECKSUM_LENGTH);
+    encoded.push_str(&hrp);
+    encoded.push('1');
+    for &value in data.iter().chain(checksum.iter()) {
+        encoded.push(CHARSET[value as usize] as char);
+    }
+    Ok(encoded)
+}
+
+/// Decode a bech32 or bech32m string
+///
+/// Returns the lowercase human-readable part, the 5-bit data without the
+/// checksum, and the checksum variant. Mixed-case strings are rejected.
+pub fn decode(s: &str) -> GovernanceResult<(String, Vec<u8>, Variant)> {
+    if s.len() > MAX_LENGTH {
+        return Err(invalid("Encoded string exceeds 90 characters"));
+    }
+    if s.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(invalid("Character out of range"));
+    }
+    if s.bytes().any(|c| c.is_ascii_uppercase()) && s.bytes().any(|c| c.is_ascii_lowercase()) {
+        return Err(invalid("Mixed-case string"));
+    }
+
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or_else(|| invalid("Missing separator"))?;
+    let (hrp, data_part) = (&s[..separator], &s[separator + 1..]);
+    if hrp.is_empty() {
+        return Err(invalid("Empty human-readable part"));
+    }
+    if data_part.len() < CHECKSUM_LENGTH {
+        return Err(invalid("Checksum too short"));
+    }
+
+    let data = data_part
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&d| d == c)
+                .map(|value| value as u8)
+                .ok_or_else(|| invalid(&format!("Invalid data character '{}'", c as char)))
+        })
+        .collect::<GovernanceResult<Vec<u8>>>()?;
+
+    let variant = match polymod_hrp(hrp.as_bytes(), &data) {
+        BECH32_CONST => Variant::Bech32,
+        BECH32M_CONST => Variant::Bech32m,
+        _ => return Err(invalid("Invalid checksum")),
+    };
+
+    let payload = data[..data.len() - CHECKSUM_LENGTH].to_vec();
+    Ok((hrp.to_string(), payload, variant))
+}
+
+/// Regroup bits, e.g. 8-bit bytes into 5-bit groups and back
+///
+/// With `pad`, a final partial group is zero-padded. Without it, leftover
+/// bits must be fewer than `from` and all zero, as segwit decoding requires.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> GovernanceResult<Vec<u8>> {
+    let mut acc: u32 = 0;
+    let mut bits: u32 = 0;
+    let max_value: u32 = (1 << to) - 1;
+    let mut result = Vec::with_capacity(data.len() * from as usize / to as usize + 1);
+
+    for &value in data {
+        let value = value as u32;
+        if value >> from != 0 {
+            return Err(invalid(&format!("Value {} exceeds {} bits", value, from)));
+        }
+        acc = (acc << from) | value;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            result.push(((acc >> bits) & max_value) as u8);
+        }
+    }
+
+    if pad {
+        if bits > 0 {
+            result.push(((acc << (to - bits)) & max_value) as u8);
+        }
+    } else if bits >= from {
+        return Err(invalid("Excess padding"));
+    } else if (acc << (to - bits)) & max_value != 0 {
+        return Err(invalid("Non-zero padding"));
+    }
+
+    Ok(result)
+}
+
+/// Encode a segwit address (BIP173 for version 0, BIP350 for 1 and later)
+pub fn encode_segwit_address(
+    hrp: &str,
+    witness_version: u8,
+    program: &[u8],
+) -> GovernanceResult<String> {
+    check_witness_program(witness_version, program)?;
+    let variant = segwit_variant(witness_version);
+    let mut data = vec![witness_version];
+    data.extend(convert_bits(program, 8, 5, true)?);
+    encode(hrp, &data, variant)
+}
+
+/// Decode a segwit address for the expected human-readable part
+///
+/// Returns the witness version and program. The checksum variant must
+/// match the witness version.
+pub fn decode_segwit_address(hrp: &str, address: &str) -> GovernanceResult<(u8, Vec<u8>)> {
+    let (decoded_hrp, data, variant) = decode(address)?;
+    if decoded_hrp != hrp.to_ascii_lowercase() {
+        return Err(invalid(&format!(
+            "Expected human-readable part '{}', got '{}'",
+            hrp, decoded_hrp
+        )));
+    }
+
+    let (&witness_version, program) = data
+        .split_first()
+        .ok_or_else(|| invalid("Empty data section"))?;
+    if witness_version > 16 {
+        return Err(invalid(&format!(
+            "Invalid witness version {}",
+            witness_version
+        )));
+    }
+    if variant != segwit_variant(witness_version) {
+        return Err(invalid(&format!(
+            "Witness version {} requires {:?}",
+            witness_version,
+            segwit_variant(witness_version)
+        )));
+    }
+
+    let program = convert_bits(program, 5, 8, false)?;
+    check_witness_program(witness_version, &program)?;
+    Ok((witness_version, program))
+}
+
+/// Checksum variant required for a witness version
+fn segwit_variant(witness_version: u8) -> Variant {
+    if witness_version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    }
+}
+
+/// Check witness version and program length limits
+fn check_witness_program(witness_version: u8, program: &[u8]) -> GovernanceResult<()> {
+    if witness_version > 16 {
+        return Err(invalid(&format!(
+            "Invalid witness version {}",
+            witness_version
+        )));
+    }
+    if !(2..=40).contains(&program.len()) {
+        return Err(invalid(&format!(
+            "Invalid witness program length {}",
+            program.len()
+        )));
+    }
+    if witness_version == 0 && program.len() != 20 && program.len() != 32 {
+        return Err(invalid(&format!(
+            "Invalid version 0 witness program length {}",
+            program.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Check the human-readable part length and characters
+fn check_hrp(hrp: &str) -> GovernanceResult<()> {
+    if hrp.is_empty() || hrp.len() > 83 {
+        return Err(invalid("Human-readable part must be 1 to 83 characters"));
+    }
+    if hrp.bytes().any(|c| !(33..=126).contains(&c)) {
+        return Err(invalid("Character out of range"));
+    }
+    Ok(())
+}
+
+fn create_checksum(hrp: &[u8], data: &[u8], variant: Variant) -> [u8; CHECKSUM_LENGTH] {
+    let mut values = data.to_vec();
+    values.extend_from_slice(&[0; CHECKSUM_LENGTH]);
+    let polymod = polymod_hrp(hrp, &values) ^ variant.constant();
+    let mut checksum = [0u8; CHECKSUM_LENGTH];
+    for (i, value) in checksum.iter_mut().enumerate() {
+        *value = ((polymod >> (5 * (5 - i))) & 31) as u8;
+    }
+    checksum
+}
+
+/// Checksum polymod over the expanded human-readable part and data
+fn polymod_hrp(hrp: &[u8], data: &[u8]) -> u32 {
+    let expanded = hrp
+        .iter()
+        .map(|c| c >> 5)
+        .chain(std::iter::once(0))
+        .chain(hrp.iter().map(|c| c & 31))
+        .chain(data.iter().copied());
+    polymod(expanded)
+}
+
+fn polymod(values: impl Iterator<Item = u8>) -> u32 {
+    let mut chk: u32 = 1;
+    for value in values {
+        let top = chk >> 25;
+        chk = ((chk & 0x1ffffff) << 5) ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn invalid(message: &str) -> GovernanceError {
+    GovernanceError::InvalidInput(format!("Bech32: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_decode_roundtrip() {
+        for variant in [Variant::Bech32, Variant::Bech32m] {
+            let data = convert_bits(b"governance", 8, 5, true).unwrap();
+            let encoded = encode("test", &data, variant).unwrap();
+            let (hrp, decoded, decoded_variant) = decode(&encoded).unwrap();
+            assert_eq!(hrp, "test");
+            assert_eq!(decoded, data);
+            assert_eq!(decoded_variant, variant);
+            assert_eq!(convert_bits(&decoded, 5, 8, false).unwrap(), b"governance");
+        }
+    }
+
+    #[test]
+    fn test_encode_rejects_bad_input() {
+        assert!(encode("", &[0], Variant::Bech32).is_err());
+        assert!(encode("Ab", &[0], Variant::Bech32).is_err());
+        assert!(encode("a", &[32], Variant::Bech32).is_err());
+        assert!(encode("a", &[0; 83], Variant::Bech32).is_err());
+    }
+}
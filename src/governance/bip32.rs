@@ -6,14 +6,42 @@
 //!
 //! Key derivation path format: m/purpose'/coin_type'/account'/change/address_index
 //! Example: m/44'/0'/0'/0/0 (BIP44 standard path for Bitcoin mainnet first address)
-
+//!
+//! ## Invalid child keys
+//!
+//! Child derivation fails if IL >= n or the derived key is zero, which
+//! happens with probability below 1 in 2^127. BIP32 says to proceed with the
+//! next index: `derive_child_private` returns `GovernanceError::InvalidChildIndex`
+//! so callers can skip, and `derive_child_private_skipping` does so itself.
+//! An invalid master key means the seed is invalid and is reported as
+//! `GovernanceError::InvalidKey`.
+
+use crate::governance::base58;
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::network::NetworkType;
+use crate::governance::signatures::hash160;
 use hmac::{Hmac, Mac};
 use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey};
 use sha2::Sha512;
+use std::fmt;
 
 type HmacSha512 = Hmac<Sha512>;
 
+/// Offset applied to hardened child indices
+pub const HARDENED_OFFSET: u32 = 0x8000_0000;
+
+/// Version bytes of a mainnet extended private key ("xprv")
+pub const XPRV_VERSION_MAINNET: [u8; 4] = [0x04, 0x88, 0xad, 0xe4];
+
+/// Version bytes of a testnet extended private key ("tprv")
+pub const XPRV_VERSION_TESTNET: [u8; 4] = [0x04, 0x35, 0x83, 0x94];
+
+/// Version bytes of a mainnet extended public key ("xpub")
+pub const XPUB_VERSION_MAINNET: [u8; 4] = [0x04, 0x88, 0xb2, 0x1e];
+
+/// Version bytes of a testnet extended public key ("tpub")
+pub const XPUB_VERSION_TESTNET: [u8; 4] = [0x04, 0x35, 0x87, 0xcf];
+
 /// Extended private key (xprv)
 #[derive(Debug, Clone)]
 pub struct ExtendedPrivateKey {
@@ -44,6 +72,69 @@ pub struct ExtendedPublicKey {
     pub public_key: PublicKey,
 }
 
+/// Suffix used to mark hardened path components
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HardenedStyle {
+    /// `44'`
+    #[default]
+    Apostrophe,
+    /// `44h`
+    LowerH,
+    /// `44H`
+    UpperH,
+}
+
+impl HardenedStyle {
+    /// Suffix character for this style
+    pub fn suffix(&self) -> char {
+        match self {
+            HardenedStyle::Apostrophe => '\'',
+            HardenedStyle::LowerH => 'h',
+            HardenedStyle::UpperH => 'H',
+        }
+    }
+
+    /// Style of a path component, or `None` if it is not hardened
+    pub fn of_component(component: &str) -> Option<Self> {
+        match component.chars().last()? {
+            '\'' => Some(HardenedStyle::Apostrophe),
+            'h' => Some(HardenedStyle::LowerH),
+            'H' => Some(HardenedStyle::UpperH),
+            _ => None,
+        }
+    }
+}
+
+/// Parse one derivation path component (e.g. `0`, `44'`, `44h`, `44H`)
+///
+/// Hardened components are returned with `HARDENED_OFFSET` set.
+pub fn parse_child_index(s: &str) -> GovernanceResult<u32> {
+    let invalid = || GovernanceError::InvalidInput(format!("Invalid child index '{}'", s));
+    let hardened = HardenedStyle::of_component(s).is_some();
+    let digits = if hardened { &s[..s.len() - 1] } else { s };
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(invalid());
+    }
+    let index: u32 = digits.parse().map_err(|_| invalid())?;
+    if index >= HARDENED_OFFSET {
+        return Err(invalid());
+    }
+    Ok(if hardened {
+        index | HARDENED_OFFSET
+    } else {
+        index
+    })
+}
+
+/// Format a child index as a path component, marking hardened indices with `style`
+pub fn format_child_index(index: u32, style: HardenedStyle) -> String {
+    if index >= HARDENED_OFFSET {
+        format!("{}{}", index - HARDENED_OFFSET, style.suffix())
+    } else {
+        index.to_string()
+    }
+}
+
 /// Derive master key from seed
 ///
 /// BIP32: I = HMAC-SHA512(Key = "Bitcoin seed", Data = seed)
@@ -103,6 +194,7 @@ pub fn derive_child_private(
     parent: &ExtendedPrivateKey,
     child_number: u32,
 ) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
+    let depth = child_depth(parent.depth)?;
     let secp = Secp256k1::new();
     let is_hardened = child_number >= 0x80000000;
 
@@ -121,9 +213,7 @@ pub fn derive_child_private(
 
     data.extend_from_slice(&child_number.to_be_bytes());
 
-    // Calculate parent fingerprint (first 4 bytes of RIPEMD160(SHA256(parent_pubkey)))
-    let parent_pubkey = parent.private_key.public_key(&secp);
-    let parent_fingerprint = calculate_fingerprint(&parent_pubkey.serialize());
+    let parent_fingerprint = parent.fingerprint();
 
     // HMAC-SHA512(chain_code, data)
     let mut hmac = HmacSha512::new_from_slice(&parent.chain_code)
@@ -144,18 +234,20 @@ pub fn derive_child_private(
     // BIP32: child_key = (IL + parent_key) mod n
     // IL is interpreted as a 256-bit integer (may be >= curve order, will be reduced mod n)
     // Convert IL to Scalar (this handles modulo curve order automatically)
-    let il_scalar = Scalar::from_be_bytes(il)
-        .map_err(|_| GovernanceError::InvalidKey("IL cannot be converted to scalar".to_string()))?;
+    // IL >= n or a zero child key: BIP32 proceeds with the next index
+    let il_scalar =
+        Scalar::from_be_bytes(il).map_err(|_| GovernanceError::InvalidChildIndex(child_number))?;
 
     // Add IL scalar to parent private key using add_tweak
-    let child_private = parent.private_key.add_tweak(&il_scalar).map_err(|_| {
-        GovernanceError::InvalidKey("Key addition resulted in zero or invalid key".to_string())
-    })?;
+    let child_private = parent
+        .private_key
+        .add_tweak(&il_scalar)
+        .map_err(|_| GovernanceError::InvalidChildIndex(child_number))?;
 
     let child_public = child_private.public_key(&secp);
 
     let child_xprv = ExtendedPrivateKey {
-        depth: parent.depth + 1,
+        depth,
         parent_fingerprint,
         child_number,
         chain_code: child_chain_code,
@@ -163,7 +255,7 @@ pub fn derive_child_private(
     };
 
     let child_xpub = ExtendedPublicKey {
-        depth: parent.depth + 1,
+        depth,
         parent_fingerprint,
         child_number,
         chain_code: child_chain_code,
@@ -173,6 +265,38 @@ pub fn derive_child_private(
     Ok((child_xprv, child_xpub))
 }
 
+/// Depth of a child key; a parent parsed at depth 255 has no valid children
+fn child_depth(parent_depth: u8) -> GovernanceResult<u8> {
+    parent_depth.checked_add(1).ok_or_else(|| {
+        GovernanceError::InvalidKey(format!(
+            "Cannot derive a child of a key at depth {}",
+            parent_depth
+        ))
+    })
+}
+
+/// Derive a child private key, moving to the next index if `child_number` is invalid
+///
+/// Returns the keys for the first valid index at or after `child_number`;
+/// the index actually used is their `child_number`. Never crosses from
+/// non-hardened into hardened indices.
+pub fn derive_child_private_skipping(
+    parent: &ExtendedPrivateKey,
+    child_number: u32,
+) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
+    let mut index = child_number;
+    loop {
+        match derive_child_private(parent, index) {
+            Err(GovernanceError::InvalidChildIndex(_))
+                if index != HARDENED_OFFSET - 1 && index != u32::MAX =>
+            {
+                index += 1;
+            }
+            result => return result,
+        }
+    }
+}
+
 /// Derive child public key from parent public key (non-hardened only)
 ///
 /// Note: Hardened derivation requires the private key and cannot be done from public key alone
@@ -186,13 +310,14 @@ pub fn derive_child_public(
         ));
     }
 
+    let depth = child_depth(parent.depth)?;
+
     // Prepare data: parent_public_key || child_number (4 bytes, big-endian)
     let mut data = Vec::with_capacity(37);
     data.extend_from_slice(&parent.public_key.serialize());
     data.extend_from_slice(&child_number.to_be_bytes());
 
-    // Calculate parent fingerprint
-    let parent_fingerprint = calculate_fingerprint(&parent.public_key.serialize());
+    let parent_fingerprint = parent.fingerprint();
 
     // HMAC-SHA512(chain_code, data)
     let mut hmac = HmacSha512::new_from_slice(&parent.chain_code)
@@ -211,8 +336,8 @@ pub fn derive_child_public(
     // Add IL to parent public key (elliptic curve point addition)
     // BIP32: child_pubkey = parent_pubkey + IL * G (where G is generator)
     // Convert IL to scalar
-    let il_scalar = Scalar::from_be_bytes(il)
-        .map_err(|_| GovernanceError::InvalidKey("Invalid scalar".to_string()))?;
+    let il_scalar =
+        Scalar::from_be_bytes(il).map_err(|_| GovernanceError::InvalidChildIndex(child_number))?;
 
     // Add il_scalar * G to parent public key using add_exp_tweak
     // This computes: parent_pubkey + (il_scalar * G)
@@ -220,12 +345,10 @@ pub fn derive_child_public(
     let child_public = parent
         .public_key
         .add_exp_tweak(&secp, &il_scalar)
-        .map_err(|_| GovernanceError::InvalidKey("Point addition failed".to_string()))?;
-
-    let parent_fingerprint = calculate_fingerprint(&parent.public_key.serialize());
+        .map_err(|_| GovernanceError::InvalidChildIndex(child_number))?;
 
     Ok(ExtendedPublicKey {
-        depth: parent.depth + 1,
+        depth,
         parent_fingerprint,
         child_number,
         chain_code: child_chain_code,
@@ -233,24 +356,10 @@ pub fn derive_child_public(
     })
 }
 
-/// Calculate key fingerprint (first 4 bytes of RIPEMD160(SHA256(pubkey)))
-fn calculate_fingerprint(pubkey: &[u8]) -> [u8; 4] {
-    use ripemd::{Digest as RipemdDigest, Ripemd160};
-    use sha2::{Digest, Sha256};
-
-    // SHA256(pubkey)
-    let mut sha256 = Sha256::new();
-    sha256.update(pubkey);
-    let sha256_hash = sha256.finalize();
-
-    // RIPEMD160(SHA256(pubkey))
-    let mut ripemd = Ripemd160::new();
-    ripemd.update(&sha256_hash);
-    let ripemd_hash = ripemd.finalize();
-
-    // First 4 bytes
+/// First 4 bytes of a key identifier
+fn fingerprint_of(identifier: [u8; 20]) -> [u8; 4] {
     let mut fingerprint = [0u8; 4];
-    fingerprint.copy_from_slice(&ripemd_hash[..4]);
+    fingerprint.copy_from_slice(&identifier[..4]);
     fingerprint
 }
 
@@ -275,10 +384,92 @@ impl ExtendedPrivateKey {
         derive_child_private(self, child_number)
     }
 
+    /// Key identifier: HASH160 of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        self.to_extended_public().identifier()
+    }
+
+    /// Key fingerprint: first 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint_of(self.identifier())
+    }
+
     /// Get private key bytes
     pub fn private_key_bytes(&self) -> [u8; 32] {
         self.private_key.secret_bytes()
     }
+
+    /// Serialize to the 78-byte BIP32 format
+    ///
+    /// `version(4) || depth(1) || parent_fingerprint(4) || child_number(4) ||
+    /// chain_code(32) || 0x00 || private_key(32)`. Testnet and regtest share
+    /// the "tprv" version bytes.
+    pub fn to_bytes(&self, network: NetworkType) -> [u8; 78] {
+        let version = match network {
+            NetworkType::Mainnet => XPRV_VERSION_MAINNET,
+            NetworkType::Testnet | NetworkType::Regtest => XPRV_VERSION_TESTNET,
+        };
+        let mut bytes = [0u8; 78];
+        bytes[..4].copy_from_slice(&version);
+        bytes[4] = self.depth;
+        bytes[5..9].copy_from_slice(&self.parent_fingerprint);
+        bytes[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+        bytes[13..45].copy_from_slice(&self.chain_code);
+        bytes[46..].copy_from_slice(&self.private_key.secret_bytes());
+        bytes
+    }
+
+    /// Encode as a Base58Check "xprv" (mainnet) or "tprv" (testnet, regtest) string
+    pub fn to_xprv_string(&self, network: NetworkType) -> String {
+        base58::encode_check(&self.to_bytes(network))
+    }
+
+    /// Parse a Base58Check "xprv" or "tprv" string
+    pub fn from_xprv_string(xprv: &str) -> GovernanceResult<Self> {
+        let bytes = base58::decode_check(xprv.trim()).map_err(|e| {
+            GovernanceError::InvalidKey(format!("Invalid extended private key: {}", e))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse the 78-byte BIP32 format (mainnet or testnet version bytes)
+    pub fn from_bytes(bytes: &[u8]) -> GovernanceResult<Self> {
+        let header = ExtendedKeyHeader::parse(
+            bytes,
+            &[XPRV_VERSION_MAINNET, XPRV_VERSION_TESTNET],
+            "private",
+        )?;
+        if bytes[45] != 0x00 {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Extended private key data must start with 0x00, got 0x{:02x}",
+                bytes[45]
+            )));
+        }
+        let private_key = SecretKey::from_slice(&bytes[46..])
+            .map_err(|e| GovernanceError::InvalidKey(format!("Invalid private key: {}", e)))?;
+
+        Ok(ExtendedPrivateKey {
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            chain_code: header.chain_code,
+            private_key,
+        })
+    }
+}
+
+impl fmt::Display for ExtendedPublicKey {
+    /// Short form for logs: `xpub[<fingerprint hex>:<depth>]`
+    ///
+    /// Use [`ExtendedPublicKey::to_xpub_string`] for the full encoding.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "xpub[{}:{}]",
+            hex::encode(self.fingerprint()),
+            self.depth
+        )
+    }
 }
 
 impl ExtendedPublicKey {
@@ -291,6 +482,116 @@ impl ExtendedPublicKey {
     pub fn public_key_bytes(&self) -> [u8; 33] {
         self.public_key.serialize()
     }
+
+    /// Key identifier: HASH160 of the compressed public key
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.public_key_bytes())
+    }
+
+    /// Key fingerprint: first 4 bytes of the identifier
+    pub fn fingerprint(&self) -> [u8; 4] {
+        fingerprint_of(self.identifier())
+    }
+
+    /// Serialize to the 78-byte BIP32 format
+    ///
+    /// `version(4) || depth(1) || parent_fingerprint(4) || child_number(4) ||
+    /// chain_code(32) || public_key(33)`. Testnet and regtest share the
+    /// "tpub" version bytes.
+    pub fn to_bytes(&self, network: NetworkType) -> [u8; 78] {
+        let version = match network {
+            NetworkType::Mainnet => XPUB_VERSION_MAINNET,
+            NetworkType::Testnet | NetworkType::Regtest => XPUB_VERSION_TESTNET,
+        };
+        let mut bytes = [0u8; 78];
+        bytes[..4].copy_from_slice(&version);
+        bytes[4] = self.depth;
+        bytes[5..9].copy_from_slice(&self.parent_fingerprint);
+        bytes[9..13].copy_from_slice(&self.child_number.to_be_bytes());
+        bytes[13..45].copy_from_slice(&self.chain_code);
+        bytes[45..].copy_from_slice(&self.public_key.serialize());
+        bytes
+    }
+
+    /// Encode as a Base58Check "xpub" (mainnet) or "tpub" (testnet, regtest) string
+    pub fn to_xpub_string(&self, network: NetworkType) -> String {
+        base58::encode_check(&self.to_bytes(network))
+    }
+
+    /// Parse a Base58Check "xpub" or "tpub" string
+    pub fn from_xpub_string(xpub: &str) -> GovernanceResult<Self> {
+        let bytes = base58::decode_check(xpub.trim()).map_err(|e| {
+            GovernanceError::InvalidKey(format!("Invalid extended public key: {}", e))
+        })?;
+        Self::from_bytes(&bytes)
+    }
+
+    /// Parse the 78-byte BIP32 format (mainnet or testnet version bytes)
+    pub fn from_bytes(bytes: &[u8]) -> GovernanceResult<Self> {
+        let header = ExtendedKeyHeader::parse(
+            bytes,
+            &[XPUB_VERSION_MAINNET, XPUB_VERSION_TESTNET],
+            "public",
+        )?;
+        let public_key = PublicKey::from_slice(&bytes[45..])
+            .map_err(|e| GovernanceError::InvalidKey(format!("Invalid public key: {}", e)))?;
+
+        Ok(ExtendedPublicKey {
+            depth: header.depth,
+            parent_fingerprint: header.parent_fingerprint,
+            child_number: header.child_number,
+            chain_code: header.chain_code,
+            public_key,
+        })
+    }
+}
+
+/// Fields shared by serialized extended private and public keys
+struct ExtendedKeyHeader {
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+}
+
+impl ExtendedKeyHeader {
+    /// Check the length and version of a 78-byte extended key and read its header
+    fn parse(bytes: &[u8], versions: &[[u8; 4]], kind: &str) -> GovernanceResult<Self> {
+        if bytes.len() != 78 {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Extended {} key must be 78 bytes, got {}",
+                kind,
+                bytes.len()
+            )));
+        }
+        if !versions.iter().any(|version| bytes[..4] == version[..]) {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Unknown extended {} key version {}",
+                kind,
+                hex::encode(&bytes[..4])
+            )));
+        }
+
+        let depth = bytes[4];
+        let mut parent_fingerprint = [0u8; 4];
+        parent_fingerprint.copy_from_slice(&bytes[5..9]);
+        let child_number = u32::from_be_bytes([bytes[9], bytes[10], bytes[11], bytes[12]]);
+        if depth == 0 && (parent_fingerprint != [0u8; 4] || child_number != 0) {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Master extended {} key has a parent",
+                kind
+            )));
+        }
+        let mut chain_code = [0u8; 32];
+        chain_code.copy_from_slice(&bytes[13..45]);
+
+        Ok(Self {
+            depth,
+            parent_fingerprint,
+            child_number,
+            chain_code,
+        })
+    }
 }
 
 #[cfg(test)]
@@ -309,6 +610,23 @@ mod tests {
         assert_eq!(xpub.child_number, 0);
     }
 
+    #[test]
+    fn test_max_depth_xpub_has_no_children() {
+        let (_, master_xpub) = derive_master_key(b"test seed for BIP32").unwrap();
+        let mut bytes = master_xpub.to_bytes(NetworkType::Mainnet);
+        bytes[4] = 255;
+        bytes[5..9].copy_from_slice(&[1, 2, 3, 4]);
+        let deep = ExtendedPublicKey::from_bytes(&bytes).unwrap();
+        let deep = ExtendedPublicKey::from_xpub_string(&deep.to_xpub_string(NetworkType::Mainnet))
+            .unwrap();
+        assert_eq!(deep.depth, 255);
+
+        assert!(matches!(
+            derive_child_public(&deep, 0),
+            Err(GovernanceError::InvalidKey(_))
+        ));
+    }
+
     #[test]
     fn test_child_derivation() {
         let seed = b"test seed for BIP32";
@@ -327,6 +645,11 @@ mod tests {
             derived_xpub.public_key_bytes(),
             child_xpub.public_key_bytes()
         );
+
+        assert_eq!(
+            child_xpub.to_string(),
+            format!("xpub[{}:1]", hex::encode(child_xpub.fingerprint()))
+        );
     }
 
     #[test]
@@ -341,4 +664,58 @@ mod tests {
         assert_eq!(hardened_xprv.child_number, hardened_index);
         assert!(hardened_xprv.child_number >= 0x80000000);
     }
+
+    #[test]
+    fn test_xpub_bytes_roundtrip() {
+        let (master_xprv, _) = derive_master_key(b"test seed for serialization").unwrap();
+        let (_, child_xpub) = master_xprv.derive_child(0x80000001).unwrap();
+
+        let bytes = child_xpub.to_bytes(NetworkType::Testnet);
+        assert_eq!(bytes[..4], XPUB_VERSION_TESTNET);
+        let parsed = ExtendedPublicKey::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.to_bytes(NetworkType::Testnet), bytes);
+
+        assert!(ExtendedPublicKey::from_bytes(&bytes[..77]).is_err());
+        let mut bad_version = bytes;
+        bad_version[0] = 0xff;
+        assert!(ExtendedPublicKey::from_bytes(&bad_version).is_err());
+    }
+
+    #[test]
+    fn test_child_index_notation() {
+        for s in ["44'", "44h", "44H"] {
+            assert_eq!(parse_child_index(s).unwrap(), 44 | HARDENED_OFFSET);
+        }
+        assert_eq!(parse_child_index("7").unwrap(), 7);
+        for s in ["", "'", "h", "44x", "-1", "4 4'", "2147483648", "44''"] {
+            assert!(parse_child_index(s).is_err(), "{:?}", s);
+        }
+
+        let hardened = 44 | HARDENED_OFFSET;
+        assert_eq!(
+            format_child_index(hardened, HardenedStyle::default()),
+            "44'"
+        );
+        assert_eq!(format_child_index(hardened, HardenedStyle::LowerH), "44h");
+        assert_eq!(format_child_index(hardened, HardenedStyle::UpperH), "44H");
+        assert_eq!(format_child_index(7, HardenedStyle::LowerH), "7");
+    }
+
+    #[test]
+    fn test_xpub_string_roundtrip() {
+        let (master_xprv, _) = derive_master_key(b"test seed for serialization").unwrap();
+        let (_, child_xpub) = master_xprv.derive_child(0x80000001).unwrap();
+
+        let xpub = child_xpub.to_xpub_string(NetworkType::Mainnet);
+        let tpub = child_xpub.to_xpub_string(NetworkType::Regtest);
+        assert!(xpub.starts_with("xpub"));
+        assert!(tpub.starts_with("tpub"));
+        for encoded in [xpub, tpub] {
+            let parsed = ExtendedPublicKey::from_xpub_string(&encoded).unwrap();
+            assert_eq!(parsed.public_key_bytes(), child_xpub.public_key_bytes());
+            assert_eq!(parsed.chain_code, child_xpub.chain_code);
+        }
+
+        assert!(ExtendedPublicKey::from_xpub_string("xpub1234").is_err());
+    }
 }
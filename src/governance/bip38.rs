@@ -0,0 +1,435 @@
+//! # BIP38: Passphrase-Protected Private Keys
+//!
+//! Encrypts governance keys with a passphrase so they can be carried on
+//! paper or over untrusted channels.
+//!
+//! Specification: https://github.com/bitcoin/bips/blob/master/bip-0038.mediawiki
+//!
+//! Passphrases are used as given; callers are responsible for Unicode NFC
+//! normalization of non-ASCII passphrases.
+
+use crate::governance::base58;
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::GovernanceKeypair;
+use crate::governance::network::NetworkType;
+use crate::governance::signatures::{hash160, sha256d};
+use aes::cipher::{generic_array::GenericArray, BlockDecrypt, BlockEncrypt, KeyInit};
+use aes::Aes256;
+use rand::RngCore;
+use secp256k1::{PublicKey, Scalar, Secp256k1, SecretKey};
+
+/// Prefix of a key encrypted without EC multiplication ("6P...")
+const PREFIX_NO_EC_MULTIPLY: [u8; 2] = [0x01, 0x42];
+
+/// Prefix of a key encrypted with EC multiplication ("6P...")
+const PREFIX_EC_MULTIPLY: [u8; 2] = [0x01, 0x43];
+
+/// Prefix of a confirmation code ("cfrm38...")
+const PREFIX_CONFIRMATION: [u8; 5] = [0x64, 0x3b, 0xf6, 0xa8, 0x9a];
+
+/// Flag bits set on keys encrypted without EC multiplication
+const FLAG_NO_EC_MULTIPLY: u8 = 0xc0;
+
+/// Flag bit for keys whose address uses the compressed public key
+const FLAG_COMPRESSED: u8 = 0x20;
+
+/// Flag bit for EC-multiplied keys carrying lot and sequence numbers
+const FLAG_LOT_SEQUENCE: u8 = 0x04;
+
+/// Length of an encrypted key payload
+const ENCRYPTED_KEY_LENGTH: usize = 39;
+
+/// Length of a confirmation code payload
+const CONFIRMATION_LENGTH: usize = 51;
+
+/// scrypt parameters for passphrase stretching (N=16384, r=8, p=8)
+const SCRYPT_LOG_N: u8 = 14;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 8;
+
+/// scrypt parameters for stretching the passpoint (N=1024, r=1, p=1)
+const PASSPOINT_SCRYPT_LOG_N: u8 = 10;
+const PASSPOINT_SCRYPT_R: u32 = 1;
+const PASSPOINT_SCRYPT_P: u32 = 1;
+
+/// WIF version bytes
+const WIF_MAINNET: u8 = 0x80;
+const WIF_TESTNET: u8 = 0xef;
+
+/// P2PKH address version bytes
+const P2PKH_MAINNET: u8 = 0x00;
+const P2PKH_TESTNET: u8 = 0x6f;
+
+/// Encrypt a WIF private key with a passphrase (non-EC-multiply mode)
+pub fn encrypt_no_ec_multiply(wif: &str, passphrase: &str) -> GovernanceResult<String> {
+    let (secret, compressed, network) = decode_wif(wif)?;
+    let keypair = GovernanceKeypair::from_secret_key(&secret)?;
+    let address = p2pkh_address(&keypair.public_key, compressed, network);
+    let address_hash = address_hash(&address);
+
+    let derived = stretch(
+        passphrase.as_bytes(),
+        &address_hash,
+        SCRYPT_LOG_N,
+        SCRYPT_R,
+        SCRYPT_P,
+        64,
+    )?;
+    let (half1, half2) = derived.split_at(32);
+
+    let mut flag = FLAG_NO_EC_MULTIPLY;
+    if compressed {
+        flag |= FLAG_COMPRESSED;
+    }
+
+    let mut payload = Vec::with_capacity(ENCRYPTED_KEY_LENGTH);
+    payload.extend_from_slice(&PREFIX_NO_EC_MULTIPLY);
+    payload.push(flag);
+    payload.extend_from_slice(&address_hash);
+    payload.extend_from_slice(&aes_encrypt(&xor(&secret[..16], &half1[..16]), half2));
+    payload.extend_from_slice(&aes_encrypt(&xor(&secret[16..], &half1[16..]), half2));
+
+    Ok(base58::encode_check(&payload))
+}
+
+/// Generate a new key encrypted under a passphrase (EC-multiply mode)
+///
+/// Returns the encrypted key and the P2PKH address it controls. The private
+/// key itself is never materialized.
+pub fn encrypt_ec_multiply(
+    passphrase: &str,
+    compressed: bool,
+    network: NetworkType,
+) -> GovernanceResult<(String, String)> {
+    let secp = Secp256k1::new();
+    let mut rng = rand::thread_rng();
+
+    let mut owner_entropy = [0u8; 8];
+    rng.fill_bytes(&mut owner_entropy);
+    let pass_factor = pass_factor(passphrase, &owner_entropy, false)?;
+    let pass_point = SecretKey::from_slice(&pass_factor)
+        .map_err(|e| GovernanceError::Cryptographic(format!("Invalid passfactor: {}", e)))?
+        .public_key(&secp);
+
+    let mut seed_b = [0u8; 24];
+    rng.fill_bytes(&mut seed_b);
+    let factor_b = sha256d(&seed_b);
+    let public_key = pass_point
+        .mul_tweak(&secp, &scalar(&factor_b)?)
+        .map_err(|e| GovernanceError::Cryptographic(format!("Invalid factorb: {}", e)))?;
+
+    let address = p2pkh_address(&public_key, compressed, network);
+    let address_hash = address_hash(&address);
+    let derived = stretch_pass_point(&pass_point, &address_hash, &owner_entropy)?;
+    let (half1, half2) = derived.split_at(32);
+
+    let part1 = aes_encrypt(&xor(&seed_b[..16], &half1[..16]), half2);
+    let mut block2 = [0u8; 16];
+    block2[..8].copy_from_slice(&part1[8..]);
+    block2[8..].copy_from_slice(&seed_b[16..]);
+    let part2 = aes_encrypt(&xor(&block2, &half1[16..]), half2);
+
+    let flag = if compressed { FLAG_COMPRESSED } else { 0 };
+    let mut payload = Vec::with_capacity(ENCRYPTED_KEY_LENGTH);
+    payload.extend_from_slice(&PREFIX_EC_MULTIPLY);
+    payload.push(flag);
+    payload.extend_from_slice(&address_hash);
+    payload.extend_from_slice(&owner_entropy);
+    payload.extend_from_slice(&part1[..8]);
+    payload.extend_from_slice(&part2);
+
+    Ok((base58::encode_check(&payload), address))
+}
+
+/// Decrypt a BIP38 encrypted key
+///
+/// Fails with `InvalidKey` if the passphrase does not match.
+pub fn decrypt(encrypted_key: &str, passphrase: &str) -> GovernanceResult<GovernanceKeypair> {
+    let payload = base58::decode_check(encrypted_key.trim())
+        .map_err(|e| GovernanceError::InvalidInput(format!("Invalid BIP38 encoding: {}", e)))?;
+    if payload.len() != ENCRYPTED_KEY_LENGTH {
+        return Err(GovernanceError::InvalidInput(format!(
+            "BIP38 key must be {} bytes, got {}",
+            ENCRYPTED_KEY_LENGTH,
+            payload.len()
+        )));
+    }
+
+    let flag = payload[2];
+    let compressed = flag & FLAG_COMPRESSED != 0;
+    let address_hash = &payload[3..7];
+
+    let keypair = match [payload[0], payload[1]] {
+        PREFIX_NO_EC_MULTIPLY => {
+            let derived = stretch(
+                passphrase.as_bytes(),
+                address_hash,
+                SCRYPT_LOG_N,
+                SCRYPT_R,
+                SCRYPT_P,
+                64,
+            )?;
+            let (half1, half2) = derived.split_at(32);
+
+            let block1 = xor(&aes_decrypt(&payload[7..23], half2), &half1[..16]);
+            let block2 = xor(&aes_decrypt(&payload[23..39], half2), &half1[16..]);
+            let mut secret = [0u8; 32];
+            secret[..16].copy_from_slice(&block1);
+            secret[16..].copy_from_slice(&block2);
+            GovernanceKeypair::from_secret_key(&secret).map_err(|_| wrong_passphrase())?
+        }
+        PREFIX_EC_MULTIPLY => {
+            let owner_entropy = &payload[7..15];
+            let pass_factor =
+                pass_factor(passphrase, owner_entropy, flag & FLAG_LOT_SEQUENCE != 0)?;
+            let pass_secret =
+                SecretKey::from_slice(&pass_factor).map_err(|_| wrong_passphrase())?;
+            let pass_point = pass_secret.public_key(&Secp256k1::new());
+            let derived = stretch_pass_point(&pass_point, address_hash, owner_entropy)?;
+            let (half1, half2) = derived.split_at(32);
+
+            // Part 2 decrypts to the tail of part 1 followed by the tail of seedb
+            let block2 = xor(&aes_decrypt(&payload[23..39], half2), &half1[16..]);
+            let mut part1 = [0u8; 16];
+            part1[..8].copy_from_slice(&payload[15..23]);
+            part1[8..].copy_from_slice(&block2[..8]);
+            let block1 = xor(&aes_decrypt(&part1, half2), &half1[..16]);
+
+            let mut seed_b = [0u8; 24];
+            seed_b[..16].copy_from_slice(&block1);
+            seed_b[16..].copy_from_slice(&block2[8..]);
+            let factor_b = sha256d(&seed_b);
+
+            let secret = pass_secret
+                .mul_tweak(&scalar(&factor_b)?)
+                .map_err(|_| wrong_passphrase())?;
+            GovernanceKeypair::from_secret_key(&secret.secret_bytes())?
+        }
+        _ => {
+            return Err(GovernanceError::InvalidInput(
+                "Unknown BIP38 key prefix".to_string(),
+            ))
+        }
+    };
+
+    match_address_hash(&keypair.public_key, compressed, address_hash)?;
+    Ok(keypair)
+}
+
+/// Check an EC-multiply confirmation code against a passphrase
+///
+/// Returns the address the confirmed encrypted key controls.
+pub fn confirm_code(confirm: &str, passphrase: &str) -> GovernanceResult<String> {
+    let payload = base58::decode_check(confirm.trim()).map_err(|e| {
+        GovernanceError::InvalidInput(format!("Invalid confirmation code encoding: {}", e))
+    })?;
+    if payload.len() != CONFIRMATION_LENGTH || payload[..5] != PREFIX_CONFIRMATION {
+        return Err(GovernanceError::InvalidInput(
+            "Not a BIP38 confirmation code".to_string(),
+        ));
+    }
+
+    let flag = payload[5];
+    let compressed = flag & FLAG_COMPRESSED != 0;
+    let address_hash = &payload[6..10];
+    let owner_entropy = &payload[10..18];
+    let encrypted_point_b = &payload[18..51];
+
+    let secp = Secp256k1::new();
+    let pass_factor = pass_factor(passphrase, owner_entropy, flag & FLAG_LOT_SEQUENCE != 0)?;
+    let pass_secret = SecretKey::from_slice(&pass_factor).map_err(|_| wrong_passphrase())?;
+    let pass_point = pass_secret.public_key(&secp);
+    let derived = stretch_pass_point(&pass_point, address_hash, owner_entropy)?;
+    let (half1, half2) = derived.split_at(32);
+
+    let mut point_b = [0u8; 33];
+    point_b[0] = encrypted_point_b[0] ^ (half2[31] & 0x01);
+    point_b[1..17].copy_from_slice(&xor(
+        &aes_decrypt(&encrypted_point_b[1..17], half2),
+        &half1[..16],
+    ));
+    point_b[17..].copy_from_slice(&xor(
+        &aes_decrypt(&encrypted_point_b[17..], half2),
+        &half1[16..],
+    ));
+    let point_b = PublicKey::from_slice(&point_b).map_err(|_| wrong_passphrase())?;
+
+    let public_key = point_b
+        .mul_tweak(&secp, &scalar(&pass_factor)?)
+        .map_err(|_| wrong_passphrase())?;
+    match_address_hash(&public_key, compressed, address_hash)
+}
+
+/// Decode a WIF key into its secret, compression flag, and network
+fn decode_wif(wif: &str) -> GovernanceResult<([u8; 32], bool, NetworkType)> {
+    let payload = base58::decode_check(wif.trim())
+        .map_err(|e| GovernanceError::InvalidKey(format!("Invalid WIF: {}", e)))?;
+
+    let network = match payload.first() {
+        Some(&WIF_MAINNET) => NetworkType::Mainnet,
+        Some(&WIF_TESTNET) => NetworkType::Testnet,
+        _ => {
+            return Err(GovernanceError::InvalidKey(
+                "Invalid WIF version byte".to_string(),
+            ))
+        }
+    };
+    let compressed = match payload.len() {
+        33 => false,
+        34 if payload[33] == 0x01 => true,
+        _ => {
+            return Err(GovernanceError::InvalidKey(
+                "Invalid WIF length".to_string(),
+            ))
+        }
+    };
+
+    let mut secret = [0u8; 32];
+    secret.copy_from_slice(&payload[1..33]);
+    Ok((secret, compressed, network))
+}
+
+/// Base58Check P2PKH address of a public key
+fn p2pkh_address(public_key: &PublicKey, compressed: bool, network: NetworkType) -> String {
+    let version = match network {
+        NetworkType::Mainnet => P2PKH_MAINNET,
+        NetworkType::Testnet | NetworkType::Regtest => P2PKH_TESTNET,
+    };
+    let hash = if compressed {
+        hash160(&public_key.serialize())
+    } else {
+        hash160(&public_key.serialize_uncompressed())
+    };
+
+    let mut payload = Vec::with_capacity(21);
+    payload.push(version);
+    payload.extend_from_slice(&hash);
+    base58::encode_check(&payload)
+}
+
+/// First four bytes of the double SHA256 of an address
+fn address_hash(address: &str) -> [u8; 4] {
+    let hash = sha256d(address.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// Find the address (mainnet or testnet) whose hash matches `expected`
+///
+/// The encrypted key does not record its network, so both are tried.
+fn match_address_hash(
+    public_key: &PublicKey,
+    compressed: bool,
+    expected: &[u8],
+) -> GovernanceResult<String> {
+    [NetworkType::Mainnet, NetworkType::Testnet]
+        .into_iter()
+        .map(|network| p2pkh_address(public_key, compressed, network))
+        .find(|address| address_hash(address)[..] == *expected)
+        .ok_or_else(wrong_passphrase)
+}
+
+/// Compute passfactor from the passphrase and owner entropy
+fn pass_factor(
+    passphrase: &str,
+    owner_entropy: &[u8],
+    lot_sequence: bool,
+) -> GovernanceResult<[u8; 32]> {
+    if lot_sequence {
+        // Owner salt is the first four bytes; the rest is lot and sequence
+        let prefactor = stretch(
+            passphrase.as_bytes(),
+            &owner_entropy[..4],
+            SCRYPT_LOG_N,
+            SCRYPT_R,
+            SCRYPT_P,
+            32,
+        )?;
+        let mut data = prefactor;
+        data.extend_from_slice(owner_entropy);
+        Ok(sha256d(&data))
+    } else {
+        let stretched = stretch(
+            passphrase.as_bytes(),
+            owner_entropy,
+            SCRYPT_LOG_N,
+            SCRYPT_R,
+            SCRYPT_P,
+            32,
+        )?;
+        let mut factor = [0u8; 32];
+        factor.copy_from_slice(&stretched);
+        Ok(factor)
+    }
+}
+
+/// Derive the 64-byte encryption key for EC-multiplied keys
+fn stretch_pass_point(
+    pass_point: &PublicKey,
+    address_hash: &[u8],
+    owner_entropy: &[u8],
+) -> GovernanceResult<Vec<u8>> {
+    let mut salt = Vec::with_capacity(12);
+    salt.extend_from_slice(address_hash);
+    salt.extend_from_slice(owner_entropy);
+    stretch(
+        &pass_point.serialize(),
+        &salt,
+        PASSPOINT_SCRYPT_LOG_N,
+        PASSPOINT_SCRYPT_R,
+        PASSPOINT_SCRYPT_P,
+        64,
+    )
+}
+
+/// scrypt key stretching
+fn stretch(
+    password: &[u8],
+    salt: &[u8],
+    log_n: u8,
+    r: u32,
+    p: u32,
+    len: usize,
+) -> GovernanceResult<Vec<u8>> {
+    let params = scrypt::Params::new(log_n, r, p, len)
+        .map_err(|e| GovernanceError::Cryptographic(format!("Invalid scrypt parameters: {}", e)))?;
+    let mut output = vec![0u8; len];
+    scrypt::scrypt(password, salt, &params, &mut output)
+        .map_err(|e| GovernanceError::Cryptographic(format!("scrypt failed: {}", e)))?;
+    Ok(output)
+}
+
+/// Encrypt one 16-byte block with AES-256
+fn aes_encrypt(block: &[u8], key: &[u8]) -> [u8; 16] {
+    let cipher = Aes256::new(GenericArray::from_slice(key));
+    let mut block = *GenericArray::from_slice(block);
+    cipher.encrypt_block(&mut block);
+    block.into()
+}
+
+/// Decrypt one 16-byte block with AES-256
+fn aes_decrypt(block: &[u8], key: &[u8]) -> [u8; 16] {
+    let cipher = Aes256::new(GenericArray::from_slice(key));
+    let mut block = *GenericArray::from_slice(block);
+    cipher.decrypt_block(&mut block);
+    block.into()
+}
+
+/// XOR two 16-byte blocks
+fn xor(a: &[u8], b: &[u8]) -> [u8; 16] {
+    let mut out = [0u8; 16];
+    for (i, byte) in out.iter_mut().enumerate() {
+        *byte = a[i] ^ b[i];
+    }
+    out
+}
+
+/// Interpret 32 bytes as a curve scalar
+fn scalar(bytes: &[u8; 32]) -> GovernanceResult<Scalar> {
+    Scalar::from_be_bytes(*bytes)
+        .map_err(|_| GovernanceError::Cryptographic("Scalar out of range".to_string()))
+}
+
+/// Error returned when a passphrase fails to decrypt a key
+fn wrong_passphrase() -> GovernanceError {
+    GovernanceError::InvalidKey("BIP38 passphrase is incorrect".to_string())
+}
@@ -444,6 +444,36 @@ pub fn mnemonic_to_seed(mnemonic: &[String], passphrase: &str) -> [u8; 64] {
     seed
 }
 
+/// Compact binary form of a mnemonic, for QR codes or NFC tags
+///
+/// Validates the mnemonic and returns only the raw entropy (16-32 bytes).
+/// The bytes do NOT include the checksum bits; `mnemonic_from_bytes`
+/// recomputes them. Equivalent to `mnemonic_to_entropy`.
+pub fn mnemonic_to_bytes(mnemonic: &[String]) -> GovernanceResult<Vec<u8>> {
+    mnemonic_to_entropy(mnemonic)
+}
+
+/// Rebuild a mnemonic from the bytes produced by `mnemonic_to_bytes`
+///
+/// `entropy` must NOT include the checksum bits; they are recomputed from
+/// SHA256(entropy). Equivalent to `mnemonic_from_entropy`.
+pub fn mnemonic_from_bytes(entropy: &[u8]) -> GovernanceResult<Vec<String>> {
+    mnemonic_from_entropy(entropy)
+}
+
+/// Convert a mnemonic to entropy followed by its checksum bits
+///
+/// The checksum (entropy_bits / 32 bits) is left-aligned in one extra
+/// byte, with the remaining low bits zero: 17 bytes for 12 words, 33 bytes
+/// for 24 words.
+pub fn mnemonic_to_entropy_with_checksum(mnemonic: &[String]) -> GovernanceResult<Vec<u8>> {
+    let mut bytes = mnemonic_to_entropy(mnemonic)?;
+    let checksum_bits = bytes.len() * 8 / 32;
+    let hash = Sha256::digest(&bytes);
+    bytes.push(hash[0] & (0xff << (8 - checksum_bits)));
+    Ok(bytes)
+}
+
 /// Validate mnemonic phrase (checks word list and checksum)
 pub fn validate_mnemonic(mnemonic: &[String]) -> GovernanceResult<()> {
     mnemonic_to_entropy(mnemonic)?;
@@ -8,35 +8,64 @@
 //! Example: m/44'/0'/0'/0/0 (Bitcoin mainnet first address)
 
 use crate::governance::bip32::{
-    derive_child_private, derive_master_key, ExtendedPrivateKey, ExtendedPublicKey,
+    derive_child_private, derive_master_key, format_child_index, parse_child_index,
+    ExtendedPrivateKey, ExtendedPublicKey, HardenedStyle, HARDENED_OFFSET,
 };
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::network::NetworkType;
+use crate::governance::GovernanceKeypair;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Range;
+#[cfg(feature = "node")]
+use std::path::Path;
+use std::str::FromStr;
 
 /// BIP44 purpose (always 44 for multi-account hierarchy)
 pub const BIP44_PURPOSE: u32 = 44;
 
-/// Coin types (BIP44 registered coin types)
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// Purposes that share the BIP44 path layout (BIP44, BIP49, BIP84, BIP86)
+pub const SUPPORTED_PURPOSES: [u32; 4] = [44, 49, 84, 86];
+
+/// Coin types (SLIP-44 registered coin types)
+///
+/// Equality and hashing compare `value()`, so `Other(0)` equals `Bitcoin`.
+#[derive(Debug, Clone, Copy)]
 pub enum CoinType {
     /// Bitcoin mainnet
-    Bitcoin = 0,
+    Bitcoin,
     /// Bitcoin testnet
-    BitcoinTestnet = 1,
+    BitcoinTestnet,
     /// Litecoin
-    Litecoin = 2,
+    Litecoin,
     /// Dogecoin
-    Dogecoin = 3,
+    Dogecoin,
     /// Ethereum (for reference)
-    Ethereum = 60,
+    Ethereum,
+    /// Any other SLIP-44 coin type (below 2^31)
+    Other(u32),
 }
 
 impl CoinType {
     /// Get coin type value
     pub fn value(&self) -> u32 {
-        *self as u32
+        match self {
+            CoinType::Bitcoin => 0,
+            CoinType::BitcoinTestnet => 1,
+            CoinType::Litecoin => 2,
+            CoinType::Dogecoin => 3,
+            CoinType::Ethereum => 60,
+            CoinType::Other(value) => *value,
+        }
     }
 
     /// Create from u32
+    ///
+    /// Known coin types map to their named variant; any other value below
+    /// 2^31 becomes `Other`.
     pub fn from_value(value: u32) -> Result<Self, GovernanceError> {
         match value {
             0 => Ok(CoinType::Bitcoin),
@@ -44,16 +73,31 @@ impl CoinType {
             2 => Ok(CoinType::Litecoin),
             3 => Ok(CoinType::Dogecoin),
             60 => Ok(CoinType::Ethereum),
+            _ if value < HARDENED_OFFSET => Ok(CoinType::Other(value)),
             _ => Err(GovernanceError::InvalidInput(format!(
-                "Unsupported coin type: {}",
+                "Coin type must be below 2^31, got {}",
                 value
             ))),
         }
     }
 }
 
+impl PartialEq for CoinType {
+    fn eq(&self, other: &Self) -> bool {
+        self.value() == other.value()
+    }
+}
+
+impl Eq for CoinType {}
+
+impl Hash for CoinType {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state);
+    }
+}
+
 /// Change chain type
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum ChangeChain {
     /// External chain (receiving addresses)
     External = 0,
@@ -68,9 +112,12 @@ impl ChangeChain {
 }
 
 /// BIP44 derivation path
-#[derive(Debug, Clone, PartialEq, Eq)]
+///
+/// Equality and hashing ignore `hardened_style`, so `m/44'/0'/0'/0/0` and
+/// `m/44h/0h/0h/0/0` are the same path.
+#[derive(Debug, Clone)]
 pub struct Bip44Path {
-    /// Purpose (always 44 for BIP44)
+    /// Purpose (44 for BIP44; 49, 84, and 86 use the same layout)
     pub purpose: u32,
     /// Coin type (0 = Bitcoin, 1 = Testnet, etc.)
     pub coin_type: CoinType,
@@ -80,6 +127,8 @@ pub struct Bip44Path {
     pub change: ChangeChain,
     /// Address index
     pub address_index: u32,
+    /// Notation for hardened components when displayed
+    pub hardened_style: HardenedStyle,
 }
 
 impl Bip44Path {
@@ -91,9 +140,16 @@ impl Bip44Path {
             account,
             change,
             address_index,
+            hardened_style: HardenedStyle::default(),
         }
     }
 
+    /// Use a different notation for hardened components
+    pub fn with_hardened_style(mut self, hardened_style: HardenedStyle) -> Self {
+        self.hardened_style = hardened_style;
+        self
+    }
+
     /// Create Bitcoin mainnet path
     pub fn bitcoin_mainnet(account: u32, change: ChangeChain, address_index: u32) -> Self {
         Self::new(CoinType::Bitcoin, account, change, address_index)
@@ -105,81 +161,9 @@ impl Bip44Path {
     }
 
     /// Parse BIP44 path from string (e.g., "m/44'/0'/0'/0/0")
+    #[deprecated(note = "use `str::parse::<Bip44Path>()`")]
     pub fn from_string(path_str: &str) -> GovernanceResult<Self> {
-        // Remove "m/" prefix if present
-        let path_str = path_str.strip_prefix("m/").unwrap_or(path_str);
-
-        let parts: Vec<&str> = path_str.split('/').collect();
-        if parts.len() != 5 {
-            return Err(GovernanceError::InvalidInput(
-                "BIP44 path must have 5 components: purpose'/coin_type'/account'/change/address_index".to_string()
-            ));
-        }
-
-        // Parse purpose (should be 44')
-        let purpose_str = parts[0].trim_end_matches('\'');
-        let purpose: u32 = purpose_str
-            .parse()
-            .map_err(|_| GovernanceError::InvalidInput("Invalid purpose".to_string()))?;
-
-        if purpose != BIP44_PURPOSE {
-            return Err(GovernanceError::InvalidInput(format!(
-                "Purpose must be {} for BIP44",
-                BIP44_PURPOSE
-            )));
-        }
-
-        // Parse coin_type (should be hardened)
-        let coin_type_str = parts[1].trim_end_matches('\'');
-        let coin_type_val: u32 = coin_type_str
-            .parse()
-            .map_err(|_| GovernanceError::InvalidInput("Invalid coin type".to_string()))?;
-        let coin_type = CoinType::from_value(coin_type_val)?;
-
-        // Parse account (should be hardened)
-        let account_str = parts[2].trim_end_matches('\'');
-        let account: u32 = account_str
-            .parse()
-            .map_err(|_| GovernanceError::InvalidInput("Invalid account".to_string()))?;
-
-        // Parse change (not hardened)
-        let change_val: u32 = parts[3]
-            .parse()
-            .map_err(|_| GovernanceError::InvalidInput("Invalid change".to_string()))?;
-        let change = match change_val {
-            0 => ChangeChain::External,
-            1 => ChangeChain::Internal,
-            _ => {
-                return Err(GovernanceError::InvalidInput(
-                    "Change must be 0 (external) or 1 (internal)".to_string(),
-                ))
-            }
-        };
-
-        // Parse address_index (not hardened)
-        let address_index: u32 = parts[4]
-            .parse()
-            .map_err(|_| GovernanceError::InvalidInput("Invalid address index".to_string()))?;
-
-        Ok(Bip44Path {
-            purpose,
-            coin_type,
-            account,
-            change,
-            address_index,
-        })
-    }
-
-    /// Convert to string representation (e.g., "m/44'/0'/0'/0/0")
-    pub fn to_string(&self) -> String {
-        format!(
-            "m/{}/{}'/{}'/{}/{}",
-            self.purpose,
-            self.coin_type.value(),
-            self.account,
-            self.change.value(),
-            self.address_index
-        )
+        path_str.parse()
     }
 
     /// Derive key from master key using this path
@@ -221,22 +205,158 @@ impl Bip44Path {
     }
 }
 
+impl FromStr for Bip44Path {
+    type Err = GovernanceError;
+
+    /// Parse a path such as "m/84'/1'/0'/0/3"
+    ///
+    /// Purpose, coin type, and account must be hardened and change and
+    /// address index must not be; anything else is rejected rather than
+    /// silently deriving a different path. Hardened components may be
+    /// written `44'`, `44h`, or `44H`. The notation of the first hardened
+    /// component is kept for display.
+    fn from_str(path_str: &str) -> GovernanceResult<Self> {
+        // Remove "m/" prefix if present
+        let path_str = path_str.strip_prefix("m/").unwrap_or(path_str);
+
+        let parts: Vec<&str> = path_str.split('/').collect();
+        if parts.len() != 5 {
+            return Err(GovernanceError::InvalidInput(
+                "BIP44 path must have 5 components: purpose'/coin_type'/account'/change/address_index".to_string()
+            ));
+        }
+        let hardened_style = parts
+            .iter()
+            .find_map(|part| HardenedStyle::of_component(part))
+            .unwrap_or_default();
+
+        // purpose, coin_type, and account must be hardened; change and
+        // address_index must not be
+        let component = |part: &str, name: &str, hardened: bool| {
+            let index = parse_child_index(part).map_err(|_| {
+                GovernanceError::InvalidInput(format!("Invalid {} '{}'", name, part))
+            })?;
+            if (index >= HARDENED_OFFSET) != hardened {
+                let expected = if hardened { "must" } else { "must not" };
+                return Err(GovernanceError::InvalidInput(format!(
+                    "BIP44 {} {} be hardened, got '{}'",
+                    name, expected, part
+                )));
+            }
+            Ok(index & !HARDENED_OFFSET)
+        };
+
+        // Parse purpose (44', 49', 84', or 86')
+        let purpose = component(parts[0], "purpose", true)?;
+
+        if !SUPPORTED_PURPOSES.contains(&purpose) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Purpose must be one of {:?}, got {}",
+                SUPPORTED_PURPOSES, purpose
+            )));
+        }
+
+        let coin_type = CoinType::from_value(component(parts[1], "coin type", true)?)?;
+        let account = component(parts[2], "account", true)?;
+
+        let change = match component(parts[3], "change", false)? {
+            0 => ChangeChain::External,
+            1 => ChangeChain::Internal,
+            _ => {
+                return Err(GovernanceError::InvalidInput(
+                    "Change must be 0 (external) or 1 (internal)".to_string(),
+                ))
+            }
+        };
+
+        let address_index = component(parts[4], "address index", false)?;
+
+        Ok(Bip44Path {
+            purpose,
+            coin_type,
+            account,
+            change,
+            address_index,
+            hardened_style,
+        })
+    }
+}
+
+impl fmt::Display for Bip44Path {
+    /// Format as "m/44'/0'/0'/0/0", using `hardened_style`
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "m")?;
+        for index in self.to_indices() {
+            write!(f, "/{}", format_child_index(index, self.hardened_style))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for Bip44Path {
+    fn eq(&self, other: &Self) -> bool {
+        self.to_indices() == other.to_indices()
+    }
+}
+
+impl Eq for Bip44Path {}
+
+impl Hash for Bip44Path {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.to_indices().hash(state);
+    }
+}
+
 /// BIP44 wallet for managing multiple accounts and addresses
+///
+/// Account and chain keys are cached after their first derivation, so each
+/// address only costs the final non-hardened step.
 pub struct Bip44Wallet {
     /// Master extended private key
     master_private: ExtendedPrivateKey,
     /// Coin type
     coin_type: CoinType,
+    /// Cached intermediate keys
+    cache: RefCell<KeyCache>,
+    /// Next unused address index per account and chain
+    next_indices: HashMap<(u32, ChangeChain), u32>,
+}
+
+/// Address counters exported by [`Bip44Wallet::export_state`]
+///
+/// Holds no key material; the fingerprint only ties the state to its wallet.
+#[derive(Serialize, Deserialize)]
+struct WalletState {
+    /// Master key fingerprint (hex)
+    fingerprint: String,
+    /// SLIP-44 coin type
+    coin_type: u32,
+    /// Next unused address index per account and chain
+    next_indices: Vec<AddressCounter>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct AddressCounter {
+    account: u32,
+    /// 0 = external, 1 = internal
+    change: u32,
+    next_index: u32,
+}
+
+/// Intermediate keys cached by `Bip44Wallet`
+#[derive(Default)]
+struct KeyCache {
+    /// Account keys (m/44'/coin'/account')
+    accounts: HashMap<u32, ExtendedPrivateKey>,
+    /// Chain keys (m/44'/coin'/account'/change)
+    chains: HashMap<(u32, ChangeChain), ExtendedPrivateKey>,
 }
 
 impl Bip44Wallet {
     /// Create a new BIP44 wallet from seed
     pub fn from_seed(seed: &[u8], coin_type: CoinType) -> GovernanceResult<Self> {
         let (master_private, _) = derive_master_key(seed)?;
-        Ok(Bip44Wallet {
-            master_private,
-            coin_type,
-        })
+        Ok(Self::from_master_key(master_private, coin_type))
     }
 
     /// Create from existing master key
@@ -244,6 +364,8 @@ impl Bip44Wallet {
         Bip44Wallet {
             master_private,
             coin_type,
+            cache: RefCell::new(KeyCache::default()),
+            next_indices: HashMap::new(),
         }
     }
 
@@ -254,8 +376,160 @@ impl Bip44Wallet {
         change: ChangeChain,
         address_index: u32,
     ) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
-        let path = Bip44Path::new(self.coin_type, account, change, address_index);
-        path.derive(&self.master_private)
+        self.chain_key(account, change)?.derive_child(address_index)
+    }
+
+    /// Derive keys for a range of address indices on one chain
+    pub fn derive_addresses(
+        &self,
+        account: u32,
+        change: ChangeChain,
+        range: Range<u32>,
+    ) -> GovernanceResult<Vec<(ExtendedPrivateKey, ExtendedPublicKey)>> {
+        let chain_key = self.chain_key(account, change)?;
+        range.map(|index| chain_key.derive_child(index)).collect()
+    }
+
+    /// Derive `count` consecutive addresses on one chain, starting at `start`
+    ///
+    /// Fails if the range would reach hardened indices (2^31 and above).
+    pub fn derive_address_range(
+        &self,
+        account: u32,
+        change: ChangeChain,
+        start: u32,
+        count: u32,
+    ) -> GovernanceResult<Vec<(ExtendedPrivateKey, ExtendedPublicKey)>> {
+        let end = start
+            .checked_add(count)
+            .filter(|&end| end <= HARDENED_OFFSET)
+            .ok_or_else(|| {
+                GovernanceError::InvalidInput(format!(
+                    "Address range {}+{} exceeds the non-hardened index space",
+                    start, count
+                ))
+            })?;
+        self.derive_addresses(account, change, start..end)
+    }
+
+    /// Derive the next unused receiving address for account
+    ///
+    /// Each call advances the account's external-chain counter.
+    pub fn next_receiving_address(
+        &mut self,
+        account: u32,
+    ) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
+        self.next_address(account, ChangeChain::External)
+    }
+
+    /// Derive the next unused change address for account
+    ///
+    /// Each call advances the account's internal-chain counter.
+    pub fn next_change_address(
+        &mut self,
+        account: u32,
+    ) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
+        self.next_address(account, ChangeChain::Internal)
+    }
+
+    /// Index the next `next_*_address` call will derive on a chain
+    pub fn next_address_index(&self, account: u32, change: ChangeChain) -> u32 {
+        self.next_indices
+            .get(&(account, change))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    fn next_address(
+        &mut self,
+        account: u32,
+        change: ChangeChain,
+    ) -> GovernanceResult<(ExtendedPrivateKey, ExtendedPublicKey)> {
+        let index = self.next_address_index(account, change);
+        if index >= HARDENED_OFFSET {
+            return Err(GovernanceError::InvalidChildIndex(index));
+        }
+        let address = self.derive_address(account, change, index)?;
+        self.next_indices.insert((account, change), index + 1);
+        Ok(address)
+    }
+
+    /// Export the address counters (no key material) as JSON
+    pub fn export_state(&self) -> GovernanceResult<String> {
+        let mut next_indices: Vec<AddressCounter> = self
+            .next_indices
+            .iter()
+            .map(|(&(account, change), &next_index)| AddressCounter {
+                account,
+                change: change.value(),
+                next_index,
+            })
+            .collect();
+        next_indices.sort_by_key(|c| (c.account, c.change));
+        let state = WalletState {
+            fingerprint: hex::encode(self.master_private.fingerprint()),
+            coin_type: self.coin_type.value(),
+            next_indices,
+        };
+
+        serde_json::to_string_pretty(&state).map_err(|e| {
+            GovernanceError::Serialization(format!("Failed to serialize wallet state: {}", e))
+        })
+    }
+
+    /// Save the address counters to a file. Requires the `node` feature.
+    #[cfg(feature = "node")]
+    pub fn save_state(&self, path: &Path) -> GovernanceResult<()> {
+        std::fs::write(path, self.export_state()?).map_err(|e| {
+            GovernanceError::InvalidInput(format!("Failed to write {}: {}", path.display(), e))
+        })
+    }
+
+    /// Restore address counters saved by [`save_state`](Self::save_state).
+    /// Requires the `node` feature.
+    #[cfg(feature = "node")]
+    pub fn load_state(&mut self, path: &Path) -> GovernanceResult<()> {
+        let json = std::fs::read_to_string(path).map_err(|e| {
+            GovernanceError::InvalidInput(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        self.import_state(&json)
+    }
+
+    /// Restore address counters from [`export_state`](Self::export_state)
+    ///
+    /// The state must come from a wallet with the same master key and coin
+    /// type. Counters replace the wallet's current ones.
+    pub fn import_state(&mut self, json: &str) -> GovernanceResult<()> {
+        let state: WalletState = serde_json::from_str(json)
+            .map_err(|e| GovernanceError::Serialization(format!("Invalid wallet state: {}", e)))?;
+
+        let fingerprint = hex::encode(self.master_private.fingerprint());
+        if state.fingerprint != fingerprint || state.coin_type != self.coin_type.value() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Wallet state is for {} coin type {}, not {} coin type {}",
+                state.fingerprint,
+                state.coin_type,
+                fingerprint,
+                self.coin_type.value()
+            )));
+        }
+
+        let mut next_indices = HashMap::new();
+        for counter in state.next_indices {
+            let change = match counter.change {
+                0 => ChangeChain::External,
+                1 => ChangeChain::Internal,
+                other => {
+                    return Err(GovernanceError::Serialization(format!(
+                        "Invalid change chain {} in wallet state",
+                        other
+                    )))
+                }
+            };
+            next_indices.insert((counter.account, change), counter.next_index);
+        }
+        self.next_indices = next_indices;
+        Ok(())
     }
 
     /// Get receiving address (external chain) for account
@@ -278,20 +552,150 @@ impl Bip44Wallet {
 
     /// Get account extended public key (can be shared to watch addresses)
     pub fn account_xpub(&self, account: u32) -> GovernanceResult<ExtendedPublicKey> {
-        // Derive to account level: m/44'/coin'/account'
-        let path_indices = vec![
-            0x80000000 | BIP44_PURPOSE,
-            0x80000000 | self.coin_type.value(),
-            0x80000000 | account,
-        ];
+        Ok(self.account_key(account)?.to_extended_public())
+    }
 
-        let mut current = self.master_private.clone();
-        for &index in &path_indices {
-            let (new_priv, _) = current.derive_child(index)?;
-            current = new_priv;
+    /// Signing keypair at `path_suffix` below an account key
+    ///
+    /// Matches the roster key that [`Multisig::from_xpubs`] derives from
+    /// this account's xpub with the same suffix.
+    ///
+    /// [`Multisig::from_xpubs`]: crate::governance::Multisig::from_xpubs
+    pub fn signing_keypair(
+        &self,
+        account: u32,
+        path_suffix: &[u32],
+    ) -> GovernanceResult<GovernanceKeypair> {
+        if let Some(&index) = path_suffix.iter().find(|&&i| i >= HARDENED_OFFSET) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Signing key suffix must not be hardened, got {}",
+                format_child_index(index, HardenedStyle::default())
+            )));
+        }
+
+        let mut key = self.account_key(account)?;
+        for &index in path_suffix {
+            key = key.derive_child(index)?.0;
+        }
+        GovernanceKeypair::from_secret_key(&key.private_key_bytes())
+    }
+
+    /// Account private key (m/44'/coin'/account'), derived once
+    fn account_key(&self, account: u32) -> GovernanceResult<ExtendedPrivateKey> {
+        if let Some(key) = self.cache.borrow().accounts.get(&account) {
+            return Ok(key.clone());
+        }
+
+        let mut key = self.master_private.clone();
+        for index in [BIP44_PURPOSE, self.coin_type.value(), account] {
+            key = key.derive_child(HARDENED_OFFSET | index)?.0;
+        }
+
+        self.cache
+            .borrow_mut()
+            .accounts
+            .insert(account, key.clone());
+        Ok(key)
+    }
+
+    /// Chain private key (m/44'/coin'/account'/change), derived once
+    fn chain_key(&self, account: u32, change: ChangeChain) -> GovernanceResult<ExtendedPrivateKey> {
+        if let Some(key) = self.cache.borrow().chains.get(&(account, change)) {
+            return Ok(key.clone());
+        }
+
+        let key = self.account_key(account)?.derive_child(change.value())?.0;
+
+        self.cache
+            .borrow_mut()
+            .chains
+            .insert((account, change), key.clone());
+        Ok(key)
+    }
+
+    /// Get the account extended public key as a Base58Check xpub/tpub string
+    ///
+    /// This is the form watch-only wallets such as Electrum and Sparrow import.
+    pub fn account_xpub_string(
+        &self,
+        account: u32,
+        network: NetworkType,
+    ) -> GovernanceResult<String> {
+        Ok(self.account_xpub(account)?.to_xpub_string(network))
+    }
+
+    /// Create a watch-only wallet from an account xpub/tpub string
+    pub fn from_account_xpub(xpub_str: &str) -> GovernanceResult<Bip44WatchOnlyWallet> {
+        Bip44WatchOnlyWallet::from_account_xpub(ExtendedPublicKey::from_xpub_string(xpub_str)?)
+    }
+}
+
+/// Depth of a BIP44 account key (m / purpose' / coin_type' / account')
+const BIP44_ACCOUNT_DEPTH: u8 = 3;
+
+/// Watch-only BIP44 wallet for a single account
+///
+/// Holds only the account extended public key, so it can derive the
+/// account's public keys but never its private keys.
+#[derive(Debug, Clone)]
+pub struct Bip44WatchOnlyWallet {
+    /// Account extended public key (m/44'/coin'/account')
+    account_xpub: ExtendedPublicKey,
+}
+
+impl Bip44WatchOnlyWallet {
+    /// Create from an account-level extended public key
+    pub fn from_account_xpub(account_xpub: ExtendedPublicKey) -> GovernanceResult<Self> {
+        if account_xpub.depth != BIP44_ACCOUNT_DEPTH || account_xpub.child_number & 0x80000000 == 0
+        {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Not a BIP44 account key: depth {}, child number {}",
+                account_xpub.depth, account_xpub.child_number
+            )));
         }
+        Ok(Bip44WatchOnlyWallet { account_xpub })
+    }
 
-        Ok(current.to_extended_public())
+    /// Account number this wallet watches
+    pub fn account(&self) -> u32 {
+        self.account_xpub.child_number & 0x7fffffff
+    }
+
+    /// Account extended public key
+    pub fn account_xpub(&self) -> &ExtendedPublicKey {
+        &self.account_xpub
+    }
+
+    /// Derive the public key for a change chain and address index
+    ///
+    /// `account` must match the account of the wallet's xpub; other accounts
+    /// need hardened derivation and cannot be reached without private keys.
+    pub fn derive_address_pubkey(
+        &self,
+        account: u32,
+        change: ChangeChain,
+        address_index: u32,
+    ) -> GovernanceResult<ExtendedPublicKey> {
+        if account != self.account() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Watch-only wallet is for account {}, not {}",
+                self.account(),
+                account
+            )));
+        }
+        self.account_xpub
+            .derive_child(change.value())?
+            .derive_child(address_index)
+    }
+
+    /// Get receiving (external chain) public key
+    pub fn receiving_address(&self, address_index: u32) -> GovernanceResult<ExtendedPublicKey> {
+        self.derive_address_pubkey(self.account(), ChangeChain::External, address_index)
+    }
+
+    /// Get change (internal chain) public key
+    pub fn change_address(&self, address_index: u32) -> GovernanceResult<ExtendedPublicKey> {
+        self.derive_address_pubkey(self.account(), ChangeChain::Internal, address_index)
     }
 }
 
@@ -302,9 +706,9 @@ mod tests {
     #[test]
     fn test_bip44_path_string() {
         let path = Bip44Path::bitcoin_mainnet(0, ChangeChain::External, 0);
-        assert_eq!(path.to_string(), "m/44/0'/0'/0/0");
+        assert_eq!(path.to_string(), "m/44'/0'/0'/0/0");
 
-        let parsed = Bip44Path::from_string("m/44'/0'/0'/0/0").unwrap();
+        let parsed: Bip44Path = "m/44'/0'/0'/0/0".parse().unwrap();
         assert_eq!(parsed.purpose, 44);
         assert_eq!(parsed.coin_type, CoinType::Bitcoin);
         assert_eq!(parsed.account, 0);
@@ -312,6 +716,89 @@ mod tests {
         assert_eq!(parsed.address_index, 0);
     }
 
+    #[test]
+    fn test_bip44_path_hardened_notation() {
+        for (input, style) in [
+            ("m/44'/0'/3'/1/9", HardenedStyle::Apostrophe),
+            ("m/44h/0h/3h/1/9", HardenedStyle::LowerH),
+            ("m/44H/0H/3H/1/9", HardenedStyle::UpperH),
+        ] {
+            let parsed: Bip44Path = input.parse().unwrap();
+            assert_eq!(parsed.hardened_style, style);
+            assert_eq!(parsed.account, 3);
+            assert_eq!(parsed.to_string(), input);
+        }
+
+        let path = Bip44Path::bitcoin_testnet(0, ChangeChain::External, 2)
+            .with_hardened_style(HardenedStyle::LowerH);
+        assert_eq!(path.to_string(), "m/44h/1h/0h/0/2");
+    }
+
+    #[test]
+    fn test_bip44_path_display_parse_roundtrip() {
+        let coin_types = [
+            CoinType::Bitcoin,
+            CoinType::BitcoinTestnet,
+            CoinType::Litecoin,
+            CoinType::Dogecoin,
+            CoinType::Ethereum,
+        ];
+        for purpose in SUPPORTED_PURPOSES {
+            for coin_type in coin_types {
+                let mut path = Bip44Path::new(coin_type, 2, ChangeChain::Internal, 7);
+                path.purpose = purpose;
+                let display = format!("{}", path);
+                assert_eq!(
+                    display,
+                    format!("m/{}'/{}'/2'/1/7", purpose, coin_type.value())
+                );
+                assert_eq!(display.parse::<Bip44Path>().unwrap(), path);
+            }
+        }
+
+        let path: Bip44Path = "m/84'/1'/0'/0/3".parse().unwrap();
+        assert_eq!(path.purpose, 84);
+        assert_eq!(path.coin_type, CoinType::BitcoinTestnet);
+        assert_eq!(path.address_index, 3);
+
+        let path: Bip44Path = "m/44'/144'/0'/0/0".parse().unwrap();
+        assert_eq!(path.coin_type, CoinType::Other(144));
+        assert_eq!(path.to_string(), "m/44'/144'/0'/0/0");
+        assert!("m/45'/0'/0'/0/0".parse::<Bip44Path>().is_err());
+    }
+
+    #[test]
+    fn test_bip44_path_hardening_is_strict() {
+        let cases = [
+            ("m/44/0'/0'/0/0", "purpose must be hardened"),
+            ("m/44'/0/0'/0/0", "coin type must be hardened"),
+            ("m/44'/0'/0/0/0", "account must be hardened"),
+            ("m/44'/0'/0'/0'/0", "change must not be hardened"),
+            ("m/44'/0'/0'/0/5'", "address index must not be hardened"),
+            ("m/44'/0'/0'/0h/5h", "change must not be hardened"),
+            ("m/44/0/0/0/0", "purpose must be hardened"),
+        ];
+        for (path, message) in cases {
+            let err = path.parse::<Bip44Path>().unwrap_err();
+            assert!(err.to_string().contains(message), "{}: {}", path, err);
+        }
+
+        let err = "m/44'/0'/x'/0/0".parse::<Bip44Path>().unwrap_err();
+        assert!(err.to_string().contains("Invalid account 'x''"), "{}", err);
+        assert!("m/44'/0'/0'/2/0".parse::<Bip44Path>().is_err());
+    }
+
+    #[test]
+    fn test_bip44_path_hash_ignores_notation() {
+        use std::collections::HashMap;
+
+        let mut cache = HashMap::new();
+        cache.insert("m/44'/0'/0'/0/1".parse::<Bip44Path>().unwrap(), "first");
+        let lower_h: Bip44Path = "m/44h/0h/0h/0/1".parse().unwrap();
+        assert_eq!(cache.get(&lower_h), Some(&"first"));
+        assert!(!cache.contains_key(&Bip44Path::bitcoin_mainnet(0, ChangeChain::External, 2)));
+    }
+
     #[test]
     fn test_bip44_path_derivation() {
         let seed = b"test seed for BIP44 derivation";
@@ -343,6 +830,66 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_bip44_wallet_address_range() {
+        let wallet =
+            Bip44Wallet::from_seed(b"test seed for BIP44 batches", CoinType::Bitcoin).unwrap();
+
+        let batch = wallet
+            .derive_address_range(0, ChangeChain::External, 5, 3)
+            .unwrap();
+        assert_eq!(batch.len(), 3);
+        for (offset, (private, _)) in batch.iter().enumerate() {
+            let (expected, _) = wallet.receiving_address(0, 5 + offset as u32).unwrap();
+            assert_eq!(private.private_key_bytes(), expected.private_key_bytes());
+        }
+
+        assert!(wallet
+            .derive_address_range(0, ChangeChain::External, 0, 0)
+            .unwrap()
+            .is_empty());
+        assert!(wallet
+            .derive_address_range(0, ChangeChain::External, HARDENED_OFFSET - 1, 2)
+            .is_err());
+        assert!(wallet
+            .derive_address_range(0, ChangeChain::External, u32::MAX, 1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_bip44_wallet_next_change_address_state() {
+        let seed = b"test seed for BIP44 counters";
+        let mut wallet = Bip44Wallet::from_seed(seed, CoinType::Bitcoin).unwrap();
+
+        for index in 0..3 {
+            let (next, _) = wallet.next_change_address(1).unwrap();
+            let (expected, _) = wallet.change_address(1, index).unwrap();
+            assert_eq!(next.private_key_bytes(), expected.private_key_bytes());
+        }
+        wallet.next_receiving_address(1).unwrap();
+        assert_eq!(wallet.next_address_index(1, ChangeChain::Internal), 3);
+        assert_eq!(wallet.next_address_index(1, ChangeChain::External), 1);
+        assert_eq!(wallet.next_address_index(0, ChangeChain::Internal), 0);
+
+        let saved = wallet.export_state().unwrap();
+        assert!(!saved.contains(&hex::encode(wallet.master_private.private_key_bytes())));
+
+        // Counters survive a reload into a fresh wallet for the same seed
+        let mut restored = Bip44Wallet::from_seed(seed, CoinType::Bitcoin).unwrap();
+        restored.import_state(&saved).unwrap();
+        let (next, _) = restored.next_change_address(1).unwrap();
+        let (expected, _) = wallet.change_address(1, 3).unwrap();
+        assert_eq!(next.private_key_bytes(), expected.private_key_bytes());
+
+        // State from another wallet or coin type is rejected
+        let mut other =
+            Bip44Wallet::from_seed(b"another seed entirely", CoinType::Bitcoin).unwrap();
+        assert!(other.import_state(&saved).is_err());
+        let mut testnet = Bip44Wallet::from_seed(seed, CoinType::BitcoinTestnet).unwrap();
+        assert!(testnet.import_state(&saved).is_err());
+        assert_eq!(testnet.next_address_index(1, ChangeChain::Internal), 0);
+    }
+
     #[test]
     fn test_coin_types() {
         assert_eq!(CoinType::Bitcoin.value(), 0);
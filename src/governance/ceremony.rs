@@ -0,0 +1,248 @@
+//! # Key Ceremony
+//!
+//! Records a multi-party governance key generation ceremony.
+//!
+//! Each participant contributes one public key. Once every participant has
+//! contributed, the ceremony is finalized into a transcript that all
+//! participants can cosign.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::{GovernanceKeypair, PublicKey};
+use crate::governance::signatures::{sign_message, verify_signature, Signature};
+
+/// A public key contributed by a ceremony participant
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyContribution {
+    /// Participant name
+    pub participant: String,
+    /// Compressed public key (hex)
+    pub public_key: String,
+    /// When the key was contributed
+    pub contributed_at: DateTime<Utc>,
+}
+
+/// A key generation ceremony in progress
+#[derive(Debug, Clone)]
+pub struct KeyCeremony {
+    participants: Vec<String>,
+    contributions: Vec<KeyContribution>,
+    started_at: DateTime<Utc>,
+}
+
+/// Final record of a completed key ceremony
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CeremonyTranscript {
+    /// Participants, in the order the ceremony was started with
+    pub participants: Vec<String>,
+    /// Key contributions, in participant order
+    pub contributions: Vec<KeyContribution>,
+    /// When the ceremony started
+    pub started_at: DateTime<Utc>,
+    /// When the ceremony was finalized
+    pub finalized_at: DateTime<Utc>,
+}
+
+impl KeyCeremony {
+    /// Start a ceremony for the given participants
+    ///
+    /// Duplicate names are ignored.
+    pub fn start(participants: Vec<String>) -> Self {
+        let mut unique = Vec::with_capacity(participants.len());
+        for participant in participants {
+            if !unique.contains(&participant) {
+                unique.push(participant);
+            }
+        }
+
+        Self {
+            participants: unique,
+            contributions: Vec::new(),
+            started_at: Utc::now(),
+        }
+    }
+
+    /// Record a participant's public key
+    pub fn add_participant_key(
+        &mut self,
+        participant: &str,
+        keypair: &GovernanceKeypair,
+    ) -> GovernanceResult<()> {
+        if !self.participants.iter().any(|p| p == participant) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "'{}' is not a ceremony participant",
+                participant
+            )));
+        }
+        if self
+            .contributions
+            .iter()
+            .any(|c| c.participant == participant)
+        {
+            return Err(GovernanceError::InvalidInput(format!(
+                "'{}' has already contributed a key",
+                participant
+            )));
+        }
+
+        let public_key = keypair.public_key().to_string();
+        if let Some(existing) = self
+            .contributions
+            .iter()
+            .find(|c| c.public_key == public_key)
+        {
+            return Err(GovernanceError::InvalidKey(format!(
+                "Key already contributed by '{}'",
+                existing.participant
+            )));
+        }
+
+        self.contributions.push(KeyContribution {
+            participant: participant.to_string(),
+            public_key,
+            contributed_at: Utc::now(),
+        });
+        Ok(())
+    }
+
+    /// Participants who have not yet contributed a key
+    pub fn pending_participants(&self) -> Vec<&str> {
+        self.participants
+            .iter()
+            .filter(|p| !self.contributions.iter().any(|c| &c.participant == *p))
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// When the ceremony started
+    pub fn started_at(&self) -> DateTime<Utc> {
+        self.started_at
+    }
+
+    /// Produce the transcript once every participant has contributed
+    pub fn finalize(&self) -> GovernanceResult<CeremonyTranscript> {
+        if self.participants.is_empty() {
+            return Err(GovernanceError::InvalidInput(
+                "Ceremony has no participants".to_string(),
+            ));
+        }
+        let pending = self.pending_participants();
+        if !pending.is_empty() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Missing key contributions from: {}",
+                pending.join(", ")
+            )));
+        }
+
+        let contributions = self
+            .participants
+            .iter()
+            .filter_map(|p| self.contributions.iter().find(|c| &c.participant == p))
+            .cloned()
+            .collect();
+
+        Ok(CeremonyTranscript {
+            participants: self.participants.clone(),
+            contributions,
+            started_at: self.started_at,
+            finalized_at: Utc::now(),
+        })
+    }
+}
+
+impl CeremonyTranscript {
+    /// Serialize the transcript to JSON
+    ///
+    /// This is the exact byte string participants sign.
+    pub fn to_json(&self) -> String {
+        // Plain strings and timestamps cannot fail to serialize
+        serde_json::to_string_pretty(self).unwrap_or_else(|_| "{}".to_string())
+    }
+
+    /// Parse a transcript from JSON
+    pub fn from_json(json: &str) -> GovernanceResult<Self> {
+        serde_json::from_str(json)
+            .map_err(|e| GovernanceError::Serialization(format!("Invalid transcript: {}", e)))
+    }
+
+    /// Cosign the transcript
+    pub fn sign_transcript(&self, keypair: &GovernanceKeypair) -> GovernanceResult<Signature> {
+        sign_message(&keypair.secret_key, self.to_json().as_bytes())
+    }
+
+    /// Verify a participant's cosignature
+    pub fn verify_transcript_signature(
+        &self,
+        signature: &Signature,
+        public_key: &PublicKey,
+    ) -> GovernanceResult<bool> {
+        verify_signature(signature, self.to_json().as_bytes(), public_key)
+    }
+
+    /// Public keys generated in the ceremony, in participant order
+    pub fn public_keys(&self) -> GovernanceResult<Vec<PublicKey>> {
+        self.contributions
+            .iter()
+            .map(|c| {
+                let bytes = hex::decode(&c.public_key).map_err(|e| {
+                    GovernanceError::InvalidKey(format!("Invalid public key hex: {}", e))
+                })?;
+                PublicKey::from_bytes(&bytes)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn test_ceremony_transcript_cosigning() {
+        let mut ceremony = KeyCeremony::start(names(&["alice", "bob"]));
+        let alice = GovernanceKeypair::generate().unwrap();
+        let bob = GovernanceKeypair::generate().unwrap();
+
+        // Contribution order does not affect the transcript order
+        ceremony.add_participant_key("bob", &bob).unwrap();
+        assert_eq!(ceremony.pending_participants(), vec!["alice"]);
+        ceremony.add_participant_key("alice", &alice).unwrap();
+
+        let transcript = ceremony.finalize().unwrap();
+        assert_eq!(
+            transcript.public_keys().unwrap(),
+            vec![alice.public_key(), bob.public_key()]
+        );
+
+        for keypair in [&alice, &bob] {
+            let signature = transcript.sign_transcript(keypair).unwrap();
+            assert!(transcript
+                .verify_transcript_signature(&signature, &keypair.public_key())
+                .unwrap());
+        }
+
+        let parsed = CeremonyTranscript::from_json(&transcript.to_json()).unwrap();
+        assert_eq!(parsed, transcript);
+    }
+
+    #[test]
+    fn test_ceremony_rejects_invalid_contributions() {
+        let mut ceremony = KeyCeremony::start(names(&["alice", "bob", "alice"]));
+        let keypair = GovernanceKeypair::generate().unwrap();
+
+        assert!(ceremony.add_participant_key("mallory", &keypair).is_err());
+        ceremony.add_participant_key("alice", &keypair).unwrap();
+        assert!(ceremony.add_participant_key("alice", &keypair).is_err());
+        assert!(matches!(
+            ceremony.add_participant_key("bob", &keypair),
+            Err(GovernanceError::InvalidKey(_))
+        ));
+        assert!(ceremony.finalize().is_err());
+    }
+}
@@ -0,0 +1,124 @@
+//! # Output Script Descriptors
+//!
+//! BIP380 descriptor checksums.
+//!
+//! Specification: https://github.com/bitcoin/bips/blob/master/bip-0380.mediawiki
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+
+/// Characters allowed in a descriptor, in checksum symbol order
+const INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+
+/// Characters used to render the checksum
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Length of a descriptor checksum
+pub const CHECKSUM_LENGTH: usize = 8;
+
+/// BCH generator constants
+const GENERATOR: [u64; 5] = [
+    0xf5dee51989,
+    0xa9fdca3312,
+    0x1bab10e32d,
+    0x3706b1677a,
+    0x644d626ffd,
+];
+
+/// Compute the 8-character BIP380 checksum of a descriptor (without `#`)
+pub fn checksum(descriptor: &str) -> GovernanceResult<String> {
+    let mut symbols = Vec::with_capacity(descriptor.len() * 2 + CHECKSUM_LENGTH);
+    let mut groups = Vec::with_capacity(3);
+
+    for c in descriptor.chars() {
+        let position = INPUT_CHARSET.find(c).ok_or_else(|| {
+            GovernanceError::InvalidInput(format!("Invalid descriptor character '{}'", c))
+        })? as u64;
+        symbols.push(position & 31);
+        groups.push(position >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.as_slice() {
+        [a] => symbols.push(*a),
+        [a, b] => symbols.push(a * 3 + b),
+        _ => {}
+    }
+    symbols.extend_from_slice(&[0; CHECKSUM_LENGTH]);
+
+    let value = polymod(&symbols) ^ 1;
+    Ok((0..CHECKSUM_LENGTH)
+        .map(|i| CHECKSUM_CHARSET[((value >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Append `#checksum` to a descriptor
+pub fn add_checksum(descriptor: &str) -> GovernanceResult<String> {
+    Ok(format!("{}#{}", descriptor, checksum(descriptor)?))
+}
+
+/// Strip and verify an optional `#checksum` suffix, returning the descriptor
+pub fn strip_checksum(descriptor: &str) -> GovernanceResult<&str> {
+    match descriptor.split_once('#') {
+        None => Ok(descriptor),
+        Some((body, given)) => {
+            if given.len() != CHECKSUM_LENGTH {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Descriptor checksum must be {} characters",
+                    CHECKSUM_LENGTH
+                )));
+            }
+            let expected = checksum(body)?;
+            if given != expected {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Descriptor checksum mismatch: expected {}, got {}",
+                    expected, given
+                )));
+            }
+            Ok(body)
+        }
+    }
+}
+
+/// BCH code polynomial modulus
+fn polymod(symbols: &[u64]) -> u64 {
+    let mut chk: u64 = 1;
+    for &value in symbols {
+        let top = chk >> 35;
+        chk = ((chk & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksum_vectors() {
+        assert_eq!(checksum("raw(deadbeef)").unwrap(), "89f8spxm");
+        assert_eq!(
+            checksum("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)").unwrap(),
+            "02wpgw69"
+        );
+    }
+
+    #[test]
+    fn test_strip_checksum() {
+        assert_eq!(
+            strip_checksum("raw(deadbeef)#89f8spxm").unwrap(),
+            "raw(deadbeef)"
+        );
+        assert_eq!(strip_checksum("raw(deadbeef)").unwrap(), "raw(deadbeef)");
+        assert!(strip_checksum("raw(deadbeef)#89f8spxn").is_err());
+        assert!(strip_checksum("raw(deadbeef)#89f8").is_err());
+        assert!(checksum("raw(deadbeef)\u{e9}").is_err());
+    }
+}
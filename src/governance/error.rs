@@ -1,7 +1,11 @@
 //! # Governance Error Types
 //!
 //! Error handling for governance operations.
+//!
+//! Every error has a stable [`code`](GovernanceError::code) and an
+//! [`ErrorCategory`]; both appear in the CLIs' JSON error output.
 
+use std::fmt;
 use thiserror::Error;
 
 /// Result type for governance operations
@@ -11,46 +15,230 @@ pub type GovernanceResult<T> = Result<T, GovernanceError>;
 #[derive(Error, Debug)]
 pub enum GovernanceError {
     /// Invalid key format or key generation failure
+    ///
+    /// Category: [`ErrorCategory::KeyManagement`]
+    /// Code: `E_KEY_FORMAT`
     #[error("Invalid key: {0}")]
     InvalidKey(String),
 
+    /// Child key derivation produced an invalid key (IL >= n or a zero key)
+    ///
+    /// BIP32 says to proceed with the next index; see
+    /// `bip32::derive_child_private_skipping`.
+    ///
+    /// Category: [`ErrorCategory::KeyManagement`]
+    /// Code: `E_KEY_CHILD_INDEX`
+    #[error("Invalid child index {0}: derived key is invalid, use the next index")]
+    InvalidChildIndex(u32),
+
     /// Signature verification failed
+    ///
+    /// Category: [`ErrorCategory::SignatureOperations`]
+    /// Code: `E_SIG_INVALID`
     #[error("Signature verification failed: {0}")]
     SignatureVerification(String),
 
     /// Invalid multisig configuration
+    ///
+    /// Category: [`ErrorCategory::MultisigLogic`]
+    /// Code: `E_MULTISIG_CONFIG`
     #[error("Invalid multisig configuration: {0}")]
     InvalidMultisig(String),
 
     /// Message format error
+    ///
+    /// Category: [`ErrorCategory::MessageFormat`]
+    /// Code: `E_MESSAGE_FORMAT`
     #[error("Message format error: {0}")]
     MessageFormat(String),
 
     /// Cryptographic operation failed
+    ///
+    /// Category: [`ErrorCategory::SignatureOperations`]
+    /// Code: `E_CRYPTO`
     #[error("Cryptographic operation failed: {0}")]
     Cryptographic(String),
 
     /// Serialization/deserialization error
+    ///
+    /// Category: [`ErrorCategory::MessageFormat`]
+    /// Code: `E_SERIALIZATION`
     #[error("Serialization error: {0}")]
     Serialization(String),
 
     /// Invalid threshold configuration
+    ///
+    /// Category: [`ErrorCategory::MultisigLogic`]
+    /// Code: `E_THRESHOLD`
     #[error("Invalid threshold: {threshold} of {total}")]
     InvalidThreshold { threshold: usize, total: usize },
 
     /// Insufficient signatures for multisig
+    ///
+    /// Category: [`ErrorCategory::MultisigLogic`]
+    /// Code: `E_SIG_INSUFFICIENT`
     #[error("Insufficient signatures: got {got}, need {need}")]
     InsufficientSignatures { got: usize, need: usize },
 
     /// Invalid signature format
+    ///
+    /// Category: [`ErrorCategory::SignatureOperations`]
+    /// Code: `E_SIG_FORMAT`
     #[error("Invalid signature format: {0}")]
     InvalidSignatureFormat(String),
 
+    /// Signing backend could not be reached (device unplugged, service down)
+    ///
+    /// Category: [`ErrorCategory::SignatureOperations`]
+    /// Code: `E_SIGNER_UNAVAILABLE`
+    #[error("Signer unavailable: {0}")]
+    SignerUnavailable(String),
+
+    /// Signing backend declined to sign (user rejected, policy denied)
+    ///
+    /// Category: [`ErrorCategory::SignatureOperations`]
+    /// Code: `E_SIGNING_REFUSED`
+    #[error("Signing refused: {0}")]
+    SigningRefused(String),
+
+    /// Character outside the Base58 alphabet
+    ///
+    /// Category: [`ErrorCategory::InvalidInput`]
+    /// Code: `E_BASE58`
+    #[error("Invalid base58 character '{character}' at position {position}")]
+    InvalidBase58Character { character: char, position: usize },
+
+    /// Encoded data failed its checksum
+    ///
+    /// Category: [`ErrorCategory::InvalidInput`]
+    /// Code: `E_CHECKSUM`
+    #[error("Checksum mismatch: {0}")]
+    ChecksumMismatch(String),
+
     /// Invalid input data
+    ///
+    /// Category: [`ErrorCategory::InvalidInput`]
+    /// Code: `E_INPUT`
     #[error("Invalid input: {0}")]
     InvalidInput(String),
 
     /// Feature not yet implemented
+    ///
+    /// Category: [`ErrorCategory::InvalidInput`]
+    /// Code: `E_NOT_IMPLEMENTED`
     #[error("Not implemented: {0}")]
     NotImplemented(String),
 }
+
+/// Broad classification of governance errors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCategory {
+    /// Key parsing, generation, and derivation
+    KeyManagement,
+    /// Signing, verification, and signature encoding
+    SignatureOperations,
+    /// Multisig configuration and threshold checks
+    MultisigLogic,
+    /// Governance message and serialization formats
+    MessageFormat,
+    /// Malformed or unsupported caller input
+    InvalidInput,
+}
+
+impl ErrorCategory {
+    /// Stable snake_case name, used in JSON error output
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCategory::KeyManagement => "key_management",
+            ErrorCategory::SignatureOperations => "signature_operations",
+            ErrorCategory::MultisigLogic => "multisig_logic",
+            ErrorCategory::MessageFormat => "message_format",
+            ErrorCategory::InvalidInput => "invalid_input",
+        }
+    }
+}
+
+impl fmt::Display for ErrorCategory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl GovernanceError {
+    /// Stable machine-readable code, e.g. `E_SIG_INVALID`
+    ///
+    /// Codes never change once released, so automation can match on them
+    /// instead of on the `Display` message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            GovernanceError::InvalidKey(_) => "E_KEY_FORMAT",
+            GovernanceError::InvalidChildIndex(_) => "E_KEY_CHILD_INDEX",
+            GovernanceError::SignatureVerification(_) => "E_SIG_INVALID",
+            GovernanceError::InvalidMultisig(_) => "E_MULTISIG_CONFIG",
+            GovernanceError::MessageFormat(_) => "E_MESSAGE_FORMAT",
+            GovernanceError::Cryptographic(_) => "E_CRYPTO",
+            GovernanceError::Serialization(_) => "E_SERIALIZATION",
+            GovernanceError::InvalidThreshold { .. } => "E_THRESHOLD",
+            GovernanceError::InsufficientSignatures { .. } => "E_SIG_INSUFFICIENT",
+            GovernanceError::InvalidSignatureFormat(_) => "E_SIG_FORMAT",
+            GovernanceError::SignerUnavailable(_) => "E_SIGNER_UNAVAILABLE",
+            GovernanceError::SigningRefused(_) => "E_SIGNING_REFUSED",
+            GovernanceError::InvalidBase58Character { .. } => "E_BASE58",
+            GovernanceError::ChecksumMismatch(_) => "E_CHECKSUM",
+            GovernanceError::InvalidInput(_) => "E_INPUT",
+            GovernanceError::NotImplemented(_) => "E_NOT_IMPLEMENTED",
+        }
+    }
+
+    /// Category of this error, for handling without matching every variant
+    pub fn category(&self) -> ErrorCategory {
+        match self {
+            GovernanceError::InvalidKey(_) | GovernanceError::InvalidChildIndex(_) => {
+                ErrorCategory::KeyManagement
+            }
+            GovernanceError::SignatureVerification(_)
+            | GovernanceError::Cryptographic(_)
+            | GovernanceError::InvalidSignatureFormat(_)
+            | GovernanceError::SignerUnavailable(_)
+            | GovernanceError::SigningRefused(_) => ErrorCategory::SignatureOperations,
+            GovernanceError::InvalidMultisig(_)
+            | GovernanceError::InvalidThreshold { .. }
+            | GovernanceError::InsufficientSignatures { .. } => ErrorCategory::MultisigLogic,
+            GovernanceError::MessageFormat(_) | GovernanceError::Serialization(_) => {
+                ErrorCategory::MessageFormat
+            }
+            GovernanceError::InvalidBase58Character { .. }
+            | GovernanceError::ChecksumMismatch(_)
+            | GovernanceError::InvalidInput(_)
+            | GovernanceError::NotImplemented(_) => ErrorCategory::InvalidInput,
+        }
+    }
+
+    /// Whether retrying the operation could plausibly succeed
+    ///
+    /// Only `SignerUnavailable` is retryable; every other variant describes
+    /// bad input or a failed check. `InvalidChildIndex` fails again at the
+    /// same index; the caller must move on to the next one instead. Variants
+    /// for other transient failures (e.g. an unavailable entropy source or
+    /// IPC errors) must return `true` here.
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            GovernanceError::SignerUnavailable(_) => true,
+            GovernanceError::InvalidKey(_)
+            | GovernanceError::InvalidChildIndex(_)
+            | GovernanceError::SignatureVerification(_)
+            | GovernanceError::InvalidMultisig(_)
+            | GovernanceError::MessageFormat(_)
+            | GovernanceError::Cryptographic(_)
+            | GovernanceError::Serialization(_)
+            | GovernanceError::InvalidThreshold { .. }
+            | GovernanceError::InsufficientSignatures { .. }
+            | GovernanceError::InvalidSignatureFormat(_)
+            | GovernanceError::SigningRefused(_)
+            | GovernanceError::InvalidBase58Character { .. }
+            | GovernanceError::ChecksumMismatch(_)
+            | GovernanceError::InvalidInput(_)
+            | GovernanceError::NotImplemented(_) => false,
+        }
+    }
+}
@@ -3,13 +3,22 @@
 //! Key generation and management for governance operations.
 
 use rand::rngs::OsRng;
-use secp256k1::{PublicKey as Secp256k1PublicKey, Secp256k1, SecretKey};
+use secp256k1::{
+    Parity, PublicKey as Secp256k1PublicKey, Scalar, Secp256k1, SecretKey,
+    XOnlyPublicKey as Secp256k1XOnlyPublicKey,
+};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use std::fmt;
+use std::str::FromStr;
 
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::hash160;
 
 /// A governance keypair for signing governance messages
-#[derive(Debug, Clone)]
+///
+/// `Display` and `Debug` never include the secret key, so keypairs are safe
+/// to log.
+#[derive(Clone)]
 pub struct GovernanceKeypair {
     pub secret_key: SecretKey,
     pub public_key: Secp256k1PublicKey,
@@ -67,6 +76,13 @@ impl GovernanceKeypair {
     pub fn public_key_bytes(&self) -> [u8; 33] {
         self.public_key.serialize()
     }
+
+    /// Key fingerprint: first 4 bytes of HASH160 of the compressed public key
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key_bytes())[..4]);
+        fingerprint
+    }
 }
 
 impl PublicKey {
@@ -92,6 +108,95 @@ impl PublicKey {
     pub fn to_uncompressed_bytes(&self) -> [u8; 65] {
         self.inner.serialize_uncompressed()
     }
+
+    /// Get the x-only public key (BIP340), dropping the parity
+    pub fn to_x_only(&self) -> XOnlyPublicKey {
+        XOnlyPublicKey::from_public_key(self)
+    }
+}
+
+/// An x-only public key (BIP340), as used by Taproot
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct XOnlyPublicKey([u8; 32]);
+
+impl XOnlyPublicKey {
+    /// Drop the parity byte of a compressed public key
+    pub fn from_public_key(public_key: &PublicKey) -> Self {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&public_key.to_bytes()[1..]);
+        Self(bytes)
+    }
+
+    /// Create from 32 bytes, which must be the x coordinate of a curve point
+    pub fn from_bytes(bytes: &[u8]) -> GovernanceResult<Self> {
+        let key = Secp256k1XOnlyPublicKey::from_slice(bytes).map_err(|e| {
+            GovernanceError::InvalidKey(format!("Invalid x-only public key: {}", e))
+        })?;
+        Ok(Self(key.serialize()))
+    }
+
+    /// Get the x-only public key bytes
+    pub fn to_bytes(&self) -> [u8; 32] {
+        self.0
+    }
+
+    /// Recover the even-parity public key with this x coordinate
+    pub fn lift_x(&self) -> GovernanceResult<PublicKey> {
+        Ok(PublicKey {
+            inner: Secp256k1PublicKey::from_x_only_public_key(self.inner()?, Parity::Even),
+        })
+    }
+
+    /// Tweak the key by `tweak * G` (BIP341 key path)
+    ///
+    /// For a Taproot output key, `tweak` is
+    /// `tagged_hash("TapTweak", internal_key || merkle_root)`, with the merkle
+    /// root omitted for key-path-only outputs.
+    pub fn tweaked(&self, tweak: &[u8; 32]) -> GovernanceResult<XOnlyPublicKey> {
+        let scalar = Scalar::from_be_bytes(*tweak)
+            .map_err(|_| GovernanceError::Cryptographic("Tweak exceeds curve order".to_string()))?;
+        let secp = Secp256k1::verification_only();
+        let (tweaked, _parity) = self
+            .inner()?
+            .add_tweak(&secp, &scalar)
+            .map_err(|e| GovernanceError::Cryptographic(format!("Invalid tweak: {}", e)))?;
+        Ok(Self(tweaked.serialize()))
+    }
+
+    fn inner(&self) -> GovernanceResult<Secp256k1XOnlyPublicKey> {
+        Secp256k1XOnlyPublicKey::from_slice(&self.0)
+            .map_err(|e| GovernanceError::InvalidKey(format!("Invalid x-only public key: {}", e)))
+    }
+}
+
+impl fmt::Display for XOnlyPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+impl FromStr for XOnlyPublicKey {
+    type Err = GovernanceError;
+
+    /// Parse a hex x-only public key, with or without a `0x` prefix
+    fn from_str(s: &str) -> GovernanceResult<Self> {
+        let bytes =
+            decode_hex_exact(s, "x-only public key", &[32]).map_err(GovernanceError::InvalidKey)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl Serialize for XOnlyPublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for XOnlyPublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
 }
 
 impl fmt::Display for PublicKey {
@@ -100,9 +205,73 @@ impl fmt::Display for PublicKey {
     }
 }
 
+impl FromStr for PublicKey {
+    type Err = GovernanceError;
+
+    /// Parse a hex public key, with or without a `0x` prefix
+    fn from_str(s: &str) -> GovernanceResult<Self> {
+        let bytes =
+            decode_hex_exact(s, "public key", &[33, 65]).map_err(GovernanceError::InvalidKey)?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+impl Serialize for PublicKey {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for PublicKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// Decode hex, optionally `0x`-prefixed
+pub(crate) fn decode_hex(s: &str, what: &str) -> Result<Vec<u8>, String> {
+    let s = s.trim();
+    let digits = s
+        .strip_prefix("0x")
+        .or_else(|| s.strip_prefix("0X"))
+        .unwrap_or(s);
+    hex::decode(digits).map_err(|e| format!("Invalid {} hex: {}", what, e))
+}
+
+/// Decode hex (optionally `0x`-prefixed) that must be one of `lengths` bytes
+pub(crate) fn decode_hex_exact(s: &str, what: &str, lengths: &[usize]) -> Result<Vec<u8>, String> {
+    let bytes = decode_hex(s, what)?;
+    if !lengths.contains(&bytes.len()) {
+        let expected: Vec<String> = lengths.iter().map(|len| len.to_string()).collect();
+        return Err(format!(
+            "Invalid {} length: expected {} bytes, got {}",
+            what,
+            expected.join(" or "),
+            bytes.len()
+        ));
+    }
+    Ok(bytes)
+}
+
 impl fmt::Display for GovernanceKeypair {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "GovernanceKeypair(pubkey: {})", self.public_key())
+        write!(
+            f,
+            "GovernanceKeypair(fingerprint={})",
+            hex::encode(self.fingerprint())
+        )
+    }
+}
+
+impl fmt::Debug for GovernanceKeypair {
+    /// Standalone keypairs are not part of a derivation tree, so depth is 0
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GovernanceKeypair")
+            .field("public_key", &format_args!("{}", self.public_key()))
+            .field("depth", &0)
+            .field("secret_key", &format_args!("<REDACTED>"))
+            .finish()
     }
 }
 
@@ -132,6 +301,99 @@ mod tests {
         assert_eq!(keypair1.public_key(), keypair2.public_key());
     }
 
+    #[test]
+    fn test_keypair_formatting_hides_secret_key() {
+        let keypair = GovernanceKeypair::from_secret_key(&[1u8; 32]).unwrap();
+        let secret_hex = hex::encode(keypair.secret_key_bytes());
+
+        let display = keypair.to_string();
+        assert_eq!(
+            display,
+            format!(
+                "GovernanceKeypair(fingerprint={})",
+                hex::encode(keypair.fingerprint())
+            )
+        );
+
+        let debug = format!("{:?}", keypair);
+        assert_eq!(
+            debug,
+            format!(
+                "GovernanceKeypair {{ public_key: {}, depth: 0, secret_key: <REDACTED> }}",
+                keypair.public_key()
+            )
+        );
+        assert!(!display.contains(&secret_hex) && !debug.contains(&secret_hex));
+        assert!(!format!("{:#?}", keypair).contains(&secret_hex));
+    }
+
+    #[test]
+    fn test_public_key_from_str() {
+        let public_key = GovernanceKeypair::generate().unwrap().public_key();
+        let hex = public_key.to_string();
+        assert_eq!(hex.parse::<PublicKey>().unwrap(), public_key);
+        assert_eq!(
+            format!("0x{}", hex).parse::<PublicKey>().unwrap(),
+            public_key
+        );
+
+        let err = hex[..64].parse::<PublicKey>().unwrap_err();
+        assert!(
+            err.to_string().contains("expected 33 or 65 bytes"),
+            "{}",
+            err
+        );
+        assert!("zz".parse::<PublicKey>().is_err());
+    }
+
+    #[test]
+    fn test_x_only_public_key() {
+        let public_key = GovernanceKeypair::generate().unwrap().public_key();
+        let x_only = public_key.to_x_only();
+        assert_eq!(x_only.to_bytes()[..], public_key.to_bytes()[1..]);
+        assert_eq!(
+            XOnlyPublicKey::from_bytes(&x_only.to_bytes()).unwrap(),
+            x_only
+        );
+
+        // lift_x recovers the even-parity key
+        let lifted = x_only.lift_x().unwrap();
+        assert_eq!(lifted.to_bytes()[0], 0x02);
+        assert_eq!(lifted.to_x_only(), x_only);
+
+        let json = serde_json::to_string(&x_only).unwrap();
+        assert_eq!(json, format!("\"{}\"", x_only));
+        assert_eq!(
+            serde_json::from_str::<XOnlyPublicKey>(&json).unwrap(),
+            x_only
+        );
+
+        // x = 5 is not on the curve
+        let mut not_on_curve = [0u8; 32];
+        not_on_curve[31] = 5;
+        assert!(XOnlyPublicKey::from_bytes(&not_on_curve).is_err());
+        assert!(XOnlyPublicKey::from_bytes(&[0u8; 33]).is_err());
+    }
+
+    #[test]
+    fn test_x_only_taproot_tweak() {
+        // BIP341 wallet test vector: key-path-only output
+        let internal: XOnlyPublicKey =
+            "d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"
+                .parse()
+                .unwrap();
+        let tweak = crate::governance::signatures::tagged_hash("TapTweak", &internal.to_bytes());
+        assert_eq!(
+            hex::encode(tweak),
+            "b86e7be8f39bab32a6f2c0443abbc210f0edac0e2c53d501b36b64437d9c6c70"
+        );
+        assert_eq!(
+            internal.tweaked(&tweak).unwrap().to_string(),
+            "53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343"
+        );
+        assert!(internal.tweaked(&[0xff; 32]).is_err());
+    }
+
     #[test]
     fn test_invalid_secret_key() {
         let invalid_bytes = [0u8; 31]; // Too short
@@ -0,0 +1,370 @@
+//! # Cloud KMS Signer
+//!
+//! [`GovernanceSigner`] backed by a cloud key management service holding a
+//! secp256k1 key (AWS KMS `ECC_SECG_P256K1`), so key material never leaves
+//! the service.
+//!
+//! The signer talks to the service through a [`KmsClient`]: the public key
+//! is fetched once with `GetPublicKey` (DER `SubjectPublicKeyInfo`), and
+//! each message digest is signed with `Sign` (`ECDSA_SHA_256`, message type
+//! `DIGEST`). KMS signatures are DER encoded and may have a high S value, so
+//! they are normalized before use.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::process::Command;
+
+use base64::Engine;
+use secp256k1::ecdsa::Signature as Secp256k1Signature;
+use sha2::{Digest, Sha256};
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::PublicKey;
+use crate::governance::signatures::{verify_signature, Signature};
+use crate::governance::signer::{GovernanceSigner, SignFuture};
+
+/// Future returned by [`KmsClient`] calls
+pub type KmsFuture<'a> = Pin<Box<dyn Future<Output = GovernanceResult<Vec<u8>>> + Send + 'a>>;
+
+/// DER `id-ecPublicKey` algorithm OID (1.2.840.10045.2.1)
+const OID_EC_PUBLIC_KEY: &[u8] = &[0x2a, 0x86, 0x48, 0xce, 0x3d, 0x02, 0x01];
+
+/// DER `secp256k1` curve OID (1.3.132.0.10)
+const OID_SECP256K1: &[u8] = &[0x2b, 0x81, 0x04, 0x00, 0x0a];
+
+const DER_SEQUENCE: u8 = 0x30;
+const DER_OID: u8 = 0x06;
+const DER_BIT_STRING: u8 = 0x03;
+
+/// Transport to a KMS service
+///
+/// Implementations map transport failures to
+/// [`GovernanceError::SignerUnavailable`] and access denials to
+/// [`GovernanceError::SigningRefused`].
+pub trait KmsClient: Send + Sync {
+    /// `GetPublicKey`: the key's DER `SubjectPublicKeyInfo`
+    fn get_public_key<'a>(&'a self, key_id: &'a str) -> KmsFuture<'a>;
+
+    /// `Sign` a SHA256 digest with `ECDSA_SHA_256`: a DER ECDSA signature
+    fn sign_digest<'a>(&'a self, key_id: &'a str, digest: &'a [u8; 32]) -> KmsFuture<'a>;
+}
+
+/// Governance signer for a secp256k1 key held in a KMS
+#[derive(Debug)]
+pub struct KmsSigner<C> {
+    client: C,
+    key_id: String,
+    public_key: PublicKey,
+}
+
+impl<C: KmsClient> KmsSigner<C> {
+    /// Fetch the public key of `key_id` and create a signer for it
+    pub async fn connect(client: C, key_id: &str) -> GovernanceResult<Self> {
+        let spki = client.get_public_key(key_id).await?;
+        let public_key = parse_spki_public_key(&spki)?;
+        Ok(Self {
+            client,
+            key_id: key_id.to_string(),
+            public_key,
+        })
+    }
+
+    /// KMS key ID or ARN
+    pub fn key_id(&self) -> &str {
+        &self.key_id
+    }
+}
+
+impl<C: KmsClient> GovernanceSigner for KmsSigner<C> {
+    fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a> {
+        Box::pin(async move {
+            let digest: [u8; 32] = Sha256::digest(message).into();
+            let der = self.client.sign_digest(&self.key_id, &digest).await?;
+            let mut inner = Secp256k1Signature::from_der(&der).map_err(|e| {
+                GovernanceError::InvalidSignatureFormat(format!("Invalid KMS signature: {}", e))
+            })?;
+            inner.normalize_s();
+
+            let signature = Signature { inner };
+            if !verify_signature(&signature, message, &self.public_key)? {
+                return Err(GovernanceError::SignatureVerification(format!(
+                    "KMS key {} returned a signature that does not match its public key",
+                    self.key_id
+                )));
+            }
+            Ok(signature)
+        })
+    }
+}
+
+/// Parse a DER `SubjectPublicKeyInfo` holding a secp256k1 key
+pub fn parse_spki_public_key(der: &[u8]) -> GovernanceResult<PublicKey> {
+    let invalid = |reason: &str| {
+        GovernanceError::InvalidKey(format!("Invalid SubjectPublicKeyInfo: {}", reason))
+    };
+
+    let (spki, rest) = read_der(der, DER_SEQUENCE).ok_or_else(|| invalid("expected SEQUENCE"))?;
+    if !rest.is_empty() {
+        return Err(invalid("trailing data"));
+    }
+    let (algorithm, spki) =
+        read_der(spki, DER_SEQUENCE).ok_or_else(|| invalid("expected algorithm SEQUENCE"))?;
+    let (key_type, algorithm) =
+        read_der(algorithm, DER_OID).ok_or_else(|| invalid("expected algorithm OID"))?;
+    let (curve, _) = read_der(algorithm, DER_OID).ok_or_else(|| invalid("expected curve OID"))?;
+    if key_type != OID_EC_PUBLIC_KEY {
+        return Err(invalid("not an EC public key"));
+    }
+    if curve != OID_SECP256K1 {
+        return Err(invalid("curve is not secp256k1"));
+    }
+
+    let (bits, _) = read_der(spki, DER_BIT_STRING).ok_or_else(|| invalid("expected BIT STRING"))?;
+    match bits.split_first() {
+        Some((0, key)) => PublicKey::from_bytes(key),
+        _ => Err(invalid("BIT STRING has unused bits")),
+    }
+}
+
+/// Read one DER element with the given tag, returning its contents and the remaining input
+fn read_der(input: &[u8], tag: u8) -> Option<(&[u8], &[u8])> {
+    let (&actual, input) = input.split_first()?;
+    if actual != tag {
+        return None;
+    }
+    let (&first, mut input) = input.split_first()?;
+    let len = if first < 0x80 {
+        first as usize
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 2 || input.len() < count {
+            return None;
+        }
+        let len = input[..count]
+            .iter()
+            .fold(0usize, |len, &b| (len << 8) | b as usize);
+        input = &input[count..];
+        len
+    };
+    (input.len() >= len).then(|| input.split_at(len))
+}
+
+/// [`KmsClient`] that runs the AWS CLI (`aws kms ...`)
+///
+/// Credentials, region, and profile are resolved by the AWS CLI as usual.
+#[derive(Debug, Clone)]
+pub struct AwsCliKmsClient {
+    program: String,
+}
+
+impl Default for AwsCliKmsClient {
+    fn default() -> Self {
+        Self {
+            program: "aws".to_string(),
+        }
+    }
+}
+
+impl AwsCliKmsClient {
+    /// Create a client using `aws` from `PATH`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Use a different AWS CLI executable
+    pub fn with_program(mut self, program: &str) -> Self {
+        self.program = program.to_string();
+        self
+    }
+
+    /// Run `aws kms <args>` and base64-decode its text output
+    fn run(&self, args: &[&str]) -> GovernanceResult<Vec<u8>> {
+        let output = Command::new(&self.program)
+            .arg("kms")
+            .args(args)
+            .args(["--output", "text"])
+            .output()
+            .map_err(|e| {
+                GovernanceError::SignerUnavailable(format!("Failed to run {}: {}", self.program, e))
+            })?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+            return Err(
+                if stderr.contains("AccessDenied") || stderr.contains("Disabled") {
+                    GovernanceError::SigningRefused(stderr)
+                } else {
+                    GovernanceError::SignerUnavailable(stderr)
+                },
+            );
+        }
+
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        base64::engine::general_purpose::STANDARD
+            .decode(stdout.trim())
+            .map_err(|e| GovernanceError::Serialization(format!("Invalid AWS CLI output: {}", e)))
+    }
+}
+
+impl KmsClient for AwsCliKmsClient {
+    fn get_public_key<'a>(&'a self, key_id: &'a str) -> KmsFuture<'a> {
+        Box::pin(async move {
+            self.run(&["get-public-key", "--key-id", key_id, "--query", "PublicKey"])
+        })
+    }
+
+    fn sign_digest<'a>(&'a self, key_id: &'a str, digest: &'a [u8; 32]) -> KmsFuture<'a> {
+        Box::pin(async move {
+            let message = base64::engine::general_purpose::STANDARD.encode(digest);
+            self.run(&[
+                "sign",
+                "--key-id",
+                key_id,
+                "--message",
+                &message,
+                "--message-type",
+                "DIGEST",
+                "--signing-algorithm",
+                "ECDSA_SHA_256",
+                "--query",
+                "Signature",
+            ])
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::signer::BlockingSigner;
+    use crate::governance::GovernanceKeypair;
+    use secp256k1::{Message, Secp256k1};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    /// secp256k1 group order
+    const CURVE_ORDER: [u8; 32] = [
+        0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff, 0xff,
+        0xfe, 0xba, 0xae, 0xdc, 0xe6, 0xaf, 0x48, 0xa0, 0x3b, 0xbf, 0xd2, 0x5e, 0x8c, 0xd0, 0x36,
+        0x41, 0x41,
+    ];
+
+    /// In-memory KMS that returns high-S signatures, as KMS may
+    struct StubKms {
+        keypair: GovernanceKeypair,
+        public_key_calls: AtomicUsize,
+        refuse: bool,
+    }
+
+    impl StubKms {
+        fn new(keypair: GovernanceKeypair) -> Self {
+            Self {
+                keypair,
+                public_key_calls: AtomicUsize::new(0),
+                refuse: false,
+            }
+        }
+    }
+
+    fn spki(public_key: &PublicKey) -> Vec<u8> {
+        let mut der = vec![DER_SEQUENCE, 0x56, DER_SEQUENCE, 0x10, DER_OID, 0x07];
+        der.extend_from_slice(OID_EC_PUBLIC_KEY);
+        der.extend_from_slice(&[DER_OID, 0x05]);
+        der.extend_from_slice(OID_SECP256K1);
+        der.extend_from_slice(&[DER_BIT_STRING, 0x42, 0x00]);
+        der.extend_from_slice(&public_key.to_uncompressed_bytes());
+        der
+    }
+
+    impl KmsClient for StubKms {
+        fn get_public_key<'a>(&'a self, _key_id: &'a str) -> KmsFuture<'a> {
+            Box::pin(async move {
+                self.public_key_calls.fetch_add(1, Ordering::SeqCst);
+                Ok(spki(&self.keypair.public_key()))
+            })
+        }
+
+        fn sign_digest<'a>(&'a self, _key_id: &'a str, digest: &'a [u8; 32]) -> KmsFuture<'a> {
+            Box::pin(async move {
+                if self.refuse {
+                    return Err(GovernanceError::SigningRefused(
+                        "AccessDeniedException".to_string(),
+                    ));
+                }
+                let message = Message::from_digest_slice(digest).unwrap();
+                let low_s = Secp256k1::new().sign_ecdsa(&message, &self.keypair.secret_key);
+
+                // s' = n - s
+                let mut compact = low_s.serialize_compact();
+                let mut borrow = 0i16;
+                for i in (0..32).rev() {
+                    let diff = CURVE_ORDER[i] as i16 - compact[32 + i] as i16 - borrow;
+                    borrow = (diff < 0) as i16;
+                    compact[32 + i] = diff.rem_euclid(256) as u8;
+                }
+                let high_s = Secp256k1Signature::from_compact(&compact).unwrap();
+                Ok(high_s.serialize_der().to_vec())
+            })
+        }
+    }
+
+    #[test]
+    fn test_parse_spki_public_key() {
+        let public_key = GovernanceKeypair::generate().unwrap().public_key();
+        let der = spki(&public_key);
+        assert_eq!(parse_spki_public_key(&der).unwrap(), public_key);
+
+        // secp384r1 (1.3.132.0.34)
+        let mut p384 = der.clone();
+        p384[19] = 0x22;
+        assert!(parse_spki_public_key(&p384).is_err());
+        assert!(parse_spki_public_key(&der[..der.len() - 1]).is_err());
+        assert!(parse_spki_public_key(&[]).is_err());
+    }
+
+    #[test]
+    fn test_kms_signer_signs_low_s() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let signer = runtime
+            .block_on(KmsSigner::connect(
+                StubKms::new(keypair.clone()),
+                "arn:aws:kms:us-east-1:111122223333:key/test",
+            ))
+            .unwrap();
+        assert_eq!(signer.public_key(), keypair.public_key());
+        assert_eq!(
+            signer.key_id(),
+            "arn:aws:kms:us-east-1:111122223333:key/test"
+        );
+
+        let signer = BlockingSigner::new(signer);
+        for message in [&b"first"[..], b"second"] {
+            let signature = signer.sign(message).unwrap();
+            assert!(verify_signature(&signature, message, &keypair.public_key()).unwrap());
+            assert_eq!(
+                signature,
+                crate::governance::signatures::sign_message(&keypair.secret_key, message).unwrap()
+            );
+        }
+        let signer = signer.into_inner();
+        assert_eq!(signer.client.public_key_calls.load(Ordering::SeqCst), 1);
+
+        // Refusals reach the caller unchanged
+        let mut stub = StubKms::new(keypair);
+        stub.refuse = true;
+        let signer = BlockingSigner::new(
+            runtime
+                .block_on(KmsSigner::connect(stub, "alias/governance"))
+                .unwrap(),
+        );
+        assert!(matches!(
+            signer.sign(b"message"),
+            Err(GovernanceError::SigningRefused(_))
+        ));
+    }
+}
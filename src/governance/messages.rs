@@ -1,11 +1,55 @@
 //! # Governance Messages
 //!
 //! Message formats for governance operations.
+//!
+//! Release messages can carry artifact hashes (`sha256:<hex>` keyed by
+//! artifact name), which are signed in sorted order after the version and
+//! commit. Budget decisions likewise sign their allocations sorted by
+//! recipient after the rationale. Every text field of both is
+//! length-prefixed, so no commit, rationale, or description can forge
+//! another artifact or allocation.
+//! Module approvals can record a security audit (report hash, rating, and
+//! auditor). Approvals without an audit sign the same bytes as before; audited
+//! approvals sign under a separate `MODULE-AUDIT:` tag with length-prefixed
+//! fields, so no plain approval can pass for an audited one.
+//!
+//! A [`GovernanceProposal`] puts a message to a vote; each vote is a
+//! signature over the proposal ID, the vote direction, and the message.
+//!
+//! Signing bytes start with [`SIGNING_DOMAIN`] so a governance signature
+//! cannot be replayed as a signature over another protocol's message.
+//!
+//! ## Migration
+//!
+//! The domain prefix was introduced after 0.1.0. Signatures made by 0.1.0
+//! tools cover the bare `message_bytes()` and do not verify against
+//! `to_signing_bytes()`; they must be re-signed.
+//!
+//! Budget decisions were a single `amount` and `purpose` before allocations
+//! were introduced. Such messages no longer parse, and their signatures
+//! cover `BUDGET:<amount>:<purpose>`; re-create them with
+//! [`GovernanceMessage::single_budget`] and re-sign.
+//!
+//! Release fields were signed without length prefixes
+//! (`RELEASE:<version>:<commit>`); release signatures made before the
+//! prefixes were added must be re-created.
 
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::rotation::KeyRotationProposal;
+use crate::governance::signatures::{sign_message, verify_signature};
+use crate::governance::{GovernanceKeypair, Multisig, PublicKey, Signature};
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "node")]
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+#[cfg(feature = "node")]
+use std::path::Path;
+use std::str::FromStr;
+use uuid::Uuid;
 
-// No error types needed for this module
+/// Domain separator prepended to every governance signing payload
+pub const SIGNING_DOMAIN: &[u8] = b"BLLVM-GOV-V1\x00";
 
 /// A governance message that can be signed
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -14,33 +58,306 @@ pub enum GovernanceMessage {
     Release {
         version: String,
         commit_hash: String,
+        /// Artifact name -> `sha256:<hex>`
+        #[serde(default)]
+        artifact_hashes: HashMap<String, String>,
     },
     /// A module approval message
     ModuleApproval {
         module_name: String,
         version: String,
+        /// SHA256 of the security audit report, as `sha256:<hex>`
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        audit_report_hash: Option<String>,
+        /// Security rating given by the audit
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        security_rating: Option<SecurityRating>,
+        /// Who performed the audit
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        auditor: Option<String>,
     },
     /// A budget decision message
-    BudgetDecision { amount: u64, purpose: String },
+    BudgetDecision {
+        /// Payments decided on
+        allocations: Vec<BudgetAllocation>,
+        /// Why the budget was decided
+        rationale: String,
+    },
+    /// Replace one roster key with another
+    KeyRotation { proposal: KeyRotationProposal },
+}
+
+/// Outcome of a module's security audit
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum SecurityRating {
+    /// Critical issues found
+    Critical,
+    /// High-severity issues found
+    High,
+    /// Medium-severity issues found
+    Medium,
+    /// Only low-severity issues found
+    Low,
+    /// No significant issues found
+    Minimal,
+}
+
+impl SecurityRating {
+    /// Lowercase name, as signed and accepted by `FromStr`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SecurityRating::Critical => "critical",
+            SecurityRating::High => "high",
+            SecurityRating::Medium => "medium",
+            SecurityRating::Low => "low",
+            SecurityRating::Minimal => "minimal",
+        }
+    }
+}
+
+impl fmt::Display for SecurityRating {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SecurityRating {
+    type Err = GovernanceError;
+
+    /// Parse a rating name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_ascii_lowercase().as_str() {
+            "critical" => Ok(SecurityRating::Critical),
+            "high" => Ok(SecurityRating::High),
+            "medium" => Ok(SecurityRating::Medium),
+            "low" => Ok(SecurityRating::Low),
+            "minimal" => Ok(SecurityRating::Minimal),
+            _ => Err(GovernanceError::InvalidInput(format!(
+                "Unknown security rating '{}' (expected critical, high, medium, low or minimal)",
+                s
+            ))),
+        }
+    }
+}
+
+/// One payment of a budget decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BudgetAllocation {
+    /// Recipient address or identifier
+    pub recipient: String,
+    /// Amount in satoshis
+    pub amount_satoshis: u64,
+    /// What the payment is for
+    #[serde(default)]
+    pub description: String,
 }
 
 impl GovernanceMessage {
-    /// Convert the message to bytes for signing
+    /// A module approval without audit information
+    pub fn module_approval(module_name: impl Into<String>, version: impl Into<String>) -> Self {
+        GovernanceMessage::ModuleApproval {
+            module_name: module_name.into(),
+            version: version.into(),
+            audit_report_hash: None,
+            security_rating: None,
+            auditor: None,
+        }
+    }
+
+    /// A budget decision paying each of `allocations`
+    ///
+    /// Fails if there are no allocations.
+    pub fn budget_decision(
+        allocations: Vec<BudgetAllocation>,
+        rationale: impl Into<String>,
+    ) -> GovernanceResult<Self> {
+        if allocations.is_empty() {
+            return Err(GovernanceError::InvalidInput(
+                "budget decision needs at least one allocation".to_string(),
+            ));
+        }
+        Ok(GovernanceMessage::BudgetDecision {
+            allocations,
+            rationale: rationale.into(),
+        })
+    }
+
+    /// A budget decision paying `amount_satoshis` to a single recipient
+    pub fn single_budget(
+        recipient: impl Into<String>,
+        amount_satoshis: u64,
+        purpose: impl Into<String>,
+    ) -> Self {
+        GovernanceMessage::BudgetDecision {
+            allocations: vec![BudgetAllocation {
+                recipient: recipient.into(),
+                amount_satoshis,
+                description: String::new(),
+            }],
+            rationale: purpose.into(),
+        }
+    }
+
+    /// Total satoshis allocated by a budget decision
+    ///
+    /// `None` if the total overflows a `u64` or the message is not a budget
+    /// decision.
+    pub fn total_amount(&self) -> Option<u64> {
+        match self {
+            GovernanceMessage::BudgetDecision { allocations, .. } => allocations
+                .iter()
+                .try_fold(0u64, |total, a| total.checked_add(a.amount_satoshis)),
+            _ => None,
+        }
+    }
+
+    /// Domain separator prepended by `to_signing_bytes`
+    pub fn signing_domain() -> &'static [u8] {
+        SIGNING_DOMAIN
+    }
+
+    /// Convert the message to bytes for signing: `SIGNING_DOMAIN || message_bytes()`
     pub fn to_signing_bytes(&self) -> Vec<u8> {
+        let mut bytes = Self::signing_domain().to_vec();
+        bytes.extend_from_slice(&self.message_bytes());
+        bytes
+    }
+
+    /// Message-specific bytes, without the domain separator
+    pub fn message_bytes(&self) -> Vec<u8> {
         // Use a standardized format for signing
         match self {
             GovernanceMessage::Release {
                 version,
                 commit_hash,
-            } => format!("RELEASE:{}:{}", version, commit_hash).into_bytes(),
+                artifact_hashes,
+            } => {
+                let mut bytes = format!(
+                    "RELEASE:{}:{}",
+                    length_prefixed(version),
+                    length_prefixed(commit_hash)
+                );
+                let mut artifacts: Vec<_> = artifact_hashes.iter().collect();
+                artifacts.sort();
+                for (name, hash) in artifacts {
+                    bytes.push_str(&format!(
+                        "\nARTIFACT:{}:{}",
+                        length_prefixed(name),
+                        length_prefixed(hash)
+                    ));
+                }
+                bytes.into_bytes()
+            }
             GovernanceMessage::ModuleApproval {
                 module_name,
                 version,
-            } => format!("MODULE:{}:{}", module_name, version).into_bytes(),
-            GovernanceMessage::BudgetDecision { amount, purpose } => {
-                format!("BUDGET:{}:{}", amount, purpose).into_bytes()
+                audit_report_hash,
+                security_rating,
+                auditor,
+            } => {
+                if audit_report_hash.is_none() && security_rating.is_none() && auditor.is_none() {
+                    return format!("MODULE:{}:{}", module_name, version).into_bytes();
+                }
+                // A distinct tag keeps a plain approval's free-form version
+                // from imitating an audit
+                let mut bytes = format!(
+                    "MODULE-AUDIT:{}:{}",
+                    length_prefixed(module_name),
+                    length_prefixed(version)
+                );
+                if let Some(hash) = audit_report_hash {
+                    bytes.push_str(&format!("\nAUDIT:{}", length_prefixed(hash)));
+                }
+                if let Some(rating) = security_rating {
+                    bytes.push_str(&format!("\nRATING:{}", rating));
+                }
+                if let Some(auditor) = auditor {
+                    bytes.push_str(&format!("\nAUDITOR:{}", length_prefixed(auditor)));
+                }
+                bytes.into_bytes()
             }
+            GovernanceMessage::BudgetDecision {
+                allocations,
+                rationale,
+            } => {
+                let mut bytes = format!("BUDGET:{}", length_prefixed(rationale));
+                let mut allocations: Vec<_> = allocations
+                    .iter()
+                    .map(|a| (&a.recipient, a.amount_satoshis, &a.description))
+                    .collect();
+                allocations.sort();
+                for (recipient, amount, description) in allocations {
+                    bytes.push_str(&format!(
+                        "\nALLOCATION:{}:{}:{}",
+                        length_prefixed(recipient),
+                        amount,
+                        length_prefixed(description)
+                    ));
+                }
+                bytes.into_bytes()
+            }
+            GovernanceMessage::KeyRotation { proposal } => format!(
+                "ROTATE:{}:{}:{}",
+                hex::encode(proposal.old_pubkey.to_compressed_bytes()),
+                hex::encode(proposal.new_pubkey.to_compressed_bytes()),
+                proposal.reason
+            )
+            .into_bytes(),
+        }
+    }
+
+    /// Add an artifact hash to a release message
+    ///
+    /// `sha256` may be bare hex or prefixed with `sha256:`; it is stored as
+    /// `sha256:<lowercase hex>`. Names must be non-empty and free of `:`,
+    /// path separators, and control characters, and the hash must be 64 hex
+    /// digits. Other message types are returned unchanged.
+    pub fn with_artifact(mut self, name: &str, sha256: &str) -> GovernanceResult<Self> {
+        if let GovernanceMessage::Release {
+            artifact_hashes, ..
+        } = &mut self
+        {
+            if name.is_empty()
+                || name
+                    .chars()
+                    .any(|c| matches!(c, ':' | '/' | '\\') || c.is_control())
+            {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Invalid artifact name '{}'",
+                    name.escape_debug()
+                )));
+            }
+            let hash = normalize_sha256(sha256);
+            let digits = &hash["sha256:".len()..];
+            if digits.len() != 64 || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Artifact hash for '{}' must be 64 hex digits",
+                    name
+                )));
+            }
+            artifact_hashes.insert(name.to_string(), hash);
+        }
+        Ok(self)
+    }
+
+    /// Record a security audit on a module approval
+    ///
+    /// `hash` is the SHA256 of the audit report, bare hex or prefixed with
+    /// `sha256:`; it is stored as `sha256:<lowercase hex>`. Other message
+    /// types are returned unchanged.
+    pub fn with_audit(mut self, hash: &str, rating: SecurityRating, auditor: &str) -> Self {
+        if let GovernanceMessage::ModuleApproval {
+            audit_report_hash,
+            security_rating,
+            auditor: audited_by,
+            ..
+        } = &mut self
+        {
+            *audit_report_hash = Some(normalize_sha256(hash));
+            *security_rating = Some(rating);
+            *audited_by = Some(auditor.to_string());
         }
+        self
     }
 
     /// Get a human-readable description of the message
@@ -49,28 +366,239 @@ impl GovernanceMessage {
             GovernanceMessage::Release {
                 version,
                 commit_hash,
-            } => {
-                format!("Release {} (commit: {})", version, commit_hash)
-            }
+                artifact_hashes,
+            } => match artifact_hashes.len() {
+                0 => format!("Release {} (commit: {})", version, commit_hash),
+                count => format!(
+                    "Release {} (commit: {}, {} artifacts)",
+                    version, commit_hash, count
+                ),
+            },
             GovernanceMessage::ModuleApproval {
                 module_name,
                 version,
+                security_rating,
+                ..
+            } => match security_rating {
+                Some(rating) => format!(
+                    "Approve module {} version {} (security rating: {})",
+                    module_name, version, rating
+                ),
+                None => format!("Approve module {} version {}", module_name, version),
+            },
+            GovernanceMessage::BudgetDecision {
+                allocations,
+                rationale,
             } => {
-                format!("Approve module {} version {}", module_name, version)
-            }
-            GovernanceMessage::BudgetDecision { amount, purpose } => {
-                format!("Budget decision: {} satoshis for {}", amount, purpose)
+                let total = self
+                    .total_amount()
+                    .map_or_else(|| "more than u64::MAX".to_string(), |t| t.to_string());
+                match allocations.as_slice() {
+                    [allocation] => format!(
+                        "Budget decision: {} satoshis to {} for {}",
+                        total, allocation.recipient, rationale
+                    ),
+                    _ => format!(
+                        "Budget decision: {} satoshis to {} recipients for {}",
+                        total,
+                        allocations.len(),
+                        rationale
+                    ),
+                }
             }
+            GovernanceMessage::KeyRotation { proposal } => format!(
+                "Rotate key {} to {} ({})",
+                proposal.old_pubkey, proposal.new_pubkey, proposal.reason
+            ),
         }
     }
 }
 
+/// `<byte length>:<value>`, so a free-text field cannot spill into the next
+fn length_prefixed(value: &str) -> String {
+    format!("{}:{}", value.len(), value)
+}
+
+/// `sha256:<lowercase hex>` from bare or `sha256:`-prefixed hex
+fn normalize_sha256(sha256: &str) -> String {
+    let hex = sha256.trim().to_ascii_lowercase();
+    let hex = hex.strip_prefix("sha256:").unwrap_or(&hex);
+    format!("sha256:{}", hex)
+}
+
+/// Hash every file in `dir` (not recursive) for a release message
+///
+/// Returns artifact name (file name) -> `sha256:<hex>`. Requires the `node`
+/// feature.
+#[cfg(feature = "node")]
+pub fn hash_artifacts_dir(dir: &Path) -> GovernanceResult<HashMap<String, String>> {
+    let read_error = |path: &Path, e: std::io::Error| {
+        GovernanceError::InvalidInput(format!("Failed to read {}: {}", path.display(), e))
+    };
+
+    let mut hashes = HashMap::new();
+    for entry in std::fs::read_dir(dir).map_err(|e| read_error(dir, e))? {
+        let path = entry.map_err(|e| read_error(dir, e))?.path();
+        if !path.is_file() {
+            continue;
+        }
+        let contents = std::fs::read(&path).map_err(|e| read_error(&path, e))?;
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        hashes.insert(
+            name,
+            format!("sha256:{}", hex::encode(Sha256::digest(contents))),
+        );
+    }
+    Ok(hashes)
+}
+
 impl fmt::Display for GovernanceMessage {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.description())
     }
 }
 
+/// A governance message put to a vote
+///
+/// Votes are `(public key hex, signature)` pairs. Timestamps are Unix
+/// seconds.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceProposal {
+    /// Proposal ID
+    pub id: Uuid,
+    /// Short title
+    pub title: String,
+    /// Description of the proposal
+    pub description: String,
+    /// Message enacted if the proposal passes
+    pub message: GovernanceMessage,
+    /// Proposer public key (hex)
+    pub proposed_by: String,
+    /// Creation time
+    pub proposed_at: u64,
+    /// Votes are accepted until this time
+    pub voting_ends_at: u64,
+    /// Votes in favor
+    #[serde(default)]
+    pub votes_for: Vec<(String, Signature)>,
+    /// Votes against
+    #[serde(default)]
+    pub votes_against: Vec<(String, Signature)>,
+}
+
+impl GovernanceProposal {
+    /// Create a proposal open for voting for `duration_secs`
+    pub fn new(
+        title: impl Into<String>,
+        description: impl Into<String>,
+        message: GovernanceMessage,
+        proposer_key: &PublicKey,
+        duration_secs: u64,
+    ) -> Self {
+        let proposed_at = now_secs();
+        Self {
+            id: Uuid::new_v4(),
+            title: title.into(),
+            description: description.into(),
+            message,
+            proposed_by: proposer_key.to_string(),
+            proposed_at,
+            voting_ends_at: proposed_at.saturating_add(duration_secs),
+            votes_for: Vec::new(),
+            votes_against: Vec::new(),
+        }
+    }
+
+    /// Bytes signed by a vote: `SIGNING_DOMAIN || PROPOSAL:<id>:<FOR|AGAINST>\n || message_bytes()`
+    pub fn vote_signing_bytes(&self, in_favor: bool) -> Vec<u8> {
+        let direction = if in_favor { "FOR" } else { "AGAINST" };
+        let mut bytes = SIGNING_DOMAIN.to_vec();
+        bytes.extend_from_slice(format!("PROPOSAL:{}:{}\n", self.id, direction).as_bytes());
+        bytes.extend_from_slice(&self.message.message_bytes());
+        bytes
+    }
+
+    /// Whether votes are still accepted
+    pub fn is_open(&self) -> bool {
+        now_secs() <= self.voting_ends_at
+    }
+
+    /// Sign and record a vote
+    ///
+    /// Fails if voting has ended or the key has already voted.
+    pub fn cast_vote(
+        &mut self,
+        keypair: &GovernanceKeypair,
+        in_favor: bool,
+    ) -> GovernanceResult<()> {
+        if !self.is_open() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Voting on proposal {} has ended",
+                self.id
+            )));
+        }
+
+        let voter = keypair.public_key().to_string();
+        if self
+            .votes_for
+            .iter()
+            .chain(&self.votes_against)
+            .any(|(key, _)| *key == voter)
+        {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Key {} has already voted on proposal {}",
+                voter, self.id
+            )));
+        }
+
+        let signature = sign_message(&keypair.secret_key, &self.vote_signing_bytes(in_favor))?;
+        if in_favor {
+            self.votes_for.push((voter, signature));
+        } else {
+            self.votes_against.push((voter, signature));
+        }
+        Ok(())
+    }
+
+    /// Whether enough members of `multisig` have validly voted in favor
+    ///
+    /// Votes from keys outside the multisig, invalid signatures, and
+    /// repeated keys are not counted.
+    pub fn is_passed(&self, multisig: &Multisig) -> bool {
+        self.count_votes(multisig, true) >= multisig.threshold()
+    }
+
+    /// Number of distinct members of `multisig` with a valid vote in the given direction
+    pub fn count_votes(&self, multisig: &Multisig, in_favor: bool) -> usize {
+        let message = self.vote_signing_bytes(in_favor);
+        let votes = if in_favor {
+            &self.votes_for
+        } else {
+            &self.votes_against
+        };
+        let mut counted = HashSet::new();
+        for (key, signature) in votes {
+            let Ok(public_key) = key.parse::<PublicKey>() else {
+                continue;
+            };
+            if multisig.public_keys().contains(&public_key)
+                && verify_signature(signature, &message, &public_key).unwrap_or(false)
+            {
+                counted.insert(public_key);
+            }
+        }
+        counted.len()
+    }
+}
+
+/// Current Unix time in seconds
+pub(crate) fn now_secs() -> u64 {
+    chrono::Utc::now().timestamp().max(0) as u64
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -80,21 +608,113 @@ mod tests {
         let message = GovernanceMessage::Release {
             version: "v1.0.0".to_string(),
             commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
         };
 
-        let bytes = message.to_signing_bytes();
-        assert_eq!(bytes, b"RELEASE:v1.0.0:abc123");
+        let bytes = message.message_bytes();
+        assert_eq!(bytes, b"RELEASE:6:v1.0.0:6:abc123");
+        assert_eq!(
+            message.to_signing_bytes(),
+            b"BLLVM-GOV-V1\x00RELEASE:6:v1.0.0:6:abc123"
+        );
         assert_eq!(message.description(), "Release v1.0.0 (commit: abc123)");
     }
 
     #[test]
-    fn test_module_approval_message() {
-        let message = GovernanceMessage::ModuleApproval {
-            module_name: "lightning".to_string(),
-            version: "v2.0.0".to_string(),
+    fn test_release_message_artifacts() {
+        let release = |artifacts: &[(&str, &str)]| {
+            artifacts.iter().fold(
+                GovernanceMessage::Release {
+                    version: "v1.0.0".to_string(),
+                    commit_hash: "abc123".to_string(),
+                    artifact_hashes: HashMap::new(),
+                },
+                |message, (name, hash)| message.with_artifact(name, hash).unwrap(),
+            )
+        };
+        let linux = "aa11".repeat(16);
+        let darwin = "bb22".repeat(16);
+
+        let message = release(&[
+            (
+                "bllvm-node-linux-x86_64",
+                &format!("SHA256:{}", linux.to_uppercase()),
+            ),
+            ("bllvm-node-darwin-arm64", &darwin),
+        ]);
+        assert_eq!(
+            message.message_bytes(),
+            format!(
+                "RELEASE:6:v1.0.0:6:abc123\nARTIFACT:23:bllvm-node-darwin-arm64:71:sha256:{}\nARTIFACT:23:bllvm-node-linux-x86_64:71:sha256:{}",
+                darwin, linux
+            )
+            .into_bytes()
+        );
+        assert_eq!(
+            message.description(),
+            "Release v1.0.0 (commit: abc123, 2 artifacts)"
+        );
+
+        // Insertion order does not affect the signed bytes
+        let reordered = release(&[
+            ("bllvm-node-darwin-arm64", &darwin),
+            ("bllvm-node-linux-x86_64", &linux),
+        ]);
+        assert_eq!(message.to_signing_bytes(), reordered.to_signing_bytes());
+
+        // Names with separators and hashes that are not SHA256 hex are refused
+        let empty = release(&[]);
+        for (name, hash) in [
+            ("", linux.as_str()),
+            ("evil:sha256", linux.as_str()),
+            ("bin\nARTIFACT", linux.as_str()),
+            ("../bin", linux.as_str()),
+            ("bin", "aa11"),
+            ("bin", &"zz".repeat(32)),
+        ] {
+            assert!(empty.clone().with_artifact(name, hash).is_err(), "{}", name);
+        }
+
+        let budget = GovernanceMessage::single_budget("bc1qdev", 1, "dev");
+        assert_eq!(budget.clone().with_artifact("a", "00").unwrap(), budget);
+    }
+
+    #[test]
+    fn test_release_commit_cannot_forge_artifacts() {
+        let hash = format!("sha256:{}", "00".repeat(32));
+        let forged = GovernanceMessage::Release {
+            version: "v1.0.0".to_string(),
+            commit_hash: format!("abc123\nARTIFACT:evil:{}", hash),
+            artifact_hashes: HashMap::new(),
+        };
+        let with_artifact = GovernanceMessage::Release {
+            version: "v1.0.0".to_string(),
+            commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
+        }
+        .with_artifact("evil", &hash)
+        .unwrap();
+        assert_ne!(forged.message_bytes(), with_artifact.message_bytes());
+
+        // Nor can a version absorb the commit
+        let shifted = GovernanceMessage::Release {
+            version: "v1.0.0:6".to_string(),
+            commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
+        };
+        let plain = GovernanceMessage::Release {
+            version: "v1.0.0".to_string(),
+            commit_hash: "6:abc123".to_string(),
+            artifact_hashes: HashMap::new(),
         };
+        assert_ne!(shifted.message_bytes(), plain.message_bytes());
+    }
+
+    #[test]
+    fn test_module_approval_message() {
+        let message = GovernanceMessage::module_approval("lightning", "v2.0.0");
 
-        let bytes = message.to_signing_bytes();
+        let bytes = message.message_bytes();
         assert_eq!(bytes, b"MODULE:lightning:v2.0.0");
         assert_eq!(
             message.description(),
@@ -102,19 +722,162 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_module_approval_audit() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let message = GovernanceMessage::module_approval("lightning", "v2.0.0").with_audit(
+            &"AB".repeat(32),
+            SecurityRating::Low,
+            "Example Audits",
+        );
+
+        assert_eq!(
+            message.message_bytes(),
+            format!(
+                "MODULE-AUDIT:9:lightning:6:v2.0.0\nAUDIT:71:sha256:{}\nRATING:low\nAUDITOR:14:Example Audits",
+                "ab".repeat(32)
+            )
+            .into_bytes()
+        );
+        assert_eq!(
+            message.description(),
+            "Approve module lightning version v2.0.0 (security rating: low)"
+        );
+
+        // The audit survives serialization and is covered by the signature
+        let json = serde_json::to_string(&message).unwrap();
+        let parsed: GovernanceMessage = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed, message);
+        let signature = sign_message(&keypair.secret_key, &parsed.to_signing_bytes()).unwrap();
+        assert!(verify_signature(
+            &signature,
+            &message.to_signing_bytes(),
+            &keypair.public_key()
+        )
+        .unwrap());
+        let unaudited = GovernanceMessage::module_approval("lightning", "v2.0.0");
+        assert!(!verify_signature(
+            &signature,
+            &unaudited.to_signing_bytes(),
+            &keypair.public_key()
+        )
+        .unwrap());
+        let rerated =
+            message
+                .clone()
+                .with_audit(&"ab".repeat(32), SecurityRating::High, "Example Audits");
+        assert!(!verify_signature(
+            &signature,
+            &rerated.to_signing_bytes(),
+            &keypair.public_key()
+        )
+        .unwrap());
+
+        // Approvals serialized before audits were added still parse
+        let legacy = r#"{"ModuleApproval":{"module_name":"lightning","version":"v2.0.0"}}"#;
+        assert_eq!(
+            serde_json::from_str::<GovernanceMessage>(legacy).unwrap(),
+            unaudited
+        );
+
+        assert_eq!(
+            "MINIMAL".parse::<SecurityRating>().unwrap(),
+            SecurityRating::Minimal
+        );
+        assert!("unknown".parse::<SecurityRating>().is_err());
+    }
+
+    #[test]
+    fn test_plain_module_approval_cannot_pass_as_audited() {
+        let plain = GovernanceMessage::module_approval("m", "1.0\nRATING:minimal");
+        let rated = GovernanceMessage::ModuleApproval {
+            module_name: "m".to_string(),
+            version: "1.0".to_string(),
+            audit_report_hash: None,
+            security_rating: Some(SecurityRating::Minimal),
+            auditor: None,
+        };
+        assert_ne!(plain.to_signing_bytes(), rated.to_signing_bytes());
+
+        let audited = GovernanceMessage::module_approval("m", "1.0").with_audit(
+            &"ab".repeat(32),
+            SecurityRating::Minimal,
+            "Example Audits",
+        );
+        let imitation = GovernanceMessage::module_approval(
+            "m",
+            format!(
+                "1.0\nAUDIT:sha256:{}\nRATING:minimal\nAUDITOR:Example Audits",
+                "ab".repeat(32)
+            ),
+        );
+        assert_ne!(audited.to_signing_bytes(), imitation.to_signing_bytes());
+    }
+
     #[test]
     fn test_budget_decision_message() {
-        let message = GovernanceMessage::BudgetDecision {
-            amount: 1000000,
-            purpose: "development".to_string(),
+        let message = GovernanceMessage::single_budget("bc1qdev", 1000000, "development");
+
+        let bytes = message.message_bytes();
+        assert_eq!(
+            bytes,
+            b"BUDGET:11:development\nALLOCATION:7:bc1qdev:1000000:0:"
+        );
+        assert_eq!(
+            message.description(),
+            "Budget decision: 1000000 satoshis to bc1qdev for development"
+        );
+        assert_eq!(message.total_amount(), Some(1000000));
+    }
+
+    #[test]
+    fn test_budget_decision_allocations() {
+        let allocation = |recipient: &str, amount_satoshis: u64| BudgetAllocation {
+            recipient: recipient.to_string(),
+            amount_satoshis,
+            description: format!("pay {}", recipient),
+        };
+        let budget = |allocations: Vec<BudgetAllocation>| GovernanceMessage::BudgetDecision {
+            allocations,
+            rationale: "Q3 grants".to_string(),
         };
 
-        let bytes = message.to_signing_bytes();
-        assert_eq!(bytes, b"BUDGET:1000000:development");
+        let message = budget(vec![allocation("bob", 200), allocation("alice", 100)]);
+        assert_eq!(
+            message.message_bytes(),
+            b"BUDGET:9:Q3 grants\nALLOCATION:5:alice:100:9:pay alice\nALLOCATION:3:bob:200:7:pay bob"
+        );
+        assert_eq!(message.total_amount(), Some(300));
         assert_eq!(
             message.description(),
-            "Budget decision: 1000000 satoshis for development"
+            "Budget decision: 300 satoshis to 2 recipients for Q3 grants"
         );
+
+        // Allocation order does not affect the signed bytes
+        let reordered = budget(vec![allocation("alice", 100), allocation("bob", 200)]);
+        assert_eq!(message.to_signing_bytes(), reordered.to_signing_bytes());
+
+        let overflowing = budget(vec![allocation("alice", u64::MAX), allocation("bob", 1)]);
+        assert_eq!(overflowing.total_amount(), None);
+        assert!(overflowing.description().contains("more than u64::MAX"));
+
+        let release = GovernanceMessage::Release {
+            version: "v1.0.0".to_string(),
+            commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
+        };
+        assert_eq!(release.total_amount(), None);
+        assert!(GovernanceMessage::budget_decision(Vec::new(), "nothing").is_err());
+    }
+
+    #[test]
+    fn test_budget_decision_fields_cannot_forge_allocations() {
+        let forged = GovernanceMessage::BudgetDecision {
+            allocations: Vec::new(),
+            rationale: "grants\nALLOCATION:mallory:5000000000:".to_string(),
+        };
+        let paying = GovernanceMessage::single_budget("mallory", 5_000_000_000, "grants");
+        assert_ne!(forged.to_signing_bytes(), paying.to_signing_bytes());
     }
 
     #[test]
@@ -122,11 +885,55 @@ mod tests {
         let message = GovernanceMessage::Release {
             version: "v1.0.0".to_string(),
             commit_hash: "abc123".to_string(),
-        };
+            artifact_hashes: HashMap::new(),
+        }
+        .with_artifact("bllvm-node-linux-x86_64", &"aa11".repeat(16))
+        .unwrap();
 
         let json = serde_json::to_string(&message).unwrap();
         let deserialized: GovernanceMessage = serde_json::from_str(&json).unwrap();
 
         assert_eq!(message, deserialized);
     }
+
+    #[test]
+    fn test_governance_proposal_voting() {
+        let keypairs: Vec<_> = (0..3)
+            .map(|_| GovernanceKeypair::generate().unwrap())
+            .collect();
+        let multisig =
+            Multisig::new(2, 3, keypairs.iter().map(|k| k.public_key()).collect()).unwrap();
+        let message = GovernanceMessage::module_approval("lightning", "v2.0.0");
+        let mut proposal = GovernanceProposal::new(
+            "Approve lightning",
+            "Ship the lightning module",
+            message,
+            &keypairs[0].public_key(),
+            3600,
+        );
+        assert_eq!(proposal.voting_ends_at, proposal.proposed_at + 3600);
+
+        proposal.cast_vote(&keypairs[0], true).unwrap();
+        proposal.cast_vote(&keypairs[1], false).unwrap();
+        assert!(!proposal.is_passed(&multisig));
+        assert!(proposal.cast_vote(&keypairs[0], true).is_err());
+
+        // A vote against does not count toward passing
+        let mut forged = proposal.clone();
+        forged.votes_for.push(forged.votes_against[0].clone());
+        assert!(!forged.is_passed(&multisig));
+
+        proposal.cast_vote(&keypairs[2], true).unwrap();
+        assert!(proposal.is_passed(&multisig));
+
+        let json = serde_json::to_string(&proposal).unwrap();
+        let deserialized: GovernanceProposal = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized, proposal);
+        assert!(deserialized.is_passed(&multisig));
+
+        // Voting is closed once the deadline passes
+        proposal.voting_ends_at = proposal.proposed_at - 1;
+        let late = GovernanceKeypair::generate().unwrap();
+        assert!(proposal.cast_vote(&late, true).is_err());
+    }
 }
@@ -7,23 +7,59 @@
 //! - Signature creation and verification
 //! - Multisig threshold logic
 //! - Message formats for governance decisions
+//! - Proposal lifecycle from draft to execution
+//! - Key rotation approved by the remaining keyholders
+//!
+//! Everything here except `BlockingSigner` and `serve_signer` builds
+//! without the `node` feature, including for `wasm32-unknown-unknown`.
 
+pub mod base58;
+pub mod bech32;
 pub mod bip32;
+pub mod bip38;
 pub mod bip39;
 pub mod bip44;
+pub mod ceremony;
+pub mod descriptor;
 pub mod error;
 pub mod keys;
+#[cfg(feature = "kms")]
+pub mod kms;
 pub mod messages;
 pub mod multisig;
 pub mod nested_multisig;
+pub mod network;
 pub mod psbt;
+pub mod remote;
+pub mod rotation;
+pub mod sighash;
 pub mod signatures;
+pub mod signer;
+pub mod state;
+pub mod transaction;
+pub mod ur;
 pub mod verification;
 
 // Re-export main types
-pub use error::{GovernanceError, GovernanceResult};
-pub use keys::{GovernanceKeypair, PublicKey};
-pub use messages::GovernanceMessage;
-pub use multisig::Multisig;
-pub use signatures::Signature;
+pub use error::{ErrorCategory, GovernanceError, GovernanceResult};
+pub use keys::{GovernanceKeypair, PublicKey, XOnlyPublicKey};
+pub use messages::{
+    BudgetAllocation, GovernanceMessage, GovernanceProposal, SecurityRating, SIGNING_DOMAIN,
+};
+pub use multisig::{Multisig, MultisigScriptType};
+pub use network::NetworkType;
+#[cfg(all(unix, feature = "node"))]
+pub use remote::serve_signer;
+#[cfg(unix)]
+pub use remote::RemoteSigner;
+pub use remote::{SignOutcome, SignRequest};
+pub use rotation::KeyRotationProposal;
+pub use signatures::{
+    hash160, sha256d, sign_governance_message, tagged_hash, verify_governance_message, Signature,
+};
+#[cfg(feature = "node")]
+pub use signer::BlockingSigner;
+pub use signer::{GovernanceSigner, MockSigner, MockSignerBehavior};
+pub use state::{GovernanceDecision, GovernanceStateMachine, ProposalState};
+pub use ur::{SignatureEnvelope, SigningRequest};
 pub use verification::verify_signature;
@@ -4,15 +4,42 @@
 
 use std::collections::HashSet;
 
+use crate::governance::bip32::{
+    format_child_index, ExtendedPublicKey, HardenedStyle, HARDENED_OFFSET,
+};
+use crate::governance::descriptor;
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::hash160;
+use crate::governance::signer::GovernanceSigner;
 use crate::governance::{PublicKey, Signature};
 
+const OP_1: u8 = 0x51;
+const OP_16: u8 = 0x60;
+const OP_EQUAL: u8 = 0x87;
+const OP_HASH160: u8 = 0xa9;
+const OP_CHECKMULTISIG: u8 = 0xae;
+
+/// Script type a multisig is wrapped in when used on-chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MultisigScriptType {
+    /// Bare `multi(...)`
+    #[default]
+    Bare,
+    /// Pay-to-script-hash `sh(multi(...))`
+    P2sh,
+    /// Pay-to-witness-script-hash `wsh(multi(...))`
+    P2wsh,
+    /// Nested segwit `sh(wsh(multi(...)))`
+    P2shP2wsh,
+}
+
 /// A multisig configuration
 #[derive(Debug, Clone)]
 pub struct Multisig {
     threshold: usize,
     total: usize,
     public_keys: Vec<PublicKey>,
+    script_type: MultisigScriptType,
 }
 
 impl Multisig {
@@ -50,9 +77,45 @@ impl Multisig {
             threshold,
             total,
             public_keys,
+            script_type: MultisigScriptType::Bare,
         })
     }
 
+    /// Build a roster from maintainer xpubs by deriving `path_suffix` from each
+    ///
+    /// With account xpubs and a suffix of `[0, release_index]`, every release
+    /// gets its own keys. The suffix must not contain hardened indices.
+    pub fn from_xpubs(
+        threshold: usize,
+        xpubs: &[ExtendedPublicKey],
+        path_suffix: &[u32],
+    ) -> GovernanceResult<Self> {
+        if let Some(&index) = path_suffix.iter().find(|&&i| i >= HARDENED_OFFSET) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Cannot derive hardened index {} from an xpub",
+                format_child_index(index, HardenedStyle::default())
+            )));
+        }
+
+        let public_keys = xpubs
+            .iter()
+            .map(|xpub| {
+                let derived = path_suffix
+                    .iter()
+                    .try_fold(xpub.clone(), |key, &index| key.derive_child(index))?;
+                PublicKey::from_bytes(&derived.public_key_bytes())
+            })
+            .collect::<GovernanceResult<Vec<_>>>()?;
+
+        Self::new(threshold, public_keys.len(), public_keys)
+    }
+
+    /// Set the script type used for descriptors
+    pub fn with_script_type(mut self, script_type: MultisigScriptType) -> Self {
+        self.script_type = script_type;
+        self
+    }
+
     /// Verify a set of signatures against a message
     pub fn verify(&self, message: &[u8], signatures: &[Signature]) -> GovernanceResult<bool> {
         if signatures.len() < self.threshold {
@@ -87,6 +150,109 @@ impl Multisig {
         Ok(valid_indices)
     }
 
+    /// Request signatures over `message` from roster members until the threshold is met
+    ///
+    /// Signers that are unavailable or refuse are skipped so the remaining
+    /// maintainers can still reach the threshold. Signers whose key is not
+    /// in the roster, or that return an invalid signature, are errors.
+    pub async fn collect_signatures(
+        &self,
+        message: &[u8],
+        signers: &[&dyn GovernanceSigner],
+    ) -> GovernanceResult<Vec<Signature>> {
+        let mut signed = HashSet::new();
+        let mut signatures = Vec::new();
+
+        for signer in signers {
+            if signatures.len() >= self.threshold {
+                break;
+            }
+            let public_key = signer.public_key();
+            if !self.public_keys.contains(&public_key) {
+                return Err(GovernanceError::InvalidMultisig(format!(
+                    "Signer {} is not in the multisig",
+                    public_key
+                )));
+            }
+            if !signed.insert(public_key.clone()) {
+                continue;
+            }
+
+            let signature = match signer.sign(message).await {
+                Ok(signature) => signature,
+                Err(GovernanceError::SignerUnavailable(_))
+                | Err(GovernanceError::SigningRefused(_)) => continue,
+                Err(e) => return Err(e),
+            };
+            if !crate::governance::verify_signature(&signature, message, &public_key)? {
+                return Err(GovernanceError::SignatureVerification(format!(
+                    "Signer {} returned an invalid signature",
+                    public_key
+                )));
+            }
+            signatures.push(signature);
+        }
+
+        if signatures.len() < self.threshold {
+            return Err(GovernanceError::InsufficientSignatures {
+                got: signatures.len(),
+                need: self.threshold,
+            });
+        }
+        Ok(signatures)
+    }
+
+    /// Replace `old_pubkey` with `new_pubkey`, approved by the remaining keys
+    ///
+    /// `message` is what the maintainers signed, normally the signing bytes
+    /// of a [`GovernanceMessage::KeyRotation`](crate::governance::GovernanceMessage::KeyRotation)
+    /// for this rotation (see [`KeyRotationProposal::apply`](crate::governance::rotation::KeyRotationProposal::apply)).
+    /// Signatures by `old_pubkey` are ignored; `threshold - 1` distinct other
+    /// keys, and at least one, must have signed. The new key takes the old
+    /// key's position.
+    pub fn rotate_key(
+        &self,
+        old_pubkey: &PublicKey,
+        new_pubkey: PublicKey,
+        signatures: &[Signature],
+        message: &[u8],
+    ) -> GovernanceResult<Multisig> {
+        let position = self
+            .public_keys
+            .iter()
+            .position(|key| key == old_pubkey)
+            .ok_or_else(|| {
+                GovernanceError::InvalidMultisig(format!(
+                    "Key {} is not in the multisig",
+                    old_pubkey
+                ))
+            })?;
+        if self.public_keys.contains(&new_pubkey) {
+            return Err(GovernanceError::InvalidMultisig(format!(
+                "Key {} is already in the multisig",
+                new_pubkey
+            )));
+        }
+
+        let need = self.threshold.saturating_sub(1).max(1);
+        let approvals: HashSet<usize> = self
+            .collect_valid_signatures(message, signatures)?
+            .into_iter()
+            .filter(|&index| index != position)
+            .collect();
+        if approvals.len() < need {
+            return Err(GovernanceError::InsufficientSignatures {
+                got: approvals.len(),
+                need,
+            });
+        }
+
+        let mut public_keys = self.public_keys.clone();
+        public_keys[position] = new_pubkey;
+        Ok(Multisig::new(self.threshold, self.total, public_keys)?
+            .with_script_type(self.script_type))
+    }
+
     /// Get the threshold
     pub fn threshold(&self) -> usize {
         self.threshold
@@ -102,6 +268,188 @@ impl Multisig {
         &self.public_keys
     }
 
+    /// Get the script type
+    pub fn script_type(&self) -> MultisigScriptType {
+        self.script_type
+    }
+
+    /// Render as a BIP380 output descriptor with checksum
+    ///
+    /// e.g. `wsh(multi(2,<pubkey1>,<pubkey2>,<pubkey3>))#checksum`
+    pub fn to_descriptor(&self) -> String {
+        let keys: Vec<String> = self
+            .public_keys
+            .iter()
+            .map(|key| hex::encode(key.to_compressed_bytes()))
+            .collect();
+        let multi = format!("multi({},{})", self.threshold, keys.join(","));
+        let body = match self.script_type {
+            MultisigScriptType::Bare => multi,
+            MultisigScriptType::P2sh => format!("sh({})", multi),
+            MultisigScriptType::P2wsh => format!("wsh({})", multi),
+            MultisigScriptType::P2shP2wsh => format!("sh(wsh({}))", multi),
+        };
+        // Hex keys and digits are always valid descriptor characters
+        descriptor::add_checksum(&body).unwrap_or(body)
+    }
+
+    /// Parse a `multi(...)` descriptor, optionally wrapped in `sh`, `wsh` or
+    /// `sh(wsh(...))`, with an optional `#checksum`
+    pub fn from_descriptor(desc: &str) -> GovernanceResult<Multisig> {
+        let body = descriptor::strip_checksum(desc.trim())?;
+
+        let (script_type, inner) = if let Some(inner) = unwrap_call(body, "sh") {
+            match unwrap_call(inner, "wsh") {
+                Some(inner) => (MultisigScriptType::P2shP2wsh, inner),
+                None => (MultisigScriptType::P2sh, inner),
+            }
+        } else if let Some(inner) = unwrap_call(body, "wsh") {
+            (MultisigScriptType::P2wsh, inner)
+        } else {
+            (MultisigScriptType::Bare, body)
+        };
+
+        let args = unwrap_call(inner, "multi").ok_or_else(|| {
+            GovernanceError::InvalidInput(format!("Unsupported descriptor: {}", desc))
+        })?;
+        let mut parts = args.split(',');
+        let threshold = parts
+            .next()
+            .and_then(|k| k.parse::<usize>().ok())
+            .ok_or_else(|| {
+                GovernanceError::InvalidInput("Invalid descriptor threshold".to_string())
+            })?;
+
+        let public_keys = parts
+            .map(|key| {
+                let bytes = hex::decode(key).map_err(|e| {
+                    GovernanceError::InvalidInput(format!("Invalid public key '{}': {}", key, e))
+                })?;
+                if bytes.len() != 33 {
+                    return Err(GovernanceError::InvalidInput(format!(
+                        "Public key '{}' must be 33 bytes",
+                        key
+                    )));
+                }
+                PublicKey::from_bytes(&bytes)
+            })
+            .collect::<GovernanceResult<Vec<_>>>()?;
+
+        Ok(Multisig::new(threshold, public_keys.len(), public_keys)?.with_script_type(script_type))
+    }
+
+    /// Build the `OP_M <pubkey>... OP_N OP_CHECKMULTISIG` redeem script
+    ///
+    /// Keys are written compressed. Scripts are limited to 16 keys.
+    pub fn to_redeem_script(&self) -> GovernanceResult<Vec<u8>> {
+        if self.total > 16 {
+            return Err(GovernanceError::InvalidMultisig(format!(
+                "Redeem scripts support at most 16 keys, got {}",
+                self.total
+            )));
+        }
+
+        let mut script = vec![OP_1 + self.threshold as u8 - 1];
+        for key in &self.public_keys {
+            script.push(33);
+            script.extend_from_slice(&key.to_compressed_bytes());
+        }
+        script.push(OP_1 + self.total as u8 - 1);
+        script.push(OP_CHECKMULTISIG);
+        Ok(script)
+    }
+
+    /// Parse a bare `OP_M <pubkey>... OP_N OP_CHECKMULTISIG` redeem script
+    ///
+    /// Public keys may be compressed (33 bytes) or uncompressed (65 bytes).
+    pub fn from_redeem_script(script: &[u8]) -> GovernanceResult<Multisig> {
+        let invalid = |reason: String| {
+            GovernanceError::InvalidInput(format!("Invalid multisig redeem script: {}", reason))
+        };
+        let small_int = |opcode: u8, name: &str| match opcode {
+            OP_1..=OP_16 => Ok((opcode - OP_1 + 1) as usize),
+            _ => Err(invalid(format!(
+                "expected OP_1..OP_16 for {}, got opcode 0x{:02x}",
+                name, opcode
+            ))),
+        };
+
+        let (&m, rest) = script
+            .split_first()
+            .ok_or_else(|| invalid("script is empty".to_string()))?;
+        let threshold = small_int(m, "M")?;
+        let (&checkmultisig, rest) = rest
+            .split_last()
+            .ok_or_else(|| invalid("script is truncated".to_string()))?;
+        if checkmultisig != OP_CHECKMULTISIG {
+            return Err(invalid(format!(
+                "expected OP_CHECKMULTISIG at the end, got opcode 0x{:02x}",
+                checkmultisig
+            )));
+        }
+        let (&n, mut keys) = rest
+            .split_last()
+            .ok_or_else(|| invalid("script is truncated".to_string()))?;
+        let total = small_int(n, "N")?;
+        if total < threshold {
+            return Err(invalid(format!(
+                "N ({}) is less than M ({})",
+                total, threshold
+            )));
+        }
+
+        let mut public_keys = Vec::with_capacity(total);
+        while let Some((&len, tail)) = keys.split_first() {
+            if len != 33 && len != 65 {
+                return Err(invalid(format!(
+                    "expected a 33 or 65 byte public key push, got opcode 0x{:02x}",
+                    len
+                )));
+            }
+            let key = tail.get(..len as usize).ok_or_else(|| {
+                invalid(format!("public key {} is truncated", public_keys.len() + 1))
+            })?;
+            public_keys
+                .push(PublicKey::from_bytes(key).map_err(|e| {
+                    invalid(format!("public key {}: {}", public_keys.len() + 1, e))
+                })?);
+            keys = &tail[len as usize..];
+        }
+        if public_keys.len() != total {
+            return Err(invalid(format!(
+                "N is {} but the script has {} public keys",
+                total,
+                public_keys.len()
+            )));
+        }
+
+        Multisig::new(threshold, total, public_keys)
+    }
+
+    /// Parse a redeem script after checking it against a P2SH output script
+    ///
+    /// `script_pubkey` must be `OP_HASH160 <hash160(redeem_script)> OP_EQUAL`.
+    pub fn from_p2sh_redeem_script(
+        script_pubkey: &[u8],
+        redeem_script: &[u8],
+    ) -> GovernanceResult<Multisig> {
+        let hash = match script_pubkey {
+            [OP_HASH160, 20, hash @ .., OP_EQUAL] if hash.len() == 20 => hash,
+            _ => {
+                return Err(GovernanceError::InvalidInput(
+                    "Not a P2SH script: expected OP_HASH160 <20 bytes> OP_EQUAL".to_string(),
+                ))
+            }
+        };
+        if hash160(redeem_script) != hash {
+            return Err(GovernanceError::InvalidInput(
+                "Redeem script does not match the P2SH script hash".to_string(),
+            ));
+        }
+
+        Ok(Self::from_redeem_script(redeem_script)?.with_script_type(MultisigScriptType::P2sh))
+    }
+
     /// Check if a signature is valid for this multisig
     pub fn is_valid_signature(
         &self,
@@ -117,6 +465,11 @@ impl Multisig {
     }
 }
 
+/// Return the arguments of `name(...)` if `s` is exactly that call
+fn unwrap_call<'a>(s: &'a str, name: &str) -> Option<&'a str> {
+    s.strip_prefix(name)?.strip_prefix('(')?.strip_suffix(')')
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
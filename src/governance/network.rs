@@ -0,0 +1,116 @@
+//! # Network Types
+//!
+//! The Bitcoin network that keys, addresses and composed nodes belong to.
+
+use crate::governance::error::GovernanceError;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// Network type for keys, addresses and node composition
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum NetworkType {
+    /// Bitcoin mainnet
+    Mainnet,
+    /// Bitcoin testnet
+    Testnet,
+    /// Regression test network
+    Regtest,
+}
+
+impl NetworkType {
+    /// Lowercase network name (`mainnet`, `testnet`, `regtest`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            NetworkType::Mainnet => "mainnet",
+            NetworkType::Testnet => "testnet",
+            NetworkType::Regtest => "regtest",
+        }
+    }
+
+    /// P2P message start bytes, read as a little-endian `u32`
+    pub fn magic_bytes(&self) -> u32 {
+        match self {
+            NetworkType::Mainnet => 0xD9B4BEF9,
+            NetworkType::Testnet => 0x0709110B,
+            NetworkType::Regtest => 0xDAB5BFFA,
+        }
+    }
+
+    /// Whether this is mainnet
+    pub fn is_mainnet(&self) -> bool {
+        *self == NetworkType::Mainnet
+    }
+
+    /// Whether this is testnet
+    pub fn is_testnet(&self) -> bool {
+        *self == NetworkType::Testnet
+    }
+
+    /// Whether this is regtest
+    pub fn is_regtest(&self) -> bool {
+        *self == NetworkType::Regtest
+    }
+}
+
+impl fmt::Display for NetworkType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for NetworkType {
+    type Err = GovernanceError;
+
+    /// Parse a network name, ignoring case
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "mainnet" => Ok(NetworkType::Mainnet),
+            "testnet" => Ok(NetworkType::Testnet),
+            "regtest" => Ok(NetworkType::Regtest),
+            _ => Err(GovernanceError::InvalidInput(format!(
+                "Unknown network '{}' (expected mainnet, testnet or regtest)",
+                s
+            ))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_network_names_roundtrip() {
+        for network in [
+            NetworkType::Mainnet,
+            NetworkType::Testnet,
+            NetworkType::Regtest,
+        ] {
+            assert_eq!(network.to_string(), network.as_str());
+            assert_eq!(network.as_str().parse::<NetworkType>().unwrap(), network);
+        }
+        assert_eq!(
+            "RegTest".parse::<NetworkType>().unwrap(),
+            NetworkType::Regtest
+        );
+        assert!("signet".parse::<NetworkType>().is_err());
+        assert!("".parse::<NetworkType>().is_err());
+    }
+
+    #[test]
+    fn test_network_magic_and_predicates() {
+        assert_eq!(
+            NetworkType::Mainnet.magic_bytes().to_le_bytes(),
+            [0xf9, 0xbe, 0xb4, 0xd9]
+        );
+        assert_eq!(NetworkType::Testnet.magic_bytes(), 0x0709110B);
+        assert_eq!(NetworkType::Regtest.magic_bytes(), 0xDAB5BFFA);
+
+        assert!(NetworkType::Mainnet.is_mainnet());
+        assert!(!NetworkType::Mainnet.is_testnet());
+        assert!(NetworkType::Testnet.is_testnet());
+        assert!(NetworkType::Regtest.is_regtest());
+        assert!(!NetworkType::Regtest.is_mainnet());
+    }
+}
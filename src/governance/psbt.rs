@@ -5,9 +5,17 @@
 //! PSBT format enables multi-party transaction signing without exposing private keys.
 //! Critical for hardware wallet support and transaction coordination.
 
+use crate::governance::bip32::{ExtendedPrivateKey, ExtendedPublicKey};
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::network::NetworkType;
+use crate::governance::sighash::{legacy_sighash_tx, p2wpkh_script_code, segwit_v0_sighash_tx};
+use crate::governance::signatures::hash160;
+use crate::governance::transaction::{read_compact_size, write_compact_size, Transaction, TxOut};
+use secp256k1::{Message, Secp256k1, SecretKey};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fmt;
 
 /// PSBT magic bytes: 0x70736274 ("psbt")
 pub const PSBT_MAGIC: [u8; 4] = [0x70, 0x73, 0x62, 0x74];
@@ -15,6 +23,21 @@ pub const PSBT_MAGIC: [u8; 4] = [0x70, 0x73, 0x62, 0x74];
 /// PSBT separator: 0xff
 pub const PSBT_SEPARATOR: u8 = 0xff;
 
+/// Identifier prefix for bllvm proprietary fields
+pub const BLLVM_PROPRIETARY_IDENTIFIER: &[u8] = b"bllvm";
+
+/// Proprietary subtype: hash of the governance signing request (global, 32 bytes)
+pub const BLLVM_SUBTYPE_SIGNING_REQUEST_HASH: u8 = 0x00;
+
+/// Proprietary subtype: maintainer roster version (global, u32 little-endian)
+pub const BLLVM_SUBTYPE_ROSTER_VERSION: u8 = 0x01;
+
+/// Upper bound on an ECDSA signature with its sighash byte (low-S DER, high R)
+pub const ESTIMATED_ECDSA_SIGNATURE_LEN: usize = 72;
+
+/// BIP340 signature with the default sighash
+pub const ESTIMATED_SCHNORR_SIGNATURE_LEN: usize = 64;
+
 /// PSBT global map key types
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum PsbtGlobalKey {
@@ -66,6 +89,55 @@ pub enum PsbtOutputKey {
     Proprietary = 0xfc,
 }
 
+impl PsbtInputKey {
+    /// Parse an input key type from its byte
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(PsbtInputKey::NonWitnessUtxo),
+            0x01 => Some(PsbtInputKey::WitnessUtxo),
+            0x02 => Some(PsbtInputKey::PartialSig),
+            0x03 => Some(PsbtInputKey::SighashType),
+            0x04 => Some(PsbtInputKey::RedeemScript),
+            0x05 => Some(PsbtInputKey::WitnessScript),
+            0x06 => Some(PsbtInputKey::Bip32Derivation),
+            0x07 => Some(PsbtInputKey::FinalScriptSig),
+            0x08 => Some(PsbtInputKey::FinalScriptWitness),
+            0xfc => Some(PsbtInputKey::Proprietary),
+            _ => None,
+        }
+    }
+}
+
+impl PsbtOutputKey {
+    /// Parse an output key type from its byte
+    pub fn from_byte(byte: u8) -> Option<Self> {
+        match byte {
+            0x00 => Some(PsbtOutputKey::RedeemScript),
+            0x01 => Some(PsbtOutputKey::WitnessScript),
+            0x02 => Some(PsbtOutputKey::Bip32Derivation),
+            0xfc => Some(PsbtOutputKey::Proprietary),
+            _ => None,
+        }
+    }
+}
+
+/// PSBT map a field belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PsbtScope {
+    /// Global map
+    Global,
+    /// Input map at index
+    Input(usize),
+    /// Output map at index
+    Output(usize),
+}
+
+/// Serialized public key (33 bytes compressed or 65 bytes uncompressed)
+pub type PubkeyBytes = Vec<u8>;
+
+/// DER signature followed by its sighash byte
+pub type SigBytes = Vec<u8>;
+
 /// BIP32 derivation path entry
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Bip32Derivation {
@@ -123,30 +195,391 @@ impl SighashType {
     }
 }
 
-/// Partially Signed Bitcoin Transaction
+/// Severity of a PSBT validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PsbtIssueSeverity {
+    /// The PSBT is malformed and should not be signed or finalized
+    Error,
+    /// The PSBT is usable but not as BIP174 intends
+    Warning,
+}
+
+/// Kind of PSBT validation issue
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PsbtIssueCode {
+    /// No unsigned transaction in the global map
+    MissingUnsignedTx,
+    /// More than one global key of the unsigned transaction type
+    DuplicateUnsignedTx,
+    /// Unsigned transaction does not parse
+    InvalidUnsignedTx,
+    /// Unsigned transaction input has a scriptSig
+    UnsignedTxHasScriptSig,
+    /// Unsigned transaction uses the witness serialization
+    UnsignedTxHasWitness,
+    /// More input or output maps than the transaction has
+    MapCountMismatch,
+    /// non_witness_utxo does not contain the spent outpoint
+    NonWitnessUtxoMismatch,
+    /// Input requests a sighash type that cannot be signed
+    UnknownSighashType,
+    /// Partial signature keyed by an invalid public key
+    InvalidPartialSigPubkey,
+    /// An `unknown` entry shadows a typed field
+    ///
+    /// Only maps built or edited in memory can have this; `deserialize`
+    /// already rejects keys repeated in the encoded map.
+    DuplicateKey,
+    /// Finalized input still carries partial signatures
+    FinalizedWithPartialSigs,
+}
+
+impl PsbtIssueCode {
+    /// Stable snake_case name of the code
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PsbtIssueCode::MissingUnsignedTx => "missing_unsigned_tx",
+            PsbtIssueCode::DuplicateUnsignedTx => "duplicate_unsigned_tx",
+            PsbtIssueCode::InvalidUnsignedTx => "invalid_unsigned_tx",
+            PsbtIssueCode::UnsignedTxHasScriptSig => "unsigned_tx_has_script_sig",
+            PsbtIssueCode::UnsignedTxHasWitness => "unsigned_tx_has_witness",
+            PsbtIssueCode::MapCountMismatch => "map_count_mismatch",
+            PsbtIssueCode::NonWitnessUtxoMismatch => "non_witness_utxo_mismatch",
+            PsbtIssueCode::UnknownSighashType => "unknown_sighash_type",
+            PsbtIssueCode::InvalidPartialSigPubkey => "invalid_partial_sig_pubkey",
+            PsbtIssueCode::DuplicateKey => "duplicate_key",
+            PsbtIssueCode::FinalizedWithPartialSigs => "finalized_with_partial_sigs",
+        }
+    }
+}
+
+/// Structural problem found by [`PartiallySignedTransaction::validate`]
 #[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PsbtIssue {
+    /// How serious the issue is
+    pub severity: PsbtIssueSeverity,
+    /// Map the issue was found in
+    pub scope: PsbtScope,
+    /// Kind of issue
+    pub code: PsbtIssueCode,
+    /// Human-readable detail
+    pub message: String,
+}
+
+impl PsbtIssue {
+    fn error(scope: PsbtScope, code: PsbtIssueCode, message: String) -> Self {
+        Self {
+            severity: PsbtIssueSeverity::Error,
+            scope,
+            code,
+            message,
+        }
+    }
+
+    fn warning(scope: PsbtScope, code: PsbtIssueCode, message: String) -> Self {
+        Self {
+            severity: PsbtIssueSeverity::Warning,
+            scope,
+            code,
+            message,
+        }
+    }
+}
+
+impl fmt::Display for PsbtIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let severity = match self.severity {
+            PsbtIssueSeverity::Error => "error",
+            PsbtIssueSeverity::Warning => "warning",
+        };
+        let scope = match self.scope {
+            PsbtScope::Global => "global".to_string(),
+            PsbtScope::Input(index) => format!("input {}", index),
+            PsbtScope::Output(index) => format!("output {}", index),
+        };
+        write!(
+            f,
+            "{} [{}] {}: {}",
+            severity,
+            self.code.as_str(),
+            scope,
+            self.message
+        )
+    }
+}
+
+/// Outcome of a signing pass over a PSBT
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SigningSummary {
+    /// Inputs that received a partial signature
+    pub signed: Vec<usize>,
+    /// Inputs that were skipped, with the reason
+    pub skipped: Vec<(usize, String)>,
+}
+
+/// PSBT input map with typed fields
+///
+/// Keys without a typed field (proprietary, unknown, or newer key types)
+/// are kept in `unknown` so they survive a round trip.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtInput {
+    /// Serialized transaction containing the spent output
+    pub non_witness_utxo: Option<Vec<u8>>,
+    /// Spent output
+    pub witness_utxo: Option<TxOut>,
+    /// Partial signatures by public key
+    pub partial_sigs: BTreeMap<PubkeyBytes, SigBytes>,
+    /// Requested sighash type
+    pub sighash_type: Option<SighashType>,
+    /// P2SH redeem script
+    pub redeem_script: Option<Vec<u8>>,
+    /// P2WSH witness script
+    pub witness_script: Option<Vec<u8>>,
+    /// BIP32 derivations of the keys involved in the input, sorted by public key
+    pub bip32_derivations: Vec<Bip32Derivation>,
+    /// Finalized scriptSig
+    pub final_script_sig: Option<Vec<u8>>,
+    /// Finalized witness stack
+    pub final_script_witness: Option<Vec<Vec<u8>>>,
+    /// Remaining key-value pairs
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+/// PSBT output map with typed fields
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PsbtOutput {
+    /// P2SH redeem script
+    pub redeem_script: Option<Vec<u8>>,
+    /// P2WSH witness script
+    pub witness_script: Option<Vec<u8>>,
+    /// BIP32 derivations of the keys involved in the output, sorted by public key
+    pub bip32_derivations: Vec<Bip32Derivation>,
+    /// Remaining key-value pairs
+    pub unknown: BTreeMap<Vec<u8>, Vec<u8>>,
+}
+
+impl PsbtInput {
+    /// Parse an input from its raw key-value map
+    pub fn from_map(map: BTreeMap<Vec<u8>, Vec<u8>>) -> GovernanceResult<Self> {
+        let mut input = PsbtInput::default();
+        for (key, value) in map {
+            input.insert(key, value)?;
+        }
+        Ok(input)
+    }
+
+    /// Convert the input back to its raw key-value map
+    pub fn to_map(&self) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        let mut map = self.unknown.clone();
+        let key = |key_type: PsbtInputKey| psbt_key(key_type as u64, &[]);
+
+        if let Some(tx) = &self.non_witness_utxo {
+            map.insert(key(PsbtInputKey::NonWitnessUtxo), tx.clone());
+        }
+        if let Some(utxo) = &self.witness_utxo {
+            map.insert(key(PsbtInputKey::WitnessUtxo), utxo.serialize());
+        }
+        for (pubkey, signature) in &self.partial_sigs {
+            map.insert(
+                psbt_key(PsbtInputKey::PartialSig as u64, pubkey),
+                signature.clone(),
+            );
+        }
+        if let Some(sighash_type) = self.sighash_type {
+            let value = (sighash_type.to_byte() as u32).to_le_bytes().to_vec();
+            map.insert(key(PsbtInputKey::SighashType), value);
+        }
+        if let Some(script) = &self.redeem_script {
+            map.insert(key(PsbtInputKey::RedeemScript), script.clone());
+        }
+        if let Some(script) = &self.witness_script {
+            map.insert(key(PsbtInputKey::WitnessScript), script.clone());
+        }
+        for derivation in &self.bip32_derivations {
+            map.insert(
+                psbt_key(PsbtInputKey::Bip32Derivation as u64, &derivation.pubkey),
+                encode_derivation(derivation),
+            );
+        }
+        if let Some(script_sig) = &self.final_script_sig {
+            map.insert(key(PsbtInputKey::FinalScriptSig), script_sig.clone());
+        }
+        if let Some(witness) = &self.final_script_witness {
+            map.insert(
+                key(PsbtInputKey::FinalScriptWitness),
+                encode_witness(witness),
+            );
+        }
+        map
+    }
+
+    /// Set one raw key-value pair, parsing it into its typed field
+    pub fn insert(&mut self, key: Vec<u8>, value: Vec<u8>) -> GovernanceResult<()> {
+        let (key_type, key_data) = split_psbt_key(&key)?;
+
+        match u8::try_from(key_type)
+            .ok()
+            .and_then(PsbtInputKey::from_byte)
+        {
+            Some(PsbtInputKey::NonWitnessUtxo) => {
+                expect_bare_key(key_data, "non_witness_utxo")?;
+                Transaction::parse(&value)?;
+                self.non_witness_utxo = Some(value);
+            }
+            Some(PsbtInputKey::WitnessUtxo) => {
+                expect_bare_key(key_data, "witness_utxo")?;
+                self.witness_utxo = Some(TxOut::parse(&value)?);
+            }
+            Some(PsbtInputKey::PartialSig) => {
+                check_pubkey(key_data)?;
+                self.partial_sigs.insert(key_data.to_vec(), value);
+            }
+            Some(PsbtInputKey::SighashType) => {
+                expect_bare_key(key_data, "sighash_type")?;
+                if value.len() != 4 {
+                    return Err(GovernanceError::InvalidInput(format!(
+                        "sighash_type must be 4 bytes, got {}",
+                        value.len()
+                    )));
+                }
+                // Any u32 is a valid request; ones we cannot sign stay raw so
+                // `validate` reports them and signers refuse the input
+                match parse_sighash_type(&value) {
+                    Some(sighash_type) => self.sighash_type = Some(sighash_type),
+                    None => {
+                        self.unknown.insert(key, value);
+                    }
+                }
+            }
+            Some(PsbtInputKey::RedeemScript) => {
+                expect_bare_key(key_data, "redeem_script")?;
+                self.redeem_script = Some(value);
+            }
+            Some(PsbtInputKey::WitnessScript) => {
+                expect_bare_key(key_data, "witness_script")?;
+                self.witness_script = Some(value);
+            }
+            Some(PsbtInputKey::Bip32Derivation) => {
+                let derivation = decode_derivation(key_data, &value)?;
+                set_derivation(&mut self.bip32_derivations, derivation);
+            }
+            Some(PsbtInputKey::FinalScriptSig) => {
+                expect_bare_key(key_data, "final_scriptsig")?;
+                self.final_script_sig = Some(value);
+            }
+            Some(PsbtInputKey::FinalScriptWitness) => {
+                expect_bare_key(key_data, "final_scriptwitness")?;
+                self.final_script_witness = Some(parse_witness(&value)?);
+            }
+            Some(PsbtInputKey::Proprietary) | None => {
+                self.unknown.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+
+    /// Check if the input has a final scriptSig or witness
+    pub fn is_finalized(&self) -> bool {
+        self.final_script_sig.is_some() || self.final_script_witness.is_some()
+    }
+}
+
+impl PsbtOutput {
+    /// Parse an output from its raw key-value map
+    pub fn from_map(map: BTreeMap<Vec<u8>, Vec<u8>>) -> GovernanceResult<Self> {
+        let mut output = PsbtOutput::default();
+        for (key, value) in map {
+            output.insert(key, value)?;
+        }
+        Ok(output)
+    }
+
+    /// Convert the output back to its raw key-value map
+    pub fn to_map(&self) -> BTreeMap<Vec<u8>, Vec<u8>> {
+        let mut map = self.unknown.clone();
+        if let Some(script) = &self.redeem_script {
+            map.insert(
+                psbt_key(PsbtOutputKey::RedeemScript as u64, &[]),
+                script.clone(),
+            );
+        }
+        if let Some(script) = &self.witness_script {
+            map.insert(
+                psbt_key(PsbtOutputKey::WitnessScript as u64, &[]),
+                script.clone(),
+            );
+        }
+        for derivation in &self.bip32_derivations {
+            map.insert(
+                psbt_key(PsbtOutputKey::Bip32Derivation as u64, &derivation.pubkey),
+                encode_derivation(derivation),
+            );
+        }
+        map
+    }
+
+    /// Set one raw key-value pair, parsing it into its typed field
+    pub fn insert(&mut self, key: Vec<u8>, value: Vec<u8>) -> GovernanceResult<()> {
+        let (key_type, key_data) = split_psbt_key(&key)?;
+
+        match u8::try_from(key_type)
+            .ok()
+            .and_then(PsbtOutputKey::from_byte)
+        {
+            Some(PsbtOutputKey::RedeemScript) => {
+                expect_bare_key(key_data, "redeem_script")?;
+                self.redeem_script = Some(value);
+            }
+            Some(PsbtOutputKey::WitnessScript) => {
+                expect_bare_key(key_data, "witness_script")?;
+                self.witness_script = Some(value);
+            }
+            Some(PsbtOutputKey::Bip32Derivation) => {
+                let derivation = decode_derivation(key_data, &value)?;
+                set_derivation(&mut self.bip32_derivations, derivation);
+            }
+            Some(PsbtOutputKey::Proprietary) | None => {
+                self.unknown.insert(key, value);
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Partially Signed Bitcoin Transaction
+///
+/// `Display` renders the Base64 form and `Debug` the hex form.
+#[derive(Clone, PartialEq, Eq)]
 pub struct PartiallySignedTransaction {
     /// Global map (unsigned transaction, xpubs, etc.)
-    pub global: HashMap<Vec<u8>, Vec<u8>>,
-    /// Input maps (one per input)
-    pub inputs: Vec<HashMap<Vec<u8>, Vec<u8>>>,
-    /// Output maps (one per output)
-    pub outputs: Vec<HashMap<Vec<u8>, Vec<u8>>>,
+    pub global: BTreeMap<Vec<u8>, Vec<u8>>,
+    /// Inputs (one per transaction input)
+    pub inputs: Vec<PsbtInput>,
+    /// Outputs (one per transaction output)
+    pub outputs: Vec<PsbtOutput>,
     /// Version (default: 0)
     pub version: u8,
 }
 
 impl PartiallySignedTransaction {
     /// Create a new PSBT from an unsigned transaction
+    ///
+    /// One empty input and output map is created per transaction input and
+    /// output. The global version key is omitted, which BIP174 reads as
+    /// version 0.
     pub fn new(unsigned_tx: &[u8]) -> GovernanceResult<Self> {
-        let mut global = HashMap::new();
-        global.insert(vec![PsbtGlobalKey::UnsignedTx as u8], unsigned_tx.to_vec());
-        global.insert(vec![PsbtGlobalKey::Version as u8], vec![0x00]); // Version 0
+        let (input_count, output_count) = unsigned_tx_io_counts(unsigned_tx)?;
+
+        let mut global = BTreeMap::new();
+        global.insert(
+            psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]),
+            unsigned_tx.to_vec(),
+        );
 
         Ok(PartiallySignedTransaction {
             global,
-            inputs: Vec::new(),
-            outputs: Vec::new(),
+            inputs: (0..input_count).map(|_| PsbtInput::default()).collect(),
+            outputs: (0..output_count).map(|_| PsbtOutput::default()).collect(),
             version: 0,
         })
     }
@@ -158,14 +591,7 @@ impl PartiallySignedTransaction {
         key: Vec<u8>,
         value: Vec<u8>,
     ) -> GovernanceResult<()> {
-        if input_index >= self.inputs.len() {
-            // Extend inputs vector if needed
-            while self.inputs.len() <= input_index {
-                self.inputs.push(HashMap::new());
-            }
-        }
-        self.inputs[input_index].insert(key, value);
-        Ok(())
+        self.input_mut(input_index).insert(key, value)
     }
 
     /// Add output data
@@ -175,14 +601,7 @@ impl PartiallySignedTransaction {
         key: Vec<u8>,
         value: Vec<u8>,
     ) -> GovernanceResult<()> {
-        if output_index >= self.outputs.len() {
-            // Extend outputs vector if needed
-            while self.outputs.len() <= output_index {
-                self.outputs.push(HashMap::new());
-            }
-        }
-        self.outputs[output_index].insert(key, value);
-        Ok(())
+        self.output_mut(output_index).insert(key, value)
     }
 
     /// Add partial signature to an input
@@ -192,15 +611,11 @@ impl PartiallySignedTransaction {
         pubkey: Vec<u8>,
         signature: Vec<u8>,
     ) -> GovernanceResult<()> {
-        // Format: <pubkey_len><pubkey><sig_len><signature>
-        let mut key = vec![PsbtInputKey::PartialSig as u8];
-        key.extend_from_slice(&pubkey);
-
-        let mut value = Vec::with_capacity(1 + signature.len());
-        value.push(signature.len() as u8);
-        value.extend_from_slice(&signature);
-
-        self.add_input_data(input_index, key, value)
+        check_pubkey(&pubkey)?;
+        self.input_mut(input_index)
+            .partial_sigs
+            .insert(pubkey, signature);
+        Ok(())
     }
 
     /// Add BIP32 derivation path to an input
@@ -210,18 +625,195 @@ impl PartiallySignedTransaction {
         pubkey: Vec<u8>,
         derivation: Bip32Derivation,
     ) -> GovernanceResult<()> {
-        let mut key = vec![PsbtInputKey::Bip32Derivation as u8];
-        key.extend_from_slice(&pubkey);
+        check_pubkey(&pubkey)?;
+        let derivation = Bip32Derivation {
+            pubkey,
+            ..derivation
+        };
+        set_derivation(
+            &mut self.input_mut(input_index).bip32_derivations,
+            derivation,
+        );
+        Ok(())
+    }
 
-        // Serialize derivation: <master_fp(4)><path_len><path>
-        let mut value = Vec::new();
-        value.extend_from_slice(&derivation.master_fingerprint);
-        value.push(derivation.path.len() as u8);
-        for &index in &derivation.path {
-            value.extend_from_slice(&index.to_be_bytes());
-        }
+    /// Add a proprietary field
+    ///
+    /// The key is `0xfc <identifier_len> <identifier> <subtype> <keydata>`
+    /// per BIP174, so other software keeps it as unknown data.
+    pub fn add_proprietary(
+        &mut self,
+        scope: PsbtScope,
+        identifier: &[u8],
+        subtype: u8,
+        keydata: &[u8],
+        value: Vec<u8>,
+    ) -> GovernanceResult<()> {
+        let key = proprietary_key(identifier, subtype.into(), keydata);
+        let map = match scope {
+            PsbtScope::Global => &mut self.global,
+            PsbtScope::Input(index) => &mut self.input_mut(index).unknown,
+            PsbtScope::Output(index) => &mut self.output_mut(index).unknown,
+        };
+        map.insert(key, value);
+        Ok(())
+    }
+
+    /// Get a proprietary field
+    pub fn get_proprietary(
+        &self,
+        scope: PsbtScope,
+        identifier: &[u8],
+        subtype: u8,
+        keydata: &[u8],
+    ) -> Option<&[u8]> {
+        let key = proprietary_key(identifier, subtype.into(), keydata);
+        let map = match scope {
+            PsbtScope::Global => &self.global,
+            PsbtScope::Input(index) => &self.inputs.get(index)?.unknown,
+            PsbtScope::Output(index) => &self.outputs.get(index)?.unknown,
+        };
+        map.get(&key).map(Vec::as_slice)
+    }
+
+    /// Add a proprietary field to the global map
+    pub fn add_proprietary_global(
+        &mut self,
+        identifier: &[u8],
+        tag: u32,
+        value: &[u8],
+    ) -> GovernanceResult<()> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.global.insert(key, value.to_vec());
+        Ok(())
+    }
+
+    /// Add a proprietary field to an input
+    pub fn add_proprietary_input(
+        &mut self,
+        input_index: usize,
+        identifier: &[u8],
+        tag: u32,
+        value: &[u8],
+    ) -> GovernanceResult<()> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.input_mut(input_index)
+            .unknown
+            .insert(key, value.to_vec());
+        Ok(())
+    }
 
-        self.add_input_data(input_index, key, value)
+    /// Add a proprietary field to an output
+    pub fn add_proprietary_output(
+        &mut self,
+        output_index: usize,
+        identifier: &[u8],
+        tag: u32,
+        value: &[u8],
+    ) -> GovernanceResult<()> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.output_mut(output_index)
+            .unknown
+            .insert(key, value.to_vec());
+        Ok(())
+    }
+
+    /// Get a proprietary field from the global map
+    pub fn get_proprietary_global(&self, identifier: &[u8], tag: u32) -> Option<&[u8]> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.global.get(&key).map(Vec::as_slice)
+    }
+
+    /// Get a proprietary field from an input
+    pub fn get_proprietary_input(
+        &self,
+        input_index: usize,
+        identifier: &[u8],
+        tag: u32,
+    ) -> Option<&[u8]> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.inputs
+            .get(input_index)?
+            .unknown
+            .get(&key)
+            .map(Vec::as_slice)
+    }
+
+    /// Get a proprietary field from an output
+    pub fn get_proprietary_output(
+        &self,
+        output_index: usize,
+        identifier: &[u8],
+        tag: u32,
+    ) -> Option<&[u8]> {
+        let key = proprietary_key(identifier, tag, &[]);
+        self.outputs
+            .get(output_index)?
+            .unknown
+            .get(&key)
+            .map(Vec::as_slice)
+    }
+
+    /// Record the hash of the governance signing request this PSBT belongs to
+    pub fn set_signing_request_hash(&mut self, hash: [u8; 32]) -> GovernanceResult<()> {
+        self.add_proprietary(
+            PsbtScope::Global,
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            BLLVM_SUBTYPE_SIGNING_REQUEST_HASH,
+            &[],
+            hash.to_vec(),
+        )
+    }
+
+    /// Hash of the governance signing request, if recorded
+    pub fn signing_request_hash(&self) -> GovernanceResult<Option<[u8; 32]>> {
+        self.get_proprietary(
+            PsbtScope::Global,
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            BLLVM_SUBTYPE_SIGNING_REQUEST_HASH,
+            &[],
+        )
+        .map(|value| {
+            value.try_into().map_err(|_| {
+                GovernanceError::InvalidInput(format!(
+                    "Signing request hash must be 32 bytes, got {}",
+                    value.len()
+                ))
+            })
+        })
+        .transpose()
+    }
+
+    /// Record the maintainer roster version used to sign this PSBT
+    pub fn set_roster_version(&mut self, version: u32) -> GovernanceResult<()> {
+        self.add_proprietary(
+            PsbtScope::Global,
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            BLLVM_SUBTYPE_ROSTER_VERSION,
+            &[],
+            version.to_le_bytes().to_vec(),
+        )
+    }
+
+    /// Maintainer roster version, if recorded
+    pub fn roster_version(&self) -> GovernanceResult<Option<u32>> {
+        self.get_proprietary(
+            PsbtScope::Global,
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            BLLVM_SUBTYPE_ROSTER_VERSION,
+            &[],
+        )
+        .map(|value| {
+            <[u8; 4]>::try_from(value)
+                .map(u32::from_le_bytes)
+                .map_err(|_| {
+                    GovernanceError::InvalidInput(format!(
+                        "Roster version must be 4 bytes, got {}",
+                        value.len()
+                    ))
+                })
+        })
+        .transpose()
     }
 
     /// Set sighash type for an input
@@ -230,48 +822,924 @@ impl PartiallySignedTransaction {
         input_index: usize,
         sighash_type: SighashType,
     ) -> GovernanceResult<()> {
-        let key = vec![PsbtInputKey::SighashType as u8];
-        let value = vec![sighash_type.to_byte()];
-        self.add_input_data(input_index, key, value)
+        self.input_mut(input_index).sighash_type = Some(sighash_type);
+        Ok(())
     }
 
-    /// Check if PSBT is finalized (all inputs have final script sig/witness)
-    pub fn is_finalized(&self) -> bool {
-        for input_map in &self.inputs {
-            let has_final_sig = input_map.contains_key(&vec![PsbtInputKey::FinalScriptSig as u8]);
-            let has_final_witness =
-                input_map.contains_key(&vec![PsbtInputKey::FinalScriptWitness as u8]);
+    /// Get the sighash type requested for an input (default: ALL)
+    ///
+    /// Fails if the input requests a sighash type this library cannot sign.
+    pub fn sighash_type(&self, input_index: usize) -> GovernanceResult<SighashType> {
+        let input = self.inputs.get(input_index);
+        let raw = input.and_then(|input| {
+            input
+                .unknown
+                .get(&psbt_key(PsbtInputKey::SighashType as u64, &[]))
+        });
+        if let Some(value) = raw {
+            return Err(GovernanceError::SigningRefused(format!(
+                "Input {} requests unsupported sighash type {}",
+                input_index,
+                hex::encode(value)
+            )));
+        }
+        Ok(input
+            .and_then(|input| input.sighash_type)
+            .unwrap_or(SighashType::All))
+    }
+
+    /// Get the BIP32 derivations recorded for an input
+    pub fn input_bip32_derivations(
+        &self,
+        input_index: usize,
+    ) -> GovernanceResult<Vec<Bip32Derivation>> {
+        Ok(self
+            .inputs
+            .get(input_index)
+            .map(|input| input.bip32_derivations.clone())
+            .unwrap_or_default())
+    }
+
+    /// Get the partial signatures (key type 0x02) on an input, ordered by pubkey
+    pub fn partial_signatures(
+        &self,
+        input_index: usize,
+    ) -> GovernanceResult<Vec<PartialSignature>> {
+        Ok(self
+            .inputs
+            .get(input_index)
+            .map(|input| {
+                input
+                    .partial_sigs
+                    .iter()
+                    .map(|(pubkey, signature)| PartialSignature {
+                        pubkey: pubkey.clone(),
+                        signature: signature.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// Number of partial signatures on an input
+    pub fn signature_count(&self, input_index: usize) -> GovernanceResult<usize> {
+        Ok(self
+            .inputs
+            .get(input_index)
+            .map_or(0, |input| input.partial_sigs.len()))
+    }
 
-            if !has_final_sig && !has_final_witness {
-                return false;
+    /// Whether an input has at least `threshold` partial signatures
+    pub fn has_enough_signatures(
+        &self,
+        input_index: usize,
+        threshold: usize,
+    ) -> GovernanceResult<bool> {
+        Ok(self.signature_count(input_index)? >= threshold)
+    }
+
+    /// Add an extended public key to the global map
+    ///
+    /// The key is serialized with mainnet ("xpub") version bytes; the value
+    /// records the master fingerprint and the path from the master to
+    /// `xpub`. Adding the same xpub twice is an error.
+    pub fn add_global_xpub(
+        &mut self,
+        xpub: &ExtendedPublicKey,
+        master_fingerprint: [u8; 4],
+        path: &[u32],
+    ) -> GovernanceResult<()> {
+        let encoded = xpub.to_bytes(NetworkType::Mainnet);
+        if self
+            .global_xpubs()
+            .iter()
+            .any(|(existing, _, _)| existing.to_bytes(NetworkType::Mainnet) == encoded)
+        {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Global xpub {} is already present",
+                hex::encode(xpub.public_key_bytes())
+            )));
+        }
+
+        let key = psbt_key(PsbtGlobalKey::Xpub as u64, &encoded);
+        self.global
+            .insert(key, encode_key_origin(&master_fingerprint, path));
+        Ok(())
+    }
+
+    /// Get the global xpubs with their master fingerprint and path
+    pub fn global_xpubs(&self) -> Vec<(ExtendedPublicKey, [u8; 4], Vec<u32>)> {
+        self.global
+            .iter()
+            .filter_map(|(key, value)| {
+                let (key_type, key_data) = split_psbt_key(key).ok()?;
+                if key_type != PsbtGlobalKey::Xpub as u64 {
+                    return None;
+                }
+                let xpub = ExtendedPublicKey::from_bytes(key_data).ok()?;
+                let (master_fingerprint, path) = decode_key_origin(value).ok()?;
+                Some((xpub, master_fingerprint, path))
+            })
+            .collect()
+    }
+
+    /// Check that an output's key derives from one of the global xpubs
+    ///
+    /// An output belongs to a global xpub when one of its BIP32 derivations
+    /// has the xpub's master fingerprint, extends the xpub's path with
+    /// unhardened steps only, and re-deriving those steps from the xpub
+    /// yields the recorded public key. The output script itself is not
+    /// checked.
+    pub fn verify_output_belongs_to_xpub(&self, output_index: usize) -> GovernanceResult<bool> {
+        let tx = self.unsigned_tx()?;
+        if output_index >= tx.outputs.len() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Output index {} out of range ({} outputs)",
+                output_index,
+                tx.outputs.len()
+            )));
+        }
+        let derivations = match self.outputs.get(output_index) {
+            Some(output) => &output.bip32_derivations,
+            None => return Ok(false),
+        };
+
+        let xpubs = self.global_xpubs();
+        for derivation in derivations {
+            for (xpub, master_fingerprint, xpub_path) in &xpubs {
+                if *master_fingerprint != derivation.master_fingerprint {
+                    continue;
+                }
+                let remaining = match derivation.path.strip_prefix(xpub_path.as_slice()) {
+                    Some(remaining) => remaining,
+                    None => continue,
+                };
+
+                // Hardened steps cannot be derived from an xpub and never match
+                let child = remaining
+                    .iter()
+                    .try_fold(xpub.clone(), |key, &index| key.derive_child(index));
+                if matches!(child, Ok(child) if child.public_key_bytes()[..] == derivation.pubkey[..])
+                {
+                    return Ok(true);
+                }
             }
         }
-        true
+        Ok(false)
     }
 
-    /// Extract final transaction (throws error if not finalized)
-    pub fn extract_transaction(&self) -> GovernanceResult<Vec<u8>> {
-        if !self.is_finalized() {
+    /// Merge another PSBT for the same transaction into this one (BIP174 Combiner)
+    ///
+    /// Keys from `other` are added to the matching map; keys already
+    /// present here keep their values.
+    pub fn combine(&mut self, other: &PartiallySignedTransaction) -> GovernanceResult<()> {
+        let unsigned_tx_key = psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]);
+        if self.global.get(&unsigned_tx_key) != other.global.get(&unsigned_tx_key) {
             return Err(GovernanceError::InvalidInput(
-                "PSBT is not finalized".to_string(),
+                "Cannot combine PSBTs for different transactions".to_string(),
             ));
         }
 
-        // Get unsigned transaction from global map
-        let unsigned_tx_key = vec![PsbtGlobalKey::UnsignedTx as u8];
-        let unsigned_tx = self.global.get(&unsigned_tx_key).ok_or_else(|| {
+        for (key, value) in &other.global {
+            self.global
+                .entry(key.clone())
+                .or_insert_with(|| value.clone());
+        }
+        for (index, other_input) in other.inputs.iter().enumerate() {
+            let input = self.input_mut(index);
+            let mut map = input.to_map();
+            for (key, value) in other_input.to_map() {
+                map.entry(key).or_insert(value);
+            }
+            *input = PsbtInput::from_map(map)?;
+        }
+        for (index, other_output) in other.outputs.iter().enumerate() {
+            let output = self.output_mut(index);
+            let mut map = output.to_map();
+            for (key, value) in other_output.to_map() {
+                map.entry(key).or_insert(value);
+            }
+            *output = PsbtOutput::from_map(map)?;
+        }
+        Ok(())
+    }
+
+    /// Get an input, adding empty inputs up to `input_index` if needed
+    fn input_mut(&mut self, input_index: usize) -> &mut PsbtInput {
+        if input_index >= self.inputs.len() {
+            self.inputs.resize_with(input_index + 1, PsbtInput::default);
+        }
+        &mut self.inputs[input_index]
+    }
+
+    /// Get an output, adding empty outputs up to `output_index` if needed
+    fn output_mut(&mut self, output_index: usize) -> &mut PsbtOutput {
+        if output_index >= self.outputs.len() {
+            self.outputs
+                .resize_with(output_index + 1, PsbtOutput::default);
+        }
+        &mut self.outputs[output_index]
+    }
+
+    /// Sign every input the key can spend
+    ///
+    /// Inputs that cannot be signed with this key are skipped and reported
+    /// in the returned summary.
+    pub fn sign_with_key(&mut self, secret_key: &SecretKey) -> GovernanceResult<SigningSummary> {
+        let tx = self.unsigned_tx()?;
+        let mut summary = SigningSummary::default();
+        for input_index in 0..tx.inputs.len() {
+            match self.sign_input(&tx, input_index, secret_key) {
+                Ok(()) => summary.signed.push(input_index),
+                Err(e) => summary.skipped.push((input_index, e.to_string())),
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Sign every input with a BIP32 derivation from this master key
+    ///
+    /// Derivation entries are matched on the master fingerprint, and the
+    /// derived child key must equal the recorded public key.
+    pub fn sign_with_xprv(
+        &mut self,
+        xprv: &ExtendedPrivateKey,
+    ) -> GovernanceResult<SigningSummary> {
+        let tx = self.unsigned_tx()?;
+        let fingerprint = xprv.fingerprint();
+        let mut summary = SigningSummary::default();
+
+        for input_index in 0..tx.inputs.len() {
+            let derivations: Vec<Bip32Derivation> = self
+                .input_bip32_derivations(input_index)?
+                .into_iter()
+                .filter(|derivation| derivation.master_fingerprint == fingerprint)
+                .collect();
+            if derivations.is_empty() {
+                summary.skipped.push((
+                    input_index,
+                    "no BIP32 derivation for this master key".to_string(),
+                ));
+                continue;
+            }
+
+            let mut result = Ok(());
+            for derivation in derivations {
+                result = derive_path(xprv, &derivation.path).and_then(|child| {
+                    let derived = child.to_extended_public().public_key_bytes();
+                    if derived[..] != derivation.pubkey[..] {
+                        return Err(GovernanceError::InvalidKey(format!(
+                            "derived key does not match {}",
+                            hex::encode(&derivation.pubkey)
+                        )));
+                    }
+                    self.sign_input(&tx, input_index, &child.private_key)
+                });
+                if result.is_err() {
+                    break;
+                }
+            }
+            match result {
+                Ok(()) => summary.signed.push(input_index),
+                Err(e) => summary.skipped.push((input_index, e.to_string())),
+            }
+        }
+        Ok(summary)
+    }
+
+    /// Parse the unsigned transaction from the global map
+    fn unsigned_tx(&self) -> GovernanceResult<Transaction> {
+        let key = psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]);
+        let unsigned_tx = self.global.get(&key).ok_or_else(|| {
             GovernanceError::InvalidInput("Missing unsigned transaction".to_string())
         })?;
+        Transaction::parse_without_witness(unsigned_tx)
+    }
+
+    /// Get the output spent by an input, from witness_utxo or non_witness_utxo
+    fn spent_output(&self, tx: &Transaction, input_index: usize) -> GovernanceResult<TxOut> {
+        let input = self.inputs.get(input_index);
+
+        if let Some(utxo) = input.and_then(|input| input.witness_utxo.as_ref()) {
+            return Ok(utxo.clone());
+        }
+        if let Some(prev_tx) = input.and_then(|input| input.non_witness_utxo.as_ref()) {
+            let prev_tx = Transaction::parse(prev_tx)?;
+            let txin = &tx.inputs[input_index];
+            if prev_tx.txid() != txin.previous_txid {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Input {} non_witness_utxo does not match its prevout",
+                    input_index
+                )));
+            }
+            return prev_tx
+                .outputs
+                .get(txin.previous_vout as usize)
+                .cloned()
+                .ok_or_else(|| {
+                    GovernanceError::InvalidInput(format!(
+                        "Input {} prevout index out of range",
+                        input_index
+                    ))
+                });
+        }
+        Err(GovernanceError::InvalidInput(format!(
+            "Input {} has no UTXO information",
+            input_index
+        )))
+    }
+
+    /// Sign one input and record the partial signature
+    fn sign_input(
+        &mut self,
+        tx: &Transaction,
+        input_index: usize,
+        secret_key: &SecretKey,
+    ) -> GovernanceResult<()> {
+        let secp = Secp256k1::new();
+        let pubkey = secret_key.public_key(&secp).serialize();
+        let sighash_type = self.sighash_type(input_index)?;
+        let utxo = self.spent_output(tx, input_index)?;
+
+        let input = self.inputs.get(input_index).cloned().unwrap_or_default();
+
+        // Unwrap P2SH to the redeem script
+        let mut script_pubkey = utxo.script_pubkey.as_slice();
+        if let Some(hash) = p2sh_hash(script_pubkey) {
+            let redeem_script = input.redeem_script.as_deref().ok_or_else(|| {
+                GovernanceError::InvalidInput(format!(
+                    "Input {} spends P2SH but has no redeem script",
+                    input_index
+                ))
+            })?;
+            if hash160(redeem_script) != hash {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Input {} redeem script does not match its output",
+                    input_index
+                )));
+            }
+            script_pubkey = redeem_script;
+        }
+
+        let digest = if let Some(hash) = p2wpkh_hash(script_pubkey) {
+            if hash160(&pubkey) != hash {
+                return Err(GovernanceError::InvalidKey(
+                    "key does not match P2WPKH output".to_string(),
+                ));
+            }
+            let script_code = p2wpkh_script_code(&hash);
+            segwit_v0_sighash_tx(tx, input_index, &script_code, utxo.value, sighash_type)?
+        } else if let Some(hash) = p2wsh_hash(script_pubkey) {
+            let witness_script = input.witness_script.as_deref().ok_or_else(|| {
+                GovernanceError::InvalidInput(format!(
+                    "Input {} spends P2WSH but has no witness script",
+                    input_index
+                ))
+            })?;
+            if <[u8; 32]>::from(Sha256::digest(witness_script)) != hash {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Input {} witness script does not match its output",
+                    input_index
+                )));
+            }
+            if !witness_script
+                .windows(pubkey.len())
+                .any(|window| window == pubkey)
+            {
+                return Err(GovernanceError::InvalidKey(
+                    "key is not in the witness script".to_string(),
+                ));
+            }
+            segwit_v0_sighash_tx(tx, input_index, witness_script, utxo.value, sighash_type)?
+        } else if matches!(
+            script_kind(script_pubkey),
+            "P2TR" | "unknown witness version"
+        ) {
+            return Err(GovernanceError::NotImplemented(format!(
+                "signing {} inputs",
+                script_kind(script_pubkey)
+            )));
+        } else {
+            // Legacy signatures don't commit to the amount, so BIP174
+            // requires the full previous transaction
+            if input.non_witness_utxo.is_none() {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Input {} spends a legacy output but has no non_witness_utxo",
+                    input_index
+                )));
+            }
+            let key_matches = match p2pkh_hash(script_pubkey) {
+                Some(hash) => hash160(&pubkey) == hash,
+                None => script_pubkey
+                    .windows(pubkey.len())
+                    .any(|window| window == pubkey),
+            };
+            if !key_matches {
+                return Err(GovernanceError::InvalidKey(format!(
+                    "key does not match {} script",
+                    script_kind(script_pubkey)
+                )));
+            }
+            legacy_sighash_tx(tx, input_index, script_pubkey, sighash_type)?
+        };
+
+        let message = Message::from_digest_slice(&digest)
+            .map_err(|e| GovernanceError::Cryptographic(format!("Invalid sighash: {}", e)))?;
+        let mut signature = secp
+            .sign_ecdsa(&message, secret_key)
+            .serialize_der()
+            .to_vec();
+        signature.push(sighash_type.to_byte());
+
+        self.add_partial_signature(input_index, pubkey.to_vec(), signature)
+    }
+
+    /// Finalize every input that is not already final
+    pub fn finalize(&mut self) -> GovernanceResult<()> {
+        let tx = self.unsigned_tx()?;
+        for input_index in 0..tx.inputs.len() {
+            self.finalize_input(input_index)?;
+        }
+        Ok(())
+    }
+
+    /// Build the final scriptSig/witness of an input from its partial signatures
+    ///
+    /// Supports P2PKH, P2WPKH, P2SH-P2WPKH, and CHECKMULTISIG scripts under
+    /// P2SH, P2WSH, or P2SH-P2WSH. Signing fields are removed once the input
+    /// is final; an input that is already final is left unchanged.
+    pub fn finalize_input(&mut self, input_index: usize) -> GovernanceResult<()> {
+        let tx = self.unsigned_tx()?;
+        if input_index >= tx.inputs.len() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Input index {} out of range ({} inputs)",
+                input_index,
+                tx.inputs.len()
+            )));
+        }
+        if self
+            .inputs
+            .get(input_index)
+            .is_some_and(PsbtInput::is_finalized)
+        {
+            return Ok(());
+        }
+        let utxo = self.spent_output(&tx, input_index)?;
+
+        let empty = PsbtInput::default();
+        let input = self.inputs.get(input_index).unwrap_or(&empty);
+        let signatures: Vec<(&[u8], &[u8])> = input
+            .partial_sigs
+            .iter()
+            .map(|(pubkey, signature)| (pubkey.as_slice(), signature.as_slice()))
+            .collect();
+        let signature_for = |pubkey_hash: &[u8; 20]| {
+            signatures
+                .iter()
+                .find(|(pubkey, _)| hash160(pubkey) == *pubkey_hash)
+                .copied()
+                .ok_or(GovernanceError::InsufficientSignatures { got: 0, need: 1 })
+        };
+
+        let script_pubkey = utxo.script_pubkey.as_slice();
+        let (script_sig, witness) = if let Some(hash) = p2pkh_hash(script_pubkey) {
+            let (pubkey, signature) = signature_for(&hash)?;
+            let mut script_sig = Vec::new();
+            push_data(&mut script_sig, signature);
+            push_data(&mut script_sig, pubkey);
+            (script_sig, None)
+        } else if let Some(hash) = p2wpkh_hash(script_pubkey) {
+            let (pubkey, signature) = signature_for(&hash)?;
+            (Vec::new(), Some(vec![signature.to_vec(), pubkey.to_vec()]))
+        } else if let Some(hash) = p2wsh_hash(script_pubkey) {
+            let witness_script = matching_witness_script(input.witness_script.as_ref(), &hash)?;
+            let mut witness = multisig_stack(witness_script, &signatures, "P2WSH")?;
+            witness.push(witness_script.to_vec());
+            (Vec::new(), Some(witness))
+        } else if let Some(hash) = p2sh_hash(script_pubkey) {
+            let redeem_script = input
+                .redeem_script
+                .as_ref()
+                .filter(|script| hash160(script) == hash)
+                .ok_or_else(|| {
+                    GovernanceError::InvalidInput(format!(
+                        "Input {} is missing a matching redeem script",
+                        input_index
+                    ))
+                })?;
+            let mut script_sig = Vec::new();
+            if let Some(hash) = p2wpkh_hash(redeem_script) {
+                let (pubkey, signature) = signature_for(&hash)?;
+                push_data(&mut script_sig, redeem_script);
+                (script_sig, Some(vec![signature.to_vec(), pubkey.to_vec()]))
+            } else if let Some(hash) = p2wsh_hash(redeem_script) {
+                let witness_script = matching_witness_script(input.witness_script.as_ref(), &hash)?;
+                let mut witness = multisig_stack(witness_script, &signatures, "P2SH-P2WSH")?;
+                witness.push(witness_script.to_vec());
+                push_data(&mut script_sig, redeem_script);
+                (script_sig, Some(witness))
+            } else {
+                for item in multisig_stack(redeem_script, &signatures, "P2SH")? {
+                    push_data(&mut script_sig, &item);
+                }
+                push_data(&mut script_sig, redeem_script);
+                (script_sig, None)
+            }
+        } else {
+            return Err(GovernanceError::NotImplemented(format!(
+                "finalizing {} inputs",
+                script_kind(script_pubkey)
+            )));
+        };
+
+        let input = self.input_mut(input_index);
+        input.partial_sigs.clear();
+        input.sighash_type = None;
+        input
+            .unknown
+            .remove(&psbt_key(PsbtInputKey::SighashType as u64, &[]));
+        input.redeem_script = None;
+        input.witness_script = None;
+        input.bip32_derivations.clear();
+        if !script_sig.is_empty() {
+            input.final_script_sig = Some(script_sig);
+        }
+        if witness.is_some() {
+            input.final_script_witness = witness;
+        }
+        Ok(())
+    }
+
+    /// Check if PSBT is finalized (all inputs have final script sig/witness)
+    pub fn is_finalized(&self) -> bool {
+        self.inputs.iter().all(PsbtInput::is_finalized)
+    }
+
+    /// Extract the final network transaction
+    ///
+    /// Splices each input's final scriptSig and witness into the unsigned
+    /// transaction; nothing else is recomputed.
+    pub fn extract_transaction(&self) -> GovernanceResult<Vec<u8>> {
+        let mut tx = self.unsigned_tx()?;
+
+        for (input_index, txin) in tx.inputs.iter_mut().enumerate() {
+            let input = match self.inputs.get(input_index) {
+                Some(input) if input.is_finalized() => input,
+                _ => {
+                    return Err(GovernanceError::InvalidInput(format!(
+                        "PSBT input {} is not finalized",
+                        input_index
+                    )))
+                }
+            };
+
+            txin.script_sig = input.final_script_sig.clone().unwrap_or_default();
+            txin.witness = input.final_script_witness.clone().unwrap_or_default();
+        }
+
+        Ok(tx.serialize())
+    }
+
+    /// Transaction fee: spent output amounts minus output amounts
+    ///
+    /// Every input needs a `witness_utxo` or `non_witness_utxo`; the error
+    /// names the first input without one.
+    pub fn fee(&self) -> GovernanceResult<u64> {
+        let tx = self.unsigned_tx()?;
+        let overflow = || GovernanceError::InvalidInput("Amount overflow".to_string());
+
+        let mut input_total = 0u64;
+        for index in 0..tx.inputs.len() {
+            let value = self.spent_output(&tx, index)?.value;
+            input_total = input_total.checked_add(value).ok_or_else(overflow)?;
+        }
+        let output_total = tx
+            .outputs
+            .iter()
+            .try_fold(0u64, |total, output| total.checked_add(output.value))
+            .ok_or_else(overflow)?;
+
+        input_total.checked_sub(output_total).ok_or_else(|| {
+            GovernanceError::InvalidInput(format!(
+                "Outputs ({} sat) exceed inputs ({} sat)",
+                output_total, input_total
+            ))
+        })
+    }
+
+    /// Estimated weight of the final transaction
+    ///
+    /// Finalized inputs count their actual scriptSig and witness. Other
+    /// inputs are sized from their spent script, redeem script, and witness
+    /// script, assuming [`ESTIMATED_ECDSA_SIGNATURE_LEN`]-byte signatures
+    /// and compressed keys.
+    pub fn estimated_weight(&self) -> GovernanceResult<usize> {
+        let mut tx = self.unsigned_tx()?;
+        for index in 0..tx.inputs.len() {
+            let (script_sig, witness) = self.estimated_satisfaction(&tx, index)?;
+            tx.inputs[index].script_sig = script_sig;
+            tx.inputs[index].witness = witness;
+        }
+        Ok(tx.serialize_without_witness().len() * 3 + tx.serialize().len())
+    }
+
+    /// Estimated virtual size of the final transaction (weight / 4, rounded up)
+    pub fn estimated_vsize(&self) -> GovernanceResult<usize> {
+        Ok(self.estimated_weight()?.div_ceil(4))
+    }
+
+    /// Estimated fee rate in sat/vB
+    pub fn fee_rate(&self) -> GovernanceResult<f64> {
+        Ok(self.fee()? as f64 / self.estimated_vsize()? as f64)
+    }
+
+    /// Final scriptSig and witness of an input, or placeholders of the expected size
+    fn estimated_satisfaction(
+        &self,
+        tx: &Transaction,
+        input_index: usize,
+    ) -> GovernanceResult<(Vec<u8>, Vec<Vec<u8>>)> {
+        let empty = PsbtInput::default();
+        let input = self.inputs.get(input_index).unwrap_or(&empty);
+        if input.is_finalized() {
+            return Ok((
+                input.final_script_sig.clone().unwrap_or_default(),
+                input.final_script_witness.clone().unwrap_or_default(),
+            ));
+        }
+
+        let signature = vec![0u8; ESTIMATED_ECDSA_SIGNATURE_LEN];
+        let pubkey = vec![0u8; 33];
+        let multisig = |script: &[u8]| -> GovernanceResult<Vec<Vec<u8>>> {
+            let (threshold, _) = parse_multisig(script).ok_or_else(|| {
+                GovernanceError::NotImplemented(format!(
+                    "estimating input {} with a non-multisig script",
+                    input_index
+                ))
+            })?;
+            let mut stack = vec![Vec::new()];
+            stack.resize(threshold + 1, signature.clone());
+            Ok(stack)
+        };
+        let witness_script = |hash: &[u8; 32]| -> GovernanceResult<Vec<Vec<u8>>> {
+            let script = matching_witness_script(input.witness_script.as_ref(), hash)?;
+            let mut witness = multisig(script)?;
+            witness.push(script.to_vec());
+            Ok(witness)
+        };
+
+        let utxo = self.spent_output(tx, input_index)?;
+        let script_pubkey = utxo.script_pubkey.as_slice();
+        let mut script_sig = Vec::new();
+        let witness = if p2pkh_hash(script_pubkey).is_some() {
+            push_data(&mut script_sig, &signature);
+            push_data(&mut script_sig, &pubkey);
+            Vec::new()
+        } else if p2wpkh_hash(script_pubkey).is_some() {
+            vec![signature, pubkey]
+        } else if let Some(hash) = p2wsh_hash(script_pubkey) {
+            witness_script(&hash)?
+        } else if let Some(hash) = p2sh_hash(script_pubkey) {
+            let redeem_script = input
+                .redeem_script
+                .as_ref()
+                .filter(|script| hash160(script) == hash)
+                .ok_or_else(|| {
+                    GovernanceError::InvalidInput(format!(
+                        "Input {} is missing a matching redeem script",
+                        input_index
+                    ))
+                })?;
+            let witness = if p2wpkh_hash(redeem_script).is_some() {
+                vec![signature, pubkey]
+            } else if let Some(hash) = p2wsh_hash(redeem_script) {
+                witness_script(&hash)?
+            } else {
+                for item in multisig(redeem_script)? {
+                    push_data(&mut script_sig, &item);
+                }
+                Vec::new()
+            };
+            push_data(&mut script_sig, redeem_script);
+            witness
+        } else if script_kind(script_pubkey) == "P2TR" {
+            vec![vec![0u8; ESTIMATED_SCHNORR_SIGNATURE_LEN]]
+        } else {
+            return Err(GovernanceError::NotImplemented(format!(
+                "estimating {} inputs",
+                script_kind(script_pubkey)
+            )));
+        };
+        Ok((script_sig, witness))
+    }
+
+    /// Check the PSBT for structural problems
+    ///
+    /// Unlike [`deserialize`](Self::deserialize), this never fails: every
+    /// problem found is reported, so PSBTs built or edited in memory can be
+    /// checked before they are signed or passed on.
+    pub fn validate(&self) -> Vec<PsbtIssue> {
+        let mut issues = Vec::new();
+
+        let unsigned_tx_entries: Vec<&Vec<u8>> = self
+            .global
+            .keys()
+            .filter(|key| {
+                split_psbt_key(key)
+                    .is_ok_and(|(key_type, _)| key_type == PsbtGlobalKey::UnsignedTx as u64)
+            })
+            .collect();
+        if unsigned_tx_entries.len() > 1 {
+            issues.push(PsbtIssue::error(
+                PsbtScope::Global,
+                PsbtIssueCode::DuplicateUnsignedTx,
+                format!(
+                    "{} unsigned transaction entries in the global map",
+                    unsigned_tx_entries.len()
+                ),
+            ));
+        }
+
+        let tx = match self
+            .global
+            .get(&psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]))
+        {
+            None => {
+                issues.push(PsbtIssue::error(
+                    PsbtScope::Global,
+                    PsbtIssueCode::MissingUnsignedTx,
+                    "Missing unsigned transaction".to_string(),
+                ));
+                None
+            }
+            Some(bytes) => match Transaction::parse_without_witness(bytes) {
+                Ok(tx) => Some(tx),
+                Err(e) => {
+                    let witness = Transaction::parse(bytes).is_ok_and(|tx| tx.has_witness());
+                    issues.push(if witness {
+                        PsbtIssue::error(
+                            PsbtScope::Global,
+                            PsbtIssueCode::UnsignedTxHasWitness,
+                            "Unsigned transaction carries witness data".to_string(),
+                        )
+                    } else {
+                        PsbtIssue::error(
+                            PsbtScope::Global,
+                            PsbtIssueCode::InvalidUnsignedTx,
+                            format!("Unsigned transaction does not parse: {}", e),
+                        )
+                    });
+                    None
+                }
+            },
+        };
+
+        if let Some(tx) = &tx {
+            for (index, input) in tx.inputs.iter().enumerate() {
+                if !input.script_sig.is_empty() {
+                    issues.push(PsbtIssue::error(
+                        PsbtScope::Input(index),
+                        PsbtIssueCode::UnsignedTxHasScriptSig,
+                        "Unsigned transaction input has a scriptSig".to_string(),
+                    ));
+                }
+            }
+            // Every transaction input and output needs exactly one map
+            for (kind, maps, expected) in [
+                ("input", self.inputs.len(), tx.inputs.len()),
+                ("output", self.outputs.len(), tx.outputs.len()),
+            ] {
+                if maps != expected {
+                    issues.push(PsbtIssue::error(
+                        PsbtScope::Global,
+                        PsbtIssueCode::MapCountMismatch,
+                        format!(
+                            "{} {} maps for {} transaction {}s",
+                            maps, kind, expected, kind
+                        ),
+                    ));
+                }
+            }
+        }
+
+        for (index, input) in self.inputs.iter().enumerate() {
+            let scope = PsbtScope::Input(index);
+
+            if let Some(utxo_tx) = &input.non_witness_utxo {
+                let prevout = tx.as_ref().and_then(|tx| tx.inputs.get(index));
+                match (Transaction::parse(utxo_tx), prevout) {
+                    (Err(e), _) => issues.push(PsbtIssue::error(
+                        scope,
+                        PsbtIssueCode::NonWitnessUtxoMismatch,
+                        format!("non_witness_utxo does not parse: {}", e),
+                    )),
+                    (Ok(utxo_tx), Some(prevout)) => {
+                        if utxo_tx.txid() != prevout.previous_txid {
+                            issues.push(PsbtIssue::error(
+                                scope,
+                                PsbtIssueCode::NonWitnessUtxoMismatch,
+                                "non_witness_utxo txid does not match the spent outpoint"
+                                    .to_string(),
+                            ));
+                        } else if prevout.previous_vout as usize >= utxo_tx.outputs.len() {
+                            issues.push(PsbtIssue::error(
+                                scope,
+                                PsbtIssueCode::NonWitnessUtxoMismatch,
+                                format!("non_witness_utxo has no output {}", prevout.previous_vout),
+                            ));
+                        }
+                    }
+                    (Ok(_), None) => {}
+                }
+            }
+
+            for pubkey in input.partial_sigs.keys() {
+                if secp256k1::PublicKey::from_slice(pubkey).is_err() {
+                    issues.push(PsbtIssue::error(
+                        scope,
+                        PsbtIssueCode::InvalidPartialSigPubkey,
+                        format!(
+                            "Partial signature key {} is not a valid point",
+                            hex::encode(pubkey)
+                        ),
+                    ));
+                }
+            }
+
+            if input.is_finalized() && !input.partial_sigs.is_empty() {
+                issues.push(PsbtIssue::warning(
+                    scope,
+                    PsbtIssueCode::FinalizedWithPartialSigs,
+                    format!(
+                        "Finalized input still has {} partial signatures",
+                        input.partial_sigs.len()
+                    ),
+                ));
+            }
 
-        // Build final transaction by combining unsigned tx with final scripts
-        // This is a simplified version - full implementation would parse transaction
-        // and insert final script sig/witness data
+            let typed = PsbtInput {
+                unknown: BTreeMap::new(),
+                ..input.clone()
+            }
+            .to_map();
+            for (key, value) in &input.unknown {
+                if typed.contains_key(key) {
+                    issues.push(duplicate_key_issue(scope, key));
+                } else if *key == psbt_key(PsbtInputKey::SighashType as u64, &[])
+                    && parse_sighash_type(value).is_none()
+                {
+                    issues.push(PsbtIssue::error(
+                        scope,
+                        PsbtIssueCode::UnknownSighashType,
+                        format!("Unrecognized sighash type {}", hex::encode(value)),
+                    ));
+                }
+            }
+        }
 
-        Ok(unsigned_tx.clone())
+        for (index, output) in self.outputs.iter().enumerate() {
+            let typed = PsbtOutput {
+                unknown: BTreeMap::new(),
+                ..output.clone()
+            }
+            .to_map();
+            for key in output.unknown.keys().filter(|key| typed.contains_key(*key)) {
+                issues.push(duplicate_key_issue(PsbtScope::Output(index), key));
+            }
+        }
+
+        issues
+    }
+
+    /// Check there is exactly one input and output map per transaction
+    /// input and output
+    pub fn validate_structure(&self) -> GovernanceResult<()> {
+        let unsigned_tx_key = psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]);
+        let unsigned_tx = self.global.get(&unsigned_tx_key).ok_or_else(|| {
+            GovernanceError::InvalidInput("Missing unsigned transaction".to_string())
+        })?;
+        let (input_count, output_count) = unsigned_tx_io_counts(unsigned_tx)?;
+
+        if self.inputs.len() != input_count || self.outputs.len() != output_count {
+            return Err(GovernanceError::InvalidInput(format!(
+                "PSBT has {} input and {} output maps but the transaction has {} inputs and {} outputs",
+                self.inputs.len(),
+                self.outputs.len(),
+                input_count,
+                output_count
+            )));
+        }
+        Ok(())
     }
 
     /// Serialize PSBT to bytes
+    ///
+    /// Fails unless [`validate_structure`](Self::validate_structure) passes.
+    /// Keys within each map are written in ascending byte order, as BIP174
+    /// recommends, so equal PSBTs always serialize to identical bytes.
     pub fn serialize(&self) -> GovernanceResult<Vec<u8>> {
+        self.validate_structure()?;
+
         let mut result = Vec::new();
 
         // Magic bytes
@@ -281,27 +1749,24 @@ impl PartiallySignedTransaction {
         // Global map
         serialize_map(&mut result, &self.global)?;
 
-        // Separator between global and inputs
-        result.push(PSBT_SEPARATOR);
-
-        // Input maps
-        for input_map in &self.inputs {
-            serialize_map(&mut result, input_map)?;
-            result.push(PSBT_SEPARATOR);
+        // Input maps, then output maps, each terminated by its own end marker
+        for input in &self.inputs {
+            serialize_map(&mut result, &input.to_map())?;
         }
-
-        // Output maps
-        for output_map in &self.outputs {
-            serialize_map(&mut result, output_map)?;
-            result.push(PSBT_SEPARATOR);
+        for output in &self.outputs {
+            serialize_map(&mut result, &output.to_map())?;
         }
 
         Ok(result)
     }
 
     /// Deserialize PSBT from bytes
+    ///
+    /// The number of input and output maps is taken from the unsigned
+    /// transaction in the global map, as required by BIP174. Keys need not be
+    /// in canonical order; `serialize` writes them back sorted.
     pub fn deserialize(data: &[u8]) -> GovernanceResult<Self> {
-        if data.len() < 5 || &data[..4] != &PSBT_MAGIC || data[4] != PSBT_SEPARATOR {
+        if data.len() < 5 || data[..4] != PSBT_MAGIC || data[4] != PSBT_SEPARATOR {
             return Err(GovernanceError::InvalidInput(
                 "Invalid PSBT magic bytes".to_string(),
             ));
@@ -310,73 +1775,486 @@ impl PartiallySignedTransaction {
         let mut offset = 5;
 
         // Parse global map
-        let (global, new_offset) = deserialize_map(&data[offset..])?;
-        offset += new_offset;
-
-        // Skip separator
-        if offset >= data.len() || data[offset] != PSBT_SEPARATOR {
-            return Err(GovernanceError::InvalidInput(
-                "Missing separator after global map".to_string(),
-            ));
+        let (global, consumed) = deserialize_map(&data[offset..])?;
+        offset += consumed;
+        for (key, value) in &global {
+            let (key_type, key_data) = split_psbt_key(key)?;
+            if key_type == PsbtGlobalKey::Xpub as u64 {
+                check_global_xpub(key_data, value)?;
+            }
         }
-        offset += 1;
 
-        // Parse input maps
-        let mut inputs = Vec::new();
-        // Determine number of inputs from unsigned transaction
-        // For now, parse until we hit output separator or end
-        while offset < data.len() && data[offset] != PSBT_SEPARATOR {
-            let (input_map, new_offset) = deserialize_map(&data[offset..])?;
-            inputs.push(input_map);
-            offset += new_offset;
+        let unsigned_tx_key = psbt_key(PsbtGlobalKey::UnsignedTx as u64, &[]);
+        let unsigned_tx = global.get(&unsigned_tx_key).ok_or_else(|| {
+            GovernanceError::InvalidInput("Missing unsigned transaction".to_string())
+        })?;
+        let (input_count, output_count) = unsigned_tx_io_counts(unsigned_tx)?;
 
-            // Skip separator
-            if offset < data.len() && data[offset] == PSBT_SEPARATOR {
-                offset += 1;
-                break; // Separator indicates start of outputs
-            }
+        // Parse exactly one map per input, then one per output
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            let (input_map, consumed) = deserialize_map(&data[offset..])?;
+            inputs.push(PsbtInput::from_map(input_map)?);
+            offset += consumed;
         }
 
-        // Parse output maps
         let mut outputs = Vec::new();
-        while offset < data.len() {
-            if data[offset] == PSBT_SEPARATOR && offset + 1 >= data.len() {
-                break; // Final separator
-            }
-            let (output_map, new_offset) = deserialize_map(&data[offset..])?;
-            outputs.push(output_map);
-            offset += new_offset;
+        for _ in 0..output_count {
+            let (output_map, consumed) = deserialize_map(&data[offset..])?;
+            outputs.push(PsbtOutput::from_map(output_map)?);
+            offset += consumed;
+        }
 
-            if offset < data.len() && data[offset] == PSBT_SEPARATOR {
-                offset += 1;
-            }
+        if offset != data.len() {
+            return Err(GovernanceError::InvalidInput(
+                "Unexpected data after PSBT".to_string(),
+            ));
         }
 
-        // Extract version
-        let version_key = vec![PsbtGlobalKey::Version as u8];
-        let version = global
-            .get(&version_key)
-            .and_then(|v| v.first().copied())
-            .unwrap_or(0);
+        // Extract version (u32 little-endian, 0 when absent)
+        let version_key = psbt_key(PsbtGlobalKey::Version as u64, &[]);
+        let version = match global.get(&version_key) {
+            Some(value) => {
+                let version = <[u8; 4]>::try_from(value.as_slice())
+                    .map(u32::from_le_bytes)
+                    .map_err(|_| {
+                        GovernanceError::InvalidInput(format!(
+                            "PSBT version must be 4 bytes, got {}",
+                            value.len()
+                        ))
+                    })?;
+                u8::try_from(version).map_err(|_| {
+                    GovernanceError::InvalidInput(format!("Unsupported PSBT version {}", version))
+                })?
+            }
+            None => 0,
+        };
 
-        Ok(PartiallySignedTransaction {
+        let psbt = PartiallySignedTransaction {
             global,
             inputs,
             outputs,
             version,
+        };
+        psbt.validate_structure()?;
+        Ok(psbt)
+    }
+
+    /// Encode PSBT as base64 (standard alphabet, padded)
+    pub fn to_base64(&self) -> GovernanceResult<String> {
+        use base64::{engine::general_purpose, Engine as _};
+        Ok(general_purpose::STANDARD.encode(self.serialize()?))
+    }
+
+    /// Decode PSBT from base64, ignoring embedded whitespace
+    ///
+    /// Returns `GovernanceError::Serialization` if the string is not valid
+    /// base64, and `GovernanceError::InvalidInput` if it decodes to bytes
+    /// that are not a valid PSBT.
+    pub fn from_base64(s: &str) -> GovernanceResult<Self> {
+        use base64::{engine::general_purpose, Engine as _};
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        let bytes = general_purpose::STANDARD
+            .decode(compact)
+            .map_err(|e| GovernanceError::Serialization(format!("Invalid base64: {}", e)))?;
+        Self::deserialize(&bytes)
+    }
+
+    /// Encode PSBT as lowercase hex
+    pub fn to_hex(&self) -> GovernanceResult<String> {
+        Ok(hex::encode(self.serialize()?))
+    }
+
+    /// Decode PSBT from hex, ignoring surrounding whitespace
+    pub fn from_hex(s: &str) -> GovernanceResult<Self> {
+        let bytes = hex::decode(s.trim())
+            .map_err(|e| GovernanceError::Serialization(format!("Invalid hex: {}", e)))?;
+        Self::deserialize(&bytes)
+    }
+
+    /// Decode PSBT from either hex or base64, ignoring embedded whitespace
+    ///
+    /// Input made only of hex digits is decoded as hex; anything else as
+    /// base64. A base64 PSBT always starts with `cHNidP8`, so the two
+    /// cannot be confused.
+    pub fn auto_decode(s: &str) -> GovernanceResult<Self> {
+        let compact: String = s.chars().filter(|c| !c.is_whitespace()).collect();
+        if !compact.is_empty() && compact.chars().all(|c| c.is_ascii_hexdigit()) {
+            Self::from_hex(&compact)
+        } else {
+            Self::from_base64(&compact)
+        }
+    }
+}
+
+impl fmt::Display for PartiallySignedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_base64() {
+            Ok(encoded) => f.write_str(&encoded),
+            Err(e) => write!(f, "<invalid PSBT: {}>", e),
+        }
+    }
+}
+
+impl fmt::Debug for PartiallySignedTransaction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.to_hex() {
+            Ok(encoded) => write!(f, "PartiallySignedTransaction({})", encoded),
+            // Not serializable (e.g. a bad unsigned transaction): show the maps
+            Err(_) => f
+                .debug_struct("PartiallySignedTransaction")
+                .field("global", &self.global)
+                .field("inputs", &self.inputs)
+                .field("outputs", &self.outputs)
+                .field("version", &self.version)
+                .finish(),
+        }
+    }
+}
+
+/// Derive a child key along a full path from `xprv`
+fn derive_path(xprv: &ExtendedPrivateKey, path: &[u32]) -> GovernanceResult<ExtendedPrivateKey> {
+    let mut key = xprv.clone();
+    for &index in path {
+        key = key.derive_child(index)?.0;
+    }
+    Ok(key)
+}
+
+/// Key hash of a P2PKH output (`OP_DUP OP_HASH160 <20> OP_EQUALVERIFY OP_CHECKSIG`)
+fn p2pkh_hash(script: &[u8]) -> Option<[u8; 20]> {
+    match script {
+        [0x76, 0xa9, 0x14, hash @ .., 0x88, 0xac] if hash.len() == 20 => hash.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Script hash of a P2SH output (`OP_HASH160 <20> OP_EQUAL`)
+fn p2sh_hash(script: &[u8]) -> Option<[u8; 20]> {
+    match script {
+        [0xa9, 0x14, hash @ .., 0x87] if hash.len() == 20 => hash.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Key hash of a P2WPKH output (`OP_0 <20>`)
+fn p2wpkh_hash(script: &[u8]) -> Option<[u8; 20]> {
+    match script {
+        [0x00, 0x14, hash @ ..] if hash.len() == 20 => hash.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Script hash of a P2WSH output (`OP_0 <32>`)
+fn p2wsh_hash(script: &[u8]) -> Option<[u8; 32]> {
+    match script {
+        [0x00, 0x20, hash @ ..] if hash.len() == 32 => hash.try_into().ok(),
+        _ => None,
+    }
+}
+
+/// Human-readable name of an output script type
+fn script_kind(script: &[u8]) -> &'static str {
+    match script {
+        [0x51, 0x20, ..] if script.len() == 34 => "P2TR",
+        [0x6a, ..] => "OP_RETURN",
+        [version, len, ..]
+            if (0x51..=0x60).contains(version) && *len as usize == script.len() - 2 =>
+        {
+            "unknown witness version"
+        }
+        [33, .., 0xac] if script.len() == 35 => "P2PK",
+        [65, .., 0xac] if script.len() == 67 => "P2PK",
+        _ if parse_multisig(script).is_some() => "bare multisig",
+        _ => "non-standard",
+    }
+}
+
+/// Get the witness script if its hash matches a P2WSH program
+fn matching_witness_script<'a>(
+    witness_script: Option<&'a Vec<u8>>,
+    hash: &[u8; 32],
+) -> GovernanceResult<&'a [u8]> {
+    witness_script
+        .filter(|script| <[u8; 32]>::from(Sha256::digest(script)) == *hash)
+        .map(Vec::as_slice)
+        .ok_or_else(|| {
+            GovernanceError::InvalidInput("Missing a matching witness script".to_string())
         })
+}
+
+/// Parse `OP_m <pubkey>... OP_n OP_CHECKMULTISIG` into (m, pubkeys)
+fn parse_multisig(script: &[u8]) -> Option<(usize, Vec<&[u8]>)> {
+    let (&first, rest) = script.split_first()?;
+    let (&checkmultisig, rest) = rest.split_last()?;
+    let (&last, mut rest) = rest.split_last()?;
+    if !(0x51..=0x60).contains(&first) || !(0x51..=0x60).contains(&last) || checkmultisig != 0xae {
+        return None;
+    }
+
+    let mut pubkeys = Vec::new();
+    while let Some((&len, tail)) = rest.split_first() {
+        if (len != 33 && len != 65) || tail.len() < len as usize {
+            return None;
+        }
+        pubkeys.push(&tail[..len as usize]);
+        rest = &tail[len as usize..];
     }
+
+    let threshold = (first - 0x50) as usize;
+    if pubkeys.len() != (last - 0x50) as usize || threshold > pubkeys.len() {
+        return None;
+    }
+    Some((threshold, pubkeys))
 }
 
-/// Serialize a key-value map (CompactSize encoding)
-fn serialize_map(result: &mut Vec<u8>, map: &HashMap<Vec<u8>, Vec<u8>>) -> GovernanceResult<()> {
+/// Build the CHECKMULTISIG stack: a dummy element then signatures in key order
+fn multisig_stack(
+    script: &[u8],
+    signatures: &[(&[u8], &[u8])],
+    kind: &str,
+) -> GovernanceResult<Vec<Vec<u8>>> {
+    let (threshold, pubkeys) = parse_multisig(script).ok_or_else(|| {
+        GovernanceError::NotImplemented(format!("finalizing non-multisig {} scripts", kind))
+    })?;
+
+    let mut stack = vec![Vec::new()];
+    stack.extend(
+        pubkeys
+            .iter()
+            .filter_map(|pubkey| signatures.iter().find(|(key, _)| key == pubkey))
+            .take(threshold)
+            .map(|(_, signature)| signature.to_vec()),
+    );
+    if stack.len() - 1 < threshold {
+        return Err(GovernanceError::InsufficientSignatures {
+            got: stack.len() - 1,
+            need: threshold,
+        });
+    }
+    Ok(stack)
+}
+
+/// Decode a serialized witness stack: `<count>(<len><item>)*`
+fn parse_witness(data: &[u8]) -> GovernanceResult<Vec<Vec<u8>>> {
+    let (count, mut offset) = read_compact_size(data)?;
+    let mut items = Vec::with_capacity(count.min(data.len()));
+    for _ in 0..count {
+        let (len, consumed) = read_compact_size(&data[offset..])?;
+        offset += consumed;
+        items.push(take(data, &mut offset, len)?.to_vec());
+    }
+    if offset != data.len() {
+        return Err(GovernanceError::InvalidInput(
+            "Unexpected data after witness".to_string(),
+        ));
+    }
+    Ok(items)
+}
+
+/// Append a minimal data push to a script
+fn push_data(script: &mut Vec<u8>, data: &[u8]) {
+    match data.len() {
+        len @ 0..=0x4b => script.push(len as u8),
+        len @ 0x4c..=0xff => script.extend_from_slice(&[0x4c, len as u8]),
+        len => {
+            script.push(0x4d);
+            script.extend_from_slice(&(len as u16).to_le_bytes());
+        }
+    }
+    script.extend_from_slice(data);
+}
+
+/// Reject key data on key types that are a single byte
+fn expect_bare_key(key_data: &[u8], field: &str) -> GovernanceResult<()> {
+    if !key_data.is_empty() {
+        return Err(GovernanceError::InvalidInput(format!(
+            "PSBT {} key must not carry key data",
+            field
+        )));
+    }
+    Ok(())
+}
+
+/// Check that key data is a 33 or 65 byte public key
+fn check_pubkey(pubkey: &[u8]) -> GovernanceResult<()> {
+    if pubkey.len() != 33 && pubkey.len() != 65 {
+        return Err(GovernanceError::InvalidInput(format!(
+            "Invalid PSBT public key length {}",
+            pubkey.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Build a map key: `<keytype (compact size)><keydata>`
+///
+/// Every key is built through this function so key types of 0xfd and above
+/// get their multi-byte encoding.
+pub fn psbt_key(key_type: u64, key_data: &[u8]) -> Vec<u8> {
+    let mut key = Vec::with_capacity(1 + key_data.len());
+    write_compact_size(&mut key, key_type as usize);
+    key.extend_from_slice(key_data);
+    key
+}
+
+/// Split a map key into its key type and key data
+///
+/// Rejects empty keys and key types that are not minimally encoded, since
+/// those would not serialize back to the same bytes.
+pub fn split_psbt_key(key: &[u8]) -> GovernanceResult<(u64, &[u8])> {
+    if key.is_empty() {
+        return Err(GovernanceError::InvalidInput("Empty PSBT key".to_string()));
+    }
+    let (key_type, consumed) = read_compact_size(key)?;
+    let mut canonical = Vec::new();
+    write_compact_size(&mut canonical, key_type);
+    if canonical.len() != consumed {
+        return Err(GovernanceError::InvalidInput(format!(
+            "Non-minimal key type encoding in key {}",
+            hex::encode(key)
+        )));
+    }
+    Ok((key_type as u64, &key[consumed..]))
+}
+
+/// Serialize a key origin: `<master_fp(4)><index(4, little-endian)>*`
+fn encode_key_origin(master_fingerprint: &[u8; 4], path: &[u32]) -> Vec<u8> {
+    let mut value = Vec::with_capacity(4 + 4 * path.len());
+    value.extend_from_slice(master_fingerprint);
+    for &index in path {
+        value.extend_from_slice(&index.to_le_bytes());
+    }
+    value
+}
+
+/// Parse a key origin into (master fingerprint, path)
+fn decode_key_origin(value: &[u8]) -> GovernanceResult<([u8; 4], Vec<u32>)> {
+    if value.len() < 4 || !value.len().is_multiple_of(4) {
+        return Err(GovernanceError::InvalidInput(
+            "Malformed BIP32 derivation".to_string(),
+        ));
+    }
+    let mut master_fingerprint = [0u8; 4];
+    master_fingerprint.copy_from_slice(&value[..4]);
+    let path = value[4..]
+        .chunks(4)
+        .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+        .collect();
+    Ok((master_fingerprint, path))
+}
+
+/// Serialize a derivation value
+fn encode_derivation(derivation: &Bip32Derivation) -> Vec<u8> {
+    encode_key_origin(&derivation.master_fingerprint, &derivation.path)
+}
+
+/// Parse a derivation from its key data (the public key) and value
+fn decode_derivation(pubkey: &[u8], value: &[u8]) -> GovernanceResult<Bip32Derivation> {
+    check_pubkey(pubkey)?;
+    let (master_fingerprint, path) = decode_key_origin(value)?;
+    Ok(Bip32Derivation {
+        pubkey: pubkey.to_vec(),
+        path,
+        master_fingerprint,
+    })
+}
+
+/// Check a global xpub entry: `<0x01><xpub(78)>` -> key origin
+fn check_global_xpub(key_data: &[u8], value: &[u8]) -> GovernanceResult<()> {
+    ExtendedPublicKey::from_bytes(key_data)?;
+    decode_key_origin(value)?;
+    Ok(())
+}
+
+/// Add a derivation, replacing any existing entry for the same public key
+///
+/// Derivations are kept sorted by public key, the order they serialize in,
+/// so a PSBT compares equal to itself after a serialization round trip.
+fn set_derivation(derivations: &mut Vec<Bip32Derivation>, derivation: Bip32Derivation) {
+    match derivations.binary_search_by(|d| d.pubkey.cmp(&derivation.pubkey)) {
+        Ok(index) => derivations[index] = derivation,
+        Err(index) => derivations.insert(index, derivation),
+    }
+}
+
+/// Serialize a witness stack: `<count>(<len><item>)*`
+fn encode_witness(witness: &[Vec<u8>]) -> Vec<u8> {
+    let mut value = Vec::new();
+    write_compact_size(&mut value, witness.len());
+    for item in witness {
+        write_compact_size(&mut value, item.len());
+        value.extend_from_slice(item);
+    }
+    value
+}
+
+/// Parse a PSBT_IN_SIGHASH_TYPE value (u32 little-endian) we can sign with
+fn parse_sighash_type(value: &[u8]) -> Option<SighashType> {
+    match value {
+        [byte, 0, 0, 0] => SighashType::from_byte(*byte),
+        _ => None,
+    }
+}
+
+/// Issue for an `unknown` entry that shadows a typed field
+fn duplicate_key_issue(scope: PsbtScope, key: &[u8]) -> PsbtIssue {
+    PsbtIssue::error(
+        scope,
+        PsbtIssueCode::DuplicateKey,
+        format!("Key {} appears twice in the map", hex::encode(key)),
+    )
+}
+
+/// Build a proprietary key: `<0xfc><identifier_len><identifier><subtype><keydata>`
+///
+/// The proprietary key type is 0xfc in every map.
+fn proprietary_key(identifier: &[u8], subtype: u32, keydata: &[u8]) -> Vec<u8> {
+    let mut key = psbt_key(PsbtGlobalKey::Proprietary as u64, &[]);
+    write_compact_size(&mut key, identifier.len());
+    key.extend_from_slice(identifier);
+    write_compact_size(&mut key, subtype as usize);
+    key.extend_from_slice(keydata);
+    key
+}
+
+/// Read the input and output counts from an unsigned transaction
+///
+/// The transaction must use the non-witness serialization with empty
+/// scriptSigs, and must be fully consumed.
+fn unsigned_tx_io_counts(tx: &[u8]) -> GovernanceResult<(usize, usize)> {
+    let tx = Transaction::parse_without_witness(tx)?;
+    if tx.inputs.iter().any(|input| !input.script_sig.is_empty()) {
+        return Err(GovernanceError::InvalidInput(
+            "Unsigned transaction has non-empty scriptSig".to_string(),
+        ));
+    }
+    Ok((tx.inputs.len(), tx.outputs.len()))
+}
+
+/// Take `len` bytes from `data` at `offset`, advancing the offset
+fn take<'a>(data: &'a [u8], offset: &mut usize, len: usize) -> GovernanceResult<&'a [u8]> {
+    let end = offset
+        .checked_add(len)
+        .filter(|&end| end <= data.len())
+        .ok_or_else(|| GovernanceError::InvalidInput("Unexpected end of data".to_string()))?;
+    let bytes = &data[*offset..end];
+    *offset = end;
+    Ok(bytes)
+}
+
+/// Raw PSBT key-value map, ordered by key
+type PsbtMap = BTreeMap<Vec<u8>, Vec<u8>>;
+
+/// Serialize a key-value map (CompactSize encoding), in key order
+fn serialize_map(result: &mut Vec<u8>, map: &PsbtMap) -> GovernanceResult<()> {
     for (key, value) in map {
         // Key length (compact size)
-        write_compact_size(result, key.len())?;
+        write_compact_size(result, key.len());
         result.extend_from_slice(key);
 
         // Value length (compact size)
-        write_compact_size(result, value.len())?;
+        write_compact_size(result, value.len());
         result.extend_from_slice(value);
     }
 
@@ -387,13 +2265,16 @@ fn serialize_map(result: &mut Vec<u8>, map: &HashMap<Vec<u8>, Vec<u8>>) -> Gover
 }
 
 /// Deserialize a key-value map
-fn deserialize_map(data: &[u8]) -> GovernanceResult<(HashMap<Vec<u8>, Vec<u8>>, usize)> {
-    let mut map = HashMap::new();
+fn deserialize_map(data: &[u8]) -> GovernanceResult<(PsbtMap, usize)> {
+    let mut map = BTreeMap::new();
     let mut offset = 0;
 
-    while offset < data.len() {
-        // Check for end marker
-        if data[offset] == 0x00 {
+    loop {
+        // Every map must be terminated by an end marker
+        let marker = *data
+            .get(offset)
+            .ok_or_else(|| GovernanceError::InvalidInput("Unterminated PSBT map".to_string()))?;
+        if marker == 0x00 {
             offset += 1;
             break;
         }
@@ -401,113 +2282,79 @@ fn deserialize_map(data: &[u8]) -> GovernanceResult<(HashMap<Vec<u8>, Vec<u8>>,
         // Read key
         let (key_len, len_offset) = read_compact_size(&data[offset..])?;
         offset += len_offset;
-
-        if offset + key_len > data.len() {
-            return Err(GovernanceError::InvalidInput(
-                "Invalid key length".to_string(),
-            ));
-        }
-        let key = data[offset..offset + key_len].to_vec();
-        offset += key_len;
+        let key = take(data, &mut offset, key_len)
+            .map_err(|_| GovernanceError::InvalidInput("Invalid key length".to_string()))?
+            .to_vec();
 
         // Read value
         let (value_len, len_offset) = read_compact_size(&data[offset..])?;
         offset += len_offset;
+        let value = take(data, &mut offset, value_len)
+            .map_err(|_| GovernanceError::InvalidInput("Invalid value length".to_string()))?
+            .to_vec();
 
-        if offset + value_len > data.len() {
+        if map.insert(key, value).is_some() {
             return Err(GovernanceError::InvalidInput(
-                "Invalid value length".to_string(),
+                "Duplicate key in PSBT map".to_string(),
             ));
         }
-        let value = data[offset..offset + value_len].to_vec();
-        offset += value_len;
-
-        map.insert(key, value);
     }
 
     Ok((map, offset))
 }
 
-/// Write compact size (VarInt encoding)
-fn write_compact_size(result: &mut Vec<u8>, size: usize) -> GovernanceResult<()> {
-    if size < 0xfd {
-        result.push(size as u8);
-    } else if size <= 0xffff {
-        result.push(0xfd);
-        result.extend_from_slice(&(size as u16).to_le_bytes());
-    } else if size <= 0xffffffff {
-        result.push(0xfe);
-        result.extend_from_slice(&(size as u32).to_le_bytes());
-    } else {
-        result.push(0xff);
-        result.extend_from_slice(&(size as u64).to_le_bytes());
-    }
-    Ok(())
-}
-
-/// Read compact size (VarInt decoding)
-fn read_compact_size(data: &[u8]) -> GovernanceResult<(usize, usize)> {
-    if data.is_empty() {
-        return Err(GovernanceError::InvalidInput(
-            "Unexpected end of data".to_string(),
-        ));
-    }
-
-    match data[0] {
-        n if n < 0xfd => Ok((n as usize, 1)),
-        0xfd => {
-            if data.len() < 3 {
-                return Err(GovernanceError::InvalidInput(
-                    "Invalid compact size".to_string(),
-                ));
-            }
-            let value = u16::from_le_bytes([data[1], data[2]]) as usize;
-            Ok((value, 3))
-        }
-        0xfe => {
-            if data.len() < 5 {
-                return Err(GovernanceError::InvalidInput(
-                    "Invalid compact size".to_string(),
-                ));
-            }
-            let value = u32::from_le_bytes([data[1], data[2], data[3], data[4]]) as usize;
-            Ok((value, 5))
-        }
-        0xff => {
-            if data.len() < 9 {
-                return Err(GovernanceError::InvalidInput(
-                    "Invalid compact size".to_string(),
-                ));
-            }
-            let value = u64::from_le_bytes([
-                data[1], data[2], data[3], data[4], data[5], data[6], data[7], data[8],
-            ]) as usize;
-            Ok((value, 9))
-        }
-        _ => Err(GovernanceError::InvalidInput(
-            "Invalid compact size marker".to_string(),
-        )),
-    }
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    /// Version 1, one input, one output, locktime 0
+    fn one_in_one_out_tx() -> Vec<u8> {
+        let mut unsigned_tx = vec![0x01, 0x00, 0x00, 0x00, 0x01];
+        unsigned_tx.extend_from_slice(&[0x00; 36]);
+        unsigned_tx.extend_from_slice(&[0x00, 0xff, 0xff, 0xff, 0xff, 0x01]);
+        unsigned_tx.extend_from_slice(&[0x00; 8]);
+        unsigned_tx.extend_from_slice(&[0x00, 0x00, 0x00, 0x00, 0x00]);
+        unsigned_tx
+    }
+
     #[test]
     fn test_psbt_creation() {
-        let unsigned_tx = vec![0x01, 0x00, 0x00, 0x00]; // Dummy transaction
-        let psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+        let psbt = PartiallySignedTransaction::new(&one_in_one_out_tx()).unwrap();
 
         assert_eq!(psbt.version, 0);
         assert!(psbt
             .global
             .contains_key(&vec![PsbtGlobalKey::UnsignedTx as u8]));
+        assert!(!psbt
+            .global
+            .contains_key(&vec![PsbtGlobalKey::Version as u8]));
+        assert_eq!(psbt.inputs, vec![PsbtInput::default()]);
+        assert_eq!(psbt.outputs, vec![PsbtOutput::default()]);
+        assert!(psbt.validate_structure().is_ok());
+
+        // Counts must be readable from the transaction, and no larger than it
+        assert!(PartiallySignedTransaction::new(&[0x01, 0x00, 0x00, 0x00]).is_err());
+        let mut truncated = one_in_one_out_tx()[..5].to_vec();
+        truncated[4] = 0xfd;
+        truncated.extend_from_slice(&[0xff, 0xff]);
+        assert!(PartiallySignedTransaction::new(&truncated).is_err());
+        assert!(PartiallySignedTransaction::new(&one_in_one_out_tx()[..46]).is_err());
+
+        // The same rules as deserialize: no witness serialization, no scriptSig
+        let unsigned_tx = one_in_one_out_tx();
+        let mut segwit = unsigned_tx[..4].to_vec();
+        segwit.extend_from_slice(&[0x00, 0x01]);
+        segwit.extend_from_slice(&unsigned_tx[4..]);
+        assert!(PartiallySignedTransaction::new(&segwit).is_err());
+        let mut script_sig = unsigned_tx.clone();
+        script_sig[41] = 0x01;
+        script_sig.insert(42, 0x51);
+        assert!(PartiallySignedTransaction::new(&script_sig).is_err());
     }
 
     #[test]
     fn test_serialize_deserialize() {
-        let unsigned_tx = vec![0x01, 0x00, 0x00, 0x00];
+        let unsigned_tx = one_in_one_out_tx();
         let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
         // Add some data
@@ -518,12 +2365,14 @@ mod tests {
         let deserialized = PartiallySignedTransaction::deserialize(&serialized).unwrap();
 
         assert_eq!(psbt.global, deserialized.global);
+        assert_eq!(psbt.inputs, deserialized.inputs);
+        assert_eq!(deserialized.outputs.len(), 1);
     }
 
     #[test]
     fn test_compact_size_encoding() {
         let mut result = Vec::new();
-        write_compact_size(&mut result, 253).unwrap();
+        write_compact_size(&mut result, 253);
         assert_eq!(result[0], 0xfd);
 
         let (value, offset) = read_compact_size(&result).unwrap();
@@ -0,0 +1,519 @@
+//! # Remote Signing
+//!
+//! Forward governance signing requests to a signing daemon over a Unix
+//! domain socket, so the workstation running the CLI never holds the key.
+//!
+//! Frames are JSON with a 4-byte big-endian length prefix. Each connection
+//! starts with a `hello` / `hello_ack` handshake carrying
+//! [`REMOTE_PROTOCOL_VERSION`]; the daemon's reply includes the public key
+//! it signs with. A connection then carries one `sign_request`, answered by
+//! a `sign_response` holding either a signature or the reason it was not
+//! produced.
+//!
+//! [`serve_signer`] runs the daemon side and asks an approval callback
+//! before every signature, so the operator can see what is being signed.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+#[cfg(unix)]
+use std::io::{Read, Write};
+#[cfg(unix)]
+use std::time::Duration;
+use uuid::Uuid;
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::PublicKey;
+use crate::governance::messages::SIGNING_DOMAIN;
+use crate::governance::signatures::{verify_signature, Signature};
+use crate::governance::signer::{GovernanceSigner, SignFuture};
+
+/// Remote signing protocol version spoken by this SDK
+pub const REMOTE_PROTOCOL_VERSION: u32 = 1;
+
+/// Largest frame (excluding the length prefix) sent or accepted, in bytes
+pub const MAX_REMOTE_FRAME_SIZE: usize = 1024 * 1024;
+
+/// Longest message preview sent to the daemon, in characters
+const PREVIEW_CHARS: usize = 512;
+
+/// A request to sign one message
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignRequest {
+    /// Correlates the response with this request
+    pub request_id: Uuid,
+    /// Message to sign (hex)
+    pub message: String,
+    /// SHA256 of the message (hex), checked by the daemon
+    pub message_hash: String,
+    /// Human-readable rendering of the message for the operator
+    pub preview: String,
+}
+
+impl SignRequest {
+    /// Build a request for `message`
+    ///
+    /// Governance signing payloads are previewed without the signing domain.
+    pub fn new(message: &[u8]) -> Self {
+        let readable = message.strip_prefix(SIGNING_DOMAIN).unwrap_or(message);
+        Self {
+            request_id: Uuid::new_v4(),
+            message: hex::encode(message),
+            message_hash: hex::encode(Sha256::digest(message)),
+            preview: String::from_utf8_lossy(readable)
+                .chars()
+                .take(PREVIEW_CHARS)
+                .collect(),
+        }
+    }
+
+    /// Decode the message, checking it against `message_hash`
+    pub fn message_bytes(&self) -> GovernanceResult<Vec<u8>> {
+        let message = hex::decode(&self.message)
+            .map_err(|e| GovernanceError::InvalidInput(format!("Invalid message hex: {}", e)))?;
+        if hex::encode(Sha256::digest(&message)) != self.message_hash.to_ascii_lowercase() {
+            return Err(GovernanceError::InvalidInput(
+                "Message does not match its hash".to_string(),
+            ));
+        }
+        Ok(message)
+    }
+}
+
+/// Outcome of a [`SignRequest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "snake_case")]
+pub enum SignOutcome {
+    /// The message was signed
+    Signed {
+        signature: Signature,
+        public_key: PublicKey,
+    },
+    /// The operator or signer declined to sign
+    Refused { reason: String },
+    /// The daemon's signer could not be reached
+    Unavailable { reason: String },
+}
+
+/// Frames exchanged between [`RemoteSigner`] and [`serve_signer`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RemoteMessage {
+    /// First frame from the client
+    Hello { version: u32 },
+    /// Daemon reply to `Hello`
+    HelloAck { version: u32, public_key: PublicKey },
+    /// Request a signature
+    SignRequest(SignRequest),
+    /// Reply to `SignRequest`
+    SignResponse {
+        request_id: Uuid,
+        outcome: SignOutcome,
+    },
+    /// Protocol failure; the connection is closed after this frame
+    Error { reason: String },
+}
+
+/// Write one length-prefixed JSON frame
+#[cfg(unix)]
+fn write_frame<W: Write>(stream: &mut W, message: &RemoteMessage) -> std::io::Result<()> {
+    let frame = serde_json::to_vec(message)?;
+    if frame.len() > MAX_REMOTE_FRAME_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            format!("frame too large ({} bytes)", frame.len()),
+        ));
+    }
+    stream.write_all(&(frame.len() as u32).to_be_bytes())?;
+    stream.write_all(&frame)?;
+    stream.flush()
+}
+
+/// Read one length-prefixed JSON frame
+#[cfg(unix)]
+fn read_frame<R: Read>(stream: &mut R) -> std::io::Result<RemoteMessage> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len)?;
+    let len = u32::from_be_bytes(len) as usize;
+    if len > MAX_REMOTE_FRAME_SIZE {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            format!("frame too large ({} bytes)", len),
+        ));
+    }
+    let mut frame = vec![0u8; len];
+    stream.read_exact(&mut frame)?;
+    Ok(serde_json::from_slice(&frame)?)
+}
+
+/// Client-side failures mean the daemon cannot be used right now
+#[cfg(unix)]
+fn unavailable(e: impl std::fmt::Display) -> GovernanceError {
+    GovernanceError::SignerUnavailable(format!("Remote signer: {}", e))
+}
+
+/// [`GovernanceSigner`] that forwards requests to a signing daemon
+#[cfg(unix)]
+#[derive(Debug, Clone)]
+pub struct RemoteSigner {
+    socket_path: std::path::PathBuf,
+    timeout: Duration,
+    public_key: PublicKey,
+}
+
+#[cfg(unix)]
+impl RemoteSigner {
+    /// Default read/write timeout; generous so the operator can review requests
+    pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(120);
+
+    /// Connect to the daemon at `socket_path` and learn its public key
+    pub fn connect<P: AsRef<std::path::Path>>(socket_path: P) -> GovernanceResult<Self> {
+        Self::connect_with_timeout(socket_path, Self::DEFAULT_TIMEOUT)
+    }
+
+    /// Connect with a custom read/write timeout
+    pub fn connect_with_timeout<P: AsRef<std::path::Path>>(
+        socket_path: P,
+        timeout: Duration,
+    ) -> GovernanceResult<Self> {
+        let socket_path = socket_path.as_ref().to_path_buf();
+        let (_, public_key) = Self::open(&socket_path, timeout)?;
+        Ok(Self {
+            socket_path,
+            timeout,
+            public_key,
+        })
+    }
+
+    /// Path of the daemon socket
+    pub fn socket_path(&self) -> &std::path::Path {
+        &self.socket_path
+    }
+
+    /// Open a connection and complete the handshake
+    fn open(
+        socket_path: &std::path::Path,
+        timeout: Duration,
+    ) -> GovernanceResult<(std::os::unix::net::UnixStream, PublicKey)> {
+        let mut stream = std::os::unix::net::UnixStream::connect(socket_path).map_err(|e| {
+            unavailable(format!(
+                "cannot connect to {}: {}",
+                socket_path.display(),
+                e
+            ))
+        })?;
+        stream
+            .set_read_timeout(Some(timeout))
+            .map_err(unavailable)?;
+        stream
+            .set_write_timeout(Some(timeout))
+            .map_err(unavailable)?;
+
+        write_frame(
+            &mut stream,
+            &RemoteMessage::Hello {
+                version: REMOTE_PROTOCOL_VERSION,
+            },
+        )
+        .map_err(unavailable)?;
+        match read_frame(&mut stream).map_err(unavailable)? {
+            RemoteMessage::HelloAck {
+                version: REMOTE_PROTOCOL_VERSION,
+                public_key,
+            } => Ok((stream, public_key)),
+            RemoteMessage::HelloAck { version, .. } => Err(unavailable(format!(
+                "incompatible protocol version (client {}, daemon {})",
+                REMOTE_PROTOCOL_VERSION, version
+            ))),
+            RemoteMessage::Error { reason } => Err(unavailable(reason)),
+            _ => Err(unavailable("expected hello_ack")),
+        }
+    }
+
+    /// Send one request and wait for its response
+    fn request(&self, message: &[u8]) -> GovernanceResult<Signature> {
+        let (mut stream, public_key) = Self::open(&self.socket_path, self.timeout)?;
+        if public_key != self.public_key {
+            return Err(unavailable("daemon public key changed since connect"));
+        }
+
+        let request = SignRequest::new(message);
+        let request_id = request.request_id;
+        write_frame(&mut stream, &RemoteMessage::SignRequest(request)).map_err(unavailable)?;
+        let outcome = match read_frame(&mut stream).map_err(unavailable)? {
+            RemoteMessage::SignResponse {
+                request_id: id,
+                outcome,
+            } if id == request_id => outcome,
+            RemoteMessage::Error { reason } => return Err(unavailable(reason)),
+            _ => return Err(unavailable("unexpected response")),
+        };
+
+        match outcome {
+            SignOutcome::Signed { signature, .. } => {
+                if !verify_signature(&signature, message, &self.public_key)? {
+                    return Err(GovernanceError::SignatureVerification(
+                        "Remote signer returned an invalid signature".to_string(),
+                    ));
+                }
+                Ok(signature)
+            }
+            SignOutcome::Refused { reason } => Err(GovernanceError::SigningRefused(reason)),
+            SignOutcome::Unavailable { reason } => Err(unavailable(reason)),
+        }
+    }
+}
+
+#[cfg(unix)]
+impl GovernanceSigner for RemoteSigner {
+    fn public_key(&self) -> PublicKey {
+        self.public_key.clone()
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a> {
+        Box::pin(async move { self.request(message) })
+    }
+}
+
+/// Run a signing daemon on `listener`, handling one connection at a time
+///
+/// `approve` is called with every request before signing; returning
+/// `Err(reason)` refuses it. Only returns if accepting a connection fails.
+/// Errors on individual connections are reported to the client and do not
+/// stop the daemon. Requires the `node` feature.
+#[cfg(all(unix, feature = "node"))]
+pub fn serve_signer<S, F>(
+    listener: std::os::unix::net::UnixListener,
+    signer: S,
+    approve: F,
+) -> GovernanceResult<()>
+where
+    S: GovernanceSigner,
+    F: Fn(&SignRequest) -> Result<(), String>,
+{
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .map_err(unavailable)?;
+    loop {
+        let (mut stream, _) = listener
+            .accept()
+            .map_err(|e| GovernanceError::InvalidInput(format!("Accept failed: {}", e)))?;
+        let _ = serve_connection(&mut stream, &runtime, &signer, &approve);
+    }
+}
+
+/// Handle the handshake and one request on a daemon connection
+#[cfg(all(unix, feature = "node"))]
+fn serve_connection<S, F>(
+    stream: &mut std::os::unix::net::UnixStream,
+    runtime: &tokio::runtime::Runtime,
+    signer: &S,
+    approve: &F,
+) -> std::io::Result<()>
+where
+    S: GovernanceSigner,
+    F: Fn(&SignRequest) -> Result<(), String>,
+{
+    match read_frame(stream)? {
+        RemoteMessage::Hello {
+            version: REMOTE_PROTOCOL_VERSION,
+        } => write_frame(
+            stream,
+            &RemoteMessage::HelloAck {
+                version: REMOTE_PROTOCOL_VERSION,
+                public_key: signer.public_key(),
+            },
+        )?,
+        RemoteMessage::Hello { version } => {
+            return write_frame(
+                stream,
+                &RemoteMessage::Error {
+                    reason: format!(
+                        "incompatible protocol version (client {}, daemon {})",
+                        version, REMOTE_PROTOCOL_VERSION
+                    ),
+                },
+            )
+        }
+        _ => {
+            return write_frame(
+                stream,
+                &RemoteMessage::Error {
+                    reason: "expected hello".to_string(),
+                },
+            )
+        }
+    }
+
+    let request = match read_frame(stream) {
+        Ok(RemoteMessage::SignRequest(request)) => request,
+        // Connections made only to learn the public key end here
+        Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => return Ok(()),
+        Ok(_) => {
+            return write_frame(
+                stream,
+                &RemoteMessage::Error {
+                    reason: "expected sign_request".to_string(),
+                },
+            )
+        }
+        Err(e) => return Err(e),
+    };
+
+    let outcome = match request.message_bytes() {
+        Err(e) => SignOutcome::Refused {
+            reason: e.to_string(),
+        },
+        Ok(message) => match approve(&request) {
+            Err(reason) => SignOutcome::Refused { reason },
+            Ok(()) => match runtime.block_on(signer.sign(&message)) {
+                Ok(signature) => SignOutcome::Signed {
+                    signature,
+                    public_key: signer.public_key(),
+                },
+                Err(GovernanceError::SignerUnavailable(reason)) => {
+                    SignOutcome::Unavailable { reason }
+                }
+                Err(e) => SignOutcome::Refused {
+                    reason: e.to_string(),
+                },
+            },
+        },
+    };
+    write_frame(
+        stream,
+        &RemoteMessage::SignResponse {
+            request_id: request.request_id,
+            outcome,
+        },
+    )
+}
+
+#[cfg(all(test, unix, feature = "node"))]
+mod tests {
+    use super::*;
+    use crate::governance::signer::{BlockingSigner, MockSigner, MockSignerBehavior};
+    use crate::governance::{GovernanceKeypair, GovernanceMessage};
+    use std::os::unix::net::UnixListener;
+    use std::path::PathBuf;
+    use std::sync::{Arc, Mutex};
+
+    /// Start a daemon for `signer` that refuses previews containing "refuse"
+    fn spawn_daemon(
+        dir: &tempfile::TempDir,
+        signer: MockSigner,
+    ) -> (PathBuf, Arc<Mutex<Vec<String>>>) {
+        let socket_path = dir.path().join("signer.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let previews = Arc::new(Mutex::new(Vec::new()));
+        let seen = previews.clone();
+        std::thread::spawn(move || {
+            serve_signer(listener, signer, move |request| {
+                seen.lock().unwrap().push(request.preview.clone());
+                if request.preview.contains("refuse") {
+                    Err("operator declined".to_string())
+                } else {
+                    Ok(())
+                }
+            })
+        });
+        (socket_path, previews)
+    }
+
+    #[test]
+    fn test_remote_signer_loopback() {
+        let dir = tempfile::tempdir().unwrap();
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let (socket_path, previews) = spawn_daemon(&dir, MockSigner::new(keypair.clone()));
+
+        let remote = RemoteSigner::connect(&socket_path).unwrap();
+        assert_eq!(remote.public_key(), keypair.public_key());
+        let signer = BlockingSigner::new(remote);
+
+        let message = GovernanceMessage::module_approval("lightning", "v2.0.0");
+        let signature = signer.sign_governance_message(&message).unwrap();
+        assert!(crate::governance::verify_governance_message(
+            &signature,
+            &message,
+            &keypair.public_key()
+        )
+        .unwrap());
+
+        // The operator sees the message without the signing domain
+        assert_eq!(*previews.lock().unwrap(), vec!["MODULE:lightning:v2.0.0"]);
+
+        let err = signer.sign(b"please refuse this").unwrap_err();
+        assert!(
+            matches!(&err, GovernanceError::SigningRefused(reason) if reason == "operator declined"),
+            "{:?}",
+            err
+        );
+    }
+
+    #[test]
+    fn test_remote_signer_unavailable() {
+        let dir = tempfile::tempdir().unwrap();
+
+        // No daemon listening
+        let err = RemoteSigner::connect(dir.path().join("missing.sock")).unwrap_err();
+        assert!(matches!(err, GovernanceError::SignerUnavailable(_)));
+        assert!(err.is_retryable());
+
+        // The daemon's own signer is offline
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let (socket_path, _) = spawn_daemon(
+            &dir,
+            MockSigner::new(keypair).with_behavior(MockSignerBehavior::Unavailable),
+        );
+        let signer = BlockingSigner::new(RemoteSigner::connect(&socket_path).unwrap());
+        assert!(matches!(
+            signer.sign(b"message"),
+            Err(GovernanceError::SignerUnavailable(_))
+        ));
+    }
+
+    #[test]
+    fn test_remote_signer_handshake_and_timeout() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("stalled.sock");
+        let listener = UnixListener::bind(&socket_path).unwrap();
+        let public_key = GovernanceKeypair::generate().unwrap().public_key();
+
+        // First connection: a daemon from the future; second: one that stalls
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            read_frame(&mut stream).unwrap();
+            write_frame(
+                &mut stream,
+                &RemoteMessage::HelloAck {
+                    version: REMOTE_PROTOCOL_VERSION + 1,
+                    public_key,
+                },
+            )
+            .unwrap();
+
+            let (mut stream, _) = listener.accept().unwrap();
+            read_frame(&mut stream).unwrap();
+            std::thread::sleep(Duration::from_secs(2));
+        });
+
+        let err = RemoteSigner::connect(&socket_path).unwrap_err();
+        assert!(
+            err.to_string().contains("incompatible protocol version"),
+            "{}",
+            err
+        );
+
+        let err = RemoteSigner::connect_with_timeout(&socket_path, Duration::from_millis(100))
+            .unwrap_err();
+        assert!(matches!(err, GovernanceError::SignerUnavailable(_)));
+    }
+
+    #[test]
+    fn test_sign_request_checks_hash() {
+        let mut request = SignRequest::new(b"message");
+        assert_eq!(request.message_bytes().unwrap(), b"message");
+        request.message = hex::encode(b"tampered");
+        assert!(request.message_bytes().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+//! # Key Rotation
+//!
+//! Replace one key in a governance roster with the approval of the other
+//! keyholders.
+//!
+//! A rotation is proposed as [`GovernanceMessage::KeyRotation`] and applied
+//! with [`Multisig::rotate_key`]. The outgoing key may be compromised, so its
+//! signature never counts: `threshold - 1` of the remaining maintainers
+//! (at least one) must approve.
+
+use serde::{Deserialize, Serialize};
+
+use crate::governance::error::GovernanceResult;
+use crate::governance::keys::PublicKey;
+use crate::governance::messages::GovernanceMessage;
+use crate::governance::multisig::Multisig;
+use crate::governance::signatures::Signature;
+
+/// A proposal to replace `old_pubkey` with `new_pubkey` in a roster
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KeyRotationProposal {
+    /// Key being retired
+    pub old_pubkey: PublicKey,
+    /// Key taking its place
+    pub new_pubkey: PublicKey,
+    /// Why the key is rotated (e.g. "compromised", "maintainer change")
+    pub reason: String,
+}
+
+impl KeyRotationProposal {
+    /// Create a rotation proposal
+    pub fn new(old_pubkey: PublicKey, new_pubkey: PublicKey, reason: impl Into<String>) -> Self {
+        Self {
+            old_pubkey,
+            new_pubkey,
+            reason: reason.into(),
+        }
+    }
+
+    /// The governance message maintainers sign to approve the rotation
+    pub fn to_message(&self) -> GovernanceMessage {
+        GovernanceMessage::KeyRotation {
+            proposal: self.clone(),
+        }
+    }
+
+    /// Apply the rotation to `multisig` if enough remaining keys approved it
+    ///
+    /// `signatures` must be over [`to_message`](Self::to_message)'s signing bytes.
+    pub fn apply(
+        &self,
+        multisig: &Multisig,
+        signatures: &[Signature],
+    ) -> GovernanceResult<Multisig> {
+        multisig.rotate_key(
+            &self.old_pubkey,
+            self.new_pubkey.clone(),
+            signatures,
+            &self.to_message().to_signing_bytes(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::error::GovernanceError;
+    use crate::governance::GovernanceKeypair;
+
+    fn roster(n: usize) -> (Vec<GovernanceKeypair>, Multisig) {
+        let keypairs: Vec<_> = (0..n)
+            .map(|_| GovernanceKeypair::generate().unwrap())
+            .collect();
+        let multisig =
+            Multisig::new(3, n, keypairs.iter().map(|k| k.public_key()).collect()).unwrap();
+        (keypairs, multisig)
+    }
+
+    fn approve(proposal: &KeyRotationProposal, signers: &[&GovernanceKeypair]) -> Vec<Signature> {
+        let message = proposal.to_message();
+        signers
+            .iter()
+            .map(|k| crate::governance::sign_governance_message(&k.secret_key, &message).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotation_replaces_key_in_place() {
+        let (keypairs, multisig) = roster(5);
+        let replacement = GovernanceKeypair::generate().unwrap();
+        let proposal = KeyRotationProposal::new(
+            keypairs[1].public_key(),
+            replacement.public_key(),
+            "maintainer change",
+        );
+        assert_eq!(
+            proposal.to_message().description(),
+            format!(
+                "Rotate key {} to {} (maintainer change)",
+                keypairs[1].public_key(),
+                replacement.public_key()
+            )
+        );
+
+        // threshold - 1 = 2 remaining maintainers suffice
+        let signatures = approve(&proposal, &[&keypairs[0], &keypairs[4]]);
+        let rotated = proposal.apply(&multisig, &signatures).unwrap();
+        assert_eq!(rotated.threshold(), 3);
+        assert_eq!(rotated.total(), 5);
+        assert_eq!(rotated.public_keys()[1], replacement.public_key());
+        assert!(!rotated.public_keys().contains(&keypairs[1].public_key()));
+    }
+
+    #[test]
+    fn test_rotation_requires_remaining_approvals() {
+        let (keypairs, multisig) = roster(5);
+        let replacement = GovernanceKeypair::generate().unwrap();
+        let proposal = KeyRotationProposal::new(
+            keypairs[1].public_key(),
+            replacement.public_key(),
+            "compromised",
+        );
+
+        // The outgoing key's approval and duplicate approvals do not count
+        let signatures = approve(&proposal, &[&keypairs[1], &keypairs[0], &keypairs[0]]);
+        assert!(matches!(
+            proposal.apply(&multisig, &signatures),
+            Err(GovernanceError::InsufficientSignatures { got: 1, need: 2 })
+        ));
+
+        // Approvals of a different rotation do not count
+        let other = KeyRotationProposal::new(
+            keypairs[2].public_key(),
+            replacement.public_key(),
+            "compromised",
+        );
+        let signatures = approve(&other, &[&keypairs[0], &keypairs[3]]);
+        assert!(proposal.apply(&multisig, &signatures).is_err());
+
+        // Keys must be rotated out of, and into, the roster
+        let signatures = approve(&proposal, &[&keypairs[0], &keypairs[3]]);
+        let stranger =
+            KeyRotationProposal::new(replacement.public_key(), keypairs[1].public_key(), "typo");
+        assert!(stranger.apply(&multisig, &signatures).is_err());
+        assert!(multisig
+            .rotate_key(
+                &keypairs[1].public_key(),
+                keypairs[2].public_key(),
+                &signatures,
+                &proposal.to_message().to_signing_bytes(),
+            )
+            .is_err());
+    }
+}
@@ -0,0 +1,235 @@
+//! # Signature Hashes
+//!
+//! Transaction digest algorithms used when signing PSBT inputs.
+//!
+//! Legacy inputs use the original transaction-copy algorithm, including its
+//! quirks; segwit v0 inputs use BIP143.
+//!
+//! BIP143: https://github.com/bitcoin/bips/blob/master/bip-0143.mediawiki
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::psbt::SighashType;
+use crate::governance::signatures::sha256d;
+use crate::governance::transaction::{write_compact_size, Transaction, TxOut};
+
+/// ANYONECANPAY flag bit
+const SIGHASH_ANYONECANPAY: u8 = 0x80;
+
+/// Mask selecting the base sighash type
+const SIGHASH_BASE_MASK: u8 = 0x1f;
+
+/// OP_CODESEPARATOR, removed from legacy scriptCodes
+const OP_CODESEPARATOR: u8 = 0xab;
+
+/// Digest signed by legacy SIGHASH_SINGLE when the input has no matching
+/// output (the value 1 as a little-endian uint256)
+const SIGHASH_SINGLE_BUG: [u8; 32] = {
+    let mut hash = [0u8; 32];
+    hash[0] = 1;
+    hash
+};
+
+/// Compute the legacy (pre-segwit) signature hash of an input
+///
+/// `script_code` is the script being satisfied (the previous output's
+/// scriptPubKey, or the redeem script for P2SH) without its length prefix.
+/// Reproduces the original algorithm's quirks, including the 1-hash
+/// returned for SIGHASH_SINGLE without a matching output.
+pub fn legacy_sighash(
+    tx_bytes: &[u8],
+    input_index: usize,
+    script_code: &[u8],
+    sighash_type: SighashType,
+) -> GovernanceResult<[u8; 32]> {
+    let tx = Transaction::parse(tx_bytes)?;
+    legacy_sighash_tx(&tx, input_index, script_code, sighash_type)
+}
+
+/// Legacy signature hash over an already parsed transaction
+pub(crate) fn legacy_sighash_tx(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    sighash_type: SighashType,
+) -> GovernanceResult<[u8; 32]> {
+    if input_index >= tx.inputs.len() {
+        return Err(GovernanceError::InvalidInput(format!(
+            "Input index {} out of range ({} inputs)",
+            input_index,
+            tx.inputs.len()
+        )));
+    }
+
+    let byte = sighash_type.to_byte();
+    let anyone_can_pay = byte & SIGHASH_ANYONECANPAY != 0;
+    let base = byte & SIGHASH_BASE_MASK;
+    let is_none = base == SighashType::None.to_byte();
+    let is_single = base == SighashType::Single.to_byte();
+
+    if is_single && input_index >= tx.outputs.len() {
+        return Ok(SIGHASH_SINGLE_BUG);
+    }
+
+    let mut tx_copy = tx.clone();
+    for (index, txin) in tx_copy.inputs.iter_mut().enumerate() {
+        txin.witness.clear();
+        if index == input_index {
+            txin.script_sig = remove_codeseparators(script_code);
+        } else {
+            txin.script_sig.clear();
+            // Other inputs may be replaced freely under NONE and SINGLE
+            if is_none || is_single {
+                txin.sequence = 0;
+            }
+        }
+    }
+
+    if is_none {
+        tx_copy.outputs.clear();
+    } else if is_single {
+        tx_copy.outputs.truncate(input_index + 1);
+        for output in &mut tx_copy.outputs[..input_index] {
+            *output = TxOut {
+                value: u64::MAX,
+                script_pubkey: Vec::new(),
+            };
+        }
+    }
+
+    if anyone_can_pay {
+        tx_copy.inputs = vec![tx_copy.inputs.swap_remove(input_index)];
+    }
+
+    let mut preimage = tx_copy.serialize_without_witness();
+    preimage.extend_from_slice(&(byte as u32).to_le_bytes());
+    Ok(sha256d(&preimage))
+}
+
+/// Compute the BIP143 (segwit v0) signature hash of an input
+///
+/// `script_code` is the input's scriptCode without its length prefix, and
+/// `amount` the value of the output being spent.
+pub fn segwit_v0_sighash(
+    tx_bytes: &[u8],
+    input_index: usize,
+    script_code: &[u8],
+    amount: u64,
+    sighash_type: SighashType,
+) -> GovernanceResult<[u8; 32]> {
+    let tx = Transaction::parse(tx_bytes)?;
+    segwit_v0_sighash_tx(&tx, input_index, script_code, amount, sighash_type)
+}
+
+/// BIP143 signature hash over an already parsed transaction
+pub(crate) fn segwit_v0_sighash_tx(
+    tx: &Transaction,
+    input_index: usize,
+    script_code: &[u8],
+    amount: u64,
+    sighash_type: SighashType,
+) -> GovernanceResult<[u8; 32]> {
+    let input = tx.inputs.get(input_index).ok_or_else(|| {
+        GovernanceError::InvalidInput(format!(
+            "Input index {} out of range ({} inputs)",
+            input_index,
+            tx.inputs.len()
+        ))
+    })?;
+
+    let byte = sighash_type.to_byte();
+    let anyone_can_pay = byte & SIGHASH_ANYONECANPAY != 0;
+    let base = byte & SIGHASH_BASE_MASK;
+    let is_none = base == SighashType::None.to_byte();
+    let is_single = base == SighashType::Single.to_byte();
+
+    let hash_prevouts = if anyone_can_pay {
+        [0u8; 32]
+    } else {
+        let mut prevouts = Vec::with_capacity(36 * tx.inputs.len());
+        for txin in &tx.inputs {
+            prevouts.extend_from_slice(&txin.previous_txid);
+            prevouts.extend_from_slice(&txin.previous_vout.to_le_bytes());
+        }
+        sha256d(&prevouts)
+    };
+
+    let hash_sequence = if anyone_can_pay || is_none || is_single {
+        [0u8; 32]
+    } else {
+        let sequences: Vec<u8> = tx
+            .inputs
+            .iter()
+            .flat_map(|txin| txin.sequence.to_le_bytes())
+            .collect();
+        sha256d(&sequences)
+    };
+
+    let hash_outputs = if !is_none && !is_single {
+        let outputs: Vec<u8> = tx.outputs.iter().flat_map(|out| out.serialize()).collect();
+        sha256d(&outputs)
+    } else if is_single && input_index < tx.outputs.len() {
+        sha256d(&tx.outputs[input_index].serialize())
+    } else {
+        [0u8; 32]
+    };
+
+    let mut preimage = Vec::with_capacity(160 + script_code.len());
+    preimage.extend_from_slice(&tx.version.to_le_bytes());
+    preimage.extend_from_slice(&hash_prevouts);
+    preimage.extend_from_slice(&hash_sequence);
+    preimage.extend_from_slice(&input.previous_txid);
+    preimage.extend_from_slice(&input.previous_vout.to_le_bytes());
+    write_compact_size(&mut preimage, script_code.len());
+    preimage.extend_from_slice(script_code);
+    preimage.extend_from_slice(&amount.to_le_bytes());
+    preimage.extend_from_slice(&input.sequence.to_le_bytes());
+    preimage.extend_from_slice(&hash_outputs);
+    preimage.extend_from_slice(&tx.lock_time.to_le_bytes());
+    preimage.extend_from_slice(&(byte as u32).to_le_bytes());
+
+    Ok(sha256d(&preimage))
+}
+
+/// Remove every OP_CODESEPARATOR opcode, skipping over push data
+fn remove_codeseparators(script: &[u8]) -> Vec<u8> {
+    let mut result = Vec::with_capacity(script.len());
+    let mut offset = 0;
+    while offset < script.len() {
+        let opcode = script[offset];
+        let (header, data_len) = match opcode {
+            0x01..=0x4b => (1, opcode as usize),
+            0x4c => (2, script.get(offset + 1).map_or(0, |&len| len as usize)),
+            0x4d => (
+                3,
+                script
+                    .get(offset + 1..offset + 3)
+                    .map_or(0, |len| u16::from_le_bytes([len[0], len[1]]) as usize),
+            ),
+            0x4e => (
+                5,
+                script.get(offset + 1..offset + 5).map_or(0, |len| {
+                    u32::from_le_bytes([len[0], len[1], len[2], len[3]]) as usize
+                }),
+            ),
+            _ => (1, 0),
+        };
+        let end = offset
+            .saturating_add(header)
+            .saturating_add(data_len)
+            .min(script.len());
+        if opcode != OP_CODESEPARATOR {
+            result.extend_from_slice(&script[offset..end]);
+        }
+        offset = end;
+    }
+    result
+}
+
+/// scriptCode for a P2WPKH input: `OP_DUP OP_HASH160 <hash> OP_EQUALVERIFY OP_CHECKSIG`
+pub fn p2wpkh_script_code(pubkey_hash: &[u8; 20]) -> Vec<u8> {
+    let mut script = Vec::with_capacity(25);
+    script.extend_from_slice(&[0x76, 0xa9, 0x14]);
+    script.extend_from_slice(pubkey_hash);
+    script.extend_from_slice(&[0x88, 0xac]);
+    script
+}
@@ -1,13 +1,24 @@
 //! # Governance Signatures
 //!
-//! Signature creation and verification for governance operations.
+//! Signature creation and verification for governance operations, plus the
+//! hash primitives shared by address derivation, PSBT, and BIP32 code.
 
 use rand::rngs::OsRng;
 use secp256k1::{ecdsa::Signature as Secp256k1Signature, Message, Secp256k1, SecretKey};
-use sha2::Digest;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
 use std::fmt;
+use std::str::FromStr;
 
 use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::decode_hex;
+use crate::governance::messages::GovernanceMessage;
+
+/// Shortest DER encoding of a secp256k1 ECDSA signature
+const MIN_DER_SIGNATURE_LEN: usize = 8;
+
+/// Longest DER encoding of a secp256k1 ECDSA signature
+const MAX_DER_SIGNATURE_LEN: usize = 72;
 
 /// A governance signature
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -36,19 +47,86 @@ impl Signature {
     }
 }
 
+/// Shows the DER encoding as hex
 impl fmt::Display for Signature {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", hex::encode(self.to_bytes()))
+        write!(f, "{}", hex::encode(self.to_der_bytes()))
+    }
+}
+
+impl FromStr for Signature {
+    type Err = GovernanceError;
+
+    /// Parse a hex DER or compact signature, with or without a `0x` prefix
+    fn from_str(s: &str) -> GovernanceResult<Self> {
+        let bytes = decode_hex(s, "signature").map_err(GovernanceError::InvalidSignatureFormat)?;
+        if !(MIN_DER_SIGNATURE_LEN..=MAX_DER_SIGNATURE_LEN).contains(&bytes.len()) {
+            return Err(GovernanceError::InvalidSignatureFormat(format!(
+                "Invalid signature length: expected 64 bytes (compact) or {} to {} bytes (DER), got {}",
+                MIN_DER_SIGNATURE_LEN,
+                MAX_DER_SIGNATURE_LEN,
+                bytes.len()
+            )));
+        }
+        // 64 bytes is normally compact, but a DER encoding can be that long too
+        if let Ok(inner) = Secp256k1Signature::from_der(&bytes) {
+            return Ok(Self { inner });
+        }
+        if bytes.len() != 64 {
+            return Err(GovernanceError::InvalidSignatureFormat(
+                "Invalid DER signature".to_string(),
+            ));
+        }
+        Self::from_bytes(&bytes)
     }
 }
 
+/// Serialized as compact hex, the format of signature files
+impl Serialize for Signature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&hex::encode(self.to_bytes()))
+    }
+}
+
+impl<'de> Deserialize<'de> for Signature {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        s.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+/// BIP340 tagged hash: `SHA256(SHA256(tag) || SHA256(tag) || data)`
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    Sha256::new()
+        .chain_update(tag_hash)
+        .chain_update(tag_hash)
+        .chain_update(data)
+        .finalize()
+        .into()
+}
+
+/// Double SHA256
+pub fn sha256d(data: &[u8]) -> [u8; 32] {
+    Sha256::digest(Sha256::digest(data)).into()
+}
+
+/// RIPEMD160(SHA256(data))
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    use ripemd::Ripemd160;
+    Ripemd160::digest(Sha256::digest(data)).into()
+}
+
 /// Sign a message with a secret key
+///
+/// Signs `message` as given. For governance messages use
+/// [`sign_governance_message`], which applies the signing domain.
 pub fn sign_message(secret_key: &SecretKey, message: &[u8]) -> GovernanceResult<Signature> {
     let secp = Secp256k1::new();
     let _rng = OsRng;
 
     // Hash the message using SHA256 (Bitcoin standard)
-    let message_hash = sha2::Sha256::digest(message);
+    let message_hash = Sha256::digest(message);
     let message = Message::from_digest_slice(&message_hash)
         .map_err(|e| GovernanceError::Cryptographic(format!("Invalid message hash: {}", e)))?;
 
@@ -66,7 +144,7 @@ pub fn verify_signature(
     let secp = Secp256k1::new();
 
     // Hash the message using SHA256 (Bitcoin standard)
-    let message_hash = sha2::Sha256::digest(message);
+    let message_hash = Sha256::digest(message);
     let message = Message::from_digest_slice(&message_hash)
         .map_err(|e| GovernanceError::Cryptographic(format!("Invalid message hash: {}", e)))?;
 
@@ -75,11 +153,58 @@ pub fn verify_signature(
     Ok(result.is_ok())
 }
 
+/// Sign a governance message under the governance signing domain
+pub fn sign_governance_message(
+    secret_key: &SecretKey,
+    message: &GovernanceMessage,
+) -> GovernanceResult<Signature> {
+    sign_message(secret_key, &message.to_signing_bytes())
+}
+
+/// Verify a governance message signature made with [`sign_governance_message`]
+pub fn verify_governance_message(
+    signature: &Signature,
+    message: &GovernanceMessage,
+    public_key: &crate::governance::PublicKey,
+) -> GovernanceResult<bool> {
+    verify_signature(signature, &message.to_signing_bytes(), public_key)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::governance::GovernanceKeypair;
 
+    #[test]
+    fn test_hash_primitives() {
+        // Matches secp256k1's own BIP340 challenge midstate construction
+        let tag_hash = Sha256::digest(b"BIP0340/challenge");
+        let mut preimage = tag_hash.to_vec();
+        preimage.extend_from_slice(&tag_hash);
+        preimage.extend_from_slice(b"data");
+        assert_eq!(
+            tagged_hash("BIP0340/challenge", b"data"),
+            <[u8; 32]>::from(Sha256::digest(&preimage))
+        );
+        assert_ne!(
+            tagged_hash("BIP0340/aux", b"data"),
+            tagged_hash("BIP0340/nonce", b"data")
+        );
+
+        // sha256d("") and hash160 of the generator point
+        assert_eq!(
+            hex::encode(sha256d(b"")),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+        let generator =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            hex::encode(hash160(&generator)),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
     #[test]
     fn test_sign_and_verify() {
         let keypair = GovernanceKeypair::generate().unwrap();
@@ -91,6 +216,19 @@ mod tests {
         assert!(verified);
     }
 
+    #[test]
+    fn test_governance_message_domain() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let message = GovernanceMessage::module_approval("lightning", "v2.0.0");
+
+        let signature = sign_governance_message(&keypair.secret_key, &message).unwrap();
+        assert!(verify_governance_message(&signature, &message, &keypair.public_key()).unwrap());
+
+        // Signatures over the bare message bytes (pre-domain format) do not verify
+        let legacy = sign_message(&keypair.secret_key, &message.message_bytes()).unwrap();
+        assert!(!verify_governance_message(&legacy, &message, &keypair.public_key()).unwrap());
+    }
+
     #[test]
     fn test_signature_serialization() {
         let keypair = GovernanceKeypair::generate().unwrap();
@@ -103,6 +241,25 @@ mod tests {
         assert_eq!(signature, reconstructed);
     }
 
+    #[test]
+    fn test_signature_display_is_der() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let signature = sign_message(&keypair.secret_key, b"test message").unwrap();
+
+        let der_hex = signature.to_string();
+        assert_eq!(der_hex, hex::encode(signature.to_der_bytes()));
+        assert_eq!(der_hex.parse::<Signature>().unwrap(), signature);
+
+        // Serde keeps the compact form, and parsing accepts it too
+        let compact_hex = hex::encode(signature.to_bytes());
+        assert_eq!(
+            serde_json::to_string(&signature).unwrap(),
+            format!("\"{}\"", compact_hex)
+        );
+        assert_eq!(compact_hex.parse::<Signature>().unwrap(), signature);
+        assert!("300602010102".parse::<Signature>().is_err());
+    }
+
     #[test]
     fn test_invalid_signature() {
         let keypair = GovernanceKeypair::generate().unwrap();
@@ -0,0 +1,179 @@
+//! # Governance Signers
+//!
+//! Pluggable signing backends for governance operations.
+//!
+//! [`GovernanceSigner`] abstracts over where the secret key lives: in memory
+//! ([`GovernanceKeypair`]), on a hardware wallet or HSM, or behind a remote
+//! signing service. Backends report [`GovernanceError::SignerUnavailable`]
+//! when they cannot be reached and [`GovernanceError::SigningRefused`] when
+//! they decline a request.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Mutex;
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::{GovernanceKeypair, PublicKey};
+use crate::governance::messages::GovernanceMessage;
+use crate::governance::signatures::{sign_message, Signature};
+
+/// Future returned by [`GovernanceSigner::sign`]
+pub type SignFuture<'a> = Pin<Box<dyn Future<Output = GovernanceResult<Signature>> + Send + 'a>>;
+
+/// A backend that can produce governance signatures
+///
+/// `sign` returns a boxed future so signers can be used as trait objects,
+/// e.g. a roster mixing hardware and in-memory keys.
+pub trait GovernanceSigner: Send + Sync {
+    /// Public key the signer signs with
+    fn public_key(&self) -> PublicKey;
+
+    /// Sign `message` as given (SHA256 + ECDSA, as [`sign_message`])
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a>;
+}
+
+impl GovernanceSigner for GovernanceKeypair {
+    fn public_key(&self) -> PublicKey {
+        GovernanceKeypair::public_key(self)
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a> {
+        Box::pin(async move { sign_message(&self.secret_key, message) })
+    }
+}
+
+impl<S: GovernanceSigner + ?Sized> GovernanceSigner for Box<S> {
+    fn public_key(&self) -> PublicKey {
+        (**self).public_key()
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a> {
+        (**self).sign(message)
+    }
+}
+
+/// Sign a governance message under the signing domain with any signer
+pub async fn sign_governance_message_with(
+    signer: &dyn GovernanceSigner,
+    message: &GovernanceMessage,
+) -> GovernanceResult<Signature> {
+    signer.sign(&message.to_signing_bytes()).await
+}
+
+/// Synchronous adapter over a [`GovernanceSigner`]
+///
+/// Drives each signing request to completion on a private current-thread
+/// runtime, so it must not be used from inside an async context. Requires
+/// the `node` feature.
+#[cfg(feature = "node")]
+#[derive(Debug)]
+pub struct BlockingSigner<S> {
+    signer: S,
+}
+
+#[cfg(feature = "node")]
+impl<S: GovernanceSigner> BlockingSigner<S> {
+    /// Wrap a signer
+    pub fn new(signer: S) -> Self {
+        Self { signer }
+    }
+
+    /// Public key of the wrapped signer
+    pub fn public_key(&self) -> PublicKey {
+        self.signer.public_key()
+    }
+
+    /// Sign `message`, blocking until the signer responds
+    pub fn sign(&self, message: &[u8]) -> GovernanceResult<Signature> {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| {
+                GovernanceError::SignerUnavailable(format!("Failed to start runtime: {}", e))
+            })?
+            .block_on(self.signer.sign(message))
+    }
+
+    /// Sign a governance message under the signing domain
+    pub fn sign_governance_message(
+        &self,
+        message: &GovernanceMessage,
+    ) -> GovernanceResult<Signature> {
+        self.sign(&message.to_signing_bytes())
+    }
+
+    /// Unwrap the inner signer
+    pub fn into_inner(self) -> S {
+        self.signer
+    }
+}
+
+/// How a [`MockSigner`] responds to signing requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MockSignerBehavior {
+    /// Sign with the mock's keypair
+    Sign,
+    /// Fail with [`GovernanceError::SignerUnavailable`]
+    Unavailable,
+    /// Fail with [`GovernanceError::SigningRefused`]
+    Refuse,
+}
+
+/// In-memory signer for tests that records every message it is asked to sign
+#[derive(Debug)]
+pub struct MockSigner {
+    keypair: GovernanceKeypair,
+    behavior: MockSignerBehavior,
+    requests: Mutex<Vec<Vec<u8>>>,
+}
+
+impl MockSigner {
+    /// Create a mock that signs with `keypair`
+    pub fn new(keypair: GovernanceKeypair) -> Self {
+        Self {
+            keypair,
+            behavior: MockSignerBehavior::Sign,
+            requests: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Set how the mock responds to signing requests
+    pub fn with_behavior(mut self, behavior: MockSignerBehavior) -> Self {
+        self.behavior = behavior;
+        self
+    }
+
+    /// Messages the mock has been asked to sign, in order
+    ///
+    /// Requests are recorded whether or not they were signed.
+    pub fn requests(&self) -> Vec<Vec<u8>> {
+        self.requests
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
+    }
+}
+
+impl GovernanceSigner for MockSigner {
+    fn public_key(&self) -> PublicKey {
+        self.keypair.public_key()
+    }
+
+    fn sign<'a>(&'a self, message: &'a [u8]) -> SignFuture<'a> {
+        Box::pin(async move {
+            self.requests
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .push(message.to_vec());
+            match self.behavior {
+                MockSignerBehavior::Sign => sign_message(&self.keypair.secret_key, message),
+                MockSignerBehavior::Unavailable => Err(GovernanceError::SignerUnavailable(
+                    "mock signer is offline".to_string(),
+                )),
+                MockSignerBehavior::Refuse => Err(GovernanceError::SigningRefused(
+                    "mock signer refused".to_string(),
+                )),
+            }
+        })
+    }
+}
@@ -0,0 +1,459 @@
+//! # Governance State Machine
+//!
+//! Enforces the lifecycle of a governance decision:
+//!
+//! ```text
+//! Draft -> Proposed -> Voting -> Passed   -> Executed
+//!                             -> Rejected
+//!                             -> Expired
+//! ```
+//!
+//! A proposal is `Rejected` once enough members have voted against that the
+//! threshold can no longer be reached, and `Expired` if voting closes
+//! without either outcome. Only `Passed` decisions can be executed, once.
+
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::messages::{now_secs, GovernanceMessage, GovernanceProposal};
+use crate::governance::{GovernanceKeypair, Multisig, PublicKey, Signature};
+
+/// Lifecycle state of a governance proposal
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ProposalState {
+    /// Message written but not yet proposed
+    Draft,
+    /// Proposed, voting not yet open
+    Proposed,
+    /// Accepting votes
+    Voting,
+    /// Threshold of votes in favor reached
+    Passed,
+    /// Threshold can no longer be reached
+    Rejected,
+    /// Voting closed without reaching either outcome
+    Expired,
+    /// Passed decision has been carried out
+    Executed,
+}
+
+impl ProposalState {
+    /// Whether the lifecycle allows moving from this state to `next`
+    pub fn can_transition_to(self, next: ProposalState) -> bool {
+        use ProposalState::*;
+        matches!(
+            (self, next),
+            (Draft, Proposed)
+                | (Proposed, Voting)
+                | (Voting, Passed)
+                | (Voting, Rejected)
+                | (Voting, Expired)
+                | (Passed, Executed)
+        )
+    }
+
+    /// Whether no further transitions are possible
+    pub fn is_terminal(self) -> bool {
+        matches!(
+            self,
+            ProposalState::Rejected | ProposalState::Expired | ProposalState::Executed
+        )
+    }
+}
+
+impl fmt::Display for ProposalState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self, f)
+    }
+}
+
+/// Final outcome of a governance proposal
+///
+/// Bundles the message with every vote cast, so the decision can be
+/// verified against the multisig without the original proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GovernanceDecision {
+    /// ID of the proposal this decision closes
+    pub proposal_id: Uuid,
+    /// Message the proposal voted on
+    pub message: GovernanceMessage,
+    /// `Passed`, `Rejected`, or `Expired`
+    pub outcome: ProposalState,
+    /// Votes in favor, `(public key hex, signature)`
+    pub votes_for: Vec<(String, Signature)>,
+    /// Votes against, `(public key hex, signature)`
+    pub votes_against: Vec<(String, Signature)>,
+    /// Unix time the decision was made
+    pub decided_at: u64,
+}
+
+impl GovernanceDecision {
+    /// Whether the proposal passed
+    pub fn is_passed(&self) -> bool {
+        self.outcome == ProposalState::Passed
+    }
+}
+
+/// Per-proposal state tracked by the machine
+#[derive(Debug, Clone)]
+struct TrackedProposal {
+    state: ProposalState,
+    multisig: Option<Multisig>,
+}
+
+/// Drives proposals through the governance lifecycle
+///
+/// Each transition checks the proposal's current state and fails with
+/// [`GovernanceError::InvalidInput`] when called out of order.
+#[derive(Debug, Clone)]
+pub struct GovernanceStateMachine {
+    proposer: PublicKey,
+    voting_period_secs: u64,
+    proposals: HashMap<Uuid, TrackedProposal>,
+}
+
+impl GovernanceStateMachine {
+    /// Default voting period: seven days
+    pub const DEFAULT_VOTING_PERIOD_SECS: u64 = 7 * 24 * 60 * 60;
+
+    /// Create a state machine proposing on behalf of `proposer`
+    pub fn new(proposer: &PublicKey) -> Self {
+        Self {
+            proposer: proposer.clone(),
+            voting_period_secs: Self::DEFAULT_VOTING_PERIOD_SECS,
+            proposals: HashMap::new(),
+        }
+    }
+
+    /// Set how long voting stays open after `open_voting`
+    pub fn with_voting_period(mut self, secs: u64) -> Self {
+        self.voting_period_secs = secs;
+        self
+    }
+
+    /// Current state of a proposal
+    ///
+    /// Proposals this machine has not seen are `Draft`.
+    pub fn state(&self, proposal_id: &Uuid) -> ProposalState {
+        self.proposals
+            .get(proposal_id)
+            .map_or(ProposalState::Draft, |tracked| tracked.state)
+    }
+
+    /// Propose a message: `Draft -> Proposed`
+    pub fn propose(&mut self, message: GovernanceMessage) -> GovernanceResult<GovernanceProposal> {
+        let description = message.description();
+        let proposal = GovernanceProposal::new(
+            description.clone(),
+            description,
+            message,
+            &self.proposer,
+            self.voting_period_secs,
+        );
+        self.transition(&proposal.id, ProposalState::Proposed)?;
+        Ok(proposal)
+    }
+
+    /// Open voting among the members of `multisig`: `Proposed -> Voting`
+    ///
+    /// The voting period starts now.
+    pub fn open_voting(
+        &mut self,
+        proposal: &mut GovernanceProposal,
+        multisig: &Multisig,
+    ) -> GovernanceResult<()> {
+        self.transition(&proposal.id, ProposalState::Voting)?;
+        if let Some(tracked) = self.proposals.get_mut(&proposal.id) {
+            tracked.multisig = Some(multisig.clone());
+        }
+        proposal.voting_ends_at = now_secs().saturating_add(self.voting_period_secs);
+        Ok(())
+    }
+
+    /// Sign and record a vote while the proposal is `Voting`
+    ///
+    /// Only members of the multisig given to `open_voting` may vote.
+    pub fn cast_vote(
+        &self,
+        proposal: &mut GovernanceProposal,
+        keypair: &GovernanceKeypair,
+        in_favor: bool,
+    ) -> GovernanceResult<()> {
+        let multisig = self.voting_multisig(&proposal.id)?;
+        if !multisig.public_keys().contains(&keypair.public_key()) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Key {} is not a member of the voting multisig",
+                keypair.public_key()
+            )));
+        }
+        proposal.cast_vote(keypair, in_favor)
+    }
+
+    /// Close voting: `Voting -> Passed | Rejected | Expired`
+    ///
+    /// A proposal can be finalized as soon as the outcome is certain; until
+    /// then, it fails while voting is still open.
+    pub fn finalize(
+        &mut self,
+        proposal: &mut GovernanceProposal,
+    ) -> GovernanceResult<GovernanceDecision> {
+        let multisig = self.voting_multisig(&proposal.id)?;
+        let votes_for = proposal.count_votes(multisig, true);
+        let votes_against = proposal.count_votes(multisig, false);
+        let undecided = multisig
+            .total()
+            .saturating_sub(votes_for)
+            .saturating_sub(votes_against);
+
+        let outcome = if votes_for >= multisig.threshold() {
+            ProposalState::Passed
+        } else if votes_for + undecided < multisig.threshold() {
+            ProposalState::Rejected
+        } else if !proposal.is_open() {
+            ProposalState::Expired
+        } else {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Voting on proposal {} is still open ({} of {} votes in favor)",
+                proposal.id,
+                votes_for,
+                multisig.threshold()
+            )));
+        };
+
+        self.transition(&proposal.id, outcome)?;
+        Ok(GovernanceDecision {
+            proposal_id: proposal.id,
+            message: proposal.message.clone(),
+            outcome,
+            votes_for: proposal.votes_for.clone(),
+            votes_against: proposal.votes_against.clone(),
+            decided_at: now_secs(),
+        })
+    }
+
+    /// Mark a passed decision as carried out: `Passed -> Executed`
+    ///
+    /// The decision's votes are re-checked against the multisig, so a
+    /// decision altered after `finalize` is refused.
+    pub fn execute(&mut self, decision: &GovernanceDecision) -> GovernanceResult<()> {
+        let state = self.state(&decision.proposal_id);
+        if state != ProposalState::Passed || !decision.is_passed() {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Cannot execute proposal {}: it is {}, not Passed",
+                decision.proposal_id, state
+            )));
+        }
+
+        let multisig = self
+            .proposals
+            .get(&decision.proposal_id)
+            .and_then(|tracked| tracked.multisig.as_ref())
+            .ok_or_else(|| {
+                GovernanceError::InvalidInput(format!(
+                    "Proposal {} has no voting multisig",
+                    decision.proposal_id
+                ))
+            })?;
+        let proposal = GovernanceProposal {
+            id: decision.proposal_id,
+            title: String::new(),
+            description: String::new(),
+            message: decision.message.clone(),
+            proposed_by: String::new(),
+            proposed_at: 0,
+            voting_ends_at: 0,
+            votes_for: decision.votes_for.clone(),
+            votes_against: Vec::new(),
+        };
+        let got = proposal.count_votes(multisig, true);
+        if got < multisig.threshold() {
+            return Err(GovernanceError::InsufficientSignatures {
+                got,
+                need: multisig.threshold(),
+            });
+        }
+
+        self.transition(&decision.proposal_id, ProposalState::Executed)
+    }
+
+    /// Multisig of a proposal that is currently `Voting`
+    fn voting_multisig(&self, proposal_id: &Uuid) -> GovernanceResult<&Multisig> {
+        match self.proposals.get(proposal_id) {
+            Some(TrackedProposal {
+                state: ProposalState::Voting,
+                multisig: Some(multisig),
+            }) => Ok(multisig),
+            _ => Err(GovernanceError::InvalidInput(format!(
+                "Proposal {} is {}, not Voting",
+                proposal_id,
+                self.state(proposal_id)
+            ))),
+        }
+    }
+
+    /// Move a proposal to `next`, checking the transition is allowed
+    fn transition(&mut self, proposal_id: &Uuid, next: ProposalState) -> GovernanceResult<()> {
+        let current = self.state(proposal_id);
+        if !current.can_transition_to(next) {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Invalid transition for proposal {}: {} -> {}",
+                proposal_id, current, next
+            )));
+        }
+        self.proposals
+            .entry(*proposal_id)
+            .or_insert(TrackedProposal {
+                state: current,
+                multisig: None,
+            })
+            .state = next;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(threshold: usize) -> (Vec<GovernanceKeypair>, Multisig, GovernanceStateMachine) {
+        let keypairs: Vec<_> = (0..3)
+            .map(|_| GovernanceKeypair::generate().unwrap())
+            .collect();
+        let multisig = Multisig::new(
+            threshold,
+            3,
+            keypairs.iter().map(|k| k.public_key()).collect(),
+        )
+        .unwrap();
+        let machine = GovernanceStateMachine::new(&keypairs[0].public_key());
+        (keypairs, multisig, machine)
+    }
+
+    fn message() -> GovernanceMessage {
+        GovernanceMessage::module_approval("lightning", "v2.0.0")
+    }
+
+    fn assert_out_of_order<T: fmt::Debug>(result: GovernanceResult<T>) {
+        assert!(
+            matches!(result, Err(GovernanceError::InvalidInput(_))),
+            "{:?}",
+            result
+        );
+    }
+
+    #[test]
+    fn test_state_machine_passed_and_executed() {
+        let (keypairs, multisig, mut machine) = setup(2);
+
+        let mut proposal = machine.propose(message()).unwrap();
+        assert_eq!(machine.state(&proposal.id), ProposalState::Proposed);
+        assert_eq!(proposal.title, "Approve module lightning version v2.0.0");
+
+        machine.open_voting(&mut proposal, &multisig).unwrap();
+        assert_eq!(machine.state(&proposal.id), ProposalState::Voting);
+
+        machine
+            .cast_vote(&mut proposal, &keypairs[0], true)
+            .unwrap();
+        assert_out_of_order(machine.finalize(&mut proposal));
+        machine
+            .cast_vote(&mut proposal, &keypairs[1], true)
+            .unwrap();
+
+        let decision = machine.finalize(&mut proposal).unwrap();
+        assert!(decision.is_passed());
+        assert_eq!(decision.message, message());
+        assert_eq!(decision.votes_for.len(), 2);
+        assert_eq!(machine.state(&proposal.id), ProposalState::Passed);
+
+        machine.execute(&decision).unwrap();
+        assert_eq!(machine.state(&proposal.id), ProposalState::Executed);
+        assert!(machine.state(&proposal.id).is_terminal());
+    }
+
+    #[test]
+    fn test_state_machine_rejected_and_expired() {
+        let (keypairs, multisig, mut machine) = setup(2);
+
+        // Two of three against: the threshold is unreachable
+        let mut proposal = machine.propose(message()).unwrap();
+        machine.open_voting(&mut proposal, &multisig).unwrap();
+        machine
+            .cast_vote(&mut proposal, &keypairs[0], false)
+            .unwrap();
+        machine
+            .cast_vote(&mut proposal, &keypairs[1], false)
+            .unwrap();
+        let decision = machine.finalize(&mut proposal).unwrap();
+        assert_eq!(decision.outcome, ProposalState::Rejected);
+        assert_out_of_order(machine.execute(&decision));
+
+        // Voting closes with the outcome still open
+        let mut proposal = machine.propose(message()).unwrap();
+        machine.open_voting(&mut proposal, &multisig).unwrap();
+        machine
+            .cast_vote(&mut proposal, &keypairs[0], true)
+            .unwrap();
+        proposal.voting_ends_at = proposal.proposed_at - 1;
+        let decision = machine.finalize(&mut proposal).unwrap();
+        assert_eq!(decision.outcome, ProposalState::Expired);
+        assert_eq!(machine.state(&proposal.id), ProposalState::Expired);
+    }
+
+    #[test]
+    fn test_state_machine_invalid_transitions() {
+        let (keypairs, multisig, mut machine) = setup(1);
+
+        // Unknown proposals are drafts and cannot be voted on or finalized
+        let mut draft = GovernanceProposal::new("t", "d", message(), &keypairs[0].public_key(), 60);
+        assert_eq!(machine.state(&draft.id), ProposalState::Draft);
+        assert_out_of_order(machine.open_voting(&mut draft, &multisig));
+        assert_out_of_order(machine.cast_vote(&mut draft, &keypairs[0], true));
+        assert_out_of_order(machine.finalize(&mut draft));
+
+        // Voting must be open before votes are accepted
+        let mut proposal = machine.propose(message()).unwrap();
+        assert_out_of_order(machine.cast_vote(&mut proposal, &keypairs[0], true));
+        assert_out_of_order(machine.finalize(&mut proposal));
+        machine.open_voting(&mut proposal, &multisig).unwrap();
+        assert_out_of_order(machine.open_voting(&mut proposal, &multisig));
+
+        // Only multisig members vote
+        let outsider = GovernanceKeypair::generate().unwrap();
+        assert_out_of_order(machine.cast_vote(&mut proposal, &outsider, true));
+
+        machine
+            .cast_vote(&mut proposal, &keypairs[0], true)
+            .unwrap();
+        let decision = machine.finalize(&mut proposal).unwrap();
+        assert_out_of_order(machine.finalize(&mut proposal));
+        assert_out_of_order(machine.cast_vote(&mut proposal, &keypairs[1], true));
+
+        // A decision stripped of its votes cannot be executed
+        let mut forged = decision.clone();
+        forged.votes_for.clear();
+        assert!(matches!(
+            machine.execute(&forged),
+            Err(GovernanceError::InsufficientSignatures { got: 0, need: 1 })
+        ));
+
+        // Executing twice is refused
+        machine.execute(&decision).unwrap();
+        assert_out_of_order(machine.execute(&decision));
+    }
+
+    #[test]
+    fn test_proposal_state_transitions() {
+        use ProposalState::*;
+        assert!(Draft.can_transition_to(Proposed));
+        assert!(Voting.can_transition_to(Expired));
+        assert!(!Proposed.can_transition_to(Passed));
+        assert!(!Rejected.can_transition_to(Executed));
+        assert!(!Executed.can_transition_to(Executed));
+        assert!(!Passed.is_terminal());
+    }
+}
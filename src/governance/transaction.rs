@@ -0,0 +1,348 @@
+//! # Bitcoin Transactions
+//!
+//! Minimal transaction parsing and serialization for PSBT processing.
+//!
+//! Only the consensus wire format is handled; no script interpretation.
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::sha256d;
+
+/// Transaction input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxIn {
+    /// Previous transaction id (internal byte order)
+    pub previous_txid: [u8; 32],
+    /// Previous output index
+    pub previous_vout: u32,
+    /// Signature script
+    pub script_sig: Vec<u8>,
+    /// Sequence number
+    pub sequence: u32,
+    /// Witness stack (empty for non-witness inputs)
+    pub witness: Vec<Vec<u8>>,
+}
+
+/// Transaction output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TxOut {
+    /// Amount in satoshis
+    pub value: u64,
+    /// Output script
+    pub script_pubkey: Vec<u8>,
+}
+
+/// Bitcoin transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    /// Transaction version
+    pub version: i32,
+    /// Inputs
+    pub inputs: Vec<TxIn>,
+    /// Outputs
+    pub outputs: Vec<TxOut>,
+    /// Lock time
+    pub lock_time: u32,
+}
+
+impl TxOut {
+    /// Parse a serialized output (`<value><script_len><script>`)
+    pub fn parse(bytes: &[u8]) -> GovernanceResult<Self> {
+        let mut offset = 0;
+        let output = read_tx_out(bytes, &mut offset)?;
+        if offset != bytes.len() {
+            return Err(GovernanceError::Serialization(
+                "Unexpected data after transaction output".to_string(),
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Serialize the output
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut result = Vec::with_capacity(9 + self.script_pubkey.len());
+        write_tx_out(&mut result, self);
+        result
+    }
+}
+
+impl Transaction {
+    /// Parse a transaction in either witness or non-witness serialization
+    pub fn parse(bytes: &[u8]) -> GovernanceResult<Self> {
+        Self::parse_inner(bytes, true)
+    }
+
+    /// Parse a transaction that must use the non-witness serialization
+    ///
+    /// Needed for PSBT unsigned transactions, where a zero-input transaction
+    /// would otherwise be mistaken for a segwit marker.
+    pub fn parse_without_witness(bytes: &[u8]) -> GovernanceResult<Self> {
+        Self::parse_inner(bytes, false)
+    }
+
+    fn parse_inner(bytes: &[u8], allow_witness: bool) -> GovernanceResult<Self> {
+        let mut offset = 0;
+        let version = i32::from_le_bytes(read_array(bytes, &mut offset)?);
+
+        // Segwit marker (0x00) and non-zero flag
+        let has_witness = allow_witness
+            && bytes.get(offset) == Some(&0x00)
+            && bytes.get(offset + 1).is_some_and(|&flag| flag != 0x00);
+        if has_witness {
+            offset += 2;
+        }
+
+        let input_count = read_count(bytes, &mut offset)?;
+        let mut inputs = Vec::new();
+        for _ in 0..input_count {
+            let previous_txid = read_array(bytes, &mut offset)?;
+            let previous_vout = u32::from_le_bytes(read_array(bytes, &mut offset)?);
+            let script_sig = read_var_bytes(bytes, &mut offset)?;
+            let sequence = u32::from_le_bytes(read_array(bytes, &mut offset)?);
+            inputs.push(TxIn {
+                previous_txid,
+                previous_vout,
+                script_sig,
+                sequence,
+                witness: Vec::new(),
+            });
+        }
+
+        let output_count = read_count(bytes, &mut offset)?;
+        let mut outputs = Vec::new();
+        for _ in 0..output_count {
+            outputs.push(read_tx_out(bytes, &mut offset)?);
+        }
+
+        if has_witness {
+            for input in &mut inputs {
+                let item_count = read_count(bytes, &mut offset)?;
+                for _ in 0..item_count {
+                    input.witness.push(read_var_bytes(bytes, &mut offset)?);
+                }
+            }
+        }
+
+        let lock_time = u32::from_le_bytes(read_array(bytes, &mut offset)?);
+
+        if offset != bytes.len() {
+            return Err(GovernanceError::Serialization(
+                "Unexpected data after transaction".to_string(),
+            ));
+        }
+
+        Ok(Transaction {
+            version,
+            inputs,
+            outputs,
+            lock_time,
+        })
+    }
+
+    /// Whether any input carries witness data
+    pub fn has_witness(&self) -> bool {
+        self.inputs.iter().any(|input| !input.witness.is_empty())
+    }
+
+    /// Serialize, including witness data if any input has it
+    pub fn serialize(&self) -> Vec<u8> {
+        self.serialize_inner(self.has_witness())
+    }
+
+    /// Serialize without witness data (the txid preimage)
+    pub fn serialize_without_witness(&self) -> Vec<u8> {
+        self.serialize_inner(false)
+    }
+
+    fn serialize_inner(&self, include_witness: bool) -> Vec<u8> {
+        let mut result = Vec::new();
+        result.extend_from_slice(&self.version.to_le_bytes());
+        if include_witness {
+            result.extend_from_slice(&[0x00, 0x01]);
+        }
+
+        write_compact_size(&mut result, self.inputs.len());
+        for input in &self.inputs {
+            result.extend_from_slice(&input.previous_txid);
+            result.extend_from_slice(&input.previous_vout.to_le_bytes());
+            write_var_bytes(&mut result, &input.script_sig);
+            result.extend_from_slice(&input.sequence.to_le_bytes());
+        }
+
+        write_compact_size(&mut result, self.outputs.len());
+        for output in &self.outputs {
+            write_tx_out(&mut result, output);
+        }
+
+        if include_witness {
+            for input in &self.inputs {
+                write_compact_size(&mut result, input.witness.len());
+                for item in &input.witness {
+                    write_var_bytes(&mut result, item);
+                }
+            }
+        }
+
+        result.extend_from_slice(&self.lock_time.to_le_bytes());
+        result
+    }
+
+    /// Transaction id (double SHA256 of the non-witness serialization,
+    /// internal byte order)
+    pub fn txid(&self) -> [u8; 32] {
+        sha256d(&self.serialize_without_witness())
+    }
+}
+
+/// Write compact size (VarInt encoding)
+pub(crate) fn write_compact_size(result: &mut Vec<u8>, size: usize) {
+    if size < 0xfd {
+        result.push(size as u8);
+    } else if size <= 0xffff {
+        result.push(0xfd);
+        result.extend_from_slice(&(size as u16).to_le_bytes());
+    } else if size <= 0xffffffff {
+        result.push(0xfe);
+        result.extend_from_slice(&(size as u32).to_le_bytes());
+    } else {
+        result.push(0xff);
+        result.extend_from_slice(&(size as u64).to_le_bytes());
+    }
+}
+
+/// Read compact size (VarInt decoding), returning the value and bytes consumed
+pub(crate) fn read_compact_size(data: &[u8]) -> GovernanceResult<(usize, usize)> {
+    let (value, consumed) = match data.first() {
+        None => {
+            return Err(GovernanceError::InvalidInput(
+                "Unexpected end of data".to_string(),
+            ))
+        }
+        Some(&n) if n < 0xfd => (n as u64, 1),
+        Some(0xfd) if data.len() >= 3 => (u16::from_le_bytes([data[1], data[2]]) as u64, 3),
+        Some(0xfe) if data.len() >= 5 => (
+            u32::from_le_bytes([data[1], data[2], data[3], data[4]]) as u64,
+            5,
+        ),
+        Some(0xff) if data.len() >= 9 => (
+            u64::from_le_bytes([
+                data[1], data[2], data[3], data[4], data[5], data[6], data[7], data[8],
+            ]),
+            9,
+        ),
+        Some(_) => {
+            return Err(GovernanceError::InvalidInput(
+                "Invalid compact size".to_string(),
+            ))
+        }
+    };
+    let value = usize::try_from(value)
+        .map_err(|_| GovernanceError::InvalidInput("Compact size too large".to_string()))?;
+    Ok((value, consumed))
+}
+
+/// Read a compact size at `offset`, advancing it
+fn read_count(data: &[u8], offset: &mut usize) -> GovernanceResult<usize> {
+    let (value, consumed) = read_compact_size(&data[*offset..])?;
+    *offset += consumed;
+    Ok(value)
+}
+
+/// Read a fixed-size array at `offset`, advancing it
+fn read_array<const N: usize>(data: &[u8], offset: &mut usize) -> GovernanceResult<[u8; N]> {
+    let bytes = data
+        .get(*offset..*offset + N)
+        .ok_or_else(|| GovernanceError::Serialization("Truncated transaction".to_string()))?;
+    *offset += N;
+    let mut array = [0u8; N];
+    array.copy_from_slice(bytes);
+    Ok(array)
+}
+
+/// Read length-prefixed bytes at `offset`, advancing it
+fn read_var_bytes(data: &[u8], offset: &mut usize) -> GovernanceResult<Vec<u8>> {
+    let len = read_count(data, offset)?;
+    let end = offset
+        .checked_add(len)
+        .filter(|&end| end <= data.len())
+        .ok_or_else(|| GovernanceError::Serialization("Truncated transaction".to_string()))?;
+    let bytes = data[*offset..end].to_vec();
+    *offset = end;
+    Ok(bytes)
+}
+
+/// Write length-prefixed bytes
+fn write_var_bytes(result: &mut Vec<u8>, bytes: &[u8]) {
+    write_compact_size(result, bytes.len());
+    result.extend_from_slice(bytes);
+}
+
+/// Read a transaction output at `offset`, advancing it
+fn read_tx_out(data: &[u8], offset: &mut usize) -> GovernanceResult<TxOut> {
+    let value = u64::from_le_bytes(read_array(data, offset)?);
+    let script_pubkey = read_var_bytes(data, offset)?;
+    Ok(TxOut {
+        value,
+        script_pubkey,
+    })
+}
+
+/// Write a transaction output
+fn write_tx_out(result: &mut Vec<u8>, output: &TxOut) {
+    result.extend_from_slice(&output.value.to_le_bytes());
+    write_var_bytes(result, &output.script_pubkey);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Mainnet transaction f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16
+    /// (first bitcoin transaction, block 170)
+    const BLOCK_170_TX: &str = "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd3704000000004847304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b00000000434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f554a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000";
+
+    #[test]
+    fn test_parse_legacy_transaction() {
+        let bytes = hex::decode(BLOCK_170_TX).unwrap();
+        let tx = Transaction::parse(&bytes).unwrap();
+
+        assert_eq!(tx.version, 1);
+        assert_eq!(tx.inputs.len(), 1);
+        assert_eq!(tx.outputs.len(), 2);
+        assert_eq!(tx.outputs[0].value, 1_000_000_000);
+        assert!(!tx.has_witness());
+        assert_eq!(tx.serialize(), bytes);
+
+        let mut txid = tx.txid();
+        txid.reverse();
+        assert_eq!(
+            hex::encode(txid),
+            "f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16"
+        );
+    }
+
+    #[test]
+    fn test_zero_input_transaction_without_witness() {
+        // Version 2, no inputs, one empty output, locktime 0
+        let bytes = hex::decode("02000000000100000000000000000000000000").unwrap();
+        let tx = Transaction::parse_without_witness(&bytes).unwrap();
+        assert!(tx.inputs.is_empty());
+        assert_eq!(tx.outputs.len(), 1);
+        assert_eq!(tx.serialize_without_witness(), bytes);
+    }
+
+    #[test]
+    fn test_truncated_transaction_rejected() {
+        let bytes = hex::decode(BLOCK_170_TX).unwrap();
+        assert!(Transaction::parse(&bytes[..bytes.len() - 1]).is_err());
+        assert!(Transaction::parse(&[bytes.clone(), vec![0x00]].concat()).is_err());
+    }
+
+    #[test]
+    fn test_compact_size_roundtrip() {
+        for size in [0usize, 0xfc, 0xfd, 0xffff, 0x10000, 0xffff_ffff] {
+            let mut encoded = Vec::new();
+            write_compact_size(&mut encoded, size);
+            assert_eq!(read_compact_size(&encoded).unwrap(), (size, encoded.len()));
+        }
+    }
+}
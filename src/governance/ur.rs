@@ -0,0 +1,991 @@
+//! # Uniform Resources (UR)
+//!
+//! Move signing requests and signature envelopes across an air gap as
+//! QR-friendly text.
+//!
+//! Specifications:
+//! - https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-005-ur.md
+//! - https://github.com/BlockchainCommons/Research/blob/master/papers/bcr-2020-012-bytewords.md
+//!
+//! A payload is CBOR-encoded, split into fragments of at most a configured
+//! size and fountain-encoded into `ur:<type>/<seq>-<count>/<bytewords>`
+//! parts. Parts past `count` mix several fragments, so a scanner that
+//! misses frames can still finish; [`UrDecoder`] accepts parts in any
+//! order. Payloads that fit in one fragment use `ur:<type>/<bytewords>`.
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use uuid::Uuid;
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::keys::PublicKey;
+use crate::governance::messages::GovernanceMessage;
+use crate::governance::signatures::{verify_signature, Signature};
+
+/// UR type of a [`SigningRequest`]
+pub const REQUEST_UR_TYPE: &str = "bllvm-request";
+
+/// UR type of a [`SignatureEnvelope`]
+pub const SIGNATURE_UR_TYPE: &str = "bllvm-signature";
+
+/// Default maximum fragment size, in bytes
+pub const DEFAULT_MAX_FRAGMENT_LEN: usize = 200;
+
+/// Smallest fragment the encoder will produce, in bytes
+const MIN_FRAGMENT_LEN: usize = 10;
+
+/// Largest message a decoder will reassemble, in bytes
+const MAX_MESSAGE_LEN: usize = 16 * 1024 * 1024;
+
+/// Maximum nesting of CBOR arrays and maps
+const MAX_CBOR_DEPTH: usize = 32;
+
+/// Bytewords, indexed by byte value; minimal encoding uses first and last letters
+const BYTEWORDS: [&str; 256] = [
+    "able", "acid", "also", "apex", "aqua", "arch", "atom", "aunt", "away", "axis", "back", "bald",
+    "barn", "belt", "beta", "bias", "blue", "body", "brag", "brew", "bulb", "buzz", "calm", "cash",
+    "cats", "chef", "city", "claw", "code", "cola", "cook", "cost", "crux", "curl", "cusp", "cyan",
+    "dark", "data", "days", "deli", "dice", "diet", "door", "down", "draw", "drop", "drum", "dull",
+    "duty", "each", "easy", "echo", "edge", "epic", "even", "exam", "exit", "eyes", "fact", "fair",
+    "fern", "figs", "film", "fish", "fizz", "flap", "flew", "flux", "foxy", "free", "frog", "fuel",
+    "fund", "gala", "game", "gear", "gems", "gift", "girl", "glow", "good", "gray", "grim", "guru",
+    "gush", "gyro", "half", "hang", "hard", "hawk", "heat", "help", "high", "hill", "holy", "hope",
+    "horn", "huts", "iced", "idea", "idle", "inch", "inky", "into", "iris", "iron", "item", "jade",
+    "jazz", "join", "jolt", "jowl", "judo", "jugs", "jump", "junk", "jury", "keep", "keno", "kept",
+    "keys", "kick", "kiln", "king", "kite", "kiwi", "knob", "lamb", "lava", "lazy", "leaf", "legs",
+    "liar", "limp", "lion", "list", "logo", "loud", "love", "luau", "luck", "lung", "main", "many",
+    "math", "maze", "memo", "menu", "meow", "mild", "mint", "miss", "monk", "nail", "navy", "need",
+    "news", "next", "noon", "note", "numb", "obey", "oboe", "omit", "onyx", "open", "oval", "owls",
+    "paid", "part", "peck", "play", "plus", "poem", "pool", "pose", "puff", "puma", "purr", "quad",
+    "quiz", "race", "ramp", "real", "redo", "rich", "road", "rock", "roof", "ruby", "ruin", "runs",
+    "rust", "safe", "saga", "scar", "sets", "silk", "skew", "slot", "soap", "solo", "song", "stub",
+    "surf", "swan", "taco", "task", "taxi", "tent", "tied", "time", "tiny", "toil", "tomb", "toys",
+    "trip", "tuna", "twin", "ugly", "undo", "unit", "urge", "user", "vast", "very", "veto", "vial",
+    "vibe", "view", "visa", "void", "vows", "wall", "wand", "warm", "wasp", "wave", "waxy", "webs",
+    "what", "when", "whiz", "wolf", "work", "yank", "yawn", "yell", "yoga", "yurt", "zaps", "zero",
+    "zest", "zinc", "zone", "zoom",
+];
+
+/// A governance message waiting to be signed on the offline machine
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SigningRequest {
+    /// Correlates the returned [`SignatureEnvelope`] with this request
+    pub request_id: Uuid,
+    /// Message to sign under the governance signing domain
+    pub message: GovernanceMessage,
+}
+
+impl SigningRequest {
+    /// Create a request with a fresh request ID
+    pub fn new(message: GovernanceMessage) -> Self {
+        Self {
+            request_id: Uuid::new_v4(),
+            message,
+        }
+    }
+
+    /// CBOR encoding of the request
+    pub fn to_cbor(&self) -> GovernanceResult<Vec<u8>> {
+        to_cbor(self)
+    }
+
+    /// Decode a request from CBOR
+    pub fn from_cbor(bytes: &[u8]) -> GovernanceResult<Self> {
+        from_cbor(bytes)
+    }
+
+    /// Encode as `ur:bllvm-request` parts of at most `max_fragment_len` bytes each
+    pub fn to_ur_parts(&self, max_fragment_len: usize) -> GovernanceResult<Vec<String>> {
+        encode(REQUEST_UR_TYPE, &self.to_cbor()?, max_fragment_len)
+    }
+
+    /// Decode from `ur:bllvm-request` parts in any order
+    pub fn from_ur_parts<I, S>(parts: I) -> GovernanceResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_cbor(&decode_typed(parts, REQUEST_UR_TYPE)?)
+    }
+}
+
+/// A signature produced for a [`SigningRequest`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SignatureEnvelope {
+    /// ID of the request that was signed
+    pub request_id: Uuid,
+    /// Signature over the request's governance signing bytes
+    pub signature: Signature,
+    /// Public key that made the signature
+    pub public_key: PublicKey,
+}
+
+impl SignatureEnvelope {
+    /// Check the envelope answers `request` with a valid signature
+    pub fn verify(&self, request: &SigningRequest) -> GovernanceResult<bool> {
+        if self.request_id != request.request_id {
+            return Ok(false);
+        }
+        verify_signature(
+            &self.signature,
+            &request.message.to_signing_bytes(),
+            &self.public_key,
+        )
+    }
+
+    /// CBOR encoding of the envelope
+    pub fn to_cbor(&self) -> GovernanceResult<Vec<u8>> {
+        to_cbor(self)
+    }
+
+    /// Decode an envelope from CBOR
+    pub fn from_cbor(bytes: &[u8]) -> GovernanceResult<Self> {
+        from_cbor(bytes)
+    }
+
+    /// Encode as `ur:bllvm-signature` parts of at most `max_fragment_len` bytes each
+    pub fn to_ur_parts(&self, max_fragment_len: usize) -> GovernanceResult<Vec<String>> {
+        encode(SIGNATURE_UR_TYPE, &self.to_cbor()?, max_fragment_len)
+    }
+
+    /// Decode from `ur:bllvm-signature` parts in any order
+    pub fn from_ur_parts<I, S>(parts: I) -> GovernanceResult<Self>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        Self::from_cbor(&decode_typed(parts, SIGNATURE_UR_TYPE)?)
+    }
+}
+
+/// Encode `message` as one part per fragment
+///
+/// This is the smallest set of parts that decodes; use [`UrEncoder`]
+/// directly to keep producing fountain parts for an animated QR code.
+pub fn encode(
+    ur_type: &str,
+    message: &[u8],
+    max_fragment_len: usize,
+) -> GovernanceResult<Vec<String>> {
+    let mut encoder = UrEncoder::new(ur_type, message, max_fragment_len)?;
+    Ok((0..encoder.seq_len())
+        .map(|_| encoder.next_part())
+        .collect())
+}
+
+/// Decode parts supplied in any order, returning the UR type and message
+pub fn decode<I, S>(parts: I) -> GovernanceResult<(String, Vec<u8>)>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut decoder = UrDecoder::new();
+    for part in parts {
+        decoder.receive(part.as_ref())?;
+    }
+    let (received, expected) = decoder.progress();
+    match (decoder.ur_type, decoder.message) {
+        (Some(ur_type), Some(message)) => Ok((ur_type, message)),
+        _ => Err(invalid(&format!(
+            "Incomplete message, {} of {} fragments recovered",
+            received, expected
+        ))),
+    }
+}
+
+fn decode_typed<I, S>(parts: I, expected: &str) -> GovernanceResult<Vec<u8>>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let (ur_type, message) = decode(parts)?;
+    if ur_type != expected {
+        return Err(invalid(&format!(
+            "Expected ur:{}, got ur:{}",
+            expected, ur_type
+        )));
+    }
+    Ok(message)
+}
+
+/// Fountain encoder producing an endless sequence of parts
+#[derive(Debug, Clone)]
+pub struct UrEncoder {
+    ur_type: String,
+    message: Vec<u8>,
+    fragments: Vec<Vec<u8>>,
+    checksum: u32,
+    seq_num: u32,
+}
+
+impl UrEncoder {
+    /// Split `message` into fragments of at most `max_fragment_len` bytes
+    pub fn new(ur_type: &str, message: &[u8], max_fragment_len: usize) -> GovernanceResult<Self> {
+        check_ur_type(ur_type)?;
+        if message.is_empty() {
+            return Err(invalid("Cannot encode an empty message"));
+        }
+        if message.len() > MAX_MESSAGE_LEN {
+            return Err(invalid("Message too large"));
+        }
+        if max_fragment_len < MIN_FRAGMENT_LEN {
+            return Err(invalid(&format!(
+                "Maximum fragment length must be at least {} bytes",
+                MIN_FRAGMENT_LEN
+            )));
+        }
+
+        let fragment_len = nominal_fragment_len(message.len(), max_fragment_len);
+        let fragments = message
+            .chunks(fragment_len)
+            .map(|chunk| {
+                let mut fragment = chunk.to_vec();
+                fragment.resize(fragment_len, 0);
+                fragment
+            })
+            .collect();
+        Ok(Self {
+            ur_type: ur_type.to_string(),
+            message: message.to_vec(),
+            fragments,
+            checksum: crc32(message),
+            seq_num: 0,
+        })
+    }
+
+    /// Number of fragments; the first `seq_len` parts carry one fragment each
+    pub fn seq_len(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// Whether the message fits in a single-part UR
+    pub fn is_single_part(&self) -> bool {
+        self.fragments.len() == 1
+    }
+
+    /// Produce the next part
+    ///
+    /// A single-part UR is returned unchanged on every call.
+    pub fn next_part(&mut self) -> String {
+        if self.is_single_part() {
+            return format!("ur:{}/{}", self.ur_type, bytewords_encode(&self.message));
+        }
+
+        self.seq_num = self.seq_num.wrapping_add(1).max(1);
+        let mut data = vec![0u8; self.fragments[0].len()];
+        for index in choose_fragments(self.seq_num, self.seq_len(), self.checksum) {
+            xor_into(&mut data, &self.fragments[index]);
+        }
+        let part = Part {
+            seq_num: self.seq_num,
+            seq_len: self.seq_len(),
+            message_len: self.message.len(),
+            checksum: self.checksum,
+            data,
+        };
+        format!(
+            "ur:{}/{}-{}/{}",
+            self.ur_type,
+            self.seq_num,
+            self.seq_len(),
+            bytewords_encode(&part.to_cbor())
+        )
+    }
+}
+
+/// Fountain decoder assembling parts received in any order
+#[derive(Debug, Default)]
+pub struct UrDecoder {
+    ur_type: Option<String>,
+    /// `(seq_len, message_len, checksum)` shared by every part
+    params: Option<(usize, usize, u32)>,
+    fragment_len: usize,
+    simple: BTreeMap<usize, Vec<u8>>,
+    mixed: BTreeMap<BTreeSet<usize>, Vec<u8>>,
+    message: Option<Vec<u8>>,
+}
+
+impl UrDecoder {
+    /// Create an empty decoder
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed one part; duplicates and parts after completion are ignored
+    ///
+    /// Parts are case-insensitive, as QR alphanumeric mode upper-cases them.
+    pub fn receive(&mut self, part: &str) -> GovernanceResult<()> {
+        let part = part.trim().to_ascii_lowercase();
+        let rest = part
+            .strip_prefix("ur:")
+            .ok_or_else(|| invalid("Missing 'ur:' scheme"))?;
+        let (ur_type, sequence, body) = match rest.split('/').collect::<Vec<_>>().as_slice() {
+            [ur_type, body] => (*ur_type, None, *body),
+            [ur_type, sequence, body] => (*ur_type, Some(*sequence), *body),
+            _ => return Err(invalid("Expected ur:<type>/[<seq>-<count>/]<payload>")),
+        };
+        check_ur_type(ur_type)?;
+        if let Some(expected) = &self.ur_type {
+            if expected != ur_type {
+                return Err(invalid(&format!(
+                    "Part has type {}, expected {}",
+                    ur_type, expected
+                )));
+            }
+        }
+        if self.message.is_some() {
+            return Ok(());
+        }
+
+        let data = bytewords_decode(body)?;
+        match sequence {
+            None => {
+                if self.params.is_some() {
+                    return Err(invalid("Single-part UR mixed with multi-part UR"));
+                }
+                self.message = Some(data);
+            }
+            Some(sequence) => {
+                let (seq_num, seq_len) = sequence
+                    .split_once('-')
+                    .and_then(|(n, m)| Some((n.parse::<u32>().ok()?, m.parse::<usize>().ok()?)))
+                    .ok_or_else(|| invalid(&format!("Invalid sequence '{}'", sequence)))?;
+                let part = Part::from_cbor(&data)?;
+                if part.seq_num != seq_num || part.seq_len != seq_len {
+                    return Err(invalid("Sequence does not match part header"));
+                }
+                self.receive_part(part)?;
+            }
+        }
+        self.ur_type = Some(ur_type.to_string());
+        Ok(())
+    }
+
+    /// Whether the message has been reassembled
+    pub fn is_complete(&self) -> bool {
+        self.message.is_some()
+    }
+
+    /// UR type of the parts received so far
+    pub fn ur_type(&self) -> Option<&str> {
+        self.ur_type.as_deref()
+    }
+
+    /// Reassembled message, once complete
+    pub fn message(&self) -> Option<&[u8]> {
+        self.message.as_deref()
+    }
+
+    /// Fragments recovered and fragments expected (0 before the first part)
+    pub fn progress(&self) -> (usize, usize) {
+        match &self.message {
+            Some(_) if self.params.is_none() => (1, 1),
+            _ => (self.simple.len(), self.seq_len()),
+        }
+    }
+
+    fn seq_len(&self) -> usize {
+        self.params.map_or(0, |(seq_len, _, _)| seq_len)
+    }
+
+    fn receive_part(&mut self, part: Part) -> GovernanceResult<()> {
+        let params = (part.seq_len, part.message_len, part.checksum);
+        match self.params {
+            None => {
+                if part.message_len == 0 || part.message_len > MAX_MESSAGE_LEN {
+                    return Err(invalid("Invalid message length"));
+                }
+                if part.data.is_empty()
+                    || part.message_len.div_ceil(part.data.len()) != part.seq_len
+                {
+                    return Err(invalid("Fragment count does not match message length"));
+                }
+                self.params = Some(params);
+                self.fragment_len = part.data.len();
+            }
+            Some(expected) => {
+                if expected != params || part.data.len() != self.fragment_len {
+                    return Err(invalid("Part belongs to a different message"));
+                }
+            }
+        }
+
+        let mut queue = VecDeque::from([(
+            choose_fragments(part.seq_num, part.seq_len, part.checksum),
+            part.data,
+        )]);
+        while let Some((indexes, data)) = queue.pop_front() {
+            if self.message.is_some() {
+                break;
+            }
+            if indexes.len() == 1 {
+                self.add_simple(indexes, data, &mut queue)?;
+            } else {
+                self.add_mixed(indexes, data, &mut queue);
+            }
+        }
+        Ok(())
+    }
+
+    fn add_simple(
+        &mut self,
+        indexes: BTreeSet<usize>,
+        data: Vec<u8>,
+        queue: &mut VecDeque<(BTreeSet<usize>, Vec<u8>)>,
+    ) -> GovernanceResult<()> {
+        let index = *indexes.iter().next().expect("simple part has one index");
+        if self.simple.contains_key(&index) {
+            return Ok(());
+        }
+        self.reduce_mixed_by(&indexes, &data, queue);
+        self.simple.insert(index, data);
+
+        let (seq_len, message_len, checksum) = self.params.expect("parameters set by first part");
+        if self.simple.len() == seq_len {
+            let mut message: Vec<u8> = self.simple.values().flatten().copied().collect();
+            message.truncate(message_len);
+            if crc32(&message) != checksum {
+                return Err(invalid("Message checksum mismatch"));
+            }
+            self.message = Some(message);
+        }
+        Ok(())
+    }
+
+    fn add_mixed(
+        &mut self,
+        mut indexes: BTreeSet<usize>,
+        mut data: Vec<u8>,
+        queue: &mut VecDeque<(BTreeSet<usize>, Vec<u8>)>,
+    ) {
+        if self.mixed.contains_key(&indexes) {
+            return;
+        }
+        for (index, fragment) in &self.simple {
+            if indexes.remove(index) {
+                xor_into(&mut data, fragment);
+            }
+        }
+        for (other, fragment) in &self.mixed {
+            if other.is_subset(&indexes) {
+                indexes = indexes.difference(other).copied().collect();
+                xor_into(&mut data, fragment);
+            }
+        }
+
+        match indexes.len() {
+            0 => {}
+            1 => queue.push_back((indexes, data)),
+            _ => {
+                self.reduce_mixed_by(&indexes, &data, queue);
+                self.mixed.insert(indexes, data);
+            }
+        }
+    }
+
+    /// XOR a known combination out of every mixed part that contains it
+    fn reduce_mixed_by(
+        &mut self,
+        indexes: &BTreeSet<usize>,
+        data: &[u8],
+        queue: &mut VecDeque<(BTreeSet<usize>, Vec<u8>)>,
+    ) {
+        for (other, mut other_data) in std::mem::take(&mut self.mixed) {
+            if !indexes.is_subset(&other) {
+                self.mixed.insert(other, other_data);
+                continue;
+            }
+            let reduced: BTreeSet<usize> = other.difference(indexes).copied().collect();
+            xor_into(&mut other_data, data);
+            match reduced.len() {
+                0 => {}
+                1 => queue.push_back((reduced, other_data)),
+                _ => {
+                    self.mixed.insert(reduced, other_data);
+                }
+            }
+        }
+    }
+}
+
+/// One fountain-encoded part: `[seq_num, seq_len, message_len, checksum, data]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Part {
+    seq_num: u32,
+    seq_len: usize,
+    message_len: usize,
+    checksum: u32,
+    data: Vec<u8>,
+}
+
+impl Part {
+    fn to_cbor(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(self.data.len() + 24);
+        cbor_head(&mut out, 4, 5);
+        cbor_head(&mut out, 0, self.seq_num as u64);
+        cbor_head(&mut out, 0, self.seq_len as u64);
+        cbor_head(&mut out, 0, self.message_len as u64);
+        cbor_head(&mut out, 0, self.checksum as u64);
+        cbor_head(&mut out, 2, self.data.len() as u64);
+        out.extend_from_slice(&self.data);
+        out
+    }
+
+    fn from_cbor(bytes: &[u8]) -> GovernanceResult<Self> {
+        let mut reader = CborReader {
+            data: bytes,
+            pos: 0,
+        };
+        if reader.head()? != (4, 5) {
+            return Err(invalid("Part must be a CBOR array of 5 items"));
+        }
+        let seq_num = u32::try_from(reader.uint()?).map_err(|_| invalid("Invalid seq_num"))?;
+        let seq_len = usize::try_from(reader.uint()?).map_err(|_| invalid("Invalid seq_len"))?;
+        let message_len =
+            usize::try_from(reader.uint()?).map_err(|_| invalid("Invalid message_len"))?;
+        let checksum = u32::try_from(reader.uint()?).map_err(|_| invalid("Invalid checksum"))?;
+        let data = reader.bytes()?.to_vec();
+        if reader.pos != bytes.len() {
+            return Err(invalid("Trailing bytes after part"));
+        }
+        if seq_num == 0 || seq_len == 0 {
+            return Err(invalid("Sequence numbers start at 1"));
+        }
+        Ok(Self {
+            seq_num,
+            seq_len,
+            message_len,
+            checksum,
+            data,
+        })
+    }
+}
+
+/// Largest fragment length no bigger than `max_fragment_len` that splits evenly
+fn nominal_fragment_len(message_len: usize, max_fragment_len: usize) -> usize {
+    let max_count = (message_len / MIN_FRAGMENT_LEN).max(1);
+    (1..=max_count)
+        .map(|count| message_len.div_ceil(count))
+        .find(|&len| len <= max_fragment_len)
+        .unwrap_or_else(|| message_len.div_ceil(max_count))
+}
+
+/// Fragment indexes mixed into part `seq_num`
+///
+/// Parts up to `seq_len` carry their own fragment; later ones draw a degree
+/// and a set of fragments from a generator seeded by the part, so encoder
+/// and decoder agree without sending the indexes.
+fn choose_fragments(seq_num: u32, seq_len: usize, checksum: u32) -> BTreeSet<usize> {
+    if seq_num as usize <= seq_len {
+        return BTreeSet::from([seq_num as usize - 1]);
+    }
+
+    let mut seed = seq_num.to_be_bytes().to_vec();
+    seed.extend_from_slice(&checksum.to_be_bytes());
+    let mut rng = Xoshiro256::from_seed(&seed);
+    let degree = choose_degree(seq_len, &mut rng);
+    let mut remaining: Vec<usize> = (0..seq_len).collect();
+    let mut chosen = BTreeSet::new();
+    while chosen.len() < degree {
+        let index = rng.next_int(0, remaining.len() as u64 - 1) as usize;
+        chosen.insert(remaining.remove(index));
+    }
+    chosen
+}
+
+/// Draw a degree in `1..=seq_len` with probability proportional to `1/degree`
+///
+/// Uses Vose's alias method, as the reference implementation does, so the
+/// same seed yields the same degree.
+fn choose_degree(seq_len: usize, rng: &mut Xoshiro256) -> usize {
+    let weights: Vec<f64> = (1..=seq_len).map(|i| 1.0 / i as f64).collect();
+    let sum: f64 = weights.iter().sum();
+    let n = weights.len();
+    let mut scaled: Vec<f64> = weights.iter().map(|w| w * n as f64 / sum).collect();
+
+    let (mut small, mut large) = (Vec::new(), Vec::new());
+    for (i, &p) in scaled.iter().enumerate().rev() {
+        if p < 1.0 {
+            small.push(i);
+        } else {
+            large.push(i);
+        }
+    }
+
+    let mut probs = vec![0.0; n];
+    let mut aliases = vec![0usize; n];
+    while let (Some(&a), Some(&g)) = (small.last(), large.last()) {
+        small.pop();
+        large.pop();
+        probs[a] = scaled[a];
+        aliases[a] = g;
+        scaled[g] += scaled[a] - 1.0;
+        if scaled[g] < 1.0 {
+            small.push(g);
+        } else {
+            large.push(g);
+        }
+    }
+    for i in large.into_iter().chain(small) {
+        probs[i] = 1.0;
+    }
+
+    let r1 = rng.next_double();
+    let r2 = rng.next_double();
+    let i = (n as f64 * r1) as usize;
+    if r2 < probs[i] {
+        i + 1
+    } else {
+        aliases[i] + 1
+    }
+}
+
+/// xoshiro256** seeded from the SHA256 of a byte string
+struct Xoshiro256 {
+    s: [u64; 4],
+}
+
+impl Xoshiro256 {
+    fn from_seed(seed: &[u8]) -> Self {
+        let digest = Sha256::digest(seed);
+        let mut s = [0u64; 4];
+        for (word, chunk) in s.iter_mut().zip(digest.chunks(8)) {
+            *word = u64::from_be_bytes(chunk.try_into().expect("8-byte chunk"));
+        }
+        Self { s }
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        let result = self.s[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.s[1] << 17;
+        self.s[2] ^= self.s[0];
+        self.s[3] ^= self.s[1];
+        self.s[1] ^= self.s[2];
+        self.s[0] ^= self.s[3];
+        self.s[2] ^= t;
+        self.s[3] = self.s[3].rotate_left(45);
+        result
+    }
+
+    fn next_double(&mut self) -> f64 {
+        self.next_u64() as f64 / (u64::MAX as f64 + 1.0)
+    }
+
+    fn next_int(&mut self, low: u64, high: u64) -> u64 {
+        (self.next_double() * (high - low + 1) as f64) as u64 + low
+    }
+}
+
+/// UR types are lowercase letters, digits and hyphens
+fn check_ur_type(ur_type: &str) -> GovernanceResult<()> {
+    if ur_type.is_empty()
+        || !ur_type
+            .bytes()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == b'-')
+    {
+        return Err(invalid(&format!("Invalid type '{}'", ur_type)));
+    }
+    Ok(())
+}
+
+fn xor_into(target: &mut [u8], other: &[u8]) {
+    for (a, b) in target.iter_mut().zip(other) {
+        *a ^= b;
+    }
+}
+
+/// CRC-32 (ISO-HDLC), as used by bytewords and fountain parts
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in data {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xedb8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Minimal bytewords of `data` followed by its CRC-32
+fn bytewords_encode(data: &[u8]) -> String {
+    let checksum = crc32(data).to_be_bytes();
+    let mut encoded = String::with_capacity((data.len() + checksum.len()) * 2);
+    for &byte in data.iter().chain(checksum.iter()) {
+        let word = BYTEWORDS[byte as usize].as_bytes();
+        encoded.push(word[0] as char);
+        encoded.push(word[3] as char);
+    }
+    encoded
+}
+
+/// Decode minimal bytewords and check the trailing CRC-32
+fn bytewords_decode(s: &str) -> GovernanceResult<Vec<u8>> {
+    let pairs = s.as_bytes().chunks_exact(2);
+    if !s.is_ascii() || !pairs.remainder().is_empty() {
+        return Err(invalid("Invalid bytewords length"));
+    }
+    let bytes = pairs
+        .map(|pair| {
+            BYTEWORDS
+                .iter()
+                .position(|word| {
+                    let word = word.as_bytes();
+                    word[0] == pair[0] && word[3] == pair[1]
+                })
+                .map(|value| value as u8)
+                .ok_or_else(|| {
+                    invalid(&format!(
+                        "Invalid byteword '{}'",
+                        String::from_utf8_lossy(pair)
+                    ))
+                })
+        })
+        .collect::<GovernanceResult<Vec<u8>>>()?;
+    if bytes.len() < 4 {
+        return Err(invalid("Bytewords checksum missing"));
+    }
+    let (data, checksum) = bytes.split_at(bytes.len() - 4);
+    if crc32(data).to_be_bytes() != checksum {
+        return Err(invalid("Bytewords checksum mismatch"));
+    }
+    Ok(data.to_vec())
+}
+
+fn to_cbor<T: Serialize>(value: &T) -> GovernanceResult<Vec<u8>> {
+    let value =
+        serde_json::to_value(value).map_err(|e| GovernanceError::Serialization(e.to_string()))?;
+    let mut out = Vec::new();
+    cbor_encode_value(&mut out, &value);
+    Ok(out)
+}
+
+fn from_cbor<T: DeserializeOwned>(bytes: &[u8]) -> GovernanceResult<T> {
+    let mut reader = CborReader {
+        data: bytes,
+        pos: 0,
+    };
+    let value = reader.value(0)?;
+    if reader.pos != bytes.len() {
+        return Err(cbor_error("trailing bytes"));
+    }
+    serde_json::from_value(value).map_err(|e| GovernanceError::Serialization(e.to_string()))
+}
+
+/// Write a CBOR initial byte and argument in the shortest form
+fn cbor_head(out: &mut Vec<u8>, major: u8, argument: u64) {
+    let major = major << 5;
+    match argument {
+        0..=23 => out.push(major | argument as u8),
+        24..=0xff => out.extend_from_slice(&[major | 24, argument as u8]),
+        0x100..=0xffff => {
+            out.push(major | 25);
+            out.extend_from_slice(&(argument as u16).to_be_bytes());
+        }
+        0x1_0000..=0xffff_ffff => {
+            out.push(major | 26);
+            out.extend_from_slice(&(argument as u32).to_be_bytes());
+        }
+        _ => {
+            out.push(major | 27);
+            out.extend_from_slice(&argument.to_be_bytes());
+        }
+    }
+}
+
+fn cbor_encode_value(out: &mut Vec<u8>, value: &Value) {
+    match value {
+        Value::Null => out.push(0xf6),
+        Value::Bool(false) => out.push(0xf4),
+        Value::Bool(true) => out.push(0xf5),
+        Value::Number(n) => {
+            if let Some(n) = n.as_u64() {
+                cbor_head(out, 0, n);
+            } else if let Some(n) = n.as_i64() {
+                // Major type 1 encodes -1 - n
+                cbor_head(out, 1, !n as u64);
+            } else {
+                out.push(0xfb);
+                out.extend_from_slice(&n.as_f64().unwrap_or(0.0).to_be_bytes());
+            }
+        }
+        Value::String(s) => {
+            cbor_head(out, 3, s.len() as u64);
+            out.extend_from_slice(s.as_bytes());
+        }
+        Value::Array(items) => {
+            cbor_head(out, 4, items.len() as u64);
+            for item in items {
+                cbor_encode_value(out, item);
+            }
+        }
+        Value::Object(map) => {
+            cbor_head(out, 5, map.len() as u64);
+            for (key, item) in map {
+                cbor_head(out, 3, key.len() as u64);
+                out.extend_from_slice(key.as_bytes());
+                cbor_encode_value(out, item);
+            }
+        }
+    }
+}
+
+/// Definite-length CBOR reader for the subset the encoder produces
+struct CborReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> CborReader<'a> {
+    fn take(&mut self, len: usize) -> GovernanceResult<&'a [u8]> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .filter(|&end| end <= self.data.len())
+            .ok_or_else(|| cbor_error("unexpected end of data"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Major type and argument; floats return their raw bits
+    fn head(&mut self) -> GovernanceResult<(u8, u64)> {
+        let initial = self.take(1)?[0];
+        let (major, info) = (initial >> 5, initial & 0x1f);
+        let argument = match info {
+            0..=23 => info as u64,
+            24 => self.take(1)?[0] as u64,
+            25 => u16::from_be_bytes(self.take(2)?.try_into().expect("2 bytes")) as u64,
+            26 => u32::from_be_bytes(self.take(4)?.try_into().expect("4 bytes")) as u64,
+            27 => u64::from_be_bytes(self.take(8)?.try_into().expect("8 bytes")),
+            _ => return Err(cbor_error("indefinite lengths are not supported")),
+        };
+        if major == 7 && info != 27 && !(20..=22).contains(&info) {
+            return Err(cbor_error("unsupported simple value"));
+        }
+        Ok((major, argument))
+    }
+
+    fn uint(&mut self) -> GovernanceResult<u64> {
+        match self.head()? {
+            (0, n) => Ok(n),
+            _ => Err(cbor_error("expected unsigned integer")),
+        }
+    }
+
+    fn len(argument: u64) -> GovernanceResult<usize> {
+        usize::try_from(argument).map_err(|_| cbor_error("length too large"))
+    }
+
+    fn bytes(&mut self) -> GovernanceResult<&'a [u8]> {
+        match self.head()? {
+            (2, len) => self.take(Self::len(len)?),
+            _ => Err(cbor_error("expected byte string")),
+        }
+    }
+
+    fn text(&mut self, len: u64) -> GovernanceResult<String> {
+        let bytes = self.take(Self::len(len)?)?;
+        String::from_utf8(bytes.to_vec()).map_err(|_| cbor_error("invalid UTF-8 in text"))
+    }
+
+    fn value(&mut self, depth: usize) -> GovernanceResult<Value> {
+        if depth > MAX_CBOR_DEPTH {
+            return Err(cbor_error("nesting too deep"));
+        }
+        let start = self.pos;
+        Ok(match self.head()? {
+            (0, n) => Value::from(n),
+            (1, n) => i64::try_from(n)
+                .map(|n| Value::from(-1 - n))
+                .map_err(|_| cbor_error("negative integer out of range"))?,
+            (3, len) => Value::String(self.text(len)?),
+            (4, len) => {
+                let mut items = Vec::new();
+                for _ in 0..len {
+                    items.push(self.value(depth + 1)?);
+                }
+                Value::Array(items)
+            }
+            (5, len) => {
+                let mut map = serde_json::Map::new();
+                for _ in 0..len {
+                    let key = match self.head()? {
+                        (3, key_len) => self.text(key_len)?,
+                        _ => return Err(cbor_error("map keys must be text")),
+                    };
+                    map.insert(key, self.value(depth + 1)?);
+                }
+                Value::Object(map)
+            }
+            (7, bits) => match self.data[start] & 0x1f {
+                20 => Value::Bool(false),
+                21 => Value::Bool(true),
+                22 => Value::Null,
+                _ => serde_json::Number::from_f64(f64::from_bits(bits))
+                    .map(Value::Number)
+                    .ok_or_else(|| cbor_error("non-finite float"))?,
+            },
+            _ => return Err(cbor_error("unsupported major type")),
+        })
+    }
+}
+
+fn cbor_error(message: &str) -> GovernanceError {
+    GovernanceError::Serialization(format!("CBOR: {}", message))
+}
+
+fn invalid(message: &str) -> GovernanceError {
+    GovernanceError::InvalidInput(format!("UR: {}", message))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytewords_vector() {
+        // BCR-2020-012 test vector
+        assert_eq!(bytewords_encode(&[0, 1, 2, 128, 255]), "aeadaolazmjendeoti");
+        assert_eq!(
+            bytewords_decode("aeadaolazmjendeoti").unwrap(),
+            vec![0, 1, 2, 128, 255]
+        );
+        assert!(bytewords_decode("aeadaolazmjendeotl").is_err());
+        assert_eq!(crc32(b"Hello, world!"), 0xebe6_c6e6);
+    }
+
+    #[test]
+    fn test_fragment_selection() {
+        assert_eq!(nominal_fragment_len(1000, 200), 200);
+        assert_eq!(nominal_fragment_len(1001, 200), 167);
+        assert_eq!(nominal_fragment_len(5, 200), 5);
+
+        // Simple parts carry their own fragment; later parts mix deterministically
+        assert_eq!(choose_fragments(3, 10, 0x1234), BTreeSet::from([2]));
+        let mixed = choose_fragments(11, 10, 0x1234);
+        assert!(!mixed.is_empty() && mixed.iter().all(|&i| i < 10));
+        assert_eq!(mixed, choose_fragments(11, 10, 0x1234));
+    }
+
+    #[test]
+    fn test_cbor_round_trip() {
+        let value = serde_json::json!({
+            "n": 65536,
+            "neg": -25,
+            "f": 1.5,
+            "s": "text",
+            "a": [true, false, null],
+        });
+        let mut out = Vec::new();
+        cbor_encode_value(&mut out, &value);
+        let decoded: Value = from_cbor(&out).unwrap();
+        assert_eq!(decoded, value);
+        assert!(from_cbor::<Value>(&out[..out.len() - 1]).is_err());
+    }
+}
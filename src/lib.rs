@@ -27,12 +27,25 @@
 //! - `bllvm-sign` - Sign governance messages
 //! - `bllvm-verify` - Verify signatures and multisig thresholds
 //!
+//! ## Feature Flags
+//!
+//! - `node` (default) - composition framework, module APIs, CLI tools and
+//!   the tokio-backed signers
+//! - `governance` - governance primitives only; builds for
+//!   `wasm32-unknown-unknown` with `--no-default-features --features governance`
+//! - `kms` - cloud KMS signing backend (implies `node`)
+//! - `ffi` - C ABI for signing and verification (the `ffi` module); the build
+//!   generates the `bllvm.h` header with cbindgen
+//! - `python` - `blvm_sdk` Python extension module (the `python` module),
+//!   built with maturin
+//!
 //! ## Quick Start
 //!
 //! ```rust
 //! use bllvm_sdk::{
 //!     GovernanceKeypair, GovernanceMessage, Multisig, sign_message
 //! };
+//! use std::collections::HashMap;
 //!
 //! # fn main() -> Result<(), Box<dyn std::error::Error>> {
 //! // Generate a keypair
@@ -42,6 +55,7 @@
 //! let message = GovernanceMessage::Release {
 //!     version: "v1.0.0".to_string(),
 //!     commit_hash: "abc123".to_string(),
+//!     artifact_hashes: HashMap::new(),
 //! };
 //!
 //! // Sign the message
@@ -56,27 +70,38 @@
 //! # }
 //! ```
 
+#[cfg(feature = "node")]
 pub mod cli;
+#[cfg(feature = "node")]
 pub mod composition;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod governance;
+#[cfg(feature = "node")]
 pub mod module;
+#[cfg(feature = "python")]
+pub mod python;
 
 // Re-export main types for convenience
 pub use governance::{
-    GovernanceError, GovernanceKeypair, GovernanceMessage, GovernanceResult, Multisig, PublicKey,
-    Signature,
+    GovernanceError, GovernanceKeypair, GovernanceMessage, GovernanceResult, Multisig, NetworkType,
+    PublicKey, Signature,
 };
 
 // Re-export governance functions
-pub use governance::signatures::{sign_message, verify_signature};
+pub use governance::signatures::{
+    sign_governance_message, sign_message, verify_governance_message, verify_signature,
+};
 
 // Re-export composition framework
+#[cfg(feature = "node")]
 pub use composition::{
     ComposedNode, ModuleHealth, ModuleInfo, ModuleLifecycle, ModuleRegistry, ModuleSource,
-    ModuleSpec, ModuleStatus, NetworkType, NodeComposer, NodeConfig, NodeSpec,
+    ModuleSpec, ModuleStatus, NodeComposer, NodeConfig, NodeSpec,
 };
 
 // Re-export module development APIs
+#[cfg(feature = "node")]
 pub use module::{
     CorrelationId,
     EventMessage,
@@ -89,6 +114,7 @@ pub use module::{
     ModuleError,
     // IPC
     ModuleIpcClient,
+    ModuleIpcClientConfig,
     // Manifest
     ModuleManifest,
     // IPC Protocol
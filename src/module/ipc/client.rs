@@ -1,8 +1,269 @@
 //! IPC Client
 //!
-//! Re-export from bllvm-node.
-//!
 //! Client-side IPC implementation that modules use to communicate with the node.
+//!
+//! Messages are framed with a 4-byte big-endian length prefix. The
+//! bllvm-node client is re-exported as `NodeModuleIpcClient`.
+//!
+//! Connects, reads, and writes are bounded by `ModuleIpcClientConfig` so a
+//! stalled peer surfaces as an "IPC timeout" error instead of blocking.
+//!
+//! Every connection starts with a protocol version handshake; a node on a
+//! different major version is rejected before any message is sent.
 
 #[cfg(unix)]
-pub use blvm_node::module::ipc::ModuleIpcClient;
+pub use blvm_node::module::ipc::ModuleIpcClient as NodeModuleIpcClient;
+
+use crate::composition::types::{CompositionError, Result};
+use crate::governance::GovernanceKeypair;
+use crate::module::ipc::protocol::{
+    encode_signed_message, sign_ipc_message, HandshakeMessage, ProtocolVersion,
+    CURRENT_PROTOCOL_VERSION,
+};
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Message sent by `ModuleIpcClient::ping`
+const PING_MESSAGE: &[u8] = br#"{"type":"ping"}"#;
+
+/// Timeouts and size limits for a module IPC connection
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleIpcClientConfig {
+    /// Maximum time to wait for the node socket to accept a connection
+    pub connect_timeout: Duration,
+    /// Maximum time to wait for data from the node
+    pub read_timeout: Duration,
+    /// Maximum time to wait for the node to accept data
+    pub write_timeout: Duration,
+    /// Largest frame (excluding the length prefix) sent or accepted, in bytes
+    pub max_message_size: usize,
+}
+
+impl Default for ModuleIpcClientConfig {
+    fn default() -> Self {
+        Self {
+            connect_timeout: Duration::from_secs(5),
+            read_timeout: Duration::from_secs(30),
+            write_timeout: Duration::from_secs(30),
+            max_message_size: 16 * 1024 * 1024,
+        }
+    }
+}
+
+/// IPC client for a module's connection to the node socket
+#[derive(Debug, Clone)]
+pub struct ModuleIpcClient {
+    socket_path: PathBuf,
+    config: ModuleIpcClientConfig,
+    module_name: String,
+    negotiated_version: Arc<Mutex<Option<ProtocolVersion>>>,
+}
+
+impl ModuleIpcClient {
+    /// Create a client for the node socket at `socket_path`
+    pub fn new<P: AsRef<Path>>(socket_path: P) -> Self {
+        Self {
+            socket_path: socket_path.as_ref().to_path_buf(),
+            config: ModuleIpcClientConfig::default(),
+            module_name: String::new(),
+            negotiated_version: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Use custom timeouts and size limits
+    pub fn with_config(mut self, config: ModuleIpcClientConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Name sent to the node in the connection handshake
+    pub fn with_module_name(mut self, module_name: impl Into<String>) -> Self {
+        self.module_name = module_name.into();
+        self
+    }
+
+    /// Path of the node socket
+    pub fn socket_path(&self) -> &Path {
+        &self.socket_path
+    }
+
+    /// Connection timeouts and size limits
+    pub fn config(&self) -> &ModuleIpcClientConfig {
+        &self.config
+    }
+
+    /// Protocol version agreed with the node on the last connection
+    ///
+    /// `None` until a handshake has completed.
+    pub fn negotiated_version(&self) -> Option<ProtocolVersion> {
+        *self
+            .negotiated_version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Send a message signed with the module's identity key
+    ///
+    /// The message is serialized to JSON, signed with `sign_message`, and
+    /// sent with an `X-Module-Signature` header.
+    pub fn send_signed<T: Serialize>(
+        &self,
+        message: T,
+        signing_key: &GovernanceKeypair,
+    ) -> Result<()> {
+        let body = serde_json::to_vec(&message)
+            .map_err(|e| CompositionError::SerializationError(e.to_string()))?;
+        let signature = sign_ipc_message(&body, signing_key)
+            .map_err(|e| CompositionError::SerializationError(e.to_string()))?;
+        let message = encode_signed_message(&body, &signature);
+        self.check_message_size(message.len())?;
+        let mut stream = self.connect()?;
+        self.write_frame(&mut stream, &message)
+    }
+
+    /// Send a ping and wait for the node's reply
+    ///
+    /// Returns the round-trip time, including connection setup. Used by
+    /// health checks.
+    pub fn ping(&self) -> Result<Duration> {
+        let start = std::time::Instant::now();
+        let mut stream = self.connect()?;
+        self.write_frame(&mut stream, PING_MESSAGE)?;
+        self.read_frame(&mut stream)?;
+        Ok(start.elapsed())
+    }
+
+    /// Connect to the node socket and negotiate the protocol version
+    #[cfg(unix)]
+    fn connect(&self) -> Result<std::os::unix::net::UnixStream> {
+        let mut stream = self.open_stream()?;
+        self.handshake(&mut stream)?;
+        Ok(stream)
+    }
+
+    /// Send `Hello` and check the node's `HelloAck`
+    ///
+    /// Fails if the node speaks a different major version or accepts a
+    /// version this client does not support.
+    #[cfg(unix)]
+    fn handshake<S: std::io::Read + std::io::Write>(&self, stream: &mut S) -> Result<()> {
+        let hello = HandshakeMessage::Hello {
+            client_version: CURRENT_PROTOCOL_VERSION,
+            module_name: self.module_name.clone(),
+        };
+        let hello = serde_json::to_vec(&hello)
+            .map_err(|e| CompositionError::SerializationError(e.to_string()))?;
+        self.write_frame(stream, &hello)?;
+
+        let reply = self.read_frame(stream)?;
+        let (server_version, accepted_version) = match serde_json::from_slice(&reply) {
+            Ok(HandshakeMessage::HelloAck {
+                server_version,
+                accepted_version,
+            }) => (server_version, accepted_version),
+            _ => {
+                return Err(CompositionError::InstallationFailed(
+                    "Expected hello_ack from node".to_string(),
+                ))
+            }
+        };
+        if server_version.major != CURRENT_PROTOCOL_VERSION.major
+            || accepted_version.major != CURRENT_PROTOCOL_VERSION.major
+            || accepted_version > CURRENT_PROTOCOL_VERSION
+        {
+            return Err(CompositionError::InstallationFailed(format!(
+                "incompatible protocol version (client {}, server {})",
+                CURRENT_PROTOCOL_VERSION, server_version
+            )));
+        }
+
+        *self
+            .negotiated_version
+            .lock()
+            .unwrap_or_else(|e| e.into_inner()) = Some(accepted_version);
+        Ok(())
+    }
+
+    /// Open the node socket, applying the configured timeouts
+    #[cfg(unix)]
+    fn open_stream(&self) -> Result<std::os::unix::net::UnixStream> {
+        use std::os::unix::net::UnixStream;
+        use std::sync::mpsc;
+
+        // UnixStream has no connect_timeout; connect on a helper thread so a
+        // full accept backlog cannot block the caller
+        let (sender, receiver) = mpsc::channel();
+        let socket_path = self.socket_path.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(UnixStream::connect(socket_path));
+        });
+        let stream = match receiver.recv_timeout(self.config.connect_timeout) {
+            Ok(stream) => stream.map_err(map_io_error)?,
+            Err(_) => return Err(ipc_timeout()),
+        };
+        stream.set_read_timeout(Some(self.config.read_timeout))?;
+        stream.set_write_timeout(Some(self.config.write_timeout))?;
+        Ok(stream)
+    }
+
+    /// Module IPC uses Unix domain sockets
+    #[cfg(not(unix))]
+    fn connect(&self) -> Result<std::io::Empty> {
+        Err(CompositionError::InstallationFailed(
+            "Module IPC requires Unix domain sockets".to_string(),
+        ))
+    }
+
+    /// Reject frames over the configured size limit
+    fn check_message_size(&self, len: usize) -> Result<u32> {
+        u32::try_from(len)
+            .ok()
+            .filter(|_| len <= self.config.max_message_size)
+            .ok_or_else(|| message_too_large(len, self.config.max_message_size))
+    }
+
+    /// Write one length-prefixed frame
+    fn write_frame<W: std::io::Write>(&self, stream: &mut W, frame: &[u8]) -> Result<()> {
+        let len = self.check_message_size(frame.len())?;
+        stream.write_all(&len.to_be_bytes()).map_err(map_io_error)?;
+        stream.write_all(frame).map_err(map_io_error)?;
+        stream.flush().map_err(map_io_error)
+    }
+
+    /// Read one length-prefixed frame
+    ///
+    /// Oversized frames are rejected from the length prefix alone, before
+    /// any of the payload is read.
+    fn read_frame<R: std::io::Read>(&self, stream: &mut R) -> Result<Vec<u8>> {
+        let mut len = [0u8; 4];
+        stream.read_exact(&mut len).map_err(map_io_error)?;
+        let len = u32::from_be_bytes(len) as usize;
+        if len > self.config.max_message_size {
+            return Err(message_too_large(len, self.config.max_message_size));
+        }
+        let mut frame = vec![0u8; len];
+        stream.read_exact(&mut frame).map_err(map_io_error)?;
+        Ok(frame)
+    }
+}
+
+fn ipc_timeout() -> CompositionError {
+    CompositionError::InstallationFailed("IPC timeout".to_string())
+}
+
+fn message_too_large(len: usize, max: usize) -> CompositionError {
+    CompositionError::InstallationFailed(format!(
+        "IPC message too large ({} bytes, limit {})",
+        len, max
+    ))
+}
+
+/// Map socket timeouts to "IPC timeout"; other I/O errors pass through
+fn map_io_error(e: std::io::Error) -> CompositionError {
+    match e.kind() {
+        std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut => ipc_timeout(),
+        _ => CompositionError::IoError(e),
+    }
+}
@@ -9,5 +9,5 @@
 pub mod client;
 pub mod protocol;
 
-pub use client::ModuleIpcClient;
+pub use client::{ModuleIpcClient, ModuleIpcClientConfig};
 pub use protocol::*;
@@ -3,6 +3,205 @@
 //! Re-export from bllvm-node.
 //!
 //! Defines the message types and serialization for IPC communication
-//! between modules and the base node.
+//! between modules and the base node, plus module message signing.
+//!
+//! Signed messages carry a header line ahead of the JSON body:
+//!
+//! ```text
+//! X-Module-Signature: <hex>
+//! {"...": "..."}
+//! ```
+//!
+//! Each connection opens with a version handshake: the module sends
+//! `hello` with its [`ProtocolVersion`] and the node answers `hello_ack`.
+//! Peers must share a major version; the lower minor version is used.
 
 pub use blvm_node::module::ipc::protocol::*;
+
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::{sign_message, verify_signature, Signature};
+use crate::governance::{GovernanceKeypair, PublicKey};
+use crate::module::manifest::ModuleManifest;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// Header carrying the module's signature over the message body
+pub const MODULE_SIGNATURE_HEADER: &str = "X-Module-Signature";
+
+/// IPC protocol version spoken by this SDK
+///
+/// 1.0 is the baseline protocol; 1.1 adds the connection handshake.
+pub const CURRENT_PROTOCOL_VERSION: ProtocolVersion = ProtocolVersion { major: 1, minor: 1 };
+
+/// IPC protocol version
+///
+/// Major versions are incompatible with each other. Minor versions add
+/// features without breaking peers on the same major version.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct ProtocolVersion {
+    /// Incremented for breaking changes
+    pub major: u32,
+    /// Incremented for backward-compatible additions
+    pub minor: u32,
+}
+
+impl ProtocolVersion {
+    /// Create a protocol version
+    pub const fn new(major: u32, minor: u32) -> Self {
+        Self { major, minor }
+    }
+
+    /// Version both peers can speak, or `None` if the major versions differ
+    pub fn negotiate(self, other: ProtocolVersion) -> Option<ProtocolVersion> {
+        (self.major == other.major).then(|| self.min(other))
+    }
+}
+
+impl fmt::Display for ProtocolVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)
+    }
+}
+
+/// Messages exchanged when a module connects to the node
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HandshakeMessage {
+    /// Sent by the module as the first frame on a connection
+    Hello {
+        /// Highest protocol version the module speaks
+        client_version: ProtocolVersion,
+        /// Name of the connecting module
+        module_name: String,
+    },
+    /// Node reply to `Hello`
+    HelloAck {
+        /// Highest protocol version the node speaks
+        server_version: ProtocolVersion,
+        /// Version used for the rest of the connection
+        accepted_version: ProtocolVersion,
+    },
+}
+
+/// Sign raw IPC message bytes, returning the hex signature
+pub fn sign_ipc_message(
+    raw_message: &[u8],
+    signing_key: &GovernanceKeypair,
+) -> GovernanceResult<String> {
+    Ok(sign_message(&signing_key.secret_key, raw_message)?.to_string())
+}
+
+/// Verify a module's signature over raw IPC message bytes
+pub fn verify_ipc_signature(
+    raw_message: &[u8],
+    signature_hex: &str,
+    expected_pubkey: &PublicKey,
+) -> GovernanceResult<bool> {
+    let bytes = hex::decode(signature_hex.trim()).map_err(|e| {
+        GovernanceError::InvalidSignatureFormat(format!("Invalid signature hex: {}", e))
+    })?;
+    let signature = Signature::from_bytes(&bytes)?;
+    verify_signature(&signature, raw_message, expected_pubkey)
+}
+
+/// Prepend the signature header to a message body
+pub fn encode_signed_message(body: &[u8], signature_hex: &str) -> Vec<u8> {
+    let header = format!("{}: {}\n", MODULE_SIGNATURE_HEADER, signature_hex);
+    let mut message = Vec::with_capacity(header.len() + body.len());
+    message.extend_from_slice(header.as_bytes());
+    message.extend_from_slice(body);
+    message
+}
+
+/// Split a signed message into its signature (hex) and body
+pub fn decode_signed_message(message: &[u8]) -> GovernanceResult<(&str, &[u8])> {
+    let missing =
+        || GovernanceError::MessageFormat(format!("Missing {} header", MODULE_SIGNATURE_HEADER));
+    let newline = message
+        .iter()
+        .position(|&b| b == b'\n')
+        .ok_or_else(missing)?;
+    let header = std::str::from_utf8(&message[..newline]).map_err(|_| missing())?;
+    let signature = header
+        .strip_prefix(MODULE_SIGNATURE_HEADER)
+        .and_then(|rest| rest.strip_prefix(':'))
+        .ok_or_else(missing)?;
+    Ok((signature.trim(), &message[newline + 1..]))
+}
+
+/// Verify a signed message against the module's declared identity key
+///
+/// Returns the message body only if the signature is valid. Modules
+/// without a `signing_public_key` in their manifest are rejected.
+pub fn open_signed_message<'a>(
+    message: &'a [u8],
+    manifest: &ModuleManifest,
+) -> GovernanceResult<&'a [u8]> {
+    let public_key = manifest.identity_key()?.ok_or_else(|| {
+        GovernanceError::InvalidKey(format!(
+            "Module '{}' does not declare a signing_public_key",
+            manifest.name
+        ))
+    })?;
+    let (signature, body) = decode_signed_message(message)?;
+    if !verify_ipc_signature(body, signature, &public_key)? {
+        return Err(GovernanceError::SignatureVerification(format!(
+            "IPC message signature from module '{}' is invalid",
+            manifest.name
+        )));
+    }
+    Ok(body)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_signed_message_roundtrip() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let body = br#"{"type":"get_block","height":1}"#;
+
+        let signature = sign_ipc_message(body, &keypair).unwrap();
+        assert!(verify_ipc_signature(body, &signature, &keypair.public_key()).unwrap());
+        assert!(!verify_ipc_signature(b"{}", &signature, &keypair.public_key()).unwrap());
+
+        let message = encode_signed_message(body, &signature);
+        assert!(message.starts_with(b"X-Module-Signature: "));
+        let (decoded_signature, decoded_body) = decode_signed_message(&message).unwrap();
+        assert_eq!(decoded_signature, signature);
+        assert_eq!(decoded_body, body);
+
+        assert!(decode_signed_message(body).is_err());
+        assert!(verify_ipc_signature(body, "zz", &keypair.public_key()).is_err());
+    }
+
+    #[test]
+    fn test_protocol_version_negotiation() {
+        let baseline = ProtocolVersion::new(1, 0);
+        assert_eq!(CURRENT_PROTOCOL_VERSION.negotiate(baseline), Some(baseline));
+        assert_eq!(baseline.negotiate(CURRENT_PROTOCOL_VERSION), Some(baseline));
+        assert_eq!(
+            CURRENT_PROTOCOL_VERSION.negotiate(ProtocolVersion::new(2, 0)),
+            None
+        );
+        assert_eq!(CURRENT_PROTOCOL_VERSION.to_string(), "1.1");
+    }
+
+    #[test]
+    fn test_handshake_message_json() {
+        let hello = HandshakeMessage::Hello {
+            client_version: CURRENT_PROTOCOL_VERSION,
+            module_name: "indexer".to_string(),
+        };
+        let json = serde_json::to_string(&hello).unwrap();
+        assert_eq!(
+            json,
+            r#"{"type":"hello","client_version":{"major":1,"minor":1},"module_name":"indexer"}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<HandshakeMessage>(&json).unwrap(),
+            hello
+        );
+    }
+}
@@ -1,7 +1,375 @@
 //! Module Manifest
 //!
-//! Re-export from bllvm-node.
+//! Module manifest parsing for module.toml files.
 //!
-//! Module manifest parsing and validation for module.toml files.
+//! Mirrors the bllvm-node manifest and extends it with SDK fields such as the
+//! module's identity key. Manifests parse from the same module.toml files.
+//!
+//! A manifest can pin its binary by SHA256 and carry a signature over its own
+//! contents. The signature covers the canonical JSON form of the manifest
+//! (sorted keys, `manifest_signature` omitted), so it also covers the hash.
+
+use crate::composition::types::{CompositionError, Result};
+use crate::governance::error::{GovernanceError, GovernanceResult};
+use crate::governance::signatures::{sign_message, verify_signature, Signature};
+use crate::governance::{GovernanceKeypair, PublicKey};
+use crate::module::security::permissions::{Permission, PermissionSet};
+use blvm_node::module::registry::manifest::ModuleManifest as RefModuleManifest;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+/// Module manifest file name
+pub const MANIFEST_FILE: &str = "module.toml";
+
+/// Module manifest (module.toml)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ModuleManifest {
+    /// Module name
+    pub name: String,
+    /// Module version
+    pub version: String,
+    /// Human-readable description
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Module author
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+    /// Capabilities the module provides
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+    /// Required dependencies (name -> version requirement)
+    ///
+    /// A dependency names either a module or a capability in another
+    /// module's `provides`.
+    #[serde(default)]
+    pub dependencies: HashMap<String, String>,
+    /// Dependencies used when installed but not required
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub optional_dependencies: HashMap<String, String>,
+    /// Abstract capabilities other modules can depend on (e.g. `bitcoin-p2p-v1`)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub provides: Vec<String>,
+    /// Binary entry point, relative to the module directory
+    pub entry_point: String,
+    /// Configuration schema (key -> description)
+    #[serde(default)]
+    pub config_schema: HashMap<String, String>,
+    /// Module identity key (compressed public key, hex) used to sign IPC messages
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signing_public_key: Option<String>,
+    /// Requested permissions (e.g. `network:outbound`, `disk:read:/data`)
+    #[serde(default)]
+    pub permissions: Vec<String>,
+    /// Networks the module supports (`mainnet`, `testnet`, `regtest`); empty means all
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub networks: Vec<String>,
+    /// SHA256 of the module binary (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub binary_sha256: Option<String>,
+    /// Signature over the manifest contents (hex)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub manifest_signature: Option<String>,
+}
+
+impl ModuleManifest {
+    /// Parse and validate a manifest from TOML
+    pub fn from_toml_str(s: &str) -> Result<Self> {
+        let manifest: Self = toml::from_str(s).map_err(|e| {
+            CompositionError::InvalidConfiguration(format!("Invalid module manifest: {}", e))
+        })?;
+        manifest.validate()?;
+        Ok(manifest)
+    }
+
+    /// Load a manifest from a module.toml file
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<Self> {
+        Self::from_toml_str(&std::fs::read_to_string(path)?)
+    }
+
+    /// Locate the manifest in a checkout or unpacked archive
+    ///
+    /// A `module.toml` at `root` wins; otherwise exactly one immediate
+    /// subdirectory must contain one.
+    pub fn locate(root: &Path) -> Result<PathBuf> {
+        let top = root.join(MANIFEST_FILE);
+        if top.is_file() {
+            return Ok(top);
+        }
+
+        let mut found: Vec<PathBuf> = std::fs::read_dir(root)?
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.is_dir() && !path.ends_with(".git"))
+            .map(|dir| dir.join(MANIFEST_FILE))
+            .filter(|manifest| manifest.is_file())
+            .collect();
+        found.sort();
+        match found.len() {
+            1 => Ok(found.remove(0)),
+            0 => Err(CompositionError::InstallationFailed(format!(
+                "No {} found in {:?}",
+                MANIFEST_FILE, root
+            ))),
+            _ => Err(CompositionError::InstallationFailed(format!(
+                "{:?} contains several modules: {:?}",
+                root,
+                found
+                    .iter()
+                    .filter_map(|m| m.parent()?.strip_prefix(root).ok())
+                    .collect::<Vec<_>>()
+            ))),
+        }
+    }
+
+    /// Parse the module's identity key, if one is declared
+    pub fn identity_key(&self) -> GovernanceResult<Option<PublicKey>> {
+        let Some(key_hex) = &self.signing_public_key else {
+            return Ok(None);
+        };
+        let bytes = hex::decode(key_hex).map_err(|e| {
+            GovernanceError::InvalidKey(format!("Invalid signing_public_key hex: {}", e))
+        })?;
+        PublicKey::from_bytes(&bytes).map(Some)
+    }
+
+    /// Parse the module's requested permissions
+    pub fn permission_set(&self) -> Result<PermissionSet> {
+        self.permissions
+            .iter()
+            .map(|p| p.parse::<Permission>())
+            .collect()
+    }
+
+    /// Whether the module supports `network`
+    pub fn supports_network(&self, network: &str) -> bool {
+        self.networks.is_empty() || self.networks.iter().any(|n| n == network)
+    }
+
+    /// Validate required fields, permissions, and the identity key
+    pub fn validate(&self) -> Result<()> {
+        for (field, value) in [
+            ("name", &self.name),
+            ("version", &self.version),
+            ("entry_point", &self.entry_point),
+        ] {
+            if value.trim().is_empty() {
+                return Err(CompositionError::InvalidConfiguration(format!(
+                    "Module manifest is missing '{}'",
+                    field
+                )));
+            }
+        }
+        self.permission_set()?;
+        self.identity_key()
+            .map_err(|e| CompositionError::InvalidConfiguration(e.to_string()))?;
+        Ok(())
+    }
+
+    /// Verify a module binary against the manifest
+    ///
+    /// Checks that the file name matches `entry_point`, that the contents
+    /// match `binary_sha256` (if set), and on Unix that the file is
+    /// executable.
+    pub fn validate_binary(&self, binary_path: &Path) -> GovernanceResult<()> {
+        let expected_name = Path::new(&self.entry_point).file_name();
+        if expected_name.is_none() || binary_path.file_name() != expected_name {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Binary {} does not match entry point '{}'",
+                binary_path.display(),
+                self.entry_point
+            )));
+        }
+
+        if let Some(expected) = &self.binary_sha256 {
+            let actual = hash_binary(binary_path)?;
+            if !actual.eq_ignore_ascii_case(expected.trim()) {
+                return Err(GovernanceError::Cryptographic(format!(
+                    "Binary hash mismatch for module '{}': expected {}, got {}",
+                    self.name, expected, actual
+                )));
+            }
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+
+            let mode = std::fs::metadata(binary_path)
+                .map_err(|e| {
+                    GovernanceError::InvalidInput(format!(
+                        "Failed to read {}: {}",
+                        binary_path.display(),
+                        e
+                    ))
+                })?
+                .permissions()
+                .mode();
+            if mode & 0o111 == 0 {
+                return Err(GovernanceError::InvalidInput(format!(
+                    "Binary {} is not executable",
+                    binary_path.display()
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Pin the binary's SHA256 and sign the manifest
+    pub fn sign_binary(
+        &mut self,
+        binary_path: &Path,
+        signing_key: &GovernanceKeypair,
+    ) -> GovernanceResult<()> {
+        self.binary_sha256 = Some(hash_binary(binary_path)?);
+        let signature = sign_message(&signing_key.secret_key, &self.signing_bytes()?)?;
+        self.manifest_signature = Some(hex::encode(signature.to_bytes()));
+        Ok(())
+    }
+
+    /// Verify `manifest_signature` against `pubkey`
+    pub fn verify_signature(&self, pubkey: &PublicKey) -> GovernanceResult<bool> {
+        let signature_hex = self.manifest_signature.as_deref().ok_or_else(|| {
+            GovernanceError::InvalidSignatureFormat(format!(
+                "Module '{}' manifest is not signed",
+                self.name
+            ))
+        })?;
+        let bytes = hex::decode(signature_hex.trim()).map_err(|e| {
+            GovernanceError::InvalidSignatureFormat(format!(
+                "Invalid manifest_signature hex: {}",
+                e
+            ))
+        })?;
+        let signature = Signature::from_bytes(&bytes)?;
+        verify_signature(&signature, &self.signing_bytes()?, pubkey)
+    }
+
+    /// Canonical bytes covered by the manifest signature
+    fn signing_bytes(&self) -> GovernanceResult<Vec<u8>> {
+        let unsigned = ModuleManifest {
+            manifest_signature: None,
+            ..self.clone()
+        };
+        // Round-trip through Value so map keys serialize in sorted order
+        serde_json::to_value(&unsigned)
+            .and_then(|value| serde_json::to_vec(&value))
+            .map_err(|e| GovernanceError::Serialization(e.to_string()))
+    }
+}
+
+/// SHA256 of a file (hex)
+fn hash_binary(path: &Path) -> GovernanceResult<String> {
+    let contents = std::fs::read(path).map_err(|e| {
+        GovernanceError::InvalidInput(format!("Failed to read {}: {}", path.display(), e))
+    })?;
+    Ok(hex::encode(Sha256::digest(contents)))
+}
+
+impl From<&RefModuleManifest> for ModuleManifest {
+    fn from(manifest: &RefModuleManifest) -> Self {
+        ModuleManifest {
+            name: manifest.name.clone(),
+            version: manifest.version.clone(),
+            description: manifest.description.clone(),
+            author: manifest.author.clone(),
+            capabilities: manifest.capabilities.clone(),
+            dependencies: manifest.dependencies.clone(),
+            optional_dependencies: HashMap::new(),
+            provides: Vec::new(),
+            entry_point: manifest.entry_point.clone(),
+            config_schema: manifest.config_schema.clone(),
+            signing_public_key: None,
+            permissions: Vec::new(),
+            networks: Vec::new(),
+            binary_sha256: None,
+            manifest_signature: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::governance::GovernanceKeypair;
+
+    #[test]
+    fn test_manifest_signing_public_key() {
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let toml = format!(
+            "name = \"lightning\"\nversion = \"1.0.0\"\nentry_point = \"lightning\"\nsigning_public_key = \"{}\"\n",
+            keypair.public_key()
+        );
+        let manifest = ModuleManifest::from_toml_str(&toml).unwrap();
+        assert_eq!(manifest.identity_key().unwrap(), Some(keypair.public_key()));
+
+        let unsigned = ModuleManifest::from_toml_str(
+            "name = \"rpc\"\nversion = \"1.0.0\"\nentry_point = \"rpc\"\n",
+        )
+        .unwrap();
+        assert_eq!(unsigned.identity_key().unwrap(), None);
+    }
+
+    #[test]
+    fn test_manifest_permissions() {
+        let manifest = ModuleManifest::from_toml_str(
+            "name = \"indexer\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\npermissions = [\"network:outbound\", \"disk:rw:/data/indexer\"]\n",
+        )
+        .unwrap();
+        let permissions = manifest.permission_set().unwrap();
+        assert!(permissions.check(&"disk:write:/data/indexer/db".parse().unwrap()));
+        assert!(!permissions.check(&"network:inbound".parse().unwrap()));
+
+        assert!(ModuleManifest::from_toml_str(
+            "name = \"indexer\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\npermissions = [\"disk:delete:/\"]\n",
+        )
+        .is_err());
+        assert!(ModuleManifest::from_toml_str(
+            "name = \"\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\n",
+        )
+        .is_err());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_manifest_sign_and_validate_binary() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let binary = dir.path().join("indexer");
+        std::fs::write(&binary, b"binary").unwrap();
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let keypair = GovernanceKeypair::generate().unwrap();
+        let mut manifest = ModuleManifest::from_toml_str(
+            "name = \"indexer\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\n",
+        )
+        .unwrap();
+        manifest.sign_binary(&binary, &keypair).unwrap();
+        assert_eq!(
+            manifest.binary_sha256.as_deref(),
+            Some(hex::encode(Sha256::digest(b"binary")).as_str())
+        );
+        manifest.validate_binary(&binary).unwrap();
+        assert!(manifest.verify_signature(&keypair.public_key()).unwrap());
+
+        let other = GovernanceKeypair::generate().unwrap();
+        assert!(!manifest.verify_signature(&other.public_key()).unwrap());
+
+        // Any manifest change invalidates the signature
+        let mut tampered = manifest.clone();
+        tampered.version = "1.0.1".to_string();
+        assert!(!tampered.verify_signature(&keypair.public_key()).unwrap());
+
+        let renamed = dir.path().join("other");
+        std::fs::copy(&binary, &renamed).unwrap();
+        assert!(manifest.validate_binary(&renamed).is_err());
+
+        std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o644)).unwrap();
+        assert!(manifest.validate_binary(&binary).is_err());
 
-pub use blvm_node::module::registry::manifest::ModuleManifest;
+        std::fs::write(&binary, b"changed").unwrap();
+        assert!(manifest.validate_binary(&binary).is_err());
+    }
+}
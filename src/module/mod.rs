@@ -11,8 +11,8 @@ pub mod security;
 pub mod traits;
 
 // Re-export main types for convenience
-pub use ipc::client::ModuleIpcClient;
+pub use ipc::client::{ModuleIpcClient, ModuleIpcClientConfig};
 pub use ipc::protocol::*;
 pub use manifest::ModuleManifest;
-pub use security::{Permission, PermissionSet};
+pub use security::{DiskPermission, IpcPermission, NetworkPermission, Permission, PermissionSet};
 pub use traits::*;
@@ -7,4 +7,6 @@
 
 pub mod permissions;
 
-pub use permissions::{Permission, PermissionSet};
+pub use permissions::{
+    DiskPermission, IpcPermission, NetworkPermission, Permission, PermissionSet,
+};
@@ -1,7 +1,300 @@
 //! Module Permissions
 //!
-//! Re-export from bllvm-node.
-//!
 //! Permission types and sets for module access control.
+//!
+//! Permissions are written as colon-separated strings in module manifests:
+//!
+//! - `network:inbound`, `network:outbound`, `network` (both directions)
+//! - `disk:read:/data`, `disk:write:/data`, `disk:rw:/data`
+//! - `ipc:connect:module-name`, `ipc:connect:*` (any module)
+//!
+//! The bllvm-node permission types are re-exported as `NodePermission` and
+//! `NodePermissionSet`.
+
+pub use blvm_node::module::security::permissions::{
+    Permission as NodePermission, PermissionSet as NodePermissionSet,
+};
+
+use crate::composition::types::CompositionError;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Network access direction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum NetworkPermission {
+    /// Accept inbound connections
+    Inbound,
+    /// Open outbound connections
+    Outbound,
+    /// Both directions
+    All,
+}
+
+/// Disk access below a path
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DiskPermission {
+    /// Directory or file the permission applies to (and everything below it)
+    pub path: PathBuf,
+    /// Read access
+    pub read: bool,
+    /// Write access
+    pub write: bool,
+}
+
+/// IPC access to another module
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct IpcPermission {
+    /// Target module name (`*` for any module)
+    pub target: String,
+}
+
+/// A single module permission
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Permission {
+    /// Network access
+    Network(NetworkPermission),
+    /// Disk access
+    Disk(DiskPermission),
+    /// IPC access
+    Ipc(IpcPermission),
+}
+
+impl Permission {
+    /// Check if this permission grants `requested`
+    pub fn covers(&self, requested: &Permission) -> bool {
+        match (self, requested) {
+            (Permission::Network(granted), Permission::Network(requested)) => {
+                granted == requested || *granted == NetworkPermission::All
+            }
+            (Permission::Disk(granted), Permission::Disk(requested)) => {
+                requested.path.starts_with(&granted.path)
+                    && (granted.read || !requested.read)
+                    && (granted.write || !requested.write)
+            }
+            (Permission::Ipc(granted), Permission::Ipc(requested)) => {
+                granted.target == "*" || granted.target == requested.target
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for Permission {
+    type Err = CompositionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = |reason: &str| {
+            CompositionError::InvalidConfiguration(format!(
+                "Invalid permission '{}': {}",
+                s, reason
+            ))
+        };
+
+        let mut parts = s.trim().splitn(3, ':');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("network"), None, None) | (Some("network"), Some("all"), None) => {
+                Ok(Permission::Network(NetworkPermission::All))
+            }
+            (Some("network"), Some("inbound"), None) => {
+                Ok(Permission::Network(NetworkPermission::Inbound))
+            }
+            (Some("network"), Some("outbound"), None) => {
+                Ok(Permission::Network(NetworkPermission::Outbound))
+            }
+            (Some("network"), _, _) => Err(invalid("expected network[:inbound|outbound|all]")),
+            (Some("disk"), Some(mode), Some(path)) if !path.is_empty() => {
+                let (read, write) = match mode {
+                    "read" => (true, false),
+                    "write" => (false, true),
+                    "rw" => (true, true),
+                    _ => return Err(invalid("disk mode must be read, write, or rw")),
+                };
+                Ok(Permission::Disk(DiskPermission {
+                    path: PathBuf::from(path),
+                    read,
+                    write,
+                }))
+            }
+            (Some("disk"), _, _) => Err(invalid("expected disk:<read|write|rw>:<path>")),
+            (Some("ipc"), Some("connect"), Some(target)) if !target.is_empty() => {
+                Ok(Permission::Ipc(IpcPermission {
+                    target: target.to_string(),
+                }))
+            }
+            (Some("ipc"), _, _) => Err(invalid("expected ipc:connect:<module>")),
+            _ => Err(invalid("unknown permission kind")),
+        }
+    }
+}
+
+impl fmt::Display for Permission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Permission::Network(NetworkPermission::Inbound) => write!(f, "network:inbound"),
+            Permission::Network(NetworkPermission::Outbound) => write!(f, "network:outbound"),
+            Permission::Network(NetworkPermission::All) => write!(f, "network"),
+            Permission::Disk(disk) => {
+                let mode = match (disk.read, disk.write) {
+                    (true, true) => "rw",
+                    (false, true) => "write",
+                    _ => "read",
+                };
+                write!(f, "disk:{}:{}", mode, disk.path.display())
+            }
+            Permission::Ipc(ipc) => write!(f, "ipc:connect:{}", ipc.target),
+        }
+    }
+}
+
+/// A set of granted permissions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PermissionSet {
+    permissions: BTreeSet<Permission>,
+}
+
+impl PermissionSet {
+    /// Create an empty permission set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Grant a permission
+    pub fn add(&mut self, permission: Permission) {
+        self.permissions.insert(permission);
+    }
+
+    /// Check if any granted permission covers `requested`
+    pub fn check(&self, requested: &Permission) -> bool {
+        self.permissions
+            .iter()
+            .any(|granted| granted.covers(requested))
+    }
+
+    /// Permissions granted by either set
+    pub fn union(&self, other: &PermissionSet) -> PermissionSet {
+        self.permissions
+            .union(&other.permissions)
+            .cloned()
+            .collect()
+    }
+
+    /// Permissions granted by both sets
+    ///
+    /// Keeps each permission from either set that the other set also
+    /// grants, so `disk:rw:/data` and `disk:read:/data/blocks` intersect to
+    /// `disk:read:/data/blocks`.
+    pub fn intersection(&self, other: &PermissionSet) -> PermissionSet {
+        self.permissions
+            .iter()
+            .filter(|permission| other.check(permission))
+            .chain(
+                other
+                    .permissions
+                    .iter()
+                    .filter(|permission| self.check(permission)),
+            )
+            .cloned()
+            .collect()
+    }
+
+    /// Iterate over the granted permissions
+    pub fn iter(&self) -> impl Iterator<Item = &Permission> {
+        self.permissions.iter()
+    }
+
+    /// Number of granted permissions
+    pub fn len(&self) -> usize {
+        self.permissions.len()
+    }
+
+    /// Check if no permissions are granted
+    pub fn is_empty(&self) -> bool {
+        self.permissions.is_empty()
+    }
+}
+
+impl FromIterator<Permission> for PermissionSet {
+    fn from_iter<I: IntoIterator<Item = Permission>>(iter: I) -> Self {
+        Self {
+            permissions: iter.into_iter().collect(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set(permissions: &[&str]) -> PermissionSet {
+        permissions.iter().map(|p| p.parse().unwrap()).collect()
+    }
+
+    #[test]
+    fn test_permission_parse_roundtrip() {
+        for s in [
+            "network",
+            "network:inbound",
+            "network:outbound",
+            "disk:read:/data",
+            "disk:rw:/var/lib/node",
+            "ipc:connect:lightning",
+        ] {
+            let permission: Permission = s.parse().unwrap();
+            assert_eq!(permission.to_string(), s);
+        }
+
+        assert_eq!(
+            "disk:write:/data".parse::<Permission>().unwrap(),
+            Permission::Disk(DiskPermission {
+                path: PathBuf::from("/data"),
+                read: false,
+                write: true,
+            })
+        );
+        for s in [
+            "",
+            "network:sideways",
+            "disk:read",
+            "disk:exec:/data",
+            "ipc:connect:",
+            "gpu",
+        ] {
+            assert!(s.parse::<Permission>().is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_permission_set_check() {
+        let granted = set(&["network:outbound", "disk:read:/data", "ipc:connect:*"]);
+
+        assert!(granted.check(&"network:outbound".parse().unwrap()));
+        assert!(!granted.check(&"network:inbound".parse().unwrap()));
+        assert!(granted.check(&"disk:read:/data/blocks".parse().unwrap()));
+        assert!(!granted.check(&"disk:write:/data/blocks".parse().unwrap()));
+        // Path prefixes match whole components only
+        assert!(!granted.check(&"disk:read:/database".parse().unwrap()));
+        assert!(granted.check(&"ipc:connect:rpc".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_permission_set_union_intersection() {
+        let a = set(&["network", "disk:rw:/data"]);
+        let b = set(&[
+            "network:inbound",
+            "disk:read:/data/blocks",
+            "ipc:connect:rpc",
+        ]);
+
+        let union = a.union(&b);
+        assert_eq!(union.len(), 5);
+        assert!(union.check(&"ipc:connect:rpc".parse().unwrap()));
 
-pub use blvm_node::module::security::permissions::{Permission, PermissionSet};
+        assert_eq!(
+            a.intersection(&b),
+            set(&["network:inbound", "disk:read:/data/blocks"])
+        );
+        assert_eq!(a.intersection(&PermissionSet::new()), PermissionSet::new());
+    }
+}
@@ -0,0 +1,784 @@
+//! # Python Bindings
+//!
+//! `pyo3` classes over the governance primitives, so release automation can
+//! sign and verify without driving the CLIs through `subprocess`.
+//!
+//! The extension module is named `blvm_sdk` and is built with maturin (see
+//! `pyproject.toml`):
+//!
+//! ```text
+//! maturin develop --release
+//! pytest tests/python
+//! ```
+//!
+//! Every error raises a subclass of `blvm_sdk.GovernanceError`, one per
+//! [`ErrorCategory`]. Secret keys stay inside Rust: `Keypair` and
+//! `Bip44Wallet` never print or pickle them, `Keypair.secret_bytes()` is the
+//! only way to read one out, and `wipe()` erases the key so that any later
+//! use raises `KeyManagementError`.
+
+use std::collections::HashMap;
+
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+use crate::governance::bip39::{
+    generate_mnemonic as bip39_generate_mnemonic, mnemonic_to_seed as bip39_mnemonic_to_seed,
+    validate_mnemonic as bip39_validate_mnemonic, EntropyStrength,
+};
+use crate::governance::bip44::{Bip44Wallet, CoinType};
+use crate::governance::error::{ErrorCategory, GovernanceError};
+use crate::governance::psbt::PartiallySignedTransaction;
+use crate::governance::signatures::sign_governance_message;
+use crate::governance::ur::DEFAULT_MAX_FRAGMENT_LEN;
+use crate::governance::{
+    BudgetAllocation, GovernanceKeypair, GovernanceMessage, Multisig, NetworkType, PublicKey,
+    SecurityRating, Signature, SignatureEnvelope, SigningRequest,
+};
+
+/// Python exception hierarchy
+mod exceptions {
+    use pyo3::create_exception;
+    use pyo3::exceptions::PyException;
+
+    create_exception!(
+        blvm_sdk,
+        GovernanceError,
+        PyException,
+        "Base class of SDK errors"
+    );
+    create_exception!(
+        blvm_sdk,
+        KeyManagementError,
+        GovernanceError,
+        "Key parsing, derivation, or use of a wiped key failed"
+    );
+    create_exception!(
+        blvm_sdk,
+        SignatureError,
+        GovernanceError,
+        "Signing, verification or signature parsing failed"
+    );
+    create_exception!(
+        blvm_sdk,
+        MultisigError,
+        GovernanceError,
+        "Invalid multisig configuration or too few signatures"
+    );
+    create_exception!(
+        blvm_sdk,
+        MessageFormatError,
+        GovernanceError,
+        "Malformed governance message or encoding"
+    );
+    create_exception!(
+        blvm_sdk,
+        InvalidInputError,
+        GovernanceError,
+        "Other malformed input"
+    );
+}
+
+impl From<GovernanceError> for PyErr {
+    fn from(error: GovernanceError) -> Self {
+        let message = error.to_string();
+        match error.category() {
+            ErrorCategory::KeyManagement => exceptions::KeyManagementError::new_err(message),
+            ErrorCategory::SignatureOperations => exceptions::SignatureError::new_err(message),
+            ErrorCategory::MultisigLogic => exceptions::MultisigError::new_err(message),
+            ErrorCategory::MessageFormat => exceptions::MessageFormatError::new_err(message),
+            ErrorCategory::InvalidInput => exceptions::InvalidInputError::new_err(message),
+        }
+    }
+}
+
+/// Error for any use of a wiped secret
+fn wiped_error(kind: &str) -> PyErr {
+    exceptions::KeyManagementError::new_err(format!("{} has been wiped", kind))
+}
+
+/// Parse a network name (mainnet, testnet, regtest)
+fn parse_network(network: &str) -> PyResult<NetworkType> {
+    network.parse::<NetworkType>().map_err(Into::into)
+}
+
+/// Split a mnemonic phrase into words
+fn mnemonic_words(mnemonic: &str) -> Vec<String> {
+    mnemonic.split_whitespace().map(str::to_string).collect()
+}
+
+/// A compressed secp256k1 public key
+#[pyclass(name = "PublicKey", module = "blvm_sdk", frozen, eq, hash)]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct PyPublicKey {
+    inner: PublicKey,
+}
+
+#[pymethods]
+impl PyPublicKey {
+    /// Parse a hex public key
+    #[new]
+    fn new(hex: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: hex.parse()?,
+        })
+    }
+
+    /// Parse a 33- or 65-byte SEC1 public key
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self {
+            inner: PublicKey::from_bytes(data)?,
+        })
+    }
+
+    /// Compressed (33-byte) encoding
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_compressed_bytes())
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("PublicKey('{}')", self.inner)
+    }
+}
+
+/// A compact ECDSA signature
+#[pyclass(name = "Signature", module = "blvm_sdk", frozen, eq)]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PySignature {
+    inner: Signature,
+}
+
+#[pymethods]
+impl PySignature {
+    /// Parse a hex DER or compact signature
+    #[new]
+    fn new(hex: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: hex.parse()?,
+        })
+    }
+
+    /// Parse a 64-byte compact signature
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self {
+            inner: Signature::from_bytes(data)?,
+        })
+    }
+
+    /// Compact (64-byte) encoding
+    fn to_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_bytes())
+    }
+
+    /// DER encoding
+    fn to_der<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_der_bytes())
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Signature('{}')", self.inner)
+    }
+}
+
+/// A governance signing keypair
+///
+/// The secret key is erased by `wipe()` or when the object is collected.
+#[pyclass(name = "Keypair", module = "blvm_sdk")]
+pub struct PyKeypair {
+    inner: Option<GovernanceKeypair>,
+}
+
+impl PyKeypair {
+    fn keypair(&self) -> PyResult<&GovernanceKeypair> {
+        self.inner.as_ref().ok_or_else(|| wiped_error("Keypair"))
+    }
+}
+
+impl From<GovernanceKeypair> for PyKeypair {
+    fn from(keypair: GovernanceKeypair) -> Self {
+        Self {
+            inner: Some(keypair),
+        }
+    }
+}
+
+impl Drop for PyKeypair {
+    fn drop(&mut self) {
+        self.wipe();
+    }
+}
+
+#[pymethods]
+impl PyKeypair {
+    /// Generate a random keypair
+    #[staticmethod]
+    fn generate() -> PyResult<Self> {
+        Ok(GovernanceKeypair::generate()?.into())
+    }
+
+    /// Load a keypair from a 32-byte secret key
+    #[staticmethod]
+    fn from_secret_bytes(secret: &[u8]) -> PyResult<Self> {
+        Ok(GovernanceKeypair::from_secret_key(secret)?.into())
+    }
+
+    /// The public half of the keypair
+    #[getter]
+    fn public_key(&self) -> PyResult<PyPublicKey> {
+        Ok(PyPublicKey {
+            inner: self.keypair()?.public_key(),
+        })
+    }
+
+    /// Sign a governance message under the governance signing domain
+    fn sign(&self, message: &PyGovernanceMessage) -> PyResult<PySignature> {
+        let keypair = self.keypair()?;
+        Ok(PySignature {
+            inner: sign_governance_message(&keypair.secret_key, &message.inner)?,
+        })
+    }
+
+    /// Sign a UR signing request, producing the envelope to send back
+    fn sign_request(&self, request: &PySigningRequest) -> PyResult<PySignatureEnvelope> {
+        let keypair = self.keypair()?;
+        Ok(PySignatureEnvelope {
+            inner: SignatureEnvelope {
+                request_id: request.inner.request_id,
+                signature: sign_governance_message(&keypair.secret_key, &request.inner.message)?,
+                public_key: keypair.public_key(),
+            },
+        })
+    }
+
+    /// The 32-byte secret key; the only way to read it out of the keypair
+    fn secret_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.keypair()?.secret_key_bytes()))
+    }
+
+    /// Erase the secret key; later use raises `KeyManagementError`
+    fn wipe(&mut self) {
+        if let Some(mut keypair) = self.inner.take() {
+            keypair.secret_key.non_secure_erase();
+        }
+    }
+
+    /// Whether `wipe()` has been called
+    #[getter]
+    fn is_wiped(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        match &self.inner {
+            Some(keypair) => format!("Keypair(public_key='{}')", keypair.public_key()),
+            None => "Keypair(<wiped>)".to_string(),
+        }
+    }
+}
+
+/// A governance message (release, module approval or budget decision)
+#[pyclass(name = "GovernanceMessage", module = "blvm_sdk", frozen, eq)]
+#[derive(Clone, PartialEq, Eq)]
+pub struct PyGovernanceMessage {
+    inner: GovernanceMessage,
+}
+
+#[pymethods]
+impl PyGovernanceMessage {
+    /// A release message, optionally with `{artifact name: sha256}` hashes
+    #[staticmethod]
+    #[pyo3(signature = (version, commit_hash, artifacts = None))]
+    fn release(
+        version: String,
+        commit_hash: String,
+        artifacts: Option<HashMap<String, String>>,
+    ) -> PyResult<Self> {
+        let mut message = GovernanceMessage::Release {
+            version,
+            commit_hash,
+            artifact_hashes: HashMap::new(),
+        };
+        for (name, sha256) in artifacts.unwrap_or_default() {
+            message = message.with_artifact(&name, &sha256)?;
+        }
+        Ok(Self { inner: message })
+    }
+
+    /// A module approval message, optionally recording a security audit
+    ///
+    /// The audit fields are given together; `security_rating` is one of
+    /// "critical", "high", "medium", "low" or "minimal".
+    #[staticmethod]
+    #[pyo3(signature = (module_name, version, audit_report_hash = None, security_rating = None, auditor = None))]
+    fn module_approval(
+        module_name: String,
+        version: String,
+        audit_report_hash: Option<String>,
+        security_rating: Option<String>,
+        auditor: Option<String>,
+    ) -> PyResult<Self> {
+        let message = GovernanceMessage::module_approval(module_name, version);
+        let inner = match (audit_report_hash, security_rating, auditor) {
+            (Some(hash), Some(rating), Some(auditor)) => {
+                message.with_audit(&hash, rating.parse::<SecurityRating>()?, &auditor)
+            }
+            (None, None, None) => message,
+            _ => {
+                return Err(GovernanceError::InvalidInput(
+                    "audit_report_hash, security_rating and auditor must be given together"
+                        .to_string(),
+                )
+                .into())
+            }
+        };
+        Ok(Self { inner })
+    }
+
+    /// A budget decision paying each `(recipient, amount_satoshis)` allocation
+    #[staticmethod]
+    fn budget_decision(allocations: Vec<(String, u64)>, rationale: String) -> PyResult<Self> {
+        let allocations = allocations
+            .into_iter()
+            .map(|(recipient, amount_satoshis)| BudgetAllocation {
+                recipient,
+                amount_satoshis,
+                description: String::new(),
+            })
+            .collect();
+        Ok(Self {
+            inner: GovernanceMessage::budget_decision(allocations, rationale)?,
+        })
+    }
+
+    /// A budget decision paying a single recipient
+    #[staticmethod]
+    fn single_budget(recipient: String, amount_satoshis: u64, purpose: String) -> Self {
+        Self {
+            inner: GovernanceMessage::single_budget(recipient, amount_satoshis, purpose),
+        }
+    }
+
+    /// Total satoshis of a budget decision (`None` on overflow or for other messages)
+    fn total_amount(&self) -> Option<u64> {
+        self.inner.total_amount()
+    }
+
+    /// Parse the JSON form written by `to_json` and the CLIs
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        let inner = serde_json::from_str(json)
+            .map_err(|e| GovernanceError::MessageFormat(format!("Invalid message JSON: {}", e)))?;
+        Ok(Self { inner })
+    }
+
+    /// JSON form of the message
+    fn to_json(&self) -> PyResult<String> {
+        Ok(serde_json::to_string(&self.inner)
+            .map_err(|e| GovernanceError::Serialization(e.to_string()))?)
+    }
+
+    /// Bytes a maintainer signs, including the governance signing domain
+    fn signing_bytes<'py>(&self, py: Python<'py>) -> Bound<'py, PyBytes> {
+        PyBytes::new(py, &self.inner.to_signing_bytes())
+    }
+
+    /// Human-readable description
+    fn description(&self) -> String {
+        self.inner.description()
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("GovernanceMessage({})", self.inner.description())
+    }
+}
+
+/// A threshold roster of governance keys
+#[pyclass(name = "Multisig", module = "blvm_sdk", frozen)]
+pub struct PyMultisig {
+    inner: Multisig,
+}
+
+#[pymethods]
+impl PyMultisig {
+    /// Create a `threshold`-of-`len(public_keys)` roster
+    #[new]
+    fn new(threshold: usize, public_keys: Vec<PyPublicKey>) -> PyResult<Self> {
+        let total = public_keys.len();
+        let keys = public_keys.into_iter().map(|key| key.inner).collect();
+        Ok(Self {
+            inner: Multisig::new(threshold, total, keys)?,
+        })
+    }
+
+    /// Whether at least `threshold` distinct roster keys signed `message`
+    ///
+    /// Raises `MultisigError` when fewer than `threshold` signatures are given.
+    fn verify(
+        &self,
+        message: &PyGovernanceMessage,
+        signatures: Vec<PySignature>,
+    ) -> PyResult<bool> {
+        let signatures: Vec<_> = signatures.into_iter().map(|sig| sig.inner).collect();
+        Ok(self
+            .inner
+            .verify(&message.inner.to_signing_bytes(), &signatures)?)
+    }
+
+    #[getter]
+    fn threshold(&self) -> usize {
+        self.inner.threshold()
+    }
+
+    #[getter]
+    fn total(&self) -> usize {
+        self.inner.total()
+    }
+
+    #[getter]
+    fn public_keys(&self) -> Vec<PyPublicKey> {
+        self.inner
+            .public_keys()
+            .iter()
+            .map(|key| PyPublicKey { inner: key.clone() })
+            .collect()
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Multisig({}-of-{})",
+            self.inner.threshold(),
+            self.inner.total()
+        )
+    }
+}
+
+/// A governance message waiting to be signed on an air-gapped machine
+#[pyclass(name = "SigningRequest", module = "blvm_sdk", frozen)]
+pub struct PySigningRequest {
+    inner: SigningRequest,
+}
+
+#[pymethods]
+impl PySigningRequest {
+    /// Create a request with a fresh request ID
+    #[new]
+    fn new(message: &PyGovernanceMessage) -> Self {
+        Self {
+            inner: SigningRequest::new(message.inner.clone()),
+        }
+    }
+
+    /// Decode a request from `ur:bllvm-request` parts
+    #[staticmethod]
+    fn from_ur(parts: Vec<String>) -> PyResult<Self> {
+        Ok(Self {
+            inner: SigningRequest::from_ur_parts(parts)?,
+        })
+    }
+
+    /// Encode as `ur:bllvm-request` parts
+    #[pyo3(signature = (max_fragment_len = DEFAULT_MAX_FRAGMENT_LEN))]
+    fn to_ur(&self, max_fragment_len: usize) -> PyResult<Vec<String>> {
+        Ok(self.inner.to_ur_parts(max_fragment_len)?)
+    }
+
+    #[getter]
+    fn request_id(&self) -> String {
+        self.inner.request_id.to_string()
+    }
+
+    #[getter]
+    fn message(&self) -> PyGovernanceMessage {
+        PyGovernanceMessage {
+            inner: self.inner.message.clone(),
+        }
+    }
+}
+
+/// A signature returned for a `SigningRequest`
+#[pyclass(name = "SignatureEnvelope", module = "blvm_sdk", frozen)]
+pub struct PySignatureEnvelope {
+    inner: SignatureEnvelope,
+}
+
+#[pymethods]
+impl PySignatureEnvelope {
+    /// Decode an envelope from `ur:bllvm-signature` parts
+    #[staticmethod]
+    fn from_ur(parts: Vec<String>) -> PyResult<Self> {
+        Ok(Self {
+            inner: SignatureEnvelope::from_ur_parts(parts)?,
+        })
+    }
+
+    /// Encode as `ur:bllvm-signature` parts
+    #[pyo3(signature = (max_fragment_len = DEFAULT_MAX_FRAGMENT_LEN))]
+    fn to_ur(&self, max_fragment_len: usize) -> PyResult<Vec<String>> {
+        Ok(self.inner.to_ur_parts(max_fragment_len)?)
+    }
+
+    /// Whether the envelope answers `request` with a valid signature
+    fn verify(&self, request: &PySigningRequest) -> PyResult<bool> {
+        Ok(self.inner.verify(&request.inner)?)
+    }
+
+    #[getter]
+    fn request_id(&self) -> String {
+        self.inner.request_id.to_string()
+    }
+
+    #[getter]
+    fn signature(&self) -> PySignature {
+        PySignature {
+            inner: self.inner.signature.clone(),
+        }
+    }
+
+    #[getter]
+    fn public_key(&self) -> PyPublicKey {
+        PyPublicKey {
+            inner: self.inner.public_key.clone(),
+        }
+    }
+}
+
+/// A BIP174 partially signed transaction
+#[pyclass(name = "Psbt", module = "blvm_sdk")]
+pub struct PyPsbt {
+    inner: PartiallySignedTransaction,
+}
+
+#[pymethods]
+impl PyPsbt {
+    /// Parse a Base64 or hex PSBT
+    #[staticmethod]
+    fn parse(encoded: &str) -> PyResult<Self> {
+        Ok(Self {
+            inner: PartiallySignedTransaction::auto_decode(encoded)?,
+        })
+    }
+
+    /// Parse a binary PSBT
+    #[staticmethod]
+    fn from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self {
+            inner: PartiallySignedTransaction::deserialize(data)?,
+        })
+    }
+
+    /// Binary encoding
+    fn to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new(py, &self.inner.serialize()?))
+    }
+
+    /// Base64 encoding
+    fn to_base64(&self) -> PyResult<String> {
+        Ok(self.inner.to_base64()?)
+    }
+
+    #[getter]
+    fn input_count(&self) -> usize {
+        self.inner.inputs.len()
+    }
+
+    #[getter]
+    fn output_count(&self) -> usize {
+        self.inner.outputs.len()
+    }
+
+    /// Whether every input has a final scriptSig or witness
+    fn is_finalized(&self) -> bool {
+        self.inner.is_finalized()
+    }
+
+    /// Fee in satoshis; needs the UTXO of every input
+    fn fee(&self) -> PyResult<u64> {
+        Ok(self.inner.fee()?)
+    }
+
+    /// Structural problems found by validation, one string per issue
+    fn issues(&self) -> Vec<String> {
+        self.inner
+            .validate()
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Sign every input the keypair can spend; returns the signed indices
+    fn sign(&mut self, keypair: &PyKeypair) -> PyResult<Vec<usize>> {
+        let summary = self.inner.sign_with_key(&keypair.keypair()?.secret_key)?;
+        Ok(summary.signed)
+    }
+
+    fn __str__(&self) -> String {
+        self.inner.to_string()
+    }
+}
+
+/// A BIP44 wallet derived from a BIP39 mnemonic
+///
+/// Holds the master private key; erase it with `wipe()`.
+#[pyclass(name = "Bip44Wallet", module = "blvm_sdk", unsendable)]
+pub struct PyBip44Wallet {
+    inner: Option<Bip44Wallet>,
+}
+
+impl PyBip44Wallet {
+    fn wallet(&self) -> PyResult<&Bip44Wallet> {
+        self.inner.as_ref().ok_or_else(|| wiped_error("Wallet"))
+    }
+}
+
+#[pymethods]
+impl PyBip44Wallet {
+    /// Derive a wallet from a BIP39 mnemonic and optional passphrase
+    #[staticmethod]
+    #[pyo3(signature = (mnemonic, passphrase = "", coin_type = 0))]
+    fn from_mnemonic(mnemonic: &str, passphrase: &str, coin_type: u32) -> PyResult<Self> {
+        let words = mnemonic_words(mnemonic);
+        bip39_validate_mnemonic(&words)?;
+        let seed = bip39_mnemonic_to_seed(&words, passphrase);
+        Ok(Self {
+            inner: Some(Bip44Wallet::from_seed(
+                &seed,
+                CoinType::from_value(coin_type)?,
+            )?),
+        })
+    }
+
+    /// Account xpub (mainnet) or tpub (testnet, regtest) string
+    #[pyo3(signature = (account, network = "mainnet"))]
+    fn account_xpub(&self, account: u32, network: &str) -> PyResult<String> {
+        Ok(self
+            .wallet()?
+            .account_xpub_string(account, parse_network(network)?)?)
+    }
+
+    /// Governance keypair at non-hardened `path_suffix` below an account
+    fn signing_keypair(&self, account: u32, path_suffix: Vec<u32>) -> PyResult<PyKeypair> {
+        Ok(self
+            .wallet()?
+            .signing_keypair(account, &path_suffix)?
+            .into())
+    }
+
+    /// Drop the master key; later use raises `KeyManagementError`
+    fn wipe(&mut self) {
+        self.inner = None;
+    }
+
+    /// Whether `wipe()` has been called
+    #[getter]
+    fn is_wiped(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    fn __repr__(&self) -> String {
+        if self.inner.is_some() {
+            "Bip44Wallet(<secret>)".to_string()
+        } else {
+            "Bip44Wallet(<wiped>)".to_string()
+        }
+    }
+}
+
+/// Generate a random BIP39 mnemonic of 12, 15, 18, 21 or 24 words
+#[pyfunction]
+#[pyo3(signature = (words = 24))]
+fn generate_mnemonic(words: usize) -> PyResult<String> {
+    let strength = match words {
+        12 => EntropyStrength::Bits128,
+        15 => EntropyStrength::Bits160,
+        18 => EntropyStrength::Bits192,
+        21 => EntropyStrength::Bits224,
+        24 => EntropyStrength::Bits256,
+        _ => {
+            return Err(GovernanceError::InvalidInput(format!(
+                "Mnemonic must have 12, 15, 18, 21 or 24 words, got {}",
+                words
+            ))
+            .into())
+        }
+    };
+    Ok(bip39_generate_mnemonic(strength)?.join(" "))
+}
+
+/// Check a mnemonic's words and checksum
+#[pyfunction]
+fn validate_mnemonic(mnemonic: &str) -> PyResult<()> {
+    Ok(bip39_validate_mnemonic(&mnemonic_words(mnemonic))?)
+}
+
+/// 64-byte BIP39 seed for a mnemonic and optional passphrase
+#[pyfunction]
+#[pyo3(signature = (mnemonic, passphrase = ""))]
+fn mnemonic_to_seed<'py>(
+    py: Python<'py>,
+    mnemonic: &str,
+    passphrase: &str,
+) -> PyResult<Bound<'py, PyBytes>> {
+    let words = mnemonic_words(mnemonic);
+    bip39_validate_mnemonic(&words)?;
+    Ok(PyBytes::new(
+        py,
+        &bip39_mnemonic_to_seed(&words, passphrase),
+    ))
+}
+
+/// The `blvm_sdk` extension module
+#[pymodule]
+#[pyo3(name = "blvm_sdk")]
+fn python_module(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    let py = m.py();
+    m.add(
+        "GovernanceError",
+        py.get_type::<exceptions::GovernanceError>(),
+    )?;
+    m.add(
+        "KeyManagementError",
+        py.get_type::<exceptions::KeyManagementError>(),
+    )?;
+    m.add(
+        "SignatureError",
+        py.get_type::<exceptions::SignatureError>(),
+    )?;
+    m.add("MultisigError", py.get_type::<exceptions::MultisigError>())?;
+    m.add(
+        "MessageFormatError",
+        py.get_type::<exceptions::MessageFormatError>(),
+    )?;
+    m.add(
+        "InvalidInputError",
+        py.get_type::<exceptions::InvalidInputError>(),
+    )?;
+
+    m.add_class::<PyPublicKey>()?;
+    m.add_class::<PySignature>()?;
+    m.add_class::<PyKeypair>()?;
+    m.add_class::<PyGovernanceMessage>()?;
+    m.add_class::<PyMultisig>()?;
+    m.add_class::<PySigningRequest>()?;
+    m.add_class::<PySignatureEnvelope>()?;
+    m.add_class::<PyPsbt>()?;
+    m.add_class::<PyBip44Wallet>()?;
+
+    m.add_function(wrap_pyfunction!(generate_mnemonic, m)?)?;
+    m.add_function(wrap_pyfunction!(validate_mnemonic, m)?)?;
+    m.add_function(wrap_pyfunction!(mnemonic_to_seed, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,107 @@
+//! Base58 and Base58Check Tests
+//!
+//! Encode/decode vectors from Bitcoin Core's base58_encode_decode.json.
+
+use blvm_sdk::governance::base58::{decode, decode_check, encode, encode_check};
+use blvm_sdk::governance::error::GovernanceError;
+
+/// (hex bytes, Base58 string)
+const VECTORS: &[(&str, &str)] = &[
+    ("", ""),
+    ("61", "2g"),
+    ("626262", "a3gV"),
+    ("636363", "aPEr"),
+    (
+        "73696d706c792061206c6f6e6720737472696e67",
+        "2cFupjhnEsSn59qHXstmK2ffpLv2",
+    ),
+    (
+        "00eb15231dfceb60925886b67d065299925915aeb172c06647",
+        "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L",
+    ),
+    ("516b6fcd0f", "ABnLTmg"),
+    ("bf4f89001e670274dd", "3SEo3LWLoPntC"),
+    ("572e4794", "3EFU7m"),
+    ("ecac89cad93923c02321", "EJDM8drfXA6uyA"),
+    ("10c8511e", "Rt5zm"),
+    ("00000000000000000000", "1111111111"),
+    (
+        "000111d38e5fc9071ffcd20b4a763cc9ae4f252bb4e48fd66a835e252ada93ff480d6dd43dc62a641155a5",
+        "123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz",
+    ),
+];
+
+#[test]
+fn test_encode_decode_vectors() {
+    for (hex_bytes, base58) in VECTORS {
+        let bytes = hex::decode(hex_bytes).unwrap();
+        assert_eq!(encode(&bytes), *base58, "{}", hex_bytes);
+        assert_eq!(decode(base58).unwrap(), bytes, "{}", base58);
+    }
+}
+
+#[test]
+fn test_leading_zeros_map_to_ones() {
+    assert_eq!(encode(&[0]), "1");
+    assert_eq!(encode(&[0, 0, 0]), "111");
+    assert_eq!(encode(&[0, 0, 1]), "112");
+    assert_eq!(decode("111").unwrap(), vec![0, 0, 0]);
+    assert_eq!(decode("112").unwrap(), vec![0, 0, 1]);
+}
+
+#[test]
+fn test_invalid_character_position() {
+    for (s, character, position) in [("0", '0', 0), ("3SEl3LWLoPntC", 'l', 3)] {
+        match decode(s) {
+            Err(GovernanceError::InvalidBase58Character {
+                character: c,
+                position: p,
+            }) => assert_eq!((c, p), (character, position), "{}", s),
+            other => panic!("{}: expected invalid character, got {:?}", s, other),
+        }
+    }
+    assert!(matches!(
+        decode("3EFU7mI"),
+        Err(GovernanceError::InvalidBase58Character { position: 6, .. })
+    ));
+    assert!(matches!(
+        decode("2gé"),
+        Err(GovernanceError::InvalidBase58Character {
+            character: 'é',
+            position: 2,
+        })
+    ));
+}
+
+#[test]
+fn test_check_matches_rust_bitcoin() {
+    for (hex_bytes, _) in VECTORS {
+        let payload = hex::decode(hex_bytes).unwrap();
+        let encoded = encode_check(&payload);
+        assert_eq!(encoded, bitcoin::base58::encode_check(&payload));
+        assert_eq!(decode_check(&encoded).unwrap(), payload);
+    }
+}
+
+#[test]
+fn test_check_detects_corruption() {
+    // Genesis block coinbase address
+    let address = "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa";
+    let payload = decode_check(address).unwrap();
+    assert_eq!(payload.len(), 21);
+    assert_eq!(payload[0], 0x00);
+
+    let corrupted = address.replace("fNa", "fNb");
+    assert!(matches!(
+        decode_check(&corrupted),
+        Err(GovernanceError::ChecksumMismatch(_))
+    ));
+    assert!(matches!(
+        decode_check("1111"),
+        Err(GovernanceError::ChecksumMismatch(_))
+    ));
+    assert!(matches!(
+        decode_check("2g"),
+        Err(GovernanceError::InvalidInput(_))
+    ));
+}
@@ -0,0 +1,252 @@
+//! Bech32 and Bech32m Tests
+//!
+//! Test vectors from the BIP173 and BIP350 specifications.
+//! Specifications:
+//! - https://github.com/bitcoin/bips/blob/master/bip-0173.mediawiki
+//! - https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+
+use blvm_sdk::governance::bech32::{
+    convert_bits, decode, decode_segwit_address, encode, encode_segwit_address, Variant,
+};
+
+const VALID_BECH32: &[&str] = &[
+    "A12UEL5L",
+    "a12uel5l",
+    "an83characterlonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1tt5tgs",
+    "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+    "11qqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqqc8247j",
+    "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+    "?1ezyfcl",
+];
+
+const VALID_BECH32M: &[&str] = &[
+    "A1LQFN3A",
+    "a1lqfn3a",
+    "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+    "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+    "11llllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllllludsr8",
+    "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+    "?1v759aa",
+];
+
+const INVALID_BECH32: &[&str] = &[
+    "\u{20}1nwldj5",
+    "\u{7f}1axkwrx",
+    "\u{80}1eym55h",
+    "an84characterslonghumanreadablepartthatcontainsthenumber1andtheexcludedcharactersbio1569pvx",
+    "pzry9x0s0muk",
+    "1pzry9x0s0muk",
+    "x1b4n0q5v",
+    "li1dgmt3",
+    "de1lg7wt\u{ff}",
+    "A1G7SGD8",
+    "10a06t8",
+    "1qzzfhee",
+];
+
+const INVALID_BECH32M: &[&str] = &[
+    "\u{20}1xj0phk",
+    "\u{7f}1g6xzxy",
+    "\u{80}1vctc34",
+    "an84characterslonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11d6pts4",
+    "qyrz8wqd2c9m",
+    "1qyrz8wqd2c9m",
+    "y1b0jsk6g",
+    "lt1igcx5c0",
+    "in1muywd",
+    "mm1crxm3i",
+    "au1s5cgom",
+    "M1VUXWEZ",
+    "16plkw9",
+    "1p2gdwpf",
+];
+
+/// (address, scriptPubKey hex)
+const VALID_ADDRESSES: &[(&str, &str)] = &[
+    (
+        "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+        "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+    ),
+    (
+        "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+        "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+    ),
+    (
+        "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+        "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+    ),
+    ("BC1SW50QGDZ25J", "6002751e"),
+    (
+        "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+        "5210751e76e8199196d454941c45d1b3a323",
+    ),
+    (
+        "tb1qqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesrxh6hy",
+        "0020000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+    ),
+    (
+        "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+        "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+    ),
+    (
+        "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+        "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    ),
+];
+
+const INVALID_ADDRESSES: &[&str] = &[
+    // Invalid human-readable part
+    "tc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq5zuyut",
+    // Bech32 checksum on a version 1+ program
+    "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+    "tb1z0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqglt7rf",
+    "BC1S0XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ54WELL",
+    // Bech32m checksum on a version 0 program
+    "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+    "tb1q0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq24jc47",
+    // Invalid character in checksum
+    "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+    // Invalid witness version
+    "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+    // Invalid program lengths
+    "bc1pw5dgrnzv",
+    "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+    "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+    // Mixed case
+    "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+    // More than 4 padding bits
+    "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+    // Non-zero padding
+    "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+    // Empty data section
+    "bc1gmk9yu",
+];
+
+/// Decode a segwit address for either mainnet or testnet
+fn decode_any_network(address: &str) -> Option<(&'static str, u8, Vec<u8>)> {
+    ["bc", "tb"].into_iter().find_map(|hrp| {
+        decode_segwit_address(hrp, address)
+            .ok()
+            .map(|(version, program)| (hrp, version, program))
+    })
+}
+
+/// scriptPubKey for a witness version and program
+fn segwit_script_pubkey(version: u8, program: &[u8]) -> Vec<u8> {
+    let opcode = if version == 0 { 0 } else { 0x50 + version };
+    let mut script = vec![opcode, program.len() as u8];
+    script.extend_from_slice(program);
+    script
+}
+
+#[test]
+fn test_bip173_valid_checksums() {
+    for s in VALID_BECH32 {
+        let (hrp, data, variant) = decode(s).unwrap_or_else(|e| panic!("{}: {}", s, e));
+        assert_eq!(variant, Variant::Bech32, "{}", s);
+        assert_eq!(encode(&hrp, &data, variant).unwrap(), s.to_lowercase());
+    }
+}
+
+#[test]
+fn test_bip350_valid_checksums() {
+    for s in VALID_BECH32M {
+        let (hrp, data, variant) = decode(s).unwrap_or_else(|e| panic!("{}: {}", s, e));
+        assert_eq!(variant, Variant::Bech32m, "{}", s);
+        assert_eq!(encode(&hrp, &data, variant).unwrap(), s.to_lowercase());
+    }
+}
+
+#[test]
+fn test_invalid_checksums() {
+    for s in INVALID_BECH32.iter().chain(INVALID_BECH32M) {
+        assert!(decode(s).is_err(), "{:?} should not decode", s);
+    }
+}
+
+#[test]
+fn test_checksum_variant_is_not_interchangeable() {
+    let (hrp, data, _) = decode(VALID_BECH32[1]).unwrap();
+    let bech32m = encode(&hrp, &data, Variant::Bech32m).unwrap();
+    assert_ne!(bech32m, VALID_BECH32[1]);
+    assert_eq!(decode(&bech32m).unwrap().2, Variant::Bech32m);
+}
+
+#[test]
+fn test_valid_segwit_addresses() {
+    for (address, script_hex) in VALID_ADDRESSES {
+        let (hrp, version, program) =
+            decode_any_network(address).unwrap_or_else(|| panic!("{} should decode", address));
+        assert_eq!(
+            hex::encode(segwit_script_pubkey(version, &program)),
+            *script_hex,
+            "{}",
+            address
+        );
+        assert_eq!(
+            encode_segwit_address(hrp, version, &program).unwrap(),
+            address.to_lowercase()
+        );
+    }
+}
+
+#[test]
+fn test_invalid_segwit_addresses() {
+    for address in INVALID_ADDRESSES {
+        assert!(
+            decode_any_network(address).is_none(),
+            "{} should not decode",
+            address
+        );
+    }
+}
+
+#[test]
+fn test_segwit_address_rejects_wrong_network() {
+    let (address, _) = VALID_ADDRESSES[0];
+    assert!(decode_segwit_address("bc", address).is_ok());
+    assert!(decode_segwit_address("tb", address).is_err());
+}
+
+#[test]
+fn test_encode_segwit_address_limits() {
+    assert!(encode_segwit_address("bc", 17, &[0; 32]).is_err());
+    assert!(encode_segwit_address("bc", 0, &[0; 16]).is_err());
+    assert!(encode_segwit_address("bc", 1, &[0; 1]).is_err());
+    assert!(encode_segwit_address("bc", 1, &[0; 41]).is_err());
+    assert!(encode_segwit_address("bc", 16, &[0; 2]).is_ok());
+    assert!(encode_segwit_address("bc", 1, &[0; 40]).is_ok());
+}
+
+#[test]
+fn test_convert_bits_padding() {
+    // 8 bits pad out to two 5-bit groups
+    assert_eq!(convert_bits(&[0xff], 8, 5, true).unwrap(), vec![31, 28]);
+    // Non-zero leftover bits are rejected without padding
+    assert!(convert_bits(&[31, 29], 5, 8, false).is_err());
+    assert_eq!(convert_bits(&[31, 28], 5, 8, false).unwrap(), vec![0xff]);
+    // A whole leftover group is excess padding
+    assert!(convert_bits(&[31, 28, 0], 5, 8, false).is_err());
+    // Values wider than the input size are rejected
+    assert!(convert_bits(&[32], 5, 8, true).is_err());
+}
+
+#[test]
+fn test_matches_rust_bitcoin_addresses() {
+    use bitcoin::{Address, Network};
+    use std::str::FromStr;
+
+    for (address, _) in VALID_ADDRESSES {
+        let Ok(parsed) = Address::from_str(address) else {
+            continue;
+        };
+        let Ok(parsed) = parsed.require_network(Network::Bitcoin) else {
+            continue;
+        };
+        let (hrp, version, program) = decode_any_network(address).unwrap();
+        assert_eq!(
+            encode_segwit_address(hrp, version, &program).unwrap(),
+            parsed.to_string()
+        );
+    }
+}
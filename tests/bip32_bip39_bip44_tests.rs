@@ -10,11 +10,16 @@ use blvm_sdk::governance::bip32::{
     ExtendedPublicKey,
 };
 use blvm_sdk::governance::bip39::{
-    generate_mnemonic, mnemonic_from_entropy, mnemonic_to_entropy, mnemonic_to_seed,
-    validate_mnemonic, EntropyStrength,
+    generate_mnemonic, mnemonic_from_bytes, mnemonic_from_entropy, mnemonic_to_bytes,
+    mnemonic_to_entropy, mnemonic_to_entropy_with_checksum, mnemonic_to_seed, validate_mnemonic,
+    EntropyStrength,
+};
+use blvm_sdk::governance::bip44::{
+    Bip44Path, Bip44Wallet, Bip44WatchOnlyWallet, ChangeChain, CoinType,
 };
-use blvm_sdk::governance::bip44::{Bip44Path, Bip44Wallet, ChangeChain, CoinType};
 use blvm_sdk::governance::error::GovernanceError;
+use blvm_sdk::NetworkType;
+use sha2::{Digest, Sha256};
 
 /// Test helper: Generate a test seed
 fn generate_test_seed() -> Vec<u8> {
@@ -111,6 +116,49 @@ fn test_mnemonic_entropy_roundtrip() {
     assert_eq!(mnemonic.len(), mnemonic2.len());
 }
 
+#[test]
+fn test_mnemonic_bytes_roundtrip() {
+    for strength in [EntropyStrength::Bits128, EntropyStrength::Bits256] {
+        let mnemonic = generate_mnemonic(strength).unwrap();
+        let bytes = mnemonic_to_bytes(&mnemonic).unwrap();
+        // Raw entropy only, without checksum bits
+        assert_eq!(bytes.len(), strength.entropy_bytes());
+        assert_eq!(mnemonic_from_bytes(&bytes).unwrap(), mnemonic);
+    }
+}
+
+#[test]
+fn test_mnemonic_bytes_vectors() {
+    let words = |s: &str| -> Vec<String> { s.split(' ').map(String::from).collect() };
+
+    // BIP39 English vectors: 0x7f x 16 and 0xff x 32
+    let legal =
+        words("legal winner thank year wave sausage worth useful legal winner thank yellow");
+    assert_eq!(mnemonic_to_bytes(&legal).unwrap(), vec![0x7f; 16]);
+    assert_eq!(mnemonic_from_bytes(&[0x7f; 16]).unwrap(), legal);
+
+    let mut zoo = vec!["zoo".to_string(); 23];
+    zoo.push("vote".to_string());
+    assert_eq!(mnemonic_to_bytes(&zoo).unwrap(), vec![0xff; 32]);
+
+    // 12 words: 4 checksum bits in the top of byte 17
+    let with_checksum = mnemonic_to_entropy_with_checksum(&legal).unwrap();
+    assert_eq!(with_checksum.len(), 17);
+    assert_eq!(with_checksum[..16], [0x7f; 16]);
+    assert_eq!(with_checksum[16], Sha256::digest([0x7f; 16])[0] & 0xf0);
+
+    // 24 words: a full checksum byte
+    let with_checksum = mnemonic_to_entropy_with_checksum(&zoo).unwrap();
+    assert_eq!(with_checksum.len(), 33);
+    assert_eq!(with_checksum[..32], [0xff; 32]);
+    assert_eq!(with_checksum[32], Sha256::digest([0xff; 32])[0]);
+
+    // A corrupted checksum is rejected
+    let mut bad = legal.clone();
+    bad[11] = "winner".to_string();
+    assert!(mnemonic_to_bytes(&bad).is_err());
+}
+
 // ============================================================================
 // Phase 2: BIP32 HD Key Derivation Tests
 // ============================================================================
@@ -249,6 +297,28 @@ fn test_derive_different_children() {
     assert_ne!(child0.private_key_bytes(), child2.private_key_bytes());
 }
 
+#[test]
+fn test_xpub_serialization_bip32_vector_1() {
+    // BIP32 test vector 1: m and m/0H
+    let seed = hex::decode("000102030405060708090a0b0c0d0e0f").unwrap();
+    let (master_xprv, master_xpub) = derive_master_key(&seed).unwrap();
+    let (_, child_xpub) = master_xprv.derive_child(0x80000000).unwrap();
+
+    let expected = [
+        (&master_xpub, "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8"),
+        (&child_xpub, "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw"),
+    ];
+    for (xpub, encoded) in expected {
+        let bytes = bitcoin::base58::decode_check(encoded).unwrap();
+        assert_eq!(xpub.to_bytes(NetworkType::Mainnet).to_vec(), bytes);
+        assert_eq!(xpub.to_xpub_string(NetworkType::Mainnet), encoded);
+
+        let parsed = ExtendedPublicKey::from_bytes(&bytes).unwrap();
+        assert_eq!(parsed.public_key_bytes(), xpub.public_key_bytes());
+        assert_eq!(parsed.chain_code, xpub.chain_code);
+    }
+}
+
 // ============================================================================
 // Phase 3: BIP44 Path Tests
 // ============================================================================
@@ -328,6 +398,7 @@ fn test_bip44_coin_type_values() {
     assert_eq!(CoinType::Litecoin.value(), 2);
     assert_eq!(CoinType::Dogecoin.value(), 3);
     assert_eq!(CoinType::Ethereum.value(), 60);
+    assert_eq!(CoinType::Other(501).value(), 501);
 }
 
 #[test]
@@ -337,8 +408,14 @@ fn test_bip44_coin_type_from_value() {
     assert_eq!(CoinType::from_value(1).unwrap(), CoinType::BitcoinTestnet);
     assert_eq!(CoinType::from_value(2).unwrap(), CoinType::Litecoin);
 
-    // Invalid coin type should fail
-    assert!(CoinType::from_value(999).is_err());
+    // Unnamed SLIP-44 coin types are carried as Other
+    assert_eq!(CoinType::from_value(60).unwrap(), CoinType::Ethereum);
+    assert_eq!(CoinType::from_value(999).unwrap(), CoinType::Other(999));
+    assert_eq!(CoinType::Other(999).value(), 999);
+    assert_eq!(CoinType::Other(0), CoinType::Bitcoin);
+
+    // Hardened-range values are not coin types
+    assert!(CoinType::from_value(0x8000_0000).is_err());
 }
 
 // ============================================================================
@@ -421,6 +498,100 @@ fn test_bip44_wallet_sequential_addresses() {
     assert_ne!(key1_pub.public_key_bytes(), key2_pub.public_key_bytes());
 }
 
+#[test]
+fn test_bip44_wallet_cached_derivation_matches_full_path() {
+    let seed = generate_test_seed();
+    let (master, _) = derive_master_key(&seed).unwrap();
+    let wallet = Bip44Wallet::from_seed(&seed, CoinType::Bitcoin).unwrap();
+
+    // Derive twice so the second pass is served from the cache
+    for _ in 0..2 {
+        for account in 0..2 {
+            for change in [ChangeChain::External, ChangeChain::Internal] {
+                let batch = wallet.derive_addresses(account, change, 0..5).unwrap();
+                assert_eq!(batch.len(), 5);
+                for (index, (batch_priv, batch_pub)) in (0..5).zip(&batch) {
+                    let path = Bip44Path::new(CoinType::Bitcoin, account, change, index);
+                    let (expected_priv, expected_pub) = path.derive(&master).unwrap();
+                    let (cached_priv, cached_pub) =
+                        wallet.derive_address(account, change, index).unwrap();
+
+                    let xprv = |key: &ExtendedPrivateKey| key.to_xprv_string(NetworkType::Mainnet);
+                    let xpub = |key: &ExtendedPublicKey| key.to_xpub_string(NetworkType::Mainnet);
+                    assert_eq!(xprv(&cached_priv), xprv(&expected_priv));
+                    assert_eq!(xpub(&cached_pub), xpub(&expected_pub));
+                    assert_eq!(xprv(batch_priv), xprv(&expected_priv));
+                    assert_eq!(xpub(batch_pub), xpub(&expected_pub));
+                }
+            }
+        }
+    }
+
+    assert!(wallet
+        .derive_addresses(0, ChangeChain::External, 3..3)
+        .unwrap()
+        .is_empty());
+}
+
+#[test]
+fn test_bip44_account_xpub_string() {
+    let seed = generate_test_seed();
+    let wallet = Bip44Wallet::from_seed(&seed, CoinType::Bitcoin).unwrap();
+
+    let xpub = wallet.account_xpub_string(2, NetworkType::Mainnet).unwrap();
+    assert!(xpub.starts_with("xpub"));
+    let tpub = wallet.account_xpub_string(2, NetworkType::Testnet).unwrap();
+    assert!(tpub.starts_with("tpub"));
+
+    let parsed = ExtendedPublicKey::from_xpub_string(&xpub).unwrap();
+    assert_eq!(
+        parsed.public_key_bytes(),
+        wallet.account_xpub(2).unwrap().public_key_bytes()
+    );
+}
+
+#[test]
+fn test_bip44_watch_only_wallet_matches_private_wallet() {
+    let seed = generate_test_seed();
+    let wallet = Bip44Wallet::from_seed(&seed, CoinType::Bitcoin).unwrap();
+    let xpub = wallet.account_xpub_string(1, NetworkType::Mainnet).unwrap();
+    let watch_only: Bip44WatchOnlyWallet = Bip44Wallet::from_account_xpub(&xpub).unwrap();
+    assert_eq!(watch_only.account(), 1);
+
+    for change in [ChangeChain::External, ChangeChain::Internal] {
+        for index in 0..3 {
+            let (_, expected) = wallet.derive_address(1, change, index).unwrap();
+            let derived = watch_only.derive_address_pubkey(1, change, index).unwrap();
+            assert_eq!(derived.public_key_bytes(), expected.public_key_bytes());
+            assert_eq!(derived.depth, 5);
+        }
+    }
+    assert_eq!(
+        watch_only.receiving_address(7).unwrap().public_key_bytes(),
+        wallet.receiving_address(1, 7).unwrap().1.public_key_bytes()
+    );
+
+    // Other accounts need hardened derivation
+    assert!(watch_only
+        .derive_address_pubkey(0, ChangeChain::External, 0)
+        .is_err());
+}
+
+#[test]
+fn test_bip44_watch_only_wallet_rejects_non_account_keys() {
+    let seed = generate_test_seed();
+    let (_, master_xpub) = derive_master_key(&seed).unwrap();
+    let master = master_xpub.to_xpub_string(NetworkType::Mainnet);
+    assert!(matches!(
+        Bip44Wallet::from_account_xpub(&master),
+        Err(GovernanceError::InvalidKey(_))
+    ));
+
+    let wallet = Bip44Wallet::from_seed(&seed, CoinType::Bitcoin).unwrap();
+    let (_, address_xpub) = wallet.receiving_address(0, 0).unwrap();
+    assert!(Bip44WatchOnlyWallet::from_account_xpub(address_xpub).is_err());
+}
+
 // ============================================================================
 // Phase 5: End-to-End BIP39 -> BIP32 -> BIP44 Tests
 // ============================================================================
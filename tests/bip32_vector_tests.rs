@@ -0,0 +1,309 @@
+//! BIP32 Test Vectors
+//!
+//! Test vectors 1-5 from the BIP32 specification.
+
+use blvm_sdk::governance::bip32::{
+    derive_child_private, derive_child_private_skipping, derive_master_key, ExtendedPrivateKey,
+    ExtendedPublicKey, HARDENED_OFFSET,
+};
+use blvm_sdk::governance::{GovernanceError, NetworkType};
+
+const H: u32 = HARDENED_OFFSET;
+
+/// (path, xpub, xprv)
+type Chain = &'static [(&'static [u32], &'static str, &'static str)];
+
+const VECTOR_1_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+const VECTOR_1: Chain = &[
+    (
+        &[],
+        "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+    ),
+    (
+        &[H],
+        "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+        "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+    ),
+    (
+        &[H, 1],
+        "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+        "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+    ),
+    (
+        &[H, 1, H + 2],
+        "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+        "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+    ),
+    (
+        &[H, 1, H + 2, 2],
+        "xpub6FHa3pjLCk84BayeJxFW2SP4XRrFd1JYnxeLeU8EqN3vDfZmbqBqaGJAyiLjTAwm6ZLRQUMv1ZACTj37sR62cfN7fe5JnJ7dh8zL4fiyLHV",
+        "xprvA2JDeKCSNNZky6uBCviVfJSKyQ1mDYahRjijr5idH2WwLsEd4Hsb2Tyh8RfQMuPh7f7RtyzTtdrbdqqsunu5Mm3wDvUAKRHSC34sJ7in334",
+    ),
+    (
+        &[H, 1, H + 2, 2, 1000000000],
+        "xpub6H1LXWLaKsWFhvm6RVpEL9P4KfRZSW7abD2ttkWP3SSQvnyA8FSVqNTEcYFgJS2UaFcxupHiYkro49S8yGasTvXEYBVPamhGW6cFJodrTHy",
+        "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+    ),
+];
+
+const VECTOR_2_SEED: &str = "fffcf9f6f3f0edeae7e4e1dedbd8d5d2cfccc9c6c3c0bdbab7b4b1aeaba8a5a29f9c999693908d8a8784817e7b7875726f6c696663605d5a5754514e4b484542";
+const VECTOR_2: Chain = &[
+    (
+        &[],
+        "xpub661MyMwAqRbcFW31YEwpkMuc5THy2PSt5bDMsktWQcFF8syAmRUapSCGu8ED9W6oDMSgv6Zz8idoc4a6mr8BDzTJY47LJhkJ8UB7WEGuduB",
+        "xprv9s21ZrQH143K31xYSDQpPDxsXRTUcvj2iNHm5NUtrGiGG5e2DtALGdso3pGz6ssrdK4PFmM8NSpSBHNqPqm55Qn3LqFtT2emdEXVYsCzC2U",
+    ),
+    (
+        &[0],
+        "xpub69H7F5d8KSRgmmdJg2KhpAK8SR3DjMwAdkxj3ZuxV27CprR9LgpeyGmXUbC6wb7ERfvrnKZjXoUmmDznezpbZb7ap6r1D3tgFxHmwMkQTPH",
+        "xprv9vHkqa6EV4sPZHYqZznhT2NPtPCjKuDKGY38FBWLvgaDx45zo9WQRUT3dKYnjwih2yJD9mkrocEZXo1ex8G81dwSM1fwqWpWkeS3v86pgKt",
+    ),
+    (
+        &[0, H + 2147483647],
+        "xpub6ASAVgeehLbnwdqV6UKMHVzgqAG8Gr6riv3Fxxpj8ksbH9ebxaEyBLZ85ySDhKiLDBrQSARLq1uNRts8RuJiHjaDMBU4Zn9h8LZNnBC5y4a",
+        "xprv9wSp6B7kry3Vj9m1zSnLvN3xH8RdsPP1Mh7fAaR7aRLcQMKTR2vidYEeEg2mUCTAwCd6vnxVrcjfy2kRgVsFawNzmjuHc2YmYRmagcEPdU9",
+    ),
+    (
+        &[0, H + 2147483647, 1],
+        "xpub6DF8uhdarytz3FWdA8TvFSvvAh8dP3283MY7p2V4SeE2wyWmG5mg5EwVvmdMVCQcoNJxGoWaU9DCWh89LojfZ537wTfunKau47EL2dhHKon",
+        "xprv9zFnWC6h2cLgpmSA46vutJzBcfJ8yaJGg8cX1e5StJh45BBciYTRXSd25UEPVuesF9yog62tGAQtHjXajPPdbRCHuWS6T8XA2ECKADdw4Ef",
+    ),
+    (
+        &[0, H + 2147483647, 1, H + 2147483646],
+        "xpub6ERApfZwUNrhLCkDtcHTcxd75RbzS1ed54G1LkBUHQVHQKqhMkhgbmJbZRkrgZw4koxb5JaHWkY4ALHY2grBGRjaDMzQLcgJvLJuZZvRcEL",
+        "xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc",
+    ),
+    (
+        &[0, H + 2147483647, 1, H + 2147483646, 2],
+        "xpub6FnCn6nSzZAw5Tw7cgR9bi15UV96gLZhjDstkXXxvCLsUXBGXPdSnLFbdpq8p9HmGsApME5hQTZ3emM2rnY5agb9rXpVGyy3bdW6EEgAtqt",
+        "xprvA2nrNbFZABcdryreWet9Ea4LvTJcGsqrMzxHx98MMrotbir7yrKCEXw7nadnHM8Dq38EGfSh6dqA9QWTyefMLEcBYJUuekgW4BYPJcr9E7j",
+    ),
+];
+
+/// Retention of leading zeros in the private key (bitpay/bitcore-lib#47)
+const VECTOR_3_SEED: &str = "4b381541583be4423346c643850da4b320e46a87ae3d2a4e6da11eba819cd4acba45d239319ac14f863b8d5ab5a0d0c64d2e8a1e7d1457df2e5a3c51c73235be";
+const VECTOR_3: Chain = &[
+    (
+        &[],
+        "xpub661MyMwAqRbcEZVB4dScxMAdx6d4nFc9nvyvH3v4gJL378CSRZiYmhRoP7mBy6gSPSCYk6SzXPTf3ND1cZAceL7SfJ1Z3GC8vBgp2epUt13",
+        "xprv9s21ZrQH143K25QhxbucbDDuQ4naNntJRi4KUfWT7xo4EKsHt2QJDu7KXp1A3u7Bi1j8ph3EGsZ9Xvz9dGuVrtHHs7pXeTzjuxBrCmmhgC6",
+    ),
+    (
+        &[H],
+        "xpub68NZiKmJWnxxS6aaHmn81bvJeTESw724CRDs6HbuccFQN9Ku14VQrADWgqbhhTHBaohPX4CjNLf9fq9MYo6oDaPPLPxSb7gwQN3ih19Zm4Y",
+        "xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L",
+    ),
+];
+
+/// Retention of leading zeros in hardened derivation (btcsuite/btcutil#172)
+const VECTOR_4_SEED: &str = "3ddd5602285899a946114506157c7997e5444528f3003f6134712147db19b678";
+const VECTOR_4: Chain = &[
+    (
+        &[],
+        "xpub661MyMwAqRbcGczjuMoRm6dXaLDEhW1u34gKenbeYqAix21mdUKJyuyu5F1rzYGVxyL6tmgBUAEPrEz92mBXjByMRiJdba9wpnN37RLLAXa",
+        "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+    ),
+    (
+        &[H],
+        "xpub69AUMk3qDBi3uW1sXgjCmVjJ2G6WQoYSnNHyzkmdCHEhSZ4tBok37xfFEqHd2AddP56Tqp4o56AePAgCjYdvpW2PU2jbUPFKsav5ut6Ch1m",
+        "xprv9vB7xEWwNp9kh1wQRfCCQMnZUEG21LpbR9NPCNN1dwhiZkjjeGRnaALmPXCX7SgjFTiCTT6bXes17boXtjq3xLpcDjzEuGLQBM5ohqkao9G",
+    ),
+    (
+        &[H, H + 1],
+        "xpub6BJA1jSqiukeaesWfxe6sNK9CCGaujFFSJLomWHprUL9DePQ4JDkM5d88n49sMGJxrhpjazuXYWdMf17C9T5XnxkopaeS7jGk1GyyVziaMt",
+        "xprv9xJocDuwtYCMNAo3Zw76WENQeAS6WGXQ55RCy7tDJ8oALr4FWkuVoHJeHVAcAqiZLE7Je3vZJHxspZdFHfnBEjHqU5hG1Jaj32dVoS6XLT1",
+    ),
+];
+
+fn check_chain(seed_hex: &str, chain: Chain) {
+    let seed = hex::decode(seed_hex).unwrap();
+    let (master, _) = derive_master_key(&seed).unwrap();
+
+    for (path, xpub, xprv) in chain {
+        let mut key = master.clone();
+        for &index in path.iter() {
+            key = derive_child_private(&key, index).unwrap().0;
+        }
+
+        assert_eq!(
+            key.to_xprv_string(NetworkType::Mainnet),
+            *xprv,
+            "{:?}",
+            path
+        );
+        assert_eq!(
+            key.to_extended_public()
+                .to_xpub_string(NetworkType::Mainnet),
+            *xpub,
+            "{:?}",
+            path
+        );
+
+        // Both strings round-trip through parsing
+        let parsed = ExtendedPrivateKey::from_xprv_string(xprv).unwrap();
+        assert_eq!(parsed.to_xprv_string(NetworkType::Mainnet), *xprv);
+        let parsed = ExtendedPublicKey::from_xpub_string(xpub).unwrap();
+        assert_eq!(parsed.to_xpub_string(NetworkType::Mainnet), *xpub);
+    }
+}
+
+#[test]
+fn test_vector_1() {
+    check_chain(VECTOR_1_SEED, VECTOR_1);
+}
+
+#[test]
+fn test_vector_2() {
+    check_chain(VECTOR_2_SEED, VECTOR_2);
+}
+
+#[test]
+fn test_vector_3_leading_zero_private_key() {
+    check_chain(VECTOR_3_SEED, VECTOR_3);
+}
+
+#[test]
+fn test_vector_4_leading_zero_hardened_derivation() {
+    check_chain(VECTOR_4_SEED, VECTOR_4);
+}
+
+#[test]
+fn test_vector_1_identifiers_and_fingerprints() {
+    let identifiers = [
+        "3442193e1bb70916e914552172cd4e2dbc9df811",
+        "5c1bd648ed23aa5fd50ba52b2457c11e9e80a6a7",
+        "bef5a2f9a56a94aab12459f72ad9cf8cf19c7bbe",
+        "ee7ab90cde56a8c0e2bb086ac49748b8db9dce72",
+        "d880d7d893848509a62d8fb74e32148dac68412f",
+        "d69aa102255fed74378278c7812701ea641fdf32",
+    ];
+
+    for (i, ((_, xpub, xprv), identifier)) in VECTOR_1.iter().zip(identifiers).enumerate() {
+        let xpub = ExtendedPublicKey::from_xpub_string(xpub).unwrap();
+        let xprv = ExtendedPrivateKey::from_xprv_string(xprv).unwrap();
+        assert_eq!(hex::encode(xpub.identifier()), identifier);
+        assert_eq!(hex::encode(xprv.identifier()), identifier);
+        assert_eq!(hex::encode(xpub.fingerprint()), identifier[..8]);
+        assert_eq!(xprv.fingerprint(), xpub.fingerprint());
+
+        // Each child records its parent's fingerprint
+        if let Some((_, child, _)) = VECTOR_1.get(i + 1) {
+            let child = ExtendedPublicKey::from_xpub_string(child).unwrap();
+            assert_eq!(child.parent_fingerprint, xpub.fingerprint());
+        }
+    }
+}
+
+#[test]
+fn test_vector_2_public_derivation() {
+    // Non-hardened steps can be derived from the parent xpub alone
+    let (_, xpub, _) = VECTOR_2[0];
+    let (_, child_xpub, _) = VECTOR_2[1];
+    let master = ExtendedPublicKey::from_xpub_string(xpub).unwrap();
+    assert_eq!(
+        master
+            .derive_child(0)
+            .unwrap()
+            .to_xpub_string(NetworkType::Mainnet),
+        child_xpub
+    );
+}
+
+#[test]
+fn test_vector_5_invalid_extended_keys() {
+    let invalid = [
+        // pubkey version / prvkey mismatch
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6LBpB85b3D2yc8sfvZU521AAwdZafEz7mnzBBsz4wKY5fTtTQBm",
+        // prvkey version / pubkey mismatch
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGTQQD3dC4H2D5GBj7vWvSQaaBv5cxi9gafk7NF3pnBju6dwKvH",
+        // invalid pubkey prefix 04
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Txnt3siSujt9RCVYsx4qHZGc62TG4McvMGcAUjeuwZdduYEvFn",
+        // invalid prvkey prefix 04
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFGpWnsj83BHtEy5Zt8CcDr1UiRXuWCmTQLxEK9vbz5gPstX92JQ",
+        // invalid pubkey prefix 01
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6N8ZMMXctdiCjxTNq964yKkwrkBJJwpzZS4HS2fxvyYUA4q2Xe4",
+        // invalid prvkey prefix 01
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fEQ3Qen6J",
+        // zero depth with non-zero parent fingerprint
+        "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+        "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+        // zero depth with non-zero index
+        "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+        "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+        // unknown extended key version
+        "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHGMQzT7ayAmfo4z3gY5KfbrZWZ6St24UVf2Qgo6oujFktLHdHY4",
+        "DMwo58pR1QLEFihHiXPVykYB6fJmsTeHvyTp7hRThAtCX8CvYzgPcn8XnmdfHPmHJiEDXkTiJTVV9rHEBUem2mwVbbNfvT2MTcAqj3nesx8uBf9",
+        // private key 0 not in 1..n-1
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzF93Y5wvzdUayhgkkFoicQZcP3y52uPPxFnfoLZB21Teqt1VvEHx",
+        // private key n not in 1..n-1
+        "xprv9s21ZrQH143K24Mfq5zL5MhWK9hUhhGbd45hLXo2Pq2oqzMMo63oStZzFAzHGBP2UuGCqWLTAPLcMtD9y5gkZ6Eq3Rjuahrv17fENZ3QzxW",
+        // invalid pubkey 020000000000000000000000000000000000000000000000000000000000000007
+        "xpub661MyMwAqRbcEYS8w7XLSVeEsBXy79zSzH1J8vCdxAZningWLdN3zgtU6Q5JXayek4PRsn35jii4veMimro1xefsM58PgBMrvdYre8QyULY",
+    ];
+
+    for key in invalid {
+        // The checksum is valid, so rejection comes from the key contents
+        assert!(
+            blvm_sdk::governance::base58::decode_check(key).is_ok(),
+            "{}",
+            key
+        );
+        assert!(
+            ExtendedPrivateKey::from_xprv_string(key).is_err(),
+            "{}",
+            key
+        );
+        assert!(ExtendedPublicKey::from_xpub_string(key).is_err(), "{}", key);
+    }
+
+    // invalid checksum
+    let bad_checksum = "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHL";
+    assert!(matches!(
+        blvm_sdk::governance::base58::decode_check(bad_checksum),
+        Err(GovernanceError::ChecksumMismatch(_))
+    ));
+    assert!(ExtendedPrivateKey::from_xprv_string(bad_checksum).is_err());
+}
+
+#[test]
+fn test_skipping_derivation_matches_valid_index() {
+    let seed = hex::decode(VECTOR_1_SEED).unwrap();
+    let (master, _) = derive_master_key(&seed).unwrap();
+
+    // Valid indices are used unchanged
+    for index in [0, 1, H, H + 1] {
+        let (skipped, _) = derive_child_private_skipping(&master, index).unwrap();
+        let (direct, _) = derive_child_private(&master, index).unwrap();
+        assert_eq!(skipped.child_number, index);
+        assert_eq!(skipped.private_key_bytes(), direct.private_key_bytes());
+    }
+
+    let error = GovernanceError::InvalidChildIndex(7);
+    assert!(error.to_string().contains("use the next index"));
+}
+
+#[test]
+fn test_max_depth_xprv_has_no_children() {
+    let xprv = VECTOR_1[0].2;
+    let mut bytes = ExtendedPrivateKey::from_xprv_string(xprv)
+        .unwrap()
+        .to_bytes(NetworkType::Mainnet);
+    bytes[4] = 255;
+    bytes[5..9].copy_from_slice(&[1, 2, 3, 4]);
+    let deep = ExtendedPrivateKey::from_bytes(&bytes).unwrap();
+    let deep =
+        ExtendedPrivateKey::from_xprv_string(&deep.to_xprv_string(NetworkType::Mainnet)).unwrap();
+    assert_eq!(deep.depth, 255);
+
+    for index in [0, H] {
+        assert!(matches!(
+            derive_child_private(&deep, index),
+            Err(GovernanceError::InvalidKey(_))
+        ));
+        assert!(matches!(
+            derive_child_private_skipping(&deep, index),
+            Err(GovernanceError::InvalidKey(_))
+        ));
+    }
+}
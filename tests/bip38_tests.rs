@@ -0,0 +1,136 @@
+//! BIP38 Encrypted Private Key Tests
+//!
+//! Test vectors from the BIP38 specification.
+//! Specification: https://github.com/bitcoin/bips/blob/master/bip-0038.mediawiki
+
+use blvm_sdk::governance::bip38::{
+    confirm_code, decrypt, encrypt_ec_multiply, encrypt_no_ec_multiply,
+};
+use blvm_sdk::governance::error::GovernanceError;
+use blvm_sdk::NetworkType;
+
+/// (passphrase, encrypted key, WIF, private key hex)
+const NO_EC_MULTIPLY_VECTORS: &[(&str, &str, &str, &str)] = &[
+    (
+        "TestingOneTwoThree",
+        "6PRVWUbkzzsbcVac2qwfssoUJAN1Xhrg6bNk8J7Nzm5H7kxEbn2Nh2ZoGg",
+        "5KN7MzqK5wt2TP1fQCYyHBtDrXdJuXbUzm4A9rKAteGu3Qi5CVR",
+        "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+    ),
+    (
+        "Satoshi",
+        "6PRNFFkZc2NZ6dJqFfhRoFNMR9Lnyj7dYGrzdgXXVMXcxoKTePPX1dWByq",
+        "5HtasZ6ofTHP6HCwTqTkLDuLQisYPah7aUnSKfC7h4hMUVw2gi5",
+        "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+    ),
+    (
+        "TestingOneTwoThree",
+        "6PYNKZ1EAgYgmQfmNVamxyXVWHzK5s6DGhwP4J5o44cvXdoY7sRzhtpUeo",
+        "L44B5gGEpqEDRS9vVPz7QT35jcBG2r3CZwSwQ4fCewXAhAhqGVpP",
+        "cbf4b9f70470856bb4f40f80b87edb90865997ffee6df315ab166d713af433a5",
+    ),
+    (
+        "Satoshi",
+        "6PYLtMnXvfG3oJde97zRyLYFZCYizPU5T3LwgdYJz1fRhh16bU7u6PPmY7",
+        "KwYgW8gcxj1JWJXhPSu4Fqwzfhp5Yfi42mdYmMa4XqK7NJxXUSK7",
+        "09c2686880095b1a4c249ee3ac4eea8a014f11e6f986d0b5025ac1f39afbd9ae",
+    ),
+];
+
+/// (passphrase, encrypted key, private key hex)
+const EC_MULTIPLY_VECTORS: &[(&str, &str, &str)] = &[
+    (
+        "TestingOneTwoThree",
+        "6PfQu77ygVyJLZjfvMLyhLMQbYnu5uguoJJ4kMCLqWwPEdfpwANVS76gTX",
+        "a43a940577f4e97f5c4d39eb14ff083a98187c64ea7c99ef7ce460833959a519",
+    ),
+    (
+        "Satoshi",
+        "6PfLGnQs6VZnrNpmVKfjotbnQuaJK4KZoPFrAjx1JMJUa1Ft8gnf5WxfKd",
+        "c2c8036df268f498099350718c4a3ef3984d2be84618c2650f5171dcc5eb660a",
+    ),
+    (
+        "MOLON LABE",
+        "6PgNBNNzDkKdhkT6uJntUXwwzQV8Rr2tZcbkDcuC9DZRsS6AtHts4Ypo1j",
+        "44ea95afbf138356a05ea32110dfd627232d0f2991ad221187be356f19fa8190",
+    ),
+];
+
+// ============================================================================
+// Phase 1: Non-EC-Multiply Tests
+// ============================================================================
+
+#[test]
+fn test_encrypt_no_ec_multiply_vectors() {
+    for (passphrase, encrypted, wif, _) in NO_EC_MULTIPLY_VECTORS {
+        assert_eq!(
+            encrypt_no_ec_multiply(wif, passphrase).unwrap(),
+            *encrypted,
+            "{}",
+            wif
+        );
+    }
+}
+
+#[test]
+fn test_decrypt_no_ec_multiply_vectors() {
+    for (passphrase, encrypted, _, secret_hex) in NO_EC_MULTIPLY_VECTORS {
+        let keypair = decrypt(encrypted, passphrase).unwrap();
+        assert_eq!(hex::encode(keypair.secret_key_bytes()), *secret_hex);
+    }
+}
+
+#[test]
+fn test_decrypt_wrong_passphrase() {
+    let (_, encrypted, _, _) = NO_EC_MULTIPLY_VECTORS[0];
+    let err = decrypt(encrypted, "wrong").unwrap_err();
+    assert!(matches!(err, GovernanceError::InvalidKey(_)));
+}
+
+#[test]
+fn test_encrypt_invalid_wif() {
+    let err = encrypt_no_ec_multiply("not a wif", "pass").unwrap_err();
+    assert!(matches!(err, GovernanceError::InvalidKey(_)));
+}
+
+// ============================================================================
+// Phase 2: EC-Multiply Tests
+// ============================================================================
+
+#[test]
+fn test_decrypt_ec_multiply_vectors() {
+    for (passphrase, encrypted, secret_hex) in EC_MULTIPLY_VECTORS {
+        let keypair = decrypt(encrypted, passphrase).unwrap();
+        assert_eq!(hex::encode(keypair.secret_key_bytes()), *secret_hex);
+    }
+}
+
+#[test]
+fn test_confirm_code_vector() {
+    let address = confirm_code(
+        "cfrm38V8aXBn7JWA1ESmFMUn6erxeBGZGAxJPY4e36S9QWkzZKtaVqLNMgnifETYw7BPwWC9aPD",
+        "MOLON LABE",
+    )
+    .unwrap();
+    assert_eq!(address, "1Jscj8ALrYu2y9TD8NrpvDBugPedmbj4Yh");
+
+    let err = confirm_code(
+        "cfrm38V8aXBn7JWA1ESmFMUn6erxeBGZGAxJPY4e36S9QWkzZKtaVqLNMgnifETYw7BPwWC9aPD",
+        "wrong",
+    )
+    .unwrap_err();
+    assert!(matches!(err, GovernanceError::InvalidKey(_)));
+}
+
+#[test]
+fn test_encrypt_ec_multiply_roundtrip() {
+    let (encrypted, address) =
+        encrypt_ec_multiply("governance", true, NetworkType::Testnet).unwrap();
+    assert!(encrypted.starts_with("6P"));
+
+    // The generated key decrypts to the key controlling the returned address
+    let keypair = decrypt(&encrypted, "governance").unwrap();
+    let pubkey = bitcoin::PublicKey::new(keypair.public_key);
+    let expected = bitcoin::Address::p2pkh(&pubkey, bitcoin::Network::Testnet);
+    assert_eq!(address, expected.to_string());
+}
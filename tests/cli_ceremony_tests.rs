@@ -0,0 +1,59 @@
+//! # Key Ceremony CLI Tests
+//!
+//! End-to-end tests for `blvm-keygen ceremony`.
+
+use assert_cmd::Command;
+use blvm_sdk::governance::ceremony::CeremonyTranscript;
+use blvm_sdk::governance::{PublicKey, Signature};
+use tempfile::TempDir;
+
+#[test]
+fn test_keygen_ceremony_writes_cosigned_transcript() {
+    let dir = TempDir::new().unwrap();
+    Command::cargo_bin("blvm-keygen")
+        .unwrap()
+        .args(["ceremony", "--participant", "alice,bob", "-p", "carol"])
+        .arg("--output-dir")
+        .arg(dir.path())
+        .assert()
+        .success();
+
+    for participant in ["alice", "bob", "carol"] {
+        assert!(dir.path().join(format!("{}.key", participant)).exists());
+    }
+
+    let transcript = std::fs::read_to_string(dir.path().join("ceremony-transcript.json")).unwrap();
+    let transcript = CeremonyTranscript::from_json(&transcript).unwrap();
+    assert_eq!(transcript.participants, vec!["alice", "bob", "carol"]);
+
+    let signatures: serde_json::Value = serde_json::from_slice(
+        &std::fs::read(dir.path().join("ceremony-signatures.json")).unwrap(),
+    )
+    .unwrap();
+    let signatures = signatures.as_array().unwrap();
+    assert_eq!(signatures.len(), 3);
+    for entry in signatures {
+        let public_key =
+            PublicKey::from_bytes(&hex::decode(entry["public_key"].as_str().unwrap()).unwrap())
+                .unwrap();
+        let signature =
+            Signature::from_bytes(&hex::decode(entry["signature"].as_str().unwrap()).unwrap())
+                .unwrap();
+        assert!(transcript
+            .verify_transcript_signature(&signature, &public_key)
+            .unwrap());
+    }
+}
+
+#[test]
+fn test_keygen_ceremony_rejects_path_like_names() {
+    let dir = TempDir::new().unwrap();
+    Command::cargo_bin("blvm-keygen")
+        .unwrap()
+        .args(["ceremony", "-p", "../escape"])
+        .arg("--output-dir")
+        .arg(dir.path())
+        .assert()
+        .failure();
+    assert!(!dir.path().join("ceremony-transcript.json").exists());
+}
@@ -0,0 +1,78 @@
+//! # CLI Completion Tests
+//!
+//! Smoke tests for `--generate-completions` across the CLI tools.
+
+use assert_cmd::Command;
+
+/// Generate a bash completion script for the given binary
+fn bash_completions(bin: &str) -> String {
+    let output = Command::cargo_bin(bin)
+        .unwrap()
+        .args(["--generate-completions", "bash"])
+        .output()
+        .unwrap();
+    assert!(
+        output.status.success(),
+        "{} failed to generate completions",
+        bin
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn test_bash_completions_mention_subcommands() {
+    let cases: &[(&str, &[&str])] = &[
+        ("blvm-keygen", &[]),
+        ("blvm-sign", &["release", "module", "budget"]),
+        ("blvm-verify", &["release", "module", "budget"]),
+        ("blvm-sign-binary", &["binary", "bundle", "checksums"]),
+        ("blvm-verify-binary", &["binary", "bundle", "checksums"]),
+        ("blvm-aggregate-signatures", &[]),
+        (
+            "blvm-compose",
+            &[
+                "compose",
+                "validate",
+                "generate-template",
+                "modules",
+                "list",
+                "install",
+                "update",
+                "remove",
+            ],
+        ),
+    ];
+
+    for (bin, subcommands) in cases {
+        let script = bash_completions(bin);
+        assert!(script.contains(bin), "{} script missing binary name", bin);
+        for subcommand in *subcommands {
+            assert!(
+                script.contains(subcommand),
+                "{} completions missing subcommand {}",
+                bin,
+                subcommand
+            );
+        }
+    }
+}
+
+#[test]
+fn test_completions_for_other_shells() {
+    for shell in ["zsh", "fish", "powershell"] {
+        Command::cargo_bin("blvm-verify-binary")
+            .unwrap()
+            .args(["--generate-completions", shell])
+            .assert()
+            .success();
+    }
+}
+
+#[test]
+fn test_completions_unknown_shell_is_usage_error() {
+    Command::cargo_bin("blvm-verify")
+        .unwrap()
+        .args(["--generate-completions", "tcsh"])
+        .assert()
+        .code(64);
+}
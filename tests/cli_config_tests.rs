@@ -0,0 +1,106 @@
+//! # CLI Configuration Tests
+//!
+//! Precedence of flags, environment variables, config file, and defaults.
+
+use blvm_sdk::cli::config::{CliConfig, SettingSource, CONFIG_ENV_VAR};
+use blvm_sdk::cli::input::InputError;
+use clap::Parser;
+
+#[derive(Parser, Debug)]
+#[command(name = "blvm-test-tool")]
+struct TestArgs {
+    #[arg(short, long, default_value = "text")]
+    format: String,
+
+    #[arg(short, long)]
+    threshold: Option<String>,
+
+    #[arg(short, long, required = true)]
+    key: String,
+}
+
+#[test]
+fn test_setting_precedence() {
+    let config = CliConfig::from_toml_str(
+        r#"
+        format = "json"
+        threshold = "3-of-5"
+        key = "file.key"
+
+        [tools.blvm-test-tool]
+        threshold = "4-of-5"
+        "#,
+    )
+    .unwrap();
+
+    // Built-in default when nothing else is set
+    let args: TestArgs = CliConfig::default()
+        .try_parse_from(["blvm-test-tool", "--key", "flag.key"])
+        .unwrap();
+    assert_eq!(args.format, "text");
+    assert_eq!(args.threshold, None);
+
+    // Config file beats the built-in default, tool section beats top-level,
+    // and a file value satisfies a required argument
+    let args: TestArgs = config.try_parse_from(["blvm-test-tool"]).unwrap();
+    assert_eq!(args.format, "json");
+    assert_eq!(args.threshold.as_deref(), Some("4-of-5"));
+    assert_eq!(args.key, "file.key");
+
+    // Environment beats the config file
+    std::env::set_var("BLLVM_OUTPUT_FORMAT", "ndjson");
+    let args: TestArgs = config.try_parse_from(["blvm-test-tool"]).unwrap();
+    assert_eq!(args.format, "ndjson");
+    let effective = config.effective("blvm-test-tool");
+    let (_, value, source) = effective
+        .iter()
+        .find(|(s, _, _)| s.key == "format")
+        .unwrap();
+    assert_eq!(value.as_deref(), Some("ndjson"));
+    assert_eq!(*source, SettingSource::Env);
+
+    // Flag beats everything
+    let args: TestArgs = config
+        .try_parse_from(["blvm-test-tool", "--format", "table"])
+        .unwrap();
+    assert_eq!(args.format, "table");
+    std::env::remove_var("BLLVM_OUTPUT_FORMAT");
+}
+
+#[test]
+fn test_required_argument_still_enforced_without_config() {
+    let result: Result<TestArgs, _> = CliConfig::default().try_parse_from(["blvm-test-tool"]);
+    assert!(result.is_err());
+}
+
+#[test]
+fn test_config_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+    std::fs::write(&path, "modules_dir = \"/srv/modules\"\nunknown = \"x\"\n").unwrap();
+
+    let config = CliConfig::from_file(&path).unwrap();
+    assert_eq!(config.path.as_deref(), Some(path.as_path()));
+    assert_eq!(
+        config.get("blvm-compose", "modules_dir"),
+        Some("/srv/modules")
+    );
+    assert_eq!(config.warnings.len(), 1);
+}
+
+#[test]
+fn test_explicit_config_path_must_exist() {
+    let dir = tempfile::tempdir().unwrap();
+    let path = dir.path().join("config.toml");
+
+    std::env::set_var(CONFIG_ENV_VAR, &path);
+    assert!(matches!(
+        CliConfig::load(),
+        Err(InputError::FileNotFound(ref missing)) if missing == &path.to_string_lossy()
+    ));
+
+    std::fs::write(&path, "network = \"regtest\"\n").unwrap();
+    let config = CliConfig::load().unwrap();
+    std::env::remove_var(CONFIG_ENV_VAR);
+    assert_eq!(config.path.as_deref(), Some(path.as_path()));
+}
@@ -0,0 +1,110 @@
+//! # CLI Environment Variable Tests
+//!
+//! Environment overrides for the CLI binaries and their documentation.
+
+use assert_cmd::Command;
+use blvm_sdk::cli::config::{CONFIG_ENV_VAR, SETTINGS};
+use tempfile::TempDir;
+
+/// Command for `bin` isolated from the caller's config and environment
+fn isolated(bin: &str, dir: &TempDir) -> Command {
+    let config = dir.path().join("config.toml");
+    if !config.exists() {
+        std::fs::write(&config, "").unwrap();
+    }
+    let mut cmd = Command::cargo_bin(bin).unwrap();
+    cmd.current_dir(dir.path()).env(CONFIG_ENV_VAR, config);
+    for setting in SETTINGS {
+        cmd.env_remove(setting.env);
+    }
+    cmd
+}
+
+#[test]
+fn test_key_file_env_does_not_redirect_keygen_output() {
+    let dir = TempDir::new().unwrap();
+    let signing_key = dir.path().join("signing.key");
+    std::fs::write(&signing_key, "existing key").unwrap();
+
+    // BLLVM_KEY_FILE names the key to sign with, not where keygen writes
+    isolated("blvm-keygen", &dir)
+        .env("BLLVM_KEY_FILE", &signing_key)
+        .assert()
+        .success();
+    assert!(dir.path().join("governance.key").exists());
+    assert_eq!(
+        std::fs::read_to_string(&signing_key).unwrap(),
+        "existing key"
+    );
+}
+
+#[test]
+fn test_keygen_refuses_to_overwrite_without_force() {
+    let dir = TempDir::new().unwrap();
+    let key = dir.path().join("governance.key");
+    std::fs::write(&key, "existing key").unwrap();
+
+    isolated("blvm-keygen", &dir)
+        .arg("--output")
+        .arg(&key)
+        .assert()
+        .code(64);
+    assert_eq!(std::fs::read_to_string(&key).unwrap(), "existing key");
+
+    isolated("blvm-keygen", &dir)
+        .arg("--output")
+        .arg(&key)
+        .arg("--force")
+        .assert()
+        .success();
+    assert_ne!(std::fs::read_to_string(&key).unwrap(), "existing key");
+}
+
+#[test]
+fn test_missing_explicit_config_file_is_an_error() {
+    let dir = TempDir::new().unwrap();
+    isolated("blvm-sign", &dir)
+        .env(CONFIG_ENV_VAR, dir.path().join("typo.toml"))
+        .arg("--help")
+        .assert()
+        .code(78);
+}
+
+#[test]
+fn test_help_lists_environment_variables() {
+    let dir = TempDir::new().unwrap();
+    let output = isolated("blvm-sign", &dir).arg("--help").output().unwrap();
+    let help = String::from_utf8(output.stdout).unwrap();
+
+    assert!(help.contains(CONFIG_ENV_VAR));
+    for setting in SETTINGS {
+        assert!(help.contains(setting.env), "missing {}", setting.env);
+    }
+}
+
+#[test]
+fn test_compose_config_env_masks_key_file() {
+    let dir = TempDir::new().unwrap();
+    let output = isolated("blvm-compose", &dir)
+        .env("BLLVM_KEY_FILE", "/secret/governance.key")
+        .env("BLLVM_NETWORK", "regtest")
+        .args(["--format", "json", "config", "env"])
+        .output()
+        .unwrap();
+    assert!(output.status.success());
+
+    let rows: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let value_of = |name: &str| {
+        rows.as_array()
+            .unwrap()
+            .iter()
+            .find(|row| row["VARIABLE"] == name)
+            .map(|row| row["VALUE"].as_str().unwrap().to_string())
+            .unwrap()
+    };
+
+    assert_eq!(value_of("BLLVM_NETWORK"), "regtest");
+    assert_eq!(value_of("BLLVM_THRESHOLD"), "(unset)");
+    assert_ne!(value_of("BLLVM_KEY_FILE"), "/secret/governance.key");
+    assert!(!String::from_utf8_lossy(&output.stdout).contains("/secret"));
+}
@@ -0,0 +1,485 @@
+//! # CLI Exit Code Tests
+//!
+//! Pin the documented exit codes of the CLI binaries.
+
+use assert_cmd::Command;
+use blvm_sdk::governance::{BudgetAllocation, GovernanceKeypair, GovernanceMessage};
+use blvm_sdk::sign_message;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const EXIT_THRESHOLD_NOT_MET: i32 = 1;
+const EXIT_USAGE: i32 = 64;
+const EXIT_VALIDATION_FAILED: i32 = 65;
+const EXIT_NO_INPUT: i32 = 66;
+
+/// Write a public key file for the keypair
+fn write_pubkey(dir: &Path, name: &str, keypair: &GovernanceKeypair) -> PathBuf {
+    let path = dir.join(format!("{}.pub.json", name));
+    let json = serde_json::json!({
+        "public_key": hex::encode(keypair.public_key_bytes()),
+    });
+    fs::write(&path, json.to_string()).unwrap();
+    path
+}
+
+/// Write a signature file signing `message` with the keypair
+fn write_signature(dir: &Path, name: &str, keypair: &GovernanceKeypair, message: &[u8]) -> PathBuf {
+    let path = dir.join(format!("{}.sig.json", name));
+    let signature = sign_message(&keypair.secret_key, message).unwrap();
+    let json = serde_json::json!({
+        "signature": hex::encode(signature.to_bytes()),
+    });
+    fs::write(&path, json.to_string()).unwrap();
+    path
+}
+
+/// Build a 2-of-2 release verification with `signers` of the keys signing
+fn verify_release(dir: &TempDir, signers: usize, extra_args: &[&str]) -> Command {
+    let message = GovernanceMessage::Release {
+        version: "v1.0.0".to_string(),
+        commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
+    };
+    let keypairs: Vec<GovernanceKeypair> = (0..2)
+        .map(|_| GovernanceKeypair::generate().unwrap())
+        .collect();
+
+    let pubkeys: Vec<String> = keypairs
+        .iter()
+        .enumerate()
+        .map(|(i, kp)| {
+            write_pubkey(dir.path(), &format!("key{}", i), kp)
+                .display()
+                .to_string()
+        })
+        .collect();
+    let signatures: Vec<String> = keypairs
+        .iter()
+        .take(signers)
+        .enumerate()
+        .map(|(i, kp)| {
+            write_signature(
+                dir.path(),
+                &format!("key{}", i),
+                kp,
+                &message.to_signing_bytes(),
+            )
+            .display()
+            .to_string()
+        })
+        .collect();
+
+    let mut cmd = Command::cargo_bin("blvm-verify").unwrap();
+    cmd.args(["--signatures", &signatures.join(",")])
+        .args(["--pubkeys", &pubkeys.join(",")])
+        .args(["--threshold", "2-of-2"])
+        .args(extra_args)
+        .args(["release", "--version", "v1.0.0", "--commit", "abc123"]);
+    cmd
+}
+
+#[test]
+fn test_verify_threshold_met_exits_zero() {
+    let dir = TempDir::new().unwrap();
+    verify_release(&dir, 2, &[]).assert().success();
+}
+
+#[test]
+fn test_verify_threshold_not_met_exits_one() {
+    let dir = TempDir::new().unwrap();
+    verify_release(&dir, 1, &[])
+        .assert()
+        .code(EXIT_THRESHOLD_NOT_MET);
+}
+
+#[test]
+fn test_verify_quiet_suppresses_stdout() {
+    let dir = TempDir::new().unwrap();
+    let output = verify_release(&dir, 2, &["--quiet"]).output().unwrap();
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+}
+
+#[test]
+fn test_verify_output_file_report() {
+    let dir = TempDir::new().unwrap();
+    let report = dir.path().join("report.json");
+    let report_arg = report.display().to_string();
+
+    verify_release(&dir, 1, &["--output-file", &report_arg, "--format", "csv"])
+        .assert()
+        .code(EXIT_THRESHOLD_NOT_MET);
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert!(json["verified_at"].is_string());
+    assert_eq!(json["subject"], "Release v1.0.0 (commit: abc123)");
+    assert_eq!(json["public_keys"].as_array().unwrap().len(), 2);
+    assert_eq!(json["signatures"][0]["valid"], true);
+    assert!(json["signatures"][0]["signer"].is_string());
+    assert_eq!(json["threshold"], "2-of-2");
+    assert_eq!(json["threshold_met"], false);
+    assert_eq!(json["valid"], false);
+
+    // Report only: the failure is recorded but the exit status is zero
+    verify_release(
+        &dir,
+        1,
+        &["--output-file", &report_arg, "--fail-on-invalid", "false"],
+    )
+    .assert()
+    .success();
+
+    verify_release(&dir, 2, &["--output-file", &report_arg, "--quiet"])
+        .assert()
+        .success()
+        .stdout("");
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(json["valid"], true);
+}
+
+#[test]
+fn test_verify_release_artifacts_dir() {
+    let dir = TempDir::new().unwrap();
+    let artifacts = dir.path().join("artifacts");
+    fs::create_dir(&artifacts).unwrap();
+    fs::write(artifacts.join("bllvm-node-linux-x86_64"), b"linux").unwrap();
+    fs::write(artifacts.join("bllvm-node-darwin-arm64"), b"darwin").unwrap();
+
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let message = GovernanceMessage::Release {
+        version: "v1.0.0".to_string(),
+        commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
+    }
+    .with_artifact(
+        "bllvm-node-linux-x86_64",
+        &hex::encode(Sha256::digest(b"linux")),
+    )
+    .unwrap()
+    .with_artifact(
+        "bllvm-node-darwin-arm64",
+        &hex::encode(Sha256::digest(b"darwin")),
+    )
+    .unwrap();
+    let pubkey = write_pubkey(dir.path(), "key", &keypair);
+    let signature = write_signature(dir.path(), "key", &keypair, &message.to_signing_bytes());
+
+    let verify = || {
+        let mut cmd = Command::cargo_bin("blvm-verify").unwrap();
+        cmd.args(["--signatures", &signature.display().to_string()])
+            .args(["--pubkeys", &pubkey.display().to_string()])
+            .args(["--threshold", "1-of-1"])
+            .args(["release", "--version", "v1.0.0", "--commit", "abc123"])
+            .args(["--artifacts-dir", &artifacts.display().to_string()]);
+        cmd
+    };
+    verify().assert().success();
+
+    // A modified artifact no longer matches the signed hashes
+    fs::write(artifacts.join("bllvm-node-linux-x86_64"), b"tampered").unwrap();
+    verify().assert().code(EXIT_THRESHOLD_NOT_MET);
+}
+
+#[test]
+fn test_verify_budget_recipients() {
+    let dir = TempDir::new().unwrap();
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let message = GovernanceMessage::BudgetDecision {
+        allocations: vec![
+            BudgetAllocation {
+                recipient: "bc1qalice".to_string(),
+                amount_satoshis: 100,
+                description: String::new(),
+            },
+            BudgetAllocation {
+                recipient: "bc1qbob".to_string(),
+                amount_satoshis: 200,
+                description: String::new(),
+            },
+        ],
+        rationale: "grants".to_string(),
+    };
+    let pubkey = write_pubkey(dir.path(), "key", &keypair);
+    let signature = write_signature(dir.path(), "key", &keypair, &message.to_signing_bytes());
+
+    let verify = |allocations: &[&str]| {
+        let mut cmd = Command::cargo_bin("blvm-verify").unwrap();
+        cmd.args(["--signatures", &signature.display().to_string()])
+            .args(["--pubkeys", &pubkey.display().to_string()])
+            .args(["--threshold", "1-of-1"])
+            .args(["budget", "--purpose", "grants"])
+            .args(allocations);
+        cmd
+    };
+    // Recipients may be given in any order
+    verify(&["--recipient", "bc1qbob", "--amount", "200"])
+        .args(["--recipient", "bc1qalice", "--amount", "100"])
+        .assert()
+        .success();
+    verify(&["--recipient", "bc1qbob", "--amount", "100"])
+        .args(["--recipient", "bc1qalice", "--amount", "200"])
+        .assert()
+        .code(EXIT_THRESHOLD_NOT_MET);
+    // Every recipient needs an amount
+    verify(&["--recipient", "bc1qbob", "--amount", "200"])
+        .args(["--recipient", "bc1qalice"])
+        .assert()
+        .code(EXIT_USAGE);
+}
+
+#[test]
+fn test_verify_missing_signature_file() {
+    Command::cargo_bin("blvm-verify")
+        .unwrap()
+        .args(["--signatures", "/nonexistent/signature.json"])
+        .args(["release", "--version", "v1.0.0", "--commit", "abc123"])
+        .assert()
+        .code(EXIT_NO_INPUT);
+}
+
+#[test]
+fn test_verify_invalid_usage() {
+    Command::cargo_bin("blvm-verify")
+        .unwrap()
+        .args(["release", "--version", "v1.0.0"])
+        .assert()
+        .code(EXIT_USAGE);
+}
+
+#[test]
+fn test_verify_binary_missing_file() {
+    let dir = TempDir::new().unwrap();
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let signature = write_signature(dir.path(), "key", &keypair, b"unused");
+
+    Command::cargo_bin("blvm-verify-binary")
+        .unwrap()
+        .args(["--signatures", &signature.display().to_string()])
+        .args(["binary", "--file", "/nonexistent/binary"])
+        .assert()
+        .code(EXIT_NO_INPUT);
+}
+
+#[test]
+fn test_verify_binary_threshold_not_met() {
+    let dir = TempDir::new().unwrap();
+    let binary = dir.path().join("binary");
+    fs::write(&binary, b"binary contents").unwrap();
+
+    let keypairs: Vec<GovernanceKeypair> = (0..2)
+        .map(|_| GovernanceKeypair::generate().unwrap())
+        .collect();
+    let pubkeys: Vec<String> = keypairs
+        .iter()
+        .enumerate()
+        .map(|(i, kp)| {
+            write_pubkey(dir.path(), &format!("key{}", i), kp)
+                .display()
+                .to_string()
+        })
+        .collect();
+    // Signature over the wrong message never verifies
+    let signature = write_signature(dir.path(), "key0", &keypairs[0], b"wrong message");
+
+    Command::cargo_bin("blvm-verify-binary")
+        .unwrap()
+        .args(["--signatures", &signature.display().to_string()])
+        .args(["--pubkeys", &pubkeys.join(",")])
+        .args(["--threshold", "1-of-2"])
+        .args(["--quiet"])
+        .args(["binary", "--file", &binary.display().to_string()])
+        .assert()
+        .code(EXIT_THRESHOLD_NOT_MET)
+        .stdout("");
+}
+
+#[test]
+fn test_verify_binary_output_file_report() {
+    let dir = TempDir::new().unwrap();
+    let binary = dir.path().join("binary");
+    fs::write(&binary, b"binary contents").unwrap();
+    let hash = hex::encode(Sha256::digest(b"binary contents"));
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let pubkey = write_pubkey(dir.path(), "key", &keypair);
+    let good = write_signature(
+        dir.path(),
+        "good",
+        &keypair,
+        format!("binary:application:{}", hash).as_bytes(),
+    );
+    let bad = write_signature(dir.path(), "bad", &keypair, b"wrong message");
+    let report = dir.path().join("report.json");
+
+    // The report is JSON even with table output, and one bad signature fails
+    Command::cargo_bin("blvm-verify-binary")
+        .unwrap()
+        .args([
+            "--signatures",
+            &format!("{},{}", good.display(), bad.display()),
+        ])
+        .args(["--pubkeys", &pubkey.display().to_string()])
+        .args(["--format", "table"])
+        .args(["--output-file", &report.display().to_string()])
+        .args(["binary", "--file", &binary.display().to_string()])
+        .assert()
+        .code(EXIT_THRESHOLD_NOT_MET);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string(&report).unwrap()).unwrap();
+    assert_eq!(json["file_hash"], hash);
+    assert_eq!(json["subject"], binary.display().to_string());
+    assert_eq!(json["signatures"][0]["valid"], true);
+    assert_eq!(json["signatures"][1]["valid"], false);
+    assert_eq!(json["signatures"][1]["file"], bad.display().to_string());
+    assert!(json.get("threshold").is_none());
+    assert_eq!(json["threshold_met"], true);
+    assert_eq!(json["valid"], false);
+}
+
+#[test]
+fn test_sign_missing_key_file() {
+    Command::cargo_bin("blvm-sign")
+        .unwrap()
+        .args(["--key", "/nonexistent/key.json"])
+        .args(["release", "--version", "v1.0.0", "--commit", "abc123"])
+        .assert()
+        .code(EXIT_NO_INPUT);
+}
+
+#[test]
+fn test_keygen_malformed_seed() {
+    Command::cargo_bin("blvm-keygen")
+        .unwrap()
+        .args(["derive", "--path", "m/0", "--seed-hex", "not-hex"])
+        .assert()
+        .code(EXIT_VALIDATION_FAILED);
+}
+
+#[test]
+fn test_aggregate_malformed_signature_file() {
+    let dir = TempDir::new().unwrap();
+    let signature = dir.path().join("sig.json");
+    fs::write(&signature, "not json").unwrap();
+
+    Command::cargo_bin("blvm-aggregate-signatures")
+        .unwrap()
+        .args(["--signatures", &signature.display().to_string()])
+        .args([
+            "--output",
+            &dir.path().join("out.json").display().to_string(),
+        ])
+        .assert()
+        .code(EXIT_VALIDATION_FAILED);
+}
+
+/// `blvm-compose` with an empty modules directory
+fn compose(dir: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("blvm-compose").unwrap();
+    cmd.args(["--modules-dir", &dir.path().display().to_string()])
+        .args(["--log-dir", &dir.path().join("logs").display().to_string()]);
+    cmd
+}
+
+#[test]
+fn test_compose_exit_codes() {
+    let dir = TempDir::new().unwrap();
+    let config = dir.path().join("node.toml");
+
+    // Missing configuration file
+    compose(&dir)
+        .args(["validate", "--config", &config.display().to_string()])
+        .assert()
+        .code(EXIT_NO_INPUT);
+
+    // Configuration that does not parse
+    fs::write(&config, "[node").unwrap();
+    compose(&dir)
+        .args(["validate", "--config", &config.display().to_string()])
+        .assert()
+        .code(EXIT_USAGE);
+
+    // Configuration that parses but names a module that is not installed
+    fs::write(
+        &config,
+        "[node]\nname = \"test-node\"\nnetwork = \"regtest\"\n\n[modules.lightning]\n",
+    )
+    .unwrap();
+    compose(&dir)
+        .args(["validate", "--config", &config.display().to_string()])
+        .assert()
+        .code(EXIT_VALIDATION_FAILED);
+
+    compose(&dir)
+        .args(["--no-such-flag"])
+        .assert()
+        .code(EXIT_USAGE);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_compose_refuses_to_remove_running_module() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let module_dir = dir.path().join("indexer");
+    fs::create_dir(&module_dir).unwrap();
+    fs::write(
+        module_dir.join("module.toml"),
+        "name = \"indexer\"\nversion = \"1.0.0\"\nentry_point = \"indexer\"\n",
+    )
+    .unwrap();
+    let binary = module_dir.join("indexer");
+    fs::write(&binary, b"#!/bin/sh\nexit 0\n").unwrap();
+    fs::set_permissions(&binary, fs::Permissions::from_mode(0o755)).unwrap();
+
+    let sockets = TempDir::new().unwrap();
+    let socket = sockets.path().join("indexer.sock");
+    let remove = |dir: &TempDir| {
+        let mut cmd = compose(dir);
+        cmd.args(["--socket-dir", &sockets.path().display().to_string()])
+            .args(["modules", "remove", "indexer"]);
+        cmd
+    };
+
+    // A module answering on its IPC socket is running, even with --force
+    let listener = std::os::unix::net::UnixListener::bind(&socket).unwrap();
+    remove(&dir).assert().code(EXIT_USAGE);
+    remove(&dir).arg("--force").assert().code(EXIT_USAGE);
+    assert!(module_dir.exists());
+
+    drop(listener);
+    fs::remove_file(&socket).unwrap();
+    remove(&dir).assert().success();
+    assert!(!module_dir.exists());
+}
+
+#[test]
+fn test_exit_codes_in_long_help() {
+    for bin in [
+        "blvm-compose",
+        "blvm-keygen",
+        "blvm-sign",
+        "blvm-sign-binary",
+        "blvm-aggregate-signatures",
+        "blvm-verify",
+        "blvm-verify-binary",
+        "blvm-psbt",
+    ] {
+        let output = Command::cargo_bin(bin)
+            .unwrap()
+            .arg("--help")
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(
+            String::from_utf8_lossy(&output.stdout).contains("Exit codes:"),
+            "{}",
+            bin
+        );
+    }
+}
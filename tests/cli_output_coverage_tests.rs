@@ -169,3 +169,26 @@ fn test_output_formatter_format_with_serde_json_value() {
     assert!(result.is_ok());
     assert_eq!(result.unwrap(), "null");
 }
+
+#[test]
+fn test_output_formatter_format_table() {
+    let headers = ["FILE", "HASH"];
+    let rows = vec![vec!["bin".to_string(), "ab".repeat(32)]];
+
+    // Table output truncates long hashes
+    let formatter = OutputFormatter::new(OutputFormat::Table);
+    let output = formatter.format_table(&headers, &rows);
+    assert!(output.contains("FILE"));
+    assert!(output.contains('…'));
+
+    // Wide table output keeps the full hash
+    let formatter = OutputFormatter::new(OutputFormat::Table).with_wide(true);
+    let output = formatter.format_table(&headers, &rows);
+    assert!(output.contains(&"ab".repeat(32)));
+
+    // JSON output emits one object per row
+    let formatter = OutputFormatter::new(OutputFormat::Json);
+    let output = formatter.format_table(&headers, &rows);
+    let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(parsed[0]["FILE"], "bin");
+}
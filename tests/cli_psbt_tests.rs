@@ -0,0 +1,228 @@
+//! # CLI PSBT Tests
+//!
+//! Drive blvm-psbt through a create, sign, combine, finalize, and extract
+//! flow on fixture files, and pin its exit codes.
+
+use assert_cmd::Command;
+use bitcoin::hashes::Hash;
+use blvm_sdk::governance::psbt::{PartiallySignedTransaction, PsbtInputKey};
+use blvm_sdk::governance::transaction::{Transaction, TxIn, TxOut};
+use secp256k1::{Secp256k1, SecretKey};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tempfile::TempDir;
+
+const EXIT_NOTHING_TO_DO: i32 = 2;
+const EXIT_DATA_ERROR: i32 = 65;
+const EXIT_NO_INPUT: i32 = 66;
+
+fn psbt_cmd() -> Command {
+    Command::cargo_bin("blvm-psbt").unwrap()
+}
+
+/// Write a key file for the secret key
+fn write_key(dir: &Path, name: &str, key: &SecretKey) -> PathBuf {
+    let path = dir.join(format!("{}.json", name));
+    let json = serde_json::json!({ "secret_key": hex::encode(key.secret_bytes()) });
+    fs::write(&path, json.to_string()).unwrap();
+    path
+}
+
+/// Test helper: 2-of-2 P2WSH spend of a 100,000 sat output, and its keys
+fn multisig_fixture() -> (PartiallySignedTransaction, Vec<SecretKey>) {
+    let secp = Secp256k1::new();
+    let keys: Vec<SecretKey> = (1..=2u8)
+        .map(|i| SecretKey::from_slice(&[i; 32]).unwrap())
+        .collect();
+
+    let mut witness_script = vec![0x52];
+    for key in &keys {
+        witness_script.push(33);
+        witness_script.extend_from_slice(&key.public_key(&secp).serialize());
+    }
+    witness_script.extend_from_slice(&[0x52, 0xae]);
+    let mut script_pubkey = vec![0x00, 0x20];
+    script_pubkey
+        .extend_from_slice(bitcoin::hashes::sha256::Hash::hash(&witness_script).as_byte_array());
+
+    let tx = Transaction {
+        version: 2,
+        inputs: vec![TxIn {
+            previous_txid: [0x11; 32],
+            previous_vout: 0,
+            script_sig: Vec::new(),
+            sequence: 0xffff_fffd,
+            witness: Vec::new(),
+        }],
+        outputs: vec![TxOut {
+            value: 90_000,
+            script_pubkey: [&[0x00, 0x14][..], &[0xaa; 20]].concat(),
+        }],
+        lock_time: 0,
+    };
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        TxOut {
+            value: 100_000,
+            script_pubkey,
+        }
+        .serialize(),
+    )
+    .unwrap();
+    psbt.add_input_data(0, vec![PsbtInputKey::WitnessScript as u8], witness_script)
+        .unwrap();
+    (psbt, keys)
+}
+
+#[test]
+fn test_psbt_full_workflow() {
+    let dir = TempDir::new().unwrap();
+    let path = |name: &str| dir.path().join(name);
+    let arg = |name: &str| path(name).to_string_lossy().into_owned();
+    let (psbt, keys) = multisig_fixture();
+
+    // Each signer receives the PSBT in a different encoding
+    fs::write(path("unsigned.psbt"), psbt.serialize().unwrap()).unwrap();
+    fs::write(path("unsigned.b64"), psbt.to_base64().unwrap()).unwrap();
+    fs::write(path("unsigned.hex"), psbt.to_hex().unwrap()).unwrap();
+    let key_a = write_key(dir.path(), "a", &keys[0]);
+    let key_b = write_key(dir.path(), "b", &keys[1]);
+
+    let output = psbt_cmd()
+        .args(["--format", "json", "inspect", &arg("unsigned.psbt")])
+        .assert()
+        .success()
+        .get_output()
+        .stdout
+        .clone();
+    let summary: serde_json::Value = serde_json::from_slice(&output).unwrap();
+    assert_eq!(summary["fee"], 10_000);
+    assert_eq!(summary["inputs"][0]["status"], "unsigned");
+    assert_eq!(summary["finalized"], false);
+
+    psbt_cmd()
+        .arg("sign")
+        .arg("--key")
+        .arg(&key_a)
+        .args([&arg("unsigned.b64"), "-o", &arg("a.psbt")])
+        .assert()
+        .success();
+    psbt_cmd()
+        .arg("sign")
+        .arg("--key")
+        .arg(&key_b)
+        .args([&arg("unsigned.hex"), "-o", &arg("b.psbt")])
+        .assert()
+        .success();
+
+    psbt_cmd()
+        .args(["combine", &arg("a.psbt"), &arg("b.psbt")])
+        .args(["-o", &arg("combined.psbt")])
+        .assert()
+        .success();
+    psbt_cmd()
+        .args(["finalize", &arg("combined.psbt"), "-o", &arg("final.psbt")])
+        .assert()
+        .success();
+    psbt_cmd()
+        .args(["extract", &arg("final.psbt"), "-o", &arg("tx.hex")])
+        .assert()
+        .success();
+
+    let tx_hex = fs::read_to_string(path("tx.hex")).unwrap();
+    let tx: bitcoin::Transaction =
+        bitcoin::consensus::deserialize(&hex::decode(tx_hex.trim()).unwrap()).unwrap();
+    assert_eq!(tx.input.len(), 1);
+    // Empty CHECKMULTISIG dummy, two signatures, witness script
+    assert_eq!(tx.input[0].witness.len(), 4);
+    assert_eq!(tx.output[0].value.to_sat(), 90_000);
+}
+
+#[test]
+fn test_psbt_nothing_to_do() {
+    let dir = TempDir::new().unwrap();
+    let (mut psbt, keys) = multisig_fixture();
+    let unsigned = dir.path().join("unsigned.psbt");
+    fs::write(&unsigned, psbt.to_base64().unwrap()).unwrap();
+
+    // A key that is not in the witness script signs nothing
+    let stranger = write_key(
+        dir.path(),
+        "stranger",
+        &SecretKey::from_slice(&[9; 32]).unwrap(),
+    );
+    psbt_cmd()
+        .arg("sign")
+        .arg("--key")
+        .arg(&stranger)
+        .arg(&unsigned)
+        .assert()
+        .code(EXIT_NOTHING_TO_DO);
+
+    // Combining a PSBT with itself adds nothing
+    psbt_cmd()
+        .arg("combine")
+        .arg(&unsigned)
+        .arg(&unsigned)
+        .assert()
+        .code(EXIT_NOTHING_TO_DO);
+
+    // Finalizing an already final PSBT
+    for key in &keys {
+        psbt.sign_with_key(key).unwrap();
+    }
+    psbt.finalize().unwrap();
+    let finalized = dir.path().join("final.psbt");
+    fs::write(&finalized, psbt.to_base64().unwrap()).unwrap();
+    psbt_cmd()
+        .arg("finalize")
+        .arg(&finalized)
+        .assert()
+        .code(EXIT_NOTHING_TO_DO);
+}
+
+#[test]
+fn test_psbt_validation_errors() {
+    let dir = TempDir::new().unwrap();
+    let (mut psbt, keys) = multisig_fixture();
+    psbt.sign_with_key(&keys[0]).unwrap();
+    let partial = dir.path().join("partial.psbt");
+    fs::write(&partial, psbt.to_base64().unwrap()).unwrap();
+
+    // One of two signatures cannot finalize, and nothing is written
+    let output = dir.path().join("final.psbt");
+    psbt_cmd()
+        .arg("finalize")
+        .arg(&partial)
+        .arg("-o")
+        .arg(&output)
+        .assert()
+        .code(EXIT_DATA_ERROR);
+    assert!(!output.exists());
+
+    psbt_cmd()
+        .arg("extract")
+        .arg(&partial)
+        .assert()
+        .code(EXIT_DATA_ERROR);
+}
+
+#[test]
+fn test_psbt_parse_errors() {
+    let dir = TempDir::new().unwrap();
+    let garbage = dir.path().join("garbage.psbt");
+    fs::write(&garbage, "not a psbt").unwrap();
+
+    psbt_cmd()
+        .arg("inspect")
+        .arg(&garbage)
+        .assert()
+        .code(EXIT_DATA_ERROR);
+    psbt_cmd()
+        .arg("inspect")
+        .arg(dir.path().join("missing.psbt"))
+        .assert()
+        .code(EXIT_NO_INPUT);
+}
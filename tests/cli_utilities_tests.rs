@@ -2,8 +2,12 @@
 //!
 //! Tests for CLI input/output formatting and parsing utilities.
 
-use blvm_sdk::cli::input::{parse_comma_separated, parse_threshold};
+use blvm_sdk::cli::input::{
+    format_derivation_path, parse_comma_separated, parse_derivation_path, parse_threshold,
+};
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
+use blvm_sdk::composition::{ModuleHealth, ModuleReport, ModuleStatus, NodeReport, NodeStatus};
+use blvm_sdk::governance::GovernanceError;
 use std::error::Error;
 
 // ============================================================================
@@ -96,6 +100,35 @@ fn test_output_formatter_json_error() {
     assert!(formatted.contains("error") || formatted.contains("Error"));
 }
 
+#[test]
+fn test_output_formatter_csv_parseable() {
+    // CSV output must survive a standard CSV reader, quoting included
+    let formatter = OutputFormatter::new(OutputFormat::Csv);
+    let rows = vec![
+        vec!["lightning".to_string(), "Payments, channels".to_string()],
+        vec!["rpc".to_string(), "Says \"hi\"\nthen exits".to_string()],
+        vec!["empty".to_string(), String::new()],
+    ];
+    let output = formatter.format_table(&["NAME", "DESCRIPTION"], &rows);
+
+    let mut reader = csv::Reader::from_reader(output.as_bytes());
+    assert_eq!(reader.headers().unwrap(), vec!["NAME", "DESCRIPTION"]);
+    let records: Vec<Vec<String>> = reader
+        .records()
+        .map(|record| record.unwrap().iter().map(str::to_string).collect())
+        .collect();
+    assert_eq!(records, rows);
+
+    // Serializable rows use their keys as the header
+    let output = formatter
+        .format_rows(&[serde_json::json!({"name": "a,b", "valid": true})])
+        .unwrap();
+    let mut reader = csv::Reader::from_reader(output.as_bytes());
+    assert_eq!(reader.headers().unwrap(), vec!["name", "valid"]);
+    let record = reader.records().next().unwrap().unwrap();
+    assert_eq!(record, vec!["a,b", "true"]);
+}
+
 // ============================================================================
 // Phase 3: Input Parsing Tests
 // ============================================================================
@@ -148,16 +181,28 @@ fn test_parse_threshold_valid() {
 #[test]
 fn test_parse_threshold_different_formats() {
     // Test parsing different threshold formats
-    // Only "3-of-5" format is supported
     let result = parse_threshold("3-of-5");
     assert!(result.is_ok());
     let (threshold, total) = result.unwrap();
     assert_eq!(threshold, 3);
     assert_eq!(total, 5);
 
+    // Common alternative spellings are normalized
+    assert_eq!(parse_threshold("3/5").unwrap(), (3, 5));
+    assert_eq!(parse_threshold("3of5").unwrap(), (3, 5));
+    assert_eq!(parse_threshold("3 of 5").unwrap(), (3, 5));
+    assert_eq!(parse_threshold(" 6-OF-7 ").unwrap(), (6, 7));
+    assert_eq!(parse_threshold("3:5").unwrap(), (3, 5));
+    assert_eq!(parse_threshold("3,5").unwrap(), (3, 5));
+
     // Other formats should fail
-    assert!(parse_threshold("3/5").is_err());
-    assert!(parse_threshold("3:5").is_err());
+    assert!(parse_threshold("3-of-5-of-7").is_err());
+    assert!(parse_threshold("3;5").is_err());
+    let err = parse_threshold("foo").unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Invalid format: expected format 'M-of-N', 'M/N', or 'M:N', got 'foo'"
+    );
 }
 
 #[test]
@@ -187,6 +232,25 @@ fn test_parse_threshold_edge_cases() {
     }
 }
 
+#[test]
+fn test_parse_derivation_path_standards() {
+    // BIP44 (legacy), BIP49 (nested segwit), BIP84 (native segwit), BIP86 (taproot)
+    let cases = [
+        ("m/44'/0'/0'/0/0", 44, 0),
+        ("m/49'/0'/0'/0/1", 49, 1),
+        ("m/84'/1'/0'/1/2", 84, 2),
+        ("m/86'/0'/3'/0/9", 86, 9),
+    ];
+
+    for (path, purpose, index) in cases {
+        let indices = parse_derivation_path(path).unwrap();
+        assert_eq!(indices.len(), 5);
+        assert_eq!(indices[0], 0x8000_0000 | purpose);
+        assert_eq!(indices[4], index);
+        assert_eq!(format_derivation_path(&indices), path);
+    }
+}
+
 // ============================================================================
 // Phase 4: CLI Integration Tests
 // ============================================================================
@@ -238,18 +302,96 @@ fn test_cli_threshold_validation() {
     let (threshold, total) = result.unwrap();
     assert!(threshold <= total);
 
-    // Threshold can be 0 (parsing succeeds, validation happens elsewhere)
-    let result = parse_threshold("0-of-5");
-    // Parsing succeeds (validation happens at usage time)
-    assert!(result.is_ok());
-    let (threshold, total) = result.unwrap();
-    assert_eq!(threshold, 0);
-    assert_eq!(total, 5);
+    // Zero threshold or total is rejected at parse time
+    let err = parse_threshold("0-of-5").unwrap_err();
+    assert!(err.to_string().contains("threshold must be at least 1"));
+    assert!(parse_threshold("0-of-0").is_err());
+
+    // Threshold above total names both numbers
+    let err = parse_threshold("7-of-5").unwrap_err();
+    assert!(err.to_string().contains("threshold 7 exceeds total 5"));
+
+    // Implausibly large totals are treated as typos
+    assert!(parse_threshold("6-of-700").is_err());
+    assert!(parse_threshold("100-of-100").is_ok());
+}
+
+#[test]
+fn test_output_formatter_node_report() {
+    let report = NodeReport {
+        name: "test-node".to_string(),
+        overall_status: NodeStatus::Running,
+        module_count: 2,
+        healthy_count: 1,
+        degraded_count: 1,
+        unhealthy_count: 0,
+        modules: vec![
+            ModuleReport {
+                name: "lightning".to_string(),
+                version: "1.0.0".to_string(),
+                status: ModuleStatus::Running,
+                health: ModuleHealth::Healthy,
+                health_detail: None,
+            },
+            ModuleReport {
+                name: "privacy".to_string(),
+                version: "0.2.0".to_string(),
+                status: ModuleStatus::Running,
+                health: ModuleHealth::Degraded,
+                health_detail: None,
+            },
+        ],
+    };
+
+    let text = OutputFormatter::new(OutputFormat::Text).format_node_report(&report);
+    assert_eq!(
+        text,
+        "⚠️ Node: test-node (running)\n\
+         Modules: 2 total, 1 healthy, 1 degraded, 0 unhealthy\n  \
+         ✅ lightning 1.0.0: running, healthy\n  \
+         ⚠️ privacy 0.2.0: running, degraded\n"
+    );
+
+    for format in [OutputFormat::Json, OutputFormat::Ndjson] {
+        let formatted = OutputFormatter::new(format).format_node_report(&report);
+        let parsed: NodeReport = serde_json::from_str(&formatted).unwrap();
+        assert_eq!(parsed, report);
+    }
+
+    let table = OutputFormatter::new(OutputFormat::Table).format_node_report(&report);
+    assert!(table.starts_with("NAME"));
+    assert!(table.contains("degraded"));
 }
 
 // ============================================================================
 // Phase 5: Error Handling Tests
 // ============================================================================
+#[test]
+fn test_output_formatter_json_error_code() {
+    // Governance errors carry a stable code and category for automation
+    let error: Box<dyn Error> =
+        Box::new(GovernanceError::InsufficientSignatures { got: 1, need: 2 });
+    for format in [OutputFormat::Json, OutputFormat::Ndjson] {
+        let formatted = OutputFormatter::new(format).format_error(&*error);
+        let json: serde_json::Value = serde_json::from_str(&formatted).unwrap();
+        assert_eq!(json["error"], true);
+        assert_eq!(json["message"], "Insufficient signatures: got 1, need 2");
+        assert_eq!(json["code"], "E_SIG_INSUFFICIENT");
+        assert_eq!(json["category"], "multisig_logic");
+        assert_eq!(json["retryable"], false);
+    }
+
+    // Text output and the message are unchanged
+    let text = OutputFormatter::new(OutputFormat::Text).format_error(&*error);
+    assert_eq!(text, "Error: Insufficient signatures: got 1, need 2");
+
+    // Other errors have no code
+    let error: Box<dyn Error> = "plain failure".into();
+    let formatted = OutputFormatter::new(OutputFormat::Ndjson).format_error(&*error);
+    let json: serde_json::Value = serde_json::from_str(&formatted).unwrap();
+    assert_eq!(json["message"], "plain failure");
+    assert!(json.get("code").is_none());
+}
 
 #[test]
 fn test_output_formatter_error_handling() {
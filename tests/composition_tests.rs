@@ -2,14 +2,19 @@
 //!
 //! Tests for node composition, module registry, lifecycle, and configuration.
 
-use blvm_sdk::composition::config::NodeMetadata;
+use blvm_sdk::composition::config::{ModuleConfig, NodeMetadata};
 use blvm_sdk::composition::schema::validate_config_schema;
 use blvm_sdk::composition::validation::validate_composition;
 use blvm_sdk::composition::{
-    ModuleHealth, ModuleLifecycle, ModuleRegistry, ModuleSource, ModuleSpec, ModuleStatus,
-    NetworkType, NodeComposer, NodeConfig, NodeSpec, NodeStatus, Result, ValidationResult,
+    ComposedNode, CompositionError, CompositionHooks, DuplicatePolicy, Fetched, InstallMetadata,
+    LifecycleState, LoadedModule, ModuleHealth, ModuleInfo, ModuleLifecycle, ModuleProcessState,
+    ModuleRegistry, ModuleSource, ModuleSpec, ModuleStatus, NetworkType, NodeCheckpoint,
+    NodeComposer, NodeConfig, NodeLock, NodeSpec, NodeStatus, ProcessController, RegistryClient,
+    ResourceLimits, ResourceUsage, Result, SystemProcessController, ValidationResult,
+    CHECKPOINT_FORMAT_VERSION, LOCKFILE_FORMAT_VERSION, LOCKFILE_NAME,
 };
 use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
 use tempfile::TempDir;
 
 /// Test helper: Create a temporary directory for modules
@@ -66,6 +71,340 @@ fn test_module_registry_install_from_path() {
     assert!(result.is_err());
 }
 
+/// Test helper: Write a bare module manifest under `dir/<name>-<version>`
+fn write_module_manifest(dir: &std::path::Path, name: &str, version: &str) {
+    let module_dir = dir.join(format!("{}-{}", name, version));
+    std::fs::create_dir_all(&module_dir).unwrap();
+    std::fs::write(
+        module_dir.join("module.toml"),
+        format!(
+            "name = \"{}\"\nversion = \"{}\"\nentry_point = \"{}\"\n",
+            name, version, name
+        ),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_module_registry_multiple_dirs() {
+    let system = create_temp_modules_dir();
+    let user = create_temp_modules_dir();
+    write_module_manifest(system.path(), "indexer", "1.9.0");
+    write_module_manifest(user.path(), "indexer", "1.10.0");
+    write_module_manifest(user.path(), "lightning", "0.1.0");
+
+    let dirs = [system.path(), user.path()];
+    let version_of = |registry: &mut ModuleRegistry| {
+        registry.discover_modules().unwrap();
+        registry.get_module("indexer", None).unwrap().version
+    };
+
+    let mut registry = ModuleRegistry::new_multi(&dirs);
+    assert_eq!(version_of(&mut registry), "1.9.0");
+    assert_eq!(registry.list_modules().len(), 2);
+
+    let mut registry =
+        ModuleRegistry::new_multi(&dirs).with_duplicate_policy(DuplicatePolicy::LatestVersion);
+    assert_eq!(version_of(&mut registry), "1.10.0");
+
+    let mut registry =
+        ModuleRegistry::new_multi(&dirs).with_duplicate_policy(DuplicatePolicy::Error);
+    assert!(registry.discover_modules().is_err());
+
+    // Directories are searched once, and missing ones are skipped
+    let mut registry = ModuleRegistry::new(user.path());
+    registry.add_search_dir(user.path());
+    registry.add_search_dir(&system.path().join("missing"));
+    assert_eq!(registry.search_dirs().len(), 2);
+    assert_eq!(version_of(&mut registry), "1.10.0");
+}
+
+#[test]
+fn test_module_registry_remove_checks_dependents() {
+    let modules_dir = create_temp_modules_dir();
+    let write_module = |name: &str, dependencies: &[&str]| {
+        let module_dir = modules_dir.path().join(name);
+        std::fs::create_dir_all(&module_dir).unwrap();
+        let mut manifest = format!(
+            "name = \"{}\"\nversion = \"1.0.0\"\nentry_point = \"{}\"\n\n[dependencies]\n",
+            name, name
+        );
+        for dependency in dependencies {
+            manifest.push_str(&format!("{} = \"1.0.0\"\n", dependency));
+        }
+        std::fs::write(module_dir.join("module.toml"), manifest).unwrap();
+    };
+    write_module("core", &[]);
+    write_module("lightning", &["core"]);
+    write_module("watchtower", &["lightning"]);
+    write_module("indexer", &[]);
+
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    assert_eq!(registry.dependents_of("core"), vec!["lightning"]);
+    assert!(registry.dependents_of("indexer").is_empty());
+
+    // Direct and indirect dependents are reported, and nothing is deleted
+    match registry.remove_module("core") {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert_eq!(msg, "module core is required by lightning, watchtower")
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+    assert!(modules_dir.path().join("core").exists());
+
+    registry.remove_module("watchtower").unwrap();
+    assert!(!modules_dir.path().join("watchtower").exists());
+    assert!(registry.remove_module("core").is_err());
+
+    registry.remove_module_unchecked("core").unwrap();
+    assert!(!modules_dir.path().join("core").exists());
+    assert!(registry.get_module("core", None).is_err());
+}
+
+#[test]
+fn test_module_registry_provides_and_optional_dependencies() {
+    let modules_dir = create_temp_modules_dir();
+    let write_module = |name: &str, extra: &str| {
+        let module_dir = modules_dir.path().join(name);
+        std::fs::create_dir_all(&module_dir).unwrap();
+        std::fs::write(
+            module_dir.join("module.toml"),
+            format!(
+                "name = \"{}\"\nversion = \"1.0.0\"\nentry_point = \"{}\"\n{}",
+                name, name, extra
+            ),
+        )
+        .unwrap();
+    };
+    write_module("p2p", "provides = [\"bitcoin-p2p-v1\"]\n");
+    write_module("p2p-tor", "provides = [\"bitcoin-p2p-v1\", \"tor\"]\n");
+    write_module("indexer", "");
+    write_module(
+        "wallet",
+        "[dependencies]\nbitcoin-p2p-v1 = \"1.0.0\"\n\n[optional_dependencies]\nindexer = \"1.0.0\"\nhsm = \"1.0.0\"\n",
+    );
+    write_module("explorer", "[dependencies]\nblock-filters-v1 = \"1.0.0\"\n");
+
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+
+    let providers: Vec<&str> = registry
+        .what_provides("bitcoin-p2p-v1")
+        .iter()
+        .map(|m| m.name.as_str())
+        .collect();
+    assert_eq!(providers, vec!["p2p", "p2p-tor"]);
+    assert!(registry.what_provides("block-filters-v1").is_empty());
+    let wallet = registry.get_module("wallet", None).unwrap();
+    assert_eq!(wallet.optional_dependencies.len(), 2);
+
+    // The capability is satisfied by a provider, the installed optional
+    // dependency loads first, and the missing one is skipped
+    let order: Vec<String> = registry
+        .resolve_dependencies(&["wallet".to_string()])
+        .unwrap()
+        .into_iter()
+        .map(|m| m.name)
+        .collect();
+    assert_eq!(order, vec!["p2p", "indexer", "wallet"]);
+
+    match registry.resolve_dependencies(&["explorer".to_string()]) {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert!(msg.contains("block-filters-v1"), "{}", msg)
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+
+    let spec = NodeSpec {
+        name: "wallet-node".to_string(),
+        version: None,
+        network: NetworkType::Regtest,
+        modules: vec![ModuleSpec {
+            name: "wallet".to_string(),
+            version: None,
+            config: HashMap::new(),
+            enabled: true,
+        }],
+    };
+    let result = validate_composition(&spec, &registry).unwrap();
+    assert!(result.valid, "{:?}", result.errors);
+    assert_eq!(result.dependencies.len(), 3);
+}
+
+#[test]
+fn test_module_registry_version_requirements() {
+    let modules_dir = create_temp_modules_dir();
+    let write_module = |dir: std::path::PathBuf, name: &str, version: &str, extra: &str| {
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("module.toml"),
+            format!(
+                "name = \"{}\"\nversion = \"{}\"\nentry_point = \"{}\"\n{}",
+                name, version, name, extra
+            ),
+        )
+        .unwrap();
+    };
+    for version in ["1.0.0", "1.2.3", "2.0.0"] {
+        let dir = modules_dir.path().join("indexer").join(version);
+        write_module(dir, "indexer", version, "");
+    }
+    let dir = modules_dir.path().join("wallet");
+    write_module(
+        dir,
+        "wallet",
+        "1.0.0",
+        "[dependencies]\nindexer = \"^1.0\"\n",
+    );
+    let dir = modules_dir.path().join("explorer");
+    write_module(
+        dir,
+        "explorer",
+        "1.0.0",
+        "[dependencies]\nindexer = \"^3\"\n",
+    );
+    let dir = modules_dir.path().join("nightly");
+    write_module(dir, "nightly", "nightly", "");
+
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    assert_eq!(registry.list_modules().len(), 6);
+    assert_eq!(registry.discovery_warnings().len(), 1);
+    assert!(registry.discovery_warnings()[0].contains("'nightly'"));
+
+    let version_for = |requirement: Option<&str>| {
+        registry
+            .get_module("indexer", requirement)
+            .map(|module| module.version)
+    };
+    assert_eq!(version_for(None).unwrap(), "2.0.0");
+    assert_eq!(version_for(Some("^1")).unwrap(), "1.2.3");
+    assert_eq!(version_for(Some("~1.0")).unwrap(), "1.0.0");
+    assert_eq!(version_for(Some("=2.0.0")).unwrap(), "2.0.0");
+    assert_eq!(version_for(Some("1.*")).unwrap(), "1.2.3");
+    assert_eq!(version_for(Some("1.0.0")).unwrap(), "1.0.0");
+    match version_for(Some("^3")) {
+        Err(CompositionError::ModuleVersionNotFound(name, detail)) => {
+            assert_eq!(name, "indexer");
+            assert_eq!(detail, "^3 (available: 1.0.0, 1.2.3, 2.0.0)");
+        }
+        other => panic!("expected version not found, got {:?}", other),
+    }
+    assert_eq!(
+        registry
+            .get_module("nightly", Some("nightly"))
+            .unwrap()
+            .version,
+        "nightly"
+    );
+
+    // Dependency requirements pick the version to load
+    let resolved = registry
+        .resolve_dependencies(&["wallet".to_string()])
+        .unwrap();
+    let order: Vec<(&str, &str)> = resolved
+        .iter()
+        .map(|m| (m.name.as_str(), m.version.as_str()))
+        .collect();
+    assert_eq!(order, vec![("indexer", "1.2.3"), ("wallet", "1.0.0")]);
+
+    match registry.resolve_dependencies(&["explorer".to_string()]) {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert!(msg.contains("available: 1.0.0, 1.2.3, 2.0.0"), "{}", msg)
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+    // A version selected for one dependent must satisfy the next
+    match registry.resolve_dependencies(&["wallet".to_string(), "explorer".to_string()]) {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert_eq!(msg, "indexer 1.2.3 is selected, but indexer ^3 is required")
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_module_registry_resolve_detects_cycles() {
+    let modules_dir = create_temp_modules_dir();
+    for (name, dependency) in [("a", "b"), ("b", "c-api"), ("c", "a")] {
+        let module_dir = modules_dir.path().join(name);
+        std::fs::create_dir_all(&module_dir).unwrap();
+        let provides = if name == "c" {
+            "provides = [\"c-api\"]\n"
+        } else {
+            ""
+        };
+        std::fs::write(
+            module_dir.join("module.toml"),
+            format!(
+                "name = \"{}\"\nversion = \"1.0.0\"\nentry_point = \"{}\"\n{}\n[dependencies]\n{} = \"1.0.0\"\n",
+                name, name, provides, dependency
+            ),
+        )
+        .unwrap();
+    }
+
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    match registry.resolve_dependencies(&["a".to_string()]) {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert_eq!(msg, "circular dependency: a -> b -> c -> a")
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+}
+
+/// Test helper: Write a module with a signed manifest under `dir/name`
+#[cfg(unix)]
+fn write_signed_module(
+    dir: &std::path::Path,
+    name: &str,
+    keypair: &blvm_sdk::governance::GovernanceKeypair,
+) -> std::path::PathBuf {
+    use blvm_sdk::module::ModuleManifest;
+    use std::os::unix::fs::PermissionsExt;
+
+    let module_dir = dir.join(name);
+    std::fs::create_dir_all(&module_dir).unwrap();
+    let binary = module_dir.join(name);
+    std::fs::write(&binary, b"#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let mut manifest = ModuleManifest::from_toml_str(&format!(
+        "name = \"{name}\"\nversion = \"1.0.0\"\nentry_point = \"{name}\"\nsigning_public_key = \"{}\"\n",
+        keypair.public_key()
+    ))
+    .unwrap();
+    manifest.sign_binary(&binary, keypair).unwrap();
+    std::fs::write(
+        module_dir.join("module.toml"),
+        toml::to_string(&manifest).unwrap(),
+    )
+    .unwrap();
+    binary
+}
+
+#[test]
+#[cfg(unix)]
+fn test_module_registry_install_verifies_binary() {
+    let keypair = blvm_sdk::governance::GovernanceKeypair::generate().unwrap();
+    let source_dir = create_temp_modules_dir();
+    let binary = write_signed_module(source_dir.path(), "indexer", &keypair);
+
+    let modules_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    let module = registry
+        .install_module(ModuleSource::Path(source_dir.path().to_path_buf()))
+        .unwrap();
+    assert_eq!(module.name, "indexer");
+
+    // A tampered binary no longer matches the signed hash
+    std::fs::write(&binary, b"#!/bin/sh\nexit 1\n").unwrap();
+    let result = registry.install_module(ModuleSource::Path(source_dir.path().to_path_buf()));
+    assert!(result.is_err());
+}
+
 // ============================================================================
 // Phase 2: ModuleLifecycle Tests
 // ============================================================================
@@ -83,6 +422,292 @@ fn test_module_lifecycle_creation() {
     assert!(temp_dir.path().exists());
 }
 
+fn running_state(pid: u32) -> String {
+    let mut state = LifecycleState::default();
+    state.modules.insert(
+        "lightning".to_string(),
+        ModuleProcessState {
+            status: ModuleStatus::Running,
+            pid: Some(pid),
+        },
+    );
+    serde_json::to_string(&state).unwrap()
+}
+
+#[test]
+fn test_module_lifecycle_state_roundtrip() {
+    let temp_dir = create_temp_modules_dir();
+    let mut lifecycle = ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path()));
+
+    // Without a socket directory, cached status is restored as-is
+    lifecycle.import_state(&running_state(4242)).unwrap();
+    let state: LifecycleState = serde_json::from_str(&lifecycle.export_state().unwrap()).unwrap();
+    assert_eq!(state.modules["lightning"].status, ModuleStatus::Running);
+    assert_eq!(state.modules["lightning"].pid, Some(4242));
+}
+
+#[test]
+fn test_module_lifecycle_import_marks_dead_modules() {
+    let temp_dir = create_temp_modules_dir();
+    let mut lifecycle =
+        ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path())).with_socket_dir(temp_dir.path());
+
+    lifecycle.import_state(&running_state(4242)).unwrap();
+    let state: LifecycleState = serde_json::from_str(&lifecycle.export_state().unwrap()).unwrap();
+    assert_eq!(
+        state.modules["lightning"].status,
+        ModuleStatus::Error("process not found".to_string())
+    );
+    assert_eq!(state.modules["lightning"].pid, None);
+
+    // Nothing left to mark on a second pass
+    assert!(lifecycle.sync_state().unwrap().is_empty());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_module_lifecycle_sync_keeps_live_modules() {
+    let temp_dir = create_temp_modules_dir();
+    let _listener =
+        std::os::unix::net::UnixListener::bind(temp_dir.path().join("lightning.sock")).unwrap();
+    let mut lifecycle =
+        ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path())).with_socket_dir(temp_dir.path());
+
+    lifecycle.import_state(&running_state(4242)).unwrap();
+    assert!(lifecycle.sync_state().unwrap().is_empty());
+}
+
+#[test]
+fn test_module_lifecycle_state_file() {
+    let temp_dir = create_temp_modules_dir();
+    let state_file = temp_dir.path().join("lifecycle.json");
+
+    // Loading a missing state file is a no-op
+    let mut lifecycle =
+        ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path())).with_state_file(&state_file);
+    lifecycle.load_state().unwrap();
+
+    lifecycle.import_state(&running_state(7)).unwrap();
+    lifecycle.save_state().unwrap();
+
+    let mut restored =
+        ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path())).with_state_file(&state_file);
+    restored.load_state().unwrap();
+    assert_eq!(
+        restored.export_state().unwrap(),
+        lifecycle.export_state().unwrap()
+    );
+
+    // Without a configured state file, saving is rejected
+    let lifecycle = ModuleLifecycle::new(ModuleRegistry::new(temp_dir.path()));
+    assert!(lifecycle.save_state().is_err());
+}
+
+#[test]
+fn test_module_lifecycle_remove_refuses_running_and_dependents() {
+    let modules_dir = create_temp_modules_dir();
+    for (name, manifest) in [
+        ("core", ""),
+        ("lightning", "\n[dependencies]\ncore = \"1.0.0\"\n"),
+    ] {
+        let module_dir = modules_dir.path().join(name);
+        std::fs::create_dir_all(&module_dir).unwrap();
+        std::fs::write(
+            module_dir.join("module.toml"),
+            format!(
+                "name = \"{}\"\nversion = \"1.0.0\"\nentry_point = \"{}\"\n{}",
+                name, name, manifest
+            ),
+        )
+        .unwrap();
+    }
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    let mut lifecycle = ModuleLifecycle::new(registry);
+
+    // A running module is refused even with force
+    lifecycle.import_state(&running_state(4242)).unwrap();
+    assert!(matches!(
+        lifecycle.remove_module("lightning", true),
+        Err(CompositionError::InvalidConfiguration(_))
+    ));
+    assert!(modules_dir.path().join("lightning").exists());
+
+    // A dependency of another module is refused unless forced
+    match lifecycle.remove_module("core", false) {
+        Err(CompositionError::DependencyResolutionFailed(msg)) => {
+            assert_eq!(msg, "module core is required by lightning")
+        }
+        other => panic!("expected dependency error, got {:?}", other),
+    }
+    assert!(modules_dir.path().join("core").exists());
+
+    lifecycle.remove_module("core", true).unwrap();
+    assert!(!modules_dir.path().join("core").exists());
+    assert!(lifecycle.registry().get_module("core", None).is_err());
+}
+
+/// Test helper: Process layer that records applied limits instead of touching processes
+#[derive(Default)]
+struct MockProcessController {
+    applied: Mutex<Vec<(String, u32, ResourceLimits)>>,
+}
+
+impl ProcessController for MockProcessController {
+    fn apply_limits(&self, module_name: &str, pid: u32, limits: &ResourceLimits) -> Result<()> {
+        limits.validate()?;
+        self.applied
+            .lock()
+            .unwrap()
+            .push((module_name.to_string(), pid, limits.clone()));
+        Ok(())
+    }
+
+    fn resource_usage(&self, pid: u32) -> Result<ResourceUsage> {
+        Ok(ResourceUsage {
+            memory_bytes: pid as u64 * 1024,
+            cpu_time_ms: 10,
+            cpu_percent: 12.5,
+        })
+    }
+}
+
+fn block_on<F: std::future::Future>(future: F) -> F::Output {
+    tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap()
+        .block_on(future)
+}
+
+#[test]
+fn test_module_lifecycle_applies_resource_limits() {
+    let temp_dir = create_temp_modules_dir();
+    write_module_manifest(temp_dir.path(), "lightning", "0.1.0");
+    write_module_manifest(temp_dir.path(), "indexer", "1.0.0");
+    let mut registry = ModuleRegistry::new(temp_dir.path());
+    registry.discover_modules().unwrap();
+
+    let limits = ResourceLimits {
+        max_memory_bytes: Some(256 * 1024 * 1024),
+        max_cpu_percent: Some(50.0),
+        max_open_files: Some(128),
+    };
+    let controller = Arc::new(MockProcessController::default());
+    let mut lifecycle = ModuleLifecycle::new(registry)
+        .with_process_controller(controller.clone())
+        .with_resource_limits("lightning", limits.clone());
+    assert_eq!(lifecycle.resource_limits("lightning"), Some(&limits));
+    assert_eq!(lifecycle.resource_limits("indexer"), None);
+
+    lifecycle.set_process_id("lightning", 4242);
+    lifecycle.set_process_id("indexer", 4343);
+    block_on(lifecycle.start_module("lightning")).unwrap();
+    block_on(lifecycle.start_module("indexer")).unwrap();
+
+    // Only the module with limits is constrained, using its recorded process
+    assert_eq!(
+        *controller.applied.lock().unwrap(),
+        vec![("lightning".to_string(), 4242, limits)]
+    );
+
+    let usage = lifecycle.get_resource_usage("lightning").unwrap();
+    assert_eq!(usage.memory_bytes, 4242 * 1024);
+    assert_eq!(usage.cpu_percent, 12.5);
+
+    // No recorded process, no usage
+    block_on(lifecycle.stop_module("lightning")).unwrap();
+    assert!(lifecycle.get_resource_usage("lightning").is_err());
+}
+
+#[test]
+fn test_module_lifecycle_rejects_invalid_resource_limits() {
+    let temp_dir = create_temp_modules_dir();
+    write_module_manifest(temp_dir.path(), "lightning", "0.1.0");
+    let mut registry = ModuleRegistry::new(temp_dir.path());
+    registry.discover_modules().unwrap();
+
+    let limits = ResourceLimits {
+        max_cpu_percent: Some(-5.0),
+        ..Default::default()
+    };
+    let mut lifecycle = ModuleLifecycle::new(registry)
+        .with_process_controller(Arc::new(MockProcessController::default()))
+        .with_resource_limits("lightning", limits);
+    lifecycle.set_process_id("lightning", 4242);
+
+    assert!(block_on(lifecycle.start_module("lightning")).is_err());
+    assert!(matches!(
+        block_on(lifecycle.get_module_status("lightning")).unwrap(),
+        ModuleStatus::Error(_)
+    ));
+}
+
+#[test]
+fn test_module_lifecycle_creates_log_files() {
+    let temp_dir = create_temp_modules_dir();
+    let log_dir = create_temp_modules_dir();
+    write_module_manifest(temp_dir.path(), "lightning", "0.1.0");
+    let mut registry = ModuleRegistry::new(temp_dir.path());
+    registry.discover_modules().unwrap();
+
+    let mut lifecycle = ModuleLifecycle::new(registry).with_log_dir(log_dir.path().join("logs"));
+    let log_path = lifecycle.log_path("lightning").unwrap();
+    assert_eq!(log_path, log_dir.path().join("logs").join("lightning.log"));
+
+    block_on(lifecycle.start_module("lightning")).unwrap();
+    assert!(log_path.exists());
+    assert_eq!(
+        blvm_sdk::composition::logs::logged_modules(&log_dir.path().join("logs")).unwrap(),
+        vec!["lightning".to_string()]
+    );
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn test_system_process_controller_enforces_limits() {
+    let cgroup_root = create_temp_modules_dir();
+    std::fs::write(cgroup_root.path().join("cgroup.controllers"), "cpu memory").unwrap();
+
+    let mut child = std::process::Command::new("sleep")
+        .arg("30")
+        .spawn()
+        .unwrap();
+    let controller = SystemProcessController::new().with_cgroup_root(cgroup_root.path());
+    let limits = ResourceLimits {
+        max_memory_bytes: Some(1 << 30),
+        max_cpu_percent: Some(25.0),
+        max_open_files: Some(64),
+    };
+    let result = controller.apply_limits("lightning", child.id(), &limits);
+    let proc_limits = std::fs::read_to_string(format!("/proc/{}/limits", child.id())).unwrap();
+    let usage = controller.resource_usage(child.id());
+    child.kill().unwrap();
+    child.wait().unwrap();
+    result.unwrap();
+
+    let limit_line = |name: &str| {
+        proc_limits
+            .lines()
+            .find(|line| line.starts_with(name))
+            .map(|line| line.split_whitespace().collect::<Vec<_>>())
+            .unwrap()
+    };
+    assert!(limit_line("Max open files").contains(&"64"));
+    assert!(limit_line("Max address space").contains(&"1073741824"));
+
+    let cgroup = cgroup_root.path().join("blvm-lightning");
+    assert_eq!(
+        std::fs::read_to_string(cgroup.join("cpu.max")).unwrap(),
+        "25000 100000"
+    );
+    assert_eq!(
+        std::fs::read_to_string(cgroup.join("cgroup.procs")).unwrap(),
+        child.id().to_string()
+    );
+
+    assert!(usage.unwrap().memory_bytes > 0);
+}
+
 // ============================================================================
 // Phase 3: NodeConfig Tests
 // ============================================================================
@@ -178,6 +803,133 @@ fn test_node_config_invalid_network() {
     assert!(result.is_err());
 }
 
+#[test]
+fn test_node_config_from_toml_str() {
+    let config = NodeConfig::from_toml_str(
+        r#"
+[node]
+name = "test-node"
+network = "regtest"
+
+[modules.lightning]
+version = "0.1.0"
+config = { port = 9735 }
+"#,
+    )
+    .unwrap();
+
+    assert_eq!(config.node.name, "test-node");
+    let lightning = &config.modules["lightning"];
+    assert!(lightning.enabled);
+    assert_eq!(lightning.config["port"], toml::Value::Integer(9735));
+
+    // Parse errors surface as invalid configuration
+    let err = NodeConfig::from_toml_str("[node").unwrap_err();
+    assert!(matches!(err, CompositionError::InvalidConfiguration(_)));
+    assert!("[node".parse::<NodeConfig>().is_err());
+}
+
+#[test]
+fn test_node_config_example_roundtrip() {
+    let example = NodeConfig::example();
+    assert_eq!(example.modules.len(), 2);
+    assert!(example.modules.values().all(|m| m.enabled));
+    assert_eq!(example.to_spec().unwrap().modules.len(), 2);
+
+    let toml_string = example.to_toml_string().unwrap();
+    assert_eq!(example.to_string(), toml_string);
+
+    let parsed: NodeConfig = toml_string.parse().unwrap();
+    assert_eq!(parsed.node.name, example.node.name);
+    assert_eq!(parsed.node.version, example.node.version);
+    assert_eq!(parsed.node.network, example.node.network);
+    for (name, module) in &example.modules {
+        assert_eq!(parsed.modules[name].version, module.version);
+        assert_eq!(parsed.modules[name].config, module.config);
+    }
+}
+
+#[test]
+fn test_node_config_preflight_check() {
+    let modules_dir = create_temp_modules_dir();
+    let write_module = |name: &str, extra: &str| {
+        let module_dir = modules_dir.path().join(name);
+        std::fs::create_dir_all(&module_dir).unwrap();
+        std::fs::write(
+            module_dir.join("module.toml"),
+            format!(
+                "name = \"{}\"\nversion = \"1.0.0\"\nentry_point = \"{}\"\n{}",
+                name, name, extra
+            ),
+        )
+        .unwrap();
+    };
+    write_module(
+        "lightning",
+        "networks = [\"testnet\", \"regtest\"]\n\n[dependencies]\nwatchtower = \"1.0.0\"\n\n[config_schema]\nport = \"Listen port\"\n",
+    );
+    write_module("indexer", "");
+
+    let module = |config: &[(&str, toml::Value)]| ModuleConfig {
+        enabled: true,
+        version: Some("1.0.0".to_string()),
+        config: config
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.clone()))
+            .collect(),
+    };
+    let mut config = NodeConfig {
+        node: NodeMetadata {
+            name: "test-node".to_string(),
+            version: None,
+            network: "regtest".to_string(),
+        },
+        modules: HashMap::from([
+            ("indexer".to_string(), module(&[])),
+            (
+                "lightning".to_string(),
+                module(&[("port", toml::Value::Integer(9735))]),
+            ),
+        ]),
+    };
+
+    // Only the missing dependency is reported
+    let result = config.preflight_check(modules_dir.path()).unwrap();
+    assert!(!result.valid);
+    assert!(
+        result.errors.iter().all(|e| e.contains("watchtower")),
+        "{:?}",
+        result.errors
+    );
+
+    write_module("watchtower", "");
+    assert!(config.preflight_check(modules_dir.path()).unwrap().valid);
+
+    // Unknown config keys and unsupported networks are errors
+    config.node.network = "mainnet".to_string();
+    config.modules.insert(
+        "lightning".to_string(),
+        module(&[("rpc_port", toml::Value::Integer(10009))]),
+    );
+    let result = config.preflight_check(modules_dir.path()).unwrap();
+    assert_eq!(result.errors.len(), 2, "{:?}", result.errors);
+    assert!(result.errors[0].contains("unknown config key 'rpc_port'"));
+    assert!(result.errors[1].contains("does not support mainnet"));
+
+    // Modules without a schema only warn about their config
+    config.modules.insert(
+        "indexer".to_string(),
+        module(&[("threads", toml::Value::Integer(4))]),
+    );
+    let result = config.preflight_check(modules_dir.path()).unwrap();
+    assert!(result.has_warnings());
+
+    // An invalid network stops before the registry checks
+    config.node.network = "signet".to_string();
+    let result = config.preflight_check(modules_dir.path()).unwrap();
+    assert_eq!(result.errors.len(), 1);
+}
+
 // ============================================================================
 // Phase 4: NodeSpec Tests
 // ============================================================================
@@ -224,6 +976,70 @@ fn test_node_spec_with_modules() {
     assert!(!spec.modules[1].enabled);
 }
 
+#[test]
+fn test_node_spec_validate_structure() {
+    let module = |name: &str, version: Option<&str>| ModuleSpec {
+        name: name.to_string(),
+        version: version.map(str::to_string),
+        enabled: true,
+        config: HashMap::new(),
+    };
+    let mut spec = NodeSpec {
+        name: "test-node".to_string(),
+        version: Some("1.2.3-rc.1+build.5".to_string()),
+        network: NetworkType::Regtest,
+        modules: vec![module("lightning", Some("0.10.0")), module("privacy", None)],
+    };
+    assert!(spec.validate_structure().is_empty());
+    assert!(spec.assert_valid().is_ok());
+
+    for version in ["1", "^1.2", "~1.2.3", "=1.2.3", "1.*", ">=1.0, <2", "*"] {
+        assert!(module("lightning", Some(version))
+            .validate_structure()
+            .is_empty());
+    }
+    for version in ["1.0.0.0", "01.0.0", "1.0.0-", "latest"] {
+        assert_eq!(
+            module("lightning", Some(version))
+                .validate_structure()
+                .len(),
+            1
+        );
+    }
+
+    spec.name = "n".repeat(65);
+    spec.version = Some("v1".to_string());
+    spec.modules.push(module("lightning", Some("1.0.0")));
+    spec.modules.push(module("", None));
+    let errors = spec.validate_structure();
+    assert_eq!(errors.len(), 4, "{:?}", errors);
+    assert!(errors.iter().any(|e| e.contains("longer than 64")));
+    assert!(errors
+        .iter()
+        .any(|e| e.contains("'lightning' is listed more than once")));
+    assert!(matches!(
+        spec.assert_valid(),
+        Err(CompositionError::ValidationFailed(_))
+    ));
+
+    spec.name = String::new();
+    assert!(spec.validate_structure()[0].contains("must not be empty"));
+}
+
+#[test]
+fn test_compose_node_rejects_invalid_spec() {
+    // Structural errors are reported before any module is looked up
+    let mut composer = NodeComposer::new(create_temp_modules_dir().path());
+    let mut spec = composed_node().spec;
+    spec.name = String::new();
+
+    let result = block_on(composer.compose_node(spec));
+    assert!(matches!(
+        result,
+        Err(CompositionError::ValidationFailed(ref msg)) if msg.contains("Node name")
+    ));
+}
+
 // ============================================================================
 // Phase 5: ModuleSpec Tests
 // ============================================================================
@@ -273,6 +1089,52 @@ fn test_module_spec_with_config() {
     assert_eq!(module_spec.config.len(), 2);
 }
 
+#[test]
+fn test_module_spec_config_accessors() {
+    let mut module_spec = ModuleSpec {
+        name: "test-module".to_string(),
+        version: None,
+        enabled: true,
+        config: HashMap::new(),
+    }
+    .with_config_value("host", "127.0.0.1")
+    .with_config_value("port", 8332)
+    .with_config_value("verbose", true);
+
+    assert_eq!(module_spec.config_string("host"), Some("127.0.0.1"));
+    assert_eq!(module_spec.config_integer("port"), Some(8332));
+    assert_eq!(module_spec.config_bool("verbose"), Some(true));
+
+    // Absent keys
+    assert_eq!(module_spec.config_string("missing"), None);
+    assert_eq!(module_spec.config_integer("missing"), None);
+    assert_eq!(module_spec.config_bool("missing"), None);
+
+    // Present keys of the wrong type
+    assert_eq!(module_spec.config_string("port"), None);
+    assert_eq!(module_spec.config_integer("verbose"), None);
+    assert_eq!(module_spec.config_bool("host"), None);
+
+    module_spec.set_config_value("port", 18332);
+    assert_eq!(module_spec.config_integer("port"), Some(18332));
+
+    assert_eq!(
+        module_spec.remove_config_value("verbose"),
+        Some(serde_json::json!(true))
+    );
+    assert_eq!(module_spec.remove_config_value("verbose"), None);
+    assert_eq!(module_spec.config_bool("verbose"), None);
+
+    let mut other = HashMap::new();
+    other.insert("port".to_string(), serde_json::json!(8333));
+    other.insert("verbose".to_string(), serde_json::json!(false));
+    module_spec.merge_config(&other);
+    assert_eq!(module_spec.config_string("host"), Some("127.0.0.1"));
+    assert_eq!(module_spec.config_integer("port"), Some(8333));
+    assert_eq!(module_spec.config_bool("verbose"), Some(false));
+    assert_eq!(module_spec.config.len(), 3);
+}
+
 // ============================================================================
 // Phase 6: NetworkType Tests
 // ============================================================================
@@ -533,6 +1395,76 @@ fn test_node_composer_validate_composition() {
     assert!(result.valid);
 }
 
+#[test]
+fn test_compose_with_lockfile_detects_drift() {
+    let modules_dir = create_temp_modules_dir();
+    let lock_dir = TempDir::new().unwrap();
+    let lockfile = lock_dir.path().join(LOCKFILE_NAME);
+    let install = |version: &str, binary: &str| {
+        write_module_manifest(modules_dir.path(), "indexer", version);
+        let module_dir = modules_dir.path().join(format!("indexer-{}", version));
+        std::fs::write(module_dir.join("indexer"), binary).unwrap();
+    };
+    let compose = |update_lock: bool| {
+        let mut composer = NodeComposer::new(modules_dir.path())
+            .with_lockfile(&lockfile)
+            .with_lock_update(update_lock);
+        block_on(composer.compose_node(NodeSpec {
+            name: "locked-node".to_string(),
+            version: None,
+            network: NetworkType::Regtest,
+            modules: vec![ModuleSpec {
+                name: "indexer".to_string(),
+                version: None,
+                enabled: true,
+                config: HashMap::new(),
+            }],
+        }))
+    };
+    install("1.0.0", "indexer 1.0.0");
+
+    // The first composition writes the lockfile; later ones check it
+    compose(false).unwrap();
+    let lock = NodeLock::load(&lockfile).unwrap();
+    assert_eq!(lock.format_version, LOCKFILE_FORMAT_VERSION);
+    let locked = lock.module_by_name("indexer").unwrap().clone();
+    assert_eq!(locked.version, "1.0.0");
+    assert!(locked.sha256.is_some());
+    compose(false).unwrap();
+
+    // A newer installed version is drift
+    install("1.1.0", "indexer 1.1.0");
+    match compose(false) {
+        Err(CompositionError::LockfileMismatch(msg)) => {
+            assert_eq!(msg, "indexer is locked at 1.0.0 but resolved to 1.1.0")
+        }
+        other => panic!("expected lockfile mismatch, got {:?}", other),
+    }
+    std::fs::remove_dir_all(modules_dir.path().join("indexer-1.1.0")).unwrap();
+
+    // So is a changed binary at the locked version
+    std::fs::write(
+        modules_dir.path().join("indexer-1.0.0").join("indexer"),
+        "patched",
+    )
+    .unwrap();
+    match compose(false) {
+        Err(CompositionError::LockfileMismatch(msg)) => {
+            assert!(msg.starts_with("indexer 1.0.0 binary hash is "), "{}", msg)
+        }
+        other => panic!("expected lockfile mismatch, got {:?}", other),
+    }
+
+    // Updating the lockfile accepts the change
+    compose(true).unwrap();
+    let relocked = NodeLock::load(&lockfile).unwrap();
+    assert_ne!(
+        relocked.module_by_name("indexer").unwrap().sha256,
+        locked.sha256
+    );
+    compose(false).unwrap();
+}
+
 // ============================================================================
 // Phase 11: ModuleSource Tests
 // ============================================================================
@@ -554,11 +1486,13 @@ fn test_module_source_path() {
 #[test]
 fn test_module_source_registry() {
     // Test ModuleSource::Registry variant
-    let source = ModuleSource::Registry("https://example.com/registry".to_string());
+    let source = ModuleSource::registry("https://example.com/registry", "indexer");
 
     match source {
-        ModuleSource::Registry(url) => {
+        ModuleSource::Registry { url, name, version } => {
             assert_eq!(url, "https://example.com/registry");
+            assert_eq!(name, "indexer");
+            assert_eq!(version, None);
         }
         _ => panic!("Expected Registry variant"),
     }
@@ -566,21 +1500,406 @@ fn test_module_source_registry() {
 
 #[test]
 fn test_module_source_git() {
-    // Test ModuleSource::Git variant
-    let source = ModuleSource::Git {
-        url: "https://github.com/example/repo".to_string(),
-        tag: Some("v1.0.0".to_string()),
-    };
+    // Test ModuleSource::Git variant and its constructors
+    let url = "https://github.com/example/repo";
+    let cases = [
+        (
+            ModuleSource::git_tag(url, "v1.0.0"),
+            GitReference::Tag("v1.0.0".to_string()),
+            "refs/tags/v1.0.0",
+        ),
+        (
+            ModuleSource::git_branch(url, "main"),
+            GitReference::Branch("main".to_string()),
+            "refs/heads/main",
+        ),
+        (
+            ModuleSource::git_commit(url, "a".repeat(40)),
+            GitReference::Commit("a".repeat(40)),
+            "aaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaaa",
+        ),
+    ];
+
+    for (source, expected, refspec) in cases {
+        match source {
+            ModuleSource::Git {
+                url: source_url,
+                reference,
+            } => {
+                assert_eq!(source_url, url);
+                assert_eq!(reference.refspec(), refspec);
+                assert_eq!(reference, expected);
+            }
+            _ => panic!("Expected Git variant"),
+        }
+    }
 
-    match source {
-        ModuleSource::Git { url, tag } => {
-            assert_eq!(url, "https://github.com/example/repo");
-            assert_eq!(tag, Some("v1.0.0".to_string()));
+    assert_eq!(GitReference::default(), GitReference::Latest);
+    assert_eq!(GitReference::Latest.refspec(), "HEAD");
+}
+
+/// Test helper: Run git in `dir` with a fixed identity
+#[cfg(unix)]
+fn git(dir: &std::path::Path, args: &[&str]) {
+    let status = std::process::Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+        .args(args)
+        .status()
+        .unwrap();
+    assert!(status.success(), "git {:?} failed", args);
+}
+
+/// Test helper: Bare repository holding module `name` 1.0.0 (tag v1.0.0)
+/// with a committed binary
+#[cfg(unix)]
+fn bare_module_repo(dir: &std::path::Path, name: &str) -> std::path::PathBuf {
+    bare_module_repo_with_entry_point(dir, name, name)
+}
+
+/// Test helper: Like `bare_module_repo`, with the manifest's `entry_point`
+/// set to `entry_point`
+#[cfg(unix)]
+fn bare_module_repo_with_entry_point(
+    dir: &std::path::Path,
+    name: &str,
+    entry_point: &str,
+) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let work = dir.join("work");
+    std::fs::create_dir_all(&work).unwrap();
+    git(&work, &["init", "-q", "-b", "main"]);
+    std::fs::write(
+        work.join("module.toml"),
+        format!("name = \"{name}\"\nversion = \"1.0.0\"\nentry_point = \"{entry_point}\"\n"),
+    )
+    .unwrap();
+    let binary = work.join(name);
+    std::fs::write(&binary, b"#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+    git(&work, &["add", "."]);
+    git(&work, &["commit", "-q", "-m", "release"]);
+    git(&work, &["tag", "-a", "v1.0.0", "-m", "v1.0.0"]);
+
+    let bare = dir.join(format!("{}.git", name));
+    git(
+        dir,
+        &[
+            "clone",
+            "-q",
+            "--bare",
+            work.to_str().unwrap(),
+            bare.to_str().unwrap(),
+        ],
+    );
+    bare
+}
+
+#[test]
+#[cfg(unix)]
+fn test_install_module_from_git() {
+    let remote = TempDir::new().unwrap();
+    let bare = bare_module_repo(remote.path(), "indexer");
+    let modules_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+
+    let url = format!("git+file://{}", bare.display());
+    let module = registry
+        .install_module(ModuleSource::git_tag(&url, "v1.0.0"))
+        .unwrap();
+    assert_eq!(module.name, "indexer");
+    assert_eq!(module.version, "1.0.0");
+
+    let installed = modules_dir.path().join("indexer").join("1.0.0");
+    assert!(installed.join("module.toml").is_file());
+    assert!(installed.join("indexer").is_file());
+    // Only the module is installed, not the checkout
+    assert!(!installed.join(".git").exists());
+    assert_eq!(
+        registry.get_module("indexer", None).unwrap().version,
+        "1.0.0"
+    );
+
+    // The same version cannot be installed twice
+    let result = registry.install_module(ModuleSource::git_branch(&url, "main"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::InstallationFailed(ref msg)) if msg.contains("already installed")
+    ));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_start_module_checks_binary_integrity() {
+    let remote = TempDir::new().unwrap();
+    let bare = bare_module_repo(remote.path(), "indexer");
+    let modules_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    let url = format!("git+file://{}", bare.display());
+    let module = registry
+        .install_module(ModuleSource::git_tag(&url, "v1.0.0"))
+        .unwrap();
+
+    let installed = modules_dir.path().join("indexer").join("1.0.0");
+    let record = InstallMetadata::load(&installed).unwrap().unwrap();
+    assert_eq!(record.version, "1.0.0");
+    assert!(matches!(record.source, ModuleSource::Git { .. }));
+    assert_eq!(module.installed_sha256, Some(record.sha256));
+    registry.verify_integrity("indexer").unwrap();
+
+    // Corrupt one byte of the installed binary
+    let binary = installed.join("indexer");
+    let mut bytes = std::fs::read(&binary).unwrap();
+    *bytes.last_mut().unwrap() ^= 0x01;
+    std::fs::write(&binary, bytes).unwrap();
+
+    let mut lifecycle = ModuleLifecycle::new(registry);
+    let result = block_on(lifecycle.start_module("indexer"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::IntegrityCheckFailed(ref name, _)) if name == "indexer"
+    ));
+    assert!(matches!(
+        block_on(lifecycle.get_module_status("indexer")).unwrap(),
+        ModuleStatus::Error(_)
+    ));
+
+    // The check can be skipped explicitly
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    let mut lifecycle = ModuleLifecycle::new(registry).with_integrity_check(false);
+    block_on(lifecycle.start_module("indexer")).unwrap();
+}
+
+#[test]
+fn test_install_module_from_git_failures() {
+    let temp_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(temp_dir.path());
+
+    // git's stderr is surfaced, and nothing is left in the modules directory
+    let elsewhere = TempDir::new().unwrap();
+    let missing = elsewhere.path().join("no-such-repo");
+    let result =
+        registry.install_module(ModuleSource::git_tag(missing.to_string_lossy(), "v1.0.0"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::InstallationFailed(ref msg)) if msg.contains("git fetch failed")
+    ));
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+    // Malformed commit SHAs are rejected before running git
+    let result = registry.install_module(ModuleSource::git_commit(
+        "git+https://github.com/example/repo",
+        "abc123",
+    ));
+    assert!(matches!(
+        result,
+        Err(CompositionError::InstallationFailed(_))
+    ));
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+
+    // Entry points may not reach outside the checkout or the install directory
+    let remote = TempDir::new().unwrap();
+    let bare = bare_module_repo_with_entry_point(remote.path(), "indexer", "../../x");
+    let url = format!("git+file://{}", bare.display());
+    let result = registry.install_module(ModuleSource::git_tag(&url, "v1.0.0"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::InstallationFailed(ref msg)) if msg.contains("relative path")
+    ));
+    assert_eq!(std::fs::read_dir(temp_dir.path()).unwrap().count(), 0);
+    assert!(!temp_dir.path().parent().unwrap().join("x").exists());
+}
+
+/// In-memory registry: URL -> (body, ETag), recording each request's If-None-Match
+#[derive(Clone, Default)]
+struct FakeRegistry {
+    files: Arc<Mutex<HashMap<String, (Vec<u8>, Option<String>)>>>,
+    requests: Arc<Mutex<Vec<(String, Option<String>)>>>,
+}
+
+impl FakeRegistry {
+    fn serve(&self, url: &str, body: Vec<u8>, etag: Option<&str>) {
+        self.files
+            .lock()
+            .unwrap()
+            .insert(url.to_string(), (body, etag.map(str::to_string)));
+    }
+}
+
+impl RegistryClient for FakeRegistry {
+    fn get(&self, url: &str, etag: Option<&str>) -> Result<Fetched> {
+        self.requests
+            .lock()
+            .unwrap()
+            .push((url.to_string(), etag.map(str::to_string)));
+        match self.files.lock().unwrap().get(url) {
+            Some((_, Some(current))) if etag == Some(current.as_str()) => Ok(Fetched::NotModified),
+            Some((body, current)) => Ok(Fetched::Body {
+                body: body.clone(),
+                etag: current.clone(),
+            }),
+            None => Err(CompositionError::RegistryUnavailable(format!(
+                "{}: HTTP 404",
+                url
+            ))),
         }
-        _ => panic!("Expected Git variant"),
     }
 }
 
+const FAKE_REGISTRY: &str = "https://modules.example";
+
+/// Test helper: Serve an index listing `name` 1.0.0 and its archive
+///
+/// Returns the archive bytes; `sha256` overrides the listed hash.
+#[cfg(unix)]
+fn publish_module(registry: &FakeRegistry, name: &str, sha256: Option<&str>) -> Vec<u8> {
+    use sha2::{Digest, Sha256};
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = TempDir::new().unwrap();
+    let module_dir = dir.path().join(name);
+    std::fs::create_dir(&module_dir).unwrap();
+    let manifest = format!(
+        "name = \"{name}\"\nversion = \"1.0.0\"\nentry_point = \"{name}\"\ndescription = \"Address index\"\n"
+    );
+    std::fs::write(module_dir.join("module.toml"), &manifest).unwrap();
+    let binary = module_dir.join(name);
+    std::fs::write(&binary, b"#!/bin/sh\nexit 0\n").unwrap();
+    std::fs::set_permissions(&binary, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+    let archive_path = dir.path().join("module.tar.gz");
+    let status = std::process::Command::new("tar")
+        .arg("-czf")
+        .arg(&archive_path)
+        .arg("-C")
+        .arg(dir.path())
+        .arg(name)
+        .status()
+        .unwrap();
+    assert!(status.success());
+    let archive = std::fs::read(&archive_path).unwrap();
+
+    let index = serde_json::json!([{
+        "name": name,
+        "version": "1.0.0",
+        "sha256": sha256.map_or_else(|| hex::encode(Sha256::digest(&archive)), str::to_string),
+        "url": format!("{}-1.0.0.tar.gz", name),
+        "manifest": toml::from_str::<serde_json::Value>(&manifest).unwrap(),
+    }]);
+    registry.serve(
+        &format!("{}/index.json", FAKE_REGISTRY),
+        serde_json::to_vec(&index).unwrap(),
+        Some("\"v1\""),
+    );
+    registry.serve(
+        &format!("{}/{}-1.0.0.tar.gz", FAKE_REGISTRY, name),
+        archive.clone(),
+        None,
+    );
+    archive
+}
+
+#[test]
+#[cfg(unix)]
+fn test_install_module_from_registry() {
+    let fake = FakeRegistry::default();
+    publish_module(&fake, "indexer", None);
+    let modules_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(modules_dir.path())
+        .with_registry_client(fake.clone())
+        .with_registry_cache(None);
+
+    let module = registry
+        .install_module(ModuleSource::registry(FAKE_REGISTRY, "indexer"))
+        .unwrap();
+    assert_eq!(module.name, "indexer");
+    assert_eq!(module.version, "1.0.0");
+    let installed = modules_dir.path().join("indexer").join("1.0.0");
+    assert!(installed.join("module.toml").is_file());
+    assert!(installed.join("indexer").is_file());
+    assert!(registry.get_module("indexer", Some("1.0.0")).is_ok());
+}
+
+#[test]
+#[cfg(unix)]
+fn test_install_module_from_registry_failures() {
+    let fake = FakeRegistry::default();
+    publish_module(&fake, "indexer", Some(&"00".repeat(32)));
+    let modules_dir = create_temp_modules_dir();
+    let mut registry = ModuleRegistry::new(modules_dir.path())
+        .with_registry_client(fake)
+        .with_registry_cache(None);
+
+    let result = registry.install_module(ModuleSource::registry(FAKE_REGISTRY, "lightning"));
+    assert!(matches!(result, Err(CompositionError::ModuleNotFound(_))));
+
+    let result = registry.install_module(ModuleSource::Registry {
+        url: FAKE_REGISTRY.to_string(),
+        name: "indexer".to_string(),
+        version: Some("2.0.0".to_string()),
+    });
+    assert!(matches!(
+        result,
+        Err(CompositionError::ModuleVersionNotFound(ref name, ref version))
+            if name == "indexer" && version == "2.0.0"
+    ));
+
+    // The listed hash does not match the archive
+    let result = registry.install_module(ModuleSource::registry(FAKE_REGISTRY, "indexer"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::ChecksumMismatch(ref module, ref expected, _))
+            if module == "indexer 1.0.0" && *expected == "00".repeat(32)
+    ));
+
+    let result = registry.install_module(ModuleSource::registry("https://down.example", "indexer"));
+    assert!(matches!(
+        result,
+        Err(CompositionError::RegistryUnavailable(_))
+    ));
+
+    assert_eq!(std::fs::read_dir(modules_dir.path()).unwrap().count(), 0);
+}
+
+#[test]
+#[cfg(unix)]
+fn test_search_remote_revalidates_cached_index() {
+    let fake = FakeRegistry::default();
+    publish_module(&fake, "indexer", None);
+    let cache = TempDir::new().unwrap();
+    let registry = ModuleRegistry::new(create_temp_modules_dir().path())
+        .with_registry_client(fake.clone())
+        .with_registry_cache(Some(cache.path().to_path_buf()));
+
+    let found = registry.search_remote(FAKE_REGISTRY, "INDEX").unwrap();
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "indexer");
+    assert_eq!(
+        found[0].manifest.description.as_deref(),
+        Some("Address index")
+    );
+    assert!(registry
+        .search_remote(FAKE_REGISTRY, "lightning")
+        .unwrap()
+        .is_empty());
+
+    // The second search sent the cached ETag and was answered from the cache
+    let requests = fake.requests.lock().unwrap().clone();
+    assert_eq!(
+        requests,
+        vec![
+            (format!("{}/index.json", FAKE_REGISTRY), None),
+            (
+                format!("{}/index.json", FAKE_REGISTRY),
+                Some("\"v1\"".to_string())
+            ),
+        ]
+    );
+}
+
 // ============================================================================
 // Phase 12: ValidationResult Tests
 // ============================================================================
@@ -613,3 +1932,363 @@ fn test_validation_result_invalid() {
     assert_eq!(result.errors.len(), 2);
     assert_eq!(result.warnings.len(), 1);
 }
+
+#[test]
+fn test_validation_result_merge() {
+    let schema = ValidationResult::from_warnings(vec!["Warning 1".to_string()]);
+    let composition = ValidationResult::from_errors(vec!["Error 1".to_string()]);
+
+    let merged = schema.merge(composition);
+    assert!(!merged.valid);
+    assert!(merged.has_errors());
+    assert!(merged.has_warnings());
+    assert_eq!(merged.error_count(), 1);
+    assert_eq!(merged.warning_count(), 1);
+}
+
+#[test]
+fn test_validation_result_from_empty_errors_is_valid() {
+    let result = ValidationResult::from_errors(vec![]);
+    assert!(result.valid);
+    assert!(!result.has_errors());
+    assert_eq!(result.warning_count(), 0);
+}
+
+#[test]
+fn test_validate_composition_merges_schema_errors() {
+    let temp_dir = create_temp_modules_dir();
+    let composer = NodeComposer::new(temp_dir.path());
+
+    let spec = NodeSpec {
+        name: String::new(),
+        version: None,
+        network: NetworkType::Regtest,
+        modules: vec![],
+    };
+
+    let result = composer.validate_composition(&spec).unwrap();
+    assert!(!result.valid);
+    assert!(result
+        .errors
+        .iter()
+        .any(|e| e.contains("Node name cannot be empty")));
+}
+
+// ============================================================================
+// ComposedNode Tests
+// ============================================================================
+
+fn loaded_module(name: &str, status: ModuleStatus, health: ModuleHealth) -> LoadedModule {
+    LoadedModule {
+        info: ModuleInfo {
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            description: None,
+            author: None,
+            capabilities: vec![],
+            dependencies: HashMap::new(),
+            optional_dependencies: HashMap::new(),
+            provides: Vec::new(),
+            entry_point: name.to_string(),
+            directory: None,
+            binary_path: None,
+            config_schema: HashMap::new(),
+            installed_sha256: None,
+        },
+        status,
+        health,
+    }
+}
+
+fn composed_node() -> ComposedNode {
+    ComposedNode {
+        spec: NodeSpec {
+            name: "test-node".to_string(),
+            version: None,
+            network: NetworkType::Regtest,
+            modules: vec![
+                ModuleSpec {
+                    name: "lightning".to_string(),
+                    version: None,
+                    enabled: true,
+                    config: HashMap::new(),
+                },
+                ModuleSpec {
+                    name: "privacy".to_string(),
+                    version: None,
+                    enabled: true,
+                    config: HashMap::new(),
+                },
+            ],
+        },
+        modules: vec![
+            loaded_module("lightning", ModuleStatus::Running, ModuleHealth::Healthy),
+            loaded_module("privacy", ModuleStatus::Stopped, ModuleHealth::Unknown),
+        ],
+        status: NodeStatus::Running,
+    }
+}
+
+#[test]
+fn test_composed_node_module_lookup() {
+    let mut node = composed_node();
+
+    assert!(node.module_by_name("lightning").is_some());
+    assert!(node.module_by_name("missing").is_none());
+
+    node.module_by_name_mut("privacy").unwrap().health = ModuleHealth::Healthy;
+    assert_eq!(
+        node.module_by_name("privacy").unwrap().health,
+        ModuleHealth::Healthy
+    );
+}
+
+#[test]
+fn test_composed_node_status_counts() {
+    let node = composed_node();
+
+    assert_eq!(node.modules_by_status(&ModuleStatus::Running).len(), 1);
+    assert_eq!(node.enabled_module_count(), 2);
+    assert_eq!(node.healthy_module_count(), 1);
+    assert!(!node.all_healthy());
+
+    let report = node.to_status_report();
+    assert!(report.contains("test-node"));
+    assert!(report.contains("lightning (1.0.0)"));
+}
+
+#[test]
+fn test_composed_node_report() {
+    let mut node = composed_node();
+    node.modules.push(loaded_module(
+        "rpc",
+        ModuleStatus::Running,
+        ModuleHealth::Unhealthy("timeout".to_string()),
+    ));
+
+    let report = node.to_report();
+    assert_eq!(report.overall_status, NodeStatus::Running);
+    assert_eq!(report.module_count, 3);
+    assert_eq!(report.healthy_count, 1);
+    assert_eq!(report.degraded_count, 0);
+    assert_eq!(report.unhealthy_count, 1);
+    assert_eq!(report.modules[2].health_detail.as_deref(), Some("timeout"));
+
+    let text = report.to_text();
+    assert!(text.starts_with("❌ Node: test-node (running)"));
+    assert!(text.contains("✅ lightning 1.0.0: running, healthy"));
+    assert!(text.contains("⚠️ privacy 1.0.0: stopped, unknown"));
+    assert!(text.contains("❌ rpc 1.0.0: running, unhealthy (timeout)"));
+
+    let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+    assert_eq!(json["unhealthy_count"], 1);
+    assert_eq!(json["modules"][0]["name"], "lightning");
+
+    node.modules.truncate(1);
+    assert!(node.to_report().to_text().starts_with("✅"));
+}
+
+#[test]
+fn test_composed_node_checkpoint_diff() {
+    let temp_dir = create_temp_modules_dir();
+    let checkpoint = temp_dir.path().join("checkpoint.json");
+    let mut node = composed_node();
+    node.save_checkpoint(&checkpoint).unwrap();
+
+    let saved = NodeCheckpoint::load(&checkpoint).unwrap();
+    assert_eq!(saved.format_version, CHECKPOINT_FORMAT_VERSION);
+    assert_eq!(saved.spec.name, "test-node");
+    assert_eq!(saved.modules.len(), 2);
+    assert!(node.diff_checkpoint(&checkpoint).unwrap().is_empty());
+
+    node.module_by_name_mut("privacy").unwrap().status = ModuleStatus::Running;
+    node.modules.retain(|m| m.info.name != "lightning");
+    node.modules.push(loaded_module(
+        "rpc",
+        ModuleStatus::Running,
+        ModuleHealth::Healthy,
+    ));
+    assert_eq!(
+        node.diff_checkpoint(&checkpoint).unwrap(),
+        vec![
+            "module lightning: removed",
+            "module privacy: status Stopped -> Running",
+            "module rpc: added (1.0.0)",
+        ]
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_restore_from_checkpoint_skips_live_modules() {
+    let modules_dir = create_temp_modules_dir();
+    write_module_manifest(modules_dir.path(), "lightning", "1.0.0");
+    write_module_manifest(modules_dir.path(), "privacy", "1.0.0");
+    let socket_dir = create_temp_modules_dir();
+    let checkpoint = socket_dir.path().join("checkpoint.json");
+    composed_node().save_checkpoint(&checkpoint).unwrap();
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+    let restore = || {
+        let mut composer = NodeComposer::new(modules_dir.path()).with_socket_dir(socket_dir.path());
+        composer.registry_mut().discover_modules().unwrap();
+        runtime
+            .block_on(composer.restore_from_checkpoint(&checkpoint))
+            .unwrap()
+    };
+
+    // Lightning still answers on its socket: its saved info is kept
+    let listener =
+        std::os::unix::net::UnixListener::bind(socket_dir.path().join("lightning.sock")).unwrap();
+    let node = restore();
+    let lightning = node.module_by_name("lightning").unwrap();
+    assert_eq!(lightning.status, ModuleStatus::Running);
+    assert!(lightning.info.directory.is_none());
+    let privacy = node.module_by_name("privacy").unwrap();
+    assert_eq!(privacy.status, ModuleStatus::Running);
+    assert!(privacy.info.directory.is_some());
+
+    // Without a live socket every module is restarted from the registry
+    drop(listener);
+    std::fs::remove_file(socket_dir.path().join("lightning.sock")).unwrap();
+    let node = restore();
+    assert!(node
+        .modules
+        .iter()
+        .all(|m| m.info.directory.is_some() && m.status == ModuleStatus::Running));
+}
+
+#[test]
+fn test_compose_with_hooks() {
+    let modules_dir = create_temp_modules_dir();
+    write_module_manifest(modules_dir.path(), "lightning", "1.0.0");
+    write_module_manifest(modules_dir.path(), "privacy", "1.0.0");
+    let spec = composed_node().spec;
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let hooks = |fail_pre: bool| {
+        let (pre, started, post, error) = (
+            events.clone(),
+            events.clone(),
+            events.clone(),
+            events.clone(),
+        );
+        CompositionHooks::new()
+            .pre_compose(move |spec| {
+                pre.lock().unwrap().push(format!("pre {}", spec.name));
+                if fail_pre {
+                    return Err(CompositionError::ValidationFailed("blocked".to_string()));
+                }
+                Ok(())
+            })
+            .on_module_started(move |module| {
+                started
+                    .lock()
+                    .unwrap()
+                    .push(format!("started {}", module.info.name));
+                Ok(())
+            })
+            .post_compose(move |node| {
+                post.lock()
+                    .unwrap()
+                    .push(format!("post {}", node.modules.len()));
+                Ok(())
+            })
+            .on_module_error(move |name, _| error.lock().unwrap().push(format!("error {}", name)))
+    };
+    let composer = || {
+        let mut composer = NodeComposer::new(modules_dir.path());
+        composer.registry_mut().discover_modules().unwrap();
+        composer
+    };
+
+    let node = block_on(composer().compose_with_hooks(spec.clone(), hooks(false))).unwrap();
+    assert_eq!(node.modules.len(), 2);
+    assert_eq!(
+        std::mem::take(&mut *events.lock().unwrap()),
+        vec![
+            "pre test-node",
+            "started lightning",
+            "started privacy",
+            "post 2"
+        ]
+    );
+
+    // A failing pre-compose hook aborts before any module starts
+    let result = block_on(composer().compose_with_hooks(spec.clone(), hooks(true)));
+    assert!(matches!(result, Err(CompositionError::ValidationFailed(_))));
+    assert_eq!(
+        std::mem::take(&mut *events.lock().unwrap()),
+        vec!["pre test-node"]
+    );
+
+    // A module that fails to start is reported and aborts the composition
+    let mut composer = composer();
+    let mut registry = ModuleRegistry::new(modules_dir.path());
+    registry.discover_modules().unwrap();
+    let invalid_limits = ResourceLimits {
+        max_cpu_percent: Some(-5.0),
+        ..Default::default()
+    };
+    *composer.lifecycle_mut() = ModuleLifecycle::new(registry)
+        .with_process_controller(Arc::new(MockProcessController::default()))
+        .with_resource_limits("privacy", invalid_limits);
+    composer.lifecycle_mut().set_process_id("privacy", 4242);
+    assert!(block_on(composer.compose_with_hooks(spec, hooks(false))).is_err());
+    assert_eq!(
+        *events.lock().unwrap(),
+        vec!["pre test-node", "started lightning", "error privacy"]
+    );
+}
+
+#[test]
+fn test_node_spec_descriptor_roundtrip() {
+    let mut config = HashMap::new();
+    config.insert("port".to_string(), serde_json::json!(9735));
+    config.insert("alias".to_string(), serde_json::json!("my-node"));
+    config.insert("label".to_string(), serde_json::json!("two words, comma"));
+    config.insert("tor".to_string(), serde_json::json!(true));
+    config.insert("numeric_string".to_string(), serde_json::json!("42"));
+
+    let spec = NodeSpec {
+        name: "node".to_string(),
+        version: None,
+        network: NetworkType::Testnet,
+        modules: vec![
+            ModuleSpec {
+                name: "lightning".to_string(),
+                version: Some("1.0".to_string()),
+                enabled: true,
+                config,
+            },
+            ModuleSpec {
+                name: "rpc".to_string(),
+                version: None,
+                enabled: false,
+                config: HashMap::new(),
+            },
+        ],
+    };
+
+    let descriptor = spec.to_descriptor();
+    assert!(descriptor.starts_with("node(testnet,[lightning@1.0{alias=my-node,"));
+    assert!(descriptor.ends_with(",!rpc])"));
+
+    let parsed = NodeSpec::from_descriptor(&descriptor).unwrap();
+    assert_eq!(parsed.network, spec.network);
+    assert_eq!(parsed.modules.len(), 2);
+    assert_eq!(parsed.modules[0].config, spec.modules[0].config);
+    assert_eq!(parsed.modules[0].version, spec.modules[0].version);
+    assert!(!parsed.modules[1].enabled);
+    assert_eq!(parsed.to_descriptor(), descriptor);
+}
+
+#[test]
+fn test_node_spec_descriptor_empty_modules() {
+    let spec = NodeSpec::from_descriptor("node(regtest,[])").unwrap();
+    assert_eq!(spec.network, NetworkType::Regtest);
+    assert!(spec.modules.is_empty());
+    assert_eq!(spec.to_descriptor(), "node(regtest,[])");
+}
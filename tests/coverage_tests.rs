@@ -6,6 +6,7 @@ use blvm_sdk::cli::input::{parse_base64, parse_comma_separated, parse_hex, parse
 use blvm_sdk::cli::output::{OutputFormat, OutputFormatter};
 use blvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Multisig, PublicKey, Signature};
 use blvm_sdk::{sign_message, verify_signature};
+use std::collections::HashMap;
 
 #[test]
 fn test_governance_keypair_display() {
@@ -79,15 +80,13 @@ fn test_governance_message_edge_cases() {
     let message = GovernanceMessage::Release {
         version: "".to_string(),
         commit_hash: "".to_string(),
+        artifact_hashes: HashMap::new(),
     };
-    let signing_bytes = message.to_signing_bytes();
-    assert_eq!(signing_bytes, b"RELEASE::");
+    let signing_bytes = message.message_bytes();
+    assert_eq!(signing_bytes, b"RELEASE:0::0:");
 
     // Test with unicode characters
-    let message = GovernanceMessage::BudgetDecision {
-        amount: 0,
-        purpose: "测试".to_string(),
-    };
+    let message = GovernanceMessage::single_budget("bc1qtest", 0, "测试");
     let signing_bytes = message.to_signing_bytes();
     assert!(signing_bytes.len() > 0);
 }
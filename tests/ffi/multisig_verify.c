@@ -0,0 +1,110 @@
+/*
+ * 2-of-3 governance verification through the C ABI.
+ *
+ * Compiled and run by tests/ffi_tests.rs. Exits non-zero on the first
+ * failed check. Keys are the secp256k1 scalars 1, 2 and 3, whose public
+ * keys are G, 2G and 3G.
+ */
+#include <stdio.h>
+#include <string.h>
+
+#include "bllvm.h"
+
+#define CHECK(cond)                                                          \
+    do {                                                                     \
+        if (!(cond)) {                                                       \
+            const char *err = bllvm_last_error_message();                    \
+            fprintf(stderr, "%s:%d: check failed: %s (last error: %s)\n",    \
+                    __FILE__, __LINE__, #cond, err ? err : "none");          \
+            return 1;                                                        \
+        }                                                                    \
+    } while (0)
+
+static const char *PUBKEYS[3] = {
+    "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5",
+    "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+};
+
+int main(void) {
+    const char *text = "RELEASE:v1.0.0:abc123";
+    const uint8_t *message = (const uint8_t *)text;
+    size_t message_len = strlen(text);
+    const uint8_t *tampered = (const uint8_t *)"RELEASE:v6.6.6:abc123";
+
+    uint8_t secret_keys[3][32];
+    memset(secret_keys, 0, sizeof(secret_keys));
+    for (int i = 0; i < 3; i++) {
+        secret_keys[i][31] = (uint8_t)(i + 1);
+    }
+
+    /* Parse the roster; the caller owns each key handle */
+    BllvmPublicKey *keys[3] = {NULL, NULL, NULL};
+    for (int i = 0; i < 3; i++) {
+        CHECK(bllvm_parse_public_key(PUBKEYS[i], &keys[i]) == BLLVM_STATUS_OK);
+    }
+
+    /* The roster copies the keys it is given */
+    BllvmMultisig *multisig = NULL;
+    CHECK(bllvm_multisig_new(2, (const BllvmPublicKey *const *)keys, 3, &multisig) ==
+          BLLVM_STATUS_OK);
+
+    /* Maintainers 1 and 3 sign */
+    BllvmSignature *sig1 = NULL;
+    BllvmSignature *sig3 = NULL;
+    CHECK(bllvm_sign_message(secret_keys[0], message, message_len, &sig1) == BLLVM_STATUS_OK);
+    CHECK(bllvm_sign_message(secret_keys[2], message, message_len, &sig3) == BLLVM_STATUS_OK);
+
+    /* Signatures survive a hex round trip; the caller frees the string */
+    char *hex = NULL;
+    CHECK(bllvm_signature_to_hex(sig3, &hex) == BLLVM_STATUS_OK);
+    CHECK(strlen(hex) == 128);
+    BllvmSignature *parsed = NULL;
+    CHECK(bllvm_parse_signature(hex, &parsed) == BLLVM_STATUS_OK);
+    bllvm_string_free(hex);
+
+    bool valid = false;
+    CHECK(bllvm_verify_signature(sig1, message, message_len, keys[0], &valid) ==
+          BLLVM_STATUS_OK);
+    CHECK(valid);
+    CHECK(bllvm_verify_signature(sig1, message, message_len, keys[1], &valid) ==
+          BLLVM_STATUS_OK);
+    CHECK(!valid);
+
+    /* 2 of 3 */
+    const BllvmSignature *sigs[2] = {sig1, parsed};
+    valid = false;
+    CHECK(bllvm_multisig_verify(multisig, message, message_len, sigs, 2, &valid) ==
+          BLLVM_STATUS_OK);
+    CHECK(valid);
+
+    /* The same signatures do not cover a different message */
+    CHECK(bllvm_multisig_verify(multisig, tampered, message_len, sigs, 2, &valid) ==
+          BLLVM_STATUS_OK);
+    CHECK(!valid);
+
+    /* Fewer signatures than the threshold is an error with a message */
+    CHECK(bllvm_multisig_verify(multisig, message, message_len, sigs, 1, &valid) ==
+          BLLVM_STATUS_MULTISIG_LOGIC);
+    CHECK(strstr(bllvm_last_error_message(), "Insufficient signatures") != NULL);
+
+    /* Bad input reports a status and leaves out pointers untouched */
+    BllvmPublicKey *bad = NULL;
+    CHECK(bllvm_parse_public_key("not hex", &bad) == BLLVM_STATUS_KEY_MANAGEMENT);
+    CHECK(bad == NULL);
+    CHECK(bllvm_parse_public_key(NULL, &bad) == BLLVM_STATUS_NULL_POINTER);
+    CHECK(bllvm_multisig_new(4, (const BllvmPublicKey *const *)keys, 3, &multisig) ==
+          BLLVM_STATUS_MULTISIG_LOGIC);
+
+    /* Every handle is released exactly once; null is ignored */
+    bllvm_signature_free(sig1);
+    bllvm_signature_free(sig3);
+    bllvm_signature_free(parsed);
+    bllvm_multisig_free(multisig);
+    for (int i = 0; i < 3; i++) {
+        bllvm_public_key_free(keys[i]);
+    }
+    bllvm_public_key_free(NULL);
+
+    return 0;
+}
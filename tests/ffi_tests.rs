@@ -0,0 +1,74 @@
+//! # FFI Tests
+//!
+//! Compile the C driver in `tests/ffi/` against the generated `bllvm.h`
+//! and the crate's cdylib, then run it.
+
+#![cfg(all(feature = "ffi", unix))]
+
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Build the SDK cdylib and header, returning the directory holding both
+///
+/// `cargo test` only builds the library as an rlib, so this runs its own
+/// build in a separate target directory, clear of the running build's lock.
+fn build_cdylib() -> PathBuf {
+    let target_dir = Path::new(env!("CARGO_TARGET_TMPDIR")).join("ffi");
+    let status = Command::new(env!("CARGO"))
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args([
+            "build",
+            "--lib",
+            "--no-default-features",
+            "--features",
+            "ffi",
+        ])
+        .arg("--target-dir")
+        .arg(&target_dir)
+        .status()
+        .expect("failed to run cargo");
+    assert!(status.success(), "building the cdylib failed");
+    target_dir.join("debug")
+}
+
+/// Compile `source` into an executable linked against the SDK in `lib_dir`
+fn compile_c(source: &Path, lib_dir: &Path, output: &Path) {
+    let compiler = cc::Build::new()
+        .cargo_metadata(false)
+        .target(env!("BLLVM_FFI_TARGET"))
+        .host(env!("BLLVM_FFI_TARGET"))
+        .opt_level(0)
+        .debug(false)
+        .warnings(true)
+        .get_compiler();
+    let status = compiler
+        .to_command()
+        .arg(source)
+        .arg("-I")
+        .arg(lib_dir.join("include"))
+        .arg("-L")
+        .arg(lib_dir)
+        .arg(format!("-Wl,-rpath,{}", lib_dir.display()))
+        .arg("-lblvm_sdk")
+        .arg("-o")
+        .arg(output)
+        .status()
+        .expect("failed to run the C compiler");
+    assert!(status.success(), "compiling {} failed", source.display());
+}
+
+#[test]
+fn test_c_two_of_three_verification() {
+    let lib_dir = build_cdylib();
+    let source = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/ffi/multisig_verify.c");
+    let dir = tempfile::tempdir().unwrap();
+    let exe = dir.path().join("multisig_verify");
+    compile_c(&source, &lib_dir, &exe);
+
+    let output = Command::new(&exe).output().unwrap();
+    assert!(
+        output.status.success(),
+        "C driver failed: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
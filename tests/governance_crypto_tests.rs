@@ -4,6 +4,7 @@
 
 use blvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Multisig};
 use blvm_sdk::sign_message;
+use std::collections::HashMap;
 
 #[test]
 fn test_complete_governance_workflow() {
@@ -20,6 +21,7 @@ fn test_complete_governance_workflow() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with 3 keys (meets threshold)
@@ -46,6 +48,7 @@ fn test_insufficient_signatures() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with only 2 keys (below threshold)
@@ -66,6 +69,7 @@ fn test_different_message_types() {
     let release_msg = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     let release_sig = sign_message(&keypair.secret_key, &release_msg.to_signing_bytes()).unwrap();
     assert!(blvm_sdk::governance::verify_signature(
@@ -76,10 +80,7 @@ fn test_different_message_types() {
     .unwrap());
 
     // Test module approval message
-    let module_msg = GovernanceMessage::ModuleApproval {
-        module_name: "lightning".to_string(),
-        version: "v2.0.0".to_string(),
-    };
+    let module_msg = GovernanceMessage::module_approval("lightning", "v2.0.0");
     let module_sig = sign_message(&keypair.secret_key, &module_msg.to_signing_bytes()).unwrap();
     assert!(blvm_sdk::governance::verify_signature(
         &module_sig,
@@ -89,10 +90,7 @@ fn test_different_message_types() {
     .unwrap());
 
     // Test budget decision message
-    let budget_msg = GovernanceMessage::BudgetDecision {
-        amount: 1000000,
-        purpose: "development".to_string(),
-    };
+    let budget_msg = GovernanceMessage::single_budget("bc1qdev", 1000000, "development");
     let budget_sig = sign_message(&keypair.secret_key, &budget_msg.to_signing_bytes()).unwrap();
     assert!(blvm_sdk::governance::verify_signature(
         &budget_sig,
@@ -110,6 +108,7 @@ fn test_signature_cross_verification() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with keypair1
@@ -144,6 +143,7 @@ fn test_multisig_edge_cases() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with exactly 6 keys (meets threshold)
@@ -1,6 +1,6 @@
 //! Additional tests for governance keys module to improve coverage.
 
-use blvm_sdk::governance::{GovernanceError, GovernanceKeypair, PublicKey};
+use blvm_sdk::governance::{ErrorCategory, GovernanceError, GovernanceKeypair, PublicKey};
 
 #[test]
 fn test_governance_keypair_debug_format() {
@@ -159,3 +159,107 @@ fn test_governance_error_variants() {
     assert!(!format!("{}", error8).is_empty());
     assert!(!format!("{}", error9).is_empty());
 }
+
+#[test]
+fn test_governance_error_category() {
+    let cases = [
+        (
+            GovernanceError::InvalidKey("test".to_string()),
+            ErrorCategory::KeyManagement,
+        ),
+        (
+            GovernanceError::Cryptographic("test".to_string()),
+            ErrorCategory::SignatureOperations,
+        ),
+        (
+            GovernanceError::InvalidSignatureFormat("test".to_string()),
+            ErrorCategory::SignatureOperations,
+        ),
+        (
+            GovernanceError::InsufficientSignatures { got: 1, need: 2 },
+            ErrorCategory::MultisigLogic,
+        ),
+        (
+            GovernanceError::Serialization("test".to_string()),
+            ErrorCategory::MessageFormat,
+        ),
+        (
+            GovernanceError::ChecksumMismatch("test".to_string()),
+            ErrorCategory::InvalidInput,
+        ),
+    ];
+    for (error, category) in cases {
+        assert_eq!(error.category(), category, "{:?}", error);
+        assert!(!error.is_retryable(), "{:?}", error);
+    }
+
+    // Only an unreachable signer is worth retrying
+    let unavailable = GovernanceError::SignerUnavailable("test".to_string());
+    assert_eq!(unavailable.category(), ErrorCategory::SignatureOperations);
+    assert!(unavailable.is_retryable());
+    let refused = GovernanceError::SigningRefused("test".to_string());
+    assert_eq!(refused.category(), ErrorCategory::SignatureOperations);
+    assert!(!refused.is_retryable());
+
+    // Errors from real operations carry their category
+    let error = PublicKey::from_bytes(&[0u8; 32]).unwrap_err();
+    assert_eq!(error.category(), ErrorCategory::KeyManagement);
+}
+
+#[test]
+fn test_governance_error_codes_are_unique_and_stable() {
+    let s = || "test".to_string();
+    // One entry per variant; codes are public API and must never change
+    let cases = [
+        (GovernanceError::InvalidKey(s()), "E_KEY_FORMAT"),
+        (GovernanceError::InvalidChildIndex(7), "E_KEY_CHILD_INDEX"),
+        (GovernanceError::SignatureVerification(s()), "E_SIG_INVALID"),
+        (GovernanceError::InvalidMultisig(s()), "E_MULTISIG_CONFIG"),
+        (GovernanceError::MessageFormat(s()), "E_MESSAGE_FORMAT"),
+        (GovernanceError::Cryptographic(s()), "E_CRYPTO"),
+        (GovernanceError::Serialization(s()), "E_SERIALIZATION"),
+        (
+            GovernanceError::InvalidThreshold {
+                threshold: 3,
+                total: 2,
+            },
+            "E_THRESHOLD",
+        ),
+        (
+            GovernanceError::InsufficientSignatures { got: 1, need: 2 },
+            "E_SIG_INSUFFICIENT",
+        ),
+        (GovernanceError::InvalidSignatureFormat(s()), "E_SIG_FORMAT"),
+        (
+            GovernanceError::SignerUnavailable(s()),
+            "E_SIGNER_UNAVAILABLE",
+        ),
+        (GovernanceError::SigningRefused(s()), "E_SIGNING_REFUSED"),
+        (
+            GovernanceError::InvalidBase58Character {
+                character: '0',
+                position: 0,
+            },
+            "E_BASE58",
+        ),
+        (GovernanceError::ChecksumMismatch(s()), "E_CHECKSUM"),
+        (GovernanceError::InvalidInput(s()), "E_INPUT"),
+        (GovernanceError::NotImplemented(s()), "E_NOT_IMPLEMENTED"),
+    ];
+
+    let mut seen = std::collections::HashSet::new();
+    for (error, code) in &cases {
+        assert_eq!(error.code(), *code, "{:?}", error);
+        assert!(seen.insert(error.code()), "duplicate code {}", code);
+    }
+
+    // Category names are stable too
+    assert_eq!(ErrorCategory::KeyManagement.to_string(), "key_management");
+    assert_eq!(
+        ErrorCategory::SignatureOperations.as_str(),
+        "signature_operations"
+    );
+    assert_eq!(ErrorCategory::MultisigLogic.as_str(), "multisig_logic");
+    assert_eq!(ErrorCategory::MessageFormat.as_str(), "message_format");
+    assert_eq!(ErrorCategory::InvalidInput.as_str(), "invalid_input");
+}
@@ -2,18 +2,20 @@
 //!
 //! Tests for message serialization and format consistency.
 
-use blvm_sdk::governance::GovernanceMessage;
+use blvm_sdk::governance::{BudgetAllocation, GovernanceMessage};
 use serde_json;
+use std::collections::HashMap;
 
 #[test]
 fn test_release_message_format() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
-    let signing_bytes = message.to_signing_bytes();
-    assert_eq!(signing_bytes, b"RELEASE:v1.0.0:abc123def456");
+    let signing_bytes = message.message_bytes();
+    assert_eq!(signing_bytes, b"RELEASE:6:v1.0.0:12:abc123def456");
 
     let description = message.description();
     assert_eq!(description, "Release v1.0.0 (commit: abc123def456)");
@@ -21,12 +23,9 @@ fn test_release_message_format() {
 
 #[test]
 fn test_module_approval_message_format() {
-    let message = GovernanceMessage::ModuleApproval {
-        module_name: "lightning-network".to_string(),
-        version: "v2.0.0".to_string(),
-    };
+    let message = GovernanceMessage::module_approval("lightning-network", "v2.0.0");
 
-    let signing_bytes = message.to_signing_bytes();
+    let signing_bytes = message.message_bytes();
     assert_eq!(signing_bytes, b"MODULE:lightning-network:v2.0.0");
 
     let description = message.description();
@@ -39,17 +38,33 @@ fn test_module_approval_message_format() {
 #[test]
 fn test_budget_decision_message_format() {
     let message = GovernanceMessage::BudgetDecision {
-        amount: 1000000,
-        purpose: "development and maintenance".to_string(),
+        allocations: vec![
+            BudgetAllocation {
+                recipient: "bc1qmaintainer".to_string(),
+                amount_satoshis: 600000,
+                description: "maintenance".to_string(),
+            },
+            BudgetAllocation {
+                recipient: "bc1qdeveloper".to_string(),
+                amount_satoshis: 400000,
+                description: "development".to_string(),
+            },
+        ],
+        rationale: "development and maintenance".to_string(),
     };
 
-    let signing_bytes = message.to_signing_bytes();
-    assert_eq!(signing_bytes, b"BUDGET:1000000:development and maintenance");
+    // Allocations are signed sorted by recipient
+    let signing_bytes = message.message_bytes();
+    assert_eq!(
+        signing_bytes,
+        b"BUDGET:27:development and maintenance\nALLOCATION:13:bc1qdeveloper:400000:11:development\nALLOCATION:14:bc1qmaintainer:600000:11:maintenance"
+    );
+    assert_eq!(message.total_amount(), Some(1000000));
 
     let description = message.description();
     assert_eq!(
         description,
-        "Budget decision: 1000000 satoshis for development and maintenance"
+        "Budget decision: 1000000 satoshis to 2 recipients for development and maintenance"
     );
 }
 
@@ -59,15 +74,10 @@ fn test_message_serialization_roundtrip() {
         GovernanceMessage::Release {
             version: "v1.0.0".to_string(),
             commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
         },
-        GovernanceMessage::ModuleApproval {
-            module_name: "lightning".to_string(),
-            version: "v2.0.0".to_string(),
-        },
-        GovernanceMessage::BudgetDecision {
-            amount: 1000000,
-            purpose: "development".to_string(),
-        },
+        GovernanceMessage::module_approval("lightning", "v2.0.0"),
+        GovernanceMessage::single_budget("bc1qdeveloper", 1000000, "development"),
     ];
 
     for message in messages {
@@ -82,11 +92,13 @@ fn test_message_signing_bytes_consistency() {
     let message1 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     let message2 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Same message should produce same signing bytes
@@ -98,11 +110,13 @@ fn test_message_signing_bytes_different() {
     let message1 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     let message2 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Different messages should produce different signing bytes
@@ -114,6 +128,7 @@ fn test_message_display_format() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     let display_str = format!("{}", message);
@@ -125,16 +140,19 @@ fn test_message_equality() {
     let message1 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     let message2 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     let message3 = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Same messages should be equal
@@ -146,13 +164,11 @@ fn test_message_equality() {
 
 #[test]
 fn test_message_special_characters() {
-    let message = GovernanceMessage::BudgetDecision {
-        amount: 1000000,
-        purpose: "development & maintenance (2024)".to_string(),
-    };
+    let message =
+        GovernanceMessage::single_budget("bc1qdev", 1000000, "development & maintenance (2024)");
 
-    let signing_bytes = message.to_signing_bytes();
-    let expected = b"BUDGET:1000000:development & maintenance (2024)";
+    let signing_bytes = message.message_bytes();
+    let expected = b"BUDGET:32:development & maintenance (2024)\nALLOCATION:7:bc1qdev:1000000:0:";
 
     assert_eq!(signing_bytes, expected);
 }
@@ -162,10 +178,11 @@ fn test_message_empty_fields() {
     let message = GovernanceMessage::Release {
         version: "".to_string(),
         commit_hash: "".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
-    let signing_bytes = message.to_signing_bytes();
-    assert_eq!(signing_bytes, b"RELEASE::");
+    let signing_bytes = message.message_bytes();
+    assert_eq!(signing_bytes, b"RELEASE:0::0:");
 
     let description = message.description();
     assert_eq!(description, "Release  (commit: )");
@@ -173,13 +190,26 @@ fn test_message_empty_fields() {
 
 #[test]
 fn test_message_unicode_support() {
-    let message = GovernanceMessage::BudgetDecision {
-        amount: 1000000,
-        purpose: "开发与维护".to_string(), // Chinese characters
-    };
+    let message = GovernanceMessage::single_budget("bc1qdev", 1000000, "开发与维护"); // Chinese characters
 
-    let signing_bytes = message.to_signing_bytes();
-    let expected = b"BUDGET:1000000:\xE5\xBC\x80\xE5\x8F\x91\xE4\xB8\x8E\xE7\xBB\xB4\xE6\x8A\xA4";
+    let signing_bytes = message.message_bytes();
+    let expected = b"BUDGET:15:\xE5\xBC\x80\xE5\x8F\x91\xE4\xB8\x8E\xE7\xBB\xB4\xE6\x8A\xA4\nALLOCATION:7:bc1qdev:1000000:0:";
 
     assert_eq!(signing_bytes, expected);
 }
+
+#[test]
+fn test_signing_bytes_domain_prefix() {
+    use blvm_sdk::governance::SIGNING_DOMAIN;
+
+    assert_eq!(GovernanceMessage::signing_domain(), b"BLLVM-GOV-V1\x00");
+    assert_eq!(GovernanceMessage::signing_domain(), SIGNING_DOMAIN);
+
+    let message = GovernanceMessage::single_budget("bc1qdev", 1000000, "development");
+    let signing_bytes = message.to_signing_bytes();
+    assert!(signing_bytes.starts_with(SIGNING_DOMAIN));
+    assert_eq!(
+        &signing_bytes[SIGNING_DOMAIN.len()..],
+        message.message_bytes()
+    );
+}
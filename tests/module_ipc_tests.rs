@@ -0,0 +1,249 @@
+//! Module IPC Signing Tests
+//!
+//! Tests for signed module-to-node IPC messages, the connection handshake,
+//! and client timeouts.
+
+#![cfg(unix)]
+
+use blvm_sdk::governance::GovernanceKeypair;
+use blvm_sdk::module::ipc::protocol::{
+    open_signed_message, HandshakeMessage, ProtocolVersion, CURRENT_PROTOCOL_VERSION,
+};
+use blvm_sdk::module::{ModuleIpcClient, ModuleIpcClientConfig, ModuleManifest};
+use serde::Serialize;
+use std::io::{Read, Write};
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::time::Duration;
+use tempfile::TempDir;
+
+#[derive(Serialize)]
+struct GetBlock {
+    height: u64,
+}
+
+fn manifest_with_key(keypair: &GovernanceKeypair) -> ModuleManifest {
+    ModuleManifest::from_toml_str(&format!(
+        "name = \"indexer\"\nversion = \"0.1.0\"\nentry_point = \"indexer\"\nsigning_public_key = \"{}\"\n",
+        keypair.public_key()
+    ))
+    .unwrap()
+}
+
+fn read_frame(stream: &mut UnixStream) -> Vec<u8> {
+    let mut len = [0u8; 4];
+    stream.read_exact(&mut len).unwrap();
+    let mut frame = vec![0u8; u32::from_be_bytes(len) as usize];
+    stream.read_exact(&mut frame).unwrap();
+    frame
+}
+
+fn write_frame(stream: &mut UnixStream, frame: &[u8]) {
+    stream
+        .write_all(&(frame.len() as u32).to_be_bytes())
+        .unwrap();
+    stream.write_all(frame).unwrap();
+}
+
+/// Answer the module's hello as a node speaking `server_version`
+fn answer_hello(stream: &mut UnixStream, server_version: ProtocolVersion) -> HandshakeMessage {
+    let hello: HandshakeMessage = serde_json::from_slice(&read_frame(stream)).unwrap();
+    let HandshakeMessage::Hello { client_version, .. } = &hello else {
+        panic!("expected hello, got {:?}", hello);
+    };
+    let ack = HandshakeMessage::HelloAck {
+        server_version,
+        accepted_version: server_version
+            .negotiate(*client_version)
+            .unwrap_or(server_version),
+    };
+    write_frame(stream, &serde_json::to_vec(&ack).unwrap());
+    hello
+}
+
+/// Send one signed message and return the frame the node received
+fn send_and_receive(keypair: &GovernanceKeypair) -> Vec<u8> {
+    let dir = TempDir::new().unwrap();
+    let socket_path = dir.path().join("node.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    let node = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        answer_hello(&mut stream, CURRENT_PROTOCOL_VERSION);
+        read_frame(&mut stream)
+    });
+
+    let client = ModuleIpcClient::new(&socket_path);
+    client
+        .send_signed(GetBlock { height: 42 }, keypair)
+        .unwrap();
+    node.join().unwrap()
+}
+
+#[test]
+fn test_send_signed_verified_by_node() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let frame = send_and_receive(&keypair);
+
+    let body = open_signed_message(&frame, &manifest_with_key(&keypair)).unwrap();
+    assert_eq!(body, br#"{"height":42}"#);
+}
+
+#[test]
+fn test_send_signed_rejected_for_other_key() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let other = GovernanceKeypair::generate().unwrap();
+    let frame = send_and_receive(&keypair);
+
+    assert!(open_signed_message(&frame, &manifest_with_key(&other)).is_err());
+
+    // Modules without a declared identity key cannot send signed messages
+    let mut manifest = manifest_with_key(&keypair);
+    manifest.signing_public_key = None;
+    assert!(open_signed_message(&frame, &manifest).is_err());
+}
+
+#[test]
+fn test_send_signed_without_node() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let dir = TempDir::new().unwrap();
+    let client = ModuleIpcClient::new(dir.path().join("missing.sock"));
+    assert!(client
+        .send_signed(GetBlock { height: 1 }, &keypair)
+        .is_err());
+}
+
+/// Spawn a node that answers one connection with `reply` (a raw frame, length prefix included)
+fn spawn_node(dir: &TempDir, reply: Option<Vec<u8>>) -> std::path::PathBuf {
+    let socket_path = dir.path().join("node.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        answer_hello(&mut stream, CURRENT_PROTOCOL_VERSION);
+        read_frame(&mut stream);
+        match reply {
+            Some(reply) => stream.write_all(&reply).unwrap(),
+            // Hold the connection open without answering
+            None => std::thread::sleep(Duration::from_secs(2)),
+        }
+    });
+    socket_path
+}
+
+fn short_timeouts() -> ModuleIpcClientConfig {
+    ModuleIpcClientConfig {
+        read_timeout: Duration::from_millis(100),
+        ..ModuleIpcClientConfig::default()
+    }
+}
+
+#[test]
+fn test_ping_round_trip() {
+    let dir = TempDir::new().unwrap();
+    let pong = br#"{"type":"pong"}"#;
+    let mut reply = (pong.len() as u32).to_be_bytes().to_vec();
+    reply.extend_from_slice(pong);
+    let socket_path = spawn_node(&dir, Some(reply));
+
+    let rtt = ModuleIpcClient::new(&socket_path).ping().unwrap();
+    assert!(rtt < Duration::from_secs(5));
+}
+
+#[test]
+fn test_ping_read_timeout() {
+    let dir = TempDir::new().unwrap();
+    let socket_path = spawn_node(&dir, None);
+
+    let client = ModuleIpcClient::new(&socket_path).with_config(short_timeouts());
+    let err = client.ping().unwrap_err();
+    assert!(err.to_string().contains("IPC timeout"), "{}", err);
+}
+
+#[test]
+fn test_max_message_size() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let config = ModuleIpcClientConfig {
+        max_message_size: 64,
+        ..short_timeouts()
+    };
+
+    // Oversized replies are rejected from the length prefix alone
+    let dir = TempDir::new().unwrap();
+    let socket_path = spawn_node(&dir, Some(1_000_000u32.to_be_bytes().to_vec()));
+    let client = ModuleIpcClient::new(&socket_path).with_config(config.clone());
+    let err = client.ping().unwrap_err();
+    assert!(err.to_string().contains("too large"), "{}", err);
+
+    // Oversized outgoing messages fail before anything is written
+    let dir = TempDir::new().unwrap();
+    let socket_path = dir.path().join("node.sock");
+    let _listener = UnixListener::bind(&socket_path).unwrap();
+    let client = ModuleIpcClient::new(&socket_path).with_config(config);
+    let err = client
+        .send_signed(GetBlock { height: 1 }, &keypair)
+        .unwrap_err();
+    assert!(err.to_string().contains("too large"), "{}", err);
+}
+
+/// Spawn a node speaking `server_version` that answers the handshake only
+fn spawn_versioned_node(
+    dir: &TempDir,
+    server_version: ProtocolVersion,
+) -> (
+    std::path::PathBuf,
+    std::thread::JoinHandle<HandshakeMessage>,
+) {
+    let socket_path = dir.path().join("node.sock");
+    let listener = UnixListener::bind(&socket_path).unwrap();
+    let node = std::thread::spawn(move || {
+        let (mut stream, _) = listener.accept().unwrap();
+        let hello = answer_hello(&mut stream, server_version);
+        // Drain until the client hangs up
+        let _ = stream.read_to_end(&mut Vec::new());
+        hello
+    });
+    (socket_path, node)
+}
+
+#[test]
+fn test_handshake_negotiates_version() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let dir = TempDir::new().unwrap();
+    let (socket_path, node) = spawn_versioned_node(&dir, ProtocolVersion::new(1, 0));
+
+    let client = ModuleIpcClient::new(&socket_path).with_module_name("indexer");
+    assert_eq!(client.negotiated_version(), None);
+    client
+        .send_signed(GetBlock { height: 1 }, &keypair)
+        .unwrap();
+
+    // A 1.0 node accepts the baseline protocol
+    assert_eq!(
+        client.negotiated_version(),
+        Some(ProtocolVersion::new(1, 0))
+    );
+    assert_eq!(
+        node.join().unwrap(),
+        HandshakeMessage::Hello {
+            client_version: CURRENT_PROTOCOL_VERSION,
+            module_name: "indexer".to_string(),
+        }
+    );
+}
+
+#[test]
+fn test_handshake_rejects_incompatible_major_version() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let dir = TempDir::new().unwrap();
+    let (socket_path, node) = spawn_versioned_node(&dir, ProtocolVersion::new(2, 0));
+
+    let client = ModuleIpcClient::new(&socket_path);
+    let err = client
+        .send_signed(GetBlock { height: 1 }, &keypair)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("incompatible protocol version"),
+        "{}",
+        err
+    );
+    assert_eq!(client.negotiated_version(), None);
+    node.join().unwrap();
+}
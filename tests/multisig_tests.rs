@@ -2,8 +2,14 @@
 //!
 //! Tests for multisig threshold validation and signature collection.
 
-use blvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, Multisig};
+use blvm_sdk::governance::bip44::{Bip44Wallet, CoinType};
+use blvm_sdk::governance::signatures::hash160;
+use blvm_sdk::governance::{
+    GovernanceError, GovernanceKeypair, GovernanceMessage, Multisig, MultisigScriptType, PublicKey,
+};
+use blvm_sdk::governance::{GovernanceSigner, MockSigner, MockSignerBehavior};
 use blvm_sdk::sign_message;
+use std::collections::HashMap;
 
 #[test]
 fn test_multisig_creation() {
@@ -68,6 +74,7 @@ fn test_multisig_verification() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with 3 keys (meets threshold)
@@ -93,6 +100,7 @@ fn test_multisig_insufficient_signatures() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with only 2 keys (below threshold)
@@ -116,6 +124,7 @@ fn test_multisig_excess_signatures() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with all 5 keys (above threshold, should still work)
@@ -141,6 +150,7 @@ fn test_multisig_collect_valid_signatures() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Sign with 3 keys
@@ -168,6 +178,7 @@ fn test_multisig_mixed_valid_invalid_signatures() {
     let message = GovernanceMessage::Release {
         version: "v1.0.0".to_string(),
         commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
     };
 
     // Create a mix of valid and invalid signatures
@@ -183,6 +194,7 @@ fn test_multisig_mixed_valid_invalid_signatures() {
     let wrong_message = GovernanceMessage::Release {
         version: "v2.0.0".to_string(),
         commit_hash: "def456".to_string(),
+        artifact_hashes: HashMap::new(),
     };
     let invalid_sig =
         sign_message(&keypairs[2].secret_key, &wrong_message.to_signing_bytes()).unwrap();
@@ -216,6 +228,7 @@ fn test_multisig_edge_case_thresholds() {
         let message = GovernanceMessage::Release {
             version: "v1.0.0".to_string(),
             commit_hash: "abc123".to_string(),
+            artifact_hashes: HashMap::new(),
         };
 
         // Sign with exactly the threshold number of keys
@@ -230,3 +243,241 @@ fn test_multisig_edge_case_thresholds() {
         assert!(verified);
     }
 }
+
+#[test]
+fn test_multisig_descriptor_known_checksum() {
+    let keys: Vec<_> = [
+        "03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd",
+        "03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+    ]
+    .iter()
+    .map(|key| PublicKey::from_bytes(&hex::decode(key).unwrap()).unwrap())
+    .collect();
+
+    let multisig = Multisig::new(1, 2, keys)
+        .unwrap()
+        .with_script_type(MultisigScriptType::P2wsh);
+    assert_eq!(
+        multisig.to_descriptor(),
+        "wsh(multi(1,03a34b99f22c790c4e36b2b3c2c35a36db06226e41c692fc82b8b56ac1c540c5bd,03dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659))#q5pus7q4"
+    );
+}
+
+#[test]
+fn test_multisig_descriptor_roundtrip() {
+    let public_keys: Vec<_> = (0..3)
+        .map(|_| GovernanceKeypair::generate().unwrap().public_key())
+        .collect();
+
+    for (script_type, prefix) in [
+        (MultisigScriptType::Bare, "multi(2,"),
+        (MultisigScriptType::P2sh, "sh(multi(2,"),
+        (MultisigScriptType::P2wsh, "wsh(multi(2,"),
+        (MultisigScriptType::P2shP2wsh, "sh(wsh(multi(2,"),
+    ] {
+        let multisig = Multisig::new(2, 3, public_keys.clone())
+            .unwrap()
+            .with_script_type(script_type);
+        let desc = multisig.to_descriptor();
+        assert!(desc.starts_with(prefix), "{}", desc);
+
+        let parsed = Multisig::from_descriptor(&desc).unwrap();
+        assert_eq!(parsed.script_type(), script_type);
+        assert_eq!(parsed.threshold(), 2);
+        assert_eq!(parsed.public_keys(), multisig.public_keys());
+
+        // The checksum is optional on input
+        let body = desc.split('#').next().unwrap();
+        assert!(Multisig::from_descriptor(body).is_ok());
+    }
+}
+
+#[test]
+fn test_multisig_descriptor_invalid() {
+    let key = hex::encode(GovernanceKeypair::generate().unwrap().public_key_bytes());
+
+    // Bad checksum
+    let desc = format!("multi(1,{})#qqqqqqqq", key);
+    assert!(Multisig::from_descriptor(&desc).is_err());
+
+    // Unsupported script, threshold too high, bad key
+    assert!(Multisig::from_descriptor(&format!("pkh({})", key)).is_err());
+    assert!(Multisig::from_descriptor(&format!("multi(2,{})", key)).is_err());
+    assert!(Multisig::from_descriptor("multi(1,02abcd)").is_err());
+    assert!(Multisig::from_descriptor(&format!("wsh(multi(1,{})", key)).is_err());
+}
+
+#[test]
+fn test_multisig_from_xpubs_per_release() {
+    let wallets: Vec<_> = (1..=3u8)
+        .map(|i| Bip44Wallet::from_seed(&[i; 32], CoinType::Bitcoin).unwrap())
+        .collect();
+    let xpubs: Vec<_> = wallets.iter().map(|w| w.account_xpub(0).unwrap()).collect();
+
+    let release_1 = Multisig::from_xpubs(2, &xpubs, &[0, 1]).unwrap();
+    let release_2 = Multisig::from_xpubs(2, &xpubs, &[0, 2]).unwrap();
+    assert_eq!(release_1.total(), 3);
+    assert_ne!(release_1.public_keys(), release_2.public_keys());
+    assert_eq!(
+        release_1.public_keys(),
+        Multisig::from_xpubs(2, &xpubs, &[0, 1])
+            .unwrap()
+            .public_keys()
+    );
+
+    // Maintainers derive the matching signing keys
+    let message = GovernanceMessage::Release {
+        version: "v1.0.0".to_string(),
+        commit_hash: "abc123".to_string(),
+        artifact_hashes: HashMap::new(),
+    }
+    .to_signing_bytes();
+    let signatures: Vec<_> = wallets[..2]
+        .iter()
+        .map(|w| {
+            let keypair = w.signing_keypair(0, &[0, 1]).unwrap();
+            sign_message(&keypair.secret_key, &message).unwrap()
+        })
+        .collect();
+    assert!(release_1.verify(&message, &signatures).unwrap());
+    assert!(!release_2.verify(&message, &signatures).unwrap());
+
+    // Hardened components cannot be derived from an xpub
+    assert!(matches!(
+        Multisig::from_xpubs(2, &xpubs, &[0x8000_0000]),
+        Err(GovernanceError::InvalidInput(_))
+    ));
+    assert!(wallets[0].signing_keypair(0, &[0x8000_0000]).is_err());
+}
+
+#[test]
+fn test_multisig_redeem_script_roundtrip() {
+    let public_keys: Vec<_> = (0..3)
+        .map(|_| GovernanceKeypair::generate().unwrap().public_key())
+        .collect();
+    let multisig = Multisig::new(2, 3, public_keys).unwrap();
+
+    let script = multisig.to_redeem_script().unwrap();
+    assert_eq!(script.len(), 3 + 3 * 34);
+    assert_eq!(script[0], 0x52);
+    assert_eq!(&script[script.len() - 2..], &[0x53, 0xae]);
+
+    let parsed = Multisig::from_redeem_script(&script).unwrap();
+    assert_eq!(parsed.threshold(), 2);
+    assert_eq!(parsed.public_keys(), multisig.public_keys());
+    assert_eq!(parsed.script_type(), MultisigScriptType::Bare);
+
+    // Uncompressed keys are accepted
+    let mut uncompressed = vec![0x51];
+    for key in multisig.public_keys() {
+        uncompressed.push(65);
+        uncompressed.extend_from_slice(&key.to_uncompressed_bytes());
+    }
+    uncompressed.extend_from_slice(&[0x53, 0xae]);
+    let parsed = Multisig::from_redeem_script(&uncompressed).unwrap();
+    assert_eq!(parsed.public_keys(), multisig.public_keys());
+
+    // P2SH output scripts must commit to the redeem script
+    let mut script_pubkey = vec![0xa9, 20];
+    script_pubkey.extend_from_slice(&hash160(&script));
+    script_pubkey.push(0x87);
+    let parsed = Multisig::from_p2sh_redeem_script(&script_pubkey, &script).unwrap();
+    assert_eq!(parsed.script_type(), MultisigScriptType::P2sh);
+    assert_eq!(parsed.public_keys(), multisig.public_keys());
+    assert!(Multisig::from_p2sh_redeem_script(&script_pubkey, &uncompressed).is_err());
+    assert!(Multisig::from_p2sh_redeem_script(&script, &script).is_err());
+}
+
+#[test]
+fn test_multisig_redeem_script_malformed() {
+    let public_keys: Vec<_> = (0..3)
+        .map(|_| GovernanceKeypair::generate().unwrap().public_key())
+        .collect();
+    let script = Multisig::new(2, 3, public_keys)
+        .unwrap()
+        .to_redeem_script()
+        .unwrap();
+    let with = |index: usize, byte: u8| {
+        let mut script = script.clone();
+        script[index] = byte;
+        script
+    };
+    let last = script.len() - 1;
+
+    let cases = [
+        (Vec::new(), "empty"),
+        (with(0, 0x00), "for M"),
+        (with(last - 1, 0x51), "less than M"),
+        (with(last - 1, 0x54), "N is 4"),
+        (with(last, 0xac), "OP_CHECKMULTISIG"),
+        (with(1, 0x20), "33 or 65 byte"),
+        (script[..script.len() - 10].to_vec(), "OP_CHECKMULTISIG"),
+    ];
+    for (script, expected) in cases {
+        match Multisig::from_redeem_script(&script) {
+            Err(GovernanceError::InvalidInput(message)) => {
+                assert!(message.contains(expected), "{}", message)
+            }
+            other => panic!("expected InvalidInput for {}, got {:?}", expected, other),
+        }
+    }
+
+    // Too many keys for a script
+    let public_keys: Vec<_> = (0..17)
+        .map(|_| GovernanceKeypair::generate().unwrap().public_key())
+        .collect();
+    assert!(Multisig::new(1, 17, public_keys)
+        .unwrap()
+        .to_redeem_script()
+        .is_err());
+}
+
+#[test]
+fn test_multisig_collect_signatures_from_signers() {
+    let keypairs: Vec<_> = (0..3)
+        .map(|_| GovernanceKeypair::generate().unwrap())
+        .collect();
+    let public_keys = keypairs.iter().map(|kp| kp.public_key()).collect();
+    let multisig = Multisig::new(2, 3, public_keys).unwrap();
+    let message = b"release v1.0.0";
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    // The first signer is offline; the other two still meet the threshold
+    let offline =
+        MockSigner::new(keypairs[0].clone()).with_behavior(MockSignerBehavior::Unavailable);
+    let second = MockSigner::new(keypairs[1].clone());
+    let signers: Vec<&dyn GovernanceSigner> = vec![&offline, &second, &keypairs[2]];
+    let signatures = runtime
+        .block_on(multisig.collect_signatures(message, &signers))
+        .unwrap();
+    assert_eq!(signatures.len(), 2);
+    assert!(multisig.verify(message, &signatures).unwrap());
+    assert_eq!(offline.requests(), vec![message.to_vec()]);
+    assert_eq!(second.requests(), vec![message.to_vec()]);
+
+    // Collection stops once the threshold is met
+    let third = MockSigner::new(keypairs[2].clone());
+    let signers: Vec<&dyn GovernanceSigner> = vec![&keypairs[0], &keypairs[1], &third];
+    runtime
+        .block_on(multisig.collect_signatures(message, &signers))
+        .unwrap();
+    assert!(third.requests().is_empty());
+
+    // Refusals count against the threshold
+    let refusing = MockSigner::new(keypairs[1].clone()).with_behavior(MockSignerBehavior::Refuse);
+    let signers: Vec<&dyn GovernanceSigner> = vec![&keypairs[0], &refusing];
+    assert!(matches!(
+        runtime.block_on(multisig.collect_signatures(message, &signers)),
+        Err(GovernanceError::InsufficientSignatures { got: 1, need: 2 })
+    ));
+
+    // Signers outside the roster are rejected
+    let outsider = GovernanceKeypair::generate().unwrap();
+    let signers: Vec<&dyn GovernanceSigner> = vec![&outsider, &keypairs[0], &keypairs[1]];
+    assert!(matches!(
+        runtime.block_on(multisig.collect_signatures(message, &signers)),
+        Err(GovernanceError::InvalidMultisig(_))
+    ));
+}
@@ -3,10 +3,18 @@
 //! Tests for BIP174 PSBT format implementation.
 //! Specification: https://github.com/bitcoin/bips/blob/master/bip-0174.mediawiki
 
+use bitcoin::hashes::Hash;
+use blvm_sdk::governance::bip32::{derive_master_key, ExtendedPrivateKey};
+use blvm_sdk::governance::error::GovernanceError;
 use blvm_sdk::governance::psbt::{
-    Bip32Derivation, PartialSignature, PartiallySignedTransaction, PsbtGlobalKey, PsbtInputKey,
-    PsbtOutputKey, SighashType, PSBT_MAGIC, PSBT_SEPARATOR,
+    psbt_key, split_psbt_key, Bip32Derivation, PartialSignature, PartiallySignedTransaction,
+    PsbtGlobalKey, PsbtInput, PsbtInputKey, PsbtIssueCode, PsbtIssueSeverity, PsbtOutput,
+    PsbtOutputKey, PsbtScope, SighashType, BLLVM_PROPRIETARY_IDENTIFIER, PSBT_MAGIC,
+    PSBT_SEPARATOR,
 };
+use blvm_sdk::governance::transaction::{Transaction, TxIn, TxOut};
+use blvm_sdk::NetworkType;
+use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1, SecretKey};
 
 /// Test helper: Create a minimal unsigned transaction (mock)
 fn create_mock_unsigned_tx() -> Vec<u8> {
@@ -53,7 +61,7 @@ fn test_psbt_version() {
     let psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
     assert_eq!(psbt.version, 0);
-    assert!(psbt
+    assert!(!psbt
         .global
         .contains_key(&vec![PsbtGlobalKey::Version as u8]));
 }
@@ -76,43 +84,45 @@ fn test_psbt_separator() {
 
 #[test]
 fn test_psbt_add_input() {
-    // Test adding input to PSBT
+    // Test adding input data to PSBT
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    // Add input map
-    psbt.inputs.push(std::collections::HashMap::new());
+    // Fill the input map created for the transaction's input
+    psbt.inputs[0] = PsbtInput {
+        sighash_type: Some(SighashType::All),
+        ..PsbtInput::default()
+    };
 
     assert_eq!(psbt.inputs.len(), 1);
+    assert!(psbt.validate_structure().is_ok());
 }
 
 #[test]
 fn test_psbt_add_output() {
-    // Test adding output to PSBT
+    // Test adding output data to PSBT
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    // Add output map
-    psbt.outputs.push(std::collections::HashMap::new());
+    // Fill the output map created for the transaction's output
+    psbt.outputs[0] = PsbtOutput {
+        redeem_script: Some(vec![0x51]),
+        ..PsbtOutput::default()
+    };
 
     assert_eq!(psbt.outputs.len(), 1);
+    assert!(psbt.validate_structure().is_ok());
 }
 
 #[test]
 fn test_psbt_multiple_inputs_outputs() {
     // Test PSBT with multiple inputs and outputs
-    let unsigned_tx = create_mock_unsigned_tx();
-    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
-
-    // Add multiple inputs
-    psbt.inputs.push(std::collections::HashMap::new());
-    psbt.inputs.push(std::collections::HashMap::new());
+    let mut tx = create_spending_tx(3);
+    tx.outputs.push(tx.outputs[0].clone());
+    let psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
 
-    // Add multiple outputs
-    psbt.outputs.push(std::collections::HashMap::new());
-    psbt.outputs.push(std::collections::HashMap::new());
-
-    assert_eq!(psbt.inputs.len(), 2);
+    // One map per transaction input and output
+    assert_eq!(psbt.inputs.len(), 3);
     assert_eq!(psbt.outputs.len(), 2);
 }
 
@@ -303,13 +313,20 @@ fn test_psbt_global_map_unsigned_tx() {
 fn test_psbt_global_map_version() {
     // Test version in global map
     let unsigned_tx = create_mock_unsigned_tx();
-    let psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
+    // Version 0 is written by omitting the key
     let key = vec![PsbtGlobalKey::Version as u8];
-    assert!(psbt.global.contains_key(&key));
+    assert!(!psbt.global.contains_key(&key));
+
+    // An explicit version is a 4-byte little-endian u32
+    psbt.global.insert(key.clone(), vec![0x00; 4]);
+    let decoded = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(decoded.version, 0);
+    assert!(bitcoin::psbt::Psbt::deserialize(&psbt.serialize().unwrap()).is_ok());
 
-    let version_data = psbt.global.get(&key).unwrap();
-    assert_eq!(version_data, &vec![0x00]); // Version 0
+    psbt.global.insert(key, vec![0x00]);
+    assert!(PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).is_err());
 }
 
 // ============================================================================
@@ -322,13 +339,14 @@ fn test_psbt_input_map_witness_utxo() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut input_map = std::collections::HashMap::new();
-    let witness_utxo = vec![0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]; // Mock UTXO
-    input_map.insert(vec![PsbtInputKey::WitnessUtxo as u8], witness_utxo.clone());
-
-    psbt.inputs.push(input_map);
+    let utxo = TxOut {
+        value: 50_000,
+        script_pubkey: vec![0x00, 0x14, 0x01, 0x02],
+    };
+    psbt.add_input_data(0, vec![PsbtInputKey::WitnessUtxo as u8], utxo.serialize())
+        .unwrap();
 
-    assert!(psbt.inputs[0].contains_key(&vec![PsbtInputKey::WitnessUtxo as u8]));
+    assert_eq!(psbt.inputs[0].witness_utxo, Some(utxo));
 }
 
 #[test]
@@ -337,16 +355,63 @@ fn test_psbt_input_map_partial_sig() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut input_map = std::collections::HashMap::new();
+    let pubkey = vec![0x02; 33];
     let partial_sig_data = vec![0x30, 0x45, 0x02, 0x21]; // Mock signature
-    input_map.insert(
-        vec![PsbtInputKey::PartialSig as u8],
-        partial_sig_data.clone(),
+    psbt.add_partial_signature(0, pubkey.clone(), partial_sig_data.clone())
+        .unwrap();
+
+    // Keyed by public key; the raw key is <0x02><pubkey>
+    assert_eq!(
+        psbt.inputs[0].partial_sigs.get(&pubkey),
+        Some(&partial_sig_data)
     );
+    let mut raw_key = vec![PsbtInputKey::PartialSig as u8];
+    raw_key.extend_from_slice(&pubkey);
+    assert_eq!(
+        psbt.inputs[0].to_map().get(&raw_key),
+        Some(&partial_sig_data)
+    );
+}
+
+#[test]
+fn test_psbt_partial_signature_accessors() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    assert_eq!(psbt.signature_count(0).unwrap(), 0);
+    assert!(psbt.partial_signatures(0).unwrap().is_empty());
+    assert!(psbt.has_enough_signatures(0, 0).unwrap());
+    assert!(!psbt.has_enough_signatures(0, 1).unwrap());
 
-    psbt.inputs.push(input_map);
+    psbt.add_partial_signature(0, vec![0x03; 33], vec![0x30, 0x02])
+        .unwrap();
+    psbt.add_partial_signature(0, vec![0x02; 33], vec![0x30, 0x01])
+        .unwrap();
+    // Other key types on the same input are not signatures
+    psbt.set_sighash_type(0, SighashType::All).unwrap();
 
-    assert!(psbt.inputs[0].contains_key(&vec![PsbtInputKey::PartialSig as u8]));
+    // Parsed back from the serialized key-value map, ordered by pubkey
+    let psbt = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(
+        psbt.partial_signatures(0).unwrap(),
+        vec![
+            PartialSignature {
+                pubkey: vec![0x02; 33],
+                signature: vec![0x30, 0x01],
+            },
+            PartialSignature {
+                pubkey: vec![0x03; 33],
+                signature: vec![0x30, 0x02],
+            },
+        ]
+    );
+    assert_eq!(psbt.signature_count(0).unwrap(), 2);
+    assert!(psbt.has_enough_signatures(0, 2).unwrap());
+    assert!(!psbt.has_enough_signatures(0, 3).unwrap());
+
+    // Inputs without a map have no signatures
+    assert_eq!(psbt.signature_count(5).unwrap(), 0);
+    assert!(psbt.partial_signatures(5).unwrap().is_empty());
 }
 
 #[test]
@@ -355,13 +420,16 @@ fn test_psbt_input_map_sighash_type() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut input_map = std::collections::HashMap::new();
-    let sighash_byte = vec![SighashType::All.to_byte()];
-    input_map.insert(vec![PsbtInputKey::SighashType as u8], sighash_byte.clone());
+    psbt.set_sighash_type(0, SighashType::All).unwrap();
 
-    psbt.inputs.push(input_map);
-
-    assert!(psbt.inputs[0].contains_key(&vec![PsbtInputKey::SighashType as u8]));
+    assert_eq!(psbt.inputs[0].sighash_type, Some(SighashType::All));
+    // Serialized as a 4-byte little-endian value
+    assert_eq!(
+        psbt.inputs[0]
+            .to_map()
+            .get(&vec![PsbtInputKey::SighashType as u8]),
+        Some(&vec![0x01, 0x00, 0x00, 0x00])
+    );
 }
 
 #[test]
@@ -370,16 +438,16 @@ fn test_psbt_input_map_bip32_derivation() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut input_map = std::collections::HashMap::new();
-    let derivation_data = vec![0x02; 33]; // Mock derivation data
-    input_map.insert(
-        vec![PsbtInputKey::Bip32Derivation as u8],
-        derivation_data.clone(),
-    );
-
-    psbt.inputs.push(input_map);
+    let derivation = Bip32Derivation {
+        pubkey: vec![0x02; 33],
+        path: vec![0x8000002c, 0x80000000, 0x80000000, 0, 0],
+        master_fingerprint: [0x12, 0x34, 0x56, 0x78],
+    };
+    psbt.add_bip32_derivation(0, vec![0x02; 33], derivation.clone())
+        .unwrap();
 
-    assert!(psbt.inputs[0].contains_key(&vec![PsbtInputKey::Bip32Derivation as u8]));
+    assert_eq!(psbt.inputs[0].bip32_derivations, vec![derivation.clone()]);
+    assert_eq!(psbt.input_bip32_derivations(0).unwrap(), vec![derivation]);
 }
 
 // ============================================================================
@@ -392,16 +460,15 @@ fn test_psbt_output_map_redeem_script() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut output_map = std::collections::HashMap::new();
     let redeem_script = vec![0x76, 0xa9, 0x14]; // Mock redeem script
-    output_map.insert(
+    psbt.add_output_data(
+        0,
         vec![PsbtOutputKey::RedeemScript as u8],
         redeem_script.clone(),
-    );
-
-    psbt.outputs.push(output_map);
+    )
+    .unwrap();
 
-    assert!(psbt.outputs[0].contains_key(&vec![PsbtOutputKey::RedeemScript as u8]));
+    assert_eq!(psbt.outputs[0].redeem_script, Some(redeem_script));
 }
 
 #[test]
@@ -410,16 +477,15 @@ fn test_psbt_output_map_witness_script() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut output_map = std::collections::HashMap::new();
     let witness_script = vec![0x00, 0x14]; // Mock witness script
-    output_map.insert(
+    psbt.add_output_data(
+        0,
         vec![PsbtOutputKey::WitnessScript as u8],
         witness_script.clone(),
-    );
-
-    psbt.outputs.push(output_map);
+    )
+    .unwrap();
 
-    assert!(psbt.outputs[0].contains_key(&vec![PsbtOutputKey::WitnessScript as u8]));
+    assert_eq!(psbt.outputs[0].witness_script, Some(witness_script));
 }
 
 #[test]
@@ -428,16 +494,168 @@ fn test_psbt_output_map_bip32_derivation() {
     let unsigned_tx = create_mock_unsigned_tx();
     let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    let mut output_map = std::collections::HashMap::new();
-    let derivation_data = vec![0x02; 33]; // Mock derivation data
-    output_map.insert(
-        vec![PsbtOutputKey::Bip32Derivation as u8],
-        derivation_data.clone(),
+    // <0x02><pubkey> -> <fingerprint><path>
+    let mut key = vec![PsbtOutputKey::Bip32Derivation as u8];
+    key.extend_from_slice(&[0x03; 33]);
+    let value = vec![0x12, 0x34, 0x56, 0x78, 0x01, 0x00, 0x00, 0x00];
+    psbt.add_output_data(0, key.clone(), value.clone()).unwrap();
+
+    assert_eq!(
+        psbt.outputs[0].bip32_derivations,
+        vec![Bip32Derivation {
+            pubkey: vec![0x03; 33],
+            path: vec![1],
+            master_fingerprint: [0x12, 0x34, 0x56, 0x78],
+        }]
+    );
+    assert_eq!(psbt.outputs[0].to_map().get(&key), Some(&value));
+}
+
+#[test]
+fn test_psbt_typed_fields_reject_malformed_data() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    // Single-byte key types with key data
+    assert!(psbt
+        .add_input_data(
+            0,
+            vec![PsbtInputKey::WitnessUtxo as u8, 0x00],
+            vec![0x00; 9]
+        )
+        .is_err());
+    // Truncated witness UTXO
+    assert!(psbt
+        .add_input_data(0, vec![PsbtInputKey::WitnessUtxo as u8], vec![0x00; 4])
+        .is_err());
+    // Partial signature keyed by something other than a public key
+    assert!(psbt
+        .add_input_data(0, vec![PsbtInputKey::PartialSig as u8], vec![0x30])
+        .is_err());
+    assert!(psbt
+        .add_partial_signature(0, vec![0x02; 10], vec![0x30])
+        .is_err());
+    // Sighash types are 4 bytes
+    assert!(psbt
+        .add_input_data(0, vec![PsbtInputKey::SighashType as u8], vec![0x01])
+        .is_err());
+    // Derivations are a fingerprint followed by whole path elements
+    let mut key = vec![PsbtOutputKey::Bip32Derivation as u8];
+    key.extend_from_slice(&[0x02; 33]);
+    assert!(psbt.add_output_data(0, key, vec![0x00; 6]).is_err());
+}
+
+#[test]
+fn test_psbt_unknown_keys_roundtrip() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+    psbt.add_input_data(0, vec![0x0f, 0x01, 0x02], vec![0xaa])
+        .unwrap();
+    psbt.add_output_data(0, vec![0x0e], vec![0xbb]).unwrap();
+
+    assert_eq!(
+        psbt.inputs[0].unknown.get(&vec![0x0f, 0x01, 0x02]),
+        Some(&vec![0xaa])
     );
 
-    psbt.outputs.push(output_map);
+    let serialized = psbt.serialize().unwrap();
+    let deserialized = PartiallySignedTransaction::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, psbt);
+    assert_eq!(deserialized.serialize().unwrap(), serialized);
+}
 
-    assert!(psbt.outputs[0].contains_key(&vec![PsbtOutputKey::Bip32Derivation as u8]));
+/// Test helper: PSBT with several entries in every map, added out of key order
+fn populated_psbt() -> PartiallySignedTransaction {
+    let secp = Secp256k1::new();
+    let mut psbt = PartiallySignedTransaction::new(&create_spending_tx(2).serialize()).unwrap();
+    for i in (1..=4u8).rev() {
+        let pubkey = SecretKey::from_slice(&[i; 32])
+            .unwrap()
+            .public_key(&secp)
+            .serialize()
+            .to_vec();
+        for index in 0..2 {
+            psbt.add_partial_signature(index, pubkey.clone(), vec![0x30, i, 0x01])
+                .unwrap();
+            psbt.add_bip32_derivation(
+                index,
+                pubkey.clone(),
+                Bip32Derivation {
+                    pubkey: Vec::new(),
+                    path: vec![0x8000_0054, u32::from(i)],
+                    master_fingerprint: [i; 4],
+                },
+            )
+            .unwrap();
+            psbt.add_input_data(index, vec![0x0f, i], vec![i]).unwrap();
+        }
+        psbt.add_output_data(0, vec![0x0e, i], vec![i]).unwrap();
+        psbt.add_proprietary(
+            PsbtScope::Global,
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            0x42,
+            &[i],
+            vec![i],
+        )
+        .unwrap();
+    }
+    psbt
+}
+
+#[test]
+fn test_psbt_serialization_is_deterministic() {
+    let psbt = populated_psbt();
+    let expected = psbt.serialize().unwrap();
+
+    let mut current = psbt.clone();
+    for _ in 0..100 {
+        assert_eq!(psbt.clone().serialize().unwrap(), expected);
+        let bytes = current.serialize().unwrap();
+        assert_eq!(bytes, expected);
+        current = PartiallySignedTransaction::deserialize(&bytes).unwrap();
+        assert_eq!(current, psbt);
+    }
+}
+
+#[test]
+fn test_psbt_derivations_sorted_by_pubkey() {
+    let psbt = populated_psbt();
+    let pubkeys: Vec<&Vec<u8>> = psbt.inputs[0]
+        .bip32_derivations
+        .iter()
+        .map(|d| &d.pubkey)
+        .collect();
+    let mut sorted = pubkeys.clone();
+    sorted.sort();
+    assert_eq!(pubkeys, sorted);
+    assert_eq!(pubkeys.len(), 4);
+}
+
+#[test]
+fn test_psbt_noncanonical_key_order_is_normalized() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut bytes = PSBT_MAGIC.to_vec();
+    bytes.push(PSBT_SEPARATOR);
+    // Global map with the version key ahead of the unsigned transaction
+    bytes.extend_from_slice(&[0x01, 0xfb, 0x04, 0x00, 0x00, 0x00, 0x00]);
+    bytes.extend_from_slice(&[0x01, 0x00, unsigned_tx.len() as u8]);
+    bytes.extend_from_slice(&unsigned_tx);
+    bytes.push(0x00);
+    // Input map with unknown keys in descending order
+    bytes.extend_from_slice(&[
+        0x02, 0x0f, 0x02, 0x01, 0xbb, 0x02, 0x0f, 0x01, 0x01, 0xaa, 0x00,
+    ]);
+    // Output map
+    bytes.push(0x00);
+
+    let psbt = PartiallySignedTransaction::deserialize(&bytes).unwrap();
+    let canonical = psbt.serialize().unwrap();
+    assert_ne!(canonical, bytes);
+    assert_eq!(canonical.len(), bytes.len());
+
+    let reparsed = PartiallySignedTransaction::deserialize(&canonical).unwrap();
+    assert_eq!(reparsed, psbt);
+    assert_eq!(reparsed.serialize().unwrap(), canonical);
 }
 
 // ============================================================================
@@ -457,11 +675,1467 @@ fn test_psbt_has_unsigned_tx() {
 
 #[test]
 fn test_psbt_empty_inputs_outputs() {
-    // Test PSBT with empty inputs/outputs (valid for creation)
+    // Test PSBT starts with one empty map per input and output
     let unsigned_tx = create_mock_unsigned_tx();
     let psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
 
-    // Initially empty
-    assert_eq!(psbt.inputs.len(), 0);
-    assert_eq!(psbt.outputs.len(), 0);
+    assert_eq!(psbt.inputs, vec![PsbtInput::default()]);
+    assert_eq!(psbt.outputs, vec![PsbtOutput::default()]);
+}
+
+#[test]
+fn test_psbt_new_rejects_unparseable_tx() {
+    // Input and output counts must be readable to size the maps
+    assert!(PartiallySignedTransaction::new(&[0x01, 0x00, 0x00, 0x00]).is_err());
+
+    // An input count the data cannot hold
+    let mut unsigned_tx = create_mock_unsigned_tx();
+    unsigned_tx[4] = 0x02;
+    assert!(PartiallySignedTransaction::new(&unsigned_tx).is_err());
+}
+
+// ============================================================================
+// Phase 11: BIP174 Test Vectors
+// ============================================================================
+
+/// BIP174 invalid test vectors (not a PSBT, truncated output maps, unsigned
+/// tx with scriptSigs, missing unsigned tx, duplicate input key)
+const BIP174_INVALID: &[&str] = &[
+    "0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300",
+    "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000",
+    "70736274ff0100fd0a010200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be4000000006a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa88292feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000",
+    "70736274ff000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000000",
+    "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000000",
+];
+
+/// BIP174 valid test vectors with their (input, output) counts
+const BIP174_VALID: &[(&str, usize, usize)] = &[
+    (
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab300000000000000",
+        1,
+        2,
+    ),
+    (
+        "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac000000000001076a47304402204759661797c01b036b25928948686218347d89864b719e1f7fcf57d1e511658702205309eabf56aa4d8891ffd111fdf1336f3a29da866d7f8486d75546ceedaf93190121035cdc61fc7ba971c0b501a646a2a83b102cb43881217ca682dc86e2d73fa882920001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb82308000000",
+        2,
+        2,
+    ),
+    (
+        "70736274ff0100750200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf60000000000feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e1300000100fda5010100000000010289a3c71eab4d20e0371bbba4cc698fa295c9463afa2e397f8533ccb62f9567e50100000017160014be18d152a9b012039daf3da7de4f53349eecb985ffffffff86f8aa43a71dff1448893a530a7237ef6b4608bbb2dd2d0171e63aec6a4890b40100000017160014fe3e9ef1a745e974d902c4355943abcb34bd5353ffffffff0200c2eb0b000000001976a91485cff1097fd9e008bb34af709c62197b38978a4888ac72fef84e2c00000017a914339725ba21efd62ac753a9bcd067d6c7a6a39d05870247304402202712be22e0270f394f568311dc7ca9a68970b8025fdd3b240229f07f8a5f3a240220018b38d7dcd314e734c9276bd6fb40f673325bc4baa144c800d2f2f02db2765c012103d2e15674941bad4a996372cb87e1856d3652606d98562fe39c5e9e7e413f210502483045022100d12b852d85dcd961d2f5f4ab660654df6eedcc794c0c33ce5cc309ffb5fce58d022067338a8e0e1725c197fb1a88af59f51e44e4255b20167c8684031c05d1f2592a01210223b72beef0965d10be0778efecd61fcac6f79a4ea169393380734464f84f2ab30000000001030401000000000000",
+        1,
+        2,
+    ),
+    (
+        "70736274ff0100a00200000002ab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40000000000feffffffab0949a08c5af7c49b8212f417e2f15ab3f5c33dcf153821a8139f877a5b7be40100000000feffffff02603bea0b000000001976a914768a40bbd740cbe81d988e71de2a4d5c71396b1d88ac8e240000000000001976a9146f4620b553fa095e721b9ee0efe9fa039cca459788ac00000000000100df0200000001268171371edff285e937adeea4b37b78000c0566cbb3ad64641713ca42171bf6000000006a473044022070b2245123e6bf474d60c5b50c043d4c691a5d2435f09a34a7662a9dc251790a022001329ca9dacf280bdf30740ec0390422422c81cb45839457aeb76fc12edd95b3012102657d118d3357b8e0f4c2cd46db7b39f6d9c38d9a70abcb9b2de5dc8dbfe4ce31feffffff02d3dff505000000001976a914d0c59903c5bac2868760e90fd521a4665aa7652088ac00e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787b32e13000001012000e1f5050000000017a9143545e6e33b832c47050f24d3eeb93c9c03948bc787010416001485d13537f2e265405a34dbafa9e3dda01fb8230800220202ead596687ca806043edc3de116cdf29d5e9257c196cd055cf698c8d02bf24e9910b4a6ba670000008000000080020000800022020394f62be9df19952c5587768aeb7698061ad2c4a25c894f47d8c162b4d7213d0510b4a6ba6700000080010000800200008000",
+        2,
+        2,
+    ),
+    (
+        "70736274ff0100550200000001279a2323a5dfb51fc45f220fa58b0fc13e1e3342792a85d7e36cd6333b5cbc390000000000ffffffff01a05aea0b000000001976a914ffe9c0061097cc3b636f2cb0460fa4fc427d2b4588ac0000000000010120955eea0b0000000017a9146345200f68d189e1adc0df1c4d16ea8f14c0dbeb87220203b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4646304302200424b58effaaa694e1559ea5c93bbfd4a89064224055cdf070b6771469442d07021f5c8eb0fea6516d60b8acb33ad64ede60e8785bfb3aa94b99bdf86151db9a9a010104220020771fd18ad459666dd49f3d564e3dbc42f4c84774e360ada16816a8ed488d5681010547522103b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd462103de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd52ae220603b1341ccba7683b6af4f1238cd6e97e7167d569fac47f1e48d47541844355bd4610b4a6ba67000000800000008004000080220603de55d1e1dac805e3f8a58c1fbf9b94c02f3dbaafe127fefca4995f26f82083bd10b4a6ba670000008000000080050000800000",
+        1,
+        1,
+    ),
+    (
+        "70736274ff01003f0200000001ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff0000000000ffffffff010000000000000000036a010000000000000a0f0102030405060708090f0102030405060708090a0b0c0d0e0f0000",
+        1,
+        1,
+    ),
+];
+
+#[test]
+fn test_bip174_valid_vectors() {
+    for (i, (vector, input_count, output_count)) in BIP174_VALID.iter().enumerate() {
+        let bytes = hex::decode(vector).unwrap();
+        let psbt = PartiallySignedTransaction::deserialize(&bytes)
+            .unwrap_or_else(|e| panic!("valid vector {} rejected: {}", i + 1, e));
+        assert_eq!(psbt.inputs.len(), *input_count, "vector {}", i + 1);
+        assert_eq!(psbt.outputs.len(), *output_count, "vector {}", i + 1);
+
+        // Round trip is byte-identical and preserves every map
+        let reserialized = psbt.serialize().unwrap();
+        assert_eq!(hex::encode(&reserialized), *vector, "vector {}", i + 1);
+        let roundtrip = PartiallySignedTransaction::deserialize(&reserialized).unwrap();
+        assert_eq!(roundtrip, psbt, "vector {}", i + 1);
+    }
+}
+
+#[test]
+fn test_bip174_valid_vector_exact_bytes() {
+    // Single-key maps serialize deterministically
+    let bytes = hex::decode(BIP174_VALID[0].0).unwrap();
+    let psbt = PartiallySignedTransaction::deserialize(&bytes).unwrap();
+    assert_eq!(psbt.serialize().unwrap(), bytes);
+}
+
+#[test]
+fn test_bip174_invalid_vectors() {
+    for (i, vector) in BIP174_INVALID.iter().enumerate() {
+        let bytes = hex::decode(vector).unwrap();
+        assert!(
+            PartiallySignedTransaction::deserialize(&bytes).is_err(),
+            "invalid vector {} accepted",
+            i + 1
+        );
+    }
+}
+
+#[test]
+fn test_bip174_vector_keys_use_compact_size_types() {
+    for (i, (vector, _, _)) in BIP174_VALID.iter().enumerate() {
+        let psbt = PartiallySignedTransaction::from_hex(vector).unwrap();
+        let maps = std::iter::once(psbt.global.clone())
+            .chain(psbt.inputs.iter().map(PsbtInput::to_map))
+            .chain(psbt.outputs.iter().map(PsbtOutput::to_map));
+        for map in maps {
+            for key in map.keys() {
+                let (key_type, key_data) = split_psbt_key(key).unwrap();
+                assert_eq!(psbt_key(key_type, key_data), *key, "vector {}", i + 1);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_psbt_multibyte_key_types_roundtrip() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    // 0xfd and above need the multi-byte compact size encoding
+    let input_key = psbt_key(0xfd, b"keydata");
+    assert_eq!(input_key[..3], [0xfd, 0xfd, 0x00]);
+    let output_key = psbt_key(0x0001_0002, &[]);
+    assert_eq!(output_key, [0xfe, 0x02, 0x00, 0x01, 0x00]);
+    psbt.add_input_data(0, input_key.clone(), vec![0x01])
+        .unwrap();
+    psbt.add_output_data(0, output_key.clone(), vec![0x02])
+        .unwrap();
+    psbt.add_proprietary(
+        PsbtScope::Input(0),
+        BLLVM_PROPRIETARY_IDENTIFIER,
+        0xfe,
+        b"kd",
+        vec![0x03],
+    )
+    .unwrap();
+
+    let bytes = psbt.serialize().unwrap();
+    let roundtrip = PartiallySignedTransaction::deserialize(&bytes).unwrap();
+    assert_eq!(roundtrip, psbt);
+    assert_eq!(roundtrip.serialize().unwrap(), bytes);
+    assert_eq!(roundtrip.inputs[0].unknown[&input_key], vec![0x01]);
+    assert_eq!(roundtrip.outputs[0].unknown[&output_key], vec![0x02]);
+    assert_eq!(
+        roundtrip.get_proprietary(
+            PsbtScope::Input(0),
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            0xfe,
+            b"kd"
+        ),
+        Some(&[0x03][..])
+    );
+    assert_eq!(split_psbt_key(&input_key).unwrap(), (0xfd, &b"keydata"[..]));
+}
+
+#[test]
+fn test_psbt_rejects_non_minimal_key_type() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    // Type 0x02 (partial signature) padded to three bytes
+    let mut key = vec![0xfd, 0x02, 0x00];
+    key.extend_from_slice(&[0x02; 33]);
+    assert!(split_psbt_key(&key).is_err());
+    assert!(psbt.add_input_data(0, key, vec![0x30]).is_err());
+    assert!(split_psbt_key(&[]).is_err());
+}
+
+#[test]
+fn test_psbt_map_data_containing_separator_bytes() {
+    // 0xff inside map values must not be mistaken for a map boundary
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+    psbt.add_input_data(0, vec![0xfc, 0xff], vec![0xff; 4])
+        .unwrap();
+    psbt.add_output_data(0, vec![0xfc, 0x00], vec![0xff, 0x00, 0xff])
+        .unwrap();
+
+    let serialized = psbt.serialize().unwrap();
+    let deserialized = PartiallySignedTransaction::deserialize(&serialized).unwrap();
+    assert_eq!(deserialized, psbt);
+}
+
+#[test]
+fn test_psbt_serialize_rejects_missing_maps() {
+    // Maps are required for every transaction input and output
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    let deserialized = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(deserialized.inputs.len(), 1);
+    assert_eq!(deserialized.outputs.len(), 1);
+    assert_eq!(deserialized.inputs[0], PsbtInput::default());
+
+    psbt.outputs.clear();
+    assert!(psbt.validate_structure().is_err());
+    assert!(psbt.serialize().is_err());
+}
+
+#[test]
+fn test_psbt_serialize_rejects_extra_maps() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+    psbt.add_input_data(1, vec![0xfc], vec![0x01]).unwrap();
+    assert!(psbt.serialize().is_err());
+}
+
+// ============================================================================
+// Phase 12: Base64 / Hex Encoding Tests
+// ============================================================================
+
+/// BIP174 valid vector 1 in its published base64 form
+const BIP174_VALID_1_BASE64: &str = "cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UBAAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHmOuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkqASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAAAA";
+
+#[test]
+fn test_psbt_base64_roundtrip_bip174_vector() {
+    let psbt = PartiallySignedTransaction::from_base64(BIP174_VALID_1_BASE64).unwrap();
+    assert_eq!(psbt.to_base64().unwrap(), BIP174_VALID_1_BASE64);
+
+    // Same PSBT as the hex form of the vector
+    let from_hex = PartiallySignedTransaction::from_hex(BIP174_VALID[0].0).unwrap();
+    assert_eq!(from_hex, psbt);
+    assert_eq!(psbt.to_hex().unwrap(), BIP174_VALID[0].0);
+}
+
+#[test]
+fn test_psbt_base64_tolerates_whitespace() {
+    let wrapped: String = BIP174_VALID_1_BASE64
+        .as_bytes()
+        .chunks(64)
+        .map(|line| format!("  {}\n", std::str::from_utf8(line).unwrap()))
+        .collect();
+    let psbt = PartiallySignedTransaction::from_base64(&wrapped).unwrap();
+    assert_eq!(psbt.to_base64().unwrap(), BIP174_VALID_1_BASE64);
+}
+
+#[test]
+fn test_psbt_auto_decode() {
+    let psbt = PartiallySignedTransaction::from_base64(BIP174_VALID_1_BASE64).unwrap();
+    let hex_upper = BIP174_VALID[0].0.to_uppercase();
+
+    assert_eq!(
+        PartiallySignedTransaction::auto_decode(BIP174_VALID_1_BASE64).unwrap(),
+        psbt
+    );
+    assert_eq!(
+        PartiallySignedTransaction::auto_decode(BIP174_VALID[0].0).unwrap(),
+        psbt
+    );
+    assert_eq!(
+        PartiallySignedTransaction::auto_decode(&format!("\n{}\n", hex_upper)).unwrap(),
+        psbt
+    );
+    assert!(PartiallySignedTransaction::auto_decode("").is_err());
+    assert!(PartiallySignedTransaction::auto_decode("70736274").is_err());
+
+    // Display is base64, Debug is hex
+    assert_eq!(psbt.to_string(), BIP174_VALID_1_BASE64);
+    assert_eq!(
+        format!("{:?}", psbt),
+        format!("PartiallySignedTransaction({})", BIP174_VALID[0].0)
+    );
+}
+
+#[test]
+fn test_psbt_base64_error_kinds() {
+    // Not base64 at all
+    let err = PartiallySignedTransaction::from_base64("not*base64!").unwrap_err();
+    assert!(matches!(err, GovernanceError::Serialization(_)));
+
+    // Valid base64 of a raw transaction, which lacks the PSBT magic
+    let raw_tx = "AgAAAAEmgXE3Ht/yhek3re6ks3t4AAwFZsuzrWRkFxPKQhcb9gAAAABqRzBEAiBwsiRRI+a/R01gxbUMBD1MaRpdJDXwmjSnZiqdwlF5CgIgATKcqdrPKAvfMHQOwDkEIkIsgctFg5RXrrdvwS7dlbMBIQJlfRGNM1e44PTCzUbbezn22cONmnCry5st5dyNv+TOMf7///8C09/1BQAAAAAZdqkU0MWZA8W6woaHYOkP1SGkZlqnZSCIrADh9QUAAAAAF6kUNUXm4zuDLEcFDyTT7rk8nAOUi8eHsy4TAA==";
+    let err = PartiallySignedTransaction::from_base64(raw_tx).unwrap_err();
+    assert!(matches!(err, GovernanceError::InvalidInput(_)));
+
+    let err = PartiallySignedTransaction::from_hex("zz").unwrap_err();
+    assert!(matches!(err, GovernanceError::Serialization(_)));
+}
+
+// ============================================================================
+// Phase 13: Proprietary Field Tests
+// ============================================================================
+
+#[test]
+fn test_psbt_proprietary_fields_roundtrip() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+
+    psbt.add_proprietary_global(b"BLLVM", 1, b"governance")
+        .unwrap();
+    psbt.add_proprietary_input(0, b"BLLVM", 2, &[0xff, 0x00])
+        .unwrap();
+    psbt.add_proprietary_output(0, b"BLLVM", 0x1_0000, b"large tag")
+        .unwrap();
+
+    let serialized = psbt.serialize().unwrap();
+    let deserialized = PartiallySignedTransaction::deserialize(&serialized).unwrap();
+
+    assert_eq!(
+        deserialized.get_proprietary_global(b"BLLVM", 1),
+        Some(&b"governance"[..])
+    );
+    assert_eq!(
+        deserialized.get_proprietary_input(0, b"BLLVM", 2),
+        Some(&[0xff, 0x00][..])
+    );
+    assert_eq!(
+        deserialized.get_proprietary_output(0, b"BLLVM", 0x1_0000),
+        Some(&b"large tag"[..])
+    );
+
+    // Lookups match on both identifier and tag
+    assert_eq!(deserialized.get_proprietary_global(b"BLLVM", 2), None);
+    assert_eq!(deserialized.get_proprietary_global(b"OTHER", 1), None);
+    assert_eq!(deserialized.get_proprietary_input(1, b"BLLVM", 2), None);
+}
+
+#[test]
+fn test_psbt_proprietary_key_format() {
+    let unsigned_tx = create_mock_unsigned_tx();
+    let mut psbt = PartiallySignedTransaction::new(&unsigned_tx).unwrap();
+    psbt.add_proprietary_global(b"BLLVM", 7, b"v").unwrap();
+
+    // <0xfc><identifier_len><identifier><subtype>
+    let mut key = vec![PsbtGlobalKey::Proprietary as u8, 5];
+    key.extend_from_slice(b"BLLVM");
+    key.push(7);
+    assert_eq!(psbt.global.get(&key), Some(&b"v".to_vec()));
+}
+
+#[test]
+fn test_psbt_proprietary_keydata_scopes() {
+    let tx = create_spending_tx(2);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    for scope in [PsbtScope::Global, PsbtScope::Input(1), PsbtScope::Output(0)] {
+        psbt.add_proprietary(scope, BLLVM_PROPRIETARY_IDENTIFIER, 0x42, b"kd", vec![1, 2])
+            .unwrap();
+    }
+
+    // <0xfc><identifier_len><identifier><subtype><keydata>
+    let mut key = vec![0xfc, 5];
+    key.extend_from_slice(b"bllvm");
+    key.push(0x42);
+    key.extend_from_slice(b"kd");
+    assert_eq!(psbt.inputs[1].unknown.get(&key), Some(&vec![1, 2]));
+
+    let roundtrip = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    for scope in [PsbtScope::Global, PsbtScope::Input(1), PsbtScope::Output(0)] {
+        assert_eq!(
+            roundtrip.get_proprietary(scope, BLLVM_PROPRIETARY_IDENTIFIER, 0x42, b"kd"),
+            Some(&[1, 2][..])
+        );
+        assert_eq!(
+            roundtrip.get_proprietary(scope, BLLVM_PROPRIETARY_IDENTIFIER, 0x42, b"other"),
+            None
+        );
+    }
+    assert_eq!(
+        roundtrip.get_proprietary(
+            PsbtScope::Input(0),
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            0x42,
+            b"kd"
+        ),
+        None
+    );
+
+    // Subtypes past 0xfc use a multi-byte compact size
+    psbt.add_proprietary(PsbtScope::Global, b"x", 0xfd, &[], vec![9])
+        .unwrap();
+    assert_eq!(
+        psbt.global.get(&vec![0xfc, 1, b'x', 0xfd, 0xfd, 0x00]),
+        Some(&vec![9])
+    );
+
+    // rust-bitcoin keeps the fields as proprietary data
+    let btc_psbt = bitcoin::psbt::Psbt::deserialize(&psbt.serialize().unwrap()).unwrap();
+    let reencoded = PartiallySignedTransaction::deserialize(&btc_psbt.serialize()).unwrap();
+    assert_eq!(
+        reencoded.get_proprietary(
+            PsbtScope::Output(0),
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            0x42,
+            b"kd"
+        ),
+        Some(&[1, 2][..])
+    );
+    assert_eq!(
+        reencoded.get_proprietary(PsbtScope::Global, b"x", 0xfd, &[]),
+        Some(&[9][..])
+    );
+}
+
+#[test]
+fn test_psbt_governance_metadata() {
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    assert_eq!(psbt.signing_request_hash().unwrap(), None);
+    assert_eq!(psbt.roster_version().unwrap(), None);
+
+    psbt.set_signing_request_hash([0xab; 32]).unwrap();
+    psbt.set_roster_version(7).unwrap();
+
+    let roundtrip = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(roundtrip.signing_request_hash().unwrap(), Some([0xab; 32]));
+    assert_eq!(roundtrip.roster_version().unwrap(), Some(7));
+
+    // Malformed values are reported, not truncated
+    psbt.add_proprietary(
+        PsbtScope::Global,
+        BLLVM_PROPRIETARY_IDENTIFIER,
+        blvm_sdk::governance::psbt::BLLVM_SUBTYPE_ROSTER_VERSION,
+        &[],
+        vec![1],
+    )
+    .unwrap();
+    assert!(psbt.roster_version().is_err());
+}
+
+#[test]
+fn test_psbt_combine_keeps_proprietary_fields() {
+    let tx = create_spending_tx(1);
+    let mut psbt_a = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    let mut psbt_b = psbt_a.clone();
+
+    psbt_a.set_roster_version(3).unwrap();
+    psbt_a
+        .add_proprietary(
+            PsbtScope::Input(0),
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            1,
+            b"a",
+            vec![0xa],
+        )
+        .unwrap();
+    psbt_b.set_signing_request_hash([0x01; 32]).unwrap();
+    psbt_b
+        .add_proprietary(
+            PsbtScope::Output(0),
+            BLLVM_PROPRIETARY_IDENTIFIER,
+            1,
+            b"b",
+            vec![0xb],
+        )
+        .unwrap();
+    psbt_b
+        .add_partial_signature(0, vec![0x02; 33], vec![0x30, 0x01])
+        .unwrap();
+
+    psbt_a.combine(&psbt_b).unwrap();
+    let combined = PartiallySignedTransaction::deserialize(&psbt_a.serialize().unwrap()).unwrap();
+    assert_eq!(combined.roster_version().unwrap(), Some(3));
+    assert_eq!(combined.signing_request_hash().unwrap(), Some([0x01; 32]));
+    assert_eq!(
+        combined.get_proprietary(PsbtScope::Input(0), BLLVM_PROPRIETARY_IDENTIFIER, 1, b"a"),
+        Some(&[0xa][..])
+    );
+    assert_eq!(
+        combined.get_proprietary(PsbtScope::Output(0), BLLVM_PROPRIETARY_IDENTIFIER, 1, b"b"),
+        Some(&[0xb][..])
+    );
+    assert_eq!(combined.inputs[0].partial_sigs.len(), 1);
+
+    // PSBTs for different transactions cannot be combined
+    let other = PartiallySignedTransaction::new(&create_spending_tx(2).serialize()).unwrap();
+    assert!(psbt_a.combine(&other).is_err());
+}
+
+// ============================================================================
+// Phase 14: Signer Tests
+// ============================================================================
+
+/// Test helper: An unsigned transaction spending one output per prevout
+fn create_spending_tx(prevouts: usize) -> Transaction {
+    Transaction {
+        version: 2,
+        inputs: (0..prevouts)
+            .map(|i| TxIn {
+                previous_txid: [i as u8 + 1; 32],
+                previous_vout: i as u32,
+                script_sig: Vec::new(),
+                sequence: 0xffff_fffd,
+                witness: Vec::new(),
+            })
+            .collect(),
+        outputs: vec![TxOut {
+            value: 90_000,
+            script_pubkey: vec![
+                0x00, 0x14, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+                0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa, 0xaa,
+            ],
+        }],
+        lock_time: 0,
+    }
+}
+
+/// Test helper: Serialize a TxOut as a witness_utxo value
+fn witness_utxo(value: u64, script_pubkey: Vec<u8>) -> Vec<u8> {
+    TxOut {
+        value,
+        script_pubkey,
+    }
+    .serialize()
+}
+
+/// Test helper: Split a partial signature into a DER signature and sighash byte
+fn split_signature(signature: &[u8]) -> (Signature, u8) {
+    let (sighash, der) = signature.split_last().unwrap();
+    (Signature::from_der(der).unwrap(), *sighash)
+}
+
+#[test]
+fn test_sign_p2wpkh_with_xprv() {
+    let secp = Secp256k1::new();
+    let (master, _) = derive_master_key(&[0x42; 32]).unwrap();
+    let path = [84 | 0x8000_0000, 0x8000_0000, 0x8000_0000, 0, 0];
+
+    let mut child = master.clone();
+    for &index in &path {
+        child = child.derive_child(index).unwrap().0;
+    }
+    let pubkey = child.to_extended_public().public_key_bytes();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x00, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey.clone()),
+    )
+    .unwrap();
+    let master_fingerprint = {
+        let hash =
+            bitcoin::hashes::hash160::Hash::hash(&master.to_extended_public().public_key_bytes());
+        let mut fp = [0u8; 4];
+        fp.copy_from_slice(&hash.as_byte_array()[..4]);
+        fp
+    };
+    psbt.add_bip32_derivation(
+        0,
+        pubkey.to_vec(),
+        Bip32Derivation {
+            pubkey: pubkey.to_vec(),
+            path: path.to_vec(),
+            master_fingerprint,
+        },
+    )
+    .unwrap();
+
+    let summary = psbt.sign_with_xprv(&master).unwrap();
+    assert_eq!(summary.signed, vec![0]);
+    assert!(summary.skipped.is_empty());
+
+    // Check the signature against an independent BIP143 implementation
+    let signature = &psbt.inputs[0].partial_sigs[&pubkey.to_vec()];
+    let (signature, sighash_byte) = split_signature(signature);
+    assert_eq!(sighash_byte, SighashType::All.to_byte());
+
+    let btc_tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+    let sighash = bitcoin::sighash::SighashCache::new(&btc_tx)
+        .p2wpkh_signature_hash(
+            0,
+            bitcoin::Script::from_bytes(&script_pubkey),
+            bitcoin::Amount::from_sat(100_000),
+            bitcoin::sighash::EcdsaSighashType::All,
+        )
+        .unwrap();
+    let message = Message::from_digest(sighash.to_byte_array());
+    let secp_pubkey = PublicKey::from_slice(&pubkey).unwrap();
+    assert!(secp
+        .verify_ecdsa(&message, &signature, &secp_pubkey)
+        .is_ok());
+
+    // The signature survives a serialization round trip
+    let roundtrip = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(
+        roundtrip.inputs[0].partial_sigs,
+        psbt.inputs[0].partial_sigs
+    );
+}
+
+#[test]
+fn test_sign_p2wsh_multisig_with_key() {
+    let secp = Secp256k1::new();
+    let keys: Vec<SecretKey> = (1..=3u8)
+        .map(|i| SecretKey::from_slice(&[i; 32]).unwrap())
+        .collect();
+
+    // 2-of-3 CHECKMULTISIG witness script
+    let mut witness_script = vec![0x52];
+    for key in &keys {
+        witness_script.push(33);
+        witness_script.extend_from_slice(&key.public_key(&secp).serialize());
+    }
+    witness_script.extend_from_slice(&[0x53, 0xae]);
+    let script_hash = bitcoin::hashes::sha256::Hash::hash(&witness_script);
+    let mut script_pubkey = vec![0x00, 0x20];
+    script_pubkey.extend_from_slice(script_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessScript as u8],
+        witness_script.clone(),
+    )
+    .unwrap();
+    psbt.set_sighash_type(0, SighashType::AllAnyoneCanPay)
+        .unwrap();
+
+    for key in &keys[..2] {
+        let summary = psbt.sign_with_key(key).unwrap();
+        assert_eq!(summary.signed, vec![0]);
+    }
+
+    let btc_tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+    let sighash = bitcoin::sighash::SighashCache::new(&btc_tx)
+        .p2wsh_signature_hash(
+            0,
+            bitcoin::Script::from_bytes(&witness_script),
+            bitcoin::Amount::from_sat(100_000),
+            bitcoin::sighash::EcdsaSighashType::AllPlusAnyoneCanPay,
+        )
+        .unwrap();
+    let message = Message::from_digest(sighash.to_byte_array());
+
+    for key in &keys[..2] {
+        let pubkey = key.public_key(&secp);
+        let (signature, sighash_byte) =
+            split_signature(&psbt.inputs[0].partial_sigs[&pubkey.serialize().to_vec()]);
+        assert_eq!(sighash_byte, SighashType::AllAnyoneCanPay.to_byte());
+        assert!(secp.verify_ecdsa(&message, &signature, &pubkey).is_ok());
+    }
+}
+
+#[test]
+fn test_sign_reports_skipped_inputs() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[7; 32]).unwrap();
+    let other = SecretKey::from_slice(&[8; 32]).unwrap();
+    let p2wpkh = |key: &SecretKey| {
+        let hash = bitcoin::hashes::hash160::Hash::hash(&key.public_key(&secp).serialize());
+        let mut script = vec![0x00, 0x14];
+        script.extend_from_slice(hash.as_byte_array());
+        script
+    };
+
+    let tx = create_spending_tx(3);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    let utxo_key = vec![PsbtInputKey::WitnessUtxo as u8];
+    psbt.add_input_data(0, utxo_key.clone(), witness_utxo(50_000, p2wpkh(&key)))
+        .unwrap();
+    psbt.add_input_data(1, utxo_key, witness_utxo(50_000, p2wpkh(&other)))
+        .unwrap();
+    // Input 2 has no UTXO information
+
+    let summary = psbt.sign_with_key(&key).unwrap();
+    assert_eq!(summary.signed, vec![0]);
+    assert_eq!(
+        summary.skipped.iter().map(|(i, _)| *i).collect::<Vec<_>>(),
+        vec![1, 2]
+    );
+
+    // No derivations for this master key: nothing is signed
+    let (master, _) = derive_master_key(&[0x01; 32]).unwrap();
+    let summary = psbt.sign_with_xprv(&master).unwrap();
+    assert!(summary.signed.is_empty());
+    assert_eq!(summary.skipped.len(), 3);
+}
+
+#[test]
+fn test_sign_p2pkh_legacy() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[9; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x76, 0xa9, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+    script_pubkey.extend_from_slice(&[0x88, 0xac]);
+
+    let mut prev_tx = create_spending_tx(1);
+    prev_tx.outputs = vec![TxOut {
+        value: 100_000,
+        script_pubkey: script_pubkey.clone(),
+    }];
+    let mut tx = create_spending_tx(1);
+    tx.inputs[0].previous_txid = prev_tx.txid();
+    tx.inputs[0].previous_vout = 0;
+
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+
+    // Legacy inputs need the full previous transaction, not just the output
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey.clone()),
+    )
+    .unwrap();
+    let summary = psbt.sign_with_key(&key).unwrap();
+    assert_eq!(summary.skipped.len(), 1);
+    assert!(summary.skipped[0].1.contains("non_witness_utxo"));
+
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::NonWitnessUtxo as u8],
+        prev_tx.serialize(),
+    )
+    .unwrap();
+    psbt.set_sighash_type(0, SighashType::Single).unwrap();
+    let summary = psbt.sign_with_key(&key).unwrap();
+    assert_eq!(summary.signed, vec![0]);
+
+    // Check the signature against an independent legacy sighash implementation
+    let btc_tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+    let sighash = bitcoin::sighash::SighashCache::new(&btc_tx)
+        .legacy_signature_hash(
+            0,
+            bitcoin::Script::from_bytes(&script_pubkey),
+            SighashType::Single.to_byte() as u32,
+        )
+        .unwrap();
+    let message = Message::from_digest(sighash.to_byte_array());
+    let (signature, sighash_byte) = split_signature(&psbt.inputs[0].partial_sigs[&pubkey.to_vec()]);
+    assert_eq!(sighash_byte, SighashType::Single.to_byte());
+    assert!(secp
+        .verify_ecdsa(&message, &signature, &key.public_key(&secp))
+        .is_ok());
+
+    // Keys that don't match the output are skipped
+    let other = SecretKey::from_slice(&[10; 32]).unwrap();
+    let summary = psbt.sign_with_key(&other).unwrap();
+    assert!(summary.signed.is_empty());
+}
+
+// ============================================================================
+// Phase 15: Finalizer Tests
+// ============================================================================
+
+#[test]
+fn test_finalize_p2wsh_multisig_in_script_order() {
+    let secp = Secp256k1::new();
+    let keys: Vec<SecretKey> = (1..=3u8)
+        .map(|i| SecretKey::from_slice(&[i; 32]).unwrap())
+        .collect();
+
+    // Script key order differs from signing order: key 3, key 1, key 2
+    let script_order = [&keys[2], &keys[0], &keys[1]];
+    let mut witness_script = vec![0x52];
+    for key in script_order {
+        witness_script.push(33);
+        witness_script.extend_from_slice(&key.public_key(&secp).serialize());
+    }
+    witness_script.extend_from_slice(&[0x53, 0xae]);
+    let script_hash = bitcoin::hashes::sha256::Hash::hash(&witness_script);
+    let mut script_pubkey = vec![0x00, 0x20];
+    script_pubkey.extend_from_slice(script_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    let utxo = witness_utxo(100_000, script_pubkey);
+    psbt.add_input_data(0, vec![PsbtInputKey::WitnessUtxo as u8], utxo.clone())
+        .unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessScript as u8],
+        witness_script.clone(),
+    )
+    .unwrap();
+    for key in &keys {
+        psbt.sign_with_key(key).unwrap();
+    }
+
+    let signature_of = |psbt: &PartiallySignedTransaction, key: &SecretKey| {
+        psbt.inputs[0].partial_sigs[&key.public_key(&secp).serialize().to_vec()].clone()
+    };
+    let expected = vec![
+        Vec::new(),
+        signature_of(&psbt, &keys[2]),
+        signature_of(&psbt, &keys[0]),
+        witness_script,
+    ];
+
+    psbt.finalize().unwrap();
+    assert!(psbt.is_finalized());
+
+    assert_eq!(psbt.inputs[0].final_script_witness, Some(expected));
+
+    // Signing fields are stripped; the UTXO is kept
+    let remaining: Vec<u8> = psbt.inputs[0].to_map().keys().map(|key| key[0]).collect();
+    assert_eq!(
+        remaining,
+        vec![
+            PsbtInputKey::WitnessUtxo as u8,
+            PsbtInputKey::FinalScriptWitness as u8
+        ]
+    );
+    assert_eq!(
+        psbt.inputs[0].witness_utxo.as_ref().map(TxOut::serialize),
+        Some(utxo)
+    );
+}
+
+#[test]
+fn test_finalize_multisig_insufficient_signatures() {
+    let secp = Secp256k1::new();
+    let keys: Vec<SecretKey> = (1..=3u8)
+        .map(|i| SecretKey::from_slice(&[i; 32]).unwrap())
+        .collect();
+    let mut witness_script = vec![0x52];
+    for key in &keys {
+        witness_script.push(33);
+        witness_script.extend_from_slice(&key.public_key(&secp).serialize());
+    }
+    witness_script.extend_from_slice(&[0x53, 0xae]);
+    let script_hash = bitcoin::hashes::sha256::Hash::hash(&witness_script);
+    let mut script_pubkey = vec![0x00, 0x20];
+    script_pubkey.extend_from_slice(script_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    psbt.add_input_data(0, vec![PsbtInputKey::WitnessScript as u8], witness_script)
+        .unwrap();
+    psbt.sign_with_key(&keys[1]).unwrap();
+
+    let err = psbt.finalize_input(0).unwrap_err();
+    assert!(matches!(
+        err,
+        GovernanceError::InsufficientSignatures { got: 1, need: 2 }
+    ));
+    // A failed finalization leaves the input untouched
+    assert!(!psbt.is_finalized());
+    assert_eq!(psbt.inputs[0].to_map().len(), 3);
+}
+
+#[test]
+fn test_finalize_p2sh_p2wpkh() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[9; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut redeem_script = vec![0x00, 0x14];
+    redeem_script.extend_from_slice(pubkey_hash.as_byte_array());
+    let script_hash = bitcoin::hashes::hash160::Hash::hash(&redeem_script);
+    let mut script_pubkey = vec![0xa9, 0x14];
+    script_pubkey.extend_from_slice(script_hash.as_byte_array());
+    script_pubkey.push(0x87);
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::RedeemScript as u8],
+        redeem_script.clone(),
+    )
+    .unwrap();
+    assert_eq!(psbt.sign_with_key(&key).unwrap().signed, vec![0]);
+    psbt.finalize_input(0).unwrap();
+
+    let mut expected_script_sig = vec![redeem_script.len() as u8];
+    expected_script_sig.extend_from_slice(&redeem_script);
+    assert_eq!(psbt.inputs[0].final_script_sig, Some(expected_script_sig));
+    let witness = psbt.inputs[0].final_script_witness.as_ref().unwrap();
+    assert_eq!(witness.len(), 2);
+    assert_eq!(witness[1], pubkey.to_vec());
+}
+
+#[test]
+fn test_finalize_p2pkh() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[5; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x76, 0xa9, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+    script_pubkey.extend_from_slice(&[0x88, 0xac]);
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    let signature = vec![0x30; 71];
+    psbt.add_partial_signature(0, pubkey.to_vec(), signature.clone())
+        .unwrap();
+    psbt.finalize().unwrap();
+
+    let mut expected = vec![71];
+    expected.extend_from_slice(&signature);
+    expected.push(33);
+    expected.extend_from_slice(&pubkey);
+    assert_eq!(psbt.inputs[0].final_script_sig, Some(expected));
+    assert_eq!(psbt.inputs[0].final_script_witness, None);
+}
+
+#[test]
+fn test_finalize_skips_finalized_inputs() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[5; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x76, 0xa9, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+    script_pubkey.extend_from_slice(&[0x88, 0xac]);
+
+    let tx = create_spending_tx(2);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    for index in 0..2 {
+        psbt.add_input_data(
+            index,
+            vec![PsbtInputKey::WitnessUtxo as u8],
+            witness_utxo(100_000, script_pubkey.clone()),
+        )
+        .unwrap();
+        psbt.add_partial_signature(index, pubkey.to_vec(), vec![0x30; 71])
+            .unwrap();
+    }
+
+    // Another party already finalized the first input
+    psbt.finalize_input(0).unwrap();
+    let first = psbt.inputs[0].clone();
+    assert!(first.partial_sigs.is_empty());
+    psbt.finalize().unwrap();
+    assert!(psbt.is_finalized());
+    assert_eq!(psbt.inputs[0], first);
+
+    // Finalizing again changes nothing
+    let finalized = psbt.clone();
+    psbt.finalize().unwrap();
+    psbt.finalize_input(1).unwrap();
+    assert_eq!(psbt, finalized);
+}
+
+#[test]
+fn test_finalize_unsupported_script() {
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    let mut p2tr = vec![0x51, 0x20];
+    p2tr.extend_from_slice(&[0x11; 32]);
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, p2tr),
+    )
+    .unwrap();
+
+    match psbt.finalize_input(0).unwrap_err() {
+        GovernanceError::NotImplemented(msg) => assert!(msg.contains("P2TR"), "{}", msg),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Phase 16: Extractor Tests
+// ============================================================================
+
+/// BIP174 extractor vector: finalized PSBT (P2SH multisig and P2SH-P2WSH inputs)
+const BIP174_FINALIZED_PSBT: &str = "cHNidP8BAJoCAAAAAljoeiG1ba8MI76OcHBFbDNvfLqlyHV5JPVFiHuyq911AAAAAAD/////g40EJ9DsZQpoqka7CwmK6kQiwHGyyng1Kgd5WdB86h0BAAAAAP////8CcKrwCAAAAAAWABTYXCtx0AYLCcmIauuBXlCZHdoSTQDh9QUAAAAAFgAUAK6pouXw+HaliN9VRuh0LR2HAI8AAAAAAAEAuwIAAAABqtc5MQGL0l+ErkALaISL4J23BurCrBgpi6vucatlb4sAAAAASEcwRAIgWPb8fGoz4bMVSNSByCbAFb0wE1qtQs1neQ2rZtKtJDsCIEoc7SYExnNbY5PltBaR3XiwDwxZQvufdRhW+qk4FX26Af7///8CgPD6AgAAAAAXqRQPuUY0IWlrgsgzryQceMF9295JNIfQ8gonAQAAABepFCnKdPigj4GZlCgYXJe12FLkBj9hh2UAAAABB9oARzBEAiB0AYrUGACXuHMyPAAVcgs2hMyBI4kQSOfbzZtVrWecmQIgc9Npt0Dj61Pc76M4I8gHBRTKVafdlUTxV8FnkTJhEYwBSDBFAiEA9hA4swjcHahlo0hSdG8BV3KTQgjG0kRUOTzZm98iF3cCIAVuZ1pnWm0KArhbFOXikHTYolqbV2C+ooFvZhkQoAbqAUdSIQKVg785rgpgl0etGZrd1jT6YQhVnWxc05tMIYPxq5bgfyEC2rYf9JoU22p9ArDNH7t4/EsYMStbTlTa5Nui+/71NtdSrgABASAAwusLAAAAABepFLf1+vQOPUClpFmx2zU18rcvqSHohwEHIyIAIIwjUxc3Q7WV37Sge3K6jkLjeX2nTof+fZ10l+OyAokDAQjaBABHMEQCIGLrelVhB6fHP0WsSrWh3d9vcHX7EnWWmn84Pv/3hLyyAiAMBdu3Rw2/LwhVfdNWxzJcHtMJE+mWzThAlF2xIijaXwFHMEQCIGX0W6WZi1mif/4ae+0BavHx+Q1Us6qPdFCqX1aiUQO9AiB/ckcDrR7blmgLKEtW1P/LiPf7dZ6rvgiqMPKbhROD0gFHUiEDCJ3BDHrG21T5EymvYXMz2ziM6tDCMfcjN50bmQMLAtwhAjrdkE89bc9Z3bkGsN7iNSm3/7ntUOXoYVGSaGAiHw5zUq4AIgIDqaTDf1mW06ol26xrVwrwZQOUSSlCRgs1R1Ptnuylh3EQ2QxqTwAAAIAAAACABAAAgAAiAgJ/Y5l1fS7/VaE2rQLGhLGDi2VW5fG2s0KCqUtrUAUQlhDZDGpPAAAAgAAAAIAFAACAAA==";
+
+/// BIP174 extractor vector: the network transaction produced by Bitcoin Core
+const BIP174_EXTRACTED_TX: &str = "0200000000010258e87a21b56daf0c23be8e7070456c336f7cbaa5c8757924f545887bb2abdd7500000000da00473044022074018ad4180097b873323c0015720b3684cc8123891048e7dbcd9b55ad679c99022073d369b740e3eb53dcefa33823c8070514ca55a7dd9544f157c167913261118c01483045022100f61038b308dc1da865a34852746f015772934208c6d24454393cd99bdf2217770220056e675a675a6d0a02b85b14e5e29074d8a25a9b5760bea2816f661910a006ea01475221029583bf39ae0a609747ad199addd634fa6108559d6c5cd39b4c2183f1ab96e07f2102dab61ff49a14db6a7d02b0cd1fbb78fc4b18312b5b4e54dae4dba2fbfef536d752aeffffffff838d0427d0ec650a68aa46bb0b098aea4422c071b2ca78352a077959d07cea1d01000000232200208c2353173743b595dfb4a07b72ba8e42e3797da74e87fe7d9d7497e3b2028903ffffffff0270aaf00800000000160014d85c2b71d0060b09c9886aeb815e50991dda124d00e1f5050000000016001400aea9a2e5f0f876a588df5546e8742d1d87008f000400473044022062eb7a556107a7c73f45ac4ab5a1dddf6f7075fb1275969a7f383efff784bcb202200c05dbb7470dbf2f08557dd356c7325c1ed30913e996cd3840945db12228da5f01473044022065f45ba5998b59a27ffe1a7bed016af1f1f90d54b3aa8f7450aa5f56a25103bd02207f724703ad1edb96680b284b56d4ffcb88f7fb759eabbe08aa30f29b851383d20147522103089dc10c7ac6db54f91329af617333db388cead0c231f723379d1b99030b02dc21023add904f3d6dcf59ddb906b0dee23529b7ffb9ed50e5e86151926860221f0e7352ae00000000";
+
+#[test]
+fn test_extract_bip174_vector() {
+    let psbt = PartiallySignedTransaction::from_base64(BIP174_FINALIZED_PSBT).unwrap();
+    assert!(psbt.is_finalized());
+    let tx = psbt.extract_transaction().unwrap();
+    assert_eq!(hex::encode(tx), BIP174_EXTRACTED_TX);
+}
+
+#[test]
+fn test_psbt_core_vector_roundtrip_exact() {
+    // Bitcoin Core finalizer output round trips byte for byte
+    let psbt = PartiallySignedTransaction::from_base64(BIP174_FINALIZED_PSBT).unwrap();
+    assert_eq!(psbt.to_base64().unwrap(), BIP174_FINALIZED_PSBT);
+
+    let bytes = psbt.serialize().unwrap();
+    assert_eq!(
+        PartiallySignedTransaction::deserialize(&bytes).unwrap(),
+        psbt
+    );
+}
+
+#[test]
+fn test_extract_p2wpkh() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[3; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x00, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    psbt.sign_with_key(&key).unwrap();
+    let signature = psbt.inputs[0].partial_sigs[&pubkey.to_vec()].clone();
+    psbt.finalize().unwrap();
+
+    // Reference: the same transaction with the witness set by rust-bitcoin
+    let mut expected: bitcoin::Transaction =
+        bitcoin::consensus::deserialize(&tx.serialize()).unwrap();
+    expected.input[0].witness = bitcoin::Witness::from_slice(&[signature, pubkey.to_vec()]);
+    let expected = bitcoin::consensus::serialize(&expected);
+
+    let extracted = psbt.extract_transaction().unwrap();
+    assert_eq!(extracted, expected);
+    // Segwit marker and flag follow the version
+    assert_eq!(&extracted[4..6], &[0x00, 0x01]);
+    assert_eq!(Transaction::parse(&extracted).unwrap().txid(), tx.txid());
+}
+
+#[test]
+fn test_extract_names_first_unfinalized_input() {
+    let tx = create_spending_tx(3);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(0, vec![PsbtInputKey::FinalScriptSig as u8], vec![0x51])
+        .unwrap();
+    psbt.add_input_data(2, vec![PsbtInputKey::FinalScriptSig as u8], vec![0x51])
+        .unwrap();
+
+    match psbt.extract_transaction().unwrap_err() {
+        GovernanceError::InvalidInput(msg) => assert!(msg.contains("input 1"), "{}", msg),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+// ============================================================================
+// Phase 17: Global Xpub Tests
+// ============================================================================
+
+/// Test helper: Master key fingerprint via rust-bitcoin
+fn master_fingerprint(master: &ExtendedPrivateKey) -> [u8; 4] {
+    let hash =
+        bitcoin::hashes::hash160::Hash::hash(&master.to_extended_public().public_key_bytes());
+    let mut fp = [0u8; 4];
+    fp.copy_from_slice(&hash.as_byte_array()[..4]);
+    fp
+}
+
+/// Test helper: Derive a private key along a full path
+fn derive_path(master: &ExtendedPrivateKey, path: &[u32]) -> ExtendedPrivateKey {
+    let mut key = master.clone();
+    for &index in path {
+        key = key.derive_child(index).unwrap().0;
+    }
+    key
+}
+
+#[test]
+fn test_global_xpub_roundtrip() {
+    let (master, _) = derive_master_key(&[0x11; 32]).unwrap();
+    let fingerprint = master_fingerprint(&master);
+    let account_path = vec![0x8000_0054, 0x8000_0000, 0x8000_0000];
+    let account = derive_path(&master, &account_path).to_extended_public();
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_global_xpub(&account, fingerprint, &account_path)
+        .unwrap();
+
+    // Duplicates are rejected
+    assert!(psbt
+        .add_global_xpub(&account, fingerprint, &account_path)
+        .is_err());
+
+    let roundtrip = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    let xpubs = roundtrip.global_xpubs();
+    assert_eq!(xpubs.len(), 1);
+    let (xpub, xpub_fingerprint, path) = &xpubs[0];
+    assert_eq!(xpub.public_key_bytes(), account.public_key_bytes());
+    assert_eq!(xpub.chain_code, account.chain_code);
+    assert_eq!(*xpub_fingerprint, fingerprint);
+    assert_eq!(*path, account_path);
+
+    // rust-bitcoin reads the same entry
+    let btc_psbt = bitcoin::psbt::Psbt::deserialize(&psbt.serialize().unwrap()).unwrap();
+    let (btc_xpub, (btc_fingerprint, btc_path)) = btc_psbt.xpub.iter().next().unwrap();
+    assert_eq!(
+        btc_xpub.encode().to_vec(),
+        account.to_bytes(NetworkType::Mainnet).to_vec()
+    );
+    assert_eq!(btc_fingerprint.as_bytes(), &fingerprint);
+    assert_eq!(
+        btc_path
+            .into_iter()
+            .map(|c| u32::from(*c))
+            .collect::<Vec<_>>(),
+        account_path
+    );
+}
+
+#[test]
+fn test_global_xpub_rejects_malformed_entry() {
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.global
+        .insert(vec![PsbtGlobalKey::Xpub as u8, 0x04, 0x88], vec![0x00; 4]);
+    assert!(psbt.global_xpubs().is_empty());
+    assert!(PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).is_err());
+}
+
+#[test]
+fn test_verify_output_belongs_to_xpub() {
+    let (master, _) = derive_master_key(&[0x22; 32]).unwrap();
+    let fingerprint = master_fingerprint(&master);
+    let account_path = vec![0x8000_0054, 0x8000_0000, 0x8000_0000];
+    let account = derive_path(&master, &account_path).to_extended_public();
+    let change_path = [account_path.clone(), vec![1, 5]].concat();
+    let change_pubkey = derive_path(&master, &change_path)
+        .to_extended_public()
+        .public_key_bytes();
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_global_xpub(&account, fingerprint, &account_path)
+        .unwrap();
+
+    // No derivation recorded yet
+    assert!(!psbt.verify_output_belongs_to_xpub(0).unwrap());
+
+    let mut key = vec![PsbtOutputKey::Bip32Derivation as u8];
+    key.extend_from_slice(&change_pubkey);
+    let mut origin = fingerprint.to_vec();
+    for index in &change_path {
+        origin.extend_from_slice(&index.to_le_bytes());
+    }
+    psbt.add_output_data(0, key, origin).unwrap();
+    assert!(psbt.verify_output_belongs_to_xpub(0).unwrap());
+
+    // A key that does not derive from the account xpub
+    let other_pubkey = derive_path(&master, &[0x8000_0054, 0x8000_0000, 0x8000_0001, 1, 5])
+        .to_extended_public()
+        .public_key_bytes();
+    psbt.outputs[0].bip32_derivations[0].pubkey = other_pubkey.to_vec();
+    assert!(!psbt.verify_output_belongs_to_xpub(0).unwrap());
+
+    // Wrong master fingerprint
+    psbt.outputs[0].bip32_derivations[0].pubkey = change_pubkey.to_vec();
+    psbt.outputs[0].bip32_derivations[0].master_fingerprint = [0; 4];
+    assert!(!psbt.verify_output_belongs_to_xpub(0).unwrap());
+
+    assert!(psbt.verify_output_belongs_to_xpub(1).is_err());
+}
+
+// ============================================================================
+// Phase 18: Validation Tests
+// ============================================================================
+
+/// Test helper: Issue codes reported for a scope
+fn issue_codes(psbt: &PartiallySignedTransaction, scope: PsbtScope) -> Vec<PsbtIssueCode> {
+    psbt.validate()
+        .into_iter()
+        .filter(|issue| issue.scope == scope)
+        .map(|issue| issue.code)
+        .collect()
+}
+
+#[test]
+fn test_validate_clean_psbt() {
+    let funding = create_spending_tx(1);
+    let mut tx = create_spending_tx(2);
+    tx.inputs[0].previous_txid = funding.txid();
+    tx.inputs[0].previous_vout = 0;
+
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.inputs[0] = PsbtInput {
+        non_witness_utxo: Some(funding.serialize()),
+        sighash_type: Some(SighashType::All),
+        ..PsbtInput::default()
+    };
+    assert!(psbt.validate().is_empty());
+
+    let roundtrip = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert!(roundtrip.validate().is_empty());
+}
+
+#[test]
+fn test_validate_reports_map_issues() {
+    let funding = create_spending_tx(1);
+    let tx = create_spending_tx(2);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.global.insert(vec![0x00, 0x01], tx.serialize());
+
+    // Input 0: utxo from the wrong transaction, signature under an invalid key
+    psbt.inputs[0] = PsbtInput {
+        non_witness_utxo: Some(funding.serialize()),
+        partial_sigs: [(vec![0x05; 33], vec![0x30])].into_iter().collect(),
+        ..PsbtInput::default()
+    };
+    // Input 1: finalized but still signed, with a raw unrecognized sighash
+    let secp = Secp256k1::new();
+    let pubkey = PublicKey::from_secret_key(&secp, &SecretKey::from_slice(&[0x01; 32]).unwrap());
+    psbt.inputs[1] = PsbtInput {
+        partial_sigs: [(pubkey.serialize().to_vec(), vec![0x30])]
+            .into_iter()
+            .collect(),
+        final_script_witness: Some(vec![vec![0x30]]),
+        unknown: [(vec![PsbtInputKey::SighashType as u8], vec![0x05, 0, 0, 0])]
+            .into_iter()
+            .collect(),
+        ..PsbtInput::default()
+    };
+    // Output 0: redeem script set twice; output 1 does not exist
+    psbt.outputs[0] = PsbtOutput {
+        redeem_script: Some(vec![0x51]),
+        unknown: [(vec![PsbtOutputKey::RedeemScript as u8], vec![0x52])]
+            .into_iter()
+            .collect(),
+        ..PsbtOutput::default()
+    };
+    psbt.outputs.push(PsbtOutput::default());
+
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Global),
+        vec![
+            PsbtIssueCode::DuplicateUnsignedTx,
+            PsbtIssueCode::MapCountMismatch
+        ]
+    );
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Input(0)),
+        vec![
+            PsbtIssueCode::NonWitnessUtxoMismatch,
+            PsbtIssueCode::InvalidPartialSigPubkey
+        ]
+    );
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Input(1)),
+        vec![
+            PsbtIssueCode::FinalizedWithPartialSigs,
+            PsbtIssueCode::UnknownSighashType
+        ]
+    );
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Output(0)),
+        vec![PsbtIssueCode::DuplicateKey]
+    );
+
+    let warnings: Vec<_> = psbt
+        .validate()
+        .into_iter()
+        .filter(|issue| issue.severity == PsbtIssueSeverity::Warning)
+        .map(|issue| issue.code)
+        .collect();
+    assert_eq!(warnings, vec![PsbtIssueCode::FinalizedWithPartialSigs]);
+}
+
+#[test]
+fn test_unknown_sighash_type_is_reported_and_not_signed() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[7; 32]).unwrap();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&key.public_key(&secp).serialize());
+    let mut script_pubkey = vec![0x00, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::SighashType as u8],
+        vec![0x05, 0, 0, 0],
+    )
+    .unwrap();
+
+    // An unrecognized sighash type parses; validation reports it
+    let mut psbt = PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap()).unwrap();
+    assert_eq!(psbt.inputs[0].sighash_type, None);
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Input(0)),
+        vec![PsbtIssueCode::UnknownSighashType]
+    );
+    assert_eq!(
+        psbt.serialize().unwrap(),
+        PartiallySignedTransaction::deserialize(&psbt.serialize().unwrap())
+            .unwrap()
+            .serialize()
+            .unwrap()
+    );
+
+    // Signers refuse the input
+    assert!(matches!(
+        psbt.sighash_type(0),
+        Err(GovernanceError::SigningRefused(_))
+    ));
+    let summary = psbt.sign_with_key(&key).unwrap();
+    assert!(summary.signed.is_empty());
+    assert!(summary.skipped[0].1.contains("sighash"), "{:?}", summary);
+    assert!(psbt.inputs[0].partial_sigs.is_empty());
+}
+
+#[test]
+fn test_validate_reports_unsigned_tx_issues() {
+    // `new` refuses these transactions, so swap them into a valid PSBT
+    let mut tx = create_spending_tx(2);
+    let with_unsigned_tx = |tx: &Transaction| {
+        let mut psbt = PartiallySignedTransaction::new(&create_spending_tx(2).serialize()).unwrap();
+        psbt.global.insert(vec![0x00], tx.serialize());
+        psbt
+    };
+
+    tx.inputs[1].script_sig = vec![0x51];
+    assert!(PartiallySignedTransaction::new(&tx.serialize()).is_err());
+    assert_eq!(
+        issue_codes(&with_unsigned_tx(&tx), PsbtScope::Input(1)),
+        vec![PsbtIssueCode::UnsignedTxHasScriptSig]
+    );
+
+    tx.inputs[1].script_sig.clear();
+    tx.inputs[1].witness = vec![vec![0x01]];
+    assert!(PartiallySignedTransaction::new(&tx.serialize()).is_err());
+    assert_eq!(
+        issue_codes(&with_unsigned_tx(&tx), PsbtScope::Global),
+        vec![PsbtIssueCode::UnsignedTxHasWitness]
+    );
+
+    let mut psbt = with_unsigned_tx(&tx);
+    psbt.global.insert(vec![0x00], vec![0x01, 0x02]);
+    let issues = psbt.validate();
+    assert_eq!(issues.len(), 1);
+    assert_eq!(issues[0].code, PsbtIssueCode::InvalidUnsignedTx);
+    assert!(issues[0]
+        .to_string()
+        .starts_with("error [invalid_unsigned_tx] global:"));
+
+    let mut psbt = with_unsigned_tx(&tx);
+    psbt.global.clear();
+    assert_eq!(
+        issue_codes(&psbt, PsbtScope::Global),
+        vec![PsbtIssueCode::MissingUnsignedTx]
+    );
+}
+
+// ============================================================================
+// Phase 19: Fee and Weight Tests
+// ============================================================================
+
+/// Test helper: P2WPKH and P2PKH inputs (60,000 + 50,000 sat) paying 90,000 sat
+fn create_two_input_psbt() -> (PartiallySignedTransaction, Transaction) {
+    let mut p2pkh = vec![0x76, 0xa9, 0x14];
+    p2pkh.extend_from_slice(&[0xbb; 20]);
+    p2pkh.extend_from_slice(&[0x88, 0xac]);
+    let mut funding = create_spending_tx(1);
+    funding.outputs.push(TxOut {
+        value: 50_000,
+        script_pubkey: p2pkh,
+    });
+
+    let mut tx = create_spending_tx(2);
+    tx.inputs[1].previous_txid = funding.txid();
+    tx.inputs[1].previous_vout = 1;
+
+    let mut p2wpkh = vec![0x00, 0x14];
+    p2wpkh.extend_from_slice(&[0xcc; 20]);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(60_000, p2wpkh),
+    )
+    .unwrap();
+    psbt.add_input_data(
+        1,
+        vec![PsbtInputKey::NonWitnessUtxo as u8],
+        funding.serialize(),
+    )
+    .unwrap();
+    (psbt, tx)
+}
+
+#[test]
+fn test_psbt_fee_two_inputs() {
+    let (psbt, _) = create_two_input_psbt();
+    assert_eq!(psbt.fee().unwrap(), 20_000);
+
+    // Base: 4 + 1 + 41 + 148 + 1 + 31 + 4 = 230 bytes
+    // Witness: marker/flag 2 + P2WPKH 108 + empty 1 = 111 bytes
+    assert_eq!(psbt.estimated_weight().unwrap(), 230 * 4 + 111);
+    assert_eq!(psbt.estimated_vsize().unwrap(), 258);
+    assert!((psbt.fee_rate().unwrap() - 20_000.0 / 258.0).abs() < 1e-9);
+}
+
+#[test]
+fn test_psbt_fee_missing_utxo() {
+    let (mut psbt, _) = create_two_input_psbt();
+    psbt.inputs[1] = PsbtInput::default();
+    let err = psbt.fee().unwrap_err();
+    assert!(err.to_string().contains("Input 1"), "{}", err);
+
+    // Outputs larger than inputs
+    let (mut psbt, _) = create_two_input_psbt();
+    psbt.inputs[0].witness_utxo.as_mut().unwrap().value = 1_000;
+    assert!(psbt.fee().is_err());
+}
+
+#[test]
+fn test_psbt_weight_matches_finalized_transaction() {
+    let secp = Secp256k1::new();
+    let key = SecretKey::from_slice(&[3; 32]).unwrap();
+    let pubkey = key.public_key(&secp).serialize();
+    let pubkey_hash = bitcoin::hashes::hash160::Hash::hash(&pubkey);
+    let mut script_pubkey = vec![0x00, 0x14];
+    script_pubkey.extend_from_slice(pubkey_hash.as_byte_array());
+
+    let tx = create_spending_tx(1);
+    let mut psbt = PartiallySignedTransaction::new(&tx.serialize()).unwrap();
+    psbt.add_input_data(
+        0,
+        vec![PsbtInputKey::WitnessUtxo as u8],
+        witness_utxo(100_000, script_pubkey),
+    )
+    .unwrap();
+    let estimate = psbt.estimated_weight().unwrap();
+
+    psbt.sign_with_key(&key).unwrap();
+    psbt.finalize().unwrap();
+    let extracted: bitcoin::Transaction =
+        bitcoin::consensus::deserialize(&psbt.extract_transaction().unwrap()).unwrap();
+    let actual = extracted.weight().to_wu() as usize;
+
+    // Finalized inputs are measured exactly; the estimate is an upper bound
+    assert_eq!(psbt.estimated_weight().unwrap(), actual);
+    assert!(estimate >= actual && estimate - actual <= 1);
 }
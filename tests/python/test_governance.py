@@ -0,0 +1,195 @@
+"""Python bindings tests.
+
+Build the extension into the active virtualenv, then run pytest:
+
+    maturin develop
+    pytest tests/python
+"""
+
+import json
+
+import pytest
+
+import blvm_sdk
+from blvm_sdk import (
+    Bip44Wallet,
+    GovernanceMessage,
+    Keypair,
+    Multisig,
+    PublicKey,
+    Signature,
+    SignatureEnvelope,
+    SigningRequest,
+)
+
+# BIP39 test vector mnemonic (all-zero entropy)
+MNEMONIC = " ".join(["abandon"] * 11 + ["about"])
+
+
+def release_message():
+    return GovernanceMessage.release(
+        "v1.2.0",
+        "abc123",
+        {"bllvm-linux-x86_64": "SHA256:" + "AB" * 32},
+    )
+
+
+def test_release_multisig_verification():
+    keypairs = [Keypair.generate() for _ in range(3)]
+    multisig = Multisig(2, [k.public_key for k in keypairs])
+    assert (multisig.threshold, multisig.total) == (2, 3)
+
+    message = release_message()
+    signatures = [k.sign(message) for k in keypairs[:2]]
+    assert multisig.verify(message, signatures)
+
+    # Signatures from keys outside the roster do not count
+    outsider = Keypair.generate().sign(message)
+    assert not multisig.verify(message, [signatures[0], outsider])
+
+    # Signatures over a different release do not count
+    other = GovernanceMessage.release("v1.2.1", "abc123")
+    assert not multisig.verify(other, signatures)
+
+    with pytest.raises(blvm_sdk.MultisigError):
+        multisig.verify(message, signatures[:1])
+
+
+def test_message_encoding():
+    message = release_message()
+    assert message.signing_bytes().startswith(b"BLLVM-GOV-V1\x00RELEASE:6:v1.2.0:6:abc123")
+    assert b"sha256:" + b"ab" * 32 in message.signing_bytes()
+
+    roundtrip = GovernanceMessage.from_json(message.to_json())
+    assert roundtrip == message
+    assert "Release" in json.loads(message.to_json())
+
+    with pytest.raises(blvm_sdk.MessageFormatError):
+        GovernanceMessage.from_json("{}")
+
+
+def test_budget_decision_allocations():
+    budget = GovernanceMessage.budget_decision([("bob", 200), ("alice", 100)], "Q3 grants")
+    assert budget.total_amount() == 300
+    assert budget.signing_bytes().endswith(
+        b"BUDGET:9:Q3 grants\nALLOCATION:5:alice:100:0:\nALLOCATION:3:bob:200:0:"
+    )
+    reordered = GovernanceMessage.budget_decision([("alice", 100), ("bob", 200)], "Q3 grants")
+    assert reordered.signing_bytes() == budget.signing_bytes()
+
+    single = GovernanceMessage.single_budget("alice", 100, "audit")
+    assert single.total_amount() == 100
+    assert release_message().total_amount() is None
+
+    with pytest.raises(blvm_sdk.InvalidInputError):
+        GovernanceMessage.budget_decision([], "nothing")
+
+
+def test_module_approval_audit():
+    audited = GovernanceMessage.module_approval(
+        "lightning", "v2.0.0", "AB" * 32, security_rating="Low", auditor="Example Audits"
+    )
+    assert audited.signing_bytes().endswith(
+        b"MODULE-AUDIT:9:lightning:6:v2.0.0\nAUDIT:71:sha256:"
+        + b"ab" * 32
+        + b"\nRATING:low\nAUDITOR:14:Example Audits"
+    )
+    assert "security rating: low" in audited.description()
+    assert GovernanceMessage.from_json(audited.to_json()) == audited
+
+    with pytest.raises(blvm_sdk.InvalidInputError):
+        GovernanceMessage.module_approval("lightning", "v2.0.0", "ab" * 32)
+    with pytest.raises(blvm_sdk.InvalidInputError):
+        GovernanceMessage.module_approval("lightning", "v2.0.0", "ab" * 32, "severe", "me")
+
+
+def test_keys_and_signatures_roundtrip():
+    keypair = Keypair.from_secret_bytes(bytes([1] * 32))
+    public_key = keypair.public_key
+    assert PublicKey(str(public_key)) == public_key
+    assert PublicKey.from_bytes(public_key.to_bytes()) == public_key
+    assert len({public_key, PublicKey(str(public_key))}) == 1
+
+    signature = keypair.sign(GovernanceMessage.module_approval("lightning", "v2.0.0"))
+    assert Signature(str(signature)) == signature
+    assert Signature.from_bytes(signature.to_bytes()) == signature
+
+    with pytest.raises(blvm_sdk.KeyManagementError):
+        PublicKey("02" + "00" * 32)
+    with pytest.raises(blvm_sdk.SignatureError):
+        Signature("00")
+    # Every SDK error derives from GovernanceError
+    with pytest.raises(blvm_sdk.GovernanceError):
+        Keypair.from_secret_bytes(b"short")
+
+
+def test_keypair_wipe():
+    keypair = Keypair.generate()
+    assert str(keypair.public_key) in repr(keypair)
+    assert keypair.secret_bytes().hex() not in repr(keypair)
+    assert len(keypair.secret_bytes()) == 32
+
+    keypair.wipe()
+    assert keypair.is_wiped
+    assert "wiped" in repr(keypair)
+    with pytest.raises(blvm_sdk.KeyManagementError):
+        keypair.secret_bytes()
+    with pytest.raises(blvm_sdk.KeyManagementError):
+        keypair.sign(release_message())
+
+
+def test_signing_request_envelope():
+    keypair = Keypair.generate()
+    request = SigningRequest(release_message())
+    request = SigningRequest.from_ur(request.to_ur())
+    assert request.message == release_message()
+
+    envelope = SignatureEnvelope.from_ur(keypair.sign_request(request).to_ur(max_fragment_len=60))
+    assert envelope.request_id == request.request_id
+    assert envelope.public_key == keypair.public_key
+    assert envelope.verify(request)
+    assert not envelope.verify(SigningRequest(release_message()))
+
+
+def test_bip39_bip44_derivation():
+    blvm_sdk.validate_mnemonic(MNEMONIC)
+    assert len(blvm_sdk.generate_mnemonic(12).split()) == 12
+    assert blvm_sdk.mnemonic_to_seed(MNEMONIC, "TREZOR").hex().startswith("c55257c360c07c72")
+    with pytest.raises(blvm_sdk.InvalidInputError):
+        blvm_sdk.generate_mnemonic(13)
+
+    wallet = Bip44Wallet.from_mnemonic(MNEMONIC)
+    assert wallet.account_xpub(0).startswith("xpub")
+    assert wallet.account_xpub(0, network="testnet").startswith("tpub")
+
+    signer = wallet.signing_keypair(0, [0, 0])
+    assert signer.public_key == wallet.signing_keypair(0, [0, 0]).public_key
+    assert signer.public_key != wallet.signing_keypair(0, [0, 1]).public_key
+
+    wallet.wipe()
+    with pytest.raises(blvm_sdk.KeyManagementError):
+        wallet.account_xpub(0)
+
+
+def test_psbt_parsing():
+    # BIP174 valid test vector 1: one P2PKH input, two outputs
+    encoded = (
+        "cHNidP8BAHUCAAAAASaBcTce3/KF6Tet7qSze3gADAVmy7OtZGQXE8pCFxv2AAAAAAD+////AtPf"
+        "9QUAAAAAGXapFNDFmQPFusKGh2DpD9UhpGZap2UgiKwA4fUFAAAAABepFDVF5uM7gyxHBQ8k0+65"
+        "PJwDlIvHh7MuEwAAAQD9pQEBAAAAAAECiaPHHqtNIOA3G7ukzGmPopXJRjr6Ljl/hTPMti+VZ+UB"
+        "AAAAFxYAFL4Y0VKpsBIDna89p95PUzSe7LmF/////4b4qkOnHf8USIk6UwpyN+9rRgi7st0tAXHm"
+        "OuxqSJC0AQAAABcWABT+Pp7xp0XpdNkCxDVZQ6vLNL1TU/////8CAMLrCwAAAAAZdqkUhc/xCX/Z"
+        "4Ai7NK9wnGIZeziXikiIrHL++E4sAAAAF6kUM5cluiHv1irHU6m80GfWx6ajnQWHAkcwRAIgJxK+"
+        "IuAnDzlPVoMR3HyppolwuAJf3TskAinwf4pfOiQCIAGLONfc0xTnNMkna9b7QPZzMlvEuqFEyADS"
+        "8vAtsnZcASED0uFWdJQbrUqZY3LLh+GFbTZSYG2YVi/jnF6efkE/IQUCSDBFAiEA0SuFLYXc2WHS"
+        "9fSrZgZU327tzHlMDDPOXMMJ/7X85Y0CIGczio4OFyXBl/saiK9Z9R5E5CVbIBZ8hoQDHAXR8lkq"
+        "ASECI7cr7vCWXRC+B3jv7NYfysb3mk6haTkzgHNEZPhPKrMAAAAAAAAA"
+    )
+    psbt = blvm_sdk.Psbt.parse(encoded)
+    assert (psbt.input_count, psbt.output_count) == (1, 2)
+    assert psbt.to_base64() == encoded
+    assert blvm_sdk.Psbt.from_bytes(psbt.to_bytes()).to_base64() == encoded
+    assert not psbt.is_finalized()
+
+    with pytest.raises(blvm_sdk.GovernanceError):
+        blvm_sdk.Psbt.parse("not a psbt")
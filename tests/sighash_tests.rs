@@ -0,0 +1,231 @@
+//! # Sighash Tests
+//!
+//! BIP143 worked examples for the segwit v0 signature hash, and mainnet
+//! signatures for the legacy signature hash.
+
+use bitcoin::hashes::Hash;
+use blvm_sdk::governance::psbt::SighashType;
+use blvm_sdk::governance::sighash::{legacy_sighash, p2wpkh_script_code, segwit_v0_sighash};
+use secp256k1::{ecdsa::Signature, Message, PublicKey, Secp256k1};
+
+fn hex32(s: &str) -> [u8; 32] {
+    hex::decode(s).unwrap().try_into().unwrap()
+}
+
+#[test]
+fn test_bip143_native_p2wpkh() {
+    let tx = hex::decode(
+        "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f00000000\
+         00eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a01000000\
+         00ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac90\
+         93510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000",
+    )
+    .unwrap();
+    let pubkey_hash: [u8; 20] = hex::decode("1d0f172a0ecb48aee1be1f2687d2963ae33f71a1")
+        .unwrap()
+        .try_into()
+        .unwrap();
+    let script_code = p2wpkh_script_code(&pubkey_hash);
+    assert_eq!(
+        hex::encode(&script_code),
+        "76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac"
+    );
+
+    let sighash = segwit_v0_sighash(&tx, 1, &script_code, 600_000_000, SighashType::All).unwrap();
+    assert_eq!(
+        sighash,
+        hex32("c37af31116d1b27caf68aae9e3ac82f1477929014d5b917657d0eb49478cb670")
+    );
+}
+
+#[test]
+fn test_bip143_p2sh_p2wpkh() {
+    let tx = hex::decode(
+        "0100000001db6b1b20aa0fd7b23880be2ecbd4a98130974cf4748fb66092ac4d3ceb1a547701000000\
+         00feffffff02b8b4eb0b000000001976a914a457b684d7f0d539a46a45bbc043f35b59d0d96388ac00\
+         08af2f000000001976a914fd270b1ee6abcaea97fea7ad0402e8bd8ad6d77c88ac92040000",
+    )
+    .unwrap();
+    // Redeem script: 0014 79091972186c449eb1ded22b78e40d009bdf0089
+    let pubkey_hash: [u8; 20] = hex::decode("79091972186c449eb1ded22b78e40d009bdf0089")
+        .unwrap()
+        .try_into()
+        .unwrap();
+
+    let sighash = segwit_v0_sighash(
+        &tx,
+        0,
+        &p2wpkh_script_code(&pubkey_hash),
+        1_000_000_000,
+        SighashType::All,
+    )
+    .unwrap();
+    assert_eq!(
+        sighash,
+        hex32("64f3b0f4dd2bb3aa1ce8566d220cc74dda9df97d8490cc81d89d735c92e59fb6")
+    );
+}
+
+#[test]
+fn test_bip143_p2sh_p2wsh_all_sighash_types() {
+    // 6-of-6 multisig, each key signing with a different sighash type
+    let tx = hex::decode(
+        "010000000136641869ca081e70f394c6948e8af409e18b619df2ed74aa106c1ca29787b96e01000000\
+         00ffffffff0200e9a435000000001976a914389ffce9cd9ae88dcc0631e88a821ffdbe9bfe2688acc0\
+         832f05000000001976a9147480a33f950689af511e6e84c138dbbd3c3ee41588ac00000000",
+    )
+    .unwrap();
+    let witness_script = hex::decode(
+        "56210307b8ae49ac90a048e9b53357a2354b3334e9c8bee813ecb98e99a7e07e8c3ba32103b28f0c28\
+         bfab54554ae8c658ac5c3e0ce6e79ad336331f78c428dd43eea8449b21034b8113d703413d57761b8b\
+         9781957b8c0ac1dfe69f492580ca4195f50376ba4a21033400f6afecb833092a9a21cfdf1ed1376e58\
+         c5d1f47de74683123987e967a8f42103a6d48b1131e94ba04d9737d61acdaa1322008af9602b3b1486\
+         2c07a1789aac162102d8b661b0b3302ee2f162b09e07a55ad5dfbe673a9f01d9f0c19617681024306b\
+         56ae",
+    )
+    .unwrap();
+
+    for (sighash_type, expected) in [
+        (
+            SighashType::All,
+            "185c0be5263dce5b4bb50a047973c1b6272bfbd0103a89444597dc40b248ee7c",
+        ),
+        (
+            SighashType::None,
+            "e9733bc60ea13c95c6527066bb975a2ff29a925e80aa14c213f686cbae5d2f36",
+        ),
+        (
+            SighashType::Single,
+            "1e1f1c303dc025bd664acb72e583e933fae4cff9148bf78c157d1e8f78530aea",
+        ),
+        (
+            SighashType::AllAnyoneCanPay,
+            "2a67f03e63a6a422125878b40b82da593be8d4efaafe88ee528af6e5a9955c6e",
+        ),
+        (
+            SighashType::NoneAnyoneCanPay,
+            "781ba15f3779d5542ce8ecb5c18716733a5ee42a6f51488ec96154934e2c890a",
+        ),
+        (
+            SighashType::SingleAnyoneCanPay,
+            "511e8e52ed574121fc1b654970395502128263f62662e076dc6baf05c2e6a99b",
+        ),
+    ] {
+        let sighash =
+            segwit_v0_sighash(&tx, 0, &witness_script, 987_654_321, sighash_type).unwrap();
+        assert_eq!(sighash, hex32(expected), "{:?}", sighash_type);
+    }
+
+    assert!(segwit_v0_sighash(&tx, 1, &witness_script, 987_654_321, SighashType::All).is_err());
+}
+
+/// Test helper: Two-input, two-output legacy transaction
+const LEGACY_TX: &str = "0100000002fff7f7881a8099afa6940d42d1e7f6362bec38171ea3edf433541db4e4ad969f0000000000eeffffffef51e1b804cc89d182d279655c3aa89e815b1b309fe287d9b2b55d57b90ec68a0100000000ffffffff02202cb206000000001976a9148280b37df378db99f66f85c95a783a76ac7a6d5988ac9093510d000000001976a9143bde42dbee7e4dbe6a21b2d50ce2f0167faa815988ac11000000";
+
+const ALL_SIGHASH_TYPES: [SighashType; 6] = [
+    SighashType::All,
+    SighashType::None,
+    SighashType::Single,
+    SighashType::AllAnyoneCanPay,
+    SighashType::NoneAnyoneCanPay,
+    SighashType::SingleAnyoneCanPay,
+];
+
+#[test]
+fn test_legacy_sighash_mainnet_signature() {
+    // f4184fc596403b9d638783cf57adfe4c75c605f6356fbc91338530e9831e9e16 (block 170),
+    // spending the P2PK output of block 9's coinbase
+    let tx = hex::decode(
+        "0100000001c997a5e56e104102fa209c6a852dd90660a20b2d9c352423edce25857fcd37040000000048\
+         47304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522\
+         ec8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d0901ffffffff0200ca9a3b000000\
+         00434104ae1a62fe09c5f51b13905f07f06b99a2f7159b2225f374cd378d71302fa28414e7aab37397f5\
+         54a7df5f142c21c1b7303b8a0626f1baded5c72a704f7e6cd84cac00286bee0000000043410411db93e1\
+         dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf9744464f82e16\
+         0bfa9b8b64f9d4c03f999b8643f656b412a3ac00000000",
+    )
+    .unwrap();
+    let pubkey = hex::decode(
+        "0411db93e1dcdb8a016b49840f8c53bc1eb68a382e97b1482ecad7b148a6909a5cb2e0eaddfb84ccf974\
+         4464f82e160bfa9b8b64f9d4c03f999b8643f656b412a3",
+    )
+    .unwrap();
+    let signature = hex::decode(
+        "304402204e45e16932b8af514961a1d3a1a25fdf3f4f7732e9d624c6c61548ab5fb8cd410220181522ec\
+         8eca07de4860a4acdd12909d831cc56cbbac4622082221a8768d1d09",
+    )
+    .unwrap();
+
+    // P2PK scriptPubKey: <pubkey> OP_CHECKSIG
+    let mut script_code = vec![65];
+    script_code.extend_from_slice(&pubkey);
+    script_code.push(0xac);
+
+    let sighash = legacy_sighash(&tx, 0, &script_code, SighashType::All).unwrap();
+    let secp = Secp256k1::verification_only();
+    assert!(secp
+        .verify_ecdsa(
+            &Message::from_digest(sighash),
+            &Signature::from_der(&signature).unwrap(),
+            &PublicKey::from_slice(&pubkey).unwrap(),
+        )
+        .is_ok());
+}
+
+#[test]
+fn test_legacy_sighash_all_types() {
+    let tx = hex::decode(LEGACY_TX).unwrap();
+    let btc_tx: bitcoin::Transaction = bitcoin::consensus::deserialize(&tx).unwrap();
+    let script_code = hex::decode("76a9141d0f172a0ecb48aee1be1f2687d2963ae33f71a188ac").unwrap();
+
+    for input_index in 0..2 {
+        for sighash_type in ALL_SIGHASH_TYPES {
+            let expected = bitcoin::sighash::SighashCache::new(&btc_tx)
+                .legacy_signature_hash(
+                    input_index,
+                    bitcoin::Script::from_bytes(&script_code),
+                    sighash_type.to_byte() as u32,
+                )
+                .unwrap();
+            assert_eq!(
+                legacy_sighash(&tx, input_index, &script_code, sighash_type).unwrap(),
+                expected.to_byte_array(),
+                "input {} {:?}",
+                input_index,
+                sighash_type
+            );
+        }
+    }
+}
+
+#[test]
+fn test_legacy_sighash_single_without_output() {
+    // Three inputs, two outputs: SIGHASH_SINGLE on input 2 signs the value 1
+    let mut tx =
+        bitcoin::consensus::deserialize::<bitcoin::Transaction>(&hex::decode(LEGACY_TX).unwrap())
+            .unwrap();
+    tx.input.push(tx.input[0].clone());
+    let tx = bitcoin::consensus::serialize(&tx);
+
+    let mut one = [0u8; 32];
+    one[0] = 1;
+    for sighash_type in [SighashType::Single, SighashType::SingleAnyoneCanPay] {
+        assert_eq!(legacy_sighash(&tx, 2, &[0x51], sighash_type).unwrap(), one);
+    }
+    assert_ne!(
+        legacy_sighash(&tx, 2, &[0x51], SighashType::All).unwrap(),
+        one
+    );
+    assert!(legacy_sighash(&tx, 3, &[0x51], SighashType::All).is_err());
+}
+
+#[test]
+fn test_legacy_sighash_removes_codeseparators() {
+    let tx = hex::decode(LEGACY_TX).unwrap();
+    let sighash = |script: &[u8]| legacy_sighash(&tx, 0, script, SighashType::All).unwrap();
+
+    // OP_CODESEPARATOR OP_1 OP_CODESEPARATOR
+    assert_eq!(sighash(&[0xab, 0x51, 0xab]), sighash(&[0x51]));
+    // 0xab inside push data is not an opcode
+    assert_ne!(sighash(&[0x01, 0xab]), sighash(&[]));
+}
@@ -2,8 +2,12 @@
 //!
 //! Tests for signature creation and verification.
 
-use blvm_sdk::governance::{GovernanceKeypair, Signature};
+use blvm_sdk::governance::{
+    BlockingSigner, GovernanceError, GovernanceKeypair, GovernanceMessage, GovernanceSigner,
+    MockSigner, MockSignerBehavior, PublicKey, Signature,
+};
 use blvm_sdk::sign_message;
+use serde::{Deserialize, Serialize};
 
 #[test]
 fn test_signature_creation_and_verification() {
@@ -146,11 +150,12 @@ fn test_signature_display_format() {
     let signature = sign_message(&keypair.secret_key, message).unwrap();
 
     let display_str = format!("{}", signature);
-    let expected_hex = hex::encode(signature.to_bytes());
+    let expected_hex = hex::encode(signature.to_der_bytes());
     assert_eq!(display_str, expected_hex);
 
-    // Should be 128 characters (64 hex chars)
-    assert_eq!(display_str.len(), 128);
+    // DER: a SEQUENCE of two INTEGERs, at most 72 bytes
+    assert!(display_str.starts_with("30"));
+    assert!(display_str.len() <= 144);
 }
 
 #[test]
@@ -169,3 +174,135 @@ fn test_signature_der_format() {
     assert!(der_bytes.len() >= 70); // Minimum DER signature length
     assert!(der_bytes.len() <= 72); // Maximum DER signature length
 }
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+struct SignedRecord {
+    public_key: PublicKey,
+    signature: Signature,
+}
+
+fn signed_record() -> SignedRecord {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    SignedRecord {
+        public_key: keypair.public_key(),
+        signature: sign_message(&keypair.secret_key, b"record").unwrap(),
+    }
+}
+
+#[test]
+fn test_key_and_signature_json_roundtrip() {
+    let record = signed_record();
+    let json = serde_json::to_value(&record).unwrap();
+
+    // Serialized as lowercase hex strings
+    assert_eq!(json["public_key"], record.public_key.to_string());
+    assert_eq!(json["signature"], hex::encode(record.signature.to_bytes()));
+    assert_eq!(
+        serde_json::from_value::<SignedRecord>(json).unwrap(),
+        record
+    );
+}
+
+#[test]
+fn test_key_and_signature_toml_roundtrip() {
+    let record = signed_record();
+    let toml_str = toml::to_string(&record).unwrap();
+    assert!(toml_str.contains(&format!("public_key = \"{}\"", record.public_key)));
+    assert_eq!(toml::from_str::<SignedRecord>(&toml_str).unwrap(), record);
+}
+
+#[test]
+fn test_key_and_signature_from_str() {
+    let record = signed_record();
+    let signature_hex = record.signature.to_string();
+
+    assert_eq!(
+        signature_hex.parse::<Signature>().unwrap(),
+        record.signature
+    );
+    assert_eq!(
+        format!("0x{}", signature_hex.to_uppercase())
+            .parse::<Signature>()
+            .unwrap(),
+        record.signature
+    );
+    assert_eq!(
+        format!("0x{}", record.public_key)
+            .parse::<PublicKey>()
+            .unwrap(),
+        record.public_key
+    );
+}
+
+#[test]
+fn test_wrong_length_hex_mentions_expected_length() {
+    let err = "abcd".parse::<Signature>().unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("expected 64 bytes (compact) or 8 to 72 bytes (DER), got 2"),
+        "{}",
+        err
+    );
+
+    let err = serde_json::from_str::<SignedRecord>(r#"{"public_key":"02abcd","signature":"00"}"#)
+        .unwrap_err();
+    assert!(
+        err.to_string().contains("expected 33 or 65 bytes"),
+        "{}",
+        err
+    );
+
+    assert!("0xzz".parse::<PublicKey>().is_err());
+}
+
+#[test]
+fn test_blocking_signer_matches_sign_message() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let public_key = keypair.public_key();
+    let message = GovernanceMessage::module_approval("test-module", "1.0.0");
+
+    let signer = BlockingSigner::new(keypair.clone());
+    assert_eq!(signer.public_key(), public_key);
+    assert_eq!(GovernanceSigner::public_key(&keypair), public_key);
+
+    let signature = signer.sign(b"raw message").unwrap();
+    assert_eq!(
+        signature,
+        sign_message(&keypair.secret_key, b"raw message").unwrap()
+    );
+
+    let signature = signer.sign_governance_message(&message).unwrap();
+    assert!(blvm_sdk::verify_governance_message(&signature, &message, &public_key).unwrap());
+}
+
+#[test]
+fn test_mock_signer_records_requests() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let signer = BlockingSigner::new(MockSigner::new(keypair.clone()));
+
+    let signature = signer.sign(b"first").unwrap();
+    assert!(
+        blvm_sdk::governance::verify_signature(&signature, b"first", &keypair.public_key())
+            .unwrap()
+    );
+    signer.sign(b"second").unwrap();
+    assert_eq!(
+        signer.into_inner().requests(),
+        vec![b"first".to_vec(), b"second".to_vec()]
+    );
+
+    // Failures are distinguishable, and still recorded
+    let unavailable = BlockingSigner::new(
+        MockSigner::new(keypair.clone()).with_behavior(MockSignerBehavior::Unavailable),
+    );
+    let err = unavailable.sign(b"message").unwrap_err();
+    assert!(matches!(err, GovernanceError::SignerUnavailable(_)));
+    assert!(err.is_retryable());
+    assert_eq!(unavailable.into_inner().requests().len(), 1);
+
+    let refusing =
+        BlockingSigner::new(MockSigner::new(keypair).with_behavior(MockSignerBehavior::Refuse));
+    let err = refusing.sign(b"message").unwrap_err();
+    assert!(matches!(err, GovernanceError::SigningRefused(_)));
+    assert!(!err.is_retryable());
+}
@@ -0,0 +1,114 @@
+//! # UR Encoding Tests
+//!
+//! Round-trip signing requests and signature envelopes through multi-part
+//! UR strings, as carried across an air gap.
+
+use blvm_sdk::governance::ur::{UrDecoder, UrEncoder, REQUEST_UR_TYPE};
+use blvm_sdk::governance::{
+    BlockingSigner, GovernanceKeypair, GovernanceMessage, SignatureEnvelope, SigningRequest,
+};
+use rand::seq::SliceRandom;
+use std::collections::HashMap;
+
+/// A release request whose CBOR encoding is a little over 5 KB
+fn large_request() -> SigningRequest {
+    let artifact_hashes: HashMap<String, String> = (0..55)
+        .map(|i| {
+            (
+                format!("bllvm-node-v1.0.0-target-{:02}.tar.gz", i),
+                format!("sha256:{}", hex::encode([i as u8; 32])),
+            )
+        })
+        .collect();
+    SigningRequest::new(GovernanceMessage::Release {
+        version: "v1.0.0".to_string(),
+        commit_hash: "abc123".to_string(),
+        artifact_hashes,
+    })
+}
+
+#[test]
+fn test_large_request_round_trips_in_shuffled_order() {
+    let request = large_request();
+    let cbor = request.to_cbor().unwrap();
+    assert!(cbor.len() > 5000, "request is {} bytes", cbor.len());
+
+    let mut parts = request.to_ur_parts(200).unwrap();
+    assert_eq!(parts.len(), cbor.len().div_ceil(200));
+    assert!(parts[0].starts_with(&format!("ur:bllvm-request/1-{}/", parts.len())));
+
+    parts.shuffle(&mut rand::thread_rng());
+    assert_eq!(SigningRequest::from_ur_parts(&parts).unwrap(), request);
+}
+
+#[test]
+fn test_fountain_parts_replace_missed_fragments() {
+    let request = large_request();
+    let cbor = request.to_cbor().unwrap();
+    let mut encoder = UrEncoder::new(REQUEST_UR_TYPE, &cbor, 200).unwrap();
+    let seq_len = encoder.seq_len();
+
+    // Drop every third of the first pass, then keep scanning mixed parts
+    let mut decoder = UrDecoder::new();
+    for seq_num in 1..=seq_len {
+        let part = encoder.next_part();
+        if seq_num % 3 != 0 {
+            decoder.receive(&part).unwrap();
+        }
+    }
+    assert!(!decoder.is_complete());
+
+    let mut extra = 0;
+    while !decoder.is_complete() {
+        decoder.receive(&encoder.next_part()).unwrap();
+        extra += 1;
+        assert!(extra < 10 * seq_len, "decoder did not converge");
+    }
+    assert_eq!(decoder.ur_type(), Some(REQUEST_UR_TYPE));
+    assert_eq!(decoder.message().unwrap(), cbor.as_slice());
+}
+
+#[test]
+fn test_signature_envelope_round_trip() {
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let request = SigningRequest::new(GovernanceMessage::module_approval("lightning", "v2.0.0"));
+    let envelope = SignatureEnvelope {
+        request_id: request.request_id,
+        signature: BlockingSigner::new(keypair.clone())
+            .sign_governance_message(&request.message)
+            .unwrap(),
+        public_key: keypair.public_key(),
+    };
+
+    // Payloads within one fragment use a single-part UR; scanners may upper-case it
+    let parts = envelope.to_ur_parts(1000).unwrap();
+    assert_eq!(parts.len(), 1);
+    assert!(parts[0].starts_with("ur:bllvm-signature/"));
+    let decoded = SignatureEnvelope::from_ur_parts([parts[0].to_uppercase()]).unwrap();
+    assert_eq!(decoded, envelope);
+    assert!(decoded.verify(&request).unwrap());
+
+    // An envelope is not a request
+    assert!(SigningRequest::from_ur_parts(&parts).is_err());
+}
+
+#[test]
+fn test_rejects_corrupt_and_inconsistent_parts() {
+    let parts = large_request().to_ur_parts(200).unwrap();
+
+    // Flip one byteword in the payload of a part
+    let mut corrupt = parts[0].clone();
+    let last = corrupt.pop().unwrap();
+    corrupt.push(if last == 'a' { 'e' } else { 'a' });
+    assert!(UrDecoder::new().receive(&corrupt).is_err());
+
+    // Parts of a different message are refused once decoding started
+    let other = large_request().to_ur_parts(100).unwrap();
+    let mut decoder = UrDecoder::new();
+    decoder.receive(&parts[0]).unwrap();
+    assert!(decoder.receive(&other[1]).is_err());
+
+    // Missing fragments are reported, not guessed
+    let err = SigningRequest::from_ur_parts(&parts[1..]).unwrap_err();
+    assert!(err.to_string().contains("Incomplete"), "{}", err);
+}
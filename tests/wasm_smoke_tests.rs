@@ -0,0 +1,54 @@
+//! # WASM Smoke Tests
+//!
+//! Governance primitives built for `wasm32-unknown-unknown` without the
+//! `node` feature. Run with:
+//!
+//! ```text
+//! wasm-pack test --node -- --no-default-features --features governance --test wasm_smoke_tests
+//! ```
+
+#![cfg(target_arch = "wasm32")]
+
+use blvm_sdk::governance::{GovernanceKeypair, GovernanceMessage, GovernanceProposal, Multisig};
+use blvm_sdk::{sign_governance_message, verify_governance_message};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn test_sign_and_verify_in_wasm() {
+    // Key generation draws entropy through getrandom's JS backend
+    let keypair = GovernanceKeypair::generate().unwrap();
+    let message = GovernanceMessage::module_approval("lightning", "v2.0.0");
+
+    let signature = sign_governance_message(&keypair.secret_key, &message).unwrap();
+    assert!(verify_governance_message(&signature, &message, &keypair.public_key()).unwrap());
+
+    let other = GovernanceKeypair::generate().unwrap();
+    assert!(!verify_governance_message(&signature, &message, &other.public_key()).unwrap());
+}
+
+#[wasm_bindgen_test]
+fn test_multisig_and_proposal_in_wasm() {
+    let keypairs: Vec<_> = (0..3)
+        .map(|_| GovernanceKeypair::generate().unwrap())
+        .collect();
+    let multisig = Multisig::new(2, 3, keypairs.iter().map(|k| k.public_key()).collect()).unwrap();
+
+    let message = GovernanceMessage::single_budget("bc1qaudit", 100_000, "audit");
+    let signatures: Vec<_> = keypairs[..2]
+        .iter()
+        .map(|k| sign_governance_message(&k.secret_key, &message).unwrap())
+        .collect();
+    assert!(multisig
+        .verify(&message.to_signing_bytes(), &signatures)
+        .unwrap());
+
+    // Proposal ids and timestamps come from uuid and chrono's wasm backends
+    let proposal = GovernanceProposal::new(
+        "Audit",
+        "Fund an audit",
+        message,
+        &keypairs[0].public_key(),
+        3600,
+    );
+    assert!(proposal.is_open());
+}